@@ -14,7 +14,7 @@ use std::error::Error;
 use std::process;
 
 use canadensis::core::transport::Receiver;
-use canadensis::encoding::{DataType, Deserialize, ReadCursor};
+use canadensis::encoding::{Deserialize, ReadCursor, SerializedSize};
 use canadensis_can::{CanReceiver, Mtu};
 use canadensis_core::time::MicrosecondDuration32;
 use canadensis_data_types::uavcan::diagnostic::record_1_1::{self, Record};
@@ -34,7 +34,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     receiver
         .subscribe_message(
             record_1_1::SUBJECT,
-            Record::EXTENT_BYTES.unwrap() as usize,
+            Record::RECEIVE_SIZE_BYTES,
             MicrosecondDuration32::from_ticks(1_000_000),
             &mut can,
         )
@@ -2,6 +2,7 @@
 //! A publisher that can be used by anonymous nodes
 //!
 
+use core::fmt;
 use core::marker::PhantomData;
 
 use crate::serialize::do_serialize;
@@ -151,3 +152,24 @@ impl<E> From<E> for AnonymousPublishError<E> {
         AnonymousPublishError::Transport(inner)
     }
 }
+
+impl<E> fmt::Display for AnonymousPublishError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnonymousPublishError::Length => f.write_str("Message too long for one frame"),
+            AnonymousPublishError::Transport(_) => f.write_str("Transport error"),
+        }
+    }
+}
+
+impl<E> core::error::Error for AnonymousPublishError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            AnonymousPublishError::Length => None,
+            AnonymousPublishError::Transport(inner) => Some(inner),
+        }
+    }
+}
@@ -33,6 +33,7 @@ pub mod register;
 pub mod requester;
 mod serialize;
 pub mod service;
+pub mod subscriber;
 
 use ::core::fmt::{Debug, Formatter};
 use ::core::marker::PhantomData;
@@ -377,6 +378,20 @@ pub trait Node {
     where
         T: Message + Serialize;
 
+    /// Publishes a message that has already been serialized into one or more byte slices
+    ///
+    /// The slices are concatenated as the transfer is split into frames, so an application that
+    /// has parts of a message in separate buffers (for example, a header and an image) does not
+    /// need to copy them into one buffer. The concatenated bytes must be a valid serialized
+    /// message of the type used on this subject.
+    ///
+    /// Publishing needs to be started by calling [`start_publishing`](#tymethod.start_publishing).
+    fn publish_vectored(
+        &mut self,
+        subject: SubjectId,
+        pieces: &[&[u8]],
+    ) -> nb::Result<(), PublishError<<Self::Transmitter as Transmitter<Self::Clock>>::Error>>;
+
     /// Sets up to send requests for a service
     ///
     /// This also subscribes to the corresponding responses.
@@ -428,6 +443,24 @@ pub trait Node {
     where
         T: Request + Serialize;
 
+    /// Sends a service request that has already been serialized into one or more byte slices
+    ///
+    /// The slices are concatenated as the transfer is split into frames. The concatenated bytes
+    /// must be a valid serialized request of type `T`.
+    ///
+    /// On success, this function returns the transfer ID of the request.
+    fn send_request_vectored<T>(
+        &mut self,
+        token: &ServiceToken<T>,
+        pieces: &[&[u8]],
+        destination: <Self::Transport as Transport>::NodeId,
+    ) -> nb::Result<
+        <Self::Transport as Transport>::TransferId,
+        <Self::Transmitter as Transmitter<Self::Clock>>::Error,
+    >
+    where
+        T: Request;
+
     /// Subscribes to messages on a topic
     fn subscribe_message(
         &mut self,
@@ -471,6 +504,22 @@ pub trait Node {
     where
         T: Response + Serialize;
 
+    /// Responds to a service request with a response that has already been serialized into one
+    /// or more byte slices
+    ///
+    /// The slices are concatenated as the transfer is split into frames. The concatenated bytes
+    /// must be a valid serialized response of the service's response type.
+    ///
+    /// # Panics
+    ///
+    /// Some implementations may panic if this function is called on an anonymous node.
+    fn send_response_vectored(
+        &mut self,
+        token: ResponseToken<Self::Transport>,
+        timeout: MicrosecondDuration32,
+        pieces: &[&[u8]],
+    ) -> nb::Result<(), <Self::Transmitter as Transmitter<Self::Clock>>::Error>;
+
     /// Attempts to flush all outgoing frames
     fn flush(&mut self) -> nb::Result<(), <Self::Transmitter as Transmitter<Self::Clock>>::Error>;
 
@@ -521,6 +570,27 @@ pub enum PublishError<T> {
     Transport(T),
 }
 
+impl<T> ::core::fmt::Display for PublishError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            PublishError::NotPublishing => f.write_str("Not publishing on this subject"),
+            PublishError::Transport(_) => f.write_str("Transport error"),
+        }
+    }
+}
+
+impl<T> ::core::error::Error for PublishError<T>
+where
+    T: ::core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)> {
+        match self {
+            PublishError::NotPublishing => None,
+            PublishError::Transport(inner) => Some(inner),
+        }
+    }
+}
+
 /// A token returned from [`Node::start_sending_requests`](Node#tymethod.start_sending_requests)
 /// that can be used to a request a service using the associated service ID
 ///
@@ -552,3 +622,30 @@ impl<E> From<E> for StartSendError<E> {
         StartSendError::Transport(inner)
     }
 }
+
+impl<E> ::core::fmt::Display for StartSendError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            StartSendError::Memory(_) => f.write_str("Out of memory"),
+            StartSendError::Transport(_) => f.write_str("Transport error"),
+            StartSendError::Duplicate => f.write_str("Port ID already in use"),
+            StartSendError::AnonymousRequest => {
+                f.write_str("An anonymous node can't send requests")
+            }
+        }
+    }
+}
+
+impl<E> ::core::error::Error for StartSendError<E>
+where
+    E: ::core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)> {
+        match self {
+            StartSendError::Transport(inner) => Some(inner),
+            StartSendError::Memory(_)
+            | StartSendError::Duplicate
+            | StartSendError::AnonymousRequest => None,
+        }
+    }
+}
@@ -226,6 +226,14 @@ where
         self.node.node_mut().publish_loopback(subject, payload)
     }
 
+    fn publish_vectored(
+        &mut self,
+        subject: SubjectId,
+        pieces: &[&[u8]],
+    ) -> nb::Result<(), PublishError<<Self::Transmitter as Transmitter<Self::Clock>>::Error>> {
+        self.node.node_mut().publish_vectored(subject, pieces)
+    }
+
     fn start_sending_requests<T>(
         &mut self,
         service: ServiceId,
@@ -291,6 +299,23 @@ where
             .send_request_loopback(token, payload, destination)
     }
 
+    fn send_request_vectored<T>(
+        &mut self,
+        token: &ServiceToken<T>,
+        pieces: &[&[u8]],
+        destination: <Self::Transport as Transport>::NodeId,
+    ) -> nb::Result<
+        <Self::Transport as Transport>::TransferId,
+        <Self::Transmitter as Transmitter<Self::Clock>>::Error,
+    >
+    where
+        T: Request,
+    {
+        self.node
+            .node_mut()
+            .send_request_vectored(token, pieces, destination)
+    }
+
     fn subscribe_message(
         &mut self,
         subject: SubjectId,
@@ -345,6 +370,17 @@ where
         self.node.node_mut().send_response(token, timeout, payload)
     }
 
+    fn send_response_vectored(
+        &mut self,
+        token: ResponseToken<Self::Transport>,
+        timeout: MicrosecondDuration32,
+        pieces: &[&[u8]],
+    ) -> nb::Result<(), <N::Transmitter as Transmitter<N::Clock>>::Error> {
+        self.node
+            .node_mut()
+            .send_response_vectored(token, timeout, pieces)
+    }
+
     fn flush(
         &mut self,
     ) -> canadensis_core::nb::Result<(), <N::Transmitter as Transmitter<N::Clock>>::Error> {
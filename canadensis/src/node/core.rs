@@ -14,7 +14,7 @@ use canadensis_encoding::{Message, Request, Response, Serialize};
 
 use crate::publisher::Publisher;
 use crate::requester::Requester;
-use crate::serialize::do_serialize;
+use crate::serialize::{prepare_payload, OutgoingPayload};
 use crate::{Node, PublishError, ResponseToken, ServiceToken, StartSendError, TransferHandler};
 
 /// Basic Cyphal node functionality
@@ -161,22 +161,23 @@ where
         &mut self,
         token: ResponseToken<T::Transport>,
         deadline: Microseconds32,
-        payload: &[u8],
+        payload: OutgoingPayload<'_>,
     ) -> nb::Result<(), T::Error> {
-        let transfer_out = Transfer {
-            header: Header::Response(ServiceHeader {
-                timestamp: deadline,
-                transfer_id: token.transfer,
-                priority: token.priority,
-                service: token.service,
-                source: self.node_id.clone().unwrap(),
-                destination: token.client,
-            }),
-            loopback: false,
-            payload,
-        };
-        self.transmitter
-            .push(transfer_out, &mut self.clock, &mut self.driver)
+        let header = Header::Response(ServiceHeader {
+            timestamp: deadline,
+            transfer_id: token.transfer,
+            priority: token.priority,
+            service: token.service,
+            source: self.node_id.clone().unwrap(),
+            destination: token.client,
+        });
+        payload.push(
+            header,
+            false,
+            &mut self.transmitter,
+            &mut self.clock,
+            &mut self.driver,
+        )
     }
 }
 
@@ -277,6 +278,30 @@ where
             })
     }
 
+    fn publish_vectored(
+        &mut self,
+        subject: SubjectId,
+        pieces: &[&[u8]],
+    ) -> nb::Result<(), PublishError<T::Error>> {
+        let publisher = match self.publishers.get_mut(&subject) {
+            Some(publisher) => publisher,
+            None => return Err(nb::Error::Other(PublishError::NotPublishing)),
+        };
+        publisher
+            .publish_vectored(
+                &mut self.clock,
+                self.node_id.clone(),
+                subject,
+                pieces,
+                &mut self.transmitter,
+                &mut self.driver,
+            )
+            .map_err(|e| match e {
+                nb::Error::WouldBlock => nb::Error::WouldBlock,
+                nb::Error::Other(e) => nb::Error::Other(PublishError::Transport(e)),
+            })
+    }
+
     /// Sets up to send requests for a service
     ///
     /// This also subscribes to the corresponding responses.
@@ -377,6 +402,30 @@ where
         )
     }
 
+    fn send_request_vectored<M>(
+        &mut self,
+        token: &ServiceToken<M>,
+        pieces: &[&[u8]],
+        destination: N::NodeId,
+    ) -> nb::Result<N::TransferId, T::Error>
+    where
+        M: Request,
+    {
+        let requester = self
+            .requesters
+            .get_mut(&token.0)
+            .expect("Bug: No requester for token");
+        requester.send_vectored(
+            &mut self.clock,
+            self.node_id.clone().unwrap(),
+            token.0,
+            pieces,
+            destination,
+            &mut self.transmitter,
+            &mut self.driver,
+        )
+    }
+
     fn subscribe_message(
         &mut self,
         subject: SubjectId,
@@ -416,11 +465,22 @@ where
     {
         let now = self.clock.now();
         let deadline = now + timeout;
-        do_serialize(payload, |payload| {
+        let mtu = self.transmitter.mtu();
+        prepare_payload(payload, mtu, |payload| {
             self.send_response_payload(token, deadline, payload)
         })
     }
 
+    fn send_response_vectored(
+        &mut self,
+        token: ResponseToken<Self::Transport>,
+        timeout: MicrosecondDuration32,
+        pieces: &[&[u8]],
+    ) -> nb::Result<(), T::Error> {
+        let deadline = self.clock.now() + timeout;
+        self.send_response_payload(token, deadline, OutgoingPayload::Pieces(pieces))
+    }
+
     fn flush(&mut self) -> canadensis_core::nb::Result<(), T::Error> {
         self.transmitter.flush(&mut self.clock, &mut self.driver)
     }
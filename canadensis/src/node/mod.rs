@@ -1,7 +1,7 @@
 //!
 //! High-level Cyphal node types
 //!
-//! Three different node implementations are provided with different features:
+//! Four different node implementations are provided with different features:
 //!
 //! * [`CoreNode`]: Keeps track of subscriptions and other state, but does not automatically
 //!   send anything
@@ -9,15 +9,21 @@
 //!   application-layer functionality according to the Cyphal specification)
 //! * [`BasicNode`]: Sends heartbeat messages, responds to GetInfo requests, and sends port list
 //!   messages
+//! * [`StandardNode`]: Gets a node ID using plug-and-play allocation if necessary, and provides
+//!   the heartbeat, GetInfo, port list, register, and ExecuteCommand services
 //!
 
 mod basic;
 mod core;
 mod minimal;
+mod standard;
 
 pub use self::basic::BasicNode;
 pub use self::core::CoreNode;
 pub use self::minimal::MinimalNode;
+pub use self::standard::{StandardNode, StandardNodeConfig, StandardNodeError};
+
+use ::core::fmt;
 
 pub mod data_types {
     //! Re-exports from `canadensis_data_types` to avoid version conflicts
@@ -33,3 +39,25 @@ pub enum NodeError<T, R> {
     /// An error from a receiver
     Receiver(R),
 }
+
+impl<T, R> fmt::Display for NodeError<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeError::Transmitter(_) => f.write_str("Transmitter error"),
+            NodeError::Receiver(_) => f.write_str("Receiver error"),
+        }
+    }
+}
+
+impl<T, R> ::core::error::Error for NodeError<T, R>
+where
+    T: ::core::error::Error + 'static,
+    R: ::core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)> {
+        match self {
+            NodeError::Transmitter(inner) => Some(inner),
+            NodeError::Receiver(inner) => Some(inner),
+        }
+    }
+}
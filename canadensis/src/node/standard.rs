@@ -0,0 +1,381 @@
+use crate::core::transport::{Receiver, Transmitter, Transport};
+use crate::register::standard::StandardRegisters;
+use crate::register::RegisterBlock;
+use crate::service::execute_command::{CommandHandler, ExecuteCommandService};
+use crate::service::get_info::GetInfoService;
+use crate::service::heartbeat::HeartbeatService;
+use crate::service::pnp_client::{self, AllocationMessage, PnpClientService};
+use crate::service::port_list::{self, PortListService};
+use crate::service::register_server::RegisterServerService;
+use crate::{Node, PublishError, StartSendError, TransferHandler};
+use canadensis_core::{nb, ServiceSubscribeError};
+use canadensis_data_types::uavcan::node::get_info_1_0::GetInfoResponse;
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+use canadensis_data_types::uavcan::node::mode_1_0::Mode;
+use canadensis_data_types::uavcan::pnp::node_id_allocation_data_1_0::NodeIDAllocationData;
+use core::{fmt, mem};
+
+/// An error from creating a [`StandardNode`], starting its services, or receiving transfers
+pub enum StandardNodeError<N: Node> {
+    /// The heartbeat service could not start publishing
+    Heartbeat(StartSendError<<N::Transmitter as Transmitter<N::Clock>>::Error>),
+    /// The plug-and-play client could not start
+    Pnp(pnp_client::NewError<N>),
+    /// The `uavcan.node.GetInfo` server could not subscribe to requests
+    GetInfo(ServiceSubscribeError<<N::Receiver as Receiver<N::Clock>>::Error>),
+    /// The port list service could not start publishing
+    PortList(port_list::NewError<N>),
+    /// The register server could not subscribe to requests
+    Registers(ServiceSubscribeError<<N::Receiver as Receiver<N::Clock>>::Error>),
+    /// The `uavcan.node.ExecuteCommand` server could not subscribe to requests
+    Commands(ServiceSubscribeError<<N::Receiver as Receiver<N::Clock>>::Error>),
+    /// The receiver failed to receive a transfer
+    Receive(<N::Receiver as Receiver<N::Clock>>::Error),
+}
+
+impl<N> fmt::Debug for StandardNodeError<N>
+where
+    N: Node,
+    <N::Receiver as Receiver<N::Clock>>::Error: fmt::Debug,
+    <N::Transmitter as Transmitter<N::Clock>>::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StandardNodeError::Heartbeat(inner) => f.debug_tuple("Heartbeat").field(inner).finish(),
+            StandardNodeError::Pnp(inner) => f.debug_tuple("Pnp").field(inner).finish(),
+            StandardNodeError::GetInfo(inner) => f.debug_tuple("GetInfo").field(inner).finish(),
+            StandardNodeError::PortList(inner) => f.debug_tuple("PortList").field(inner).finish(),
+            StandardNodeError::Registers(inner) => f.debug_tuple("Registers").field(inner).finish(),
+            StandardNodeError::Commands(inner) => f.debug_tuple("Commands").field(inner).finish(),
+            StandardNodeError::Receive(inner) => f.debug_tuple("Receive").field(inner).finish(),
+        }
+    }
+}
+
+impl<N: Node> fmt::Display for StandardNodeError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StandardNodeError::Heartbeat(_) => f.write_str("Failed to start heartbeat service"),
+            StandardNodeError::Pnp(_) => f.write_str("Failed to start plug-and-play client"),
+            StandardNodeError::GetInfo(_) => f.write_str("Failed to start GetInfo service"),
+            StandardNodeError::PortList(_) => f.write_str("Failed to start port list service"),
+            StandardNodeError::Registers(_) => f.write_str("Failed to start register service"),
+            StandardNodeError::Commands(_) => f.write_str("Failed to start ExecuteCommand service"),
+            StandardNodeError::Receive(_) => f.write_str("Receiver error"),
+        }
+    }
+}
+
+impl<N> core::error::Error for StandardNodeError<N>
+where
+    N: Node + 'static,
+    <N::Receiver as Receiver<N::Clock>>::Error: core::error::Error + 'static,
+    <N::Transmitter as Transmitter<N::Clock>>::Error: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            StandardNodeError::Heartbeat(inner) => Some(inner),
+            StandardNodeError::Pnp(inner) => Some(inner),
+            StandardNodeError::GetInfo(inner) => Some(inner),
+            StandardNodeError::PortList(inner) => Some(inner),
+            StandardNodeError::Registers(inner) => Some(inner),
+            StandardNodeError::Commands(inner) => Some(inner),
+            StandardNodeError::Receive(inner) => Some(inner),
+        }
+    }
+}
+
+/// Settings for a [`StandardNode`]
+pub struct StandardNodeConfig<I> {
+    /// The ID of this node, or None to get an ID using plug-and-play allocation
+    ///
+    /// If this is None, the value of the `uavcan.node.id` register is used. If that register is
+    /// unset and the underlying node already has an ID, that ID is used.
+    pub node_id: Option<I>,
+    /// The information that should be returned when handling node information requests
+    ///
+    /// The unique ID in this structure is also used for plug-and-play node ID allocation.
+    pub node_info: GetInfoResponse,
+}
+
+/// A node that provides all the standard application-layer functions
+///
+/// This node combines these services:
+///
+/// * A plug-and-play client that gets a node ID, if the node does not have a fixed ID
+/// * Sending a `uavcan.node.Heartbeat` every second
+/// * Responding to `uavcan.node.GetInfo` requests
+/// * Sending a `uavcan.node.port.List` message when it starts and every 10 seconds after that
+/// * Responding to `uavcan.register.List` and `uavcan.register.Access` requests for the
+///   [standard registers](StandardRegisters) and the application registers
+/// * Responding to `uavcan.node.ExecuteCommand` requests
+///
+/// All services except plug-and-play start when this node has a node ID.
+///
+/// The type `M` is the plug-and-play message version, which must fit into one frame. The default
+/// (`uavcan.pnp.NodeIDAllocationData.1.0`) works with all transports.
+///
+/// The underlying node type `N` is usually a [`CoreNode`](crate::node::CoreNode).
+///
+/// # Examples
+///
+/// ```ignore
+/// let core_node = CoreNode::new_anonymous(clock, transmitter, receiver, driver);
+/// let config = StandardNodeConfig {
+///     node_id: None,
+///     node_info,
+/// };
+/// let standard_registers = StandardRegisters::<4>::new();
+/// let mut node = StandardNode::new(
+///     core_node,
+///     config,
+///     standard_registers,
+///     app_registers,
+///     |_command, _parameter| ExecuteCommandResponse::STATUS_BAD_COMMAND,
+/// )?;
+/// loop {
+///     node.receive(&mut application_handler)?;
+///     if one_second_elapsed {
+///         node.run_per_second_tasks()?;
+///     }
+///     node.node_mut().flush()?;
+/// }
+/// ```
+pub struct StandardNode<N, B, X, const P: usize, M = NodeIDAllocationData>
+where
+    N: Node,
+    B: RegisterBlock,
+{
+    node: N,
+    heartbeat: HeartbeatService<N>,
+    /// The plug-and-play client, if this node is waiting for a node ID
+    pnp: Option<PnpClientService<N, M>>,
+    services: Services<N, B, X, P>,
+    /// The number of calls to `run_per_second_tasks` before the next port list message
+    seconds_until_port_list: u8,
+}
+
+/// The services that require a node ID
+enum Services<N: Node, B: RegisterBlock, X, const P: usize> {
+    /// Waiting for a node ID
+    Pending {
+        node_info: GetInfoResponse,
+        registers: (StandardRegisters<P>, B),
+        commands: X,
+    },
+    /// Running
+    Running {
+        get_info: GetInfoService<N>,
+        port_list: PortListService<N>,
+        registers: RegisterServerService<N, (StandardRegisters<P>, B)>,
+        commands: ExecuteCommandService<N, X>,
+    },
+    /// Starting the services failed
+    Failed,
+}
+
+impl<N, B, X, const P: usize, M> StandardNode<N, B, X, P, M>
+where
+    N: Node,
+    B: RegisterBlock,
+    X: CommandHandler,
+    M: AllocationMessage<N::Transport>,
+{
+    /// Creates a standard node
+    ///
+    /// * `node`: The underlying node (this is usually a [`CoreNode`](crate::node::CoreNode))
+    /// * `config`: The node ID and node information
+    /// * `standard_registers`: The standard `uavcan.*` registers
+    /// * `registers`: The application registers to make available through the register server
+    /// * `commands`: The handler for `uavcan.node.ExecuteCommand` requests
+    pub fn new(
+        mut node: N,
+        config: StandardNodeConfig<<N::Transport as Transport>::NodeId>,
+        mut standard_registers: StandardRegisters<P>,
+        registers: B,
+        commands: X,
+    ) -> Result<Self, StandardNodeError<N>> {
+        // Register values loaded before start-up take effect now
+        standard_registers.apply_changes(&mut node, |_, _| {});
+        if let Some(node_id) = config.node_id {
+            node.set_node_id(node_id);
+        }
+        let heartbeat = HeartbeatService::new(&mut node).map_err(StandardNodeError::Heartbeat)?;
+        let pnp = if node.node_id().is_none() {
+            let unique_id = config.node_info.unique_id;
+            Some(PnpClientService::new(&mut node, unique_id).map_err(StandardNodeError::Pnp)?)
+        } else {
+            None
+        };
+        let mut standard = StandardNode {
+            node,
+            heartbeat,
+            pnp,
+            services: Services::Pending {
+                node_info: config.node_info,
+                registers: (standard_registers, registers),
+                commands,
+            },
+            seconds_until_port_list: 0,
+        };
+        if standard.pnp.is_none() {
+            standard.start_services()?;
+        }
+        Ok(standard)
+    }
+
+    /// Handles incoming transfers
+    ///
+    /// Transfers that the standard services do not handle are passed to `handler`.
+    pub fn receive<H>(&mut self, handler: &mut H) -> Result<(), StandardNodeError<N>>
+    where
+        H: TransferHandler<N::Transport>,
+    {
+        if let Some(pnp) = self.pnp.as_mut() {
+            self.node
+                .receive(&mut pnp.handler().chain(handler))
+                .map_err(StandardNodeError::Receive)?;
+            if self.node.node_id().is_some() {
+                // Node ID allocated
+                self.pnp = None;
+                self.start_services()?;
+            }
+            Ok(())
+        } else {
+            match &mut self.services {
+                Services::Running {
+                    get_info,
+                    registers,
+                    commands,
+                    ..
+                } => {
+                    let mut chained_handler = get_info
+                        .handler()
+                        .chain(registers.handler())
+                        .chain(commands.handler())
+                        .chain(handler);
+                    self.node.receive(&mut chained_handler)
+                }
+                Services::Pending { .. } | Services::Failed => self.node.receive(handler),
+            }
+            .map_err(StandardNodeError::Receive)
+        }
+    }
+
+    /// This function must be called once per second
+    ///
+    /// While this node is waiting for a node ID, it sends a plug-and-play request. After that,
+    /// it sends heartbeat messages and port list messages (on the first call after the services
+    /// start, and every 10 seconds after that).
+    pub fn run_per_second_tasks(
+        &mut self,
+    ) -> nb::Result<(), PublishError<<N::Transmitter as Transmitter<N::Clock>>::Error>> {
+        if let Some(pnp) = self.pnp.as_mut() {
+            return pnp.send_request(&mut self.node);
+        }
+        self.heartbeat.publish_heartbeat(&mut self.node)?;
+        if let Services::Running { port_list, .. } = &mut self.services {
+            if self.seconds_until_port_list == 0 {
+                self.seconds_until_port_list = 9;
+                port_list.publish_port_list(&mut self.node)?;
+            } else {
+                self.seconds_until_port_list -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if this node has a node ID and its services are running
+    pub fn is_running(&self) -> bool {
+        matches!(self.services, Services::Running { .. })
+    }
+
+    /// Sets the operating mode that will be reported in the heartbeat messages
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.heartbeat.set_mode(mode);
+    }
+    /// Sets the health status that will be reported in the heartbeat messages
+    pub fn set_health(&mut self, health: Health) {
+        self.heartbeat.set_health(health);
+    }
+    /// Sets the vendor-specific status code that will be reported in the heartbeat messages
+    pub fn set_status_code(&mut self, status: u8) {
+        self.heartbeat.set_status_code(status);
+    }
+
+    /// Returns a reference to the application registers
+    ///
+    /// This function returns None if starting the services failed.
+    pub fn registers(&self) -> Option<&B> {
+        self.all_registers().map(|(_, registers)| registers)
+    }
+    /// Returns a mutable reference to the application registers
+    ///
+    /// This function returns None if starting the services failed.
+    pub fn registers_mut(&mut self) -> Option<&mut B> {
+        self.all_registers_mut().map(|(_, registers)| registers)
+    }
+    /// Returns a reference to the standard registers
+    ///
+    /// This function returns None if starting the services failed.
+    pub fn standard_registers(&self) -> Option<&StandardRegisters<P>> {
+        self.all_registers().map(|(standard, _)| standard)
+    }
+    /// Returns a mutable reference to the standard registers
+    ///
+    /// This function returns None if starting the services failed.
+    pub fn standard_registers_mut(&mut self) -> Option<&mut StandardRegisters<P>> {
+        self.all_registers_mut().map(|(standard, _)| standard)
+    }
+
+    fn all_registers(&self) -> Option<&(StandardRegisters<P>, B)> {
+        match &self.services {
+            Services::Pending { registers, .. } => Some(registers),
+            Services::Running { registers, .. } => Some(registers.registers()),
+            Services::Failed => None,
+        }
+    }
+    fn all_registers_mut(&mut self) -> Option<&mut (StandardRegisters<P>, B)> {
+        match &mut self.services {
+            Services::Pending { registers, .. } => Some(registers),
+            Services::Running { registers, .. } => Some(registers.registers_mut()),
+            Services::Failed => None,
+        }
+    }
+
+    /// Returns a reference to the enclosed node
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+    /// Returns a mutable reference to the enclosed node
+    pub fn node_mut(&mut self) -> &mut N {
+        &mut self.node
+    }
+
+    /// Starts the services that require a node ID
+    fn start_services(&mut self) -> Result<(), StandardNodeError<N>> {
+        if let Services::Pending {
+            node_info,
+            registers,
+            commands,
+        } = mem::replace(&mut self.services, Services::Failed)
+        {
+            let node = &mut self.node;
+            let get_info =
+                GetInfoService::new(node, node_info).map_err(StandardNodeError::GetInfo)?;
+            let port_list = PortListService::new(node).map_err(StandardNodeError::PortList)?;
+            let registers = RegisterServerService::new(node, registers)
+                .map_err(StandardNodeError::Registers)?;
+            let commands =
+                ExecuteCommandService::new(node, commands).map_err(StandardNodeError::Commands)?;
+            self.services = Services::Running {
+                get_info,
+                port_list,
+                registers,
+                commands,
+            };
+            self.seconds_until_port_list = 0;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use crate::serialize::do_serialize;
+use crate::serialize::{prepare_payload, OutgoingPayload};
 use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
-use canadensis_core::transfer::{Header, MessageHeader, Transfer};
+use canadensis_core::transfer::{Header, MessageHeader};
 use canadensis_core::transport::{TransferId, Transmitter, Transport};
 use canadensis_core::{nb, SubjectId};
 use canadensis_encoding::{Message, Serialize};
@@ -49,7 +49,8 @@ impl<C: Clock, T: Transmitter<C>> Publisher<C, T> {
     {
         let deadline = clock.now() + self.timeout;
         // Part 1: Serialize
-        do_serialize(payload, |payload_bytes| {
+        let mtu = transmitter.mtu();
+        prepare_payload(payload, mtu, |payload_bytes| {
             // Part 2: Split into frames and put frames in the queue
             self.send_payload(
                 source,
@@ -78,7 +79,8 @@ impl<C: Clock, T: Transmitter<C>> Publisher<C, T> {
     {
         let deadline = clock.now() + self.timeout;
         // Part 1: Serialize
-        do_serialize(payload, |payload_bytes| {
+        let mtu = transmitter.mtu();
+        prepare_payload(payload, mtu, |payload_bytes| {
             // Part 2: Split into frames and put frames in the queue
             self.send_payload(
                 source,
@@ -93,11 +95,37 @@ impl<C: Clock, T: Transmitter<C>> Publisher<C, T> {
         })
     }
 
+    /// Publishes a message that has already been serialized into one or more byte slices
+    ///
+    /// The slices are concatenated as the transmitter splits the transfer into frames.
+    /// The loopback flag is set to false.
+    pub fn publish_vectored(
+        &mut self,
+        clock: &mut C,
+        source: Option<<T::Transport as Transport>::NodeId>,
+        subject: SubjectId,
+        pieces: &[&[u8]],
+        transmitter: &mut T,
+        driver: &mut T::Driver,
+    ) -> nb::Result<(), T::Error> {
+        let deadline = clock.now() + self.timeout;
+        self.send_payload(
+            source,
+            subject,
+            OutgoingPayload::Pieces(pieces),
+            deadline,
+            false,
+            transmitter,
+            clock,
+            driver,
+        )
+    }
+
     fn send_payload(
         &mut self,
         source: Option<<T::Transport as Transport>::NodeId>,
         subject: SubjectId,
-        payload: &[u8],
+        payload: OutgoingPayload<'_>,
         deadline: Microseconds32,
         loopback: bool,
         transmitter: &mut T,
@@ -105,20 +133,16 @@ impl<C: Clock, T: Transmitter<C>> Publisher<C, T> {
         driver: &mut T::Driver,
     ) -> nb::Result<(), T::Error> {
         // Assemble the transfer
-        let transfer = Transfer {
-            header: Header::Message(MessageHeader {
-                timestamp: deadline,
-                transfer_id: self.next_transfer_id.clone(),
-                priority: self.priority.clone(),
-                subject,
-                source,
-            }),
-            loopback,
-            payload,
-        };
+        let header = Header::Message(MessageHeader {
+            timestamp: deadline,
+            transfer_id: self.next_transfer_id.clone(),
+            priority: self.priority.clone(),
+            subject,
+            source,
+        });
         self.next_transfer_id = self.next_transfer_id.clone().increment();
 
-        transmitter.push(transfer, clock, driver)
+        payload.push(header, loopback, transmitter, clock, driver)
     }
 }
 
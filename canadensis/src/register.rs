@@ -2,9 +2,11 @@
 //! Node configuration registers that can be accessed from other nodes
 
 pub mod basic;
+pub mod standard;
 
 use alloc::vec::Vec;
 use canadensis_core::ServiceSubscribeError;
+use core::fmt;
 use core::str;
 
 use crate::{Node, ResponseToken, TransferHandler};
@@ -55,6 +57,75 @@ pub trait RegisterBlock {
     fn register_by_index_mut(&mut self, index: usize) -> Option<&mut dyn Register>;
     /// Returns a mutable reference to the register with the provided name
     fn register_by_name_mut(&mut self, name: &str) -> Option<&mut dyn Register>;
+    /// Returns the number of registers in this block
+    ///
+    /// The default implementation looks up registers by index until it finds a gap, which takes
+    /// time proportional to the number of registers.
+    fn register_count(&self) -> usize {
+        (0..)
+            .take_while(|&index| self.register_by_index(index).is_some())
+            .count()
+    }
+}
+
+/// Combines two register blocks into one
+///
+/// The registers in the first block come before the registers in the second block. This can be
+/// used to combine [`StandardRegisters`](standard::StandardRegisters) with application-specific
+/// registers.
+impl<A, B> RegisterBlock for (A, B)
+where
+    A: RegisterBlock,
+    B: RegisterBlock,
+{
+    fn register_by_index(&self, index: usize) -> Option<&dyn Register> {
+        match self.0.register_by_index(index) {
+            Some(register) => Some(register),
+            None => self.1.register_by_index(index - self.0.register_count()),
+        }
+    }
+
+    fn register_by_index_mut(&mut self, index: usize) -> Option<&mut dyn Register> {
+        let first_count = self.0.register_count();
+        if index < first_count {
+            self.0.register_by_index_mut(index)
+        } else {
+            self.1.register_by_index_mut(index - first_count)
+        }
+    }
+
+    fn register_by_name_mut(&mut self, name: &str) -> Option<&mut dyn Register> {
+        match self.0.register_by_name_mut(name) {
+            Some(register) => Some(register),
+            None => self.1.register_by_name_mut(name),
+        }
+    }
+
+    fn register_count(&self) -> usize {
+        self.0.register_count() + self.1.register_count()
+    }
+}
+
+/// An empty register block
+///
+/// This can be used with a [`StandardNode`](crate::node::StandardNode) that has no
+/// application-specific registers.
+impl RegisterBlock for () {
+    fn register_by_index(&self, _index: usize) -> Option<&dyn Register> {
+        None
+    }
+
+    fn register_by_index_mut(&mut self, _index: usize) -> Option<&mut dyn Register> {
+        None
+    }
+
+    fn register_by_name_mut(&mut self, _name: &str) -> Option<&mut dyn Register> {
+        None
+    }
+
+    fn register_count(&self) -> usize {
+        0
+    }
 }
 
 /// Information about how a register can be accessed
@@ -74,7 +145,7 @@ pub struct Access {
 pub trait Register {
     /// Returns the name of this register
     ///
-    /// The name must not be more than 256 bytes long. Each register must have a distinct name.
+    /// The name must not be more than 255 bytes long. Each register must have a distinct name.
     fn name(&self) -> &str;
 
     /// Returns information about how this register can be accessed
@@ -111,6 +182,16 @@ pub enum WriteError {
     Type,
 }
 
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteError::Type => f.write_str("Incorrect value type or length"),
+        }
+    }
+}
+
+impl core::error::Error for WriteError {}
+
 /// Handles access requests for registers
 ///
 /// Basic steps:
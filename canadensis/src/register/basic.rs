@@ -18,6 +18,7 @@ use canadensis_data_types::uavcan::primitive::unstructured_1_0;
 use canadensis_data_types::uavcan::register::value_1_0::Value;
 use canadensis_encoding::bits::BitArray;
 use core::convert::TryFrom;
+use core::fmt;
 use half::f16;
 
 /// A register containing its name, value, and mutable/persistent flags
@@ -478,6 +479,14 @@ impl<const N: usize> RegisterType for [bool; N] {
 #[derive(Debug)]
 pub struct LengthError(());
 
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Value too long")
+    }
+}
+
+impl core::error::Error for LengthError {}
+
 /// A non-mutable, persistent register that holds a fixed string value
 ///
 /// This is useful for registers that provide information and cannot be changed.
@@ -0,0 +1,522 @@
+//!
+//! Standard registers defined by the Cyphal specification
+//!
+//! [`StandardRegisters`] exposes the registers that standard tools like Yakut expect a node to
+//! provide:
+//!
+//! * `uavcan.node.id`
+//! * `uavcan.node.description`
+//! * `uavcan.can.bitrate` (optional, for Cyphal/CAN nodes)
+//! * `uavcan.pub.PORT_NAME.id` and `uavcan.pub.PORT_NAME.type` for each published subject
+//! * `uavcan.sub.PORT_NAME.id` and `uavcan.sub.PORT_NAME.type` for each subscribed subject
+//! * `uavcan.cli.PORT_NAME.id` and `uavcan.cli.PORT_NAME.type` for each service client
+//! * `uavcan.srv.PORT_NAME.id` and `uavcan.srv.PORT_NAME.type` for each service server
+//!
+//! Writes to the node ID and port ID registers do not take effect immediately. According to the
+//! specification, a node applies its new configuration when it restarts. A node that restarts
+//! by resetting the whole device can load the persisted register values on startup. A node that
+//! restarts in software can call [`StandardRegisters::apply_changes`] to apply the new values.
+//!
+//! The standard registers can be combined with application-specific registers by putting both
+//! blocks in a tuple:
+//!
+//! ```
+//! # use canadensis::register::basic::SimpleRegister;
+//! # use canadensis::register::standard::{PortKind, StandardRegisters};
+//! # use canadensis::register::{RegisterBlock, RegisterHandler};
+//! #[derive(RegisterBlock)]
+//! struct AppRegisters {
+//!     gain: SimpleRegister<f32>,
+//! }
+//!
+//! let mut standard = StandardRegisters::<4>::new();
+//! standard
+//!     .add_port(PortKind::Publisher, "temperature", "uavcan.si.unit.temperature.Scalar.1.0", Some(1000))
+//!     .unwrap();
+//! let app = AppRegisters {
+//!     gain: SimpleRegister::with_value("app.gain", true, true, 1.0),
+//! };
+//! let handler = RegisterHandler::new((standard, app));
+//! ```
+//!
+
+use crate::core::transport::Transport;
+use crate::register::basic::{RegisterString, RegisterType, SimpleRegister};
+use crate::register::{Access, Register, RegisterBlock, WriteError};
+use crate::Node;
+use canadensis_core::{OutOfMemoryError, ServiceId, SubjectId};
+use canadensis_data_types::uavcan::primitive::string_1_0;
+use canadensis_data_types::uavcan::register::value_1_0::Value;
+use core::convert::TryFrom;
+use core::fmt::Write;
+
+/// The register value that indicates that a node ID or port ID is not set
+pub const UNSET_ID: u16 = u16::MAX;
+
+/// The maximum length of a register name, in bytes (`uavcan.register.Name.1.0` holds up to 255)
+const NAME_CAPACITY: usize = 255;
+/// The maximum length of a string register value, in bytes
+const STRING_CAPACITY: usize = 256;
+
+/// The kinds of ports that have standard registers
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PortKind {
+    /// A subject that this node publishes (`uavcan.pub.*`)
+    Publisher,
+    /// A subject that this node subscribes to (`uavcan.sub.*`)
+    Subscriber,
+    /// A service that this node sends requests for (`uavcan.cli.*`)
+    Client,
+    /// A service that this node responds to (`uavcan.srv.*`)
+    Server,
+}
+
+impl PortKind {
+    /// Returns the register name segment for this kind of port
+    fn segment(&self) -> &'static str {
+        match self {
+            PortKind::Publisher => "pub",
+            PortKind::Subscriber => "sub",
+            PortKind::Client => "cli",
+            PortKind::Server => "srv",
+        }
+    }
+
+    /// Returns true if this kind of port uses subject IDs, or false if it uses service IDs
+    fn is_subject(&self) -> bool {
+        matches!(self, PortKind::Publisher | PortKind::Subscriber)
+    }
+
+    /// Returns true if the provided value is a valid ID for this kind of port, or is `UNSET_ID`
+    fn accepts(&self, id: u16) -> bool {
+        if id == UNSET_ID {
+            true
+        } else if self.is_subject() {
+            SubjectId::try_from(id).is_ok()
+        } else {
+            ServiceId::try_from(id).is_ok()
+        }
+    }
+}
+
+/// A register that holds the ID of a port (`uavcan.*.PORT_NAME.id`)
+///
+/// The register holds two values: the configured ID, which can be read and written through the
+/// register interface, and the active ID, which is the ID that the node is actually using.
+/// Writes change the configured ID only.
+#[derive(Debug, Clone)]
+pub struct PortIdRegister {
+    name: heapless::String<NAME_CAPACITY>,
+    kind: PortKind,
+    configured: u16,
+    active: u16,
+}
+
+impl PortIdRegister {
+    /// Returns the configured ID, or `UNSET_ID` if the ID is not set
+    pub fn configured(&self) -> u16 {
+        self.configured
+    }
+    /// Returns the active ID, or `UNSET_ID` if the ID is not set
+    pub fn active(&self) -> u16 {
+        self.active
+    }
+    /// Returns true if the configured ID is different from the active ID
+    pub fn is_changed(&self) -> bool {
+        self.configured != self.active
+    }
+}
+
+impl Register for PortIdRegister {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn access(&self) -> Access {
+        Access {
+            mutable: true,
+            persistent: true,
+        }
+    }
+
+    fn read(&self) -> Value {
+        self.configured.read()
+    }
+
+    fn write(&mut self, value: &Value) -> Result<(), WriteError> {
+        let mut new_id = self.configured;
+        new_id.write(value)?;
+        if self.kind.accepts(new_id) {
+            self.configured = new_id;
+            Ok(())
+        } else {
+            Err(WriteError::Type)
+        }
+    }
+}
+
+/// A non-mutable register that holds the name of a port's data type
+/// (`uavcan.*.PORT_NAME.type`)
+#[derive(Debug, Clone)]
+pub struct PortTypeRegister {
+    name: heapless::String<NAME_CAPACITY>,
+    type_name: &'static str,
+}
+
+impl Register for PortTypeRegister {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn access(&self) -> Access {
+        Access {
+            mutable: false,
+            persistent: true,
+        }
+    }
+
+    fn read(&self) -> Value {
+        Value::String(string_1_0::String {
+            value: heapless::Vec::from_slice(self.type_name.as_bytes())
+                .expect("Type name too long"),
+        })
+    }
+
+    fn write(&mut self, _value: &Value) -> Result<(), WriteError> {
+        unimplemented!("A port type register cannot be written")
+    }
+}
+
+/// The ID and type registers for one port
+#[derive(Debug, Clone)]
+pub struct PortRegisters {
+    port_name: &'static str,
+    id: PortIdRegister,
+    data_type: PortTypeRegister,
+}
+
+impl PortRegisters {
+    /// Returns the name of this port (the `PORT_NAME` part of the register names)
+    pub fn port_name(&self) -> &'static str {
+        self.port_name
+    }
+    /// Returns the kind of this port
+    pub fn kind(&self) -> PortKind {
+        self.id.kind
+    }
+    /// Returns the ID register of this port
+    pub fn id(&self) -> &PortIdRegister {
+        &self.id
+    }
+    /// Returns the full name of the data type used on this port
+    pub fn type_name(&self) -> &'static str {
+        self.data_type.type_name
+    }
+
+    /// Returns the active subject ID of this port
+    ///
+    /// This function returns None if this port is a client or server, or if the ID is not set.
+    pub fn subject_id(&self) -> Option<SubjectId> {
+        if self.kind().is_subject() {
+            SubjectId::try_from(self.id.active).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the active service ID of this port
+    ///
+    /// This function returns None if this port is a publisher or subscriber, or if the ID is not
+    /// set.
+    pub fn service_id(&self) -> Option<ServiceId> {
+        if self.kind().is_subject() {
+            None
+        } else {
+            ServiceId::try_from(self.id.active).ok()
+        }
+    }
+}
+
+/// A port ID change that [`StandardRegisters::apply_changes`] has applied
+#[derive(Debug, Clone)]
+pub struct PortChange<'r> {
+    /// The registers of the port that changed, with the new ID already active
+    pub port: &'r PortRegisters,
+    /// The ID that was active before the change, or `UNSET_ID` if the ID was not set
+    pub previous: u16,
+}
+
+/// The standard registers of a node
+///
+/// Type parameters:
+/// * `P`: The maximum number of ports (publishers, subscribers, clients, and servers combined)
+///   that can have registers
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug, Clone)]
+pub struct StandardRegisters<const P: usize> {
+    node_id: SimpleRegister<u16>,
+    active_node_id: u16,
+    description: SimpleRegister<RegisterString>,
+    can_bitrate: Option<SimpleRegister<[u32; 2]>>,
+    ports: heapless::Vec<PortRegisters, P>,
+}
+
+impl<const P: usize> StandardRegisters<P> {
+    /// Creates a set of standard registers with no node ID, an empty description, and no ports
+    pub fn new() -> Self {
+        StandardRegisters {
+            node_id: SimpleRegister::with_value("uavcan.node.id", true, true, UNSET_ID),
+            active_node_id: UNSET_ID,
+            description: SimpleRegister::new("uavcan.node.description", true, true),
+            can_bitrate: None,
+            ports: heapless::Vec::new(),
+        }
+    }
+
+    /// Creates a set of standard registers that reflect the current node ID of a node
+    pub fn for_node<N: Node>(node: &N) -> Self {
+        let mut registers = Self::new();
+        if let Some(node_id) = node.node_id() {
+            let node_id = node_id.into() as u16;
+            registers.node_id.set_value(node_id);
+            registers.active_node_id = node_id;
+        }
+        registers
+    }
+
+    /// Enables the `uavcan.can.bitrate` register with the provided arbitration and data phase
+    /// bit rates
+    ///
+    /// For classic CAN, the data phase bit rate should be equal to the arbitration bit rate.
+    pub fn enable_can_bitrate(&mut self, arbitration: u32, data: u32) {
+        self.can_bitrate = Some(SimpleRegister::with_value(
+            "uavcan.can.bitrate",
+            true,
+            true,
+            [arbitration, data],
+        ));
+    }
+
+    /// Adds registers for a port
+    ///
+    /// * `kind`: The kind of port
+    /// * `port_name`: The name of the port, which becomes part of the register names
+    /// * `type_name`: The full name of the data type used on this port, like
+    ///   `uavcan.si.unit.temperature.Scalar.1.0`
+    /// * `default_id`: The port ID to use until another ID is written
+    ///
+    /// This function returns an error if there is no space for another port, if the register names
+    /// would be too long, or if another port of the same kind has the same name.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `default_id` is not a valid subject or service ID for the
+    /// provided kind of port.
+    pub fn add_port(
+        &mut self,
+        kind: PortKind,
+        port_name: &'static str,
+        type_name: &'static str,
+        default_id: Option<u16>,
+    ) -> Result<(), OutOfMemoryError> {
+        let default_id = default_id.unwrap_or(UNSET_ID);
+        assert!(kind.accepts(default_id), "Invalid default port ID");
+        if self
+            .ports
+            .iter()
+            .any(|port| port.kind() == kind && port.port_name == port_name)
+            || type_name.len() > STRING_CAPACITY
+        {
+            return Err(OutOfMemoryError);
+        }
+        let port = PortRegisters {
+            port_name,
+            id: PortIdRegister {
+                name: port_register_name(kind, port_name, "id")?,
+                kind,
+                configured: default_id,
+                active: default_id,
+            },
+            data_type: PortTypeRegister {
+                name: port_register_name(kind, port_name, "type")?,
+                type_name,
+            },
+        };
+        self.ports.push(port).map_err(|_| OutOfMemoryError)
+    }
+
+    /// Returns the active node ID
+    ///
+    /// This function returns None if the node ID is not set or is too large for the transport.
+    pub fn node_id<T: Transport>(&self) -> Option<T::NodeId> {
+        if self.active_node_id == UNSET_ID {
+            None
+        } else {
+            T::NodeId::try_from(self.active_node_id).ok()
+        }
+    }
+
+    /// Returns the node description
+    pub fn description(&self) -> &[u8] {
+        &self.description.value().0
+    }
+
+    /// Returns the configured CAN arbitration and data phase bit rates, if the bit rate register
+    /// is enabled
+    pub fn can_bitrate(&self) -> Option<[u32; 2]> {
+        self.can_bitrate.as_ref().map(|register| *register.value())
+    }
+
+    /// Returns the registers for a port
+    pub fn port(&self, kind: PortKind, port_name: &str) -> Option<&PortRegisters> {
+        self.ports
+            .iter()
+            .find(|port| port.kind() == kind && port.port_name == port_name)
+    }
+
+    /// Returns an iterator over the registers for all ports
+    pub fn ports(&self) -> impl Iterator<Item = &PortRegisters> {
+        self.ports.iter()
+    }
+
+    /// Returns the active subject ID of a publisher or subscriber
+    pub fn subject_id(&self, kind: PortKind, port_name: &str) -> Option<SubjectId> {
+        self.port(kind, port_name)
+            .and_then(PortRegisters::subject_id)
+    }
+
+    /// Returns the active service ID of a client or server
+    pub fn service_id(&self, kind: PortKind, port_name: &str) -> Option<ServiceId> {
+        self.port(kind, port_name)
+            .and_then(PortRegisters::service_id)
+    }
+
+    /// Returns true if any node ID or port ID has been written but not yet applied
+    pub fn has_pending_changes(&self) -> bool {
+        *self.node_id.value() != self.active_node_id
+            || self.ports.iter().any(|port| port.id.is_changed())
+    }
+
+    /// Applies all node ID and port ID changes that have been written since the node started
+    ///
+    /// This should be called when the node restarts. If the node ID has changed to a valid
+    /// value, the node's ID is updated.
+    ///
+    /// For each port whose ID has changed, this function calls `on_change` after making the
+    /// new ID active. The application can use this to unsubscribe from the old port and subscribe
+    /// to the new one (or stop and start publishing).
+    pub fn apply_changes<N, F>(&mut self, node: &mut N, mut on_change: F)
+    where
+        N: Node,
+        F: FnMut(&mut N, PortChange<'_>),
+    {
+        let configured_node_id = *self.node_id.value();
+        if configured_node_id != self.active_node_id {
+            self.active_node_id = configured_node_id;
+            if let Some(node_id) = self.node_id::<N::Transport>() {
+                node.set_node_id(node_id);
+            }
+        }
+        for port in self.ports.iter_mut() {
+            if port.id.is_changed() {
+                let previous = port.id.active;
+                port.id.active = port.id.configured;
+                on_change(node, PortChange { port, previous });
+            }
+        }
+    }
+
+    /// Returns the number of fixed registers (not associated with ports)
+    fn fixed_register_count(&self) -> usize {
+        if self.can_bitrate.is_some() {
+            3
+        } else {
+            2
+        }
+    }
+}
+
+impl<const P: usize> Default for StandardRegisters<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const P: usize> RegisterBlock for StandardRegisters<P> {
+    fn register_by_index(&self, index: usize) -> Option<&dyn Register> {
+        match index {
+            0 => Some(&self.node_id),
+            1 => Some(&self.description),
+            2 if self.can_bitrate.is_some() => {
+                self.can_bitrate.as_ref().map(|r| r as &dyn Register)
+            }
+            _ => {
+                let port_index = index - self.fixed_register_count();
+                let port = self.ports.get(port_index / 2)?;
+                match port_index % 2 {
+                    0 => Some(&port.id),
+                    _ => Some(&port.data_type),
+                }
+            }
+        }
+    }
+
+    fn register_by_index_mut(&mut self, index: usize) -> Option<&mut dyn Register> {
+        let fixed_count = self.fixed_register_count();
+        match index {
+            0 => Some(&mut self.node_id),
+            1 => Some(&mut self.description),
+            2 if self.can_bitrate.is_some() => {
+                self.can_bitrate.as_mut().map(|r| r as &mut dyn Register)
+            }
+            _ => {
+                let port_index = index - fixed_count;
+                let port = self.ports.get_mut(port_index / 2)?;
+                match port_index % 2 {
+                    0 => Some(&mut port.id),
+                    _ => Some(&mut port.data_type),
+                }
+            }
+        }
+    }
+
+    fn register_by_name_mut(&mut self, name: &str) -> Option<&mut dyn Register> {
+        if name == self.node_id.name() {
+            return Some(&mut self.node_id);
+        }
+        if name == self.description.name() {
+            return Some(&mut self.description);
+        }
+        if let Some(bitrate) = self.can_bitrate.as_mut() {
+            if name == bitrate.name() {
+                return Some(bitrate);
+            }
+        }
+        for port in self.ports.iter_mut() {
+            if name == port.id.name() {
+                return Some(&mut port.id);
+            }
+            if name == port.data_type.name() {
+                return Some(&mut port.data_type);
+            }
+        }
+        None
+    }
+
+    fn register_count(&self) -> usize {
+        self.fixed_register_count() + 2 * self.ports.len()
+    }
+}
+
+/// Creates a register name in the format `uavcan.[kind].[port name].[suffix]`
+fn port_register_name(
+    kind: PortKind,
+    port_name: &str,
+    suffix: &str,
+) -> Result<heapless::String<NAME_CAPACITY>, OutOfMemoryError> {
+    let mut name = heapless::String::new();
+    write!(name, "uavcan.{}.{}.{}", kind.segment(), port_name, suffix)
+        .map_err(|_| OutOfMemoryError)?;
+    Ok(name)
+}
@@ -3,12 +3,12 @@
 use heapless::FnvIndexMap;
 
 use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
-use canadensis_core::transfer::{Header, ServiceHeader, Transfer};
+use canadensis_core::transfer::{Header, ServiceHeader};
 use canadensis_core::transport::{TransferId, Transmitter, Transport};
 use canadensis_core::{nb, OutOfMemoryError, ServiceId, TransferIdTracker};
 use canadensis_encoding::{Request, Serialize};
 
-use crate::serialize::do_serialize;
+use crate::serialize::{prepare_payload, OutgoingPayload};
 
 /// Assembles transfers and manages transfer IDs to send service requests
 pub struct Requester<C: Clock, T: Transmitter<C>, R> {
@@ -53,7 +53,8 @@ impl<C: Clock, T: Transmitter<C>, R: TransferIdTracker<T::Transport>> Requester<
     {
         // Part 1: Serialize
         let deadline = clock.now() + self.timeout;
-        do_serialize(payload, |payload_bytes| {
+        let mtu = transmitter.mtu();
+        prepare_payload(payload, mtu, |payload_bytes| {
             // Part 2: Split into frames and send
             self.send_payload(
                 payload_bytes,
@@ -85,7 +86,8 @@ impl<C: Clock, T: Transmitter<C>, R: TransferIdTracker<T::Transport>> Requester<
     {
         // Part 1: Serialize
         let deadline = clock.now() + self.timeout;
-        do_serialize(payload, |payload_bytes| {
+        let mtu = transmitter.mtu();
+        prepare_payload(payload, mtu, |payload_bytes| {
             // Part 2: Split into frames and send
             self.send_payload(
                 payload_bytes,
@@ -101,9 +103,38 @@ impl<C: Clock, T: Transmitter<C>, R: TransferIdTracker<T::Transport>> Requester<
         })
     }
 
+    /// Sends a service request that has already been serialized into one or more byte slices,
+    /// and returns its transfer ID
+    ///
+    /// The slices are concatenated as the transmitter splits the transfer into frames.
+    /// The loopback flag is set to false.
+    pub fn send_vectored(
+        &mut self,
+        clock: &mut C,
+        source: <T::Transport as Transport>::NodeId,
+        service: ServiceId,
+        pieces: &[&[u8]],
+        destination: <T::Transport as Transport>::NodeId,
+        transmitter: &mut T,
+        driver: &mut T::Driver,
+    ) -> nb::Result<<T::Transport as Transport>::TransferId, T::Error> {
+        let deadline = clock.now() + self.timeout;
+        self.send_payload(
+            OutgoingPayload::Pieces(pieces),
+            source,
+            service,
+            destination,
+            deadline,
+            false,
+            transmitter,
+            clock,
+            driver,
+        )
+    }
+
     fn send_payload(
         &mut self,
-        payload: &[u8],
+        payload: OutgoingPayload<'_>,
         source: <T::Transport as Transport>::NodeId,
         service: ServiceId,
         destination: <T::Transport as Transport>::NodeId,
@@ -118,20 +149,16 @@ impl<C: Clock, T: Transmitter<C>, R: TransferIdTracker<T::Transport>> Requester<
             .transfer_ids
             .next_transfer_id(destination.clone())
             .map_err(|oom| nb::Error::Other(oom.into()))?;
-        let transfer = Transfer {
-            header: Header::Request(ServiceHeader {
-                timestamp: deadline,
-                transfer_id: transfer_id.clone(),
-                priority: self.priority.clone(),
-                service,
-                source,
-                destination,
-            }),
-            loopback,
-            payload,
-        };
+        let header = Header::Request(ServiceHeader {
+            timestamp: deadline,
+            transfer_id: transfer_id.clone(),
+            priority: self.priority.clone(),
+            service,
+            source,
+            destination,
+        });
 
-        transmitter.push(transfer, clock, driver)?;
+        payload.push(header, loopback, transmitter, clock, driver)?;
         Ok(transfer_id)
     }
 }
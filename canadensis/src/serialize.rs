@@ -1,5 +1,8 @@
 use crate::encoding::{Serialize, WriteCursor};
 use alloc::vec::Vec;
+use canadensis_core::time::Clock;
+use canadensis_core::transfer::{Header, Transfer};
+use canadensis_core::transport::{StreamingPayload, Transmitter};
 use canadensis_core::{nb, OutOfMemoryError};
 use core::iter;
 use fallible_collections::{FallibleVec, TryReserveError};
@@ -14,7 +17,7 @@ where
     F: FnOnce(&[u8]) -> nb::Result<R, E>,
     E: From<OutOfMemoryError>,
 {
-    let payload_bytes = (payload.size_bits() + 7) / 8;
+    let payload_bytes = payload.size_bytes();
     if payload_bytes > STACK_THRESHOLD {
         let mut bytes: Vec<u8> = FallibleVec::try_with_capacity(payload_bytes)
             .map_err(|e: TryReserveError| nb::Error::Other(E::from(OutOfMemoryError::from(e))))?;
@@ -28,3 +31,97 @@ where
         operation(bytes)
     }
 }
+
+/// A payload that is ready to be sent
+pub(crate) enum OutgoingPayload<'p> {
+    /// A payload that has already been serialized
+    Bytes(&'p [u8]),
+    /// A value that will be serialized as the transmitter breaks it into frames
+    Streaming(&'p dyn StreamingPayload),
+    /// Serialized byte slices that will be concatenated as the transmitter breaks them into
+    /// frames
+    Pieces(&'p [&'p [u8]]),
+}
+
+impl OutgoingPayload<'_> {
+    /// Passes a transfer with this payload to a transmitter
+    pub(crate) fn push<C, T>(
+        self,
+        header: Header<T::Transport>,
+        loopback: bool,
+        transmitter: &mut T,
+        clock: &mut C,
+        driver: &mut T::Driver,
+    ) -> nb::Result<(), T::Error>
+    where
+        C: Clock,
+        T: Transmitter<C>,
+    {
+        match self {
+            OutgoingPayload::Bytes(payload) => transmitter.push(
+                Transfer {
+                    header,
+                    loopback,
+                    payload,
+                },
+                clock,
+                driver,
+            ),
+            OutgoingPayload::Streaming(payload) => transmitter.push_streaming(
+                Transfer {
+                    header,
+                    loopback,
+                    payload,
+                },
+                clock,
+                driver,
+            ),
+            OutgoingPayload::Pieces(payload) => transmitter.push_streaming(
+                Transfer {
+                    header,
+                    loopback,
+                    payload,
+                },
+                clock,
+                driver,
+            ),
+        }
+    }
+}
+
+/// Prepares a payload to be sent and passes it to a closure
+///
+/// A payload that fits into one frame (with an MTU of `mtu` bytes) and is not too large for a
+/// buffer on the stack is serialized into that buffer. Any other payload is serialized as the
+/// transmitter breaks it into frames, so no buffer for the whole payload is needed.
+pub(crate) fn prepare_payload<T, F, R, E>(payload: &T, mtu: usize, operation: F) -> nb::Result<R, E>
+where
+    T: Serialize,
+    F: FnOnce(OutgoingPayload<'_>) -> nb::Result<R, E>,
+{
+    let payload_bytes = payload.size_bytes();
+    if payload_bytes > STACK_THRESHOLD || payload_bytes > mtu {
+        operation(OutgoingPayload::Streaming(&SerializedPayload(payload)))
+    } else {
+        let mut bytes = [0u8; STACK_THRESHOLD];
+        let bytes = &mut bytes[..payload_bytes];
+        payload.serialize(&mut WriteCursor::new(bytes));
+        operation(OutgoingPayload::Bytes(bytes))
+    }
+}
+
+/// A value that is serialized in pieces as it is sent
+struct SerializedPayload<'p, T>(&'p T);
+
+impl<T> StreamingPayload for SerializedPayload<'_, T>
+where
+    T: Serialize,
+{
+    fn payload_length(&self) -> usize {
+        self.0.size_bytes()
+    }
+
+    fn write_to(&self, sink: &mut dyn FnMut(&[u8])) {
+        self.0.serialize_in_pieces(sink)
+    }
+}
@@ -0,0 +1,116 @@
+// All versions of uavcan.node.ExecuteCommand in the current public regulated data types
+// are deprecated, but version 1.2 is still the one that other nodes send.
+#![allow(deprecated)]
+
+use crate::{Node, ResponseToken, ServiceTransfer, TransferHandler};
+use alloc::vec::Vec;
+use canadensis_core::time::milliseconds;
+use canadensis_core::transport::Receiver;
+use canadensis_core::ServiceSubscribeError;
+use canadensis_data_types::uavcan::node::execute_command_1_2::{
+    ExecuteCommandRequest, ExecuteCommandResponse, SERVICE,
+};
+use canadensis_encoding::{Deserialize, SerializedSize};
+use core::marker::PhantomData;
+
+/// Something that can carry out commands from `uavcan.node.ExecuteCommand` requests
+pub trait CommandHandler {
+    /// Executes a command and returns the status code to send in the response
+    ///
+    /// The standard command and status codes are defined in
+    /// `ExecuteCommandRequest` and `ExecuteCommandResponse`.
+    ///
+    /// Commands that take a long time (like a restart or a software update) should only be
+    /// started here. The response is sent after this function returns.
+    fn execute(&mut self, command: u16, parameter: &[u8]) -> u8;
+}
+
+impl<F> CommandHandler for F
+where
+    F: FnMut(u16, &[u8]) -> u8,
+{
+    fn execute(&mut self, command: u16, parameter: &[u8]) -> u8 {
+        self(command, parameter)
+    }
+}
+
+/// A service that responds to `uavcan.node.ExecuteCommand` requests
+pub struct ExecuteCommandService<N, X> {
+    commands: X,
+    _node: PhantomData<N>,
+}
+
+impl<N, X> ExecuteCommandService<N, X>
+where
+    N: Node,
+    X: CommandHandler,
+{
+    /// Creates a new ExecuteCommand service
+    ///
+    /// * `node`: The node to use for responding to requests
+    /// * `commands`: The handler that executes commands
+    pub fn new(
+        node: &mut N,
+        commands: X,
+    ) -> Result<Self, ServiceSubscribeError<<N::Receiver as Receiver<N::Clock>>::Error>> {
+        node.subscribe_request(
+            SERVICE,
+            ExecuteCommandRequest::RECEIVE_SIZE_BYTES,
+            milliseconds(1000),
+        )?;
+        Ok(Self {
+            commands,
+            _node: PhantomData,
+        })
+    }
+
+    /// Returns a reference to the command handler
+    pub fn commands(&self) -> &X {
+        &self.commands
+    }
+
+    /// Returns a mutable reference to the command handler
+    pub fn commands_mut(&mut self) -> &mut X {
+        &mut self.commands
+    }
+
+    /// Returns the handler for this service
+    pub fn handler(&mut self) -> ExecuteCommandServiceHandler<'_, N, X> {
+        ExecuteCommandServiceHandler { service: self }
+    }
+}
+
+/// The [`TransferHandler`] for the [`ExecuteCommandService`]
+pub struct ExecuteCommandServiceHandler<'a, N, X> {
+    service: &'a mut ExecuteCommandService<N, X>,
+}
+
+impl<N, X> TransferHandler<N::Transport> for ExecuteCommandServiceHandler<'_, N, X>
+where
+    N: Node,
+    X: CommandHandler,
+{
+    fn handle_request<N2: Node<Transport = N::Transport>>(
+        &mut self,
+        node: &mut N2,
+        token: ResponseToken<N2::Transport>,
+        transfer: &ServiceTransfer<Vec<u8>, N2::Transport>,
+    ) -> bool {
+        if transfer.header.service != SERVICE {
+            return false;
+        }
+        let status = match ExecuteCommandRequest::deserialize_from_bytes(&transfer.payload) {
+            Ok(request) => self
+                .service
+                .commands
+                .execute(request.command, &request.parameter),
+            Err(_) => ExecuteCommandResponse::STATUS_BAD_PARAMETER,
+        };
+        let _ = node.send_response(
+            token,
+            milliseconds(1000),
+            &ExecuteCommandResponse { status },
+        );
+        true
+    }
+}
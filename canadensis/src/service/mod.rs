@@ -1,12 +1,21 @@
 //!
 //! Cyphal services intended for use with Nodes
 
+/// Handles ExecuteCommand requests
+pub mod execute_command;
+
 /// Handles GetInfo requests
 pub mod get_info;
 
 /// Generate heartbeat messages
 pub mod heartbeat;
 
+/// Node ID conflict detection
+pub mod node_id_conflict;
+
+/// Remote node monitoring using heartbeats
+pub mod node_monitor;
+
 /// Cyphal plug-and-play client
 pub mod pnp_client;
 
@@ -15,3 +24,6 @@ pub mod port_list;
 
 /// Register server
 pub mod register_server;
+
+/// Combination of remote node health values
+pub mod system_health;
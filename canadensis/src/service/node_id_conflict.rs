@@ -0,0 +1,234 @@
+use crate::core::time::{milliseconds, MicrosecondDuration32, Microseconds32};
+use crate::core::transfer::{MessageTransfer, ServiceTransfer};
+use crate::core::transport::{Receiver, Transport};
+use crate::encoding::SerializedSize;
+use crate::{Node, ResponseToken, TransferHandler};
+use alloc::vec::Vec;
+use canadensis_core::{ServiceId, SubjectId};
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+use canadensis_data_types::uavcan::node::heartbeat_1_0::{Heartbeat, SUBJECT};
+use core::marker::PhantomData;
+
+/// Detects other nodes that send transfers using the node ID of this node
+///
+/// Two nodes with the same node ID corrupt each other's transfers. This service watches incoming
+/// transfers (and subscribes to heartbeats, which all non-anonymous nodes publish) and records
+/// a conflict whenever it sees a transfer that was not sent by this node but has this node's ID
+/// as its source.
+///
+/// The application can check [`conflict_count`](#method.conflict_count) or
+/// [`health`](#method.health) periodically and report the conflict in its own heartbeat messages.
+/// It can also provide a hook function using [`with_hook`](#method.with_hook), which is called
+/// with each conflicting transfer.
+///
+/// Loopback transfers (copies of transfers that this node sent) are not conflicts.
+pub struct NodeIdConflictService<N, H = fn(&NodeIdConflict)> {
+    /// Number of conflicting transfers detected
+    conflicts: u32,
+    /// The time when the most recent conflicting transfer started
+    last_conflict: Option<Microseconds32>,
+    /// The length of time after a conflict when it is still reported
+    hold_time: MicrosecondDuration32,
+    /// The function to call when a conflict is detected
+    hook: Option<H>,
+    _node: PhantomData<N>,
+}
+
+/// A transfer from another node that had this node's ID as its source
+#[derive(Debug, Clone)]
+pub struct NodeIdConflict {
+    /// The time when the transfer started
+    pub timestamp: Microseconds32,
+    /// The kind of transfer and its port
+    pub port: ConflictPort,
+}
+
+/// The kind and port of a conflicting transfer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ConflictPort {
+    /// A message on a subject
+    Message(SubjectId),
+    /// A service request
+    Request(ServiceId),
+    /// A service response
+    Response(ServiceId),
+}
+
+impl<N> NodeIdConflictService<N>
+where
+    N: Node,
+{
+    /// Creates a node ID conflict detector and subscribes to heartbeat messages
+    ///
+    /// After a conflict, [`health`](#method.health) reports a warning for
+    /// `uavcan.node.Heartbeat.OFFLINE_TIMEOUT` (3 seconds).
+    pub fn new(node: &mut N) -> Result<Self, <N::Receiver as Receiver<N::Clock>>::Error> {
+        Self::create(node, None)
+    }
+}
+
+impl<N, H> NodeIdConflictService<N, H>
+where
+    N: Node,
+    H: FnMut(&NodeIdConflict),
+{
+    /// Creates a node ID conflict detector that calls `hook` with each conflicting transfer,
+    /// and subscribes to heartbeat messages
+    pub fn with_hook(
+        node: &mut N,
+        hook: H,
+    ) -> Result<Self, <N::Receiver as Receiver<N::Clock>>::Error> {
+        Self::create(node, Some(hook))
+    }
+
+    fn create(
+        node: &mut N,
+        hook: Option<H>,
+    ) -> Result<Self, <N::Receiver as Receiver<N::Clock>>::Error> {
+        node.subscribe_message(SUBJECT, Heartbeat::RECEIVE_SIZE_BYTES, milliseconds(1000))?;
+        Ok(NodeIdConflictService {
+            conflicts: 0,
+            last_conflict: None,
+            hold_time: milliseconds(u32::from(Heartbeat::OFFLINE_TIMEOUT) * 1000),
+            hook,
+            _node: PhantomData,
+        })
+    }
+
+    /// Sets the length of time after a conflict when [`health`](#method.health) still reports
+    /// a warning
+    pub fn set_hold_time(&mut self, hold_time: MicrosecondDuration32) {
+        self.hold_time = hold_time;
+    }
+
+    /// Returns the number of conflicting transfers that have been detected
+    pub fn conflict_count(&self) -> u32 {
+        self.conflicts
+    }
+
+    /// Returns the timestamp of the most recent conflicting transfer, if any conflict has been
+    /// detected
+    pub fn last_conflict(&self) -> Option<Microseconds32> {
+        self.last_conflict
+    }
+
+    /// Returns true if a conflicting transfer was detected within the hold time before `now`
+    pub fn conflict_active(&self, now: Microseconds32) -> bool {
+        match self.last_conflict {
+            Some(last_conflict) => now <= last_conflict + self.hold_time,
+            None => false,
+        }
+    }
+
+    /// Returns the health that this node should report based on detected conflicts
+    ///
+    /// This is `WARNING` if a conflict is active, or `NOMINAL` otherwise.
+    pub fn health(&self, now: Microseconds32) -> Health {
+        let value = if self.conflict_active(now) {
+            Health::WARNING
+        } else {
+            Health::NOMINAL
+        };
+        Health { value }
+    }
+
+    /// Clears all detected conflicts
+    ///
+    /// This can be used after this node gets a different node ID.
+    pub fn clear(&mut self) {
+        self.conflicts = 0;
+        self.last_conflict = None;
+    }
+
+    /// Returns the handler for this service
+    pub fn handler(&mut self) -> NodeIdConflictServiceHandler<'_, N, H> {
+        NodeIdConflictServiceHandler { service: self }
+    }
+
+    fn check_source<T: Transport>(
+        &mut self,
+        local_id: Option<T::NodeId>,
+        source: &T::NodeId,
+        loopback: bool,
+        conflict: NodeIdConflict,
+    ) {
+        if !loopback && local_id.as_ref() == Some(source) {
+            log::warn!(
+                "Another node is sending transfers with this node's ID ({:?})",
+                conflict.port
+            );
+            self.conflicts = self.conflicts.wrapping_add(1);
+            self.last_conflict = Some(conflict.timestamp);
+            if let Some(hook) = self.hook.as_mut() {
+                hook(&conflict);
+            }
+        }
+    }
+}
+
+/// A handler that detects node ID conflicts
+///
+/// This handler never consumes a transfer, so it can be placed anywhere in a handler chain.
+pub struct NodeIdConflictServiceHandler<'a, N, H = fn(&NodeIdConflict)> {
+    service: &'a mut NodeIdConflictService<N, H>,
+}
+
+impl<N, H> TransferHandler<N::Transport> for NodeIdConflictServiceHandler<'_, N, H>
+where
+    N: Node,
+    H: FnMut(&NodeIdConflict),
+{
+    fn handle_message<N2: Node<Transport = N::Transport>>(
+        &mut self,
+        node: &mut N2,
+        transfer: &MessageTransfer<Vec<u8>, N2::Transport>,
+    ) -> bool {
+        if let Some(source) = &transfer.header.source {
+            self.service.check_source::<N::Transport>(
+                node.node_id(),
+                source,
+                transfer.loopback,
+                NodeIdConflict {
+                    timestamp: transfer.header.timestamp,
+                    port: ConflictPort::Message(transfer.header.subject),
+                },
+            );
+        }
+        false
+    }
+
+    fn handle_request<N2: Node<Transport = N::Transport>>(
+        &mut self,
+        node: &mut N2,
+        _token: ResponseToken<N2::Transport>,
+        transfer: &ServiceTransfer<Vec<u8>, N2::Transport>,
+    ) -> bool {
+        self.service.check_source::<N::Transport>(
+            node.node_id(),
+            &transfer.header.source,
+            transfer.loopback,
+            NodeIdConflict {
+                timestamp: transfer.header.timestamp,
+                port: ConflictPort::Request(transfer.header.service),
+            },
+        );
+        false
+    }
+
+    fn handle_response<N2: Node<Transport = N::Transport>>(
+        &mut self,
+        node: &mut N2,
+        transfer: &ServiceTransfer<Vec<u8>, N2::Transport>,
+    ) -> bool {
+        self.service.check_source::<N::Transport>(
+            node.node_id(),
+            &transfer.header.source,
+            transfer.loopback,
+            NodeIdConflict {
+                timestamp: transfer.header.timestamp,
+                port: ConflictPort::Response(transfer.header.service),
+            },
+        );
+        false
+    }
+}
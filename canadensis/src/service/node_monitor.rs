@@ -0,0 +1,288 @@
+use crate::core::time::{milliseconds, MicrosecondDuration32, Microseconds32};
+use crate::core::transfer::MessageTransfer;
+use crate::core::transport::{Receiver, TransferId, Transport};
+use crate::encoding::{Deserialize, SerializedSize};
+use crate::{Node, TransferHandler};
+use alloc::vec::Vec;
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+use canadensis_data_types::uavcan::node::heartbeat_1_0::{Heartbeat, SUBJECT};
+use canadensis_data_types::uavcan::node::mode_1_0::Mode;
+use core::marker::PhantomData;
+use heapless::Deque;
+
+/// Keeps track of the other nodes on the network using their heartbeat messages
+///
+/// This service can track up to `C` nodes. It reports changes in the set of nodes as
+/// [`NodeEvent`]s.
+///
+/// A remote node is considered to have restarted when its uptime decreases or when the
+/// transfer IDs of its heartbeats skip more heartbeats than the node could have published since
+/// the last heartbeat was received (or within the offline timeout, if that is longer).
+/// This is reported as a [`NodeEvent::Restarted`] event, which can be used to send configuration
+/// to a node again.
+pub struct NodeMonitorService<N: Node, const C: usize> {
+    /// The nodes currently online
+    nodes: heapless::Vec<NodeStatus<N::Transport>, C>,
+    /// Events not yet handled by the application
+    events: Deque<NodeEvent<<N::Transport as Transport>::NodeId>, C>,
+    /// The time after the last heartbeat from a node when the node is considered offline
+    offline_timeout: MicrosecondDuration32,
+    _node: PhantomData<N>,
+}
+
+impl<N: Node, const C: usize> NodeMonitorService<N, C> {
+    /// Creates a node monitor and subscribes to heartbeat messages
+    ///
+    /// Nodes are considered offline `uavcan.node.Heartbeat.OFFLINE_TIMEOUT` (3 seconds) after
+    /// their last heartbeat.
+    pub fn new(node: &mut N) -> Result<Self, <N::Receiver as Receiver<N::Clock>>::Error> {
+        node.subscribe_message(SUBJECT, Heartbeat::RECEIVE_SIZE_BYTES, milliseconds(1000))?;
+        Ok(NodeMonitorService {
+            nodes: heapless::Vec::new(),
+            events: Deque::new(),
+            offline_timeout: milliseconds(u32::from(Heartbeat::OFFLINE_TIMEOUT) * 1000),
+            _node: PhantomData,
+        })
+    }
+
+    /// Sets the time after the last heartbeat from a node when the node is considered offline
+    pub fn set_offline_timeout(&mut self, timeout: MicrosecondDuration32) {
+        self.offline_timeout = timeout;
+    }
+
+    /// Returns the status of all nodes that are currently online
+    pub fn nodes(&self) -> impl Iterator<Item = &NodeStatus<N::Transport>> {
+        self.nodes.iter()
+    }
+
+    /// Returns the status of a node, if it is online
+    pub fn node(
+        &self,
+        node_id: &<N::Transport as Transport>::NodeId,
+    ) -> Option<&NodeStatus<N::Transport>> {
+        self.nodes.iter().find(|status| &status.node_id == node_id)
+    }
+
+    /// Removes and returns the oldest event that has not been handled
+    ///
+    /// If more than `C` events are not handled, the oldest events are discarded.
+    pub fn pop_event(&mut self) -> Option<NodeEvent<<N::Transport as Transport>::NodeId>> {
+        self.events.pop_front()
+    }
+
+    /// Removes nodes that have not sent a heartbeat within the offline timeout
+    ///
+    /// This function should be called periodically (for example, once per second). It generates
+    /// a [`NodeEvent::Offline`] event for each removed node.
+    pub fn check_timeouts(&mut self, now: Microseconds32) {
+        let timeout = self.offline_timeout;
+        let mut i = 0;
+        while i < self.nodes.len() {
+            if now > self.nodes[i].last_heartbeat + timeout {
+                let status = self.nodes.swap_remove(i);
+                self.push_event(NodeEvent::Offline(status.node_id));
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Returns the handler for this service
+    pub fn handler(&mut self) -> NodeMonitorServiceHandler<'_, N, C> {
+        NodeMonitorServiceHandler { service: self }
+    }
+
+    fn push_event(&mut self, event: NodeEvent<<N::Transport as Transport>::NodeId>) {
+        if self.events.is_full() {
+            self.events.pop_front();
+        }
+        // This can't fail because the deque is not full
+        let _ = self.events.push_back(event);
+    }
+
+    fn handle_heartbeat(
+        &mut self,
+        header_source: &<N::Transport as Transport>::NodeId,
+        transfer_id: &<N::Transport as Transport>::TransferId,
+        timestamp: Microseconds32,
+        heartbeat: Heartbeat,
+    ) where
+        <N::Transport as Transport>::TransferId: PartialEq,
+    {
+        let offline_timeout = self.offline_timeout;
+        let existing = self
+            .nodes
+            .iter_mut()
+            .find(|status| &status.node_id == header_source);
+        match existing {
+            Some(status) => {
+                let elapsed = timestamp
+                    .checked_duration_since(status.last_heartbeat)
+                    .unwrap_or_else(|| MicrosecondDuration32::from_ticks(0));
+                let max_gap = max_transfer_id_gap(elapsed, offline_timeout);
+                let restarted = heartbeat.uptime < status.uptime
+                    || !transfer_id_continuous(&status.transfer_id, transfer_id, max_gap);
+                status.update(transfer_id, timestamp, &heartbeat);
+                if restarted {
+                    status.restarts = status.restarts.wrapping_add(1);
+                    let node_id = status.node_id.clone();
+                    self.push_event(NodeEvent::Restarted(node_id));
+                }
+            }
+            None => {
+                let mut status = NodeStatus {
+                    node_id: header_source.clone(),
+                    transfer_id: transfer_id.clone(),
+                    last_heartbeat: timestamp,
+                    uptime: 0,
+                    health: Health::NOMINAL,
+                    mode: Mode::OPERATIONAL,
+                    vendor_specific_status_code: 0,
+                    restarts: 0,
+                };
+                status.update(transfer_id, timestamp, &heartbeat);
+                if self.nodes.push(status).is_ok() {
+                    self.push_event(NodeEvent::Online(header_source.clone()));
+                } else {
+                    log::warn!("Node monitor full, ignoring node {:?}", header_source);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the number of heartbeats that a node may have published since its last received
+/// heartbeat
+///
+/// Heartbeats are published once every `uavcan.node.Heartbeat.MAX_PUBLICATION_PERIOD`. A node
+/// that is still online may have published heartbeats for up to `offline_timeout`, so the gap is
+/// never smaller than that.
+fn max_transfer_id_gap(
+    elapsed: MicrosecondDuration32,
+    offline_timeout: MicrosecondDuration32,
+) -> usize {
+    let period = u32::from(Heartbeat::MAX_PUBLICATION_PERIOD) * 1_000_000;
+    let elapsed = elapsed.ticks().max(offline_timeout.ticks());
+    elapsed.div_ceil(period) as usize + 1
+}
+
+/// Returns true if `new` is a plausible transfer ID for a heartbeat up to `max_gap` heartbeats
+/// after one with the transfer ID `previous`
+fn transfer_id_continuous<I>(previous: &I, new: &I, max_gap: usize) -> bool
+where
+    I: TransferId + PartialEq,
+{
+    let mut expected = previous.clone();
+    for _ in 0..max_gap {
+        expected = expected.increment();
+        if &expected == new {
+            return true;
+        }
+    }
+    false
+}
+
+/// A change in the status of a remote node
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeEvent<I> {
+    /// A node sent its first heartbeat, or sent a heartbeat after being offline
+    Online(I),
+    /// A node restarted
+    ///
+    /// This is detected when the node's uptime decreases or its heartbeat transfer IDs skip
+    /// more heartbeats than the node could have published.
+    Restarted(I),
+    /// A node has not sent a heartbeat within the offline timeout
+    Offline(I),
+}
+
+/// The most recent information about a node
+pub struct NodeStatus<T: Transport> {
+    node_id: T::NodeId,
+    transfer_id: T::TransferId,
+    last_heartbeat: Microseconds32,
+    uptime: u32,
+    health: u8,
+    mode: u8,
+    vendor_specific_status_code: u8,
+    restarts: u32,
+}
+
+impl<T: Transport> NodeStatus<T> {
+    /// Returns the ID of this node
+    pub fn node_id(&self) -> &T::NodeId {
+        &self.node_id
+    }
+    /// Returns the time when the most recent heartbeat from this node was received
+    pub fn last_heartbeat(&self) -> Microseconds32 {
+        self.last_heartbeat
+    }
+    /// Returns the uptime reported in the most recent heartbeat, in seconds
+    pub fn uptime(&self) -> u32 {
+        self.uptime
+    }
+    /// Returns the health reported in the most recent heartbeat
+    pub fn health(&self) -> Health {
+        Health { value: self.health }
+    }
+    /// Returns the mode reported in the most recent heartbeat
+    pub fn mode(&self) -> Mode {
+        Mode { value: self.mode }
+    }
+    /// Returns the vendor-specific status code reported in the most recent heartbeat
+    pub fn vendor_specific_status_code(&self) -> u8 {
+        self.vendor_specific_status_code
+    }
+    /// Returns the number of times this node has been detected restarting
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    fn update(
+        &mut self,
+        transfer_id: &T::TransferId,
+        timestamp: Microseconds32,
+        heartbeat: &Heartbeat,
+    ) {
+        self.transfer_id = transfer_id.clone();
+        self.last_heartbeat = timestamp;
+        self.uptime = heartbeat.uptime;
+        self.health = heartbeat.health.value;
+        self.mode = heartbeat.mode.value;
+        self.vendor_specific_status_code = heartbeat.vendor_specific_status_code;
+    }
+}
+
+/// A handler that processes heartbeat messages for a node monitor
+///
+/// This handler does not consume heartbeat messages, so other handlers can also receive them.
+pub struct NodeMonitorServiceHandler<'a, N: Node, const C: usize> {
+    service: &'a mut NodeMonitorService<N, C>,
+}
+
+impl<N, const C: usize> TransferHandler<N::Transport> for NodeMonitorServiceHandler<'_, N, C>
+where
+    N: Node,
+    <N::Transport as Transport>::TransferId: PartialEq,
+{
+    fn handle_message<N2: Node<Transport = N::Transport>>(
+        &mut self,
+        _node: &mut N2,
+        transfer: &MessageTransfer<Vec<u8>, N2::Transport>,
+    ) -> bool {
+        if transfer.header.subject != SUBJECT {
+            return false;
+        }
+        if let Some(source) = &transfer.header.source {
+            if let Ok(heartbeat) = Heartbeat::deserialize_from_bytes(&transfer.payload) {
+                self.service.handle_heartbeat(
+                    source,
+                    &transfer.header.transfer_id,
+                    transfer.header.timestamp,
+                    heartbeat,
+                );
+            }
+        }
+        false
+    }
+}
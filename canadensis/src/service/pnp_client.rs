@@ -15,6 +15,7 @@ use canadensis_data_types::uavcan::pnp::{
     node_id_allocation_data_1_0, node_id_allocation_data_2_0,
 };
 use core::convert::TryFrom;
+use core::fmt;
 use core::marker::PhantomData;
 use crc_any::CRCu64;
 
@@ -78,7 +79,6 @@ where
 }
 
 /// Error type returned by [`PnpClientService::new`].
-#[derive(Debug)]
 pub enum NewError<N: Node> {
     /// The client could not subscribe to the message subject due to a receiver error.
     Subscribe(<N::Receiver as Receiver<N::Clock>>::Error),
@@ -90,6 +90,48 @@ pub enum NewError<N: Node> {
     Publish(<N::Transmitter as Transmitter<N::Clock>>::Error),
 }
 
+impl<N> fmt::Debug for NewError<N>
+where
+    N: Node,
+    <N::Receiver as Receiver<N::Clock>>::Error: fmt::Debug,
+    <N::Transmitter as Transmitter<N::Clock>>::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewError::Subscribe(inner) => f.debug_tuple("Subscribe").field(inner).finish(),
+            NewError::OutOfMemory => f.write_str("OutOfMemory"),
+            NewError::Duplicate => f.write_str("Duplicate"),
+            NewError::Publish(inner) => f.debug_tuple("Publish").field(inner).finish(),
+        }
+    }
+}
+
+impl<N: Node> fmt::Display for NewError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewError::Subscribe(_) => f.write_str("Receiver error"),
+            NewError::OutOfMemory => f.write_str("Out of memory"),
+            NewError::Duplicate => f.write_str("Allocation subject already in use"),
+            NewError::Publish(_) => f.write_str("Transmitter error"),
+        }
+    }
+}
+
+impl<N> core::error::Error for NewError<N>
+where
+    N: Node,
+    <N::Receiver as Receiver<N::Clock>>::Error: core::error::Error + 'static,
+    <N::Transmitter as Transmitter<N::Clock>>::Error: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            NewError::Subscribe(inner) => Some(inner),
+            NewError::Publish(inner) => Some(inner),
+            NewError::OutOfMemory | NewError::Duplicate => None,
+        }
+    }
+}
+
 /// Handler for the client
 pub struct PnpClientServiceHandler<'a, N, M> {
     client: &'a mut PnpClientService<N, M>,
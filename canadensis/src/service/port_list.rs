@@ -1,3 +1,4 @@
+use core::fmt;
 use core::marker::PhantomData;
 
 use crate::core::time::milliseconds;
@@ -82,3 +83,42 @@ pub enum NewError<N: Node> {
     /// The node is anonymous
     Anonymous,
 }
+
+impl<N> fmt::Debug for NewError<N>
+where
+    N: Node,
+    <N::Transmitter as Transmitter<N::Clock>>::Error: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewError::OutOfMemory => f.write_str("OutOfMemory"),
+            NewError::Duplicate => f.write_str("Duplicate"),
+            NewError::Other(inner) => f.debug_tuple("Other").field(inner).finish(),
+            NewError::Anonymous => f.write_str("Anonymous"),
+        }
+    }
+}
+
+impl<N: Node> fmt::Display for NewError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewError::OutOfMemory => f.write_str("Out of memory"),
+            NewError::Duplicate => f.write_str("Port list subject already in use"),
+            NewError::Other(_) => f.write_str("Transmitter error"),
+            NewError::Anonymous => f.write_str("Node is anonymous"),
+        }
+    }
+}
+
+impl<N> core::error::Error for NewError<N>
+where
+    N: Node,
+    <N::Transmitter as Transmitter<N::Clock>>::Error: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            NewError::Other(inner) => Some(inner),
+            NewError::OutOfMemory | NewError::Duplicate | NewError::Anonymous => None,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::core::time::{milliseconds, MicrosecondDuration32, Microseconds32};
+use crate::core::transport::Transport;
+use crate::core::OutOfMemoryError;
+use crate::service::node_monitor::NodeMonitorService;
+use crate::Node;
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+
+/// Combines the health of several remote nodes into one system health value
+///
+/// The system health is the worst health of all monitored nodes. A monitored node that is not
+/// online counts as [`WARNING`](Health::WARNING) by default.
+///
+/// When the system health gets worse, the change takes effect immediately. When it gets better,
+/// the better value must stay the same for the recovery time (3 seconds by default) before the
+/// change takes effect. This keeps the reported health from changing rapidly when a node is
+/// on the edge between two health values.
+///
+/// This can track up to `S` monitored nodes. The local node can report the system health in its
+/// own heartbeat messages.
+pub struct SystemHealth<I, const S: usize> {
+    /// The IDs of the nodes that contribute to the system health
+    monitored: heapless::Vec<I, S>,
+    /// The health used for monitored nodes that are not online
+    missing_health: u8,
+    /// The time that a better health value must be seen before it is reported
+    recovery_time: MicrosecondDuration32,
+    /// The health currently reported
+    current: u8,
+    /// The better health value that has been seen recently, and the time when it was first seen
+    improving: Option<(u8, Microseconds32)>,
+}
+
+impl<I, const S: usize> SystemHealth<I, S>
+where
+    I: PartialEq,
+{
+    /// Creates a system health aggregator with no monitored nodes
+    pub fn new() -> Self {
+        SystemHealth {
+            monitored: heapless::Vec::new(),
+            missing_health: Health::WARNING,
+            recovery_time: milliseconds(3000),
+            current: Health::NOMINAL,
+            improving: None,
+        }
+    }
+
+    /// Adds a node to the set of monitored nodes
+    ///
+    /// This function returns an error if `S` nodes are already monitored.
+    pub fn add_node(&mut self, node_id: I) -> Result<(), OutOfMemoryError> {
+        if !self.monitored.contains(&node_id) {
+            self.monitored.push(node_id).map_err(|_| OutOfMemoryError)?;
+        }
+        Ok(())
+    }
+
+    /// Removes a node from the set of monitored nodes
+    pub fn remove_node(&mut self, node_id: &I) {
+        self.monitored.retain(|monitored| monitored != node_id);
+    }
+
+    /// Sets the health used for monitored nodes that are not online
+    pub fn set_missing_health(&mut self, health: Health) {
+        self.missing_health = health.value;
+    }
+
+    /// Sets the length of time that a better health value must be seen before it is reported
+    pub fn set_recovery_time(&mut self, recovery_time: MicrosecondDuration32) {
+        self.recovery_time = recovery_time;
+    }
+
+    /// Returns the current system health
+    pub fn health(&self) -> Health {
+        Health {
+            value: self.current,
+        }
+    }
+
+    /// Updates the system health using the latest node information from a node monitor, and
+    /// returns the new system health
+    ///
+    /// This function should be called periodically (for example, once per second).
+    pub fn update<N, const C: usize>(
+        &mut self,
+        monitor: &NodeMonitorService<N, C>,
+        now: Microseconds32,
+    ) -> Health
+    where
+        N: Node,
+        N::Transport: Transport<NodeId = I>,
+    {
+        let worst = self
+            .monitored
+            .iter()
+            .map(|node_id| match monitor.node(node_id) {
+                Some(status) => status.health().value,
+                None => self.missing_health,
+            })
+            .max()
+            .unwrap_or(Health::NOMINAL);
+        self.apply(worst, now);
+        self.health()
+    }
+
+    /// Applies hysteresis to a new worst health value
+    fn apply(&mut self, worst: u8, now: Microseconds32) {
+        if worst >= self.current {
+            // Same or worse, take effect immediately
+            self.current = worst;
+            self.improving = None;
+        } else {
+            match self.improving {
+                Some((improved, since)) if improved == worst => {
+                    if now >= since + self.recovery_time {
+                        self.current = worst;
+                        self.improving = None;
+                    }
+                }
+                _ => self.improving = Some((worst, now)),
+            }
+        }
+    }
+}
+
+impl<I, const S: usize> Default for SystemHealth<I, S>
+where
+    I: PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,257 @@
+//!
+//! Buffering of received message transfers
+//!
+//! A [`BufferedSubscriber`] is a [`TransferHandler`] that stores incoming messages on one subject
+//! so that the application can process them later. This is useful when the code that uses the
+//! messages runs less often than the code that receives frames.
+//!
+
+use alloc::vec::Vec;
+use fallible_collections::FallibleVec;
+use heapless::Deque;
+
+use crate::core::time::MicrosecondDuration32;
+use crate::core::transfer::MessageTransfer;
+use crate::core::transport::{Receiver, Transport};
+use crate::core::SubjectId;
+use crate::{Node, TransferHandler};
+
+/// What a [`BufferedSubscriber`] does when it receives a transfer and its buffer is full
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Remove the oldest transfer from the buffer to make space for the new transfer
+    DropOldest,
+    /// Discard the new transfer
+    DropNewest,
+}
+
+/// Stores up to `N` received message transfers on one subject
+pub struct BufferedSubscriber<T: Transport, const N: usize> {
+    /// The subject to receive
+    subject: SubjectId,
+    /// Received transfers, oldest first
+    transfers: Deque<MessageTransfer<Vec<u8>, T>, N>,
+    /// What to do when the buffer is full
+    policy: OverflowPolicy,
+    /// Number of transfers dropped because the buffer was full or memory allocation failed
+    dropped: u64,
+}
+
+impl<T: Transport, const N: usize> BufferedSubscriber<T, N> {
+    /// Subscribes to a subject and creates a buffer for its messages
+    ///
+    /// * `node`: The node to subscribe
+    /// * `subject`: The subject to subscribe to
+    /// * `payload_size_max`: The maximum number of payload bytes expected on this subject
+    /// * `timeout`: The maximum time between the first and last frames in a transfer
+    /// * `policy`: What to do when a transfer arrives and the buffer is full
+    pub fn new<M>(
+        node: &mut M,
+        subject: SubjectId,
+        payload_size_max: usize,
+        timeout: MicrosecondDuration32,
+        policy: OverflowPolicy,
+    ) -> Result<Self, <M::Receiver as Receiver<M::Clock>>::Error>
+    where
+        M: Node<Transport = T>,
+    {
+        node.subscribe_message(subject, payload_size_max, timeout)?;
+        Ok(BufferedSubscriber {
+            subject,
+            transfers: Deque::new(),
+            policy,
+            dropped: 0,
+        })
+    }
+
+    /// Returns the subject that this subscriber receives
+    pub fn subject(&self) -> SubjectId {
+        self.subject
+    }
+
+    /// Removes and returns the oldest buffered transfer
+    pub fn pop(&mut self) -> Option<MessageTransfer<Vec<u8>, T>> {
+        self.transfers.pop_front()
+    }
+
+    /// Returns a reference to the oldest buffered transfer
+    pub fn peek(&self) -> Option<&MessageTransfer<Vec<u8>, T>> {
+        self.transfers.front()
+    }
+
+    /// Returns the number of buffered transfers
+    pub fn len(&self) -> usize {
+        self.transfers.len()
+    }
+
+    /// Returns true if no transfers are buffered
+    pub fn is_empty(&self) -> bool {
+        self.transfers.is_empty()
+    }
+
+    /// Removes all buffered transfers
+    pub fn clear(&mut self) {
+        self.transfers.clear();
+    }
+
+    /// Returns the number of transfers that were dropped because the buffer was full or memory
+    /// allocation failed
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Sets what to do when a transfer arrives and the buffer is full
+    pub fn set_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+
+    fn increment_dropped(&mut self) {
+        self.dropped = self.dropped.wrapping_add(1);
+    }
+
+    /// Adds a transfer to the buffer, applying the overflow policy if the buffer is full
+    ///
+    /// `copy_payload` returns a copy of the transfer payload, or None if memory allocation failed.
+    /// It is called before any buffered transfer is removed, so a failed allocation drops only
+    /// the new transfer.
+    fn store<F>(&mut self, transfer: &MessageTransfer<Vec<u8>, T>, copy_payload: F)
+    where
+        F: FnOnce(&[u8]) -> Option<Vec<u8>>,
+    {
+        if self.transfers.is_full() && self.policy == OverflowPolicy::DropNewest {
+            self.increment_dropped();
+            return;
+        }
+        let payload = match copy_payload(&transfer.payload) {
+            Some(payload) => payload,
+            None => {
+                self.increment_dropped();
+                return;
+            }
+        };
+        if self.transfers.is_full() {
+            // Policy is DropOldest
+            self.transfers.pop_front();
+            self.increment_dropped();
+        }
+        // This can't fail because the deque is not full
+        let _ = self.transfers.push_back(MessageTransfer {
+            header: transfer.header.clone(),
+            loopback: transfer.loopback,
+            payload,
+        });
+    }
+}
+
+/// Copies a payload into a new vector, or returns None if memory allocation fails
+fn copy_payload(payload: &[u8]) -> Option<Vec<u8>> {
+    let mut copy = Vec::new();
+    FallibleVec::try_extend_from_slice(&mut copy, payload).ok()?;
+    Some(copy)
+}
+
+impl<T: Transport, const N: usize> TransferHandler<T> for BufferedSubscriber<T, N> {
+    fn handle_message<M: Node<Transport = T>>(
+        &mut self,
+        _node: &mut M,
+        transfer: &MessageTransfer<Vec<u8>, T>,
+    ) -> bool {
+        if transfer.header.subject != self.subject {
+            return false;
+        }
+        self.store(transfer, copy_payload);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::core::convert::TryFrom;
+    use alloc::vec;
+    use canadensis_can::{CanNodeId, CanTransferId, CanTransport};
+
+    use crate::core::time::Microseconds32;
+    use crate::core::transfer::MessageHeader;
+    use crate::core::Priority;
+
+    fn subscriber(policy: OverflowPolicy) -> BufferedSubscriber<CanTransport, 2> {
+        BufferedSubscriber {
+            subject: SubjectId::try_from(10u16).unwrap(),
+            transfers: Deque::new(),
+            policy,
+            dropped: 0,
+        }
+    }
+
+    fn transfer(transfer_id: u8) -> MessageTransfer<Vec<u8>, CanTransport> {
+        MessageTransfer {
+            header: MessageHeader {
+                timestamp: Microseconds32::from_ticks(0),
+                transfer_id: CanTransferId::try_from(transfer_id).unwrap(),
+                priority: Priority::Nominal,
+                subject: SubjectId::try_from(10u16).unwrap(),
+                source: Some(CanNodeId::try_from(3u8).unwrap()),
+            },
+            loopback: false,
+            payload: vec![transfer_id],
+        }
+    }
+
+    fn buffered(subscriber: &mut BufferedSubscriber<CanTransport, 2>) -> Vec<u8> {
+        let mut payloads = Vec::new();
+        while let Some(transfer) = subscriber.pop() {
+            payloads.extend_from_slice(&transfer.payload);
+        }
+        payloads
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let mut subscriber = subscriber(OverflowPolicy::DropOldest);
+        for transfer_id in 0..4 {
+            subscriber.store(&transfer(transfer_id), copy_payload);
+        }
+        assert_eq!(2, subscriber.dropped());
+        assert_eq!(vec![2, 3], buffered(&mut subscriber));
+    }
+
+    #[test]
+    fn drop_newest() {
+        let mut subscriber = subscriber(OverflowPolicy::DropNewest);
+        for transfer_id in 0..4 {
+            subscriber.store(&transfer(transfer_id), copy_payload);
+        }
+        assert_eq!(2, subscriber.dropped());
+        assert_eq!(vec![0, 1], buffered(&mut subscriber));
+    }
+
+    #[test]
+    fn drop_newest_does_not_copy() {
+        let mut subscriber = subscriber(OverflowPolicy::DropNewest);
+        subscriber.store(&transfer(0), copy_payload);
+        subscriber.store(&transfer(1), copy_payload);
+        subscriber.store(&transfer(2), |_| {
+            panic!("Copied a transfer that was dropped")
+        });
+        assert_eq!(1, subscriber.dropped());
+        assert_eq!(vec![0, 1], buffered(&mut subscriber));
+    }
+
+    #[test]
+    fn allocation_failure() {
+        for policy in [OverflowPolicy::DropOldest, OverflowPolicy::DropNewest] {
+            let mut subscriber = subscriber(policy);
+            subscriber.store(&transfer(0), |_| None);
+            assert_eq!(1, subscriber.dropped());
+            assert!(subscriber.is_empty());
+
+            // When the buffer is full, a failed allocation drops only the new transfer
+            subscriber.store(&transfer(1), copy_payload);
+            subscriber.store(&transfer(2), copy_payload);
+            subscriber.store(&transfer(3), |_| None);
+            assert_eq!(2, subscriber.dropped());
+            assert_eq!(vec![1, 2], buffered(&mut subscriber));
+        }
+    }
+}
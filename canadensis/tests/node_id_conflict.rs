@@ -0,0 +1,177 @@
+//! Tests detection of other nodes that use this node's ID
+
+extern crate canadensis;
+extern crate canadensis_can;
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+
+use canadensis::node::CoreNode;
+use canadensis::requester::TransferIdFixedMap;
+use canadensis::service::node_id_conflict::{ConflictPort, NodeIdConflict, NodeIdConflictService};
+use canadensis::TransferHandler;
+use canadensis_can::driver::{ReceiveDriver, TransmitDriver};
+use canadensis_can::{
+    CanNodeId, CanReceiver, CanTransferId, CanTransmitter, CanTransport, Frame, Mtu,
+};
+use canadensis_core::subscription::Subscription;
+use canadensis_core::time::{Clock, Microseconds32};
+use canadensis_core::transfer::{MessageHeader, MessageTransfer};
+use canadensis_core::{nb, OutOfMemoryError, Priority};
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+use canadensis_data_types::uavcan::node::heartbeat_1_0::{self, Heartbeat};
+use canadensis_data_types::uavcan::node::mode_1_0::Mode;
+use canadensis_encoding::Serialize;
+use std::cell::RefCell;
+use std::convert::{Infallible, TryFrom};
+
+type TestNode = CoreNode<
+    ZeroClock,
+    CanTransmitter<ZeroClock, NullDriver>,
+    CanReceiver<ZeroClock, NullDriver>,
+    TransferIdFixedMap<CanTransport, 4>,
+    NullDriver,
+    4,
+    4,
+>;
+
+/// The ID of the node under test
+const LOCAL_ID: u8 = 1;
+
+fn make_node() -> TestNode {
+    let node_id = CanNodeId::try_from(LOCAL_ID).unwrap();
+    CoreNode::new(
+        ZeroClock,
+        node_id,
+        CanTransmitter::new(Mtu::Can8),
+        CanReceiver::new(node_id, Mtu::Can8),
+        NullDriver,
+    )
+}
+
+fn heartbeat_transfer(
+    source: u8,
+    time: u32,
+    loopback: bool,
+) -> MessageTransfer<Vec<u8>, CanTransport> {
+    let heartbeat = Heartbeat {
+        uptime: 10,
+        health: Health {
+            value: Health::NOMINAL,
+        },
+        mode: Mode {
+            value: Mode::OPERATIONAL,
+        },
+        vendor_specific_status_code: 0,
+    };
+    let mut payload = vec![0u8; 7];
+    heartbeat.serialize_to_bytes(&mut payload);
+    MessageTransfer {
+        header: MessageHeader {
+            timestamp: Microseconds32::from_ticks(time),
+            transfer_id: CanTransferId::try_from(0).unwrap(),
+            priority: Priority::Nominal,
+            subject: heartbeat_1_0::SUBJECT,
+            source: Some(CanNodeId::try_from(source).unwrap()),
+        },
+        loopback,
+        payload,
+    }
+}
+
+#[test]
+fn foreign_transfer_with_local_id() {
+    let mut node = make_node();
+    let conflicts = RefCell::new(Vec::new());
+    let mut service = NodeIdConflictService::with_hook(&mut node, |conflict: &NodeIdConflict| {
+        conflicts.borrow_mut().push(conflict.clone())
+    })
+    .unwrap();
+    let time = Microseconds32::from_ticks;
+
+    // A heartbeat from another node with a different ID is not a conflict
+    assert!(!service
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 0, false)));
+    assert_eq!(0, service.conflict_count());
+
+    // A heartbeat with this node's ID that this node did not send is a conflict
+    assert!(!service
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(LOCAL_ID, 1_000_000, false)));
+    assert_eq!(1, service.conflict_count());
+    assert_eq!(Some(time(1_000_000)), service.last_conflict());
+    assert_eq!(Health::WARNING, service.health(time(2_000_000)).value);
+    assert_eq!(Health::NOMINAL, service.health(time(4_000_001)).value);
+
+    let conflicts = conflicts.borrow();
+    assert_eq!(1, conflicts.len());
+    assert_eq!(time(1_000_000), conflicts[0].timestamp);
+    assert_eq!(
+        ConflictPort::Message(heartbeat_1_0::SUBJECT),
+        conflicts[0].port
+    );
+}
+
+#[test]
+fn loopback_transfer_is_not_conflict() {
+    let mut node = make_node();
+    let mut service = NodeIdConflictService::new(&mut node).unwrap();
+
+    service
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(LOCAL_ID, 0, true));
+    assert_eq!(0, service.conflict_count());
+    assert_eq!(None, service.last_conflict());
+    assert_eq!(
+        Health::NOMINAL,
+        service.health(Microseconds32::from_ticks(0)).value
+    );
+}
+
+/// A clock that always returns zero
+struct ZeroClock;
+
+impl Clock for ZeroClock {
+    fn now(&mut self) -> Microseconds32 {
+        Microseconds32::from_ticks(0)
+    }
+}
+
+/// A driver that discards all outgoing frames and never receives anything
+struct NullDriver;
+
+impl TransmitDriver<ZeroClock> for NullDriver {
+    type Error = Infallible;
+
+    fn try_reserve(&mut self, _frames: usize) -> Result<(), OutOfMemoryError> {
+        Ok(())
+    }
+
+    fn transmit(
+        &mut self,
+        _frame: Frame,
+        _clock: &mut ZeroClock,
+    ) -> nb::Result<Option<Frame>, Self::Error> {
+        Ok(None)
+    }
+
+    fn flush(&mut self, _clock: &mut ZeroClock) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ReceiveDriver<ZeroClock> for NullDriver {
+    type Error = Infallible;
+
+    fn receive(&mut self, _clock: &mut ZeroClock) -> nb::Result<Frame, Self::Error> {
+        Err(nb::Error::WouldBlock)
+    }
+
+    fn apply_filters<S>(&mut self, _local_node: Option<CanNodeId>, _subscriptions: S)
+    where
+        S: IntoIterator<Item = Subscription>,
+    {
+    }
+
+    fn apply_accept_all(&mut self) {}
+}
@@ -0,0 +1,239 @@
+//! Tests the node monitor's tracking of remote node heartbeats
+
+extern crate canadensis;
+extern crate canadensis_can;
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+
+use canadensis::node::CoreNode;
+use canadensis::requester::TransferIdFixedMap;
+use canadensis::service::node_monitor::{NodeEvent, NodeMonitorService};
+use canadensis::service::system_health::SystemHealth;
+use canadensis::TransferHandler;
+use canadensis_can::driver::{ReceiveDriver, TransmitDriver};
+use canadensis_can::{
+    CanNodeId, CanReceiver, CanTransferId, CanTransmitter, CanTransport, Frame, Mtu,
+};
+use canadensis_core::subscription::Subscription;
+use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
+use canadensis_core::transfer::{MessageHeader, MessageTransfer};
+use canadensis_core::{nb, OutOfMemoryError, Priority};
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+use canadensis_data_types::uavcan::node::heartbeat_1_0::{self, Heartbeat};
+use canadensis_data_types::uavcan::node::mode_1_0::Mode;
+use canadensis_encoding::Serialize;
+use std::convert::{Infallible, TryFrom};
+
+type TestNode = CoreNode<
+    ZeroClock,
+    CanTransmitter<ZeroClock, NullDriver>,
+    CanReceiver<ZeroClock, NullDriver>,
+    TransferIdFixedMap<CanTransport, 4>,
+    NullDriver,
+    4,
+    4,
+>;
+
+fn make_node() -> TestNode {
+    let node_id = CanNodeId::try_from(1_u8).unwrap();
+    CoreNode::new(
+        ZeroClock,
+        node_id,
+        CanTransmitter::new(Mtu::Can8),
+        CanReceiver::new(node_id, Mtu::Can8),
+        NullDriver,
+    )
+}
+
+fn heartbeat_transfer(
+    source: u8,
+    transfer_id: u8,
+    time: u32,
+    uptime: u32,
+) -> MessageTransfer<Vec<u8>, CanTransport> {
+    heartbeat_transfer_with_health(source, transfer_id, time, uptime, Health::NOMINAL)
+}
+
+fn heartbeat_transfer_with_health(
+    source: u8,
+    transfer_id: u8,
+    time: u32,
+    uptime: u32,
+    health: u8,
+) -> MessageTransfer<Vec<u8>, CanTransport> {
+    let heartbeat = Heartbeat {
+        uptime,
+        health: Health { value: health },
+        mode: Mode {
+            value: Mode::OPERATIONAL,
+        },
+        vendor_specific_status_code: 0,
+    };
+    let mut payload = vec![0u8; 7];
+    heartbeat.serialize_to_bytes(&mut payload);
+    MessageTransfer {
+        header: MessageHeader {
+            timestamp: Microseconds32::from_ticks(time),
+            transfer_id: CanTransferId::try_from(transfer_id).unwrap(),
+            priority: Priority::Nominal,
+            subject: heartbeat_1_0::SUBJECT,
+            source: Some(CanNodeId::try_from(source).unwrap()),
+        },
+        loopback: false,
+        payload,
+    }
+}
+
+#[test]
+fn node_online_restart_offline() {
+    let mut node = make_node();
+    let mut monitor = NodeMonitorService::<_, 4>::new(&mut node).unwrap();
+    let node_8 = CanNodeId::try_from(8_u8).unwrap();
+
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 10, 0, 100));
+    assert_eq!(Some(NodeEvent::Online(node_8)), monitor.pop_event());
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 11, 1_000_000, 101));
+    assert_eq!(None, monitor.pop_event());
+
+    // Uptime went backwards
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 12, 2_000_000, 0));
+    assert_eq!(Some(NodeEvent::Restarted(node_8)), monitor.pop_event());
+    // Transfer ID jumped
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 30, 3_000_000, 1));
+    assert_eq!(Some(NodeEvent::Restarted(node_8)), monitor.pop_event());
+    assert_eq!(2, monitor.node(&node_8).unwrap().restarts());
+
+    monitor.check_timeouts(Microseconds32::from_ticks(5_000_000));
+    assert_eq!(None, monitor.pop_event());
+    monitor.check_timeouts(Microseconds32::from_ticks(6_000_001));
+    assert_eq!(Some(NodeEvent::Offline(node_8)), monitor.pop_event());
+    assert_eq!(0, monitor.nodes().count());
+}
+
+#[test]
+fn lost_heartbeats_not_restart() {
+    let mut node = make_node();
+    let mut monitor = NodeMonitorService::<_, 4>::new(&mut node).unwrap();
+    monitor.set_offline_timeout(MicrosecondDuration32::from_ticks(10_000_000));
+    let node_8 = CanNodeId::try_from(8_u8).unwrap();
+
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 0, 0, 100));
+    assert_eq!(Some(NodeEvent::Online(node_8)), monitor.pop_event());
+    // Seven heartbeats were lost, but the node is still within the offline timeout
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 8, 8_000_000, 108));
+    monitor.check_timeouts(Microseconds32::from_ticks(8_000_000));
+    assert_eq!(None, monitor.pop_event());
+    assert_eq!(0, monitor.node(&node_8).unwrap().restarts());
+
+    // Without calling check_timeouts, the allowed gap grows with the time since the last
+    // heartbeat
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(8, 28, 28_000_000, 128));
+    assert_eq!(None, monitor.pop_event());
+    assert_eq!(0, monitor.node(&node_8).unwrap().restarts());
+}
+
+#[test]
+fn system_health_hysteresis() {
+    let mut node = make_node();
+    let mut monitor = NodeMonitorService::<_, 4>::new(&mut node).unwrap();
+    let mut system = SystemHealth::<CanNodeId, 2>::new();
+    system.add_node(CanNodeId::try_from(8_u8).unwrap()).unwrap();
+    system.add_node(CanNodeId::try_from(9_u8).unwrap()).unwrap();
+    let time = Microseconds32::from_ticks;
+
+    // Node 9 is missing
+    monitor.handler().handle_message(
+        &mut node,
+        &heartbeat_transfer_with_health(8, 0, 0, 0, Health::ADVISORY),
+    );
+    assert_eq!(Health::WARNING, system.update(&monitor, time(0)).value);
+
+    // Node 9 appears, but the better health only takes effect after the recovery time
+    monitor
+        .handler()
+        .handle_message(&mut node, &heartbeat_transfer(9, 0, 1_000_000, 0));
+    assert_eq!(
+        Health::WARNING,
+        system.update(&monitor, time(1_000_000)).value
+    );
+    assert_eq!(
+        Health::WARNING,
+        system.update(&monitor, time(3_000_000)).value
+    );
+    assert_eq!(
+        Health::ADVISORY,
+        system.update(&monitor, time(4_000_000)).value
+    );
+
+    // Worse health takes effect immediately
+    monitor.handler().handle_message(
+        &mut node,
+        &heartbeat_transfer_with_health(9, 1, 4_500_000, 1, Health::CAUTION),
+    );
+    assert_eq!(
+        Health::CAUTION,
+        system.update(&monitor, time(4_500_000)).value
+    );
+}
+
+/// A clock that always returns zero
+struct ZeroClock;
+
+impl Clock for ZeroClock {
+    fn now(&mut self) -> Microseconds32 {
+        Microseconds32::from_ticks(0)
+    }
+}
+
+/// A driver that discards all outgoing frames and never receives anything
+struct NullDriver;
+
+impl TransmitDriver<ZeroClock> for NullDriver {
+    type Error = Infallible;
+
+    fn try_reserve(&mut self, _frames: usize) -> Result<(), OutOfMemoryError> {
+        Ok(())
+    }
+
+    fn transmit(
+        &mut self,
+        _frame: Frame,
+        _clock: &mut ZeroClock,
+    ) -> nb::Result<Option<Frame>, Self::Error> {
+        Ok(None)
+    }
+
+    fn flush(&mut self, _clock: &mut ZeroClock) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ReceiveDriver<ZeroClock> for NullDriver {
+    type Error = Infallible;
+
+    fn receive(&mut self, _clock: &mut ZeroClock) -> nb::Result<Frame, Self::Error> {
+        Err(nb::Error::WouldBlock)
+    }
+
+    fn apply_filters<S>(&mut self, _local_node: Option<CanNodeId>, _subscriptions: S)
+    where
+        S: IntoIterator<Item = Subscription>,
+    {
+    }
+
+    fn apply_accept_all(&mut self) {}
+}
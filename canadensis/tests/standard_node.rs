@@ -0,0 +1,468 @@
+//! Tests bringing up a standard node and checking the messages and responses that it sends
+
+// The ExecuteCommand 1.2 types are deprecated, but version 1.2 is the one that StandardNode serves.
+#![allow(deprecated)]
+
+extern crate canadensis;
+extern crate canadensis_can;
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+extern crate canadensis_udp;
+extern crate heapless;
+
+use canadensis::node::{CoreNode, StandardNode, StandardNodeConfig, StandardNodeError};
+use canadensis::register::basic::SimpleRegister;
+use canadensis::register::standard::StandardRegisters;
+use canadensis::register::RegisterBlock;
+use canadensis::requester::TransferIdFixedMap;
+use canadensis::service::{pnp_client, port_list};
+use canadensis::{Node, ResponseToken, TransferHandler};
+use canadensis_can::driver::{ReceiveDriver, TransmitDriver};
+use canadensis_can::{CanNodeId, CanReceiver, CanTransmitter, CanTransport, Frame, Mtu};
+use canadensis_core::session::SessionDynamicMap;
+use canadensis_core::subscription::Subscription;
+use canadensis_core::time::{milliseconds, Clock, Microseconds32};
+use canadensis_core::transfer::{MessageTransfer, ServiceTransfer};
+use canadensis_core::{nb, OutOfMemoryError, Priority, SubjectId};
+use canadensis_data_types::uavcan::node::execute_command_1_2::{self, ExecuteCommandResponse};
+use canadensis_data_types::uavcan::node::get_info_1_0::{self, GetInfoRequest, GetInfoResponse};
+use canadensis_data_types::uavcan::node::health_1_0::Health;
+use canadensis_data_types::uavcan::node::heartbeat_1_0::{self, Heartbeat};
+use canadensis_data_types::uavcan::node::mode_1_0::Mode;
+use canadensis_data_types::uavcan::node::port::list_1_0::{self, List};
+use canadensis_data_types::uavcan::node::port::subject_id_list_1_0::SubjectIDList;
+use canadensis_data_types::uavcan::node::version_1_0::Version;
+use canadensis_data_types::uavcan::primitive::array::natural16_1_0::Natural16;
+use canadensis_data_types::uavcan::register::value_1_0::Value;
+use canadensis_data_types::uavcan::register::{access_1_0, list_1_0 as register_list};
+use canadensis_encoding::Deserialize;
+use canadensis_udp::driver::UdpSocket;
+use canadensis_udp::{
+    UdpNodeId, UdpReceiver, UdpSessionData, UdpTransferId, UdpTransmitter, UdpTransport,
+};
+use core::net::{Ipv4Addr, SocketAddrV4};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::{Infallible, TryFrom};
+use std::rc::Rc;
+
+type TestNode<const P: usize> = CoreNode<
+    ZeroClock,
+    CanTransmitter<ZeroClock, BusDriver>,
+    CanReceiver<ZeroClock, BusDriver>,
+    TransferIdFixedMap<CanTransport, 4>,
+    BusDriver,
+    P,
+    4,
+>;
+
+type UdpTestNode = CoreNode<
+    ZeroClock,
+    UdpTransmitter<NullSocket, 1472>,
+    UdpReceiver<
+        ZeroClock,
+        SessionDynamicMap<UdpNodeId, UdpTransferId, UdpSessionData>,
+        NullSocket,
+        1472,
+    >,
+    TransferIdFixedMap<UdpTransport, 4>,
+    NullSocket,
+    2,
+    4,
+>;
+
+#[derive(RegisterBlock)]
+struct AppRegisters {
+    gain: SimpleRegister<u8>,
+}
+
+fn node_info() -> GetInfoResponse {
+    GetInfoResponse {
+        protocol_version: Version { major: 1, minor: 0 },
+        hardware_version: Version { major: 0, minor: 0 },
+        software_version: Version { major: 2, minor: 1 },
+        software_vcs_revision_id: 0,
+        unique_id: [7; 16],
+        name: heapless::Vec::from_slice(b"org.example.standard").unwrap(),
+        software_image_crc: heapless::Vec::new(),
+        certificate_of_authenticity: heapless::Vec::new(),
+    }
+}
+
+fn bad_command(_command: u16, _parameter: &[u8]) -> u8 {
+    ExecuteCommandResponse::STATUS_BAD_COMMAND
+}
+
+#[test]
+fn heartbeat_get_info_port_list() {
+    let to_node = Bus::default();
+    let to_client = Bus::default();
+    let node_id = CanNodeId::try_from(5_u8).unwrap();
+    let client_id = CanNodeId::try_from(6_u8).unwrap();
+
+    let core_node: TestNode<4> = CoreNode::new_anonymous(
+        ZeroClock,
+        CanTransmitter::new(Mtu::Can8),
+        CanReceiver::new_anonymous(Mtu::Can8),
+        BusDriver::new(&to_node, &to_client),
+    );
+    let mut standard_registers = StandardRegisters::<2>::new();
+    standard_registers
+        .register_by_name_mut("uavcan.node.id")
+        .unwrap()
+        .write(&Value::Natural16(Natural16 {
+            value: heapless::Vec::from_slice(&[5]).unwrap(),
+        }))
+        .unwrap();
+    let app_registers = AppRegisters {
+        gain: SimpleRegister::with_value("app.gain", true, true, 3),
+    };
+    let config = StandardNodeConfig {
+        node_id: None,
+        node_info: node_info(),
+    };
+    let mut node: StandardNode<_, _, _, 2> = StandardNode::new(
+        core_node,
+        config,
+        standard_registers,
+        app_registers,
+        bad_command,
+    )
+    .unwrap();
+    // The node ID comes from the uavcan.node.id register
+    assert_eq!(Some(node_id), node.node().node_id());
+    assert!(node.is_running());
+    assert_eq!(
+        "app.gain",
+        node.registers()
+            .unwrap()
+            .register_by_index(0)
+            .unwrap()
+            .name()
+    );
+    assert_eq!(
+        "uavcan.node.id",
+        node.standard_registers()
+            .unwrap()
+            .register_by_index(0)
+            .unwrap()
+            .name()
+    );
+
+    let mut client: TestNode<4> = CoreNode::new(
+        ZeroClock,
+        client_id,
+        CanTransmitter::new(Mtu::Can8),
+        CanReceiver::new(client_id, Mtu::Can8),
+        BusDriver::new(&to_client, &to_node),
+    );
+    client
+        .subscribe_message(heartbeat_1_0::SUBJECT, 7, milliseconds(1000))
+        .unwrap();
+    client
+        .subscribe_message(list_1_0::SUBJECT, 2194, milliseconds(1000))
+        .unwrap();
+    let get_info_token = client
+        .start_sending_requests::<GetInfoRequest>(
+            get_info_1_0::SERVICE,
+            milliseconds(1000),
+            313,
+            Priority::Nominal,
+        )
+        .unwrap();
+
+    // The first call publishes a heartbeat and the port list
+    node.set_mode(Mode {
+        value: Mode::MAINTENANCE,
+    });
+    node.run_per_second_tasks().unwrap();
+    node.node_mut().flush().unwrap();
+
+    client
+        .send_request(&get_info_token, &GetInfoRequest {}, node_id)
+        .unwrap();
+    client.flush().unwrap();
+    node.receive(&mut Collector::default()).unwrap();
+    node.node_mut().flush().unwrap();
+
+    let mut collector = Collector::default();
+    while !to_client.borrow().is_empty() {
+        client.receive(&mut collector).unwrap();
+    }
+
+    let heartbeat = collector.message(heartbeat_1_0::SUBJECT).unwrap();
+    let heartbeat = Heartbeat::deserialize_from_bytes(heartbeat).unwrap();
+    assert_eq!(Mode::MAINTENANCE, heartbeat.mode.value);
+    assert_eq!(Health::NOMINAL, heartbeat.health.value);
+
+    let list = collector.message(list_1_0::SUBJECT).unwrap();
+    let list = List::deserialize_from_bytes(list).unwrap();
+    match list.publishers {
+        SubjectIDList::SparseList(publishers) => {
+            let mut publishers: Vec<u16> = publishers.iter().map(|id| id.value).collect();
+            publishers.sort_unstable();
+            assert_eq!(
+                vec![
+                    u16::from(heartbeat_1_0::SUBJECT),
+                    u16::from(list_1_0::SUBJECT)
+                ],
+                publishers
+            );
+        }
+        _ => panic!("Publishers are not in a sparse list"),
+    }
+    for service in [
+        get_info_1_0::SERVICE,
+        execute_command_1_2::SERVICE,
+        access_1_0::SERVICE,
+        register_list::SERVICE,
+    ] {
+        assert!(list.servers.mask.get(u16::from(service).into()));
+    }
+
+    assert_eq!(1, collector.responses.len());
+    let response = GetInfoResponse::deserialize_from_bytes(&collector.responses[0]).unwrap();
+    assert_eq!(node_info().unique_id, response.unique_id);
+    assert_eq!(&b"org.example.standard"[..], &response.name[..]);
+
+    // The port list is published again after 10 seconds
+    for _ in 0..9 {
+        node.run_per_second_tasks().unwrap();
+    }
+    node.node_mut().flush().unwrap();
+    let mut collector = Collector::default();
+    while !to_client.borrow().is_empty() {
+        client.receive(&mut collector).unwrap();
+    }
+    assert_eq!(9, collector.count(heartbeat_1_0::SUBJECT));
+    assert_eq!(0, collector.count(list_1_0::SUBJECT));
+    node.run_per_second_tasks().unwrap();
+    node.node_mut().flush().unwrap();
+    while !to_client.borrow().is_empty() {
+        client.receive(&mut collector).unwrap();
+    }
+    assert_eq!(1, collector.count(list_1_0::SUBJECT));
+}
+
+#[test]
+fn port_list_error() {
+    let bus = Bus::default();
+    let node_id = CanNodeId::try_from(5_u8).unwrap();
+    let mut core_node: TestNode<4> = CoreNode::new(
+        ZeroClock,
+        node_id,
+        CanTransmitter::new(Mtu::Can8),
+        CanReceiver::new(node_id, Mtu::Can8),
+        BusDriver::new(&bus, &bus),
+    );
+    core_node
+        .start_publishing(list_1_0::SUBJECT, milliseconds(1000), Priority::Optional)
+        .unwrap();
+    let config = StandardNodeConfig {
+        node_id: None,
+        node_info: node_info(),
+    };
+    let result: Result<StandardNode<_, _, _, 0>, _> = StandardNode::new(
+        core_node,
+        config,
+        StandardRegisters::<0>::new(),
+        (),
+        bad_command,
+    );
+    assert!(matches!(
+        result,
+        Err(StandardNodeError::PortList(port_list::NewError::Duplicate))
+    ));
+}
+
+#[test]
+fn pnp_out_of_memory() {
+    // A plug-and-play message does not fit into one classic CAN frame, so this uses UDP
+    let mut core_node: UdpTestNode = CoreNode::new_anonymous(
+        ZeroClock,
+        UdpTransmitter::new(canadensis_udp::DEFAULT_PORT),
+        UdpReceiver::new(None, Ipv4Addr::LOCALHOST),
+        NullSocket,
+    );
+    // Use one of the two publisher slots, so the plug-and-play client can't get one
+    core_node
+        .start_publishing(
+            SubjectId::try_from(100_u16).unwrap(),
+            milliseconds(1000),
+            Priority::Nominal,
+        )
+        .unwrap();
+    let config = StandardNodeConfig {
+        node_id: None,
+        node_info: node_info(),
+    };
+    let result: Result<StandardNode<_, _, _, 0>, _> = StandardNode::new(
+        core_node,
+        config,
+        StandardRegisters::<0>::new(),
+        (),
+        bad_command,
+    );
+    assert!(matches!(
+        result,
+        Err(StandardNodeError::Pnp(pnp_client::NewError::OutOfMemory))
+    ));
+}
+
+/// Collects the payloads of incoming messages and responses
+#[derive(Default)]
+struct Collector {
+    messages: Vec<(SubjectId, Vec<u8>)>,
+    responses: Vec<Vec<u8>>,
+}
+
+impl Collector {
+    fn message(&self, subject: SubjectId) -> Option<&[u8]> {
+        self.messages
+            .iter()
+            .find(|(message_subject, _)| *message_subject == subject)
+            .map(|(_, payload)| &payload[..])
+    }
+
+    fn count(&self, subject: SubjectId) -> usize {
+        self.messages
+            .iter()
+            .filter(|(message_subject, _)| *message_subject == subject)
+            .count()
+    }
+}
+
+impl TransferHandler<CanTransport> for Collector {
+    fn handle_message<N: Node<Transport = CanTransport>>(
+        &mut self,
+        _node: &mut N,
+        transfer: &MessageTransfer<Vec<u8>, CanTransport>,
+    ) -> bool {
+        self.messages
+            .push((transfer.header.subject, transfer.payload.clone()));
+        true
+    }
+
+    fn handle_request<N: Node<Transport = CanTransport>>(
+        &mut self,
+        _node: &mut N,
+        _token: ResponseToken<CanTransport>,
+        _transfer: &ServiceTransfer<Vec<u8>, CanTransport>,
+    ) -> bool {
+        false
+    }
+
+    fn handle_response<N: Node<Transport = CanTransport>>(
+        &mut self,
+        _node: &mut N,
+        transfer: &ServiceTransfer<Vec<u8>, CanTransport>,
+    ) -> bool {
+        self.responses.push(transfer.payload.clone());
+        true
+    }
+}
+
+/// A clock that always returns zero
+struct ZeroClock;
+
+impl Clock for ZeroClock {
+    fn now(&mut self) -> Microseconds32 {
+        Microseconds32::from_ticks(0)
+    }
+}
+
+/// Frames travelling in one direction
+type Bus = Rc<RefCell<VecDeque<Frame>>>;
+
+/// A driver that receives frames from one bus and transmits frames to another
+struct BusDriver {
+    incoming: Bus,
+    outgoing: Bus,
+}
+
+impl BusDriver {
+    fn new(incoming: &Bus, outgoing: &Bus) -> Self {
+        BusDriver {
+            incoming: Rc::clone(incoming),
+            outgoing: Rc::clone(outgoing),
+        }
+    }
+}
+
+impl TransmitDriver<ZeroClock> for BusDriver {
+    type Error = Infallible;
+
+    fn try_reserve(&mut self, _frames: usize) -> Result<(), OutOfMemoryError> {
+        Ok(())
+    }
+
+    fn transmit(
+        &mut self,
+        frame: Frame,
+        _clock: &mut ZeroClock,
+    ) -> nb::Result<Option<Frame>, Self::Error> {
+        self.outgoing.borrow_mut().push_back(frame);
+        Ok(None)
+    }
+
+    fn flush(&mut self, _clock: &mut ZeroClock) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ReceiveDriver<ZeroClock> for BusDriver {
+    type Error = Infallible;
+
+    fn receive(&mut self, _clock: &mut ZeroClock) -> nb::Result<Frame, Self::Error> {
+        self.incoming
+            .borrow_mut()
+            .pop_front()
+            .ok_or(nb::Error::WouldBlock)
+    }
+
+    fn apply_filters<S>(&mut self, _local_node: Option<CanNodeId>, _subscriptions: S)
+    where
+        S: IntoIterator<Item = Subscription>,
+    {
+    }
+
+    fn apply_accept_all(&mut self) {}
+}
+
+/// A UDP socket that discards all outgoing packets and never receives anything
+struct NullSocket;
+
+impl UdpSocket for NullSocket {
+    type Error = Infallible;
+
+    fn local_addr(&self) -> Result<SocketAddrV4, Self::Error> {
+        Ok(SocketAddrV4::new(
+            Ipv4Addr::LOCALHOST,
+            canadensis_udp::DEFAULT_PORT,
+        ))
+    }
+
+    fn join_multicast_v4(
+        &mut self,
+        _multiaddr: &Ipv4Addr,
+        _interface: &Ipv4Addr,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn leave_multicast_v4(
+        &mut self,
+        _multiaddr: &Ipv4Addr,
+        _interface: &Ipv4Addr,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_to(&mut self, data: &[u8], _destination: SocketAddrV4) -> Result<usize, Self::Error> {
+        Ok(data.len())
+    }
+
+    fn recv(&mut self, _buffer: &mut [u8]) -> Result<usize, nb::Error<Self::Error>> {
+        Err(nb::Error::WouldBlock)
+    }
+}
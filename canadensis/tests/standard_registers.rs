@@ -0,0 +1,127 @@
+extern crate canadensis;
+extern crate canadensis_data_types;
+extern crate heapless;
+
+use canadensis::register::basic::SimpleRegister;
+use canadensis::register::standard::{PortKind, StandardRegisters, UNSET_ID};
+use canadensis::register::RegisterBlock;
+use canadensis_data_types::uavcan::primitive::array::natural16_1_0::Natural16;
+use canadensis_data_types::uavcan::register::value_1_0::Value;
+
+fn natural16(value: u16) -> Value {
+    Value::Natural16(Natural16 {
+        value: heapless::Vec::from_slice(&[value]).unwrap(),
+    })
+}
+
+#[test]
+fn standard_register_names() {
+    let mut registers = StandardRegisters::<2>::new();
+    registers
+        .add_port(
+            PortKind::Publisher,
+            "temperature",
+            "uavcan.si.unit.temperature.Scalar.1.0",
+            Some(100),
+        )
+        .unwrap();
+    registers.enable_can_bitrate(1_000_000, 1_000_000);
+
+    let names: Vec<&str> = (0..)
+        .map_while(|i| registers.register_by_index(i))
+        .map(|register| register.name())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "uavcan.node.id",
+            "uavcan.node.description",
+            "uavcan.can.bitrate",
+            "uavcan.pub.temperature.id",
+            "uavcan.pub.temperature.type",
+        ]
+    );
+}
+
+#[test]
+fn port_id_applied_on_restart() {
+    let mut registers = StandardRegisters::<2>::new();
+    registers
+        .add_port(
+            PortKind::Subscriber,
+            "setpoint",
+            "uavcan.si.unit.angle.Scalar.1.0",
+            None,
+        )
+        .unwrap();
+    assert_eq!(None, registers.subject_id(PortKind::Subscriber, "setpoint"));
+
+    let id_register = registers
+        .register_by_name_mut("uavcan.sub.setpoint.id")
+        .unwrap();
+    // Subject IDs above 8191 are not valid
+    assert!(id_register.write(&natural16(9000)).is_err());
+    id_register.write(&natural16(1234)).unwrap();
+    assert_eq!(
+        1234,
+        registers
+            .port(PortKind::Subscriber, "setpoint")
+            .unwrap()
+            .id()
+            .configured()
+    );
+    // The new ID is not active until the node restarts
+    assert!(registers.has_pending_changes());
+    assert_eq!(
+        UNSET_ID,
+        registers
+            .port(PortKind::Subscriber, "setpoint")
+            .unwrap()
+            .id()
+            .active()
+    );
+}
+
+#[test]
+fn combined_blocks() {
+    #[derive(RegisterBlock)]
+    struct AppRegisters {
+        gain: SimpleRegister<u8>,
+    }
+    let mut combined = (
+        StandardRegisters::<1>::new(),
+        AppRegisters {
+            gain: SimpleRegister::with_value("app.gain", true, true, 3),
+        },
+    );
+    assert_eq!(
+        "uavcan.node.id",
+        combined.register_by_index(0).unwrap().name()
+    );
+    assert_eq!("app.gain", combined.register_by_index(2).unwrap().name());
+    assert_eq!(
+        "app.gain",
+        combined.register_by_index_mut(2).unwrap().name()
+    );
+    assert!(combined.register_by_index(3).is_none());
+    assert_eq!(3, combined.register_count());
+    assert!(combined.register_by_name_mut("app.gain").is_some());
+    assert!(combined
+        .register_by_name_mut("uavcan.node.description")
+        .is_some());
+}
+
+#[test]
+fn register_name_length() {
+    // "uavcan.pub." + port name + ".type" must fit in uavcan.register.Name (255 bytes)
+    let mut registers = StandardRegisters::<2>::new();
+    let longest: &'static str = Box::leak("a".repeat(239).into_boxed_str());
+    let too_long: &'static str = Box::leak("b".repeat(240).into_boxed_str());
+    registers
+        .add_port(PortKind::Publisher, longest, "test.Type.1.0", None)
+        .unwrap();
+    assert!(registers
+        .add_port(PortKind::Publisher, too_long, "test.Type.1.0", None)
+        .is_err());
+    assert_eq!(4, registers.register_count());
+}
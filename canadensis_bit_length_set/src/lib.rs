@@ -1,4 +1,3 @@
-extern crate itertools;
 extern crate num_integer;
 
 mod operator;
@@ -158,7 +157,8 @@ impl BitLengthSet {
 
     /// Expands this bit length set and returns a set with all enclosed values
     ///
-    /// For some bit length sets, especially when large sets are concatenated, this may be slow.
+    /// The time and memory required are proportional to the number of values in the expanded set,
+    /// so this may be slow for sets that contain many different lengths.
     ///
     /// # Examples
     ///
@@ -1,10 +1,6 @@
-use std::cmp::min;
 use std::collections::BTreeSet;
-use std::convert::TryInto;
 use std::ops::RangeToInclusive;
 
-use itertools::Itertools;
-
 #[derive(Debug, Clone)]
 pub enum Operator {
     /// A fixed set of bit lengths
@@ -71,7 +67,7 @@ impl Operator {
                 let max = self.max();
                 let lcm = num_integer::lcm(u64::from(*alignment), divisor);
                 child
-                    .modulo(divisor)
+                    .modulo(lcm)
                     .into_iter()
                     .map(|value| {
                         debug_assert!(value <= max);
@@ -80,50 +76,21 @@ impl Operator {
                     })
                     .collect()
             }
-            Operator::Concatenate { children } => {
-                let child_mods: Vec<BTreeSet<u64>> =
-                    children.iter().map(|child| child.modulo(divisor)).collect();
-                child_mods
-                    .iter()
-                    .map(|mods| mods.iter().copied())
-                    .multi_cartesian_product()
-                    // For each possible combination of child sizes, add them up and then modulo
-                    .map(|values: Vec<u64>| values.into_iter().sum())
-                    .map(|sum: u64| sum % divisor)
-                    .collect()
-            }
+            Operator::Concatenate { children } => children
+                .iter()
+                .map(|child| child.modulo(divisor))
+                .fold(zero_set(), |sum, child_mods| {
+                    sumset_modulo(&sum, &child_mods, divisor)
+                }),
             Operator::Repeat { child, count } => {
-                let equivalent_count = min(*count, divisor + *count % divisor);
-                debug_assert_eq!(*count % divisor, equivalent_count % divisor);
-
-                child
-                    .modulo(divisor)
-                    .into_iter()
-                    .combinations_with_replacement(
-                        equivalent_count
-                            .try_into()
-                            .expect("equivalent_count too large for usize"),
-                    )
-                    .map(|values| values.into_iter().sum::<u64>() % divisor)
-                    .collect()
+                repeat_modulo(child.modulo(divisor), *count, divisor)
             }
             Operator::RangeRepeat { child, count } => {
-                let single = child.modulo(divisor);
-                let count_max = count.end;
-                let equivalent_count_max = min(count_max, divisor + count_max % divisor);
-                debug_assert_eq!(count_max % divisor, equivalent_count_max % divisor);
-
-                (0..=equivalent_count_max)
-                    .flat_map(|k| {
-                        single
-                            .iter()
-                            .copied()
-                            .combinations_with_replacement(
-                                k.try_into().expect("k too large for usize"),
-                            )
-                            .map(|values| values.into_iter().sum::<u64>() % divisor)
-                    })
-                    .collect()
+                // Repeating 0..=n times is the same as repeating n times with an extra
+                // zero-length option
+                let mut single = child.modulo(divisor);
+                single.insert(0);
+                repeat_modulo(single, count.end, divisor)
             }
             Operator::Union { children } => children
                 .iter()
@@ -134,44 +101,46 @@ impl Operator {
 
     /// Expands this bit length set and returns a set with all enclosed values
     pub fn expand(&self) -> BTreeSet<u64> {
+        self.expand_sorted().into_iter().collect()
+    }
+
+    /// Expands this bit length set and returns all enclosed values in ascending order, without
+    /// duplicates
+    ///
+    /// Concatenations and repetitions are expanded by adding up two sets at a time, so the time
+    /// required depends on the number of distinct values in the result instead of the number of
+    /// combinations of child values.
+    fn expand_sorted(&self) -> Vec<u64> {
         match self {
-            Operator::Leaf(values) => values.clone(),
-            Operator::Padding { child, alignment } => child
-                .expand()
-                .into_iter()
-                .map(|length| align_up(length, u64::from(*alignment)))
-                .collect(),
-            Operator::Concatenate { children } => {
-                let child_values: Vec<BTreeSet<u64>> =
-                    children.iter().map(Operator::expand).collect();
-                child_values
-                    .iter()
-                    .multi_cartesian_product()
-                    .map(|child_sizes| child_sizes.into_iter().sum())
-                    .collect()
+            Operator::Leaf(values) => values.iter().copied().collect(),
+            Operator::Padding { child, alignment } => {
+                let mut values: Vec<u64> = child
+                    .expand_sorted()
+                    .into_iter()
+                    .map(|length| align_up(length, u64::from(*alignment)))
+                    .collect();
+                values.dedup();
+                values
             }
-            Operator::Repeat { child, count } => child
-                .expand()
-                .into_iter()
-                .combinations_with_replacement(
-                    (*count).try_into().expect("count too large for usize"),
-                )
-                .map(|child_sizes| child_sizes.into_iter().sum())
-                .collect(),
+            Operator::Concatenate { children } => children
+                .iter()
+                .map(Operator::expand_sorted)
+                .fold(vec![0], |sum, child_values| sumset(&sum, &child_values)),
+            Operator::Repeat { child, count } => repeat(child.expand_sorted(), *count),
             Operator::RangeRepeat { child, count } => {
-                let ch = child.expand();
-                (0..=count.end)
-                    .flat_map(|k| {
-                        ch.iter()
-                            .copied()
-                            .combinations_with_replacement(
-                                k.try_into().expect("k too large for usize"),
-                            )
-                            .map(|child_sizes| child_sizes.into_iter().sum())
-                    })
-                    .collect()
+                let mut single = child.expand_sorted();
+                if single.first() != Some(&0) {
+                    single.insert(0, 0);
+                }
+                repeat(single, count.end)
+            }
+            Operator::Union { children } => {
+                let mut values: Vec<u64> =
+                    children.iter().flat_map(Operator::expand_sorted).collect();
+                values.sort_unstable();
+                values.dedup();
+                values
             }
-            Operator::Union { children } => children.iter().flat_map(Operator::expand).collect(),
         }
     }
 
@@ -260,6 +229,118 @@ impl Operator {
     }
 }
 
+/// Returns a set that contains only zero, which is the identity for `sumset_modulo`
+fn zero_set() -> BTreeSet<u64> {
+    let mut set = BTreeSet::new();
+    set.insert(0);
+    set
+}
+
+/// Returns the set of all sums (modulo `divisor`) of one value from `a` and one value from `b`
+fn sumset_modulo(a: &BTreeSet<u64>, b: &BTreeSet<u64>, divisor: u64) -> BTreeSet<u64> {
+    a.iter()
+        .flat_map(|a_value| b.iter().map(move |b_value| (a_value + b_value) % divisor))
+        .collect()
+}
+
+/// Returns the set of all sums (modulo `divisor`) of `count` values from `set`
+///
+/// This uses repeated squaring, so it needs only about `2 * log2(count)` calls to
+/// `sumset_modulo`.
+fn repeat_modulo(mut power: BTreeSet<u64>, mut count: u64, divisor: u64) -> BTreeSet<u64> {
+    let mut result = zero_set();
+    while count != 0 {
+        if count & 1 == 1 {
+            result = sumset_modulo(&result, &power, divisor);
+        }
+        count >>= 1;
+        if count != 0 {
+            power = sumset_modulo(&power, &power, divisor);
+        }
+    }
+    result
+}
+
+/// Returns the set of all sums of `count` values from `values` (with repetition)
+///
+/// `values` must be sorted and must not contain duplicates. The returned values are sorted
+/// and do not contain duplicates.
+fn repeat(mut power: Vec<u64>, mut count: u64) -> Vec<u64> {
+    let mut result = vec![0];
+    while count != 0 {
+        if count & 1 == 1 {
+            result = sumset(&result, &power);
+        }
+        count >>= 1;
+        if count != 0 {
+            power = sumset(&power, &power);
+        }
+    }
+    result
+}
+
+/// Returns the set of all sums of one value from `a` and one value from `b`
+///
+/// `a` and `b` must be sorted and must not contain duplicates. The returned values are sorted
+/// and do not contain duplicates.
+fn sumset(a: &[u64], b: &[u64]) -> Vec<u64> {
+    // Put the shorter set in `small`
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let (small_min, small_max) = match (small.first(), small.last()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => return Vec::new(),
+    };
+    let (large_min, large_max) = (large[0], large[large.len() - 1]);
+
+    // When the values are close together, a bitmap of the possible sums is faster and uses less
+    // memory than listing every pair of values
+    let bitmap_words = (small_max - small_min + large_max - large_min) / 64 + 2;
+    let pairs = (small.len() as u64).saturating_mul(large.len() as u64);
+    if bitmap_words.saturating_mul(small.len() as u64) <= pairs.saturating_mul(4) {
+        let mut large_bits = vec![0u64; ((large_max - large_min) / 64 + 1) as usize];
+        for &value in large {
+            let offset = value - large_min;
+            large_bits[(offset / 64) as usize] |= 1 << (offset % 64);
+        }
+        let mut sum_bits = vec![0u64; bitmap_words as usize];
+        for &value in small {
+            // Add the large set, shifted by this value, to the sums
+            let shift = value - small_min;
+            let word_shift = (shift / 64) as usize;
+            let bit_shift = shift % 64;
+            for (i, &word) in large_bits.iter().enumerate() {
+                sum_bits[i + word_shift] |= word << bit_shift;
+                if bit_shift != 0 {
+                    sum_bits[i + word_shift + 1] |= word >> (64 - bit_shift);
+                }
+            }
+        }
+        let sum_min = small_min + large_min;
+        let mut sums = Vec::new();
+        for (i, &word) in sum_bits.iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                let bit = u64::from(word.trailing_zeros());
+                sums.push(sum_min + 64 * i as u64 + bit);
+                word &= word - 1;
+            }
+        }
+        sums
+    } else {
+        let mut sums: Vec<u64> = small
+            .iter()
+            .flat_map(|small_value| {
+                large
+                    .iter()
+                    .map(move |large_value| small_value + large_value)
+            })
+            .collect();
+        sums.sort_unstable();
+        sums.dedup();
+        sums
+    }
+}
+
 /// Rounds the value up to a multiple of alignment
 fn align_up(value: u64, alignment: u64) -> u64 {
     value.div_ceil(alignment) * alignment
@@ -44,3 +44,43 @@ fn combinatorial_explosion() {
     assert_eq!(270532640, explosion_max_length);
     assert!(explosion_byte_aligned);
 }
+
+/// A type with many variable-length fields, each of which has many possible lengths
+///
+/// Expanding this set by combining every possible length of every field would take far too
+/// long.
+#[test]
+fn many_variable_length_fields() {
+    // Length of uavcan.primitive.String.1.0
+    let string_length = bit_length![16].concatenate([bit_length![8].repeat_range(..=256)]);
+    // A bool array that makes the lengths not byte-aligned
+    let bits_length = bit_length![8].concatenate([bit_length![1].repeat_range(..=7)]);
+
+    let fields = (0..40).flat_map(|_| [string_length.clone(), bits_length.clone()]);
+    let length = bit_length![0].concatenate(fields);
+
+    assert_eq!(40 * (16 + 8), length.min_value());
+    assert_eq!(40 * (16 + 256 * 8 + 8 + 7), length.max_value());
+    assert!(!length.is_byte_aligned());
+    assert!(length.clone().pad_to_alignment(8).is_byte_aligned());
+    // Every length from the minimum to the maximum is possible
+    let expanded = length.expand();
+    assert_eq!(
+        (length.max_value() - length.min_value() + 1) as usize,
+        expanded.len()
+    );
+}
+
+#[test]
+fn padding_modulo() {
+    // The remainder of a padded value depends on the remainder of the unpadded value modulo
+    // the least common multiple of the alignment and the divisor
+    let lengths = bit_length![0, 1, 7, 9, 30]
+        .concatenate([bit_length![3].repeat_range(..=5)])
+        .pad_to_alignment(8);
+    lengths.validate_numerically();
+    bit_length![5, 6, 21]
+        .repeat(9)
+        .pad_to_alignment(12)
+        .validate_numerically();
+}
@@ -0,0 +1,359 @@
+//! Generates Rust code from Cyphal DSDL files in build scripts
+//!
+//! This crate runs the same compiler as `canadensis_codegen_rust` from a `build.rs` file. It
+//! tells Cargo to run the build script again when any DSDL file changes, and reports DSDL
+//! warnings, errors, and the output of `@print` directives as Cargo warnings.
+//!
+//! # Example
+//!
+//! In the `main` function of `build.rs`:
+//!
+//! ```no_run
+//! canadensis_build::Builder::new()
+//!     .root("dsdl/public_regulated_data_types")
+//!     .root("dsdl/my_company")
+//!     .run_or_panic();
+//! ```
+//!
+//! In `src/lib.rs` or another module:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/dsdl.rs"));
+//! ```
+//!
+//! The root directories and options can also come from a project configuration file, which the
+//! `canadensis_codegen_rust` command-line tool also reads:
+//!
+//! ```no_run
+//! canadensis_build::Builder::from_config_file("canadensis.conf")
+//!     .unwrap()
+//!     .run_or_panic();
+//! ```
+//!
+//! The generated code depends on some other crates. `canadensis_codegen_rust print-dependencies`
+//! prints them, and [`canadensis_codegen_rust::generated_code_dependencies_with_config`] returns
+//! them.
+
+extern crate canadensis_codegen_rust;
+extern crate canadensis_dsdl_frontend;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use canadensis_codegen_rust::project::{add_cyphal_path, ProjectConfig, ProjectError, CYPHAL_PATH};
+use canadensis_dsdl_frontend::Package;
+
+pub use canadensis_codegen_rust::Config as CodegenConfig;
+pub use canadensis_dsdl_frontend::warning::WarningRule;
+pub use canadensis_dsdl_frontend::Config as ParserConfig;
+
+/// The name of the file in `OUT_DIR` that the code is written to by default
+pub const DEFAULT_OUTPUT_FILE: &str = "dsdl.rs";
+
+/// Compiles DSDL files and generates Rust code from a build script
+#[derive(Debug, Clone)]
+pub struct Builder {
+    roots: Vec<PathBuf>,
+    output: Option<PathBuf>,
+    config: ParserConfig,
+    codegen_config: CodegenConfig,
+    external_packages: BTreeMap<Vec<String>, Vec<String>>,
+    warning_rules: Vec<(WarningRule, bool)>,
+    deny_warnings: bool,
+    config_file: Option<PathBuf>,
+    cyphal_path: bool,
+}
+
+impl Builder {
+    /// Creates a builder with no root directories, which writes the code to
+    /// [`DEFAULT_OUTPUT_FILE`] in `OUT_DIR`
+    pub fn new() -> Self {
+        Builder {
+            roots: Vec::new(),
+            output: None,
+            config: ParserConfig {
+                allow_utf8_and_byte: false,
+                allow_saturated_bool: true,
+            },
+            codegen_config: CodegenConfig::default(),
+            external_packages: BTreeMap::new(),
+            warning_rules: Vec::new(),
+            deny_warnings: false,
+            config_file: None,
+            cyphal_path: false,
+        }
+    }
+
+    /// Creates a builder with the root directories and options in a project configuration file
+    ///
+    /// The format of the file is described in [`canadensis_codegen_rust::project`]. A relative
+    /// path to the file is relative to the directory with `Cargo.toml`, and relative root
+    /// directories in the file are relative to the directory that contains the file. The other
+    /// methods can add root directories and change the options after this.
+    ///
+    /// The build script runs again when the file changes.
+    pub fn from_config_file<P: Into<PathBuf>>(path: P) -> Result<Self, Error> {
+        let path = path.into();
+        let project = ProjectConfig::load(&path)?;
+        Ok(Builder {
+            roots: project.roots,
+            config: project.config,
+            codegen_config: project.codegen_config,
+            external_packages: project.external_packages,
+            warning_rules: project.warning_rules,
+            deny_warnings: project.deny_warnings,
+            config_file: Some(path),
+            ..Builder::new()
+        })
+    }
+
+    /// Adds a root directory with DSDL files
+    ///
+    /// Each subdirectory of a root directory is a namespace, like `uavcan` or `reg` in the
+    /// public regulated data types. Relative paths are relative to the directory with
+    /// `Cargo.toml`.
+    pub fn root<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.roots.push(path.into());
+        self
+    }
+
+    /// Sets whether the directories in the `CYPHAL_PATH` environment variable are also used as
+    /// root directories
+    ///
+    /// Those directories come after the other root directories, and directories that are
+    /// already root directories are not added again. The build script runs again when the
+    /// variable changes.
+    ///
+    /// Default false
+    pub fn cyphal_path(mut self, enabled: bool) -> Self {
+        self.cyphal_path = enabled;
+        self
+    }
+
+    /// Sets the file to write the code to
+    ///
+    /// Relative paths are relative to `OUT_DIR`.
+    pub fn output<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.output = Some(path.into());
+        self
+    }
+
+    /// Sets the parser configuration
+    pub fn config(mut self, config: ParserConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the code generation options, like view types, serde, and derives
+    pub fn codegen_config(mut self, codegen_config: CodegenConfig) -> Self {
+        self.codegen_config = codegen_config;
+        self
+    }
+
+    /// Uses existing Rust code for a DSDL package instead of generating it
+    ///
+    /// `package` is a DSDL package name like `uavcan.node`, and `rust_module` is the path to the
+    /// Rust module that has the code for that package, like `canadensis_data_types::uavcan::node`.
+    pub fn external_package(mut self, package: &str, rust_module: &str) -> Self {
+        self.external_packages.insert(
+            package.split('.').map(str::to_owned).collect(),
+            rust_module.split("::").map(str::to_owned).collect(),
+        );
+        self
+    }
+
+    /// Enables or disables a warning rule
+    pub fn warning(mut self, rule: WarningRule, enabled: bool) -> Self {
+        self.warning_rules.push((rule, enabled));
+        self
+    }
+
+    /// Sets whether the build fails if any warnings are reported
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
+    /// Compiles the DSDL files and writes the code
+    ///
+    /// This prints `cargo:rerun-if-changed` for each root directory and DSDL file, and a
+    /// `cargo:warning` for each DSDL warning and the output of each `@print` directive. It
+    /// returns the path to the file with the code.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if `OUT_DIR` is not set, any DSDL file is invalid, code
+    /// generation fails, the code could not be written, or `deny_warnings` is enabled and any
+    /// warnings are reported.
+    pub fn compile(&self) -> Result<PathBuf, Error> {
+        let stdout = io::stdout();
+        self.compile_with_directives(&mut stdout.lock())
+    }
+
+    /// Compiles the DSDL files and writes the code, reporting any error to Cargo
+    ///
+    /// This is like [`compile`](Self::compile), but an error and its causes are also printed
+    /// as Cargo warnings and standard error output before the error is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as [`compile`](Self::compile).
+    pub fn run(&self) -> Result<PathBuf, Error> {
+        match self.compile() {
+            Ok(path) => Ok(path),
+            Err(e) => {
+                let stdout = io::stdout();
+                let mut stdout = stdout.lock();
+                // Include the causes, which often have the location of a DSDL error
+                let mut message = format!("error: {}", e);
+                let mut source = std::error::Error::source(&e);
+                while let Some(cause) = source {
+                    message.push_str(&format!("\ncaused by: {}", cause));
+                    source = cause.source();
+                }
+                let _ = cargo_warning(&mut stdout, &message);
+                let _ = stdout.flush();
+                eprintln!("{}", message);
+                Err(e)
+            }
+        }
+    }
+
+    /// Compiles the DSDL files and writes the code, or panics if an error occurs
+    ///
+    /// This calls [`run`](Self::run), so the error is also reported as Cargo warnings.
+    pub fn run_or_panic(&self) -> PathBuf {
+        self.run().expect("DSDL code generation failed")
+    }
+
+    /// Compiles the DSDL files and writes the code, writing Cargo directives to `directives`
+    #[doc(hidden)]
+    pub fn compile_with_directives<W: Write>(&self, directives: &mut W) -> Result<PathBuf, Error> {
+        let output = match &self.output {
+            Some(output) if output.is_absolute() => output.clone(),
+            output => {
+                let out_dir = env::var_os("OUT_DIR").ok_or(Error::OutDir)?;
+                Path::new(&out_dir)
+                    .join(output.as_deref().unwrap_or(Path::new(DEFAULT_OUTPUT_FILE)))
+            }
+        };
+
+        if let Some(config_file) = &self.config_file {
+            writeln!(
+                directives,
+                "cargo:rerun-if-changed={}",
+                config_file.display()
+            )?;
+        }
+        let mut roots = self.roots.clone();
+        if self.cyphal_path {
+            writeln!(directives, "cargo:rerun-if-env-changed={}", CYPHAL_PATH)?;
+            add_cyphal_path(&mut roots);
+        }
+
+        let mut package = Package::new();
+        for root in &roots {
+            // This also runs the build script again when a file is added or removed
+            writeln!(directives, "cargo:rerun-if-changed={}", root.display())?;
+            package.add_files(root)?;
+        }
+        for path in package.paths() {
+            writeln!(directives, "cargo:rerun-if-changed={}", path.display())?;
+        }
+        for &(rule, enabled) in &self.warning_rules {
+            package.set_warning_enabled(rule, enabled);
+        }
+        // Standard output is for Cargo directives, so @print output becomes Cargo warnings
+        let prints = Rc::new(RefCell::new(Vec::new()));
+        {
+            let prints = Rc::clone(&prints);
+            package.set_print_handler(move |output| {
+                let message = match output.path() {
+                    Some(path) => {
+                        format!("{}:{}: {}", path.display(), output.line(), output.text())
+                    }
+                    None => format!("{}: {}", output.key(), output.text()),
+                };
+                prints.borrow_mut().push(message);
+            });
+        }
+
+        let result = package.compile_with_warnings(&self.config);
+        for message in prints.borrow().iter() {
+            cargo_warning(directives, message)?;
+        }
+        let package = match result {
+            Ok(package) => package,
+            Err((e, warnings)) => {
+                for warning in &warnings {
+                    cargo_warning(directives, format_args!("{} [{}]", warning, warning.rule()))?;
+                }
+                return Err(e.into());
+            }
+        };
+        for warning in package.warnings() {
+            cargo_warning(directives, format_args!("{} [{}]", warning, warning.rule()))?;
+        }
+        if self.deny_warnings && !package.warnings().is_empty() {
+            return Err(Error::Warnings(package.warnings().len()));
+        }
+
+        let generated = canadensis_codegen_rust::generate_code_with_config(
+            &package,
+            &self.external_packages,
+            &self.codegen_config,
+        )?;
+        if let Some(directory) = output.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(&output, generated.to_string())?;
+        Ok(output)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// Writes a message as one or more Cargo warnings
+///
+/// Cargo shows each warning on one line, so a message with several lines becomes several
+/// warnings.
+fn cargo_warning<W: Write>(directives: &mut W, message: impl Display) -> io::Result<()> {
+    for line in message.to_string().lines() {
+        writeln!(directives, "cargo:warning={}", line)?;
+    }
+    Ok(())
+}
+
+/// Errors that can occur when generating code
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The `OUT_DIR` environment variable is not set, which means that the code is not running
+    /// in a build script
+    #[error("OUT_DIR is not set (canadensis_build must be used in a build script, or with an absolute output path)")]
+    OutDir,
+    /// The project configuration file could not be read
+    #[error(transparent)]
+    Project(#[from] ProjectError),
+    /// A DSDL file could not be read or compiled
+    #[error(transparent)]
+    Dsdl(#[from] Box<canadensis_dsdl_frontend::Error>),
+    /// Code generation failed
+    #[error(transparent)]
+    Codegen(#[from] canadensis_codegen_rust::Error),
+    /// The code could not be written
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Warnings were reported, and `deny_warnings` is enabled
+    #[error("{0} warnings reported, and deny_warnings is enabled")]
+    Warnings(usize),
+}
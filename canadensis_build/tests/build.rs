@@ -0,0 +1,114 @@
+extern crate canadensis_build;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use canadensis_build::{Builder, CodegenConfig, Error, WarningRule};
+
+fn simple_dsdl() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../canadensis_dsdl_frontend/tests/simple_dsdl_no_byte_utf8")
+}
+
+/// Checks that the builder writes the code and tells Cargo about every input file
+#[test]
+fn build_simple() -> Result<(), Error> {
+    let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("build_simple/dsdl.rs");
+    let mut directives = Vec::new();
+    let path = Builder::new()
+        .root(simple_dsdl())
+        .output(&output)
+        .codegen_config(CodegenConfig {
+            builders: true,
+            ..Default::default()
+        })
+        .compile_with_directives(&mut directives)?;
+    assert_eq!(output, path);
+
+    let code = fs::read_to_string(&path)?;
+    assert!(code.contains("pub mod canadensis {"));
+    assert!(code.contains("Builder"));
+
+    let directives = String::from_utf8(directives).unwrap();
+    let mut lines = directives.lines();
+    assert_eq!(
+        Some(format!("cargo:rerun-if-changed={}", simple_dsdl().display()).as_str()),
+        lines.next()
+    );
+    let mut files = 0;
+    let mut prints = Vec::new();
+    for line in lines {
+        if let Some(message) = line.strip_prefix("cargo:warning=") {
+            prints.push(message);
+            continue;
+        }
+        let path = line.strip_prefix("cargo:rerun-if-changed=").unwrap();
+        assert!(Path::new(path).is_file());
+        files += 1;
+    }
+    assert_ne!(0, files);
+    // The output of @print directives is reported as warnings with the file and line
+    assert!(prints
+        .iter()
+        .any(|message| message.contains("Constants.1.0.uavcan:19: 2")));
+    Ok(())
+}
+
+/// Checks that the build fails when a warning is reported and warnings are denied
+#[test]
+fn deny_warnings() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("deny_warnings/dsdl");
+    fs::create_dir_all(root.join("test")).unwrap();
+    fs::write(
+        root.join("test/Unused.1.0.dsdl"),
+        "uint8 UNUSED = 1\n@sealed\n",
+    )
+    .unwrap();
+
+    let output = root.with_file_name("dsdl.rs");
+    let mut directives = Vec::new();
+    let result = Builder::new()
+        .root(&root)
+        .output(&output)
+        .warning(WarningRule::UnusedConstant, true)
+        .deny_warnings(true)
+        .compile_with_directives(&mut directives);
+    assert!(matches!(result, Err(Error::Warnings(1))));
+    let directives = String::from_utf8(directives).unwrap();
+    assert!(directives.contains("cargo:warning="));
+    assert!(directives.contains("[unused-constant]"));
+}
+
+/// Checks that the builder uses the root directories and options from a project configuration
+/// file
+#[test]
+fn config_file() -> Result<(), Error> {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("config_file");
+    fs::create_dir_all(&directory)?;
+    let config_file = directory.join("canadensis.conf");
+    fs::write(
+        &config_file,
+        format!("root {}\nbuilders\n", simple_dsdl().display()),
+    )?;
+
+    let mut directives = Vec::new();
+    let path = Builder::from_config_file(&config_file)?
+        .output(directory.join("dsdl.rs"))
+        .compile_with_directives(&mut directives)?;
+    let code = fs::read_to_string(&path)?;
+    assert!(code.contains("pub mod canadensis {"));
+    assert!(code.contains("Builder"));
+
+    let directives = String::from_utf8(directives).unwrap();
+    assert_eq!(
+        Some(format!("cargo:rerun-if-changed={}", config_file.display()).as_str()),
+        directives.lines().next()
+    );
+
+    fs::write(&config_file, "root\n")?;
+    assert!(matches!(
+        Builder::from_config_file(&config_file),
+        Err(Error::Project(_))
+    ));
+    Ok(())
+}
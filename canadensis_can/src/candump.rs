@@ -0,0 +1,601 @@
+//!
+//! Conversion between frames and candump log files
+//!
+//! The `candump -l` and `candump -L` commands from the Linux
+//! [can-utils](https://github.com/linux-can/can-utils) package write one frame per line, like
+//! this:
+//!
+//! ```text
+//! (1436509052.249713) can0 107D557B#0102E0
+//! (1436509052.250127) can0 107D557B##1000102030405060708090A0B0C0D0EE1
+//! ```
+//!
+//! Each line has a timestamp in seconds, an interface name, a 29-bit CAN ID in hexadecimal,
+//! and the frame data. CAN FD frames use `##` and a hexadecimal digit of flags before the data.
+//! Tools like `canplayer`, `log2asc`, and Wireshark can read these logs.
+//!
+//! [`Entry::parse`] and [`parse_log`] read logs, and the [`Display`](core::fmt::Display)
+//! implementation of [`Entry`] writes them. A [`CandumpDriver`] wraps another driver and writes
+//! the frames that pass through it to a log.
+//!
+//! Cyphal/CAN only uses extended CAN IDs, so lines with 11-bit IDs, remote frames, and error
+//! frames cause parse errors. A program that reads logs from a mixed bus can skip those lines.
+//!
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::driver::{ReceiveDriver, TransmitDriver};
+use crate::types::CanNodeId;
+use crate::{CanId, Frame, FRAME_CAPACITY};
+use canadensis_core::subscription::Subscription;
+use canadensis_core::time::{Clock, Microseconds32};
+use canadensis_core::{nb, OutOfMemoryError};
+
+/// The CAN FD flag for bit rate switching
+pub const FD_FLAG_BRS: u8 = 0x1;
+/// The CAN FD flag for the error state indicator
+pub const FD_FLAG_ESI: u8 = 0x2;
+
+/// One line of a candump log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry<'a> {
+    /// The time when the frame was received or sent, in microseconds
+    ///
+    /// candump uses the time since the Unix epoch.
+    pub timestamp: u64,
+    /// The name of the interface that received or sent the frame
+    pub interface: &'a str,
+    /// The frame
+    ///
+    /// When an entry is parsed, the frame timestamp is the lower 32 bits of `timestamp`.
+    /// When an entry is written, the frame timestamp is ignored.
+    pub frame: Frame,
+    /// For CAN FD frames, the flags ([`FD_FLAG_BRS`] and [`FD_FLAG_ESI`]), or None for
+    /// classic CAN frames
+    pub fd_flags: Option<u8>,
+}
+
+impl<'a> Entry<'a> {
+    /// Creates an entry
+    ///
+    /// Frames with more than 8 bytes of data are CAN FD frames with no flags set. Other frames
+    /// are classic CAN frames.
+    pub fn new(timestamp: u64, interface: &'a str, frame: Frame) -> Self {
+        let fd_flags = if frame.data().len() > 8 {
+            Some(0)
+        } else {
+            None
+        };
+        Entry {
+            timestamp,
+            interface,
+            frame,
+            fd_flags,
+        }
+    }
+
+    /// Parses a line from a candump log
+    ///
+    /// Leading and trailing whitespace is ignored.
+    pub fn parse(line: &'a str) -> Result<Self, ParseError> {
+        let mut parts = line.split_whitespace();
+        let (timestamp, interface, frame) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(timestamp), Some(interface), Some(frame)) => (timestamp, interface, frame),
+            _ => return Err(ParseError::Format),
+        };
+        if parts.next().is_some() {
+            return Err(ParseError::Format);
+        }
+        let timestamp = parse_timestamp(timestamp)?;
+
+        let (id, data) = frame.split_once('#').ok_or(ParseError::Format)?;
+        // from_str_radix also accepts a sign, which should not appear here
+        let all_hex = |s: &str| s.bytes().all(|c| c.is_ascii_hexdigit());
+        if !all_hex(id) {
+            return Err(ParseError::Id);
+        }
+        if id.len() != 8 {
+            return Err(if id.len() == 3 {
+                ParseError::StandardId
+            } else {
+                ParseError::Id
+            });
+        }
+        let id = u32::from_str_radix(id, 16).map_err(|_| ParseError::Id)?;
+        let id = CanId::try_from(id).map_err(|_| ParseError::Id)?;
+        let (data, fd_flags) = match data.strip_prefix('#') {
+            Some(fd_data) => {
+                let mut chars = fd_data.chars();
+                let flags = chars
+                    .next()
+                    .and_then(|flags| flags.to_digit(16))
+                    .ok_or(ParseError::Format)?;
+                (chars.as_str(), Some(flags as u8))
+            }
+            None if data.starts_with('R') => return Err(ParseError::Remote),
+            None => (data, None),
+        };
+
+        let mut bytes = heapless::Vec::<u8, FRAME_CAPACITY>::new();
+        if data.len() % 2 != 0 || !all_hex(data) {
+            return Err(ParseError::Data);
+        }
+        for i in (0..data.len()).step_by(2) {
+            let byte = data
+                .get(i..i + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .expect("Bug: Invalid hexadecimal digits");
+            bytes.push(byte).map_err(|_| ParseError::TooLong)?;
+        }
+        if fd_flags.is_none() && bytes.len() > 8 {
+            return Err(ParseError::TooLong);
+        }
+
+        Ok(Entry {
+            timestamp,
+            interface,
+            frame: Frame::new(Microseconds32::from_ticks(timestamp as u32), id, &bytes),
+            fd_flags,
+        })
+    }
+}
+
+impl fmt::Display for Entry<'_> {
+    /// Writes this entry in the candump log format, without a line ending
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({}.{:06}) {} {:08X}#",
+            self.timestamp / 1_000_000,
+            self.timestamp % 1_000_000,
+            self.interface,
+            u32::from(self.frame.id())
+        )?;
+        if let Some(flags) = self.fd_flags {
+            write!(f, "#{:X}", flags & 0xf)?;
+        }
+        for byte in self.frame.data() {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a timestamp like `(1436509052.249713)` into microseconds
+fn parse_timestamp(timestamp: &str) -> Result<u64, ParseError> {
+    let timestamp = timestamp
+        .strip_prefix('(')
+        .and_then(|timestamp| timestamp.strip_suffix(')'))
+        .ok_or(ParseError::Timestamp)?;
+    let (seconds, fraction) = timestamp.split_once('.').unwrap_or((timestamp, ""));
+    let all_digits = |s: &str| s.bytes().all(|c| c.is_ascii_digit());
+    if seconds.is_empty() || !all_digits(seconds) || !all_digits(fraction) {
+        return Err(ParseError::Timestamp);
+    }
+    let seconds: u64 = seconds.parse().map_err(|_| ParseError::Timestamp)?;
+    // Use the first 6 digits of the fraction, and add zeros if there are fewer than 6
+    let micros = fraction
+        .bytes()
+        .chain(core::iter::repeat(b'0'))
+        .take(6)
+        .fold(0, |micros, digit| micros * 10 + u64::from(digit - b'0'));
+    seconds
+        .checked_mul(1_000_000)
+        .and_then(|seconds| seconds.checked_add(micros))
+        .ok_or(ParseError::Timestamp)
+}
+
+/// Parses the lines of a candump log
+///
+/// Empty lines are skipped. Each item contains the line number (starting at 1) and the result
+/// of parsing the line.
+pub fn parse_log(log: &str) -> impl Iterator<Item = (usize, Result<Entry<'_>, ParseError>)> {
+    log.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, Entry::parse(line)))
+}
+
+/// Errors that can occur when parsing a line from a candump log
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseError {
+    /// The line does not have a timestamp, interface, and frame separated by whitespace,
+    /// or the frame does not contain `#`
+    Format,
+    /// The timestamp is not a number of seconds in parentheses
+    Timestamp,
+    /// The frame has an 11-bit CAN ID, which Cyphal/CAN does not use
+    StandardId,
+    /// The CAN ID is not a valid 29-bit ID in hexadecimal
+    ///
+    /// This includes error frames, which candump writes with bit 29 set.
+    Id,
+    /// The frame is a remote frame, which Cyphal/CAN does not use
+    Remote,
+    /// The data is not an even number of hexadecimal digits
+    Data,
+    /// The frame has more data than a classic CAN frame (for a `#` line) or
+    /// [`FRAME_CAPACITY`] (for a `##` line)
+    TooLong,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParseError::Format => "Invalid line format",
+            ParseError::Timestamp => "Invalid timestamp",
+            ParseError::StandardId => "Unsupported 11-bit CAN ID",
+            ParseError::Id => "Invalid CAN ID",
+            ParseError::Remote => "Unsupported remote frame",
+            ParseError::Data => "Invalid frame data",
+            ParseError::TooLong => "Too much frame data",
+        };
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// A driver that wraps another driver and writes the frames that pass through it to a
+/// candump log
+///
+/// Each line has the interface name from [`new`](Self::new) and ends with `\n`. The frames
+/// that the wrapped driver accepts for transmission are written with the time when they were
+/// transmitted, and received frames are written with their receive timestamps.
+///
+/// Clocks only count microseconds in 32 bits, which wrap around after about 71 minutes.
+/// A candump driver keeps its own 64-bit time, starting at the offset from
+/// [`set_time_offset`](Self::set_time_offset), which stays correct as long as frames are
+/// written at least once per wraparound. An offset of the Unix time when the clock read zero
+/// makes the log timestamps match candump's.
+///
+/// The log is written to any [`core::fmt::Write`]. If writing fails, the frame is still
+/// transmitted or received, and [`write_errors`](Self::write_errors) counts the failure.
+pub struct CandumpDriver<'i, D, W> {
+    /// The wrapped driver
+    driver: D,
+    /// The log destination
+    log: W,
+    /// The interface name written on each line
+    interface: &'i str,
+    /// The log time, in microseconds, of a clock time of zero
+    offset: u64,
+    /// The clock time and log time of the latest frame
+    latest: Option<(Microseconds32, u64)>,
+    /// If received frames should be written
+    log_received: bool,
+    /// The number of frames that could not be written
+    write_errors: u32,
+}
+
+impl<'i, D, W> CandumpDriver<'i, D, W> {
+    /// Creates a candump driver that wraps a driver and writes to `log`
+    ///
+    /// The candump driver initially writes both transmitted and received frames, with a time
+    /// offset of zero.
+    pub fn new(driver: D, log: W, interface: &'i str) -> Self {
+        CandumpDriver {
+            driver,
+            log,
+            interface,
+            offset: 0,
+            latest: None,
+            log_received: true,
+            write_errors: 0,
+        }
+    }
+
+    /// Sets the log time, in microseconds, that corresponds to a clock time of zero
+    ///
+    /// This should be called before any frames are written.
+    pub fn set_time_offset(&mut self, offset: u64) {
+        self.offset = offset;
+    }
+
+    /// Sets whether received frames should be written (true, the default) or only
+    /// transmitted frames (false)
+    pub fn set_log_received(&mut self, log_received: bool) {
+        self.log_received = log_received;
+    }
+
+    /// Returns the number of frames that could not be written
+    pub fn write_errors(&self) -> u32 {
+        self.write_errors
+    }
+
+    /// Returns a reference to the log
+    pub fn log(&self) -> &W {
+        &self.log
+    }
+    /// Returns a mutable reference to the log
+    pub fn log_mut(&mut self) -> &mut W {
+        &mut self.log
+    }
+    /// Returns a reference to the wrapped driver
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+    /// Returns a mutable reference to the wrapped driver
+    pub fn driver_mut(&mut self) -> &mut D {
+        &mut self.driver
+    }
+    /// Returns the wrapped driver and the log
+    pub fn into_inner(self) -> (D, W) {
+        (self.driver, self.log)
+    }
+}
+
+impl<D, W: fmt::Write> CandumpDriver<'_, D, W> {
+    fn write(&mut self, frame: &Frame, clock_time: Microseconds32) {
+        let time = match self.latest {
+            Some((latest_clock_time, latest_time)) => {
+                // Wrapping subtraction handles clock wraparound. Received frames can be a little
+                // older than the latest frame, and then the difference is more than half the
+                // range.
+                let elapsed = clock_time.ticks().wrapping_sub(latest_clock_time.ticks());
+                if elapsed <= u32::MAX / 2 {
+                    latest_time + u64::from(elapsed)
+                } else {
+                    latest_time.saturating_sub(u64::from(elapsed.wrapping_neg()))
+                }
+            }
+            None => self.offset + u64::from(clock_time.ticks()),
+        };
+        if self
+            .latest
+            .is_none_or(|(_, latest_time)| time > latest_time)
+        {
+            self.latest = Some((clock_time, time));
+        }
+        let entry = Entry::new(time, self.interface, frame.clone());
+        if writeln!(self.log, "{}", entry).is_err() {
+            self.write_errors = self.write_errors.wrapping_add(1);
+        }
+    }
+}
+
+impl<C, D, W> TransmitDriver<C> for CandumpDriver<'_, D, W>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+    W: fmt::Write,
+{
+    type Error = D::Error;
+
+    fn try_reserve(&mut self, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.driver.try_reserve(frames)
+    }
+
+    fn try_reserve_for(&mut self, id: CanId, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.driver.try_reserve_for(id, frames)
+    }
+
+    fn transmit(&mut self, frame: Frame, clock: &mut C) -> nb::Result<Option<Frame>, Self::Error> {
+        let copy = frame.clone();
+        let result = self.driver.transmit(frame, clock)?;
+        // Only write frames that the wrapped driver accepted
+        let now = clock.now();
+        self.write(&copy, now);
+        Ok(result)
+    }
+
+    fn flush(&mut self, clock: &mut C) -> nb::Result<(), Self::Error> {
+        self.driver.flush(clock)
+    }
+}
+
+impl<C, D, W> ReceiveDriver<C> for CandumpDriver<'_, D, W>
+where
+    C: Clock,
+    D: ReceiveDriver<C>,
+    W: fmt::Write,
+{
+    type Error = D::Error;
+
+    fn receive(&mut self, clock: &mut C) -> nb::Result<Frame, Self::Error> {
+        let frame = self.driver.receive(clock)?;
+        // Loopback frames are copies of transmitted frames, which have already been written
+        if self.log_received && !frame.loopback() {
+            self.write(&frame, frame.timestamp());
+        }
+        Ok(frame)
+    }
+
+    fn apply_filters<S>(&mut self, local_node: Option<CanNodeId>, subscriptions: S)
+    where
+        S: IntoIterator<Item = Subscription>,
+    {
+        self.driver.apply_filters(local_node, subscriptions)
+    }
+
+    fn apply_accept_all(&mut self) {
+        self.driver.apply_accept_all()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+
+    fn frame(time: u32, id: u32, data: &[u8]) -> Frame {
+        Frame::new(
+            Microseconds32::from_ticks(time),
+            CanId::try_from(id).unwrap(),
+            data,
+        )
+    }
+
+    #[test]
+    fn parse_classic() {
+        let entry = Entry::parse("(1436509052.249713) can0 107D557B#0102E0").unwrap();
+        assert_eq!(1_436_509_052_249_713, entry.timestamp);
+        assert_eq!("can0", entry.interface);
+        assert_eq!(
+            frame(1_436_509_052_249_713_u64 as u32, 0x107d557b, &[1, 2, 0xe0]),
+            entry.frame
+        );
+        assert_eq!(None, entry.fd_flags);
+
+        let entry = Entry::parse("  (12.5)\tvcan1 00000001#  ").unwrap();
+        assert_eq!(12_500_000, entry.timestamp);
+        assert_eq!("vcan1", entry.interface);
+        assert!(entry.frame.data().is_empty());
+    }
+
+    #[cfg(feature = "can-fd")]
+    #[test]
+    fn parse_fd() {
+        let entry =
+            Entry::parse("(0.000001) can0 107D557B##1000102030405060708090A0B0C0D0EE1").unwrap();
+        assert_eq!(1, entry.timestamp);
+        assert_eq!(Some(FD_FLAG_BRS), entry.fd_flags);
+        assert_eq!(
+            &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 0xe1],
+            entry.frame.data()
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("", ParseError::Format),
+            ("(1.0) can0", ParseError::Format),
+            ("(1.0) can0 107D557B#00 extra", ParseError::Format),
+            ("(1.0) can0 107D557B", ParseError::Format),
+            ("1.0 can0 107D557B#00", ParseError::Timestamp),
+            ("(1.x) can0 107D557B#00", ParseError::Timestamp),
+            ("(1.0) can0 123#00", ParseError::StandardId),
+            ("(1.0) can0 20000080#0000000000000000", ParseError::Id),
+            ("(1.0) can0 1G7D557B#00", ParseError::Id),
+            ("(1.0) can0 107D557B#R", ParseError::Remote),
+            ("(1.0) can0 107D557B#0", ParseError::Data),
+            ("(1.0) can0 107D557B#0G", ParseError::Data),
+            ("(1.0) can0 107D557B#+1", ParseError::Data),
+            ("(1.0) can0 +07D557B#00", ParseError::Id),
+            ("(1.0) can0 107D557B##", ParseError::Format),
+            (
+                "(1.0) can0 107D557B#000102030405060708",
+                ParseError::TooLong,
+            ),
+        ];
+        for (line, expected) in cases.iter() {
+            assert_eq!(Err(expected.clone()), Entry::parse(line), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn write_and_parse() {
+        let entry = Entry::new(
+            1_436_509_052_000_042,
+            "can0",
+            frame(0, 0x107d557b, &[0xab, 0x01, 0xe0]),
+        );
+        let line = entry.to_string();
+        assert_eq!("(1436509052.000042) can0 107D557B#AB01E0", line);
+        let parsed = Entry::parse(&line).unwrap();
+        assert_eq!(entry.timestamp, parsed.timestamp);
+        assert_eq!(entry.frame.data(), parsed.frame.data());
+
+        let fd = Entry {
+            fd_flags: Some(FD_FLAG_BRS | FD_FLAG_ESI),
+            ..entry
+        };
+        assert_eq!("(1436509052.000042) can0 107D557B##3AB01E0", fd.to_string());
+    }
+
+    #[test]
+    fn parse_log_lines() {
+        let log = "(1.000000) can0 107D557B#E0\n\n(2.000000) can0 123#00\n";
+        let entries: Vec<_> = parse_log(log).collect();
+        assert_eq!(2, entries.len());
+        assert_eq!(1, entries[0].0);
+        assert!(entries[0].1.is_ok());
+        assert_eq!((3, Err(ParseError::StandardId)), entries[1]);
+    }
+
+    /// A driver that accepts all transmitted frames and receives frames from a list
+    struct ListDriver(Vec<Frame>);
+
+    impl<C: Clock> TransmitDriver<C> for ListDriver {
+        type Error = Infallible;
+        fn try_reserve(&mut self, _frames: usize) -> Result<(), OutOfMemoryError> {
+            Ok(())
+        }
+        fn transmit(
+            &mut self,
+            _frame: Frame,
+            _clock: &mut C,
+        ) -> nb::Result<Option<Frame>, Infallible> {
+            Ok(None)
+        }
+        fn flush(&mut self, _clock: &mut C) -> nb::Result<(), Infallible> {
+            Ok(())
+        }
+    }
+
+    impl<C: Clock> ReceiveDriver<C> for ListDriver {
+        type Error = Infallible;
+        fn receive(&mut self, _clock: &mut C) -> nb::Result<Frame, Infallible> {
+            if self.0.is_empty() {
+                Err(nb::Error::WouldBlock)
+            } else {
+                Ok(self.0.remove(0))
+            }
+        }
+        fn apply_filters<S>(&mut self, _local_node: Option<CanNodeId>, _subscriptions: S)
+        where
+            S: IntoIterator<Item = Subscription>,
+        {
+        }
+        fn apply_accept_all(&mut self) {}
+    }
+
+    struct FixedClock(u32);
+
+    impl Clock for FixedClock {
+        fn now(&mut self) -> Microseconds32 {
+            Microseconds32::from_ticks(self.0)
+        }
+    }
+
+    #[test]
+    fn driver_writes_log() {
+        let mut looped_back = frame(20, 0x107d557b, &[0xe1]);
+        looped_back.set_loopback(true);
+        let received = vec![
+            frame(u32::MAX - 9, 0x107d5501, &[0xe0]),
+            looped_back,
+            frame(5, 0x107d5501, &[0xe1]),
+            // A frame received slightly out of order
+            frame(3, 0x107d5501, &[0xe2]),
+        ];
+        let mut driver = CandumpDriver::new(ListDriver(received), String::new(), "can1");
+        driver.set_time_offset(1_000_000_000_000);
+        let mut clock = FixedClock(u32::MAX - 20);
+        TransmitDriver::transmit(&mut driver, frame(0, 0x107d557b, &[0xe0]), &mut clock).unwrap();
+        // The time wraps around between the received frames
+        while ReceiveDriver::receive(&mut driver, &mut clock).is_ok() {}
+
+        assert_eq!(0, driver.write_errors());
+        let (_, log) = driver.into_inner();
+        let expected_start = 1_000_000_000_000_u64 + u64::from(u32::MAX - 20);
+        let times: Vec<u64> = parse_log(&log)
+            .map(|(_, entry)| entry.unwrap().timestamp)
+            .collect();
+        assert_eq!(
+            vec![
+                expected_start,
+                expected_start + 11,
+                expected_start + 26,
+                expected_start + 24
+            ],
+            times
+        );
+        assert!(log.lines().all(|line| line.contains(" can1 ")));
+    }
+}
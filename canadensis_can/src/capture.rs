@@ -0,0 +1,293 @@
+//!
+//! Capture of raw CAN frames for remote bus monitoring
+//!
+//! A [`CaptureDriver`] wraps another driver and keeps a copy of the frames that pass through it.
+//! A gateway node can convert the captured frames into `uavcan.metatransport.can.Frame.0.2`
+//! messages and publish them on any subject, so that a remote tool can observe the bus.
+//!
+//! By default, a capture driver configures the wrapped driver to accept all frames, even ones
+//! that do not match any subscription of the local node.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Set up a node whose driver is a CaptureDriver, and a publisher for the captured frames
+//! let capture_token = node.start_publishing(capture_subject, timeout, priority)?;
+//! // Later, in the main loop:
+//! while let Some(captured) = node.driver_mut().pop_message() {
+//!     node.publish(&capture_token, &captured)?;
+//! }
+//! ```
+//!
+//! The frames that the gateway node sends to publish captured frames are not captured again,
+//! because a capture driver ignores loopback frames and does not capture outgoing frames unless
+//! [`CaptureDriver::set_capture_transmitted`] enables that.
+//!
+
+use crate::driver::{ReceiveDriver, TransmitDriver};
+use crate::types::CanNodeId;
+use crate::{CanId, Frame};
+use canadensis_core::subscription::Subscription;
+use canadensis_core::time::Clock;
+use canadensis_core::{nb, OutOfMemoryError};
+use canadensis_data_types::uavcan::metatransport::can::arbitration_id_0_1::ArbitrationID;
+use canadensis_data_types::uavcan::metatransport::can::data_classic_0_1::DataClassic;
+use canadensis_data_types::uavcan::metatransport::can::data_fd_0_1::DataFD;
+use canadensis_data_types::uavcan::metatransport::can::extended_arbitration_id_0_1::ExtendedArbitrationID;
+use canadensis_data_types::uavcan::metatransport::can::frame_0_2::Frame as FrameMessage;
+use heapless::Deque;
+
+/// A driver that wraps another driver and keeps copies of observed frames
+///
+/// Up to `N` captured frames are stored. When the buffer is full, the oldest captured frame
+/// is discarded to make space for a new one.
+pub struct CaptureDriver<D, const N: usize> {
+    /// The wrapped driver
+    driver: D,
+    /// Captured frames, oldest first
+    frames: Deque<Frame, N>,
+    /// Number of captured frames that were discarded because the buffer was full
+    dropped: u32,
+    /// If the wrapped driver should be configured to accept all frames
+    accept_all: bool,
+    /// If outgoing frames should be captured
+    capture_transmitted: bool,
+}
+
+impl<D, const N: usize> CaptureDriver<D, N> {
+    /// Creates a capture driver that wraps the provided driver
+    ///
+    /// The capture driver initially captures received frames only, and configures the wrapped
+    /// driver to accept all frames.
+    pub fn new(driver: D) -> Self {
+        CaptureDriver {
+            driver,
+            frames: Deque::new(),
+            dropped: 0,
+            accept_all: true,
+            capture_transmitted: false,
+        }
+    }
+
+    /// Sets whether the wrapped driver should accept all frames (true, the default) or only
+    /// the frames that match the local node's subscriptions (false)
+    ///
+    /// This takes effect the next time the node applies its receive filters.
+    pub fn set_accept_all(&mut self, accept_all: bool) {
+        self.accept_all = accept_all;
+    }
+
+    /// Sets whether frames sent by the local node should also be captured
+    ///
+    /// This is disabled by default. If it is enabled, the frames used to publish captured frames
+    /// will also be captured, which can cause a feedback loop.
+    pub fn set_capture_transmitted(&mut self, capture_transmitted: bool) {
+        self.capture_transmitted = capture_transmitted;
+    }
+
+    /// Removes and returns the oldest captured frame
+    pub fn pop_frame(&mut self) -> Option<Frame> {
+        self.frames.pop_front()
+    }
+
+    /// Removes the oldest captured frame and converts it into a
+    /// `uavcan.metatransport.can.Frame.0.2` message
+    pub fn pop_message(&mut self) -> Option<FrameMessage> {
+        self.pop_frame().map(|frame| frame_to_message(&frame))
+    }
+
+    /// Returns the number of captured frames that have not been removed
+    pub fn captured_len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the number of captured frames that were discarded because the capture buffer was
+    /// full
+    pub fn dropped_frames(&self) -> u32 {
+        self.dropped
+    }
+
+    /// Returns a reference to the wrapped driver
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+    /// Returns a mutable reference to the wrapped driver
+    pub fn driver_mut(&mut self) -> &mut D {
+        &mut self.driver
+    }
+    /// Returns the wrapped driver
+    pub fn into_inner(self) -> D {
+        self.driver
+    }
+
+    fn capture(&mut self, frame: &Frame) {
+        if self.frames.is_full() {
+            self.frames.pop_front();
+            self.dropped = self.dropped.wrapping_add(1);
+        }
+        // This can't fail because the deque is not full
+        let _ = self.frames.push_back(frame.clone());
+    }
+}
+
+impl<C, D, const N: usize> TransmitDriver<C> for CaptureDriver<D, N>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+{
+    type Error = D::Error;
+
+    fn try_reserve(&mut self, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.driver.try_reserve(frames)
+    }
+
+    fn try_reserve_for(&mut self, id: CanId, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.driver.try_reserve_for(id, frames)
+    }
+
+    fn transmit(&mut self, frame: Frame, clock: &mut C) -> nb::Result<Option<Frame>, Self::Error> {
+        if self.capture_transmitted {
+            let copy = frame.clone();
+            let result = self.driver.transmit(frame, clock)?;
+            // Only capture frames that the wrapped driver accepted
+            self.capture(&copy);
+            Ok(result)
+        } else {
+            self.driver.transmit(frame, clock)
+        }
+    }
+
+    fn flush(&mut self, clock: &mut C) -> nb::Result<(), Self::Error> {
+        self.driver.flush(clock)
+    }
+}
+
+impl<C, D, const N: usize> ReceiveDriver<C> for CaptureDriver<D, N>
+where
+    C: Clock,
+    D: ReceiveDriver<C>,
+{
+    type Error = D::Error;
+
+    fn receive(&mut self, clock: &mut C) -> nb::Result<Frame, Self::Error> {
+        let frame = self.driver.receive(clock)?;
+        if !frame.loopback() {
+            self.capture(&frame);
+        }
+        Ok(frame)
+    }
+
+    fn apply_filters<S>(&mut self, local_node: Option<CanNodeId>, subscriptions: S)
+    where
+        S: IntoIterator<Item = Subscription>,
+    {
+        if self.accept_all {
+            self.driver.apply_accept_all()
+        } else {
+            self.driver.apply_filters(local_node, subscriptions)
+        }
+    }
+
+    fn apply_accept_all(&mut self) {
+        self.driver.apply_accept_all()
+    }
+}
+
+/// Converts a frame into a `uavcan.metatransport.can.Frame.0.2` message
+///
+/// Frames with up to 8 bytes of data become `DataClassic` messages. Longer frames become
+/// `DataFD` messages.
+pub fn frame_to_message(frame: &Frame) -> FrameMessage {
+    let arbitration_id = ArbitrationID::Extended(ExtendedArbitrationID {
+        value: u32::from(frame.id()),
+    });
+    let data = frame.data();
+    if data.len() <= 8 {
+        FrameMessage::DataClassic(DataClassic {
+            arbitration_id,
+            // Length checked above
+            data: heapless::Vec::from_slice(data).unwrap(),
+        })
+    } else {
+        FrameMessage::DataFd(DataFD {
+            arbitration_id,
+            // A frame can't hold more than 64 bytes
+            data: heapless::Vec::from_slice(data).unwrap(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CanId;
+    use canadensis_core::time::Microseconds32;
+    use core::convert::TryFrom;
+
+    struct TestDriver(Option<Frame>);
+
+    impl<C: Clock> ReceiveDriver<C> for TestDriver {
+        type Error = core::convert::Infallible;
+
+        fn receive(&mut self, _clock: &mut C) -> nb::Result<Frame, Self::Error> {
+            self.0.take().ok_or(nb::Error::WouldBlock)
+        }
+        fn apply_filters<S>(&mut self, _local_node: Option<CanNodeId>, _subscriptions: S)
+        where
+            S: IntoIterator<Item = Subscription>,
+        {
+        }
+        fn apply_accept_all(&mut self) {}
+    }
+
+    struct TestClock;
+    impl Clock for TestClock {
+        fn now(&mut self) -> Microseconds32 {
+            Microseconds32::from_ticks(0)
+        }
+    }
+
+    fn frame(data: &[u8]) -> Frame {
+        Frame::new(
+            Microseconds32::from_ticks(0),
+            CanId::try_from(0x107d552a).unwrap(),
+            data,
+        )
+    }
+
+    #[test]
+    fn capture_oldest_dropped() {
+        let mut driver = CaptureDriver::<_, 2>::new(TestDriver(None));
+        for i in 0..3u8 {
+            driver.driver_mut().0 = Some(frame(&[i]));
+            ReceiveDriver::<TestClock>::receive(&mut driver, &mut TestClock).unwrap();
+        }
+        assert_eq!(1, driver.dropped_frames());
+        assert_eq!(&[1], driver.pop_frame().unwrap().data());
+        assert_eq!(&[2], driver.pop_frame().unwrap().data());
+        assert!(driver.pop_frame().is_none());
+    }
+
+    #[test]
+    fn loopback_not_captured() {
+        let mut looped_back = frame(&[]);
+        looped_back.set_loopback(true);
+        let mut driver = CaptureDriver::<_, 2>::new(TestDriver(Some(looped_back)));
+        ReceiveDriver::<TestClock>::receive(&mut driver, &mut TestClock).unwrap();
+        assert_eq!(0, driver.captured_len());
+    }
+
+    #[test]
+    fn convert_classic() {
+        match frame_to_message(&frame(&[1, 2, 3])) {
+            FrameMessage::DataClassic(classic) => {
+                assert_eq!(&[1, 2, 3], &classic.data[..]);
+                match classic.arbitration_id {
+                    ArbitrationID::Extended(id) => assert_eq!(0x107d552a, id.value),
+                    _ => panic!("Incorrect arbitration ID type"),
+                }
+            }
+            _ => panic!("Incorrect frame type"),
+        }
+    }
+}
@@ -1,40 +1,150 @@
 //!
 //! Transfer CRC calculation
 //!
+//! The transfer CRC is CRC-16/CCITT-FALSE. [`BitwiseCrc`] calculates it with little code,
+//! [`TableCrc`] calculates it faster using a 512-byte lookup table, and applications can implement
+//! [`CrcAlgorithm`] to use a hardware CRC peripheral.
+//!
+//! Transmitters and receivers use [`DefaultCrc`] unless another algorithm is provided. Each
+//! transmitter and receiver owns its algorithm instance, so an implementation can hold a
+//! peripheral handle or other state. The
+//! `crc-table` feature (enabled by default) makes it [`TableCrc`]. Applications such as bootloaders
+//! that need the smallest code can disable that feature to use [`BitwiseCrc`].
+//!
 
 /// CRC initial value
-const TRANSFER_CRC_INIT: u16 = 0xffff;
+pub(crate) const TRANSFER_CRC_INIT: u16 = 0xffff;
 /// CRC polynomial, not reversed, big endian
 const TRANSFER_CRC_POLY: u16 = 0x1021;
 
-/// Calculates the 32-bit transfer CRC
-pub struct TransferCrc {
+/// An implementation of the transfer CRC calculation
+///
+/// An implementation for a hardware CRC peripheral must use the CRC-16/CCITT-FALSE parameters
+/// (polynomial 0x1021, no input or output reflection, no final XOR) and continue from the
+/// provided CRC value.
+pub trait CrcAlgorithm {
+    /// Adds bytes to a CRC calculation and returns the new CRC value
+    fn update(&mut self, crc: u16, bytes: &[u8]) -> u16;
+}
+
+impl<A> CrcAlgorithm for &mut A
+where
+    A: CrcAlgorithm + ?Sized,
+{
+    fn update(&mut self, crc: u16, bytes: &[u8]) -> u16 {
+        (**self).update(crc, bytes)
+    }
+}
+
+/// Calculates the CRC one bit at a time
+///
+/// This uses the least code and no lookup table.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BitwiseCrc;
+
+impl CrcAlgorithm for BitwiseCrc {
+    fn update(&mut self, mut crc: u16, bytes: &[u8]) -> u16 {
+        for &byte in bytes {
+            crc ^= u16::from(byte) << 8;
+            for _bit in 0..8 {
+                if (crc & 0x8000) != 0 {
+                    crc = (crc << 1) ^ TRANSFER_CRC_POLY;
+                } else {
+                    crc <<= 1;
+                }
+            }
+        }
+        crc
+    }
+}
+
+/// Calculates the CRC one byte at a time using a 256-entry lookup table
+///
+/// This is faster than [`BitwiseCrc`], but the table uses 512 bytes of read-only memory.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TableCrc;
+
+/// The CRC of each possible byte value, used by `TableCrc`
+static CRC_TABLE: [u16; 256] = make_table();
+
+const fn make_table() -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < table.len() {
+        let mut value = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if (value & 0x8000) != 0 {
+                (value << 1) ^ TRANSFER_CRC_POLY
+            } else {
+                value << 1
+            };
+            bit += 1;
+        }
+        table[i] = value;
+        i += 1;
+    }
+    table
+}
+
+impl CrcAlgorithm for TableCrc {
+    fn update(&mut self, mut crc: u16, bytes: &[u8]) -> u16 {
+        for &byte in bytes {
+            let index = usize::from((crc >> 8) as u8 ^ byte);
+            crc = (crc << 8) ^ CRC_TABLE[index];
+        }
+        crc
+    }
+}
+
+/// The algorithm that transmitters and receivers use by default
+///
+/// This is [`TableCrc`] if the `crc-table` feature is enabled, or [`BitwiseCrc`] otherwise.
+#[cfg(feature = "crc-table")]
+pub type DefaultCrc = TableCrc;
+/// The algorithm that transmitters and receivers use by default
+///
+/// This is [`TableCrc`] if the `crc-table` feature is enabled, or [`BitwiseCrc`] otherwise.
+#[cfg(not(feature = "crc-table"))]
+pub type DefaultCrc = BitwiseCrc;
+
+/// Calculates the 16-bit transfer CRC
+///
+/// The CRC is calculated using the algorithm `A`. Because `&mut A` also implements
+/// [`CrcAlgorithm`], a `TransferCrc` can borrow an algorithm that is owned by something else.
+#[derive(Debug, Clone)]
+pub struct TransferCrc<A = DefaultCrc> {
     value: u16,
+    algorithm: A,
 }
 
 impl TransferCrc {
-    /// Creates a new CRC calculation
+    /// Creates a new CRC calculation that uses the default algorithm
     pub fn new() -> Self {
+        Self::with_algorithm(DefaultCrc::default())
+    }
+}
+
+impl<A> TransferCrc<A>
+where
+    A: CrcAlgorithm,
+{
+    /// Creates a new CRC calculation that uses the provided algorithm
+    pub fn with_algorithm(algorithm: A) -> Self {
         TransferCrc {
             value: TRANSFER_CRC_INIT,
+            algorithm,
         }
     }
 
     /// Adds a byte to the CRC calculation
     pub fn add(&mut self, byte: u8) {
-        self.value ^= u16::from(byte) << 8;
-        for _bit in 0..8 {
-            if (self.value & 0x8000) != 0 {
-                self.value = (self.value << 1) ^ TRANSFER_CRC_POLY;
-            } else {
-                self.value <<= 1;
-            }
-        }
+        self.add_bytes(&[byte]);
     }
 
     /// Adds all the bytes in a slice to the CRC calculation
     pub fn add_bytes(&mut self, bytes: &[u8]) {
-        bytes.iter().for_each(|&byte| self.add(byte));
+        self.value = self.algorithm.update(self.value, bytes);
     }
 
     /// Returns the CRC calculated over the bytes added through previous calls
@@ -48,3 +158,32 @@ impl Default for TransferCrc {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{BitwiseCrc, CrcAlgorithm, TableCrc, TransferCrc};
+
+    #[test]
+    fn check_value() {
+        // Check value for CRC-16/CCITT-FALSE
+        let mut bitwise = TransferCrc::with_algorithm(BitwiseCrc);
+        bitwise.add_bytes(b"123456789");
+        assert_eq!(0x29b1, bitwise.get());
+        let mut table = TransferCrc::with_algorithm(TableCrc);
+        table.add_bytes(b"123456789");
+        assert_eq!(0x29b1, table.get());
+    }
+
+    #[test]
+    fn algorithms_match() {
+        let bytes: [u8; 256] = core::array::from_fn(|i| (i * 7) as u8);
+        for length in 0..bytes.len() {
+            for start in [0x0000, 0xffff, 0x1234] {
+                assert_eq!(
+                    BitwiseCrc.update(start, &bytes[..length]),
+                    TableCrc.update(start, &bytes[..length])
+                );
+            }
+        }
+    }
+}
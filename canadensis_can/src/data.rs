@@ -21,6 +21,13 @@ impl fmt::Debug for CanId {
     }
 }
 
+impl CanId {
+    /// Creates a CAN ID from a u32, ignoring the bits that do not fit into 29 bits
+    pub const fn from_truncating(value: u32) -> Self {
+        CanId(value & CAN_ID_MASK)
+    }
+}
+
 impl TryFrom<u32> for CanId {
     type Error = InvalidValue;
 
@@ -123,6 +130,25 @@ impl Frame {
         }
     }
 
+    /// Creates a frame, or returns an error if the length of data is greater than FRAME_CAPACITY
+    ///
+    /// The loopback flag is set to false.
+    ///
+    /// Unlike [`new`](Self::new), this function never panics, so it is useful for building
+    /// frames from untrusted or fuzzer-generated input.
+    pub fn try_new(
+        timestamp: Microseconds32,
+        id: CanId,
+        data: &[u8],
+    ) -> core::result::Result<Self, InvalidValue> {
+        Ok(Frame {
+            timestamp,
+            id,
+            loopback: false,
+            data: heapless::Vec::from_slice(data).map_err(|_| InvalidValue)?,
+        })
+    }
+
     /// Sets the timestamp
     #[inline]
     pub fn set_timestamp(&mut self, timestamp: Microseconds32) {
@@ -175,3 +201,27 @@ impl Frame {
         self.timestamp
     }
 }
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl {
+    use super::{CanId, Frame, CAN_ID_MASK, FRAME_CAPACITY};
+    use arbitrary::{Arbitrary, Result, Unstructured};
+    use canadensis_core::time::Microseconds32;
+
+    impl<'a> Arbitrary<'a> for CanId {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            Ok(CanId(u.int_in_range(0..=CAN_ID_MASK)?))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for Frame {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let timestamp = Microseconds32::from_ticks(u.arbitrary()?);
+            let id = u.arbitrary()?;
+            let length = u.int_in_range(0..=FRAME_CAPACITY)?;
+            let mut frame = Frame::new(timestamp, id, u.bytes(length)?);
+            frame.set_loopback(u.arbitrary()?);
+            Ok(frame)
+        }
+    }
+}
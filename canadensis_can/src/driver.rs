@@ -1,6 +1,6 @@
 //! CAN driver traits
 
-use crate::data::Frame;
+use crate::data::{CanId, Frame};
 use crate::types::CanNodeId;
 use alloc::vec::Vec;
 use canadensis_core::subscription::Subscription;
@@ -30,6 +30,16 @@ where
     /// is greater than 1.
     fn try_reserve(&mut self, frames: usize) -> Result<(), OutOfMemoryError>;
 
+    /// Attempts to reserve space to transmit `frames` additional frames that will all have the
+    /// CAN ID `id`
+    ///
+    /// A driver with a queue may use the ID to make space by removing lower-priority frames,
+    /// depending on its [overflow policy](crate::queue::OverflowPolicy). The default
+    /// implementation calls `try_reserve()`.
+    fn try_reserve_for(&mut self, _id: CanId, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.try_reserve(frames)
+    }
+
     /// Attempts to send a frame without blocking
     ///
     /// If this driver contains a queue, this function may add the frame to the queue and not
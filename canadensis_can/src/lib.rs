@@ -9,21 +9,30 @@
 //!
 
 extern crate alloc;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
 extern crate canadensis_core;
 extern crate canadensis_filter_config;
 extern crate fallible_collections;
 extern crate heapless;
+#[cfg(feature = "log")]
 extern crate log;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 
-pub use crate::crc::TransferCrc;
+pub use crate::crc::{BitwiseCrc, CrcAlgorithm, DefaultCrc, TableCrc, TransferCrc};
 pub use crate::data::*;
 pub use crate::rx::CanReceiver;
 pub use crate::tx::CanTransmitter;
 pub use crate::types::*;
 
+pub mod candump;
+#[cfg(feature = "capture")]
+pub mod capture;
 mod crc;
 mod data;
 pub mod driver;
+pub mod load;
 pub mod queue;
 pub mod redundant;
 mod rx;
@@ -0,0 +1,642 @@
+//! Bus load analysis
+//!
+//! A [`LoadAnalyzer`] takes received frames (from a live bus or a capture file) and reports,
+//! for each time window, the bus utilization and the frames, bytes, and bus time used by each
+//! port and each node.
+//!
+//! The bus time of a frame includes all of its bits on the wire: the extended CAN ID, control
+//! bits, data, CRC, stuff bits, acknowledgement, end of frame, and the interframe space.
+//! Stuff bits depend on the content of the frame, so they are counted exactly for each frame.
+
+use alloc::collections::BTreeMap;
+use core::convert::TryFrom;
+
+use canadensis_core::time::{MicrosecondDuration32, Microseconds32};
+use canadensis_core::transfer::Header;
+use canadensis_core::{ServiceId, SubjectId};
+
+use crate::rx::parse_can_id;
+use crate::{round_up_frame_length, CanId, CanNodeId, CanTransferId, Frame};
+
+/// The number of bits after the CRC of each frame that are not subject to bit stuffing
+///
+/// These are the CRC delimiter, the acknowledgement slot and delimiter, the end of frame,
+/// and the interframe space.
+const TRAILING_BITS: u32 = 1 + 2 + 7 + 3;
+
+/// Bit rates of a CAN or CAN FD bus
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BusTiming {
+    /// Bit rate of the arbitration phase, bits per second
+    nominal_bit_rate: u32,
+    /// Bit rate of the data phase for CAN FD frames, bits per second, or None for a classic
+    /// CAN bus
+    data_bit_rate: Option<u32>,
+}
+
+impl BusTiming {
+    /// Returns the timing of a classic CAN bus with the provided bit rate (bits per second)
+    ///
+    /// Frames with more than 8 bytes of data can't appear on a classic CAN bus. If they do,
+    /// they are counted as CAN FD frames without bit rate switching.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `bit_rate` is zero.
+    pub fn classic(bit_rate: u32) -> Self {
+        assert_ne!(bit_rate, 0, "Bit rate must not be zero");
+        BusTiming {
+            nominal_bit_rate: bit_rate,
+            data_bit_rate: None,
+        }
+    }
+
+    /// Returns the timing of a CAN FD bus with the provided nominal (arbitration) and data
+    /// bit rates (bits per second)
+    ///
+    /// All frames are counted as CAN FD frames. If the two bit rates are different,
+    /// the frames use bit rate switching.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if either bit rate is zero.
+    pub fn fd(nominal_bit_rate: u32, data_bit_rate: u32) -> Self {
+        assert_ne!(nominal_bit_rate, 0, "Bit rate must not be zero");
+        assert_ne!(data_bit_rate, 0, "Bit rate must not be zero");
+        BusTiming {
+            nominal_bit_rate,
+            data_bit_rate: Some(data_bit_rate),
+        }
+    }
+
+    /// Returns the nominal (arbitration) bit rate
+    pub fn nominal_bit_rate(&self) -> u32 {
+        self.nominal_bit_rate
+    }
+
+    /// Returns the data bit rate for CAN FD, or None for a classic CAN bus
+    pub fn data_bit_rate(&self) -> Option<u32> {
+        self.data_bit_rate
+    }
+
+    /// Returns the time, in nanoseconds, that a frame occupies the bus
+    ///
+    /// This includes stuff bits and the interframe space after the frame.
+    pub fn frame_time(&self, frame: &Frame) -> u64 {
+        let bits = match self.data_bit_rate {
+            None if frame.data().len() <= 8 => classic_frame_bits(frame.id(), frame.data()),
+            None => fd_frame_bits(frame.id(), frame.data(), false),
+            Some(data_bit_rate) => fd_frame_bits(
+                frame.id(),
+                frame.data(),
+                data_bit_rate != self.nominal_bit_rate,
+            ),
+        };
+        let data_bit_rate = self.data_bit_rate.unwrap_or(self.nominal_bit_rate);
+        bit_time(bits.nominal, self.nominal_bit_rate) + bit_time(bits.data, data_bit_rate)
+    }
+}
+
+/// Returns the time in nanoseconds to transmit some bits
+fn bit_time(bits: u32, bit_rate: u32) -> u64 {
+    u64::from(bits) * 1_000_000_000 / u64::from(bit_rate)
+}
+
+/// The numbers of bits in a frame that are sent at the nominal and data bit rates
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct FrameBits {
+    nominal: u32,
+    data: u32,
+}
+
+/// Counts the bits in a classic CAN frame with an extended ID
+fn classic_frame_bits(id: CanId, data: &[u8]) -> FrameBits {
+    let mut stuffer = Stuffer::new();
+    push_arbitration(&mut stuffer, id);
+    // RTR, r1, r0
+    stuffer.push_bits(0, 3);
+    stuffer.push_bits(data.len() as u32, 4);
+    for &byte in data {
+        stuffer.push_bits(u32::from(byte), 8);
+    }
+    let crc = stuffer.crc;
+    stuffer.push_bits(u32::from(crc), 15);
+    FrameBits {
+        nominal: stuffer.bits + stuffer.stuff_bits + TRAILING_BITS,
+        data: 0,
+    }
+}
+
+/// Counts the bits in a CAN FD frame with an extended ID
+///
+/// If `switch` is true, the ESI bit through the CRC are counted as data phase bits.
+fn fd_frame_bits(id: CanId, data: &[u8], switch: bool) -> FrameBits {
+    let length = round_up_frame_length(data.len());
+    let mut stuffer = Stuffer::new();
+    push_arbitration(&mut stuffer, id);
+    // RRS, FDF, res
+    stuffer.push_bits(0b010, 3);
+    // BRS
+    stuffer.push_bits(u32::from(switch), 1);
+    let arbitration_bits = stuffer.bits + stuffer.stuff_bits;
+
+    // ESI
+    stuffer.push_bits(0, 1);
+    stuffer.push_bits(u32::from(dlc(length)), 4);
+    for &byte in data {
+        stuffer.push_bits(u32::from(byte), 8);
+    }
+    // Padding
+    for _ in data.len()..length {
+        stuffer.push_bits(0, 8);
+    }
+    // The stuff count and CRC have fixed stuff bits instead of dynamic stuff bits
+    let crc_bits = if length <= 16 { 4 + 17 + 6 } else { 4 + 21 + 7 };
+    let data_bits = stuffer.bits + stuffer.stuff_bits - arbitration_bits + crc_bits;
+
+    if switch {
+        FrameBits {
+            nominal: arbitration_bits + TRAILING_BITS,
+            data: data_bits,
+        }
+    } else {
+        FrameBits {
+            nominal: arbitration_bits + data_bits + TRAILING_BITS,
+            data: 0,
+        }
+    }
+}
+
+/// Pushes the start of frame bit and the extended ID bits (including SRR and IDE)
+fn push_arbitration(stuffer: &mut Stuffer, id: CanId) {
+    let id = u32::from(id);
+    // SOF
+    stuffer.push_bits(0, 1);
+    // Base ID
+    stuffer.push_bits(id >> 18, 11);
+    // SRR, IDE
+    stuffer.push_bits(0b11, 2);
+    // ID extension
+    stuffer.push_bits(id & 0x3ffff, 18);
+}
+
+/// Returns the data length code for a frame length that CAN FD can represent
+fn dlc(length: usize) -> u8 {
+    match length {
+        0..=8 => length as u8,
+        12 => 9,
+        16 => 10,
+        20 => 11,
+        24 => 12,
+        32 => 13,
+        48 => 14,
+        _ => 15,
+    }
+}
+
+/// Counts bits and dynamic stuff bits, and calculates the classic CAN CRC-15
+struct Stuffer {
+    /// The number of bits pushed, not including stuff bits
+    bits: u32,
+    /// The number of stuff bits inserted
+    stuff_bits: u32,
+    /// The value of the last bit on the bus
+    last: bool,
+    /// The number of consecutive bits on the bus with the value `last`
+    run: u8,
+    /// The CRC-15 of the bits pushed (not including stuff bits)
+    crc: u16,
+}
+
+impl Stuffer {
+    fn new() -> Self {
+        Stuffer {
+            bits: 0,
+            stuff_bits: 0,
+            last: false,
+            run: 0,
+            crc: 0,
+        }
+    }
+
+    /// Pushes the `count` least significant bits of `value`, most significant bit first
+    fn push_bits(&mut self, value: u32, count: u32) {
+        for i in (0..count).rev() {
+            self.push((value >> i) & 1 == 1);
+        }
+    }
+
+    fn push(&mut self, bit: bool) {
+        self.bits += 1;
+        let crc_next = bit ^ (self.crc & 0x4000 != 0);
+        self.crc = (self.crc << 1) & 0x7fff;
+        if crc_next {
+            self.crc ^= 0x4599;
+        }
+
+        if self.run != 0 && bit == self.last {
+            self.run += 1;
+        } else {
+            self.last = bit;
+            self.run = 1;
+        }
+        if self.run == 5 {
+            // Insert a stuff bit with the opposite value, which starts a new run
+            self.stuff_bits += 1;
+            self.last = !bit;
+            self.run = 1;
+        }
+    }
+}
+
+/// A Cyphal port that frames can belong to
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Port {
+    /// Messages on a subject
+    Message(SubjectId),
+    /// Requests for a service
+    Request(ServiceId),
+    /// Responses from a service
+    Response(ServiceId),
+}
+
+/// Frames, bytes, and bus time used by some part of the traffic on a bus
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Traffic {
+    /// The number of frames
+    pub frames: u32,
+    /// The number of transfers (counted as the number of start-of-transfer frames)
+    pub transfers: u32,
+    /// The number of data bytes in the frames, including tail bytes
+    pub bytes: u64,
+    /// The time that the frames occupied the bus, in nanoseconds
+    pub bus_time: u64,
+}
+
+impl Traffic {
+    /// Returns the number of frames per second over a duration
+    pub fn frame_rate(&self, duration: MicrosecondDuration32) -> f32 {
+        per_second(self.frames as f32, duration)
+    }
+
+    /// Returns the number of transfers per second over a duration
+    pub fn transfer_rate(&self, duration: MicrosecondDuration32) -> f32 {
+        per_second(self.transfers as f32, duration)
+    }
+
+    /// Returns the number of data bytes per second over a duration
+    pub fn bandwidth(&self, duration: MicrosecondDuration32) -> f32 {
+        per_second(self.bytes as f32, duration)
+    }
+
+    /// Returns the fraction of a duration (0 to 1) that the frames occupied the bus
+    pub fn utilization(&self, duration: MicrosecondDuration32) -> f32 {
+        if duration.ticks() == 0 {
+            0.0
+        } else {
+            self.bus_time as f32 / (duration.ticks() as f32 * 1000.0)
+        }
+    }
+
+    fn add(&mut self, frame: &Frame, start_of_transfer: bool, bus_time: u64) {
+        self.frames += 1;
+        self.transfers += u32::from(start_of_transfer);
+        self.bytes += frame.data().len() as u64;
+        self.bus_time += bus_time;
+    }
+}
+
+fn per_second(count: f32, duration: MicrosecondDuration32) -> f32 {
+    if duration.ticks() == 0 {
+        0.0
+    } else {
+        count * 1_000_000.0 / duration.ticks() as f32
+    }
+}
+
+/// The traffic on a bus during one time window
+#[derive(Debug, Clone)]
+pub struct LoadReport {
+    start: Microseconds32,
+    duration: MicrosecondDuration32,
+    total: Traffic,
+    invalid: Traffic,
+    ports: BTreeMap<Port, Traffic>,
+    nodes: BTreeMap<Option<CanNodeId>, Traffic>,
+}
+
+impl LoadReport {
+    fn new(start: Microseconds32, duration: MicrosecondDuration32) -> Self {
+        LoadReport {
+            start,
+            duration,
+            total: Traffic::default(),
+            invalid: Traffic::default(),
+            ports: BTreeMap::new(),
+            nodes: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the start time of this window
+    pub fn start(&self) -> Microseconds32 {
+        self.start
+    }
+
+    /// Returns the duration of this window
+    pub fn duration(&self) -> MicrosecondDuration32 {
+        self.duration
+    }
+
+    /// Returns the fraction of this window (0 to 1) that frames occupied the bus
+    pub fn utilization(&self) -> f32 {
+        self.total.utilization(self.duration)
+    }
+
+    /// Returns the traffic from all frames, including invalid frames
+    pub fn total(&self) -> &Traffic {
+        &self.total
+    }
+
+    /// Returns the traffic from frames that were not valid Cyphal frames
+    pub fn invalid(&self) -> &Traffic {
+        &self.invalid
+    }
+
+    /// Returns the traffic on each port, in order by port
+    pub fn ports(&self) -> &BTreeMap<Port, Traffic> {
+        &self.ports
+    }
+
+    /// Returns the traffic sent by each node, in order by node ID
+    ///
+    /// The traffic from anonymous nodes has the key `None`.
+    pub fn nodes(&self) -> &BTreeMap<Option<CanNodeId>, Traffic> {
+        &self.nodes
+    }
+
+    fn add(&mut self, timing: &BusTiming, frame: &Frame) {
+        let bus_time = timing.frame_time(frame);
+        let tail = frame.data().last().copied();
+        let header = tail.and_then(|tail| {
+            let transfer_id = CanTransferId::try_from(tail & 0x1f).expect("Invalid transfer ID");
+            parse_can_id(frame.id(), frame.timestamp(), transfer_id).ok()
+        });
+        let start_of_transfer = tail.map(|tail| tail & 0x80 != 0).unwrap_or(false);
+        self.total.add(frame, start_of_transfer, bus_time);
+        match header {
+            Some(header) => {
+                let port = match &header {
+                    Header::Message(header) => Port::Message(header.subject),
+                    Header::Request(header) => Port::Request(header.service),
+                    Header::Response(header) => Port::Response(header.service),
+                };
+                self.ports
+                    .entry(port)
+                    .or_default()
+                    .add(frame, start_of_transfer, bus_time);
+                self.nodes.entry(header.source().copied()).or_default().add(
+                    frame,
+                    start_of_transfer,
+                    bus_time,
+                );
+            }
+            None => self.invalid.add(frame, start_of_transfer, bus_time),
+        }
+    }
+}
+
+/// Divides received frames into time windows and reports the traffic in each window
+///
+/// Frames must be pushed in order by timestamp. A window ends when a frame or a call to
+/// [`advance`](Self::advance) has a time at or after the end of the window. If no frames arrive
+/// for more than a whole window, the empty windows are skipped.
+#[derive(Debug, Clone)]
+pub struct LoadAnalyzer {
+    timing: BusTiming,
+    window: MicrosecondDuration32,
+    current: Option<LoadReport>,
+}
+
+impl LoadAnalyzer {
+    /// Creates an analyzer for a bus with the provided timing that reports traffic in windows
+    /// of the provided duration
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `window` is zero.
+    pub fn new(timing: BusTiming, window: MicrosecondDuration32) -> Self {
+        assert_ne!(window.ticks(), 0, "Window must not be zero");
+        LoadAnalyzer {
+            timing,
+            window,
+            current: None,
+        }
+    }
+
+    /// Returns the bus timing
+    pub fn timing(&self) -> &BusTiming {
+        &self.timing
+    }
+
+    /// Adds a received frame
+    ///
+    /// If the frame is after the end of the current window, this function returns the report
+    /// for the window and starts a new window that contains the frame.
+    pub fn push(&mut self, frame: &Frame) -> Option<LoadReport> {
+        let finished = self.advance(frame.timestamp());
+        let window = self.window;
+        self.current
+            .get_or_insert_with(|| LoadReport::new(frame.timestamp(), window))
+            .add(&self.timing, frame);
+        finished
+    }
+
+    /// Moves time forward without adding a frame
+    ///
+    /// If `now` is at or after the end of the current window, this function returns the report
+    /// for the window. This allows windows to end on a bus with no traffic.
+    pub fn advance(&mut self, now: Microseconds32) -> Option<LoadReport> {
+        let current = self.current.as_ref()?;
+        let elapsed = now.checked_duration_since(current.start)?;
+        if elapsed < self.window {
+            return None;
+        }
+        let whole_windows = elapsed.ticks() / self.window.ticks();
+        let next_start =
+            current.start + MicrosecondDuration32::from_ticks(whole_windows * self.window.ticks());
+        self.current
+            .replace(LoadReport::new(next_start, self.window))
+    }
+
+    /// Ends the current window early at the provided time and returns its report
+    ///
+    /// This is useful at the end of a capture file, where the last window is usually
+    /// incomplete. The report's duration is the time from the start of the window to `end`.
+    pub fn finish(&mut self, end: Microseconds32) -> Option<LoadReport> {
+        let mut report = self.current.take()?;
+        report.duration = end
+            .checked_duration_since(report.start)
+            .unwrap_or_else(|| MicrosecondDuration32::from_ticks(0))
+            .min(self.window);
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use canadensis_core::time::Microseconds32;
+    use canadensis_core::Priority;
+
+    use crate::data::FRAME_CAPACITY;
+
+    fn frame(time: u32, id: u32, data: &[u8]) -> Frame {
+        Frame::new(
+            Microseconds32::from_ticks(time),
+            CanId::try_from(id).unwrap(),
+            data,
+        )
+    }
+
+    /// Returns a CAN ID for a message frame
+    fn message_id(subject: u16, source: Option<u8>) -> u32 {
+        let priority = Priority::Nominal as u32;
+        let anonymous = source.is_none() as u32;
+        (priority << 26)
+            | (anonymous << 24)
+            | (3 << 21)
+            | (u32::from(subject) << 8)
+            | u32::from(source.unwrap_or(0x55))
+    }
+
+    #[test]
+    fn classic_frame_time() {
+        let timing = BusTiming::classic(1_000_000);
+        for length in 0..=8 {
+            let data = [0xaa; 8];
+            let frame = frame(0, message_id(7509, Some(3)), &data[..length]);
+            let bits = classic_frame_bits(frame.id(), frame.data()).nominal;
+            // Without stuff bits, there are 67 bits plus the data
+            let unstuffed = 67 + 8 * length as u32;
+            // At most one stuff bit for every four bits from SOF through the CRC
+            let worst = unstuffed + (54 + 8 * length as u32 - 1) / 4;
+            assert!(bits >= unstuffed && bits <= worst, "{} bits", bits);
+            assert_eq!(u64::from(bits) * 1000, timing.frame_time(&frame));
+        }
+        // A run of zeros requires a stuff bit after every 5 zeros
+        let zeros = frame(0, message_id(7509, Some(3)), &[0; 8]);
+        let alternating = frame(0, message_id(7509, Some(3)), &[0x55; 8]);
+        assert!(
+            classic_frame_bits(zeros.id(), zeros.data()).nominal
+                >= classic_frame_bits(alternating.id(), alternating.data()).nominal + 12
+        );
+    }
+
+    #[test]
+    fn stuffing() {
+        let mut stuffer = Stuffer::new();
+        stuffer.push_bits(0, 5);
+        assert_eq!(1, stuffer.stuff_bits);
+        // The stuff bit (1) starts a new run, so four more ones make another stuff bit
+        stuffer.push_bits(0b1111, 4);
+        assert_eq!(2, stuffer.stuff_bits);
+        stuffer.push_bits(0b1010, 4);
+        assert_eq!(2, stuffer.stuff_bits);
+        assert_eq!(13, stuffer.bits);
+    }
+
+    #[test]
+    fn fd_frame_time() {
+        if FRAME_CAPACITY < 64 {
+            return;
+        }
+        let data = [0x55; 64];
+        let id = message_id(100, Some(10));
+        let switched = fd_frame_bits(CanId::try_from(id).unwrap(), &data, true);
+        let unswitched = fd_frame_bits(CanId::try_from(id).unwrap(), &data, false);
+        assert_eq!(
+            switched.nominal + switched.data,
+            unswitched.nominal + unswitched.data
+        );
+        // 36 arbitration bits and 13 trailing bits, plus any stuff bits in the arbitration
+        // field
+        assert!(switched.nominal >= 49 && switched.nominal <= 49 + 8);
+        // ESI, DLC, data, stuff count, CRC, and fixed stuff bits
+        assert!(switched.data >= 1 + 4 + 512 + 4 + 21 + 7);
+
+        let frame = frame(0, id, &data);
+        let time = BusTiming::fd(1_000_000, 4_000_000).frame_time(&frame);
+        assert_eq!(
+            u64::from(switched.nominal) * 1000 + u64::from(switched.data) * 250,
+            time
+        );
+        // 10 bytes are padded to 12, and the shorter CRC is used
+        let padded = fd_frame_bits(CanId::try_from(id).unwrap(), &data[..10], true);
+        assert!(padded.data >= 1 + 4 + 96 + 4 + 17 + 6);
+        assert!(padded.data < switched.data);
+    }
+
+    #[test]
+    fn windows() {
+        let timing = BusTiming::classic(1_000_000);
+        let mut analyzer = LoadAnalyzer::new(timing, MicrosecondDuration32::from_ticks(1000));
+
+        let heartbeat = [0, 0, 0, 0, 0, 0, 0, 0xe0];
+        let first = frame(100, message_id(7509, Some(3)), &heartbeat);
+        let second = frame(200, message_id(7509, Some(4)), &heartbeat);
+        let anonymous = frame(300, message_id(7509, None), &heartbeat);
+        // Service request 430 from 8 to 9
+        let request_id = (4 << 26) | (3 << 24) | (430 << 14) | (9 << 7) | 8;
+        let request = frame(400, request_id, &[0xe1]);
+        let invalid = frame(500, message_id(10, Some(3)), &[]);
+
+        for frame in [&first, &second, &anonymous, &request, &invalid].iter() {
+            assert!(analyzer.push(frame).is_none());
+        }
+        assert!(analyzer.advance(Microseconds32::from_ticks(1099)).is_none());
+        let report = analyzer
+            .advance(Microseconds32::from_ticks(3500))
+            .expect("No report");
+        assert_eq!(100, report.start().ticks());
+        assert_eq!(1000, report.duration().ticks());
+        assert_eq!(5, report.total().frames);
+        assert_eq!(4, report.total().transfers);
+        assert_eq!(25, report.total().bytes);
+        assert_eq!(1, report.invalid().frames);
+
+        let heartbeats = report.ports()[&Port::Message(SubjectId::try_from(7509).unwrap())];
+        assert_eq!(3, heartbeats.frames);
+        assert_eq!(3000.0, heartbeats.frame_rate(report.duration()));
+        assert_eq!(24000.0, heartbeats.bandwidth(report.duration()));
+        assert_eq!(
+            1,
+            report.ports()[&Port::Request(ServiceId::try_from(430).unwrap())].frames
+        );
+        assert_eq!(2, report.ports().len());
+
+        let node_ids: alloc::vec::Vec<Option<u8>> =
+            report.nodes().keys().map(|id| id.map(u8::from)).collect();
+        assert_eq!(node_ids, [None, Some(3), Some(4), Some(8)]);
+
+        let expected_time = [&first, &second, &anonymous, &request, &invalid]
+            .iter()
+            .map(|frame| timing.frame_time(frame))
+            .sum::<u64>();
+        assert_eq!(expected_time, report.total().bus_time);
+        assert_eq!(expected_time as f32 / 1_000_000.0, report.utilization());
+
+        // The next window starts at a multiple of the window duration after the first
+        let report = analyzer
+            .push(&frame(4200, message_id(7509, Some(3)), &heartbeat))
+            .expect("No report");
+        assert_eq!(3100, report.start().ticks());
+        assert_eq!(0, report.total().frames);
+        let report = analyzer
+            .finish(Microseconds32::from_ticks(4600))
+            .expect("No report");
+        assert_eq!(4100, report.start().ticks());
+        assert_eq!(500, report.duration().ticks());
+        assert_eq!(1, report.total().frames);
+        assert!(analyzer.finish(Microseconds32::from_ticks(5000)).is_none());
+    }
+}
@@ -1,5 +1,5 @@
-use crate::queue::FrameQueue;
-use crate::Frame;
+use crate::queue::{FrameQueue, OverflowCounts, OverflowPolicy};
+use crate::{CanId, Frame};
 use canadensis_core::OutOfMemoryError;
 use core::mem::{self, MaybeUninit};
 use core::ptr;
@@ -8,6 +8,9 @@ use core::ptr;
 ///
 /// `N` is the maximum number of frames that the queue can hold. This should be at least as large
 /// as the number of frames required for the largest outgoing transfer that will be sent.
+///
+/// When the queue does not have space for a new transfer, it applies its [`OverflowPolicy`]. The
+/// default policy rejects the new transfer.
 #[derive(Debug)]
 pub struct ArrayQueue<const N: usize> {
     /// The frames in this queue
@@ -19,11 +22,20 @@ pub struct ArrayQueue<const N: usize> {
     head: usize,
     /// The number of valid frames in the queue
     length: usize,
+    /// What to do when a new transfer does not fit
+    policy: OverflowPolicy,
+    /// The number of times the overflow policy has been applied
+    overflow_counts: OverflowCounts,
 }
 
 impl<const N: usize> ArrayQueue<N> {
     /// Returns a new emtpy queue
     pub fn new() -> Self {
+        Self::with_overflow_policy(OverflowPolicy::default())
+    }
+
+    /// Returns a new empty queue that handles overflow using the provided policy
+    pub fn with_overflow_policy(policy: OverflowPolicy) -> Self {
         // Need to use unsafe code to initialize the array of items.
         let items: [Frame; N] = unsafe {
             let mut items: MaybeUninit<[Frame; N]> = MaybeUninit::uninit();
@@ -46,9 +58,24 @@ impl<const N: usize> ArrayQueue<N> {
             items,
             head: 0,
             length: 0,
+            policy,
+            overflow_counts: OverflowCounts::default(),
         }
     }
 
+    /// Returns the overflow policy
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+    /// Sets the overflow policy
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.policy = policy;
+    }
+    /// Returns the number of times that the overflow policy has been applied
+    pub fn overflow_counts(&self) -> &OverflowCounts {
+        &self.overflow_counts
+    }
+
     /// Returns the number of frames in this queue
     pub fn len(&self) -> usize {
         self.length
@@ -74,6 +101,50 @@ impl<const N: usize> ArrayQueue<N> {
             self.head = self.head.wrapping_add(N - 1) % N;
         }
     }
+
+    /// Returns the index in self.items of the frame at position `position` from the front
+    fn index(&self, position: usize) -> usize {
+        self.head.wrapping_add(position) % N
+    }
+
+    /// Returns the CAN ID and transfer ID of the transfer at the back of the queue, which will be
+    /// sent last
+    fn last_transfer(&self) -> Option<(CanId, Option<u8>)> {
+        if self.length != 0 {
+            let frame = &self.items[self.index(self.length - 1)];
+            Some((frame.id(), transfer_id(frame)))
+        } else {
+            None
+        }
+    }
+
+    /// Removes frames that match a predicate, keeping the other frames in order
+    ///
+    /// If `first_only` is true, this removes at most one frame (the one closest to the front).
+    fn remove_frames<F>(&mut self, mut predicate: F, first_only: bool)
+    where
+        F: FnMut(&Frame) -> bool,
+    {
+        let mut kept = 0;
+        let mut removed_any = false;
+        for position in 0..self.length {
+            let index = self.index(position);
+            if predicate(&self.items[index]) && !(first_only && removed_any) {
+                self.items[index] = Frame::default();
+                removed_any = true;
+            } else {
+                let kept_index = self.index(kept);
+                self.items.swap(kept_index, index);
+                kept += 1;
+            }
+        }
+        self.length = kept;
+    }
+}
+
+/// Returns the transfer ID from the tail byte of a frame
+fn transfer_id(frame: &Frame) -> Option<u8> {
+    frame.data().last().map(|tail| tail & 0x1f)
 }
 
 impl<const N: usize> FrameQueue for ArrayQueue<N> {
@@ -83,10 +154,65 @@ impl<const N: usize> FrameQueue for ArrayQueue<N> {
             Ok(())
         } else {
             // Too full, can't allocate memory
+            self.overflow_counts.rejected_transfers =
+                self.overflow_counts.rejected_transfers.wrapping_add(1);
             Err(OutOfMemoryError)
         }
     }
 
+    fn try_reserve_for(&mut self, id: CanId, additional: usize) -> Result<(), OutOfMemoryError> {
+        let free_capacity = N - self.length;
+        if free_capacity >= additional || self.policy == OverflowPolicy::Reject {
+            return self.try_reserve(additional);
+        }
+        // Only frames that would be sent after the new frames can be removed. Because the queue
+        // is in order by CAN ID, they are all at the back.
+        let removable = (0..self.length)
+            .rev()
+            .take_while(|&position| self.items[self.index(position)].id() > id)
+            .count();
+        if free_capacity + removable < additional {
+            // Removing frames would not make enough space
+            return self.try_reserve(additional);
+        }
+        while N - self.length < additional {
+            // Because enough frames are removable, the last transfer always has a greater CAN ID
+            let (last_id, last_transfer_id) = self.last_transfer().expect("Queue empty");
+            let is_last_transfer =
+                |frame: &Frame| frame.id() == last_id && transfer_id(frame) == last_transfer_id;
+            match self.policy {
+                OverflowPolicy::Reject => unreachable!(),
+                OverflowPolicy::DropLowestPriorityTransfer => {
+                    self.remove_frames(is_last_transfer, false);
+                    self.overflow_counts.dropped_transfers =
+                        self.overflow_counts.dropped_transfers.wrapping_add(1);
+                    #[cfg(feature = "log")]
+                    log::warn!(
+                        "Transmit queue dropped transfer {:?} with CAN ID {:?} to make space for \
+                        CAN ID {:?}",
+                        last_transfer_id,
+                        last_id,
+                        id
+                    );
+                }
+                OverflowPolicy::DropOldestLowestPriorityFrame => {
+                    self.remove_frames(is_last_transfer, true);
+                    self.overflow_counts.dropped_frames =
+                        self.overflow_counts.dropped_frames.wrapping_add(1);
+                    #[cfg(feature = "log")]
+                    log::warn!(
+                        "Transmit queue dropped a frame of transfer {:?} with CAN ID {:?} to \
+                        make space for CAN ID {:?}",
+                        last_transfer_id,
+                        last_id,
+                        id
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn shrink_to_fit(&mut self) {
         // Doesn't dynamically allocate memory, nothing to do
     }
@@ -183,8 +309,10 @@ impl<const N: usize> Default for ArrayQueue<N> {
 #[cfg(test)]
 mod test {
     use super::ArrayQueue;
-    use crate::queue::FrameQueue;
+    use crate::queue::{FrameQueue, OverflowCounts, OverflowPolicy};
     use crate::{CanId, Frame};
+    use alloc::vec;
+    use alloc::vec::Vec;
     use canadensis_core::time::Microseconds32;
     use core::convert::TryFrom;
 
@@ -336,4 +464,104 @@ mod test {
         assert_eq!(queue.pop_frame(), Some(frame_with_id(128, 6)));
         assert_eq!(queue.pop_frame(), Some(frame_with_id(128, 7)));
     }
+
+    /// Returns a queue containing three transfers
+    ///
+    /// * CAN ID 10, transfer ID 0: 1 frame
+    /// * CAN ID 128, transfer ID 1: 2 frames
+    /// * CAN ID 128, transfer ID 2: 3 frames
+    fn full_queue(policy: OverflowPolicy) -> ArrayQueue<6> {
+        let mut queue = ArrayQueue::<6>::with_overflow_policy(policy);
+        for (id, tail) in [
+            (128, 0xa1),
+            (128, 0x41),
+            (10, 0xe0),
+            (128, 0x82),
+            (128, 0x02),
+            (128, 0x42),
+        ] {
+            queue.push_frame(frame_with_id(id, tail)).unwrap();
+        }
+        queue
+    }
+
+    fn remaining_frames<const N: usize>(mut queue: ArrayQueue<N>) -> Vec<Frame> {
+        core::iter::from_fn(|| queue.pop_frame()).collect()
+    }
+
+    #[test]
+    fn overflow_reject() {
+        let mut queue = full_queue(OverflowPolicy::Reject);
+        assert!(queue
+            .try_reserve_for(CanId::try_from(20).unwrap(), 1)
+            .is_err());
+        assert_eq!(1, queue.overflow_counts().rejected_transfers);
+        assert_eq!(6, queue.len());
+    }
+
+    #[test]
+    fn overflow_drop_transfer() {
+        let mut queue = full_queue(OverflowPolicy::DropLowestPriorityTransfer);
+        queue
+            .try_reserve_for(CanId::try_from(20).unwrap(), 2)
+            .unwrap();
+        assert_eq!(1, queue.overflow_counts().dropped_transfers);
+        assert_eq!(3, queue.len());
+
+        // The remaining frames have a lower CAN ID than the new transfer, so they stay
+        queue.push_frame(frame_with_id(200, 0xe3)).unwrap();
+        queue.push_frame(frame_with_id(200, 0xe4)).unwrap();
+        queue.push_frame(frame_with_id(200, 0xe5)).unwrap();
+        assert!(queue
+            .try_reserve_for(CanId::try_from(200).unwrap(), 1)
+            .is_err());
+        assert_eq!(1, queue.overflow_counts().rejected_transfers);
+        assert_eq!(
+            vec![
+                frame_with_id(10, 0xe0),
+                frame_with_id(128, 0xa1),
+                frame_with_id(128, 0x41),
+                frame_with_id(200, 0xe3),
+                frame_with_id(200, 0xe4),
+                frame_with_id(200, 0xe5),
+            ],
+            remaining_frames(queue)
+        );
+    }
+
+    #[test]
+    fn overflow_drop_frames() {
+        let mut queue = full_queue(OverflowPolicy::DropOldestLowestPriorityFrame);
+        queue
+            .try_reserve_for(CanId::try_from(20).unwrap(), 2)
+            .unwrap();
+        assert_eq!(2, queue.overflow_counts().dropped_frames);
+        assert_eq!(
+            vec![
+                frame_with_id(10, 0xe0),
+                frame_with_id(128, 0xa1),
+                frame_with_id(128, 0x41),
+                frame_with_id(128, 0x42),
+            ],
+            remaining_frames(queue)
+        );
+    }
+
+    #[test]
+    fn overflow_not_enough_removable() {
+        // Removing the 5 frames with CAN ID 128 would not make space for 6 frames
+        let mut queue = full_queue(OverflowPolicy::DropLowestPriorityTransfer);
+        assert!(queue
+            .try_reserve_for(CanId::try_from(20).unwrap(), 6)
+            .is_err());
+        assert_eq!(
+            OverflowCounts {
+                rejected_transfers: 1,
+                dropped_transfers: 0,
+                dropped_frames: 0,
+            },
+            *queue.overflow_counts()
+        );
+        assert_eq!(6, queue.len());
+    }
 }
@@ -11,11 +11,38 @@ use core::marker::PhantomData;
 
 use crate::driver::{ReceiveDriver, TransmitDriver};
 use crate::types::CanNodeId;
-use crate::Frame;
+use crate::{CanId, Frame};
 use canadensis_core::subscription::Subscription;
 use canadensis_core::time::{Clock, Microseconds32};
 use canadensis_core::{nb, OutOfMemoryError};
 
+/// What a queue does when it does not have space for a new transfer
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum OverflowPolicy {
+    /// Reject the new transfer
+    #[default]
+    Reject,
+    /// Remove whole lower-priority transfers from the queue, starting with the transfer that
+    /// would be sent last, until the new transfer fits
+    DropLowestPriorityTransfer,
+    /// Remove lower-priority frames from the queue one at a time until the new transfer fits
+    ///
+    /// Each removed frame is the oldest frame of the transfer that would be sent last. The
+    /// receivers will discard the rest of that transfer.
+    DropOldestLowestPriorityFrame,
+}
+
+/// The number of times that a queue has applied its overflow policy
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct OverflowCounts {
+    /// The number of new transfers that did not fit and were rejected
+    pub rejected_transfers: u64,
+    /// The number of queued transfers removed by [`OverflowPolicy::DropLowestPriorityTransfer`]
+    pub dropped_transfers: u64,
+    /// The number of queued frames removed by [`OverflowPolicy::DropOldestLowestPriorityFrame`]
+    pub dropped_frames: u64,
+}
+
 /// A queue of outgoing frames
 pub trait FrameQueue {
     /// Attempts to reserve memory for some number of additional frames
@@ -24,6 +51,15 @@ pub trait FrameQueue {
     /// `return Ok(())`.
     ///
     fn try_reserve(&mut self, additional: usize) -> Result<(), OutOfMemoryError>;
+    /// Attempts to reserve memory for some number of additional frames that will all have the
+    /// CAN ID `id`
+    ///
+    /// A queue with an [`OverflowPolicy`] may remove frames with a greater CAN ID (lower priority)
+    /// than `id` to make space. Frames that would be sent before the new frames are never removed.
+    /// The default implementation calls `try_reserve()`.
+    fn try_reserve_for(&mut self, _id: CanId, additional: usize) -> Result<(), OutOfMemoryError> {
+        self.try_reserve(additional)
+    }
     /// Attempts to free memory by reducing excess capacity in this queue
     fn shrink_to_fit(&mut self);
 
@@ -70,6 +106,15 @@ impl<C, Q, D> SingleQueueDriver<C, Q, D> {
         (self.queue, self.driver)
     }
 
+    /// Returns a reference to the queue
+    pub fn queue(&self) -> &Q {
+        &self.queue
+    }
+    /// Returns a mutable reference to the queue
+    pub fn queue_mut(&mut self) -> &mut Q {
+        &mut self.queue
+    }
+
     /// Returns a reference to the driver
     pub fn driver(&self) -> &D {
         &self.driver
@@ -92,6 +137,10 @@ where
         self.queue.try_reserve(frames)
     }
 
+    fn try_reserve_for(&mut self, id: CanId, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.queue.try_reserve_for(id, frames)
+    }
+
     /// Adds a frame to the back of the queue
     ///
     /// This function returns `Err(nb::Error::WouldBlock)` if the queue is full.
@@ -2,9 +2,9 @@
 //! receive messages
 
 use crate::driver::{ReceiveDriver, TransmitDriver};
-use crate::queue::{ArrayQueue, FrameQueue};
+use crate::queue::{ArrayQueue, FrameQueue, OverflowCounts, OverflowPolicy};
 use crate::types::CanNodeId;
-use crate::Frame;
+use crate::{CanId, Frame};
 use alloc::vec::Vec;
 use canadensis_core::subscription::Subscription;
 use canadensis_core::time::Clock;
@@ -65,6 +65,16 @@ impl<const TC: usize, const RC: usize> QueueOnlyDriver<TC, RC> {
         self.tx_queue.return_frame(frame)
     }
 
+    /// Sets what the transmit queue does when it does not have space for a new transfer
+    pub fn set_tx_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.tx_queue.set_overflow_policy(policy)
+    }
+
+    /// Returns the number of times that the transmit queue has applied its overflow policy
+    pub fn tx_overflow_counts(&self) -> &OverflowCounts {
+        self.tx_queue.overflow_counts()
+    }
+
     /// Returns the subscriptions provided in the last call to `apply_filters()`
     ///
     /// This function returns None
@@ -92,6 +102,10 @@ impl<C: Clock, const TC: usize, const RC: usize> TransmitDriver<C> for QueueOnly
         self.tx_queue.try_reserve(frames)
     }
 
+    fn try_reserve_for(&mut self, id: CanId, frames: usize) -> Result<(), OutOfMemoryError> {
+        self.tx_queue.try_reserve_for(id, frames)
+    }
+
     fn transmit(&mut self, frame: Frame, _clock: &mut C) -> nb::Result<Option<Frame>, Self::Error> {
         self.tx_queue
             .push_frame(frame)
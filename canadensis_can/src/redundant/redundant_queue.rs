@@ -1,7 +1,8 @@
 use crate::driver::TransmitDriver;
-use crate::Frame;
+use crate::{CanId, Frame};
 use canadensis_core::time::Clock;
 use canadensis_core::{nb, OutOfMemoryError};
+use core::fmt;
 
 /// An aggregation of two outgoing frame queues that can be used for double-redundant transports
 ///
@@ -50,6 +51,14 @@ where
         self.status0.clone().or_else(|_| self.status1.clone())
     }
 
+    /// Tries to reserve space on both queues for frames with a CAN ID, returning `Ok(())` if the
+    /// operation succeeded on at least one queue
+    fn try_reserve_for(&mut self, id: CanId, additional: usize) -> Result<(), OutOfMemoryError> {
+        self.status0 = self.driver0.try_reserve_for(id, additional);
+        self.status1 = self.driver1.try_reserve_for(id, additional);
+        self.status0.clone().or_else(|_| self.status1.clone())
+    }
+
     /// Tries to push a frame to both drivers, returning `Ok(())` if the operation succeeded
     /// on at least one driver
     ///
@@ -111,3 +120,27 @@ pub enum RedundantError<E0, E1> {
     /// Errors from both drivers
     Both(E0, E1),
 }
+
+impl<E0, E1> fmt::Display for RedundantError<E0, E1> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedundantError::Driver0(_) => f.write_str("Driver 0 failed"),
+            RedundantError::Driver1(_) => f.write_str("Driver 1 failed"),
+            RedundantError::Both(_, _) => f.write_str("Both drivers failed"),
+        }
+    }
+}
+
+impl<E0, E1> core::error::Error for RedundantError<E0, E1>
+where
+    E0: core::error::Error + 'static,
+    E1: core::error::Error + 'static,
+{
+    /// Returns the error from driver 0 if it failed, otherwise the error from driver 1
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            RedundantError::Driver0(inner) | RedundantError::Both(inner, _) => Some(inner),
+            RedundantError::Driver1(inner) => Some(inner),
+        }
+    }
+}
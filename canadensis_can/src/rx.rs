@@ -8,17 +8,20 @@ mod subscription;
 
 use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
+use core::fmt;
 use core::fmt::Debug;
 use core::marker::PhantomData;
 
 use fallible_collections::FallibleVec;
 
+use crate::crc::{CrcAlgorithm, DefaultCrc};
 use crate::data::{CanId, Frame};
 use crate::driver::ReceiveDriver;
 use crate::rx::session::SessionError;
 use crate::rx::subscription::{Subscription, SubscriptionError};
 use crate::types::{CanNodeId, CanTransferId, CanTransport, Error};
 use crate::Mtu;
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
 use canadensis_core::transfer::{Header, MessageHeader, ServiceHeader, Transfer};
 use canadensis_core::transport::Receiver;
@@ -27,8 +30,11 @@ use canadensis_core::{
 };
 
 /// Handles subscriptions and assembles incoming frames into transfers
+///
+/// The profiler `P` measures the time spent handling each frame, and `A` calculates the
+/// transfer CRC of multi-frame transfers.
 #[derive(Debug)]
-pub struct CanReceiver<C, D> {
+pub struct CanReceiver<C, D, P = NoProfiler, A = DefaultCrc> {
     /// Subscriptions for messages
     subscriptions_message: Vec<Subscription>,
     /// Subscriptions for service responses
@@ -50,17 +56,27 @@ pub struct CanReceiver<C, D> {
     _driver: PhantomData<D>,
     /// The clock used to get the current time
     _clock: PhantomData<C>,
+    /// Measures the duration of operations
+    profiler: P,
+    /// Calculates transfer CRCs
+    crc: A,
 }
 
-impl<C, D> Receiver<C> for CanReceiver<C, D>
+impl<C, D, P, A> Receiver<C> for CanReceiver<C, D, P, A>
 where
     C: Clock,
     D: ReceiveDriver<C>,
+    P: Profiler,
+    A: CrcAlgorithm,
 {
     type Transport = CanTransport;
     type Driver = D;
     type Error = Error<D::Error>;
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "can_receive", level = "trace", skip_all)
+    )]
     fn receive(
         &mut self,
         clock: &mut C,
@@ -73,7 +89,14 @@ where
         loop {
             match driver.receive(clock) {
                 Ok(frame) => {
-                    match self.accept_frame(frame) {
+                    let start = self.profiler.counter();
+                    let result = self.accept_frame(frame);
+                    let operation = match result {
+                        Ok(Some(_)) => Operation::Reassemble,
+                        _ => Operation::Accept,
+                    };
+                    self.profiler.finish(operation, start);
+                    match result {
                         Ok(Some(transfer)) => break Ok(Some(transfer)),
                         Ok(None) => { /* Keep going and try another frame */ }
                         Err(e) => break Err(e.into()),
@@ -123,6 +146,21 @@ where
         self.apply_frame_filters(driver);
     }
 
+    fn set_accept_anonymous(&mut self, subject: SubjectId, accept: bool) -> bool {
+        let port_id = PortId::from(subject);
+        match self
+            .subscriptions_message
+            .iter_mut()
+            .find(|sub| sub.port_id() == port_id)
+        {
+            Some(subscription) => {
+                subscription.set_accept_anonymous(accept);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Subscribes to requests for a service
     ///
     /// This will enable incoming service request transfers from all nodes on the specified service
@@ -250,17 +288,43 @@ where
     ///
     /// id: The ID of this node. This is used to filter incoming service requests and responses.
     pub fn new(id: CanNodeId, mtu: Mtu) -> Self {
-        Self::new_inner(Some(id), mtu)
+        Self::with_profiler(Some(id), mtu, NoProfiler)
     }
 
     /// Creates an anonymous receiver
     ///
     /// An anonymous receiver cannot receive service requests or responses.
     pub fn new_anonymous(mtu: Mtu) -> Self {
-        Self::new_inner(None, mtu)
+        Self::with_profiler(None, mtu, NoProfiler)
+    }
+}
+
+impl<C, D, P> CanReceiver<C, D, P>
+where
+    C: Clock,
+    D: ReceiveDriver<C>,
+    P: Profiler,
+{
+    /// Creates a receiver that reports the duration of operations to a profiler
+    ///
+    /// id: The ID of this node, or None if this node is anonymous
+    pub fn with_profiler(id: Option<CanNodeId>, mtu: Mtu, profiler: P) -> Self {
+        Self::with_crc_algorithm(id, mtu, profiler, DefaultCrc::default())
     }
+}
 
-    fn new_inner(id: Option<CanNodeId>, mtu: Mtu) -> Self {
+impl<C, D, P, A> CanReceiver<C, D, P, A>
+where
+    C: Clock,
+    D: ReceiveDriver<C>,
+    P: Profiler,
+    A: CrcAlgorithm,
+{
+    /// Creates a receiver that reports the duration of operations to a profiler and checks
+    /// transfer CRCs using the provided algorithm
+    ///
+    /// id: The ID of this node, or None if this node is anonymous
+    pub fn with_crc_algorithm(id: Option<CanNodeId>, mtu: Mtu, profiler: P, crc: A) -> Self {
         CanReceiver {
             subscriptions_message: Vec::new(),
             subscriptions_response: Vec::new(),
@@ -271,9 +335,21 @@ where
             error_count: 0,
             _driver: PhantomData,
             _clock: PhantomData,
+            profiler,
+            crc,
         }
     }
 
+    /// Returns a reference to the profiler
+    pub fn profiler(&self) -> &P {
+        &self.profiler
+    }
+
+    /// Returns a mutable reference to the profiler
+    pub fn profiler_mut(&mut self) -> &mut P {
+        &mut self.profiler
+    }
+
     /// Handles an incoming CAN or CAN FD frame
     ///
     /// If this frame is the last frame in a transfer, this function returns the completed transfer.
@@ -294,7 +370,10 @@ where
             Some(data) => data,
             None => {
                 // Can't use this frame
-                log::debug!("Frame failed sanity checks, ignoring");
+                #[cfg(feature = "log")]
+                log::debug!("Frame {:?} failed sanity checks, ignoring", frame.id());
+                #[cfg(feature = "tracing")]
+                tracing::debug!(id = ?frame.id(), reason = "failed sanity checks", "Frame dropped");
                 self.increment_error_count();
                 return Ok(None);
             }
@@ -325,20 +404,54 @@ where
         frame_header: Header<CanTransport>,
         tail: TailByte,
     ) -> Result<Option<Transfer<Vec<u8>, CanTransport>>, OutOfMemoryError> {
-        let kind = TransferKind::from_header(&frame_header);
-        let subscriptions = self.subscriptions_for_kind(kind);
+        // This borrows the subscriptions separately so that the CRC algorithm can also be
+        // borrowed
+        let subscriptions = match TransferKind::from_header(&frame_header) {
+            TransferKind::Message => &mut self.subscriptions_message,
+            TransferKind::Response => &mut self.subscriptions_response,
+            TransferKind::Request => &mut self.subscriptions_request,
+        };
         if let Some(subscription) = subscriptions
             .iter_mut()
             .find(|subscription| subscription.port_id() == frame_header.port_id())
         {
-            match subscription.accept(frame, frame_header, tail) {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                port = ?frame_header.port_id(),
+                source = ?frame_header.source(),
+                transfer_id = ?tail.transfer_id,
+                length = frame.data().len(),
+                "Frame accepted"
+            );
+            match subscription.accept(frame, frame_header, tail, &mut self.crc) {
                 Ok(Some(transfer)) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        port = ?transfer.header.port_id(),
+                        source = ?transfer.header.source(),
+                        transfer_id = ?transfer.header.transfer_id(),
+                        length = transfer.payload.len(),
+                        "Transfer completed"
+                    );
                     self.increment_transfer_count();
                     Ok(Some(transfer))
                 }
                 Ok(None) => Ok(None),
                 Err(e) => {
-                    log::info!("Receiver accept error {:?}", e);
+                    #[cfg(feature = "log")]
+                    match &e {
+                        SubscriptionError::NotStart => log::debug!(
+                            "Frame on port {:?} is not the start of a transfer, ignoring",
+                            subscription.port_id()
+                        ),
+                        _ => log::warn!(
+                            "Dropped transfer on port {:?}: {:?}",
+                            subscription.port_id(),
+                            e
+                        ),
+                    }
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(port = ?subscription.port_id(), reason = ?e, "Transfer dropped");
                     self.increment_error_count();
                     match e {
                         SubscriptionError::Session(SessionError::Memory(e))
@@ -369,6 +482,7 @@ where
             if message_header.source.is_none() {
                 // Anonymous message transfers must always fit into one frame
                 if !(tail_byte.toggle && tail_byte.start && tail_byte.end) {
+                    #[cfg(feature = "log")]
                     log::debug!("Anonymous multi-frame transfer, ignoring");
                     return None;
                 }
@@ -461,11 +575,20 @@ where
 fn clean_sessions_from_subscriptions(subscriptions: &mut Vec<Subscription>, now: Microseconds32) {
     for subscription in subscriptions {
         let timeout = subscription.timeout();
+        #[cfg(feature = "tracing")]
+        let port_id = subscription.port_id();
         for slot in subscription.sessions_mut().iter_mut() {
             if let Some(session) = slot.as_deref_mut() {
                 let deadline = session.transfer_timestamp() + timeout;
                 if now > deadline {
                     // This session has timed out, delete it.
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        port = ?port_id,
+                        transfer_id = ?session.transfer_id(),
+                        reason = "timeout",
+                        "Session destroyed"
+                    );
                     *slot = None;
                 }
             }
@@ -473,6 +596,7 @@ fn clean_sessions_from_subscriptions(subscriptions: &mut Vec<Subscription>, now:
     }
 }
 
+/// Errors that can occur when parsing a transfer header from a CAN ID
 #[derive(Debug)]
 pub enum CanIdParseError {
     /// Reserved bit 23 was set
@@ -481,8 +605,19 @@ pub enum CanIdParseError {
     Bit7Set,
 }
 
+impl fmt::Display for CanIdParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanIdParseError::Bit23Set => f.write_str("Reserved bit 23 is set"),
+            CanIdParseError::Bit7Set => f.write_str("Reserved bit 7 is set in a message CAN ID"),
+        }
+    }
+}
+
+impl core::error::Error for CanIdParseError {}
+
 /// Parses a transfer header from a CAN ID, frame timestamp, and frame transfer ID
-fn parse_can_id(
+pub(crate) fn parse_can_id(
     id: CanId,
     timestamp: Microseconds32,
     transfer_id: CanTransferId,
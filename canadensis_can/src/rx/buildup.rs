@@ -1,6 +1,7 @@
 use alloc::vec::Vec;
 use core::mem;
 
+use crate::crc::{CrcAlgorithm, TRANSFER_CRC_INIT};
 use crate::types::CanTransferId;
 use fallible_collections::{FallibleVec, TryReserveError};
 
@@ -20,6 +21,11 @@ pub struct Buildup {
     expect_toggle: bool,
     /// The bytes collected so far, not including tail bytes
     transfer: Vec<u8>,
+    /// The transfer CRC of the bytes collected so far
+    ///
+    /// This is updated as each frame arrives, so the CRC does not need to be calculated over
+    /// the whole payload when the last frame arrives.
+    crc: u16,
 }
 
 impl Buildup {
@@ -37,10 +43,11 @@ impl Buildup {
             expect_start: true,
             expect_toggle: true,
             transfer: FallibleVec::try_with_capacity(max_payload_length)?,
+            crc: TRANSFER_CRC_INIT,
         })
     }
 
-    /// Handles an incoming frame for this transfer
+    /// Handles an incoming frame for this transfer, using `crc` to update the transfer CRC
     ///
     /// This function panics if the transfer ID is not equal to the transfer ID used to create
     /// this Buildup, or if the frame data is empty.
@@ -48,7 +55,14 @@ impl Buildup {
     /// If this frame is the last frame in the transfer, this function returns the reassembled
     /// payload, including the padding and transfer CRC (if applicable) but excluding any
     /// tail bytes. After the payload is returned, this Buildup must not be used again.
-    pub fn add(&mut self, frame_data: &[u8]) -> Result<Option<Vec<u8>>, BuildupError> {
+    pub fn add<A>(
+        &mut self,
+        frame_data: &[u8],
+        crc: &mut A,
+    ) -> Result<Option<Vec<u8>>, BuildupError>
+    where
+        A: CrcAlgorithm,
+    {
         self.frames += 1;
         assert!(
             !frame_data.is_empty(),
@@ -73,6 +87,7 @@ impl Buildup {
         // Copy data
         let frame_without_tail = &frame_data[..frame_data.len() - 1];
         FallibleVec::try_extend_from_slice(&mut self.transfer, frame_without_tail)?;
+        self.crc = crc.update(self.crc, frame_without_tail);
 
         if tail.end {
             // End of transfer, return the transfer data
@@ -97,6 +112,13 @@ impl Buildup {
     pub fn frames(&self) -> usize {
         self.frames
     }
+    /// Returns the transfer CRC of all the bytes collected (including the transfer CRC from
+    /// the last frame, if it has been processed)
+    ///
+    /// If the transfer is complete and its CRC is correct, this returns zero.
+    pub fn crc(&self) -> u16 {
+        self.crc
+    }
 }
 
 #[derive(Debug)]
@@ -115,6 +137,7 @@ impl From<TryReserveError> for BuildupError {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::crc::BitwiseCrc;
     use core::convert::TryFrom;
 
     #[test]
@@ -138,7 +161,10 @@ mod test {
                 0xe0 | transfer_id,
             ];
 
-            assert_eq!(Some(payload.to_vec()), buildup.add(&frame).unwrap());
+            assert_eq!(
+                Some(payload.to_vec()),
+                buildup.add(&frame, &mut BitwiseCrc).unwrap()
+            );
         }
 
         fn make_heartbeat_payload(uptime: u32) -> [u8; 7] {
@@ -170,7 +196,10 @@ mod test {
                 Buildup::new(CanTransferId::try_from(transfer_id).unwrap(), 16).unwrap();
 
             // Put in the payload bytes
-            assert_eq!(Some(payload.to_vec()), buildup.add(&frame).unwrap());
+            assert_eq!(
+                Some(payload.to_vec()),
+                buildup.add(&frame, &mut BitwiseCrc).unwrap()
+            );
         }
 
         fn make_frame(payload: &[u8; 15], transfer_id: u8) -> [u8; 16] {
@@ -188,7 +217,10 @@ mod test {
     #[test]
     fn test_node_info_request() {
         let mut buildup = Buildup::new(CanTransferId::try_from(1).unwrap(), 0).unwrap();
-        assert_eq!(Some(Vec::new()), buildup.add(&[0xe1]).unwrap());
+        assert_eq!(
+            Some(Vec::new()),
+            buildup.add(&[0xe1], &mut BitwiseCrc).unwrap()
+        );
     }
 
     #[test]
@@ -228,11 +260,16 @@ mod test {
 
         for (i, frame) in frames.iter().enumerate() {
             if i != frames.len() - 1 {
-                assert_eq!(None, buildup.add(*frame).unwrap());
+                assert_eq!(None, buildup.add(frame, &mut BitwiseCrc).unwrap());
             } else {
-                assert_eq!(Some(payload.to_vec()), buildup.add(*frame).unwrap());
+                assert_eq!(
+                    Some(payload.to_vec()),
+                    buildup.add(frame, &mut BitwiseCrc).unwrap()
+                );
             }
         }
+        // The CRC over the payload and the transfer CRC is zero
+        assert_eq!(0, buildup.crc());
     }
 
     #[test]
@@ -271,10 +308,15 @@ mod test {
 
         for (i, frame) in frames.iter().enumerate() {
             if i != frames.len() - 1 {
-                assert_eq!(None, buildup.add(*frame).unwrap());
+                assert_eq!(None, buildup.add(frame, &mut BitwiseCrc).unwrap());
             } else {
-                assert_eq!(Some(payload.to_vec()), buildup.add(*frame).unwrap());
+                assert_eq!(
+                    Some(payload.to_vec()),
+                    buildup.add(frame, &mut BitwiseCrc).unwrap()
+                );
             }
         }
+        // The CRC over the payload and the transfer CRC is zero
+        assert_eq!(0, buildup.crc());
     }
 }
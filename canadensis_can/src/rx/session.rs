@@ -1,7 +1,8 @@
+use crate::crc::CrcAlgorithm;
 use crate::rx::buildup::{Buildup, BuildupError};
 use crate::rx::TailByte;
 use crate::types::{CanTransferId, Header, Transfer};
-use crate::{Frame, TransferCrc};
+use crate::Frame;
 use alloc::vec::Vec;
 use canadensis_core::time::{MicrosecondDuration32, Microseconds32};
 use canadensis_core::OutOfMemoryError;
@@ -38,33 +39,53 @@ impl Session {
 
     /// Accepts a frame associated with this session
     ///
-    /// If this frame completes a transfer, this function returns the transfer.
+    /// If this frame completes a transfer, this function returns the transfer. The transfer CRC
+    /// is calculated using `crc`.
     ///
     /// The `max_payload_length` value must include space for the transfer CRC and/or padding bytes
     /// that may be inserted, depending on the transport MTU and frame length constraints.
-    pub(crate) fn accept(
+    pub(crate) fn accept<A>(
         &mut self,
         frame: Frame,
         frame_header: Header,
         tail: TailByte,
         max_payload_length: usize,
         transfer_timeout: MicrosecondDuration32,
-    ) -> Result<Option<Transfer<Vec<u8>>>, SessionError> {
+        crc: &mut A,
+    ) -> Result<Option<Transfer<Vec<u8>>>, SessionError>
+    where
+        A: CrcAlgorithm,
+    {
         if tail.transfer_id != self.buildup.transfer_id() {
             // This is a frame from some other transfer. Ignore it, but keep this session to receive
             // possible later frames.
-            log::info!("Frame transfer ID does not match, ignoring");
+            #[cfg(feature = "log")]
+            log::debug!(
+                "Frame on port {:?} from {:?} has transfer ID {:?}, expected {:?}, ignoring",
+                frame_header.port_id(),
+                frame_header.source(),
+                tail.transfer_id,
+                self.buildup.transfer_id()
+            );
             return Ok(None);
         }
         if frame.loopback() != self.loopback {
-            log::info!("Frame loopback flag does not match, ignoring");
+            #[cfg(feature = "log")]
+            log::debug!(
+                "Frame on port {:?} from {:?} has a different loopback flag, ignoring",
+                frame_header.port_id(),
+                frame_header.source()
+            );
             return Ok(None);
         }
         // Check if this frame will make the transfer exceed the maximum length
         let new_payload_length = self.buildup.payload_length() + (frame.data().len() - 1);
         if new_payload_length > max_payload_length {
+            #[cfg(feature = "log")]
             log::warn!(
-                "Payload too large ({} + {} > {}), ending session",
+                "Payload on port {:?} from {:?} too large ({} + {} > {}), ending session",
+                frame_header.port_id(),
+                frame_header.source(),
                 self.buildup.payload_length(),
                 frame.data().len() - 1,
                 max_payload_length
@@ -76,11 +97,17 @@ impl Session {
 
         if time_since_first_frame > transfer_timeout {
             // Frame arrived too late. Give up on this session.
-            log::info!("Frame timeout expired, ending session");
+            #[cfg(feature = "log")]
+            log::warn!(
+                "Transfer {:?} on port {:?} from {:?} timed out, ending session",
+                tail.transfer_id,
+                frame_header.port_id(),
+                frame_header.source()
+            );
             return Err(SessionError::Timeout);
         }
         // This frame looks OK. Do the reassembly.
-        match self.buildup.add(frame.data())? {
+        match self.buildup.add(frame.data(), crc)? {
             Some(transfer_data) => self.handle_transfer_data(transfer_data, frame_header),
             None => {
                 // Reassembly still in progress
@@ -95,10 +122,9 @@ impl Session {
         frame_header: Header,
     ) -> Result<Option<Transfer<Vec<u8>>>, SessionError> {
         // Check CRC, if this transfer used more than one frame
+        // (the buildup calculated it while the frames arrived)
         if self.buildup.frames() > 1 {
-            let mut crc = TransferCrc::new();
-            crc.add_bytes(&transfer_data);
-            if crc.get() != 0 {
+            if self.buildup.crc() != 0 {
                 // Invalid CRC, drop transfer
                 return Err(SessionError::Crc);
             }
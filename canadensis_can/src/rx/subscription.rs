@@ -1,3 +1,4 @@
+use crate::crc::CrcAlgorithm;
 use crate::rx::session::{Session, SessionError};
 use crate::rx::TailByte;
 use crate::types::{CanNodeId, Header, Transfer};
@@ -25,6 +26,8 @@ pub struct Subscription {
     payload_size_max: usize,
     /// Subject or service ID that this subscription is about
     port_id: PortId,
+    /// If transfers from anonymous nodes are accepted
+    accepts_anonymous: bool,
 }
 
 impl fmt::Debug for Subscription {
@@ -34,6 +37,7 @@ impl fmt::Debug for Subscription {
             .field("transfer_id_timeout", &self.timeout)
             .field("payload_size_max", &self.payload_size_max)
             .field("port_id", &self.port_id)
+            .field("accepts_anonymous", &self.accepts_anonymous)
             .finish()
     }
 }
@@ -66,30 +70,40 @@ impl Subscription {
             timeout,
             payload_size_max: add_padding_and_crc_space(payload_size_max, mtu),
             port_id,
+            accepts_anonymous: true,
         }
     }
 
-    /// Handles an incoming frame on this subscription's topic
-    pub(crate) fn accept(
+    /// Handles an incoming frame on this subscription's topic, using `crc` to check the
+    /// transfer CRC
+    pub(crate) fn accept<A>(
         &mut self,
         frame: Frame,
         frame_header: Header,
         tail: TailByte,
-    ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError> {
+        crc: &mut A,
+    ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError>
+    where
+        A: CrcAlgorithm,
+    {
         if let Some(source_node) = frame_header.source().cloned() {
-            self.accept_non_anonymous(frame, frame_header, source_node, tail)
+            self.accept_non_anonymous(frame, frame_header, source_node, tail, crc)
         } else {
             self.accept_anonymous(frame, frame_header)
         }
     }
 
-    fn accept_non_anonymous(
+    fn accept_non_anonymous<A>(
         &mut self,
         frame: Frame,
         frame_header: Header,
         source_node: CanNodeId,
         tail: TailByte,
-    ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError> {
+        crc: &mut A,
+    ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError>
+    where
+        A: CrcAlgorithm,
+    {
         let max_payload_length = self.payload_size_max;
 
         if tail.start && tail.end {
@@ -108,23 +122,28 @@ impl Subscription {
             };
             Ok(Some(transfer))
         } else {
-            self.accept_with_session(frame, frame_header, source_node, tail)
+            self.accept_with_session(frame, frame_header, source_node, tail, crc)
         }
     }
 
-    fn accept_with_session(
+    fn accept_with_session<A>(
         &mut self,
         frame: Frame,
         frame_header: Header,
         source_node: CanNodeId,
         tail: TailByte,
-    ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError> {
+        crc: &mut A,
+    ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError>
+    where
+        A: CrcAlgorithm,
+    {
         let max_payload_length = self.payload_size_max;
         let transfer_timeout = self.timeout;
 
         let slot = &mut self.sessions[usize::from(source_node)];
         let session = match slot {
             Some(session) => {
+                #[cfg(feature = "log")]
                 log::debug!(
                     "Using existing session with transfer ID {:?} for port {:?} (frame transfer ID {:?})",
                     session.transfer_id(),
@@ -146,11 +165,19 @@ impl Subscription {
                     self.payload_size_max,
                     frame.loopback(),
                 )?)?);
+                #[cfg(feature = "log")]
                 log::debug!(
                     "Created new session for transfer ID {:?} on port {:?}",
                     tail.transfer_id,
                     self.port_id
                 );
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    port = ?self.port_id,
+                    source = ?source_node,
+                    transfer_id = ?tail.transfer_id,
+                    "Session created"
+                );
                 slot.as_deref_mut().unwrap()
             }
         };
@@ -161,11 +188,19 @@ impl Subscription {
             tail,
             max_payload_length,
             transfer_timeout,
+            crc,
         );
         match accept_status {
             Ok(Some(transfer)) => {
                 // Transfer received, this session has served its purpose and can be deleted.
                 *slot = None;
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    port = ?self.port_id,
+                    source = ?source_node,
+                    reason = "transfer completed",
+                    "Session destroyed"
+                );
                 Ok(Some(transfer))
             }
             Ok(None) => Ok(None),
@@ -173,6 +208,13 @@ impl Subscription {
                 // This is either out-of-memory or an unexpected frame that invalidates
                 // the session. Delete the session to free memory.
                 *slot = None;
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    port = ?self.port_id,
+                    source = ?source_node,
+                    reason = ?e,
+                    "Session destroyed"
+                );
                 Err(e.into())
             }
         }
@@ -183,6 +225,17 @@ impl Subscription {
         frame: Frame,
         frame_header: Header,
     ) -> Result<Option<Transfer<Vec<u8>>>, SubscriptionError> {
+        if !self.accepts_anonymous {
+            // This is a policy choice, not an error, so it is not counted as one
+            #[cfg(feature = "log")]
+            log::trace!(
+                "Ignoring anonymous transfer on port {:?}",
+                frame_header.port_id()
+            );
+            #[cfg(feature = "tracing")]
+            tracing::trace!(port = ?self.port_id, "Anonymous transfer ignored");
+            return Ok(None);
+        }
         // An anonymous transfer is always a single frame and does not have a corresponding session.
         // Just convert it into a transfer.
         // Remove the tail byte
@@ -203,6 +256,11 @@ impl Subscription {
         self.port_id
     }
 
+    /// Sets whether this subscription accepts transfers from anonymous nodes
+    pub fn set_accept_anonymous(&mut self, accept: bool) {
+        self.accepts_anonymous = accept;
+    }
+
     /// Returns a mutable reference to the array of sessions
     pub fn sessions_mut(&mut self) -> &mut [Option<Box<Session>>; RX_SESSIONS_PER_SUBSCRIPTION] {
         &mut self.sessions
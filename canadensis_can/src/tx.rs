@@ -2,16 +2,18 @@
 //! Cyphal message transmission
 //!
 
+use alloc::vec::Vec;
 use core::convert::TryFrom;
-use core::iter;
 use core::marker::PhantomData;
 
-use canadensis_core::nb;
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::time::{Clock, Microseconds32};
 use canadensis_core::transfer::{Header, ServiceHeader, Transfer};
-use canadensis_core::transport::Transmitter;
+use canadensis_core::transport::{StreamingPayload, Transmitter};
+use canadensis_core::{nb, OutOfMemoryError};
+use fallible_collections::FallibleVec;
 
-use crate::crc::TransferCrc;
+use crate::crc::{CrcAlgorithm, DefaultCrc, TransferCrc};
 use crate::data::Frame;
 use crate::driver::TransmitDriver;
 use crate::tx::breakdown::Breakdown;
@@ -23,7 +25,10 @@ mod breakdown;
 mod tx_test;
 
 /// Splits outgoing transfers into frames
-pub struct CanTransmitter<C, D> {
+///
+/// The profiler `P` measures the time spent splitting transfers into frames and flushing the
+/// queue, and `A` calculates the transfer CRC of multi-frame transfers.
+pub struct CanTransmitter<C, D, P = NoProfiler, A = DefaultCrc> {
     /// Transport MTU (including the tail byte)
     mtu: usize,
     /// Number of transfers successfully transmitted
@@ -37,12 +42,18 @@ pub struct CanTransmitter<C, D> {
     error_count: u64,
     _clock: PhantomData<C>,
     _driver: PhantomData<D>,
+    /// Measures the duration of operations
+    profiler: P,
+    /// Calculates transfer CRCs
+    crc: A,
 }
 
-impl<C, D> Transmitter<C> for CanTransmitter<C, D>
+impl<C, D, P, A> Transmitter<C> for CanTransmitter<C, D, P, A>
 where
     C: Clock,
     D: TransmitDriver<C>,
+    P: Profiler,
+    A: CrcAlgorithm,
 {
     type Transport = CanTransport;
     type Driver = D;
@@ -54,14 +65,18 @@ where
     ///
     /// This function returns an error if the queue does not have enough space to hold all
     /// the required frames.
-    fn push<A>(
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "can_push", level = "trace", skip_all)
+    )]
+    fn push<T>(
         &mut self,
-        transfer: Transfer<A, CanTransport>,
+        transfer: Transfer<T, CanTransport>,
         clock: &mut C,
         driver: &mut D,
     ) -> nb::Result<(), Self::Error>
     where
-        A: AsRef<[u8]>,
+        T: AsRef<[u8]>,
     {
         // Convert the transfer payload into borrowed form
         let transfer = Transfer {
@@ -70,20 +85,52 @@ where
             payload: transfer.payload.as_ref(),
         };
 
-        match self.push_inner(transfer, clock, driver) {
-            Ok(()) => {
-                self.transfer_count = self.transfer_count.wrapping_add(1);
-                Ok(())
-            }
-            Err(e) => {
-                self.error_count = self.error_count.wrapping_add(1);
-                Err(e)
-            }
-        }
+        let start = self.profiler.counter();
+        let result = self.push_inner(transfer, clock, driver);
+        self.profiler.finish(Operation::Serialize, start);
+        self.count_result(result)
+    }
+
+    /// Breaks a transfer into frames, writing the payload directly into the frames as it is
+    /// produced
+    ///
+    /// Because the pseudo-ID of an anonymous transfer depends on the payload, the payload of an
+    /// anonymous transfer is collected into a temporary buffer first.
+    fn push_streaming<S>(
+        &mut self,
+        transfer: Transfer<&S, CanTransport>,
+        clock: &mut C,
+        driver: &mut D,
+    ) -> nb::Result<(), Self::Error>
+    where
+        S: StreamingPayload + ?Sized,
+    {
+        let start = self.profiler.counter();
+        let result = if transfer.header.source().is_none() {
+            let mut payload: Vec<u8> = Vec::new();
+            FallibleVec::try_reserve(&mut payload, transfer.payload.payload_length())
+                .map_err(|e| nb::Error::Other(Error::Memory(OutOfMemoryError::from(e))))?;
+            transfer
+                .payload
+                .write_to(&mut |piece| payload.extend_from_slice(piece));
+            let transfer = Transfer {
+                header: transfer.header,
+                loopback: transfer.loopback,
+                payload: &payload[..],
+            };
+            self.push_inner(transfer, clock, driver)
+        } else {
+            self.push_streaming_inner(transfer, clock, driver)
+        };
+        self.profiler.finish(Operation::Serialize, start);
+        self.count_result(result)
     }
 
     fn flush(&mut self, clock: &mut C, driver: &mut D) -> nb::Result<(), Self::Error> {
-        driver.flush(clock).map_err(|e| e.map(Error::Driver))
+        let start = self.profiler.counter();
+        let result = driver.flush(clock);
+        self.profiler.finish(Operation::Flush, start);
+        result.map_err(|e| e.map(Error::Driver))
     }
 
     fn mtu(&self) -> usize {
@@ -101,15 +148,57 @@ where
     ///
     /// mtu: The maximum number of bytes in a frame
     pub fn new(mtu: Mtu) -> Self {
+        Self::with_profiler(mtu, NoProfiler)
+    }
+}
+
+impl<C, D, P> CanTransmitter<C, D, P>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+    P: Profiler,
+{
+    /// Creates a transmitter that reports the duration of operations to a profiler
+    ///
+    /// mtu: The maximum number of bytes in a frame
+    pub fn with_profiler(mtu: Mtu, profiler: P) -> Self {
+        Self::with_crc_algorithm(mtu, profiler, DefaultCrc::default())
+    }
+}
+
+impl<C, D, P, A> CanTransmitter<C, D, P, A>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+    P: Profiler,
+    A: CrcAlgorithm,
+{
+    /// Creates a transmitter that reports the duration of operations to a profiler and
+    /// calculates transfer CRCs using the provided algorithm
+    ///
+    /// mtu: The maximum number of bytes in a frame
+    pub fn with_crc_algorithm(mtu: Mtu, profiler: P, crc: A) -> Self {
         CanTransmitter {
             mtu: mtu as usize,
             transfer_count: 0,
             error_count: 0,
             _clock: PhantomData,
             _driver: PhantomData,
+            profiler,
+            crc,
         }
     }
 
+    /// Returns a reference to the profiler
+    pub fn profiler(&self) -> &P {
+        &self.profiler
+    }
+
+    /// Returns a mutable reference to the profiler
+    pub fn profiler_mut(&mut self) -> &mut P {
+        &mut self.profiler
+    }
+
     /// Sets the MTU
     ///
     /// This will take effect on the next call to push().
@@ -117,6 +206,23 @@ where
         self.mtu = mtu as usize;
     }
 
+    /// Updates the transfer and error counts based on the result of sending a transfer
+    fn count_result(
+        &mut self,
+        result: nb::Result<(), Error<D::Error>>,
+    ) -> nb::Result<(), Error<D::Error>> {
+        match result {
+            Ok(()) => {
+                self.transfer_count = self.transfer_count.wrapping_add(1);
+                Ok(())
+            }
+            Err(e) => {
+                self.error_count = self.error_count.wrapping_add(1);
+                Err(e)
+            }
+        }
+    }
+
     fn push_inner(
         &mut self,
         transfer: Transfer<&[u8], CanTransport>,
@@ -124,90 +230,57 @@ where
         driver: &mut D,
     ) -> nb::Result<(), Error<D::Error>> {
         let frame_stats = crate::calculate_frame_stats(transfer.payload.len(), self.mtu);
+        let can_id = make_can_id(&transfer.header, transfer.payload);
         // Check that enough space is available in the queue for all the frames.
         // Return an error if space is not available.
-        driver
-            .try_reserve(frame_stats.frames)
-            .map_err(|oom| nb::Error::Other(Error::Memory(oom)))?;
+        reserve_frames::<C, D>(driver, can_id, frame_stats.frames)?;
 
-        // Make an iterator over the payload bytes and padding. Run the CRC on that.
-        let mut crc = TransferCrc::new();
-        let payload_and_padding = transfer
-            .payload
-            .iter()
-            .cloned()
-            .chain(iter::repeat(0).take(frame_stats.last_frame_padding))
-            .inspect(|byte| crc.add(*byte));
-        // Break into frames
-        let can_id = make_can_id(&transfer.header, transfer.payload);
-        let mut breakdown = Breakdown::new(self.mtu, *transfer.header.transfer_id());
-        let mut frames = 0;
-        // Do the non-last frames
-        for byte in payload_and_padding {
-            if let Some(frame_data) = breakdown.add(byte) {
-                // Filled up a frame
-                self.push_frame(
-                    transfer.header.timestamp(),
-                    transfer.loopback,
-                    can_id,
-                    &frame_data,
-                    driver,
-                    clock,
-                )
-                .map_err(|e| e.map(Error::Driver))?;
-                frames += 1;
-            }
-        }
-        if frames != 0 {
-            // The payload + padding was split across at least one non-last frame (handled above)
-            // and the last frame (still in the Breakdown). It needs a CRC.
-            let crc_value = crc.get();
-            // Add the CRC value, most significant byte first
-            let crc_bytes = [(crc_value >> 8) as u8, crc_value as u8];
-            for &byte in crc_bytes.iter() {
-                if let Some(frame_data) = breakdown.add(byte) {
-                    // Filled up a frame
-                    self.push_frame(
-                        transfer.header.timestamp(),
-                        transfer.loopback,
-                        can_id,
-                        &frame_data,
-                        driver,
-                        clock,
-                    )
-                    .map_err(|e| e.map(Error::Driver))?;
-                }
-            }
-        }
-        let last_frame_data = breakdown.finish();
-        self.push_frame(
-            transfer.header.timestamp(),
-            transfer.loopback,
+        let mut writer = FrameWriter::new(
+            self.mtu,
+            &transfer.header,
             can_id,
-            &last_frame_data,
+            transfer.loopback,
             driver,
             clock,
-        )
-        .map_err(|e| e.map(Error::Driver))?;
-        Ok(())
+            &mut self.crc,
+        );
+        writer.add_payload(transfer.payload);
+        writer
+            .finish(frame_stats.last_frame_padding)
+            .map_err(|e| e.map(Error::Driver))
     }
 
-    /// Creates a frame and sends it to the driver to be transmitted
-    ///
-    /// If the driver returns a removed lower-priority frame, this function discards it.
-    fn push_frame(
+    /// Breaks a non-anonymous transfer into frames without collecting the payload into a
+    /// buffer first
+    fn push_streaming_inner<S>(
         &mut self,
-        timestamp: Microseconds32,
-        loopback: bool,
-        id: CanId,
-        data: &[u8],
-        driver: &mut D,
+        transfer: Transfer<&S, CanTransport>,
         clock: &mut C,
-    ) -> nb::Result<(), D::Error> {
-        let mut frame = Frame::new(timestamp, id, data);
-        frame.set_loopback(loopback);
-        // If a lower-priority frame was removed, drop it
-        driver.transmit(frame, clock).map(drop)
+        driver: &mut D,
+    ) -> nb::Result<(), Error<D::Error>>
+    where
+        S: StreamingPayload + ?Sized,
+    {
+        let frame_stats = crate::calculate_frame_stats(transfer.payload.payload_length(), self.mtu);
+        // The payload is used only to make a pseudo-ID for anonymous transfers
+        let can_id = make_can_id(&transfer.header, &[]);
+        reserve_frames::<C, D>(driver, can_id, frame_stats.frames)?;
+
+        let mut writer = FrameWriter::new(
+            self.mtu,
+            &transfer.header,
+            can_id,
+            transfer.loopback,
+            driver,
+            clock,
+            &mut self.crc,
+        );
+        transfer
+            .payload
+            .write_to(&mut |piece| writer.add_payload(piece));
+        writer
+            .finish(frame_stats.last_frame_padding)
+            .map_err(|e| e.map(Error::Driver))
     }
 
     /// Returns the number of transfers successfully transmitted
@@ -228,6 +301,150 @@ where
     }
 }
 
+/// Adds payload bytes to frames and sends each frame to the driver when it is full
+///
+/// The transfer CRC is calculated as the bytes are added.
+struct FrameWriter<'a, C, D, A>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+{
+    breakdown: Breakdown,
+    crc: TransferCrc<&'a mut A>,
+    can_id: CanId,
+    timestamp: Microseconds32,
+    loopback: bool,
+    driver: &'a mut D,
+    clock: &'a mut C,
+    /// The number of frames sent before the CRC is added
+    frames: usize,
+    /// The first error that occurred when sending a frame
+    ///
+    /// After an error, no more frames are sent.
+    error: Option<nb::Error<D::Error>>,
+}
+
+impl<'a, C, D, A> FrameWriter<'a, C, D, A>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+    A: CrcAlgorithm,
+{
+    fn new(
+        mtu: usize,
+        header: &Header<CanTransport>,
+        can_id: CanId,
+        loopback: bool,
+        driver: &'a mut D,
+        clock: &'a mut C,
+        crc: &'a mut A,
+    ) -> Self {
+        FrameWriter {
+            breakdown: Breakdown::new(mtu, *header.transfer_id()),
+            crc: TransferCrc::with_algorithm(crc),
+            can_id,
+            timestamp: header.timestamp(),
+            loopback,
+            driver,
+            clock,
+            frames: 0,
+            error: None,
+        }
+    }
+
+    /// Adds payload bytes (or padding) and includes them in the CRC
+    fn add_payload(&mut self, bytes: &[u8]) {
+        self.crc.add_bytes(bytes);
+        for &byte in bytes {
+            self.add(byte);
+        }
+    }
+
+    /// Adds a byte, sending a frame if it is full
+    fn add(&mut self, byte: u8) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Some(frame_data) = self.breakdown.add(byte) {
+            // Filled up a frame
+            match self.push_frame(&frame_data) {
+                Ok(()) => self.frames += 1,
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    /// Adds padding and the CRC if required, and sends the last frame
+    fn finish(mut self, padding: usize) -> nb::Result<(), D::Error> {
+        for _ in 0..padding {
+            self.add_payload(&[0]);
+        }
+        if self.frames != 0 {
+            // The payload + padding was split across at least one non-last frame (handled above)
+            // and the last frame (still in the Breakdown). It needs a CRC.
+            let crc_value = self.crc.get();
+            // Add the CRC value, most significant byte first
+            self.add((crc_value >> 8) as u8);
+            self.add(crc_value as u8);
+        }
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        let last_frame_data = self.breakdown.finish();
+        let mut frame = Frame::new(self.timestamp, self.can_id, &last_frame_data);
+        frame.set_loopback(self.loopback);
+        transmit_frame(frame, self.driver, self.clock)
+    }
+
+    /// Creates a frame and sends it to the driver to be transmitted
+    fn push_frame(&mut self, data: &[u8]) -> nb::Result<(), D::Error> {
+        let mut frame = Frame::new(self.timestamp, self.can_id, data);
+        frame.set_loopback(self.loopback);
+        transmit_frame(frame, self.driver, self.clock)
+    }
+}
+
+/// Sends a frame to the driver to be transmitted
+///
+/// If the driver returns a removed lower-priority frame, this function discards it.
+fn transmit_frame<C, D>(frame: Frame, driver: &mut D, clock: &mut C) -> nb::Result<(), D::Error>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+{
+    #[cfg(feature = "tracing")]
+    tracing::trace!(id = ?frame.id(), length = frame.data().len(), "Frame queued");
+    match driver.transmit(frame, clock)? {
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        Some(removed) => {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "Transmit queue full, removed a lower-priority frame {:?}",
+                removed.id()
+            );
+            #[cfg(feature = "tracing")]
+            tracing::warn!(id = ?removed.id(), "Queue overflow, removed a lower-priority frame");
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reserves space in the driver's queue for some frames
+fn reserve_frames<C, D>(driver: &mut D, id: CanId, frames: usize) -> nb::Result<(), Error<D::Error>>
+where
+    C: Clock,
+    D: TransmitDriver<C>,
+{
+    driver.try_reserve_for(id, frames).map_err(|oom| {
+        #[cfg(feature = "log")]
+        log::warn!("Transmit queue has no space for {} frames", frames);
+        #[cfg(feature = "tracing")]
+        tracing::warn!(frames, "Queue overflow, no space for a transfer");
+        nb::Error::Other(Error::Memory(oom))
+    })
+}
+
 fn make_can_id(header: &Header<CanTransport>, payload: &[u8]) -> CanId {
     let mut bits = 0u32;
 
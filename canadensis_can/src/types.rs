@@ -205,3 +205,24 @@ impl<E> From<OutOfMemoryError> for Error<E> {
         Error::Memory(oom)
     }
 }
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Memory(_) => f.write_str("Out of memory"),
+            Error::Driver(_) => f.write_str("Driver error"),
+        }
+    }
+}
+
+impl<E> core::error::Error for Error<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::Memory(_) => None,
+            Error::Driver(inner) => Some(inner),
+        }
+    }
+}
@@ -409,6 +409,59 @@ fn test_multi_frame_anonymous() {
     );
 }
 
+#[test]
+fn test_reject_anonymous() {
+    let mut driver = StubDriver::default();
+    let mut receiver = CanReceiver::new(CanNodeId::try_from(3u8).unwrap(), Mtu::Can8);
+    let subject_id = SubjectId::try_from(10).unwrap();
+    receiver
+        .subscribe_message(subject_id, 8, duration(100), &mut driver)
+        .unwrap();
+    let anonymous_id: CanId = 0b100_0_1_011_0000000001010_0_1000000_u32
+        .try_into()
+        .unwrap();
+    let clock = ClockOwner::default();
+    clock.set_ticks(1);
+
+    // By default, anonymous transfers are accepted
+    driver.push(Frame::new(instant(1), anonymous_id, &[0x1, 0b111_00000]));
+    let transfer = receiver
+        .receive(&mut clock.make_clock(), &mut driver)
+        .unwrap()
+        .expect("Didn't get a transfer");
+    assert_eq!(
+        Header::Message(MessageHeader {
+            timestamp: instant(1),
+            transfer_id: 0.try_into().unwrap(),
+            priority: Priority::Nominal,
+            subject: subject_id,
+            source: None,
+        }),
+        transfer.header
+    );
+
+    // The option only applies to subjects that the receiver is subscribed to
+    assert!(!receiver.set_accept_anonymous(SubjectId::try_from(11u16).unwrap(), false));
+    assert!(receiver.set_accept_anonymous(subject_id, false));
+    driver.push(Frame::new(instant(1), anonymous_id, &[0x1, 0b111_00001]));
+    assert_eq!(
+        Ok(None),
+        receiver.receive(&mut clock.make_clock(), &mut driver)
+    );
+    // Ignoring the transfer is not an error
+    assert_eq!(0, receiver.error_count());
+
+    // Subscribing again accepts anonymous transfers
+    receiver
+        .subscribe_message(subject_id, 8, duration(100), &mut driver)
+        .unwrap();
+    driver.push(Frame::new(instant(1), anonymous_id, &[0x1, 0b111_00010]));
+    assert!(receiver
+        .receive(&mut clock.make_clock(), &mut driver)
+        .unwrap()
+        .is_some());
+}
+
 /// Tests an anonymous receiver receiving a multi-frame non-anonymous transfer
 #[test]
 fn test_anonymous_receive_multi_frame() {
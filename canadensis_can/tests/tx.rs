@@ -10,10 +10,13 @@ use std::collections::VecDeque;
 use std::convert::Infallible;
 
 use canadensis_can::driver::TransmitDriver;
-use canadensis_can::{CanId, CanNodeId, CanTransferId, CanTransmitter, Frame, Mtu};
+use canadensis_can::{
+    BitwiseCrc, CanId, CanNodeId, CanTransferId, CanTransmitter, CrcAlgorithm, Frame, Mtu, TableCrc,
+};
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::time::{Clock, Microseconds32};
 use canadensis_core::transfer::*;
-use canadensis_core::transport::Transmitter;
+use canadensis_core::transport::{StreamingPayload, Transmitter};
 use canadensis_core::{OutOfMemoryError, Priority, ServiceId, SubjectId};
 
 fn instant(ticks: u32) -> Microseconds32 {
@@ -258,6 +261,243 @@ fn test_array() {
     assert_eq!(None, driver.pop_frame());
 }
 
+#[test]
+fn test_streaming_matches_push() {
+    for length in 0..100 {
+        for source in [None, Some(CanNodeId::try_from(59u8).unwrap())] {
+            let payload: Vec<u8> = (0..length).map(|i| (i * 7) as u8).collect();
+            let header = Header::Message(MessageHeader {
+                timestamp: instant(0),
+                transfer_id: CanTransferId::try_from(3).unwrap(),
+                priority: Priority::Nominal,
+                subject: SubjectId::try_from(4919).unwrap(),
+                source,
+            });
+
+            let mut expected_driver = MockDriver::default();
+            let mut tx = CanTransmitter::new(Mtu::Can8);
+            tx.push(
+                Transfer {
+                    header: header.clone(),
+                    loopback: false,
+                    payload: &payload,
+                },
+                &mut ZeroClock,
+                &mut expected_driver,
+            )
+            .unwrap();
+
+            let mut actual_driver = MockDriver::default();
+            tx.push_streaming(
+                Transfer {
+                    header,
+                    loopback: false,
+                    payload: &Pieces(&payload),
+                },
+                &mut ZeroClock,
+                &mut actual_driver,
+            )
+            .unwrap();
+            assert_eq!(expected_driver.queue, actual_driver.queue);
+        }
+    }
+}
+
+#[test]
+fn test_table_crc_matches_bitwise() {
+    for length in 0..200 {
+        let payload: Vec<u8> = (0..length).map(|i| (i * 13) as u8).collect();
+        let header = Header::Message(MessageHeader {
+            timestamp: instant(0),
+            transfer_id: CanTransferId::try_from(3).unwrap(),
+            priority: Priority::Nominal,
+            subject: SubjectId::try_from(4919).unwrap(),
+            source: Some(CanNodeId::try_from(59u8).unwrap()),
+        });
+
+        let mut expected_driver = MockDriver::default();
+        let mut bitwise_tx = CanTransmitter::with_crc_algorithm(Mtu::Can8, NoProfiler, BitwiseCrc);
+        bitwise_tx
+            .push(
+                Transfer {
+                    header: header.clone(),
+                    loopback: false,
+                    payload: &payload,
+                },
+                &mut ZeroClock,
+                &mut expected_driver,
+            )
+            .unwrap();
+
+        let mut actual_driver = MockDriver::default();
+        let mut table_tx = CanTransmitter::with_crc_algorithm(Mtu::Can8, NoProfiler, TableCrc);
+        table_tx
+            .push(
+                Transfer {
+                    header,
+                    loopback: false,
+                    payload: &payload,
+                },
+                &mut ZeroClock,
+                &mut actual_driver,
+            )
+            .unwrap();
+        assert_eq!(expected_driver.queue, actual_driver.queue);
+    }
+}
+
+/// A CRC algorithm with state, like a driver for a CRC peripheral
+#[derive(Default)]
+struct CountingCrc {
+    bytes: usize,
+}
+
+impl CrcAlgorithm for CountingCrc {
+    fn update(&mut self, crc: u16, bytes: &[u8]) -> u16 {
+        self.bytes += bytes.len();
+        BitwiseCrc.update(crc, bytes)
+    }
+}
+
+#[test]
+fn test_borrowed_crc_algorithm() {
+    let mut crc = CountingCrc::default();
+    let mut driver = MockDriver::default();
+    let mut tx = CanTransmitter::with_crc_algorithm(Mtu::Can8, NoProfiler, &mut crc);
+    for (transfer_id, length) in [(0, 20), (1, 30)] {
+        let payload = vec![0x55; length];
+        tx.push(
+            Transfer {
+                header: Header::Message(MessageHeader {
+                    timestamp: instant(0),
+                    transfer_id: CanTransferId::try_from(transfer_id).unwrap(),
+                    priority: Priority::Nominal,
+                    subject: SubjectId::try_from(4919).unwrap(),
+                    source: Some(CanNodeId::try_from(59u8).unwrap()),
+                }),
+                loopback: false,
+                payload: &payload,
+            },
+            &mut ZeroClock,
+            &mut driver,
+        )
+        .unwrap();
+    }
+    // Both transfers used the same algorithm instance
+    assert_eq!(50, crc.bytes);
+}
+
+#[test]
+fn test_vectored_matches_push() {
+    let header_bytes = [0xd1, 0xd2, 0xd3];
+    let body: Vec<u8> = (0..90).collect();
+    let payload: Vec<u8> = header_bytes.iter().chain(body.iter()).copied().collect();
+    let header = Header::Message(MessageHeader {
+        timestamp: instant(0),
+        transfer_id: CanTransferId::try_from(9).unwrap(),
+        priority: Priority::Nominal,
+        subject: SubjectId::try_from(4919).unwrap(),
+        source: Some(CanNodeId::try_from(59u8).unwrap()),
+    });
+
+    let mut expected_driver = MockDriver::default();
+    let mut tx = CanTransmitter::new(Mtu::Can8);
+    tx.push(
+        Transfer {
+            header: header.clone(),
+            loopback: false,
+            payload: &payload,
+        },
+        &mut ZeroClock,
+        &mut expected_driver,
+    )
+    .unwrap();
+
+    let mut actual_driver = MockDriver::default();
+    let pieces: [&[u8]; 3] = [&header_bytes, &[], &body];
+    tx.push_streaming(
+        Transfer {
+            header,
+            loopback: false,
+            payload: &pieces[..],
+        },
+        &mut ZeroClock,
+        &mut actual_driver,
+    )
+    .unwrap();
+    assert_eq!(expected_driver.queue, actual_driver.queue);
+}
+
+/// A payload that is produced in pieces of increasing length
+struct Pieces<'a>(&'a [u8]);
+
+impl StreamingPayload for Pieces<'_> {
+    fn payload_length(&self) -> usize {
+        self.0.len()
+    }
+
+    fn write_to(&self, sink: &mut dyn FnMut(&[u8])) {
+        let mut remaining = self.0;
+        let mut piece_length = 1;
+        while !remaining.is_empty() {
+            let (piece, rest) = remaining.split_at(piece_length.min(remaining.len()));
+            sink(piece);
+            remaining = rest;
+            piece_length += 1;
+        }
+    }
+}
+
+#[test]
+fn test_profiler() {
+    /// A profiler with a counter that advances by 10 every time it is read
+    #[derive(Default)]
+    struct StepProfiler {
+        counter: u32,
+        records: Vec<(Operation, u32)>,
+    }
+    impl Profiler for StepProfiler {
+        fn counter(&mut self) -> u32 {
+            self.counter = self.counter.wrapping_add(10);
+            self.counter
+        }
+        fn record(&mut self, operation: Operation, duration: u32) {
+            self.records.push((operation, duration));
+        }
+    }
+
+    let mut driver = MockDriver::default();
+    let mut tx = CanTransmitter::with_profiler(
+        Mtu::Can8,
+        StepProfiler {
+            // Start close to the end to check wrapping
+            counter: u32::MAX - 5,
+            records: Vec::new(),
+        },
+    );
+    tx.push(
+        Transfer {
+            header: Header::Message(MessageHeader {
+                timestamp: instant(0),
+                transfer_id: CanTransferId::try_from(0).unwrap(),
+                priority: Priority::Nominal,
+                subject: SubjectId::try_from(7509).unwrap(),
+                source: Some(CanNodeId::try_from(42u8).unwrap()),
+            }),
+            loopback: false,
+            payload: &[0x00, 0x00, 0x00, 0x00, 0x04, 0x78, 0x68],
+        },
+        &mut ZeroClock,
+        &mut driver,
+    )
+    .unwrap();
+    tx.flush(&mut ZeroClock, &mut driver).unwrap();
+    assert_eq!(
+        &[(Operation::Serialize, 10), (Operation::Flush, 10)],
+        &tx.profiler().records[..]
+    );
+}
+
 /// A simple driver that uses a `VecDeque`
 ///
 /// This does not keep the frames in order by priority, but it is correct as long as it is used for
@@ -0,0 +1,566 @@
+//! Connections to Cyphal networks that send and receive serialized payloads
+//!
+//! The tools work with data types that are only known at run time, so they send and receive
+//! payloads as bytes instead of generated types. [`Bus`] has the operations that they need, and
+//! [`TransportBus`] implements it with the transmitter and receiver of any transport.
+//! [`open_can`] and [`open_udp`] create buses for Cyphal/CAN and Cyphal/UDP.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::io;
+use std::net::Ipv4Addr;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use canadensis_core::session::SessionDynamicMap;
+use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32, StdClock};
+use canadensis_core::transfer::{Header, MessageHeader, ServiceHeader, Transfer};
+use canadensis_core::transport::{Receiver, TransferId, Transmitter, Transport};
+use canadensis_core::{nb, Priority, ServiceId, SubjectId};
+use canadensis_dynamic::registry::PayloadKind;
+use canadensis_udp::driver::StdUdpSocket;
+use canadensis_udp::{
+    UdpNodeId, UdpReceiver, UdpSessionData, UdpTransferId, UdpTransmitter, DEFAULT_PORT,
+};
+
+/// The maximum time between the first and last frames of an incoming transfer, and the time
+/// allowed to send an outgoing transfer
+pub const TRANSFER_TIMEOUT: MicrosecondDuration32 = MicrosecondDuration32::millis(1000);
+
+/// The MTU used for Cyphal/UDP
+const UDP_MTU: usize = 1200;
+
+/// A transfer that was received
+#[derive(Debug, Clone)]
+pub struct ReceivedTransfer {
+    /// The kind of transfer
+    pub kind: PayloadKind,
+    /// The subject ID or service ID
+    pub port_id: u16,
+    /// The node that sent this transfer, or None if it was anonymous
+    pub source: Option<u16>,
+    /// The node that this service transfer was sent to, or None for a message
+    pub destination: Option<u16>,
+    /// The transfer ID
+    pub transfer_id: u64,
+    /// The priority
+    pub priority: Priority,
+    /// The time when the first frame was received, according to the bus clock
+    pub timestamp: Microseconds32,
+    /// The system time when the transfer was received
+    pub system_time: SystemTime,
+    /// The transfer payload
+    pub payload: Vec<u8>,
+}
+
+impl ReceivedTransfer {
+    /// Creates a received transfer from a transfer of any transport
+    pub fn new<T>(transfer: Transfer<Vec<u8>, T>, system_time: SystemTime) -> Self
+    where
+        T: Transport<Priority = Priority>,
+        T::TransferId: TransferIdNumber,
+    {
+        let kind = match transfer.header {
+            Header::Message(_) => PayloadKind::Message,
+            Header::Request(_) => PayloadKind::Request,
+            Header::Response(_) => PayloadKind::Response,
+        };
+        match transfer.header {
+            Header::Message(header) => ReceivedTransfer {
+                kind,
+                port_id: header.subject.into(),
+                source: header.source.map(node_id_number),
+                destination: None,
+                transfer_id: header.transfer_id.number(),
+                priority: header.priority,
+                timestamp: header.timestamp,
+                system_time,
+                payload: transfer.payload,
+            },
+            Header::Request(header) | Header::Response(header) => ReceivedTransfer {
+                kind,
+                port_id: header.service.into(),
+                source: Some(node_id_number(header.source)),
+                destination: Some(node_id_number(header.destination)),
+                transfer_id: header.transfer_id.number(),
+                priority: header.priority,
+                timestamp: header.timestamp,
+                system_time,
+                payload: transfer.payload,
+            },
+        }
+    }
+}
+
+/// A connection to a Cyphal network
+pub trait Bus {
+    /// Returns the current time of the clock that this bus uses for timestamps
+    fn now(&mut self) -> Microseconds32;
+
+    /// Returns the ID of this node, or None if this node is anonymous
+    fn node_id(&self) -> Option<u16>;
+
+    /// Starts receiving transfers of one kind on a port
+    ///
+    /// Anonymous nodes can only subscribe to messages.
+    fn subscribe(
+        &mut self,
+        kind: PayloadKind,
+        port_id: u16,
+        payload_size_max: usize,
+    ) -> Result<(), BusError>;
+
+    /// Returns the next transfer that has been received, or None if no transfer is available
+    /// yet
+    ///
+    /// This may block for a short time (a few milliseconds) while waiting for a frame.
+    fn receive(&mut self) -> Result<Option<ReceivedTransfer>, BusError>;
+
+    /// Publishes a message
+    ///
+    /// Each subject has its own transfer ID, which increases by 1 after each message.
+    fn publish(&mut self, subject: u16, priority: Priority, payload: &[u8])
+        -> Result<(), BusError>;
+
+    /// Sends a service request to another node and returns its transfer ID
+    ///
+    /// The transfer ID of the response will be the same.
+    fn send_request(
+        &mut self,
+        service: u16,
+        destination: u16,
+        priority: Priority,
+        payload: &[u8],
+    ) -> Result<u64, BusError>;
+
+    /// Sends a response to a service request that was received
+    fn send_response(&mut self, request: &ReceivedTransfer, payload: &[u8])
+        -> Result<(), BusError>;
+}
+
+/// Conversions from transport-specific transfer IDs to integers
+pub trait TransferIdNumber {
+    /// Returns the value of this transfer ID
+    fn number(&self) -> u64;
+    /// Returns the transfer ID with a value, or None if the value is out of range
+    fn from_number(number: u64) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+impl TransferIdNumber for canadensis_can::CanTransferId {
+    fn number(&self) -> u64 {
+        u8::from(*self).into()
+    }
+    fn from_number(number: u64) -> Option<Self> {
+        u8::try_from(number)
+            .ok()
+            .and_then(|number| canadensis_can::CanTransferId::try_from(number).ok())
+    }
+}
+
+impl TransferIdNumber for UdpTransferId {
+    fn number(&self) -> u64 {
+        u64::from(*self)
+    }
+    fn from_number(number: u64) -> Option<Self> {
+        Some(number.into())
+    }
+}
+
+/// Receive errors that may mean that no frame was available yet
+pub trait WouldBlock {
+    /// Returns true if this error means that no frame was available yet
+    fn would_block(&self) -> bool;
+}
+
+impl WouldBlock for canadensis_can::Error<io::Error> {
+    fn would_block(&self) -> bool {
+        matches!(self, canadensis_can::Error::Driver(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut))
+    }
+}
+
+impl<E> WouldBlock for canadensis_udp::Error<E> {
+    fn would_block(&self) -> bool {
+        // The UDP receiver returns Ok(None) when no packet is available
+        false
+    }
+}
+
+/// A [`Bus`] that uses a transmitter and receiver of some transport
+pub struct TransportBus<C, T, R, D>
+where
+    T: Transmitter<C, Driver = D>,
+    C: Clock,
+{
+    clock: C,
+    transmitter: T,
+    receiver: R,
+    driver: D,
+    node_id: Option<<T::Transport as Transport>::NodeId>,
+    /// The next transfer ID for each kind of outgoing transfer, port ID, and destination
+    transfer_ids: BTreeMap<(u8, u16, u16), <T::Transport as Transport>::TransferId>,
+}
+
+impl<C, T, R, D> TransportBus<C, T, R, D>
+where
+    C: Clock,
+    T: Transmitter<C, Driver = D>,
+    R: Receiver<C, Driver = D, Transport = T::Transport>,
+    T::Transport: Transport<Priority = Priority>,
+    <T::Transport as Transport>::TransferId: TransferIdNumber,
+    R::Error: WouldBlock,
+{
+    /// Creates a bus
+    ///
+    /// If `node_id` is None, the bus is anonymous and can only send and receive messages.
+    pub fn new(
+        clock: C,
+        transmitter: T,
+        receiver: R,
+        driver: D,
+        node_id: Option<<T::Transport as Transport>::NodeId>,
+    ) -> Self {
+        TransportBus {
+            clock,
+            transmitter,
+            receiver,
+            driver,
+            node_id,
+            transfer_ids: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the transfer ID to use for an outgoing transfer, and increments the stored
+    /// transfer ID
+    fn next_transfer_id(
+        &mut self,
+        kind: u8,
+        port_id: u16,
+        destination: u16,
+    ) -> <T::Transport as Transport>::TransferId {
+        let stored = self
+            .transfer_ids
+            .entry((kind, port_id, destination))
+            .or_default();
+        let transfer_id = stored.clone();
+        *stored = transfer_id.clone().increment();
+        transfer_id
+    }
+
+    /// Sends a transfer and waits until all its frames have been sent
+    fn send(&mut self, header: Header<T::Transport>, payload: &[u8]) -> Result<(), BusError> {
+        let deadline = match &header {
+            Header::Message(header) => header.timestamp,
+            Header::Request(header) | Header::Response(header) => header.timestamp,
+        };
+        let transfer = Transfer {
+            header,
+            loopback: false,
+            payload,
+        };
+        loop {
+            match self
+                .transmitter
+                .push(transfer.clone(), &mut self.clock, &mut self.driver)
+            {
+                Ok(()) => break,
+                Err(nb::Error::WouldBlock) if self.clock.now() < deadline => {
+                    thread::sleep(Duration::from_millis(1))
+                }
+                Err(nb::Error::WouldBlock) => return Err(BusError::Timeout),
+                Err(nb::Error::Other(e)) => return Err(BusError::transport(e)),
+            }
+        }
+        loop {
+            match self.transmitter.flush(&mut self.clock, &mut self.driver) {
+                Ok(()) => return Ok(()),
+                Err(nb::Error::WouldBlock) if self.clock.now() < deadline => {
+                    thread::sleep(Duration::from_millis(1))
+                }
+                Err(nb::Error::WouldBlock) => return Err(BusError::Timeout),
+                Err(nb::Error::Other(e)) => return Err(BusError::transport(e)),
+            }
+        }
+    }
+
+    /// Returns the ID of this node, or an error if this node is anonymous
+    fn require_node_id(&self) -> Result<<T::Transport as Transport>::NodeId, BusError> {
+        self.node_id.clone().ok_or(BusError::Anonymous)
+    }
+}
+
+impl<C, T, R, D> Bus for TransportBus<C, T, R, D>
+where
+    C: Clock,
+    T: Transmitter<C, Driver = D>,
+    R: Receiver<C, Driver = D, Transport = T::Transport>,
+    T::Transport: Transport<Priority = Priority>,
+    <T::Transport as Transport>::TransferId: TransferIdNumber,
+    R::Error: WouldBlock,
+{
+    fn now(&mut self) -> Microseconds32 {
+        self.clock.now()
+    }
+
+    fn node_id(&self) -> Option<u16> {
+        self.node_id.clone().map(node_id_number)
+    }
+
+    fn subscribe(
+        &mut self,
+        kind: PayloadKind,
+        port_id: u16,
+        payload_size_max: usize,
+    ) -> Result<(), BusError> {
+        let receiver = &mut self.receiver;
+        let driver = &mut self.driver;
+        match kind {
+            PayloadKind::Message => {
+                let subject =
+                    SubjectId::try_from(port_id).map_err(|_| BusError::PortId(port_id))?;
+                receiver
+                    .subscribe_message(subject, payload_size_max, TRANSFER_TIMEOUT, driver)
+                    .map_err(BusError::transport)
+            }
+            PayloadKind::Request | PayloadKind::Response => {
+                let service =
+                    ServiceId::try_from(port_id).map_err(|_| BusError::PortId(port_id))?;
+                if self.node_id.is_none() {
+                    return Err(BusError::Anonymous);
+                }
+                let result = if kind == PayloadKind::Request {
+                    receiver.subscribe_request(service, payload_size_max, TRANSFER_TIMEOUT, driver)
+                } else {
+                    receiver.subscribe_response(service, payload_size_max, TRANSFER_TIMEOUT, driver)
+                };
+                result.map_err(BusError::transport)
+            }
+        }
+    }
+
+    fn receive(&mut self) -> Result<Option<ReceivedTransfer>, BusError> {
+        let transfer = match self.receiver.receive(&mut self.clock, &mut self.driver) {
+            Ok(Some(transfer)) => transfer,
+            Ok(None) => return Ok(None),
+            Err(e) if e.would_block() => return Ok(None),
+            Err(e) => return Err(BusError::transport(e)),
+        };
+        Ok(Some(ReceivedTransfer::new(transfer, SystemTime::now())))
+    }
+
+    fn publish(
+        &mut self,
+        subject: u16,
+        priority: Priority,
+        payload: &[u8],
+    ) -> Result<(), BusError> {
+        let subject_id = SubjectId::try_from(subject).map_err(|_| BusError::PortId(subject))?;
+        let transfer_id = self.next_transfer_id(0, subject, 0);
+        let header = Header::Message(MessageHeader {
+            timestamp: self.clock.now() + TRANSFER_TIMEOUT,
+            transfer_id,
+            priority,
+            subject: subject_id,
+            source: self.node_id.clone(),
+        });
+        self.send(header, payload)
+    }
+
+    fn send_request(
+        &mut self,
+        service: u16,
+        destination: u16,
+        priority: Priority,
+        payload: &[u8],
+    ) -> Result<u64, BusError> {
+        let service_id = ServiceId::try_from(service).map_err(|_| BusError::PortId(service))?;
+        let source = self.require_node_id()?;
+        let destination_id = node_id_from_number::<T::Transport>(destination)?;
+        let transfer_id = self.next_transfer_id(1, service, destination);
+        let number = transfer_id.number();
+        let header = Header::Request(ServiceHeader {
+            timestamp: self.clock.now() + TRANSFER_TIMEOUT,
+            transfer_id,
+            priority,
+            service: service_id,
+            source,
+            destination: destination_id,
+        });
+        self.send(header, payload)?;
+        Ok(number)
+    }
+
+    fn send_response(
+        &mut self,
+        request: &ReceivedTransfer,
+        payload: &[u8],
+    ) -> Result<(), BusError> {
+        let service_id =
+            ServiceId::try_from(request.port_id).map_err(|_| BusError::PortId(request.port_id))?;
+        let source = self.require_node_id()?;
+        let requester = request.source.ok_or(BusError::Anonymous)?;
+        let destination = node_id_from_number::<T::Transport>(requester)?;
+        let transfer_id = <T::Transport as Transport>::TransferId::from_number(request.transfer_id)
+            .ok_or_else(|| BusError::Transport("Invalid transfer ID".to_owned()))?;
+        let header = Header::Response(ServiceHeader {
+            timestamp: self.clock.now() + TRANSFER_TIMEOUT,
+            transfer_id,
+            priority: request.priority,
+            service: service_id,
+            source,
+            destination,
+        });
+        self.send(header, payload)
+    }
+}
+
+fn node_id_number<N: Into<usize>>(node_id: N) -> u16 {
+    node_id.into() as u16
+}
+
+fn node_id_from_number<T: Transport>(number: u16) -> Result<T::NodeId, BusError> {
+    T::NodeId::try_from(number).map_err(|_| BusError::NodeId(number))
+}
+
+/// Opens a Cyphal/CAN bus on a SocketCAN interface, like `can0` or `vcan0`
+///
+/// If `node_id` is None, the bus is anonymous.
+#[cfg(feature = "can")]
+pub fn open_can(interface: &str, node_id: Option<u16>) -> Result<Box<dyn Bus>, BusError> {
+    use canadensis_can::{CanReceiver, CanTransmitter, CanTransport, Mtu};
+    use canadensis_linux::{LinuxCan, SystemClock};
+    use socketcan::{CanSocket, Socket};
+
+    let open_error = |inner| BusError::Open {
+        name: interface.to_owned(),
+        inner,
+    };
+    let socket = CanSocket::open(interface).map_err(open_error)?;
+    // Receiving waits for at most this long, so the tools can do other things while
+    // waiting for transfers
+    socket
+        .set_read_timeout(Duration::from_millis(5))
+        .map_err(open_error)?;
+    let node_id = node_id
+        .map(|number| node_id_from_number::<CanTransport>(number))
+        .transpose()?;
+    let receiver = match node_id {
+        Some(node_id) => CanReceiver::new(node_id, Mtu::Can8),
+        None => CanReceiver::new_anonymous(Mtu::Can8),
+    };
+    let bus: TransportBus<SystemClock, CanTransmitter<SystemClock, LinuxCan>, _, _> =
+        TransportBus::new(
+            SystemClock::new(),
+            CanTransmitter::new(Mtu::Can8),
+            receiver,
+            LinuxCan::new(socket),
+            node_id,
+        );
+    Ok(Box::new(bus))
+}
+
+/// The receiver for Cyphal/UDP buses
+type UdpBusReceiver = UdpReceiver<
+    StdClock,
+    SessionDynamicMap<UdpNodeId, UdpTransferId, UdpSessionData>,
+    StdUdpSocket,
+    UDP_MTU,
+>;
+
+/// Opens a Cyphal/UDP bus on the network interface with an IPv4 address
+///
+/// If `node_id` is None, the bus is anonymous.
+///
+/// If `listen` is true, the bus binds to the Cyphal/UDP port so that it can receive transfers.
+/// Only one program on a computer can do that at a time, so commands that only send
+/// transfers should set `listen` to false.
+pub fn open_udp(
+    interface: Ipv4Addr,
+    node_id: Option<u16>,
+    listen: bool,
+) -> Result<Box<dyn Bus>, BusError> {
+    let node_id = node_id
+        .map(node_id_from_number::<canadensis_udp::UdpTransport>)
+        .transpose()?;
+    let bind_result = if listen {
+        StdUdpSocket::bind(Ipv4Addr::UNSPECIFIED, DEFAULT_PORT)
+    } else {
+        StdUdpSocket::bind(interface, 0)
+    };
+    let socket = bind_result.map_err(|inner| BusError::Open {
+        name: interface.to_string(),
+        inner,
+    })?;
+    let bus: TransportBus<_, UdpTransmitter<_, UDP_MTU>, UdpBusReceiver, _> = TransportBus::new(
+        StdClock::new(),
+        UdpTransmitter::new(DEFAULT_PORT),
+        UdpReceiver::new(node_id, interface),
+        socket,
+        node_id,
+    );
+    Ok(Box::new(bus))
+}
+
+/// The names of the priorities, from highest (0) to lowest (7)
+pub const PRIORITY_NAMES: [&str; 8] = [
+    "exceptional",
+    "immediate",
+    "fast",
+    "high",
+    "nominal",
+    "low",
+    "slow",
+    "optional",
+];
+
+/// Returns the name of a priority, like `nominal`
+pub fn priority_name(priority: Priority) -> &'static str {
+    PRIORITY_NAMES[usize::from(u8::from(priority))]
+}
+
+/// Finds a priority by its name
+pub fn parse_priority(name: &str) -> Option<Priority> {
+    PRIORITY_NAMES
+        .iter()
+        .position(|&priority_name| priority_name == name)
+        .map(|number| Priority::try_from(number as u8).expect("Invalid priority"))
+}
+
+/// Errors that can occur when opening or using a bus
+#[derive(thiserror::Error, Debug)]
+pub enum BusError {
+    /// A network interface could not be opened
+    #[error("Failed to open {name}")]
+    Open {
+        /// The name or address of the interface
+        name: String,
+        /// The error from opening it
+        #[source]
+        inner: io::Error,
+    },
+    /// A node ID was too large for the transport
+    #[error("Node ID {0} is not valid for this transport")]
+    NodeId(u16),
+    /// A subject ID or service ID was too large
+    #[error("Port ID {0} is not valid")]
+    PortId(u16),
+    /// A service transfer can't be sent or received because this node is anonymous
+    #[error(
+        "This node is anonymous, so it can't send or receive service transfers (use --node-id)"
+    )]
+    Anonymous,
+    /// A transfer could not be sent before its deadline
+    #[error("Timed out while sending a transfer")]
+    Timeout,
+    /// The transport reported an error
+    #[error("Transport error: {0}")]
+    Transport(String),
+}
+
+impl BusError {
+    fn transport<E: Debug>(error: E) -> Self {
+        BusError::Transport(format!("{:?}", error))
+    }
+}
@@ -0,0 +1,203 @@
+//! Sending service requests and waiting for the responses
+//!
+//! The request is written as YAML or JSON, in the same way as a message for
+//! [`publish`](crate::publish). The response is printed as a YAML document that maps the
+//! service ID to the response fields:
+//!
+//! ```text
+//! ---
+//! 430:
+//!   protocol_version:
+//!     major: 1
+//!     minor: 0
+//!   ...
+//! ```
+
+use std::time::{Duration, Instant};
+
+use canadensis_core::Priority;
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::yaml::{from_yaml, YamlConfig, YamlError};
+use canadensis_dynamic::Value;
+
+use crate::bus::{Bus, BusError};
+use crate::monitor::yaml_document;
+use crate::types::{receive_size, Port};
+
+/// Serializes a request, written as YAML or JSON, into a payload for a service
+pub fn request_payload(
+    registry: &TypeRegistry,
+    service: &Port,
+    text: &str,
+) -> Result<Vec<u8>, CallError> {
+    let request = registry.message(&service.key, PayloadKind::Request)?;
+    let value = from_yaml(request, text).map_err(|inner| CallError::Request {
+        key: service.key.to_string(),
+        inner,
+    })?;
+    Ok(registry.serialize(&service.key, PayloadKind::Request, &value)?)
+}
+
+/// Sends a request to a server node and waits for the response
+///
+/// This returns an error if the server does not respond before the timeout.
+pub fn call(
+    bus: &mut dyn Bus,
+    registry: &TypeRegistry,
+    service: &Port,
+    server: u16,
+    payload: &[u8],
+    priority: Priority,
+    timeout: Duration,
+) -> Result<Value, CallError> {
+    let response = registry.message(&service.key, PayloadKind::Response)?;
+    bus.subscribe(
+        PayloadKind::Response,
+        service.port_id,
+        receive_size(response),
+    )?;
+    let deadline = Instant::now() + timeout;
+    let transfer_id = bus.send_request(service.port_id, server, priority, payload)?;
+    while Instant::now() < deadline {
+        let transfer = match bus.receive()? {
+            Some(transfer) => transfer,
+            None => continue,
+        };
+        let matches = transfer.kind == PayloadKind::Response
+            && transfer.port_id == service.port_id
+            && transfer.source == Some(server)
+            && transfer.transfer_id == transfer_id;
+        if matches {
+            return Ok(registry.deserialize(
+                &service.key,
+                PayloadKind::Response,
+                &transfer.payload,
+            )?);
+        }
+    }
+    Err(CallError::Timeout { server, timeout })
+}
+
+/// Formats a response as a YAML document (without a newline at the end)
+pub fn format_response(service: &Port, response: &Value, config: &YamlConfig) -> String {
+    yaml_document(service.port_id, None, response, config)
+}
+
+/// Errors that can occur when calling a service
+#[derive(thiserror::Error, Debug)]
+pub enum CallError {
+    /// The request text was not valid for the type
+    #[error("Invalid request for {key}")]
+    Request {
+        /// The service type
+        key: String,
+        /// The problem with the text
+        #[source]
+        inner: YamlError,
+    },
+    /// The type was not a service type, or the response could not be deserialized
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    /// Sending or receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+    /// The server did not respond in time
+    #[error("No response from node {server} within {timeout:?}")]
+    Timeout {
+        /// The node ID of the server
+        server: u16,
+        /// The time allowed for the response
+        timeout: Duration,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::{call, format_response, request_payload, CallError};
+    use crate::bus::ReceivedTransfer;
+    use crate::mock_bus::{response_to, MockBus, Server};
+    use crate::types::find_service;
+    use canadensis_core::Priority;
+    use canadensis_dsdl_frontend::{Config, Package};
+    use canadensis_dynamic::registry::TypeRegistry;
+    use canadensis_dynamic::yaml::YamlConfig;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    /// A server that responds to each request with a fixed payload, or does not respond if
+    /// the payload is None
+    struct EchoServer {
+        response: Option<Vec<u8>>,
+    }
+
+    impl Server for EchoServer {
+        fn request(
+            &mut self,
+            request: &ReceivedTransfer,
+            received: &mut VecDeque<ReceivedTransfer>,
+        ) {
+            let response = match &self.response {
+                Some(response) => response,
+                None => return,
+            };
+            // A response from a different node, which should be ignored
+            let mut other = response_to(request, response.clone());
+            other.source = request.destination.map(|destination| destination + 1);
+            received.push_back(other);
+            received.push_back(response_to(request, response.clone()));
+        }
+    }
+
+    #[test]
+    fn call_service() {
+        let mut package = Package::new();
+        package
+            .add_string(
+                Some(200),
+                "test.Add.1.0".parse().unwrap(),
+                "uint8 a\nuint8 b\n@sealed\n---\nuint16 sum\n@sealed\n".into(),
+            )
+            .unwrap();
+        let registry = TypeRegistry::new(package.compile(&Config::default()).unwrap());
+        let service = find_service(&registry, "test.Add").unwrap();
+        let payload = request_payload(&registry, &service, "{a: 1, b: 2}").unwrap();
+        assert_eq!(vec![1, 2], payload);
+        assert!(matches!(
+            request_payload(&registry, &service, "sum: 3"),
+            Err(CallError::Request { .. })
+        ));
+
+        let mut bus = MockBus::new(EchoServer {
+            response: Some(vec![3, 0]),
+        });
+        let response = call(
+            &mut bus,
+            &registry,
+            &service,
+            42,
+            &payload,
+            Priority::Nominal,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(1, bus.requests.len());
+        assert_eq!(42, bus.requests[0].destination.unwrap());
+        assert_eq!(payload, bus.requests[0].payload);
+        assert_eq!(
+            "---\n200:\n  sum: 3",
+            format_response(&service, &response, &YamlConfig::default())
+        );
+
+        bus.server.response = None;
+        let result = call(
+            &mut bus,
+            &registry,
+            &service,
+            7,
+            &payload,
+            Priority::Nominal,
+            Duration::from_millis(10),
+        );
+        assert!(matches!(result, Err(CallError::Timeout { server: 7, .. })));
+    }
+}
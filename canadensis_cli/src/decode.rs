@@ -0,0 +1,265 @@
+//! Decoding messages from captured Cyphal/UDP traffic
+//!
+//! This reads a pcapng file, reassembles the Cyphal/UDP transfers in it, and prints the messages
+//! on some subjects in the same format as [`Monitor`]. The `ts_system` metadata is the capture
+//! time of the last frame of each transfer, and `ts_monotonic` is the time since the first
+//! packet in the capture.
+
+use std::io::{self, Read, Write};
+use std::time::{Duration, UNIX_EPOCH};
+
+use canadensis_udp::capture::{udp_datagram, CaptureError, Decoder, PcapngReader};
+use canadensis_udp::DEFAULT_PORT;
+
+use crate::bus::{ReceivedTransfer, TRANSFER_TIMEOUT};
+use crate::monitor::Monitor;
+
+/// The maximum payload size of a decoded transfer
+const PAYLOAD_SIZE_MAX: usize = 65_536;
+
+/// Options for decoding a capture
+#[derive(Debug, Clone, Default)]
+pub struct DecodeOptions {
+    /// If this is not None, only packets captured on the interface with this index are decoded
+    pub interface: Option<u32>,
+    /// The UDP port that Cyphal/UDP datagrams were sent to, or None to use the default port
+    pub port: Option<u16>,
+}
+
+/// Reads a pcapng capture and writes the messages that `monitor` can format to `output`
+///
+/// Messages that can't be deserialized are reported on standard error and skipped. This returns
+/// the number of messages written.
+pub fn run<R: Read, W: Write>(
+    monitor: &Monitor<'_>,
+    input: R,
+    options: &DecodeOptions,
+    mut output: W,
+) -> Result<usize, DecodeError> {
+    let mut reader = PcapngReader::new(input)?;
+    let port = options.port.unwrap_or(DEFAULT_PORT);
+    let mut decoder = Decoder::new(port, PAYLOAD_SIZE_MAX, TRANSFER_TIMEOUT);
+    let mut start = None;
+    let mut count = 0;
+    while let Some(packet) = reader.next_packet()? {
+        if options
+            .interface
+            .is_some_and(|interface| interface != packet.interface)
+        {
+            continue;
+        }
+        let datagram = match udp_datagram(packet.link_type, &packet.data) {
+            Some(datagram) if datagram.destination.port() == port => datagram,
+            _ => continue,
+        };
+        let start = *start.get_or_insert(packet.timestamp);
+        let transfer = match decoder
+            .push_datagram(packet.timestamp.saturating_sub(start), datagram.payload)
+            .map_err(|_| DecodeError::Memory)?
+        {
+            Some(transfer) => transfer,
+            None => continue,
+        };
+        let system_time = UNIX_EPOCH + Duration::from_micros(packet.timestamp);
+        let transfer = ReceivedTransfer::new(transfer, system_time);
+        match monitor.format(&transfer) {
+            Ok(Some(document)) => {
+                writeln!(output, "{}", document)?;
+                count += 1;
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!(
+                "Invalid message on subject {} from node {:?}: {}",
+                transfer.port_id, transfer.source, e
+            ),
+        }
+    }
+    Ok(count)
+}
+
+/// Errors that can occur while decoding a capture
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeError {
+    /// The capture could not be read
+    #[error(transparent)]
+    Capture(#[from] CaptureError),
+    /// Memory allocation failed while reassembling a transfer
+    #[error("Out of memory")]
+    Memory,
+    /// Writing the output failed
+    #[error("Failed to write output")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run, DecodeOptions};
+    use crate::monitor::Monitor;
+    use crate::types::find_subject;
+    use canadensis_core::time::{Clock, Microseconds32};
+    use canadensis_core::transfer::{Header, MessageHeader, Transfer};
+    use canadensis_core::transport::Transmitter;
+    use canadensis_core::{nb, Priority, SubjectId};
+    use canadensis_dsdl_frontend::{Config, Package};
+    use canadensis_dynamic::registry::TypeRegistry;
+    use canadensis_dynamic::yaml::YamlConfig;
+    use canadensis_udp::driver::UdpSocket;
+    use canadensis_udp::{UdpNodeId, UdpTransmitter, UdpTransport, DEFAULT_PORT};
+    use std::convert::{Infallible, TryFrom};
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    /// A socket that keeps the datagrams that are sent
+    #[derive(Default)]
+    struct RecordingSocket(Vec<(Vec<u8>, SocketAddrV4)>);
+
+    impl UdpSocket for RecordingSocket {
+        type Error = Infallible;
+        fn local_addr(&self) -> Result<SocketAddrV4, Infallible> {
+            Ok(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 50000))
+        }
+        fn join_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn leave_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn send_to(&mut self, data: &[u8], destination: SocketAddrV4) -> Result<usize, Infallible> {
+            self.0.push((data.to_vec(), destination));
+            Ok(data.len())
+        }
+        fn recv(&mut self, _: &mut [u8]) -> Result<usize, nb::Error<Infallible>> {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    struct ZeroClock;
+
+    impl Clock for ZeroClock {
+        fn now(&mut self) -> Microseconds32 {
+            Microseconds32::from_ticks(0)
+        }
+    }
+
+    /// Returns an IPv4 packet with a Cyphal/UDP message from node 42
+    fn message_packet(subject: u16, payload: &[u8]) -> Vec<u8> {
+        let mut transmitter = UdpTransmitter::<RecordingSocket, 1200>::new(DEFAULT_PORT);
+        let mut socket = RecordingSocket::default();
+        let transfer: Transfer<_, UdpTransport> = Transfer {
+            header: Header::Message(MessageHeader {
+                timestamp: Microseconds32::from_ticks(1_000_000),
+                transfer_id: 3.into(),
+                priority: Priority::Nominal,
+                subject: SubjectId::try_from(subject).unwrap(),
+                source: Some(UdpNodeId::try_from(42).unwrap()),
+            }),
+            loopback: false,
+            payload,
+        };
+        transmitter
+            .push(transfer, &mut ZeroClock, &mut socket)
+            .unwrap();
+        let (datagram, destination) = socket.0.remove(0);
+
+        let mut packet = vec![0x45, 0];
+        packet.extend_from_slice(&(28 + datagram.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&[0, 0, 0, 0, 16, 17, 0, 0, 127, 0, 0, 1]);
+        packet.extend_from_slice(&destination.ip().octets());
+        packet.extend_from_slice(&50000_u16.to_be_bytes());
+        packet.extend_from_slice(&destination.port().to_be_bytes());
+        packet.extend_from_slice(&(8 + datagram.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(&datagram);
+        packet
+    }
+
+    /// Appends a little-endian pcapng block
+    fn block(capture: &mut Vec<u8>, block_type: u32, mut body: Vec<u8>) {
+        while !body.len().is_multiple_of(4) {
+            body.push(0);
+        }
+        let length = (body.len() as u32 + 12).to_le_bytes();
+        capture.extend_from_slice(&block_type.to_le_bytes());
+        capture.extend_from_slice(&length);
+        capture.extend_from_slice(&body);
+        capture.extend_from_slice(&length);
+    }
+
+    /// Returns a capture of raw IPv4 packets on two interfaces
+    fn capture(packets: &[(u32, u64, Vec<u8>)]) -> Vec<u8> {
+        let mut capture = Vec::new();
+        let mut section_header = 0x1a2b_3c4d_u32.to_le_bytes().to_vec();
+        section_header
+            .extend_from_slice(&[1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+        block(&mut capture, 0x0a0d_0d0a, section_header);
+        for _ in 0..2 {
+            block(&mut capture, 1, vec![228, 0, 0, 0, 0, 0, 0, 0]);
+        }
+        for (interface, timestamp, data) in packets {
+            let mut body = Vec::new();
+            for value in [
+                *interface,
+                (timestamp >> 32) as u32,
+                *timestamp as u32,
+                data.len() as u32,
+                data.len() as u32,
+            ] {
+                body.extend_from_slice(&value.to_le_bytes());
+            }
+            body.extend_from_slice(data);
+            block(&mut capture, 6, body);
+        }
+        capture
+    }
+
+    #[test]
+    fn decode_messages() {
+        let mut package = Package::new();
+        package
+            .add_string(
+                Some(100),
+                "test.Point.1.0".parse().unwrap(),
+                "int16 x\nint16 y\n@sealed\n".into(),
+            )
+            .unwrap();
+        let registry = TypeRegistry::new(package.compile(&Config::default()).unwrap());
+        let subjects = vec![find_subject(&registry, "test.Point").unwrap()];
+        let monitor = Monitor::new(&registry, subjects, YamlConfig::default());
+
+        let capture = capture(&[
+            (
+                0,
+                1_700_000_000_000_000,
+                message_packet(100, &[1, 0, 0xff, 0xff]),
+            ),
+            // Another subject
+            (0, 1_700_000_000_100_000, message_packet(101, &[0])),
+            // A message on another interface
+            (1, 1_700_000_001_500_250, message_packet(100, &[2, 0, 0, 0])),
+        ]);
+        let mut output = Vec::new();
+        let count = run(
+            &monitor,
+            &capture[..],
+            &DecodeOptions::default(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(2, count);
+        assert_eq!(
+            "---\n100:\n  _meta_: {ts_system: 1700000000.000000, ts_monotonic: 0.000000, \
+            source_node_id: 42, transfer_id: 3, priority: nominal, dtype: test.Point.1.0}\n  \
+            x: 1\n  y: -1\n\
+            ---\n100:\n  _meta_: {ts_system: 1700000001.500250, ts_monotonic: 1.500250, \
+            source_node_id: 42, transfer_id: 3, priority: nominal, dtype: test.Point.1.0}\n  \
+            x: 2\n  y: 0\n",
+            String::from_utf8(output).unwrap()
+        );
+
+        let options = DecodeOptions {
+            interface: Some(1),
+            ..DecodeOptions::default()
+        };
+        let count = run(&monitor, &capture[..], &options, Vec::new()).unwrap();
+        assert_eq!(1, count);
+    }
+}
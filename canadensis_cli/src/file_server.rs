@@ -0,0 +1,705 @@
+//! Serving the files in a local directory to other nodes
+//!
+//! A [`FileServer`] responds to the `uavcan.file` services (`GetInfo`, `List`, `Modify`,
+//! `Read`, and `Write`). Paths in requests are relative to the root directory of the server,
+//! and paths that would refer to something outside the root directory (using `..`) are
+//! rejected with the `ACCESS_DENIED` error.
+//!
+//! The error codes in responses have the same values as the corresponding POSIX `errno`
+//! values, so most I/O errors are reported with their own codes.
+
+use std::convert::TryFrom;
+use std::fs::{self, File, Metadata};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use canadensis_dynamic::json::{from_json, JsonError};
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::Value;
+use serde_json::json;
+
+use crate::bus::{Bus, BusError, ReceivedTransfer};
+use crate::types::{field, find_service, receive_size, uint8_text, Port};
+
+/// The maximum number of bytes in a read response
+const READ_SIZE: u64 = 256;
+
+const OK: u16 = 0;
+const NOT_FOUND: u16 = 2;
+const ACCESS_DENIED: u16 = 13;
+const INVALID_VALUE: u16 = 22;
+const UNKNOWN_ERROR: u16 = 65535;
+/// Other error codes that have the same values as `errno` values (`EIO`, `EISDIR`, `EINVAL`,
+/// `EFBIG`, `ENOSPC`, and `ENOSYS`)
+const ERRNO_CODES: [u16; 6] = [5, 21, 22, 27, 28, 38];
+
+/// Responds to file service requests using the files in a directory
+pub struct FileServer<'r> {
+    registry: &'r TypeRegistry,
+    root: PathBuf,
+    read_only: bool,
+    get_info: Port,
+    list: Port,
+    modify: Port,
+    read: Port,
+    write: Port,
+}
+
+/// A response to a request, and a description of what happened
+pub struct Handled {
+    /// The response payload
+    pub response: Vec<u8>,
+    /// A description of the request and the result, or None if the request was one of the
+    /// middle parts of a file that was read or written
+    pub summary: Option<String>,
+    /// The part of a file that was read successfully, if the request was a read request
+    pub read: Option<FileRead>,
+}
+
+/// A part of a file that a client read
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileRead {
+    /// The path in the request
+    pub path: String,
+    /// The offset of the end of the data that was read
+    pub end: u64,
+}
+
+impl<'r> FileServer<'r> {
+    /// Creates a server for the files in the `root` directory
+    ///
+    /// If `read_only` is true, the server rejects all `Modify` and `Write` requests.
+    pub fn new(
+        registry: &'r TypeRegistry,
+        root: PathBuf,
+        read_only: bool,
+    ) -> Result<Self, FileServerError> {
+        let service = |name| find_service(registry, name).map_err(FileServerError::Types);
+        Ok(FileServer {
+            registry,
+            root,
+            read_only,
+            get_info: service("uavcan.file.GetInfo.0")?,
+            list: service("uavcan.file.List.0")?,
+            modify: service("uavcan.file.Modify.1")?,
+            read: service("uavcan.file.Read.1")?,
+            write: service("uavcan.file.Write.1")?,
+        })
+    }
+
+    fn services(&self) -> [&Port; 5] {
+        [
+            &self.get_info,
+            &self.list,
+            &self.modify,
+            &self.read,
+            &self.write,
+        ]
+    }
+
+    /// Starts receiving requests for all the file services
+    pub fn subscribe(&self, bus: &mut dyn Bus) -> Result<(), FileServerError> {
+        for service in self.services() {
+            let request = self.registry.message(&service.key, PayloadKind::Request)?;
+            bus.subscribe(PayloadKind::Request, service.port_id, receive_size(request))?;
+        }
+        Ok(())
+    }
+
+    /// Responds to requests until an error occurs
+    ///
+    /// Each completed operation is printed on standard output. Requests that can't be handled
+    /// are reported on standard error and do not get responses.
+    pub fn run(&self, bus: &mut dyn Bus) -> Result<(), FileServerError> {
+        loop {
+            let request = match bus.receive()? {
+                Some(request) => request,
+                None => continue,
+            };
+            match self.handle(&request) {
+                Ok(Some(handled)) => {
+                    bus.send_response(&request, &handled.response)?;
+                    if let (Some(summary), Some(client)) = (handled.summary, request.source) {
+                        println!("Node {}: {}", client, summary);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "Invalid request on service {} from node {:?}: {}",
+                    request.port_id, request.source, e
+                ),
+            }
+        }
+    }
+
+    /// Handles a request and returns the response
+    ///
+    /// This returns Ok(None) if the transfer is not a request for one of the file services.
+    pub fn handle(&self, transfer: &ReceivedTransfer) -> Result<Option<Handled>, FileServerError> {
+        if transfer.kind != PayloadKind::Request {
+            return Ok(None);
+        }
+        let service = match self
+            .services()
+            .iter()
+            .copied()
+            .find(|service| service.port_id == transfer.port_id)
+        {
+            Some(service) => service,
+            None => return Ok(None),
+        };
+        let request =
+            self.registry
+                .deserialize(&service.key, PayloadKind::Request, &transfer.payload)?;
+        let (response, summary) = if service == &self.get_info {
+            self.get_info(&request)
+        } else if service == &self.list {
+            self.list(&request)
+        } else if service == &self.modify {
+            self.modify(&request)
+        } else if service == &self.read {
+            self.read(&request)
+        } else {
+            self.write(&request)
+        };
+        let read = match response["data"]["value"].as_array() {
+            Some(data) if service == &self.read => Some(FileRead {
+                path: path_field(&request, "path"),
+                end: offset(&request) + data.len() as u64,
+            }),
+            _ => None,
+        };
+        let response = from_json(
+            self.registry.message(&service.key, PayloadKind::Response)?,
+            &response,
+        )?;
+        Ok(Some(Handled {
+            response: self
+                .registry
+                .serialize(&service.key, PayloadKind::Response, &response)?,
+            summary,
+            read,
+        }))
+    }
+
+    fn get_info(&self, request: &Value) -> (serde_json::Value, Option<String>) {
+        let path = path_field(request, "path");
+        let result = self
+            .resolve(&path)
+            .and_then(|local| Ok((fs::symlink_metadata(&local)?, local)));
+        let (link_metadata, local) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                let code = error_code(&e);
+                let summary = format!("get info {}: {}", path, error_name(code));
+                return (json!({ "error": { "value": code } }), Some(summary));
+            }
+        };
+        let metadata = fs::metadata(&local).unwrap_or_else(|_| link_metadata.clone());
+        let readable = if metadata.is_dir() {
+            fs::read_dir(&local).is_ok()
+        } else {
+            File::open(&local).is_ok()
+        };
+        let size = if metadata.is_dir() { 0 } else { metadata.len() };
+        let response = json!({
+            "size": size,
+            "unix_timestamp_of_last_modification": modification_time(&metadata),
+            "is_file_not_directory": !metadata.is_dir(),
+            "is_link": link_metadata.file_type().is_symlink(),
+            "is_readable": readable,
+            "is_writeable": !self.read_only && !metadata.permissions().readonly(),
+        });
+        (response, Some(format!("get info {}: ok", path)))
+    }
+
+    fn list(&self, request: &Value) -> (serde_json::Value, Option<String>) {
+        let path = path_field(request, "directory_path");
+        let index = match field(request, "entry_index") {
+            Some(Value::UInt(index)) => *index as usize,
+            _ => 0,
+        };
+        let entries = self.resolve(&path).and_then(|local| {
+            let mut names = fs::read_dir(local)?
+                .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+                .collect::<io::Result<Vec<String>>>()?;
+            names.sort();
+            Ok(names)
+        });
+        let name = entries
+            .ok()
+            .and_then(|mut names| {
+                if index < names.len() {
+                    Some(names.swap_remove(index))
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        // Only the first request of each listing is interesting
+        let summary = if index == 0 {
+            Some(format!("list {}", path))
+        } else {
+            None
+        };
+        (json!({ "entry_base_name": { "path": name } }), summary)
+    }
+
+    fn modify(&self, request: &Value) -> (serde_json::Value, Option<String>) {
+        let source = path_field(request, "source");
+        let destination = path_field(request, "destination");
+        let flag = |name| matches!(field(request, name), Some(Value::Bool(true)));
+        let (preserve_source, overwrite) = (flag("preserve_source"), flag("overwrite_destination"));
+        let (operation, result) = match (source.is_empty(), destination.is_empty()) {
+            (true, true) => ("modify", Err(error(INVALID_VALUE))),
+            (true, false) => (
+                "touch",
+                self.writable_path(&destination)
+                    .and_then(|local| touch(&local)),
+            ),
+            (false, true) => (
+                "remove",
+                self.writable_path(&source).and_then(|local| remove(&local)),
+            ),
+            (false, false) => {
+                let operation = if preserve_source { "copy" } else { "move" };
+                let result = self.writable_path(&source).and_then(|source| {
+                    let destination = self.writable_path(&destination)?;
+                    move_or_copy(&source, &destination, preserve_source, overwrite)
+                });
+                (operation, result)
+            }
+        };
+        let code = result.map(|_| OK).unwrap_or_else(|e| error_code(&e));
+        let paths = [source, destination]
+            .iter()
+            .filter(|path| !path.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<&str>>()
+            .join(" to ");
+        let summary = format!("{} {}: {}", operation, paths, error_name(code));
+        (json!({ "error": { "value": code } }), Some(summary))
+    }
+
+    fn read(&self, request: &Value) -> (serde_json::Value, Option<String>) {
+        let path = path_field(request, "path");
+        let offset = offset(request);
+        let result = self.resolve(&path).and_then(|local| {
+            let mut file = File::open(local)?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut data = Vec::new();
+            file.take(READ_SIZE).read_to_end(&mut data)?;
+            Ok(data)
+        });
+        match result {
+            Ok(data) => {
+                let summary = if (data.len() as u64) < READ_SIZE {
+                    Some(format!(
+                        "read {}: finished, {} bytes",
+                        path,
+                        offset + data.len() as u64
+                    ))
+                } else if offset == 0 {
+                    Some(format!("read {}: started", path))
+                } else {
+                    None
+                };
+                (json!({ "data": { "value": data } }), summary)
+            }
+            Err(e) => {
+                let code = error_code(&e);
+                let summary = format!("read {} at {}: {}", path, offset, error_name(code));
+                (json!({ "error": { "value": code } }), Some(summary))
+            }
+        }
+    }
+
+    fn write(&self, request: &Value) -> (serde_json::Value, Option<String>) {
+        let path = path_field(request, "path");
+        let offset = offset(request);
+        let data = field(request, "data")
+            .and_then(|data| field(data, "value"))
+            .map(|data| match data {
+                Value::Array(bytes) => bytes
+                    .iter()
+                    .filter_map(|byte| match byte {
+                        Value::UInt(byte) => Some(*byte as u8),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .unwrap_or_default();
+        let result = self.writable_path(&path).and_then(|local| {
+            if let Some(parent) = local.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(local)?;
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&data)?;
+            // The file ends after the data that was just written
+            file.set_len(offset + data.len() as u64)
+        });
+        let code = result.map(|_| OK).unwrap_or_else(|e| error_code(&e));
+        let summary = if code != OK {
+            Some(format!(
+                "write {} at {}: {}",
+                path,
+                offset,
+                error_name(code)
+            ))
+        } else if data.is_empty() {
+            Some(format!("write {}: finished, {} bytes", path, offset))
+        } else if offset == 0 {
+            Some(format!("write {}: started", path))
+        } else {
+            None
+        };
+        (json!({ "error": { "value": code } }), summary)
+    }
+
+    /// Converts a path in a request into a local path in the root directory
+    fn resolve(&self, path: &str) -> io::Result<PathBuf> {
+        let mut local = self.root.clone();
+        for component in Path::new(path.trim_start_matches('/')).components() {
+            match component {
+                Component::Normal(name) => local.push(name),
+                Component::CurDir => {}
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    return Err(error(ACCESS_DENIED))
+                }
+            }
+        }
+        Ok(local)
+    }
+
+    /// Converts a path in a request into a local path that may be changed
+    fn writable_path(&self, path: &str) -> io::Result<PathBuf> {
+        if self.read_only {
+            return Err(error(ACCESS_DENIED));
+        }
+        let local = self.resolve(path)?;
+        if local == self.root {
+            // Don't remove or replace the whole root directory
+            return Err(error(ACCESS_DENIED));
+        }
+        Ok(local)
+    }
+}
+
+/// Returns the text of a `uavcan.file.Path` field
+fn path_field(request: &Value, name: &str) -> String {
+    field(request, name)
+        .and_then(|path| field(path, "path"))
+        .and_then(uint8_text)
+        .unwrap_or_default()
+}
+
+fn offset(request: &Value) -> u64 {
+    match field(request, "offset") {
+        Some(Value::UInt(offset)) => *offset,
+        _ => 0,
+    }
+}
+
+fn modification_time(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|time| time.as_secs())
+        .unwrap_or(0)
+}
+
+/// Creates an empty file, or updates the modification time of an existing file or directory
+fn touch(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        return File::open(path)?.set_modified(SystemTime::now());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .set_modified(SystemTime::now())
+}
+
+fn remove(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn move_or_copy(
+    source: &Path,
+    destination: &Path,
+    preserve_source: bool,
+    overwrite: bool,
+) -> io::Result<()> {
+    fs::symlink_metadata(source)?;
+    if fs::symlink_metadata(destination).is_ok() {
+        if !overwrite {
+            return Err(error(INVALID_VALUE));
+        }
+        remove(destination)?;
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if preserve_source {
+        copy(source, destination)
+    } else {
+        fs::rename(source, destination)
+    }
+}
+
+/// Copies a file, or a directory and everything in it
+fn copy(source: &Path, destination: &Path) -> io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, destination).map(drop)
+    }
+}
+
+/// Returns an I/O error with an error code as its OS error code
+fn error(code: u16) -> io::Error {
+    io::Error::from_raw_os_error(code.into())
+}
+
+/// Converts an I/O error into a `uavcan.file.Error` value
+fn error_code(error: &io::Error) -> u16 {
+    match error.kind() {
+        io::ErrorKind::NotFound => NOT_FOUND,
+        io::ErrorKind::PermissionDenied => ACCESS_DENIED,
+        _ => error
+            .raw_os_error()
+            .and_then(|code| u16::try_from(code).ok())
+            .filter(|code| ERRNO_CODES.contains(code))
+            .unwrap_or(UNKNOWN_ERROR),
+    }
+}
+
+fn error_name(code: u16) -> &'static str {
+    match code {
+        OK => "ok",
+        NOT_FOUND => "not found",
+        5 => "I/O error",
+        ACCESS_DENIED => "access denied",
+        21 => "is a directory",
+        INVALID_VALUE => "invalid value",
+        27 => "file too large",
+        28 => "out of space",
+        38 => "not supported",
+        _ => "unknown error",
+    }
+}
+
+/// Errors that can occur while serving files
+#[derive(thiserror::Error, Debug)]
+pub enum FileServerError {
+    /// The file service types could not be found
+    #[error("{0}")]
+    Types(String),
+    /// A request could not be deserialized, or a response could not be serialized
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    /// A response could not be created
+    #[error(transparent)]
+    Json(#[from] JsonError),
+    /// Sending or receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::FileServer;
+    use crate::bus::ReceivedTransfer;
+    use crate::types::standard_registry;
+    use canadensis_core::time::Microseconds32;
+    use canadensis_core::Priority;
+    use canadensis_dynamic::json::{from_json, to_json};
+    use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+    use serde_json::json;
+    use std::fs;
+    use std::time::SystemTime;
+
+    /// Sends a request to a server and returns the response
+    fn request(
+        server: &FileServer<'_>,
+        registry: &TypeRegistry,
+        service: u16,
+        request: serde_json::Value,
+    ) -> serde_json::Value {
+        let key = registry.service_type(service).unwrap();
+        let request = from_json(
+            registry.message(key, PayloadKind::Request).unwrap(),
+            &request,
+        )
+        .unwrap();
+        let transfer = ReceivedTransfer {
+            kind: PayloadKind::Request,
+            port_id: service,
+            source: Some(42),
+            destination: Some(1),
+            transfer_id: 0,
+            priority: Priority::Nominal,
+            timestamp: Microseconds32::from_ticks(0),
+            system_time: SystemTime::now(),
+            payload: registry
+                .serialize(key, PayloadKind::Request, &request)
+                .unwrap(),
+        };
+        let handled = server.handle(&transfer).unwrap().unwrap();
+        to_json(
+            &registry
+                .deserialize(key, PayloadKind::Response, &handled.response)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn serve_files() {
+        let root =
+            std::env::temp_dir().join(format!("canadensis_file_server_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("images")).unwrap();
+        let image: Vec<u8> = (0..300).map(|i| i as u8).collect();
+        fs::write(root.join("images/firmware.bin"), &image).unwrap();
+
+        let registry = standard_registry();
+        let server = FileServer::new(&registry, root.clone(), false).unwrap();
+        let error = |response: &serde_json::Value| response["error"]["value"].as_u64().unwrap();
+        let bytes = |text: &str| json!(text.as_bytes());
+
+        // Read the file in two parts
+        let response = request(
+            &server,
+            &registry,
+            408,
+            json!({ "path": { "path": "/images/firmware.bin" } }),
+        );
+        assert_eq!(0, error(&response));
+        assert_eq!(json!(image[..256]), response["data"]["value"]);
+        let response = request(
+            &server,
+            &registry,
+            408,
+            json!({ "offset": 256, "path": { "path": "images/firmware.bin" } }),
+        );
+        assert_eq!(json!(image[256..]), response["data"]["value"]);
+        let response = request(
+            &server,
+            &registry,
+            408,
+            json!({ "path": { "path": "missing.bin" } }),
+        );
+        assert_eq!(2, error(&response));
+        // Paths outside the root are not allowed
+        let response = request(
+            &server,
+            &registry,
+            408,
+            json!({ "path": { "path": "../secret" } }),
+        );
+        assert_eq!(13, error(&response));
+
+        let response = request(
+            &server,
+            &registry,
+            405,
+            json!({ "path": { "path": "images/firmware.bin" } }),
+        );
+        assert_eq!(0, error(&response));
+        assert_eq!(json!(300), response["size"]);
+        assert_eq!(json!(true), response["is_file_not_directory"]);
+        assert_eq!(json!(true), response["is_writeable"]);
+
+        // Write a file, and then copy it
+        let response = request(
+            &server,
+            &registry,
+            409,
+            json!({ "path": { "path": "config/params.yaml" }, "data": { "value": "gain: 1" } }),
+        );
+        assert_eq!(0, error(&response));
+        request(
+            &server,
+            &registry,
+            409,
+            json!({ "offset": 7, "path": { "path": "config/params.yaml" } }),
+        );
+        assert_eq!(
+            "gain: 1",
+            fs::read_to_string(root.join("config/params.yaml")).unwrap()
+        );
+        let response = request(
+            &server,
+            &registry,
+            407,
+            json!({
+                "preserve_source": true,
+                "source": { "path": "config/params.yaml" },
+                "destination": { "path": "params.yaml" },
+            }),
+        );
+        assert_eq!(0, error(&response));
+
+        let list = |index: u32| {
+            request(
+                &server,
+                &registry,
+                406,
+                json!({ "entry_index": index, "directory_path": { "path": "/" } }),
+            )["entry_base_name"]["path"]
+                .clone()
+        };
+        assert_eq!(
+            vec![
+                bytes("config"),
+                bytes("images"),
+                bytes("params.yaml"),
+                bytes("")
+            ],
+            (0..4).map(list).collect::<Vec<_>>()
+        );
+
+        // Remove the copy
+        let response = request(
+            &server,
+            &registry,
+            407,
+            json!({ "source": { "path": "params.yaml" } }),
+        );
+        assert_eq!(0, error(&response));
+        assert!(!root.join("params.yaml").exists());
+
+        // A read-only server does not allow changes
+        let read_only = FileServer::new(&registry, root.clone(), true).unwrap();
+        let response = request(
+            &read_only,
+            &registry,
+            407,
+            json!({ "source": { "path": "images" } }),
+        );
+        assert_eq!(13, error(&response));
+        assert!(root.join("images").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,272 @@
+//! Publishing synthetic traffic for stress testing
+//!
+//! Each [`Stream`] publishes messages with a fixed payload size on one subject at a fixed rate,
+//! or as fast as possible. Streams are written like `1000:64@100` (subject 1000, 64-byte
+//! payloads, 100 messages per second), with an optional priority at the end like
+//! `1001:300@max:fast`. Payloads that do not fit in one frame are sent as multi-frame
+//! transfers.
+//!
+//! The payloads have no data type. Byte `i` of message `n` on a stream is `(n + i) mod 256`, so
+//! receivers can check that transfers were reassembled correctly.
+
+use std::fmt;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use canadensis_core::Priority;
+
+use crate::bus::{parse_priority, priority_name, Bus, BusError};
+use crate::node_list::format_table;
+
+/// How often a stream publishes
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Rate {
+    /// A number of messages per second
+    PerSecond(f64),
+    /// As often as possible
+    Max,
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rate::PerSecond(rate) => write!(f, "{}", rate),
+            Rate::Max => f.write_str("max"),
+        }
+    }
+}
+
+/// A sequence of messages on one subject
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stream {
+    /// The subject ID
+    pub subject: u16,
+    /// The number of bytes in each payload
+    pub size: usize,
+    /// The rate of messages
+    pub rate: Rate,
+    /// The priority of the messages
+    pub priority: Priority,
+}
+
+impl FromStr for Stream {
+    type Err = String;
+
+    /// Parses a stream like `1000:64@100` or `1001:300@max:fast`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid stream {} (expected subject:size@rate or subject:size@rate:priority)",
+                text
+            )
+        };
+        let (subject, rest) = text.split_once(':').ok_or_else(invalid)?;
+        let (size, rest) = rest.split_once('@').ok_or_else(invalid)?;
+        let (rate, priority) = match rest.split_once(':') {
+            Some((rate, priority)) => (rate, Some(priority)),
+            None => (rest, None),
+        };
+        let subject = subject
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid subject ID {}", subject))?;
+        let size = size
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid payload size {}", size))?;
+        let rate = match rate {
+            "max" => Rate::Max,
+            _ => match rate.parse::<f64>() {
+                Ok(per_second) if per_second.is_finite() && per_second > 0.0 => {
+                    Rate::PerSecond(per_second)
+                }
+                _ => return Err(format!("Invalid rate {}", rate)),
+            },
+        };
+        let priority = match priority {
+            Some(name) => {
+                parse_priority(name).ok_or_else(|| format!("Invalid priority {}", name))?
+            }
+            None => Priority::Nominal,
+        };
+        Ok(Stream {
+            subject,
+            size,
+            rate,
+            priority,
+        })
+    }
+}
+
+/// Returns the payload of message `sequence` on a stream
+pub fn payload(sequence: u64, size: usize) -> Vec<u8> {
+    (0..size)
+        .map(|i| (sequence as u8).wrapping_add(i as u8))
+        .collect()
+}
+
+/// What a stream published
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamStats {
+    /// The number of messages sent
+    pub sent: u64,
+    /// The number of messages that could not be sent before their deadlines
+    pub dropped: u64,
+}
+
+/// Publishes messages on some streams until a duration has passed, and returns what each stream
+/// published
+///
+/// Messages that can't be sent before their deadlines (because the bus is too busy) are counted
+/// as dropped. A stream that falls behind its rate does not try to catch up later.
+pub fn run(
+    bus: &mut dyn Bus,
+    streams: &[Stream],
+    duration: Duration,
+) -> Result<Vec<StreamStats>, BusError> {
+    let start = Instant::now();
+    let end = start + duration;
+    let mut stats = vec![StreamStats::default(); streams.len()];
+    let mut next = vec![start; streams.len()];
+    if streams.is_empty() {
+        return Ok(stats);
+    }
+    loop {
+        let now = Instant::now();
+        if now >= end {
+            break;
+        }
+        let (index, &scheduled) = next
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &time)| time)
+            .expect("No streams");
+        if scheduled > now {
+            thread::sleep(scheduled.min(end) - now);
+            continue;
+        }
+        let stream = &streams[index];
+        let stream_stats = &mut stats[index];
+        let sequence = stream_stats.sent + stream_stats.dropped;
+        match bus.publish(
+            stream.subject,
+            stream.priority,
+            &payload(sequence, stream.size),
+        ) {
+            Ok(()) => stream_stats.sent += 1,
+            Err(BusError::Timeout) => stream_stats.dropped += 1,
+            Err(e) => return Err(e),
+        }
+        next[index] = match stream.rate {
+            Rate::PerSecond(rate) => {
+                (scheduled + Duration::from_secs_f64(1.0 / rate)).max(Instant::now())
+            }
+            Rate::Max => Instant::now(),
+        };
+    }
+    Ok(stats)
+}
+
+/// Formats a table of what each stream published over a duration
+pub fn format_stats(streams: &[Stream], stats: &[StreamStats], duration: Duration) -> String {
+    let rows: Vec<[String; 7]> = streams
+        .iter()
+        .zip(stats)
+        .map(|(stream, stats)| {
+            [
+                stream.subject.to_string(),
+                stream.size.to_string(),
+                priority_name(stream.priority).to_owned(),
+                stream.rate.to_string(),
+                stats.sent.to_string(),
+                format!("{:.1}", stats.sent as f64 / duration.as_secs_f64()),
+                stats.dropped.to_string(),
+            ]
+        })
+        .collect();
+    let headings = [
+        "SUBJECT", "SIZE", "PRIORITY", "TARGET", "SENT", "RATE", "DROPPED",
+    ]
+    .map(String::from);
+    format_table(headings, &rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_stats, payload, run, Rate, Stream, StreamStats};
+    use crate::bus::ReceivedTransfer;
+    use crate::mock_bus::MockBus;
+    use canadensis_core::Priority;
+    use std::time::Duration;
+
+    #[test]
+    fn generate_streams() {
+        assert_eq!(
+            Ok(Stream {
+                subject: 1000,
+                size: 64,
+                rate: Rate::PerSecond(100.0),
+                priority: Priority::Nominal,
+            }),
+            "1000:64@100".parse()
+        );
+        assert_eq!(
+            Ok(Stream {
+                subject: 1001,
+                size: 300,
+                rate: Rate::Max,
+                priority: Priority::Fast,
+            }),
+            "1001:300@max:fast".parse()
+        );
+        for invalid in [
+            "1000",
+            "1000:64",
+            "1000:64@0",
+            "1000:64@-1",
+            "x:64@1",
+            "1000:64@1:urgent",
+        ] {
+            assert!(invalid.parse::<Stream>().is_err(), "{}", invalid);
+        }
+        assert_eq!(vec![255, 0, 1], payload(255, 3));
+
+        let streams = [
+            "1000:3@20".parse::<Stream>().unwrap(),
+            "1001:100@max:slow".parse().unwrap(),
+        ];
+        let mut bus = MockBus::new(());
+        let stats = run(&mut bus, &streams, Duration::from_millis(120)).unwrap();
+        // The first message is at the start, and the next messages are 50 ms apart
+        assert!((1..=3).contains(&stats[0].sent), "{:?}", stats);
+        assert!(stats[1].sent > stats[0].sent);
+        assert_eq!(0, stats[0].dropped + stats[1].dropped);
+        let slow: Vec<&ReceivedTransfer> = bus
+            .published
+            .iter()
+            .filter(|message| message.port_id == 1000)
+            .collect();
+        assert_eq!(stats[0].sent as usize, slow.len());
+        for (sequence, message) in slow.iter().enumerate() {
+            assert_eq!(sequence as u64, message.transfer_id);
+            assert_eq!(Priority::Nominal, message.priority);
+            assert_eq!(super::payload(sequence as u64, 3), message.payload);
+        }
+
+        let stats = [
+            StreamStats {
+                sent: 20,
+                dropped: 0,
+            },
+            StreamStats {
+                sent: 1500,
+                dropped: 3,
+            },
+        ];
+        assert_eq!(
+            "SUBJECT  SIZE  PRIORITY  TARGET  SENT  RATE    DROPPED\n\
+            1000     3     nominal   20      20    20.0    0\n\
+            1001     100   slow      max     1500  1500.0  3",
+            format_stats(&streams, &stats, Duration::from_secs(1))
+        );
+    }
+}
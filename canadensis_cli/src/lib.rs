@@ -0,0 +1,43 @@
+//!
+//! # Canadensis command-line tools
+//!
+//! This library contains the parts of the `canadensis` command-line tool, which monitors and
+//! interacts with Cyphal networks using data types that are loaded from DSDL files when it
+//! runs.
+//!
+//! The [`bus`] module connects to a Cyphal/CAN or Cyphal/UDP network, the [`types`] module
+//! loads data types and finds the types that command-line arguments refer to, and each other
+//! module implements one command.
+//!
+
+extern crate canadensis_can;
+extern crate canadensis_codegen_rust;
+extern crate canadensis_core;
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dynamic;
+#[cfg(feature = "can")]
+extern crate canadensis_linux;
+extern crate canadensis_udp;
+extern crate ratatui;
+extern crate serde_json;
+#[cfg(feature = "can")]
+extern crate socketcan;
+extern crate thiserror;
+
+pub mod bus;
+pub mod call;
+pub mod decode;
+pub mod file_server;
+pub mod generate;
+pub mod load;
+#[cfg(test)]
+mod mock_bus;
+pub mod monitor;
+pub mod node_list;
+pub mod ping;
+pub mod publish;
+pub mod record;
+pub mod register;
+pub mod tui;
+pub mod types;
+pub mod update;
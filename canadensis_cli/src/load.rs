@@ -0,0 +1,304 @@
+//! Measuring the load on a CAN bus
+//!
+//! This uses a [`LoadAnalyzer`] to divide frames into time windows and prints a report for each
+//! window, like this:
+//!
+//! ```text
+//! Bus load 12.4% over 1.000 s: 310 frames, 0 invalid
+//! PORT          FRAMES/S  TRANSFERS/S  BYTES/S  LOAD
+//! message 1000  200.0     200.0        1600.0   10.3%
+//! message 7509  10.0      10.0         80.0     0.5%
+//!
+//! NODE       FRAMES/S  BYTES/S  LOAD
+//! 42         205.0     1640.0   10.6%
+//! anonymous  5.0       40.0     0.3%
+//! ```
+//!
+//! Ports and nodes are sorted by the amount of bus time that they used, from most to least.
+//!
+//! The frames can come from a SocketCAN interface or from a log file that `candump -l` wrote.
+
+use std::cmp::Reverse;
+use std::io;
+
+use canadensis_can::candump::{Entry, ParseError};
+use canadensis_can::load::{LoadAnalyzer, LoadReport, Port, Traffic};
+use canadensis_core::time::MicrosecondDuration32;
+
+use crate::bus::BusError;
+use crate::node_list::format_table;
+
+/// Formats a report as text
+pub fn format_report(report: &LoadReport) -> String {
+    let duration = report.duration();
+    let mut text = format!(
+        "Bus load {} over {:.3} s: {} frames, {} invalid\n",
+        percent(report.utilization()),
+        duration.ticks() as f64 / 1_000_000.0,
+        report.total().frames,
+        report.invalid().frames,
+    );
+
+    let mut ports: Vec<(&Port, &Traffic)> = report.ports().iter().collect();
+    ports.sort_by_key(|(_, traffic)| Reverse(traffic.bus_time));
+    let rows: Vec<[String; 5]> = ports
+        .into_iter()
+        .map(|(port, traffic)| {
+            [
+                port_name(port),
+                format!("{:.1}", traffic.frame_rate(duration)),
+                format!("{:.1}", traffic.transfer_rate(duration)),
+                format!("{:.1}", traffic.bandwidth(duration)),
+                percent(traffic.utilization(duration)),
+            ]
+        })
+        .collect();
+    let headings = ["PORT", "FRAMES/S", "TRANSFERS/S", "BYTES/S", "LOAD"].map(String::from);
+    text.push_str(&format_table(headings, &rows));
+    text.push_str("\n\n");
+
+    let mut nodes: Vec<_> = report.nodes().iter().collect();
+    nodes.sort_by_key(|(_, traffic)| Reverse(traffic.bus_time));
+    let rows: Vec<[String; 4]> = nodes
+        .into_iter()
+        .map(|(node, traffic)| {
+            [
+                node.map(|node| u8::from(node).to_string())
+                    .unwrap_or_else(|| "anonymous".to_owned()),
+                format!("{:.1}", traffic.frame_rate(duration)),
+                format!("{:.1}", traffic.bandwidth(duration)),
+                percent(traffic.utilization(duration)),
+            ]
+        })
+        .collect();
+    let headings = ["NODE", "FRAMES/S", "BYTES/S", "LOAD"].map(String::from);
+    text.push_str(&format_table(headings, &rows));
+    text
+}
+
+fn port_name(port: &Port) -> String {
+    match port {
+        Port::Message(subject) => format!("message {}", u16::from(*subject)),
+        Port::Request(service) => format!("request {}", u16::from(*service)),
+        Port::Response(service) => format!("response {}", u16::from(*service)),
+    }
+}
+
+fn percent(fraction: f32) -> String {
+    format!("{:.1}%", fraction * 100.0)
+}
+
+/// Receives frames from a SocketCAN interface and prints a report at the end of each window
+///
+/// This runs until an error occurs.
+#[cfg(feature = "can")]
+pub fn run_can<W: io::Write>(
+    interface: &str,
+    mut analyzer: canadensis_can::load::LoadAnalyzer,
+    mut output: W,
+) -> Result<(), LoadError> {
+    use canadensis_can::driver::ReceiveDriver;
+    use canadensis_core::nb;
+    use canadensis_core::time::Clock;
+    use canadensis_linux::{LinuxCan, SystemClock};
+    use socketcan::{CanSocket, Socket};
+    use std::io::Write;
+    use std::time::Duration;
+
+    let open_error = |inner| BusError::Open {
+        name: interface.to_owned(),
+        inner,
+    };
+    let socket = CanSocket::open(interface).map_err(open_error)?;
+    // Windows can end while no frames are arriving
+    socket
+        .set_read_timeout(Duration::from_millis(5))
+        .map_err(open_error)?;
+    let mut can = LinuxCan::new(socket);
+    can.apply_accept_all();
+    let mut clock = SystemClock::new();
+    loop {
+        let report = match can.receive(&mut clock) {
+            Ok(frame) => analyzer.push(&frame),
+            Err(nb::Error::WouldBlock) => analyzer.advance(clock.now()),
+            Err(nb::Error::Other(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                analyzer.advance(clock.now())
+            }
+            Err(nb::Error::Other(e)) => return Err(LoadError::Receive(e)),
+        };
+        if let Some(report) = report {
+            writeln!(output, "{}\n", format_report(&report))?;
+        }
+    }
+}
+
+/// Reads frames from a candump log and prints a report for each window
+///
+/// Lines with frames that Cyphal/CAN does not use (with 11-bit IDs, remote frames, and error
+/// frames) are skipped, and the number of skipped lines is printed at the end.
+pub fn run_file<R: io::BufRead, W: io::Write>(
+    input: R,
+    mut analyzer: LoadAnalyzer,
+    mut output: W,
+) -> Result<(), LoadError> {
+    let mut skipped = 0_u64;
+    let mut last_time = None;
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = match Entry::parse(&line) {
+            Ok(entry) => entry,
+            Err(ParseError::StandardId | ParseError::Remote | ParseError::Id) => {
+                skipped += 1;
+                continue;
+            }
+            Err(inner) => return Err(LoadError::Parse { line: i + 1, inner }),
+        };
+        last_time = Some(entry.frame.timestamp());
+        if let Some(report) = analyzer.push(&entry.frame) {
+            writeln!(output, "{}\n", format_report(&report))?;
+        }
+    }
+    if let Some(report) = last_time.and_then(|end| analyzer.finish(end)) {
+        writeln!(output, "{}\n", format_report(&report))?;
+    }
+    if skipped != 0 {
+        writeln!(
+            output,
+            "Skipped {} frames that Cyphal/CAN does not use",
+            skipped
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the window duration for a number of seconds
+pub fn window_duration(seconds: f64) -> Option<MicrosecondDuration32> {
+    let microseconds = (seconds * 1_000_000.0).round();
+    if microseconds >= 1.0 && microseconds <= f64::from(u32::MAX / 2) {
+        Some(MicrosecondDuration32::from_ticks(microseconds as u32))
+    } else {
+        None
+    }
+}
+
+/// Errors that can occur when measuring bus load
+#[derive(thiserror::Error, Debug)]
+pub enum LoadError {
+    /// The bus could not be opened
+    #[error(transparent)]
+    Bus(#[from] BusError),
+    /// A frame could not be received
+    #[error("Failed to receive a frame")]
+    Receive(#[source] io::Error),
+    /// A line of a log file could not be parsed
+    #[error("Line {line}: {inner}")]
+    Parse {
+        /// The line number, starting at 1
+        line: usize,
+        /// The problem with the line
+        inner: ParseError,
+    },
+    /// A log file could not be read, or a report could not be written
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_report, run_file, window_duration, LoadError};
+    use canadensis_can::candump::ParseError;
+    use canadensis_can::load::{BusTiming, LoadAnalyzer};
+    use canadensis_can::{CanId, Frame};
+    use canadensis_core::time::Microseconds32;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn report_text() {
+        let mut analyzer = LoadAnalyzer::new(
+            BusTiming::classic(1_000_000),
+            window_duration(0.001).unwrap(),
+        );
+        let heartbeat = [0, 0, 0, 0, 0, 0, 0, 0xe0];
+        for (time, id) in [(0, 0x107d_552a), (100, 0x107d_552a), (200, 0x1063_e801)]
+            .iter()
+            .copied()
+        {
+            let frame = Frame::new(
+                Microseconds32::from_ticks(time),
+                CanId::try_from(id).unwrap(),
+                &heartbeat,
+            );
+            assert!(analyzer.push(&frame).is_none());
+        }
+        let report = analyzer
+            .finish(Microseconds32::from_ticks(1000))
+            .expect("No report");
+        let text = format_report(&report);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!("Bus load 44.1% over 0.001 s: 3 frames, 0 invalid", lines[0]);
+        assert_eq!(
+            lines[1..4],
+            [
+                "PORT          FRAMES/S  TRANSFERS/S  BYTES/S  LOAD",
+                "message 7509  2000.0    2000.0       16000.0  29.4%",
+                "message 1000  1000.0    1000.0       8000.0   14.7%",
+            ]
+        );
+        assert_eq!("", lines[4]);
+        assert_eq!("NODE  FRAMES/S  BYTES/S  LOAD", lines[5]);
+        assert_eq!("42    2000.0    16000.0  29.4%", lines[6]);
+        assert_eq!("1     1000.0    8000.0   14.7%", lines[7]);
+
+        assert!(window_duration(0.0).is_none());
+        assert!(window_duration(f64::NAN).is_none());
+        assert_eq!(2_500_000, window_duration(2.5).unwrap().ticks());
+    }
+
+    #[test]
+    fn candump_file() {
+        // The same frames as in report_text, with an 11-bit frame and a frame in the next window
+        let log = "\
+(1700000000.000000) can0 107D552A#00000000000000E0
+(1700000000.000100) can0 107D552A#00000000000000E0
+(1700000000.000150) can0 123#00
+
+(1700000000.000200) can0 1063E801#00000000000000E0
+(1700000000.001000) can0 1063E801#00000000000000E1
+";
+        let analyzer = LoadAnalyzer::new(
+            BusTiming::classic(1_000_000),
+            window_duration(0.001).unwrap(),
+        );
+        let mut output = Vec::new();
+        run_file(log.as_bytes(), analyzer, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!("Bus load 44.1% over 0.001 s: 3 frames, 0 invalid", lines[0]);
+        assert!(lines.contains(&"Bus load 0.0% over 0.000 s: 1 frames, 0 invalid"));
+        assert_eq!(
+            Some(&"Skipped 1 frames that Cyphal/CAN does not use"),
+            lines.last()
+        );
+
+        let analyzer = LoadAnalyzer::new(
+            BusTiming::classic(1_000_000),
+            window_duration(0.001).unwrap(),
+        );
+        let log = "(1.000000) can0 107D552A#00000000000000E0\n(1.000100) can0 107D552A#0\n";
+        match run_file(log.as_bytes(), analyzer, Vec::new()) {
+            Err(LoadError::Parse {
+                line: 2,
+                inner: ParseError::Data,
+            }) => {}
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+}
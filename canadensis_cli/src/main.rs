@@ -0,0 +1,942 @@
+extern crate canadensis_can;
+extern crate canadensis_cli;
+extern crate canadensis_core;
+extern crate canadensis_dynamic;
+extern crate clap;
+
+use canadensis_can::load::{BusTiming, LoadAnalyzer};
+use canadensis_cli::bus::{self, Bus};
+use canadensis_cli::call;
+use canadensis_cli::decode::{self, DecodeOptions};
+use canadensis_cli::file_server::FileServer;
+use canadensis_cli::generate::{self, Stream};
+use canadensis_cli::load;
+use canadensis_cli::monitor::Monitor;
+use canadensis_cli::node_list::NodeList;
+use canadensis_cli::ping::{self, PingOptions};
+use canadensis_cli::publish::{self, PublishOptions};
+use canadensis_cli::record::Recorder;
+use canadensis_cli::register::{self, RegisterClient};
+use canadensis_cli::tui::{self, Dashboard};
+use canadensis_cli::types::{self, Port};
+use canadensis_cli::update::{Update, UpdateOptions};
+use canadensis_core::Priority;
+use canadensis_dynamic::registry::TypeRegistry;
+use canadensis_dynamic::yaml::YamlConfig;
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use std::fs::File;
+use std::io;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+fn main() {
+    match run() {
+        Ok(()) => {}
+        Err(e) => {
+            print_error(&*e);
+            process::exit(-1);
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = command().get_matches();
+    match matches.subcommand() {
+        Some(("monitor", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let subjects = sub_matches
+                .get_many::<String>("subject")
+                .expect("Missing subjects")
+                .map(|subject| types::find_subject(&registry, subject).map_err(StringError))
+                .collect::<Result<Vec<Port>, StringError>>()?;
+            let yaml_config = YamlConfig {
+                max_array_elements: sub_matches.get_one::<usize>("max_array_elements").copied(),
+            };
+            let monitor = Monitor::new(&registry, subjects, yaml_config);
+            let mut bus = open_bus(&matches, true)?;
+            monitor.subscribe(&mut *bus)?;
+            let count = sub_matches.get_one::<usize>("count").copied();
+            monitor.run(&mut *bus, count, io::stdout().lock())?;
+        }
+        Some(("record", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let subjects = sub_matches
+                .get_many::<String>("subject")
+                .expect("Missing subjects")
+                .map(|subject| types::find_subject(&registry, subject).map_err(StringError))
+                .collect::<Result<Vec<Port>, StringError>>()?;
+            let recorder = Recorder::new(&registry, subjects)?;
+            let directory = sub_matches
+                .get_one::<PathBuf>("directory")
+                .expect("No directory");
+            let mut bus = open_bus(&matches, true)?;
+            recorder.subscribe(&mut *bus)?;
+            let count = sub_matches.get_one::<usize>("count").copied();
+            recorder.run(&mut *bus, directory, count)?;
+        }
+        Some(("decode", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let subjects = sub_matches
+                .get_many::<String>("subject")
+                .expect("Missing subjects")
+                .map(|subject| types::find_subject(&registry, subject).map_err(StringError))
+                .collect::<Result<Vec<Port>, StringError>>()?;
+            let yaml_config = YamlConfig {
+                max_array_elements: sub_matches.get_one::<usize>("max_array_elements").copied(),
+            };
+            let monitor = Monitor::new(&registry, subjects, yaml_config);
+            let path = sub_matches.get_one::<PathBuf>("file").expect("No file");
+            let file = File::open(path).map_err(|e| {
+                ErrorContext::new(format!("Can't open {}", path.display()), e.into())
+            })?;
+            let options = DecodeOptions {
+                interface: sub_matches.get_one::<u32>("interface").copied(),
+                port: sub_matches.get_one::<u16>("port").copied(),
+            };
+            decode::run(
+                &monitor,
+                io::BufReader::new(file),
+                &options,
+                io::stdout().lock(),
+            )?;
+        }
+        Some(("pub", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let subject = sub_matches
+                .get_one::<String>("subject")
+                .expect("Missing subject");
+            let subject = types::find_subject(&registry, subject).map_err(StringError)?;
+            let text = match sub_matches.get_one::<String>("message").map(String::as_str) {
+                Some("-") | None => io::read_to_string(io::stdin())?,
+                Some(text) => text.to_owned(),
+            };
+            let payload = publish::message_payload(&registry, &subject, &text)?;
+            let rate = *sub_matches.get_one::<f64>("rate").expect("No rate");
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(StringError(format!("Invalid rate {}", rate)).into());
+            }
+            let options = PublishOptions {
+                period: Duration::from_secs_f64(1.0 / rate),
+                count: sub_matches.get_one::<usize>("count").copied(),
+                priority: priority(sub_matches),
+            };
+            let mut bus = open_bus(&matches, false)?;
+            publish::run(&mut *bus, subject.port_id, &payload, &options)?;
+        }
+        Some(("call", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let server = *sub_matches
+                .get_one::<u16>("server")
+                .expect("Missing server");
+            let service = sub_matches
+                .get_one::<String>("service")
+                .expect("Missing service");
+            let service = types::find_service(&registry, service).map_err(StringError)?;
+            let text = match sub_matches.get_one::<String>("request").map(String::as_str) {
+                Some("-") => io::read_to_string(io::stdin())?,
+                Some(text) => text.to_owned(),
+                None => String::new(),
+            };
+            let payload = call::request_payload(&registry, &service, &text)?;
+            let timeout = timeout(sub_matches)?;
+            let mut bus = open_bus(&matches, true)?;
+            let response = call::call(
+                &mut *bus,
+                &registry,
+                &service,
+                server,
+                &payload,
+                priority(sub_matches),
+                timeout,
+            )?;
+            println!(
+                "{}",
+                call::format_response(&service, &response, &YamlConfig::default())
+            );
+        }
+        Some(("generate", sub_matches)) => {
+            let streams = sub_matches
+                .get_many::<String>("stream")
+                .expect("No streams")
+                .map(|stream| stream.parse::<Stream>())
+                .collect::<Result<Vec<Stream>, String>>()
+                .map_err(StringError)?;
+            let duration = *sub_matches.get_one::<f64>("duration").expect("No duration");
+            if !(duration.is_finite() && duration > 0.0) {
+                return Err(StringError(format!("Invalid duration {}", duration)).into());
+            }
+            let duration = Duration::from_secs_f64(duration);
+            let mut bus = open_bus(&matches, false)?;
+            let stats = generate::run(&mut *bus, &streams, duration)?;
+            println!("{}", generate::format_stats(&streams, &stats, duration));
+        }
+        Some(("ping", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let server = *sub_matches
+                .get_one::<u16>("server")
+                .expect("Missing server");
+            let service = sub_matches
+                .get_one::<String>("service")
+                .expect("No service");
+            let service = types::find_service(&registry, service).map_err(StringError)?;
+            let text = sub_matches
+                .get_one::<String>("request")
+                .map(String::as_str)
+                .unwrap_or_default();
+            let payload = call::request_payload(&registry, &service, text)?;
+            let interval = *sub_matches.get_one::<f64>("interval").expect("No interval");
+            if !(interval.is_finite() && interval >= 0.0) {
+                return Err(StringError(format!("Invalid interval {}", interval)).into());
+            }
+            let options = PingOptions {
+                count: *sub_matches.get_one::<u32>("count").expect("No count"),
+                interval: Duration::from_secs_f64(interval),
+                timeout: timeout(sub_matches)?,
+                priority: priority(sub_matches),
+            };
+            let mut bus = open_bus(&matches, true)?;
+            let stats = ping::ping(
+                &mut *bus,
+                &registry,
+                &service,
+                server,
+                &payload,
+                &options,
+                io::stdout().lock(),
+            )?;
+            println!("{}", stats.format_summary());
+        }
+        Some(("file-server", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let root = sub_matches
+                .get_one::<PathBuf>("directory")
+                .expect("No directory");
+            if !root.is_dir() {
+                return Err(StringError(format!("{} is not a directory", root.display())).into());
+            }
+            let server =
+                FileServer::new(&registry, root.clone(), sub_matches.get_flag("read_only"))?;
+            let mut bus = open_bus(&matches, true)?;
+            server.subscribe(&mut *bus)?;
+            server.run(&mut *bus)?;
+        }
+        Some(("node-list", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let duration = *sub_matches.get_one::<f64>("duration").expect("No duration");
+            if !(duration.is_finite() && duration >= 0.0) {
+                return Err(StringError(format!("Invalid duration {}", duration)).into());
+            }
+            let mut nodes = NodeList::new(&registry)?;
+            let mut bus = open_bus(&matches, true)?;
+            nodes.subscribe(&mut *bus)?;
+            nodes.listen(&mut *bus, Duration::from_secs_f64(duration))?;
+            if bus.node_id().is_some() {
+                nodes.query_info(&mut *bus, priority(sub_matches), timeout(sub_matches)?)?;
+            } else {
+                eprintln!(
+                    "Names and versions are unknown because a node ID is required to request them"
+                );
+            }
+            println!("{}", nodes.format_table());
+        }
+        Some(("tui", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let subjects = sub_matches
+                .get_many::<String>("subject")
+                .unwrap_or_default()
+                .map(|subject| types::find_subject(&registry, subject).map_err(StringError))
+                .collect::<Result<Vec<Port>, StringError>>()?;
+            let mut dashboard = Dashboard::new(&registry, subjects)?;
+            let mut bus = open_bus(&matches, true)?;
+            dashboard.subscribe(&mut *bus)?;
+            tui::run(&mut dashboard, &mut *bus)?;
+        }
+        Some(("register-list", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let mut bus = open_bus(&matches, true)?;
+            let mut client = register_client(&mut *bus, &registry, sub_matches)?;
+            for name in client.list()? {
+                println!("{}", name);
+            }
+        }
+        Some(("register-get", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let mut bus = open_bus(&matches, true)?;
+            let mut client = register_client(&mut *bus, &registry, sub_matches)?;
+            let names: Vec<String> = match sub_matches.get_many::<String>("name") {
+                Some(names) => names.cloned().collect(),
+                None => client.list()?,
+            };
+            let mutable_only = sub_matches.get_flag("mutable");
+            let mut values = Vec::with_capacity(names.len());
+            for name in names {
+                let register = client.read(&name)?;
+                if register.mutable || !mutable_only {
+                    values.push((name, register.value));
+                }
+            }
+            println!(
+                "{}",
+                register::format_registers(
+                    values.iter().map(|(name, value)| (name.as_str(), value))
+                )
+            );
+        }
+        Some(("register-set", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let name = sub_matches.get_one::<String>("name").expect("Missing name");
+            let text = sub_matches
+                .get_one::<String>("value")
+                .expect("Missing value");
+            let value = canadensis_dynamic::yaml::parse_yaml(text)?;
+            let mut bus = open_bus(&matches, true)?;
+            let mut client = register_client(&mut *bus, &registry, sub_matches)?;
+            let register = client.write(name, &value)?;
+            println!(
+                "{}",
+                register::format_registers([(name.as_str(), &register.value)])
+            );
+        }
+        Some(("register-apply", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let text = match sub_matches.get_one::<String>("file").map(String::as_str) {
+                Some("-") | None => io::read_to_string(io::stdin())?,
+                Some(path) => std::fs::read_to_string(path)
+                    .map_err(|e| ErrorContext::new(format!("Failed to read {}", path), e.into()))?,
+            };
+            let registers = register::parse_registers(&text)?;
+            let mut bus = open_bus(&matches, true)?;
+            let mut client = register_client(&mut *bus, &registry, sub_matches)?;
+            let mut failures = 0;
+            for (name, value) in &registers {
+                match client.write(name, value) {
+                    Ok(register) => println!(
+                        "{}",
+                        register::format_registers([(name.as_str(), &register.value)])
+                    ),
+                    Err(e) => {
+                        print_error(&e);
+                        failures += 1;
+                    }
+                }
+            }
+            if failures != 0 {
+                return Err(StringError(format!(
+                    "{} of {} registers were not written",
+                    failures,
+                    registers.len()
+                ))
+                .into());
+            }
+        }
+        Some(("update", sub_matches)) => {
+            let registry = load_registry(&matches)?;
+            let target = *sub_matches.get_one::<u16>("node").expect("Missing node");
+            let image = sub_matches
+                .get_one::<PathBuf>("image")
+                .expect("Missing image");
+            let max_duration = *sub_matches
+                .get_one::<f64>("max_duration")
+                .expect("No maximum duration");
+            if !(max_duration.is_finite() && max_duration > 0.0) {
+                return Err(StringError(format!("Invalid duration {}", max_duration)).into());
+            }
+            let options = UpdateOptions {
+                priority: priority(sub_matches),
+                request_timeout: timeout(sub_matches)?,
+                timeout: Duration::from_secs_f64(max_duration),
+            };
+            let update = Update::new(&registry, image).map_err(|e| {
+                ErrorContext::new(format!("Can't use image {}", image.display()), e.into())
+            })?;
+            let mut bus = open_bus(&matches, true)?;
+            update.subscribe(&mut *bus)?;
+            update.run(&mut *bus, target, &options, io::stdout().lock())?;
+        }
+        Some(("load", sub_matches)) => {
+            let bit_rate = *sub_matches.get_one::<u32>("bit_rate").expect("No bit rate");
+            let timing = match sub_matches.get_one::<u32>("data_bit_rate") {
+                Some(data_bit_rate) => BusTiming::fd(bit_rate, *data_bit_rate),
+                None => BusTiming::classic(bit_rate),
+            };
+            let seconds = *sub_matches.get_one::<f64>("window").expect("No window");
+            let window = load::window_duration(seconds)
+                .ok_or_else(|| StringError(format!("Invalid window {}", seconds)))?;
+            let analyzer = LoadAnalyzer::new(timing, window);
+            if let Some(path) = sub_matches.get_one::<PathBuf>("file") {
+                let file = File::open(path).map_err(|e| {
+                    ErrorContext::new(format!("Can't open {}", path.display()), e.into())
+                })?;
+                load::run_file(io::BufReader::new(file), analyzer, io::stdout().lock())?;
+            } else {
+                #[cfg(feature = "can")]
+                {
+                    let interface = matches.get_one::<String>("can").ok_or_else(|| {
+                        StringError(
+                            "load requires a CAN interface (use --can) or a log file".to_owned(),
+                        )
+                    })?;
+                    load::run_can(interface, analyzer, io::stdout().lock())?;
+                }
+                #[cfg(not(feature = "can"))]
+                return Err(StringError("load requires a log file".to_owned()).into());
+            }
+        }
+        _ => unreachable!("Unknown subcommand"),
+    }
+    Ok(())
+}
+
+fn command() -> Command {
+    let command = Command::new("canadensis")
+        .version(clap::crate_version!())
+        .about("Monitors and interacts with Cyphal networks")
+        .subcommand_required(true);
+    #[cfg(feature = "can")]
+    let command = command.arg(
+        Arg::new("can")
+            .long("can")
+            .global(true)
+            .value_name("interface")
+            .conflicts_with("udp")
+            .help("The SocketCAN interface to use for Cyphal/CAN, like can0 or vcan0"),
+    );
+    let command = command
+        .arg(
+            Arg::new("udp")
+                .long("udp")
+                .global(true)
+                .value_parser(value_parser!(Ipv4Addr))
+                .value_name("address")
+                .help("The IPv4 address of the network interface to use for Cyphal/UDP, like 127.0.0.1"),
+        )
+        .arg(
+            Arg::new("node_id")
+                .long("node-id")
+                .short('n')
+                .global(true)
+                .value_parser(value_parser!(u16))
+                .help("The node ID to use (default: anonymous, which can only send and receive messages)"),
+        )
+        .arg(
+            Arg::new("dsdl")
+                .long("dsdl")
+                .global(true)
+                .action(ArgAction::Append)
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("A directory with DSDL files (default: the root directories in canadensis.conf in the current directory or the closest parent directory that has one), in addition to the directories in CYPHAL_PATH"),
+        )
+        .subcommand(
+            Command::new("monitor")
+                .about("Subscribes to subjects and prints the messages received as YAML")
+                .arg(
+                    Arg::new("subject")
+                        .index(1)
+                        .num_args(1..)
+                        .required(true)
+                        .help("The subjects to subscribe to, each like 1234:reg.udral.physics.kinematics.cartesian.Twist.0.1, or a type or subject ID with a fixed subject ID, like uavcan.node.Heartbeat or 7509"),
+                )
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .short('c')
+                        .value_parser(value_parser!(usize))
+                        .help("Exit after receiving this many messages"),
+                )
+                .arg(
+                    Arg::new("max_array_elements")
+                        .long("max-array-elements")
+                        .value_parser(value_parser!(usize))
+                        .value_name("count")
+                        .help("Show at most this many elements of each array"),
+                ),
+        )
+        .subcommand(
+            Command::new("record")
+                .about("Subscribes to subjects and appends the messages received to a CSV file for each subject")
+                .arg(
+                    Arg::new("subject")
+                        .index(1)
+                        .num_args(1..)
+                        .required(true)
+                        .help("The subjects to record, in the same format as for monitor"),
+                )
+                .arg(
+                    Arg::new("directory")
+                        .long("directory")
+                        .short('d')
+                        .value_parser(value_parser!(PathBuf))
+                        .default_value(".")
+                        .value_name("path")
+                        .help("The directory to write the CSV files in"),
+                )
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .short('c')
+                        .value_parser(value_parser!(usize))
+                        .help("Exit after receiving this many messages"),
+                ),
+        )
+        .subcommand(
+            Command::new("decode")
+                .about("Reads Cyphal/UDP traffic from a pcapng capture file and prints the messages on some subjects as YAML")
+                .arg(
+                    Arg::new("file")
+                        .index(1)
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf))
+                        .help("The capture file"),
+                )
+                .arg(
+                    Arg::new("subject")
+                        .index(2)
+                        .num_args(1..)
+                        .required(true)
+                        .help("The subjects to decode, in the same format as for monitor"),
+                )
+                .arg(
+                    Arg::new("interface")
+                        .long("interface")
+                        .value_parser(value_parser!(u32))
+                        .value_name("index")
+                        .help("Only decode packets captured on the interface with this index (starting at 0), for captures of redundant interfaces"),
+                )
+                .arg(
+                    Arg::new("port")
+                        .long("port")
+                        .value_parser(value_parser!(u16))
+                        .help("The UDP port that Cyphal/UDP traffic was sent to (default 9382)"),
+                )
+                .arg(
+                    Arg::new("max_array_elements")
+                        .long("max-array-elements")
+                        .value_parser(value_parser!(usize))
+                        .value_name("count")
+                        .help("Show at most this many elements of each array"),
+                ),
+        )
+        .subcommand(
+            Command::new("pub")
+                .about("Publishes a message, written as YAML or JSON, repeatedly")
+                .arg(
+                    Arg::new("subject")
+                        .index(1)
+                        .required(true)
+                        .help("The subject to publish on, like 1234:reg.udral.physics.kinematics.cartesian.Twist.0.1, or a type or subject ID with a fixed subject ID"),
+                )
+                .arg(
+                    Arg::new("message")
+                        .index(2)
+                        .help("The message as YAML or JSON, like '{uptime: 3, health: {value: 0}}' (fields that are left out are zero or empty), or - to read it from standard input (default: read from standard input)"),
+                )
+                .arg(
+                    Arg::new("rate")
+                        .long("rate")
+                        .short('r')
+                        .value_parser(value_parser!(f64))
+                        .default_value("1")
+                        .value_name("hertz")
+                        .help("The number of messages to publish each second"),
+                )
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .short('c')
+                        .value_parser(value_parser!(usize))
+                        .help("Exit after publishing this many messages (default: publish until interrupted)"),
+                )
+                .arg(
+                    Arg::new("priority")
+                        .long("priority")
+                        .short('p')
+                        .value_parser(bus::PRIORITY_NAMES)
+                        .default_value("nominal")
+                        .help("The priority of the messages"),
+                ),
+        )
+        .subcommand(
+            Command::new("call")
+                .about("Sends a service request, written as YAML or JSON, and prints the response")
+                .arg(
+                    Arg::new("server")
+                        .index(1)
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The node ID of the server"),
+                )
+                .arg(
+                    Arg::new("service")
+                        .index(2)
+                        .required(true)
+                        .help("The service, like 123:example.Service.1.0, or a type or service ID with a fixed service ID, like uavcan.node.GetInfo or 430"),
+                )
+                .arg(
+                    Arg::new("request")
+                        .index(3)
+                        .help("The request as YAML or JSON, like '{command: 65535}' (fields that are left out are zero or empty), or - to read it from standard input (default: all fields are zero or empty)"),
+                )
+                .arg(timeout_arg())
+                .arg(request_priority_arg()),
+        )
+        .subcommand(
+            Command::new("generate")
+                .about("Publishes synthetic messages at configurable rates to stress-test other nodes and the bus, and prints what was sent")
+                .arg(
+                    Arg::new("stream")
+                        .index(1)
+                        .num_args(1..)
+                        .required(true)
+                        .help("The streams to publish, each like subject:size@rate, like 1000:64@100 (64-byte messages on subject 1000 100 times per second), with max as the rate to publish as fast as possible and an optional priority at the end, like 1001:300@max:fast"),
+                )
+                .arg(
+                    Arg::new("duration")
+                        .long("duration")
+                        .short('d')
+                        .value_parser(value_parser!(f64))
+                        .default_value("10")
+                        .value_name("seconds")
+                        .help("The time to publish for"),
+                ),
+        )
+        .subcommand(
+            Command::new("ping")
+                .about("Sends requests to a node repeatedly and prints the round-trip times")
+                .arg(
+                    Arg::new("server")
+                        .index(1)
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The node ID of the server"),
+                )
+                .arg(
+                    Arg::new("service")
+                        .index(2)
+                        .default_value("uavcan.node.GetInfo")
+                        .help("The service to call, in the same forms as for the call command"),
+                )
+                .arg(
+                    Arg::new("request")
+                        .index(3)
+                        .help("The request as YAML or JSON (default: all fields are zero or empty)"),
+                )
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .short('c')
+                        .value_parser(value_parser!(u32))
+                        .default_value("10")
+                        .help("The number of requests to send"),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .short('i')
+                        .value_parser(value_parser!(f64))
+                        .default_value("1")
+                        .value_name("seconds")
+                        .help("The time from the start of one request to the start of the next"),
+                )
+                .arg(timeout_arg())
+                .arg(request_priority_arg()),
+        )
+        .subcommand(
+            Command::new("file-server")
+                .about("Serves the files in a directory to other nodes using the uavcan.file services")
+                .arg(
+                    Arg::new("directory")
+                        .index(1)
+                        .value_parser(value_parser!(PathBuf))
+                        .default_value(".")
+                        .help("The directory to serve"),
+                )
+                .arg(
+                    Arg::new("read_only")
+                        .long("read-only")
+                        .action(ArgAction::SetTrue)
+                        .help("Reject requests to write, move, copy, or remove files"),
+                ),
+        )
+        .subcommand(
+            Command::new("node-list")
+                .about("Listens for heartbeats and port lists, requests information from each node found, and prints a table of the nodes")
+                .arg(
+                    Arg::new("duration")
+                        .long("duration")
+                        .short('d')
+                        .value_parser(value_parser!(f64))
+                        .default_value("3")
+                        .value_name("seconds")
+                        .help("The time to listen for nodes (nodes publish their port lists at least every 10 seconds)"),
+                )
+                .arg(timeout_arg())
+                .arg(request_priority_arg()),
+        )
+        .subcommand(
+            Command::new("tui")
+                .about("Shows the nodes and subjects on the network, with message rates and the latest messages, in an interactive view")
+                .arg(
+                    Arg::new("subject")
+                        .index(1)
+                        .num_args(0..)
+                        .help("Subjects to show in addition to the heartbeat and port list subjects, in the same format as for monitor"),
+                ),
+        )
+        .subcommand(
+            register_command("register-list")
+                .about("Prints the names of the registers of a node"),
+        )
+        .subcommand(
+            register_command("register-get")
+                .about("Prints the values of registers of a node as YAML")
+                .arg(
+                    Arg::new("name")
+                        .index(2)
+                        .num_args(1..)
+                        .help("The registers to read (default: all registers, which can be saved and written back with register-apply)"),
+                )
+                .arg(
+                    Arg::new("mutable")
+                        .long("mutable")
+                        .action(ArgAction::SetTrue)
+                        .help("Leave out registers that can't be written"),
+                ),
+        )
+        .subcommand(
+            register_command("register-set")
+                .about("Writes a value to a register of a node")
+                .arg(
+                    Arg::new("name")
+                        .index(2)
+                        .required(true)
+                        .help("The register name, like uavcan.node.description"),
+                )
+                .arg(
+                    Arg::new("value")
+                        .index(3)
+                        .required(true)
+                        .allow_hyphen_values(true)
+                        .help("The value as YAML or JSON, which is converted to the type of the register, like 42, '[0.5, 0.25]', or 'Left motor', or a value with its type, like '{natural16: {value: [42]}}'"),
+                ),
+        )
+        .subcommand(
+            register_command("register-apply")
+                .about("Writes values to registers of a node from a YAML file that maps register names to values")
+                .arg(
+                    Arg::new("file")
+                        .index(2)
+                        .help("The file, or - to read standard input (default: read standard input)"),
+                ),
+        )
+        .subcommand(
+            Command::new("update")
+                .about("Updates the software of a node with an image file, serves the file, and reports the progress")
+                .arg(
+                    Arg::new("node")
+                        .index(1)
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The node ID of the node to update"),
+                )
+                .arg(
+                    Arg::new("image")
+                        .index(2)
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf))
+                        .help("The software image file"),
+                )
+                .arg(
+                    Arg::new("max_duration")
+                        .long("max-duration")
+                        .value_parser(value_parser!(f64))
+                        .default_value("600")
+                        .value_name("seconds")
+                        .help("The time allowed for the whole update"),
+                )
+                .arg(timeout_arg())
+                .arg(request_priority_arg()),
+        );
+    command.subcommand(
+        Command::new("load")
+            .about("Measures the load on a CAN bus and prints the bus utilization and the traffic on each port and from each node")
+            .arg(
+                Arg::new("file")
+                    .long("file")
+                    .short('f')
+                    .value_parser(value_parser!(PathBuf))
+                    .value_name("path")
+                    .help("A log file from candump -l to read frames from, instead of the bus"),
+            )
+            .arg(
+                Arg::new("bit_rate")
+                    .long("bit-rate")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .default_value("1000000")
+                    .value_name("bits/s")
+                    .help("The bit rate of the bus (for CAN FD, the nominal bit rate)"),
+            )
+            .arg(
+                Arg::new("data_bit_rate")
+                    .long("data-bit-rate")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .value_name("bits/s")
+                    .help("The CAN FD data bit rate (default: classic CAN)"),
+            )
+            .arg(
+                Arg::new("window")
+                    .long("window")
+                    .short('w')
+                    .value_parser(value_parser!(f64))
+                    .default_value("1")
+                    .value_name("seconds")
+                    .help("The time between reports"),
+            ),
+    )
+}
+
+/// Returns a subcommand that accesses the registers of a server node
+fn register_command(name: &'static str) -> Command {
+    Command::new(name)
+        .arg(
+            Arg::new("server")
+                .index(1)
+                .required(true)
+                .value_parser(value_parser!(u16))
+                .help("The node ID of the node that has the registers"),
+        )
+        .arg(timeout_arg())
+        .arg(request_priority_arg())
+}
+
+fn timeout_arg() -> Arg {
+    Arg::new("timeout")
+        .long("timeout")
+        .short('t')
+        .value_parser(value_parser!(f64))
+        .default_value("1")
+        .value_name("seconds")
+        .help("The time to wait for each response")
+}
+
+fn request_priority_arg() -> Arg {
+    Arg::new("priority")
+        .long("priority")
+        .short('p')
+        .value_parser(bus::PRIORITY_NAMES)
+        .default_value("nominal")
+        .help("The priority of the requests")
+}
+
+/// Returns the time that a subcommand's --timeout option selects
+fn timeout(matches: &ArgMatches) -> Result<Duration, StringError> {
+    let timeout = *matches.get_one::<f64>("timeout").expect("No timeout");
+    if timeout.is_finite() && timeout > 0.0 {
+        Ok(Duration::from_secs_f64(timeout))
+    } else {
+        Err(StringError(format!("Invalid timeout {}", timeout)))
+    }
+}
+
+/// Creates a client for the registers of the node that a subcommand's server argument selects
+fn register_client<'a>(
+    bus: &'a mut dyn Bus,
+    registry: &'a TypeRegistry,
+    matches: &ArgMatches,
+) -> Result<RegisterClient<'a>, Box<dyn std::error::Error>> {
+    let server = *matches.get_one::<u16>("server").expect("Missing server");
+    Ok(RegisterClient::new(
+        bus,
+        registry,
+        server,
+        priority(matches),
+        timeout(matches)?,
+    )?)
+}
+
+/// Returns the priority that a subcommand's --priority option selects
+fn priority(matches: &ArgMatches) -> Priority {
+    matches
+        .get_one::<String>("priority")
+        .and_then(|priority| bus::parse_priority(priority))
+        .expect("Invalid priority")
+}
+
+fn load_registry(matches: &ArgMatches) -> Result<TypeRegistry, Box<dyn std::error::Error>> {
+    let roots: Vec<PathBuf> = matches
+        .get_many::<PathBuf>("dsdl")
+        .map(|roots| roots.cloned().collect())
+        .unwrap_or_default();
+    types::load_registry(&roots)
+        .map_err(|e| ErrorContext::new("Failed to load data types".to_owned(), e).into())
+}
+
+/// Opens the bus that the command-line options select
+///
+/// `listen` is false for commands that only send transfers.
+fn open_bus(
+    matches: &ArgMatches,
+    listen: bool,
+) -> Result<Box<dyn Bus>, Box<dyn std::error::Error>> {
+    let node_id = matches.get_one::<u16>("node_id").copied();
+    #[cfg(feature = "can")]
+    {
+        if let Some(interface) = matches.get_one::<String>("can") {
+            return Ok(bus::open_can(interface, node_id)?);
+        }
+    }
+    match matches.get_one::<Ipv4Addr>("udp") {
+        Some(interface) => Ok(bus::open_udp(*interface, node_id, listen)?),
+        None if cfg!(feature = "can") => {
+            Err(StringError("A transport is required (use --can or --udp)".to_owned()).into())
+        }
+        None => Err(StringError("A transport is required (use --udp)".to_owned()).into()),
+    }
+}
+
+fn print_error(e: &dyn std::error::Error) {
+    eprintln!("{}", e);
+    if let Some(source) = e.source() {
+        eprintln!("Caused by:");
+        print_error(source);
+    }
+}
+
+/// Wraps an error with a message
+#[derive(Debug)]
+struct ErrorContext {
+    context: String,
+    inner: Box<dyn std::error::Error>,
+}
+
+impl ErrorContext {
+    pub fn new(context: String, inner: Box<dyn std::error::Error>) -> Self {
+        ErrorContext { context, inner }
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.context)
+    }
+}
+
+impl std::error::Error for ErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.inner)
+    }
+}
+
+/// An error that contains only a message
+#[derive(Debug)]
+struct StringError(String);
+
+impl std::fmt::Display for StringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for StringError {}
@@ -0,0 +1,235 @@
+//! A bus for tests that connects a command to a simulated node instead of a network
+//!
+//! [`MockBus`] records the transfers that a command sends. A [`Server`] simulates the other
+//! nodes: it is called for each request and response that the command sends, and it adds the
+//! transfers that the command should receive.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::SystemTime;
+
+use canadensis_core::time::Microseconds32;
+use canadensis_core::Priority;
+use canadensis_dynamic::json::{from_json, to_json};
+use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+
+use crate::bus::{Bus, BusError, ReceivedTransfer};
+
+/// The ID of the node that runs the command
+pub const LOCAL_NODE: u16 = 1;
+
+/// Simulated nodes that respond to the transfers that a command sends
+pub trait Server {
+    /// Handles a request that the command sent, adding any transfers that the command
+    /// should receive to `received`
+    fn request(&mut self, _request: &ReceivedTransfer, _received: &mut VecDeque<ReceivedTransfer>) {
+    }
+
+    /// Handles a response that the command sent to `request`, adding any transfers that the
+    /// command should receive to `received`
+    fn response(
+        &mut self,
+        _request: &ReceivedTransfer,
+        _response: &ReceivedTransfer,
+        _received: &mut VecDeque<ReceivedTransfer>,
+    ) {
+    }
+}
+
+/// No other nodes
+impl Server for () {}
+
+/// A bus that records outgoing transfers and passes them to a server
+pub struct MockBus<S> {
+    /// The simulated nodes
+    pub server: S,
+    /// The ID of this node, or None if it is anonymous
+    pub node_id: Option<u16>,
+    /// The subscriptions, in the order they were made
+    pub subscriptions: Vec<(PayloadKind, u16)>,
+    /// Transfers that the command will receive
+    pub received: VecDeque<ReceivedTransfer>,
+    /// The messages that the command published
+    pub published: Vec<ReceivedTransfer>,
+    /// The requests that the command sent
+    pub requests: Vec<ReceivedTransfer>,
+    /// The responses that the command sent
+    pub responses: Vec<ReceivedTransfer>,
+    /// The next transfer ID for each kind of transfer, port, and destination
+    transfer_ids: BTreeMap<(u8, u16, u16), u64>,
+}
+
+impl<S: Server> MockBus<S> {
+    /// Creates a bus with node ID [`LOCAL_NODE`]
+    pub fn new(server: S) -> Self {
+        MockBus {
+            server,
+            node_id: Some(LOCAL_NODE),
+            subscriptions: Vec::new(),
+            received: VecDeque::new(),
+            published: Vec::new(),
+            requests: Vec::new(),
+            responses: Vec::new(),
+            transfer_ids: BTreeMap::new(),
+        }
+    }
+
+    fn next_transfer_id(&mut self, kind: u8, port_id: u16, destination: u16) -> u64 {
+        let next = self
+            .transfer_ids
+            .entry((kind, port_id, destination))
+            .or_insert(0);
+        let current = *next;
+        *next += 1;
+        current
+    }
+}
+
+impl<S: Server> Bus for MockBus<S> {
+    fn now(&mut self) -> Microseconds32 {
+        Microseconds32::from_ticks(0)
+    }
+
+    fn node_id(&self) -> Option<u16> {
+        self.node_id
+    }
+
+    fn subscribe(&mut self, kind: PayloadKind, port_id: u16, _: usize) -> Result<(), BusError> {
+        if kind != PayloadKind::Message && self.node_id.is_none() {
+            return Err(BusError::Anonymous);
+        }
+        self.subscriptions.push((kind, port_id));
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Option<ReceivedTransfer>, BusError> {
+        Ok(self.received.pop_front())
+    }
+
+    fn publish(
+        &mut self,
+        subject: u16,
+        priority: Priority,
+        payload: &[u8],
+    ) -> Result<(), BusError> {
+        let transfer_id = self.next_transfer_id(0, subject, 0);
+        self.published.push(ReceivedTransfer {
+            kind: PayloadKind::Message,
+            port_id: subject,
+            source: self.node_id,
+            destination: None,
+            transfer_id,
+            priority,
+            timestamp: Microseconds32::from_ticks(0),
+            system_time: SystemTime::now(),
+            payload: payload.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn send_request(
+        &mut self,
+        service: u16,
+        destination: u16,
+        priority: Priority,
+        payload: &[u8],
+    ) -> Result<u64, BusError> {
+        let source = self.node_id.ok_or(BusError::Anonymous)?;
+        let transfer_id = self.next_transfer_id(1, service, destination);
+        let request = ReceivedTransfer {
+            kind: PayloadKind::Request,
+            port_id: service,
+            source: Some(source),
+            destination: Some(destination),
+            transfer_id,
+            priority,
+            timestamp: Microseconds32::from_ticks(0),
+            system_time: SystemTime::now(),
+            payload: payload.to_vec(),
+        };
+        self.server.request(&request, &mut self.received);
+        self.requests.push(request);
+        Ok(transfer_id)
+    }
+
+    fn send_response(
+        &mut self,
+        request: &ReceivedTransfer,
+        payload: &[u8],
+    ) -> Result<(), BusError> {
+        let source = self.node_id.ok_or(BusError::Anonymous)?;
+        let mut response = response_to(request, payload.to_vec());
+        response.source = Some(source);
+        self.server.response(request, &response, &mut self.received);
+        self.responses.push(response);
+        Ok(())
+    }
+}
+
+/// Returns a transfer from node `source` to [`LOCAL_NODE`], or a message if `kind` is
+/// `Message`
+pub fn transfer(
+    kind: PayloadKind,
+    port_id: u16,
+    source: u16,
+    payload: Vec<u8>,
+) -> ReceivedTransfer {
+    ReceivedTransfer {
+        kind,
+        port_id,
+        source: Some(source),
+        destination: match kind {
+            PayloadKind::Message => None,
+            _ => Some(LOCAL_NODE),
+        },
+        transfer_id: 0,
+        priority: Priority::Nominal,
+        timestamp: Microseconds32::from_ticks(0),
+        system_time: SystemTime::now(),
+        payload,
+    }
+}
+
+/// Returns a response to a request, from the destination of the request
+pub fn response_to(request: &ReceivedTransfer, payload: Vec<u8>) -> ReceivedTransfer {
+    ReceivedTransfer {
+        kind: PayloadKind::Response,
+        port_id: request.port_id,
+        source: request.destination,
+        destination: request.source,
+        transfer_id: request.transfer_id,
+        priority: request.priority,
+        timestamp: Microseconds32::from_ticks(0),
+        system_time: SystemTime::now(),
+        payload,
+    }
+}
+
+/// Deserializes the payload of a transfer and converts it into JSON
+pub fn decode(registry: &TypeRegistry, transfer: &ReceivedTransfer) -> serde_json::Value {
+    let key = match transfer.kind {
+        PayloadKind::Message => registry.subject_type(transfer.port_id),
+        _ => registry.service_type(transfer.port_id),
+    }
+    .unwrap();
+    to_json(
+        &registry
+            .deserialize(key, transfer.kind, &transfer.payload)
+            .unwrap(),
+    )
+}
+
+/// Converts JSON into a value of the type on a port and serializes it
+pub fn encode(
+    registry: &TypeRegistry,
+    kind: PayloadKind,
+    port_id: u16,
+    json: &serde_json::Value,
+) -> Vec<u8> {
+    let key = match kind {
+        PayloadKind::Message => registry.subject_type(port_id),
+        _ => registry.service_type(port_id),
+    }
+    .unwrap();
+    let value = from_json(registry.message(key, kind).unwrap(), json).unwrap();
+    registry.serialize(key, kind, &value).unwrap()
+}
@@ -0,0 +1,251 @@
+//! Subscribing to subjects and printing the messages received on them
+//!
+//! Each message is printed as a YAML document in the style of `yakut sub`. The document maps
+//! the subject ID to the message fields, and a `_meta_` entry has the transfer metadata:
+//!
+//! ```text
+//! ---
+//! 7509:
+//!   _meta_: {ts_system: 1700000000.123456, ts_monotonic: 12.345678, source_node_id: 42, transfer_id: 17, priority: nominal, dtype: uavcan.node.Heartbeat.1.0}
+//!   uptime: 17
+//!   health:
+//!     value: 0
+//!   mode:
+//!     value: 0
+//!   vendor_specific_status_code: 0
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::time::UNIX_EPOCH;
+
+use canadensis_dsdl_frontend::TypeKey;
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::yaml::{to_yaml, YamlConfig};
+use canadensis_dynamic::Value;
+
+use crate::bus::{priority_name, Bus, BusError, ReceivedTransfer};
+use crate::types::{receive_size, Port};
+
+/// Receives messages on some subjects and formats them as YAML
+pub struct Monitor<'r> {
+    registry: &'r TypeRegistry,
+    /// The type of each subject
+    subjects: BTreeMap<u16, TypeKey>,
+    yaml_config: YamlConfig,
+}
+
+impl<'r> Monitor<'r> {
+    /// Creates a monitor for some subjects
+    ///
+    /// If the same subject ID appears more than once, the last type is used.
+    pub fn new<I>(registry: &'r TypeRegistry, subjects: I, yaml_config: YamlConfig) -> Self
+    where
+        I: IntoIterator<Item = Port>,
+    {
+        Monitor {
+            registry,
+            subjects: subjects
+                .into_iter()
+                .map(|port| (port.port_id, port.key))
+                .collect(),
+            yaml_config,
+        }
+    }
+
+    /// Subscribes to all the subjects on a bus
+    pub fn subscribe(&self, bus: &mut dyn Bus) -> Result<(), Box<dyn std::error::Error>> {
+        for (&subject, key) in &self.subjects {
+            let message = self.registry.message(key, PayloadKind::Message)?;
+            bus.subscribe(PayloadKind::Message, subject, receive_size(message))?;
+        }
+        Ok(())
+    }
+
+    /// Formats a received message as a YAML document (without a newline at the end)
+    ///
+    /// This returns Ok(None) if the transfer is not a message on one of the subjects.
+    pub fn format(&self, transfer: &ReceivedTransfer) -> Result<Option<String>, RegistryError> {
+        if transfer.kind != PayloadKind::Message {
+            return Ok(None);
+        }
+        let key = match self.subjects.get(&transfer.port_id) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let value = self
+            .registry
+            .deserialize(key, PayloadKind::Message, &transfer.payload)?;
+        Ok(Some(self.document(transfer, key, &value)))
+    }
+
+    fn document(&self, transfer: &ReceivedTransfer, key: &TypeKey, value: &Value) -> String {
+        let ts_system = transfer
+            .system_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let source = match transfer.source {
+            Some(source) => source.to_string(),
+            None => "null".to_owned(),
+        };
+        let meta = format!(
+            "{{ts_system: {}.{:06}, ts_monotonic: {}.{:06}, source_node_id: {}, transfer_id: {}, \
+            priority: {}, dtype: {}}}",
+            ts_system.as_secs(),
+            ts_system.subsec_micros(),
+            transfer.timestamp.ticks() / 1_000_000,
+            transfer.timestamp.ticks() % 1_000_000,
+            source,
+            transfer.transfer_id,
+            priority_name(transfer.priority),
+            key
+        );
+        yaml_document(transfer.port_id, Some(&meta), value, &self.yaml_config)
+    }
+
+    /// Receives messages and writes them to `output`
+    ///
+    /// This returns after `count` messages, or runs until an error occurs if `count` is None.
+    /// Messages that can't be deserialized are reported on standard error and skipped.
+    pub fn run<W: Write>(
+        &self,
+        bus: &mut dyn Bus,
+        count: Option<usize>,
+        mut output: W,
+    ) -> Result<(), MonitorError> {
+        let mut remaining = count;
+        while remaining != Some(0) {
+            let transfer = match bus.receive()? {
+                Some(transfer) => transfer,
+                None => continue,
+            };
+            match self.format(&transfer) {
+                Ok(Some(document)) => {
+                    writeln!(output, "{}", document)?;
+                    output.flush()?;
+                    if let Some(remaining) = &mut remaining {
+                        *remaining -= 1;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "Invalid message on subject {} from node {:?}: {}",
+                    transfer.port_id, transfer.source, e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats a value as a YAML document that maps a port ID to the value, like `yakut` does
+///
+/// If `meta` is not None, it is the value of a `_meta_` entry before the fields of the value.
+pub(crate) fn yaml_document(
+    port_id: u16,
+    meta: Option<&str>,
+    value: &Value,
+    config: &YamlConfig,
+) -> String {
+    let mut document = format!("---\n{}:", port_id);
+    if let Some(meta) = meta {
+        document.push_str("\n  _meta_: ");
+        document.push_str(meta);
+    }
+    let yaml = to_yaml(value, config);
+    match value {
+        // A struct with no fields is "{}", which is left out if the metadata is already
+        // a mapping
+        Value::Struct(fields) if fields.is_empty() && meta.is_some() => {}
+        Value::Struct(_) | Value::Union { .. } if !yaml.starts_with('{') => {
+            for line in yaml.lines() {
+                document.push_str("\n  ");
+                document.push_str(line);
+            }
+        }
+        _ => {
+            document.push(' ');
+            document.push_str(&yaml);
+        }
+    }
+    document
+}
+
+/// Errors that can occur while monitoring subjects
+#[derive(thiserror::Error, Debug)]
+pub enum MonitorError {
+    /// Receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+    /// Writing the output failed
+    #[error("Failed to write output")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::Monitor;
+    use crate::bus::ReceivedTransfer;
+    use crate::types::find_subject;
+    use canadensis_core::time::Microseconds32;
+    use canadensis_core::Priority;
+    use canadensis_dsdl_frontend::{Config, Package};
+    use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+    use canadensis_dynamic::yaml::YamlConfig;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn registry() -> TypeRegistry {
+        let mut package = Package::new();
+        package
+            .add_string(
+                Some(100),
+                "test.Point.1.0".parse().unwrap(),
+                "int16 x\nint16 y\n@sealed\n".into(),
+            )
+            .unwrap();
+        package
+            .add_string(None, "test.Empty.1.0".parse().unwrap(), "@sealed\n".into())
+            .unwrap();
+        TypeRegistry::new(package.compile(&Config::default()).unwrap())
+    }
+
+    fn transfer(port_id: u16, payload: Vec<u8>) -> ReceivedTransfer {
+        ReceivedTransfer {
+            kind: PayloadKind::Message,
+            port_id,
+            source: Some(42),
+            destination: None,
+            transfer_id: 3,
+            priority: Priority::Nominal,
+            timestamp: Microseconds32::from_ticks(1_500_000),
+            system_time: UNIX_EPOCH + Duration::from_micros(1_700_000_000_000_250),
+            payload,
+        }
+    }
+
+    #[test]
+    fn format_messages() {
+        let registry = registry();
+        let subjects = vec![
+            find_subject(&registry, "test.Point").unwrap(),
+            find_subject(&registry, "200:test.Empty.1").unwrap(),
+        ];
+        let monitor = Monitor::new(&registry, subjects, YamlConfig::default());
+
+        assert_eq!(
+            "---\n100:\n  _meta_: {ts_system: 1700000000.000250, ts_monotonic: 1.500000, \
+            source_node_id: 42, transfer_id: 3, priority: nominal, dtype: test.Point.1.0}\n  \
+            x: 1\n  y: -1",
+            monitor
+                .format(&transfer(100, vec![0x01, 0x00, 0xff, 0xff]))
+                .unwrap()
+                .unwrap()
+        );
+        assert_eq!(
+            "---\n200:\n  _meta_: {ts_system: 1700000000.000250, ts_monotonic: 1.500000, \
+            source_node_id: 42, transfer_id: 3, priority: nominal, dtype: test.Empty.1.0}",
+            monitor.format(&transfer(200, vec![])).unwrap().unwrap()
+        );
+        assert_eq!(None, monitor.format(&transfer(300, vec![])).unwrap());
+    }
+}
@@ -0,0 +1,481 @@
+//! Discovering the nodes on a network and printing an overview of them
+//!
+//! A [`NodeList`] finds nodes using their heartbeats and port list messages
+//! (`uavcan.node.port.List`), which each node publishes at least every 10 seconds. It can then
+//! send a `uavcan.node.GetInfo` request to each node to find its name and software version.
+//! The result is a table like this:
+//!
+//! ```text
+//! NODE  NAME               VERSION  HEALTH   MODE         UPTIME       PORTS
+//! 42    org.example.motor  1.3      nominal  operational  0:12:07      pub 1000,7509,7510; sub 1200; srv 384,385,430
+//! 43    ?                  ?        warning  maintenance  3d 01:00:02  ?
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use canadensis_core::Priority;
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::Value;
+
+use crate::bus::{Bus, BusError, ReceivedTransfer};
+use crate::call::{call, request_payload, CallError};
+use crate::types::{field, find_service, find_subject, receive_size, uint8_text, Port};
+
+/// What is known about a node
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeStatus {
+    /// The uptime from the latest heartbeat, in seconds
+    pub uptime: Option<u32>,
+    /// The health from the latest heartbeat
+    pub health: Option<u8>,
+    /// The mode from the latest heartbeat
+    pub mode: Option<u8>,
+    /// The name from the GetInfo response
+    pub name: Option<String>,
+    /// The major and minor software version from the GetInfo response
+    pub software_version: Option<(u8, u8)>,
+    /// The ports from the latest port list message
+    pub ports: Option<PortList>,
+}
+
+/// The ports that a node uses
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortList {
+    /// The subjects that the node publishes on
+    pub publishers: SubjectSet,
+    /// The subjects that the node subscribes to
+    pub subscribers: SubjectSet,
+    /// The services that the node sends requests to
+    pub clients: Vec<u16>,
+    /// The services that the node responds to
+    pub servers: Vec<u16>,
+}
+
+/// A set of subject IDs
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubjectSet {
+    /// Some subject IDs, in increasing order
+    Some(Vec<u16>),
+    /// All subject IDs
+    All,
+}
+
+impl Default for SubjectSet {
+    fn default() -> Self {
+        SubjectSet::Some(Vec::new())
+    }
+}
+
+/// Collects information about the nodes on a network
+pub struct NodeList<'r> {
+    registry: &'r TypeRegistry,
+    heartbeat: Port,
+    port_list: Port,
+    get_info: Port,
+    nodes: BTreeMap<u16, NodeStatus>,
+}
+
+impl<'r> NodeList<'r> {
+    /// Creates an empty node list
+    ///
+    /// This returns an error if the registry does not have the heartbeat, port list, and
+    /// GetInfo types.
+    pub fn new(registry: &'r TypeRegistry) -> Result<Self, NodeListError> {
+        Ok(NodeList {
+            registry,
+            heartbeat: find_subject(registry, "uavcan.node.Heartbeat.1")
+                .map_err(NodeListError::Types)?,
+            port_list: find_subject(registry, "uavcan.node.port.List.1")
+                .map_err(NodeListError::Types)?,
+            get_info: find_service(registry, "uavcan.node.GetInfo.1")
+                .map_err(NodeListError::Types)?,
+            nodes: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the nodes that have been found, in order of node ID
+    pub fn nodes(&self) -> &BTreeMap<u16, NodeStatus> {
+        &self.nodes
+    }
+
+    /// Subscribes to heartbeats and port lists on a bus
+    pub fn subscribe(&self, bus: &mut dyn Bus) -> Result<(), NodeListError> {
+        for port in [&self.heartbeat, &self.port_list] {
+            let message = self.registry.message(&port.key, PayloadKind::Message)?;
+            bus.subscribe(PayloadKind::Message, port.port_id, receive_size(message))?;
+        }
+        Ok(())
+    }
+
+    /// Receives heartbeats and port lists until `duration` has passed
+    ///
+    /// Messages that can't be deserialized are reported on standard error and skipped.
+    pub fn listen(&mut self, bus: &mut dyn Bus, duration: Duration) -> Result<(), NodeListError> {
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            if let Some(transfer) = bus.receive()? {
+                if let Err(e) = self.handle(&transfer) {
+                    eprintln!(
+                        "Invalid message on subject {} from node {:?}: {}",
+                        transfer.port_id, transfer.source, e
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates the node list from a received transfer
+    ///
+    /// Transfers that are not heartbeats or port lists from non-anonymous nodes are ignored.
+    pub fn handle(&mut self, transfer: &ReceivedTransfer) -> Result<(), RegistryError> {
+        let source = match (transfer.kind, transfer.source) {
+            (PayloadKind::Message, Some(source)) => source,
+            _ => return Ok(()),
+        };
+        if transfer.port_id == self.heartbeat.port_id {
+            let heartbeat = self.registry.deserialize(
+                &self.heartbeat.key,
+                PayloadKind::Message,
+                &transfer.payload,
+            )?;
+            let status = self.nodes.entry(source).or_default();
+            status.uptime = uint(field(&heartbeat, "uptime")).map(|uptime| uptime as u32);
+            status.health = nested_uint(&heartbeat, "health");
+            status.mode = nested_uint(&heartbeat, "mode");
+        } else if transfer.port_id == self.port_list.port_id {
+            let list = self.registry.deserialize(
+                &self.port_list.key,
+                PayloadKind::Message,
+                &transfer.payload,
+            )?;
+            self.nodes.entry(source).or_default().ports = Some(PortList {
+                publishers: subject_set(field(&list, "publishers")),
+                subscribers: subject_set(field(&list, "subscribers")),
+                clients: service_ids(field(&list, "clients")),
+                servers: service_ids(field(&list, "servers")),
+            });
+        }
+        Ok(())
+    }
+
+    /// Sends a GetInfo request to each node that has been found and records the names and
+    /// software versions
+    ///
+    /// Nodes that do not respond within `timeout` are reported on standard error and skipped.
+    pub fn query_info(
+        &mut self,
+        bus: &mut dyn Bus,
+        priority: Priority,
+        timeout: Duration,
+    ) -> Result<(), NodeListError> {
+        let payload = request_payload(self.registry, &self.get_info, "")?;
+        let node_ids: Vec<u16> = self.nodes.keys().copied().collect();
+        for node_id in node_ids {
+            let info = match call(
+                bus,
+                self.registry,
+                &self.get_info,
+                node_id,
+                &payload,
+                priority,
+                timeout,
+            ) {
+                Ok(info) => info,
+                Err(e @ CallError::Timeout { .. }) => {
+                    eprintln!("{}", e);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let status = self.nodes.get_mut(&node_id).expect("No node");
+            status.name = field(&info, "name").and_then(uint8_text);
+            let version = field(&info, "software_version");
+            status.software_version = version.and_then(|version| {
+                nested_uint(version, "major").zip(nested_uint(version, "minor"))
+            });
+        }
+        Ok(())
+    }
+
+    /// Formats the nodes as a table, with one line for each node and a line of headings
+    pub fn format_table(&self) -> String {
+        let rows: Vec<[String; 7]> = self
+            .nodes
+            .iter()
+            .map(|(node_id, status)| {
+                [
+                    node_id.to_string(),
+                    unknown(status.name.clone()),
+                    unknown(
+                        status
+                            .software_version
+                            .map(|(major, minor)| format!("{}.{}", major, minor)),
+                    ),
+                    unknown(status.health.map(|health| health_name(health).to_owned())),
+                    unknown(status.mode.map(|mode| mode_name(mode).to_owned())),
+                    unknown(status.uptime.map(format_uptime)),
+                    unknown(status.ports.as_ref().map(format_ports)),
+                ]
+            })
+            .collect();
+        let headings = [
+            "NODE", "NAME", "VERSION", "HEALTH", "MODE", "UPTIME", "PORTS",
+        ]
+        .map(String::from);
+        format_table(headings, &rows)
+    }
+}
+
+/// Formats rows of cells as a table with aligned columns separated by two spaces
+pub(crate) fn format_table<const N: usize>(headings: [String; N], rows: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+    for row in std::iter::once(&headings).chain(rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for row in std::iter::once(&headings).chain(rows) {
+        let mut line = String::new();
+        for (i, (cell, width)) in row.iter().zip(widths).enumerate() {
+            if i != 0 {
+                line.push_str("  ");
+            }
+            let _ = write!(line, "{:width$}", cell, width = width);
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table.pop();
+    table
+}
+
+pub(crate) fn uint(value: Option<&Value>) -> Option<u64> {
+    match value {
+        Some(Value::UInt(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Returns the `value` field of a struct field with a small integer value, like the health in a
+/// heartbeat or the major version in a `uavcan.node.Version`
+pub(crate) fn nested_uint(value: &Value, name: &str) -> Option<u8> {
+    let inner = field(value, name)?;
+    uint(field(inner, "value").or(Some(inner))).map(|value| value as u8)
+}
+
+/// Converts a `uavcan.node.port.SubjectIDList`
+fn subject_set(list: Option<&Value>) -> SubjectSet {
+    match list {
+        Some(Value::Union { variant, value }) => match variant.as_str() {
+            "mask" => SubjectSet::Some(mask_ids(value)),
+            "sparse_list" => {
+                let mut ids: Vec<u16> = match &**value {
+                    Value::Array(ids) => ids
+                        .iter()
+                        .filter_map(|id| uint(field(id, "value")))
+                        .map(|id| id as u16)
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                ids.sort_unstable();
+                ids.dedup();
+                SubjectSet::Some(ids)
+            }
+            _ => SubjectSet::All,
+        },
+        _ => SubjectSet::default(),
+    }
+}
+
+/// Converts a `uavcan.node.port.ServiceIDList`
+fn service_ids(list: Option<&Value>) -> Vec<u16> {
+    list.and_then(|list| field(list, "mask"))
+        .map(mask_ids)
+        .unwrap_or_default()
+}
+
+/// Returns the indices of the true elements of an array of booleans
+fn mask_ids(mask: &Value) -> Vec<u16> {
+    match mask {
+        Value::Array(bits) => bits
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit == Value::Bool(true))
+            .map(|(i, _)| i as u16)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn unknown(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "?".to_owned())
+}
+
+pub(crate) fn health_name(health: u8) -> &'static str {
+    match health {
+        0 => "nominal",
+        1 => "advisory",
+        2 => "caution",
+        _ => "warning",
+    }
+}
+
+pub(crate) fn mode_name(mode: u8) -> &'static str {
+    match mode {
+        0 => "operational",
+        1 => "initialization",
+        2 => "maintenance",
+        _ => "software_update",
+    }
+}
+
+/// Formats an uptime in seconds as hours, minutes, and seconds, with days if the uptime is
+/// at least one day
+pub(crate) fn format_uptime(uptime: u32) -> String {
+    let (days, seconds) = (uptime / 86400, uptime % 86400);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if days == 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}d {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    }
+}
+
+pub(crate) fn format_ports(ports: &PortList) -> String {
+    let subjects = |set: &SubjectSet| match set {
+        SubjectSet::Some(ids) => join(ids),
+        SubjectSet::All => "all".to_owned(),
+    };
+    let groups = [
+        ("pub", subjects(&ports.publishers)),
+        ("sub", subjects(&ports.subscribers)),
+        ("cli", join(&ports.clients)),
+        ("srv", join(&ports.servers)),
+    ];
+    let groups: Vec<String> = groups
+        .iter()
+        .filter(|(_, ids)| !ids.is_empty())
+        .map(|(kind, ids)| format!("{} {}", kind, ids))
+        .collect();
+    if groups.is_empty() {
+        "none".to_owned()
+    } else {
+        groups.join("; ")
+    }
+}
+
+fn join(ids: &[u16]) -> String {
+    ids.iter()
+        .map(u16::to_string)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Errors that can occur when discovering nodes
+#[derive(thiserror::Error, Debug)]
+pub enum NodeListError {
+    /// The heartbeat, port list, or GetInfo types could not be found
+    #[error("{0}")]
+    Types(String),
+    /// A type could not be used
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    /// A GetInfo request failed
+    #[error(transparent)]
+    Call(#[from] CallError),
+    /// Sending or receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NodeList, PortList, SubjectSet};
+    use crate::bus::ReceivedTransfer;
+    use crate::types::standard_registry;
+    use canadensis_core::time::Microseconds32;
+    use canadensis_core::Priority;
+    use canadensis_dynamic::registry::PayloadKind;
+    use canadensis_dynamic::yaml::from_yaml;
+    use std::time::SystemTime;
+
+    fn transfer(port_id: u16, source: Option<u16>, payload: Vec<u8>) -> ReceivedTransfer {
+        ReceivedTransfer {
+            kind: PayloadKind::Message,
+            port_id,
+            source,
+            destination: None,
+            transfer_id: 0,
+            priority: Priority::Nominal,
+            timestamp: Microseconds32::from_ticks(0),
+            system_time: SystemTime::now(),
+            payload,
+        }
+    }
+
+    #[test]
+    fn discover_nodes() {
+        let registry = standard_registry();
+        let mut list = NodeList::new(&registry).unwrap();
+        let payload = |name: &str, yaml: &str| {
+            let key = registry.find(name).unwrap();
+            let message = registry.message(key, PayloadKind::Message).unwrap();
+            registry
+                .serialize(
+                    key,
+                    PayloadKind::Message,
+                    &from_yaml(message, yaml).unwrap(),
+                )
+                .unwrap()
+        };
+        let heartbeat = |yaml| payload("uavcan.node.Heartbeat", yaml);
+        list.handle(&transfer(
+            7509,
+            Some(43),
+            heartbeat("{uptime: 262802, health: {value: 3}, mode: {value: 2}}"),
+        ))
+        .unwrap();
+        list.handle(&transfer(7509, Some(42), heartbeat("uptime: 727")))
+            .unwrap();
+        // Anonymous nodes are ignored
+        list.handle(&transfer(7509, None, heartbeat("uptime: 1")))
+            .unwrap();
+        let mut servers = vec![false; 512];
+        servers[430] = true;
+        servers[384] = true;
+        let port_list = format!(
+            "{{publishers: {{sparse_list: [{{value: 7510}}, {{value: 7509}}]}}, \
+             subscribers: {{total: {{}}}}, servers: {{mask: {:?}}}}}",
+            servers
+        );
+        list.handle(&transfer(
+            7510,
+            Some(42),
+            payload("uavcan.node.port.List", &port_list),
+        ))
+        .unwrap();
+
+        assert_eq!(vec![&42, &43], list.nodes().keys().collect::<Vec<_>>());
+        assert_eq!(
+            Some(&PortList {
+                publishers: SubjectSet::Some(vec![7509, 7510]),
+                subscribers: SubjectSet::All,
+                clients: vec![],
+                servers: vec![384, 430],
+            }),
+            list.nodes()[&42].ports.as_ref()
+        );
+        list.nodes.get_mut(&42).unwrap().name = Some("org.example.motor".into());
+        list.nodes.get_mut(&42).unwrap().software_version = Some((1, 3));
+        assert_eq!(
+            "NODE  NAME               VERSION  HEALTH   MODE         UPTIME       PORTS\n\
+             42    org.example.motor  1.3      nominal  operational  0:12:07      pub 7509,7510; sub all; srv 384,430\n\
+             43    ?                  ?        warning  maintenance  3d 01:00:02  ?",
+            list.format_table()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Measuring the round-trip time of service requests
+//!
+//! [`ping`] repeatedly sends a request (usually `uavcan.node.GetInfo`, which all nodes support)
+//! to a server node and waits for each response. It prints a line for each request and then a
+//! summary:
+//!
+//! ```text
+//! Response from node 42: seq=0 time=1.204 ms
+//! Response from node 42: seq=1 time=1.187 ms
+//! No response from node 42: seq=2
+//! ...
+//! 10 requests, 9 responses, 10.0% loss
+//! Round-trip time min/avg/max/jitter = 1.118/1.201/1.342/0.045 ms
+//! ```
+//!
+//! The jitter is the average difference between the round-trip times of consecutive
+//! responses.
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use canadensis_core::Priority;
+use canadensis_dynamic::registry::TypeRegistry;
+
+use crate::bus::Bus;
+use crate::call::{call, CallError};
+use crate::types::Port;
+
+/// Options for [`ping`]
+#[derive(Debug, Clone)]
+pub struct PingOptions {
+    /// The number of requests to send
+    pub count: u32,
+    /// The time from the start of one request to the start of the next
+    pub interval: Duration,
+    /// The time to wait for each response
+    pub timeout: Duration,
+    /// The priority of the requests
+    pub priority: Priority,
+}
+
+/// Round-trip time statistics
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PingStats {
+    /// The number of requests sent
+    pub sent: u32,
+    /// The round-trip time of each response, in the order that the requests were sent
+    pub round_trip_times: Vec<Duration>,
+}
+
+impl PingStats {
+    /// Returns the number of responses received
+    pub fn received(&self) -> u32 {
+        self.round_trip_times.len() as u32
+    }
+
+    /// Returns the fraction of requests (0 to 1) that did not get a response
+    pub fn loss(&self) -> f64 {
+        if self.sent == 0 {
+            0.0
+        } else {
+            f64::from(self.sent - self.received()) / f64::from(self.sent)
+        }
+    }
+
+    /// Returns the shortest round-trip time
+    pub fn min(&self) -> Option<Duration> {
+        self.round_trip_times.iter().copied().min()
+    }
+
+    /// Returns the longest round-trip time
+    pub fn max(&self) -> Option<Duration> {
+        self.round_trip_times.iter().copied().max()
+    }
+
+    /// Returns the average round-trip time
+    pub fn mean(&self) -> Option<Duration> {
+        if self.round_trip_times.is_empty() {
+            None
+        } else {
+            Some(self.round_trip_times.iter().sum::<Duration>() / self.received())
+        }
+    }
+
+    /// Returns the average absolute difference between the round-trip times of consecutive
+    /// responses, or None if there are fewer than two responses
+    pub fn jitter(&self) -> Option<Duration> {
+        let differences = self
+            .round_trip_times
+            .windows(2)
+            .map(|pair| pair[1].abs_diff(pair[0]))
+            .collect::<Vec<Duration>>();
+        if differences.is_empty() {
+            None
+        } else {
+            Some(differences.iter().sum::<Duration>() / differences.len() as u32)
+        }
+    }
+
+    /// Formats the number of requests and responses and the round-trip times
+    pub fn format_summary(&self) -> String {
+        let mut summary = format!(
+            "{} requests, {} responses, {:.1}% loss",
+            self.sent,
+            self.received(),
+            self.loss() * 100.0
+        );
+        if let (Some(min), Some(mean), Some(max)) = (self.min(), self.mean(), self.max()) {
+            summary.push_str(&format!(
+                "\nRound-trip time min/avg/max/jitter = {}/{}/{}/{} ms",
+                milliseconds(min),
+                milliseconds(mean),
+                milliseconds(max),
+                milliseconds(self.jitter().unwrap_or_default())
+            ));
+        }
+        summary
+    }
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+/// Sends requests to a server node, waits for the responses, and prints the round-trip time of
+/// each request
+///
+/// Requests without a response before the timeout count as lost. Other errors stop the pings.
+pub fn ping<W: Write>(
+    bus: &mut dyn Bus,
+    registry: &TypeRegistry,
+    service: &Port,
+    server: u16,
+    payload: &[u8],
+    options: &PingOptions,
+    mut output: W,
+) -> Result<PingStats, PingError> {
+    let mut stats = PingStats::default();
+    for sequence in 0..options.count {
+        let start = Instant::now();
+        stats.sent += 1;
+        match call(
+            bus,
+            registry,
+            service,
+            server,
+            payload,
+            options.priority,
+            options.timeout,
+        ) {
+            Ok(_) => {
+                let time = start.elapsed();
+                stats.round_trip_times.push(time);
+                writeln!(
+                    output,
+                    "Response from node {}: seq={} time={} ms",
+                    server,
+                    sequence,
+                    milliseconds(time)
+                )?;
+            }
+            Err(CallError::Timeout { .. }) => {
+                writeln!(output, "No response from node {}: seq={}", server, sequence)?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        if sequence + 1 != options.count {
+            if let Some(remaining) = options.interval.checked_sub(start.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Errors that can occur when sending pings
+#[derive(thiserror::Error, Debug)]
+pub enum PingError {
+    /// A request could not be sent or a response could not be received
+    #[error(transparent)]
+    Call(#[from] CallError),
+    /// The output could not be written
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ping, PingOptions, PingStats};
+    use crate::bus::ReceivedTransfer;
+    use crate::call::request_payload;
+    use crate::mock_bus::{response_to, MockBus, Server};
+    use crate::types::{find_service, standard_registry};
+    use canadensis_core::Priority;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    /// A server that responds to every other request
+    struct PingServer;
+
+    impl Server for PingServer {
+        fn request(
+            &mut self,
+            request: &ReceivedTransfer,
+            received: &mut VecDeque<ReceivedTransfer>,
+        ) {
+            if request.transfer_id.is_multiple_of(2) {
+                // A GetInfo response with all fields zero or empty
+                received.push_back(response_to(request, vec![0; 33]));
+            }
+        }
+    }
+
+    #[test]
+    fn ping_node() {
+        let registry = standard_registry();
+        let service = find_service(&registry, "uavcan.node.GetInfo").unwrap();
+        let payload = request_payload(&registry, &service, "").unwrap();
+        let mut bus = MockBus::new(PingServer);
+        let options = PingOptions {
+            count: 4,
+            interval: Duration::from_millis(1),
+            timeout: Duration::from_millis(20),
+            priority: Priority::Nominal,
+        };
+        let mut output = Vec::new();
+        let stats = ping(
+            &mut bus,
+            &registry,
+            &service,
+            42,
+            &payload,
+            &options,
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(4, stats.sent);
+        assert_eq!(4, bus.requests.len());
+        assert_eq!(2, stats.received());
+        assert_eq!(0.5, stats.loss());
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with("Response from node 42: seq=0 time="));
+        assert_eq!("No response from node 42: seq=1", lines[1]);
+        assert!(lines[2].starts_with("Response from node 42: seq=2 time="));
+        assert_eq!("No response from node 42: seq=3", lines[3]);
+    }
+
+    #[test]
+    fn statistics() {
+        let stats = PingStats {
+            sent: 5,
+            round_trip_times: [1, 4, 2, 3]
+                .iter()
+                .map(|&ms| Duration::from_millis(ms))
+                .collect(),
+        };
+        assert_eq!(Some(Duration::from_millis(1)), stats.min());
+        assert_eq!(Some(Duration::from_millis(4)), stats.max());
+        assert_eq!(Some(Duration::from_micros(2500)), stats.mean());
+        assert_eq!(Some(Duration::from_millis(2)), stats.jitter());
+        assert_eq!(
+            "5 requests, 4 responses, 20.0% loss\n\
+            Round-trip time min/avg/max/jitter = 1.000/2.500/4.000/2.000 ms",
+            stats.format_summary()
+        );
+
+        let lost = PingStats {
+            sent: 2,
+            round_trip_times: vec![],
+        };
+        assert_eq!(None, lost.mean());
+        assert_eq!(
+            "2 requests, 0 responses, 100.0% loss",
+            lost.format_summary()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Publishing messages that are written as YAML or JSON
+//!
+//! The message is converted into a value of the subject's type with
+//! [`from_yaml`](canadensis_dynamic::yaml::from_yaml), so fields that are left out get their
+//! default values. JSON is also accepted because YAML flow mappings and sequences can be
+//! written like JSON.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use canadensis_core::Priority;
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::yaml::{from_yaml, YamlError};
+
+use crate::bus::{Bus, BusError};
+use crate::types::Port;
+
+/// Serializes a message, written as YAML or JSON, into a payload for a subject
+pub fn message_payload(
+    registry: &TypeRegistry,
+    subject: &Port,
+    text: &str,
+) -> Result<Vec<u8>, PublishError> {
+    let message = registry.message(&subject.key, PayloadKind::Message)?;
+    let value = from_yaml(message, text).map_err(|inner| PublishError::Message {
+        key: subject.key.to_string(),
+        inner,
+    })?;
+    Ok(registry.serialize(&subject.key, PayloadKind::Message, &value)?)
+}
+
+/// How often and how many times to publish a message
+#[derive(Debug, Clone)]
+pub struct PublishOptions {
+    /// The time between messages
+    pub period: Duration,
+    /// The number of messages to publish, or None to publish until an error occurs
+    pub count: Option<usize>,
+    /// The priority of the messages
+    pub priority: Priority,
+}
+
+/// Publishes the same payload repeatedly
+///
+/// The first message is published immediately. Each message has the next transfer ID, so
+/// receivers handle them as separate messages.
+pub fn run(
+    bus: &mut dyn Bus,
+    subject: u16,
+    payload: &[u8],
+    options: &PublishOptions,
+) -> Result<(), BusError> {
+    let mut next_time = Instant::now();
+    let mut remaining = options.count;
+    while remaining != Some(0) {
+        bus.publish(subject, options.priority, payload)?;
+        if let Some(remaining) = &mut remaining {
+            *remaining -= 1;
+            if *remaining == 0 {
+                break;
+            }
+        }
+        // Schedule from the previous time instead of now, so the rate does not drift
+        next_time += options.period;
+        let now = Instant::now();
+        if next_time > now {
+            thread::sleep(next_time - now);
+        }
+    }
+    Ok(())
+}
+
+/// Errors that can occur when preparing a message to publish
+#[derive(thiserror::Error, Debug)]
+pub enum PublishError {
+    /// The message text was not valid for the type
+    #[error("Invalid message for {key}")]
+    Message {
+        /// The message type
+        key: String,
+        /// The problem with the text
+        #[source]
+        inner: YamlError,
+    },
+    /// The type was not a message type
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{message_payload, PublishError};
+    use crate::types::find_subject;
+    use canadensis_dsdl_frontend::{Config, Package};
+    use canadensis_dynamic::registry::TypeRegistry;
+
+    #[test]
+    fn payload_from_yaml() {
+        let mut package = Package::new();
+        package
+            .add_string(
+                Some(100),
+                "test.Point.1.0".parse().unwrap(),
+                "int16 x\nint16 y\n@sealed\n".into(),
+            )
+            .unwrap();
+        let registry = TypeRegistry::new(package.compile(&Config::default()).unwrap());
+        let subject = find_subject(&registry, "test.Point").unwrap();
+
+        let payload = [0x01, 0x00, 0xff, 0xff];
+        assert_eq!(
+            payload,
+            &message_payload(&registry, &subject, "x: 1\ny: -1\n").unwrap()[..]
+        );
+        assert_eq!(
+            payload,
+            &message_payload(&registry, &subject, r#"{"x": 1, "y": -1}"#).unwrap()[..]
+        );
+        assert_eq!(
+            [0x01, 0x00, 0x00, 0x00],
+            &message_payload(&registry, &subject, "{x: 1}").unwrap()[..]
+        );
+        assert!(matches!(
+            message_payload(&registry, &subject, "z: 1"),
+            Err(PublishError::Message { .. })
+        ));
+    }
+}
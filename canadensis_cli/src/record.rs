@@ -0,0 +1,521 @@
+//! Subscribing to subjects and appending the messages received on them to CSV files
+//!
+//! Each subject has its own file, named with the subject ID and type like
+//! `7509_uavcan.node.Heartbeat.1.0.csv`. The first line has the column names, and each message
+//! is one row:
+//!
+//! ```text
+//! ts_system,ts_monotonic,source_node_id,transfer_id,priority,uptime,health.value,mode.value,vendor_specific_status_code
+//! 1700000000.123456,12.345678,42,17,nominal,17,0,0,0
+//! ```
+//!
+//! The columns after the metadata come from the fields of the type:
+//!
+//! * Each number or boolean is one column, and the names of nested fields are joined with `.`
+//! * Each element of a fixed-length array has its own columns, like `position[0]`
+//! * A string is one column
+//! * A variable-length array or a `byte` array is one column, written as a JSON array
+//! * A union has a column with the name of the active variant (named `_variant_` if the message
+//!   type is a union), followed by the columns of all its variants. The columns of the inactive
+//!   variants are empty.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use canadensis_dsdl_frontend::compiled::{FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use canadensis_dsdl_frontend::TypeKey;
+use canadensis_dynamic::json::to_json;
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::Value;
+
+use crate::bus::{priority_name, Bus, BusError, ReceivedTransfer};
+use crate::types::{receive_size, Port};
+
+/// The names of the columns with the transfer metadata, which come before the message fields
+const META_COLUMNS: [&str; 5] = [
+    "ts_system",
+    "ts_monotonic",
+    "source_node_id",
+    "transfer_id",
+    "priority",
+];
+
+/// Receives messages on some subjects and formats them as CSV rows
+pub struct Recorder<'r> {
+    registry: &'r TypeRegistry,
+    subjects: BTreeMap<u16, Table>,
+}
+
+/// The type and columns of one subject
+struct Table {
+    key: TypeKey,
+    layout: Layout,
+    header: String,
+}
+
+impl<'r> Recorder<'r> {
+    /// Creates a recorder for some subjects
+    ///
+    /// If the same subject ID appears more than once, the last type is used.
+    pub fn new<I>(registry: &'r TypeRegistry, subjects: I) -> Result<Self, RegistryError>
+    where
+        I: IntoIterator<Item = Port>,
+    {
+        let subjects = subjects
+            .into_iter()
+            .map(|port| {
+                let message = registry.message(&port.key, PayloadKind::Message)?;
+                let mut columns: Vec<String> =
+                    META_COLUMNS.iter().map(|&name| name.to_owned()).collect();
+                let layout = message_layout(message, "", &mut columns);
+                let mut header = String::new();
+                for (i, column) in columns.iter().enumerate() {
+                    if i != 0 {
+                        header.push(',');
+                    }
+                    push_field(&mut header, column);
+                }
+                Ok((
+                    port.port_id,
+                    Table {
+                        key: port.key,
+                        layout,
+                        header,
+                    },
+                ))
+            })
+            .collect::<Result<_, RegistryError>>()?;
+        Ok(Recorder { registry, subjects })
+    }
+
+    /// Subscribes to all the subjects on a bus
+    pub fn subscribe(&self, bus: &mut dyn Bus) -> Result<(), Box<dyn std::error::Error>> {
+        for (&subject, table) in &self.subjects {
+            let message = self.registry.message(&table.key, PayloadKind::Message)?;
+            bus.subscribe(PayloadKind::Message, subject, receive_size(message))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the file for a subject, or None if the subject is not recorded
+    pub fn file_name(&self, subject: u16) -> Option<String> {
+        self.subjects
+            .get(&subject)
+            .map(|table| format!("{}_{}.csv", subject, table.key))
+    }
+
+    /// Returns the line with the column names for a subject (without a newline at the end),
+    /// or None if the subject is not recorded
+    pub fn header(&self, subject: u16) -> Option<&str> {
+        self.subjects
+            .get(&subject)
+            .map(|table| table.header.as_str())
+    }
+
+    /// Formats a received message as a CSV row (without a newline at the end)
+    ///
+    /// This returns Ok(None) if the transfer is not a message on one of the subjects.
+    pub fn row(&self, transfer: &ReceivedTransfer) -> Result<Option<String>, RegistryError> {
+        if transfer.kind != PayloadKind::Message {
+            return Ok(None);
+        }
+        let table = match self.subjects.get(&transfer.port_id) {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+        let value =
+            self.registry
+                .deserialize(&table.key, PayloadKind::Message, &transfer.payload)?;
+
+        let ts_system = transfer
+            .system_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let mut cells = vec![
+            format!("{}.{:06}", ts_system.as_secs(), ts_system.subsec_micros()),
+            format!(
+                "{}.{:06}",
+                transfer.timestamp.ticks() / 1_000_000,
+                transfer.timestamp.ticks() % 1_000_000
+            ),
+            transfer
+                .source
+                .map(|source| source.to_string())
+                .unwrap_or_default(),
+            transfer.transfer_id.to_string(),
+            priority_name(transfer.priority).to_owned(),
+        ];
+        push_cells(&table.layout, &value, &mut cells);
+
+        let mut row = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i != 0 {
+                row.push(',');
+            }
+            push_field(&mut row, cell);
+        }
+        Ok(Some(row))
+    }
+
+    /// Receives messages and appends them to files in `directory`
+    ///
+    /// Files that do not exist are created with a line of column names. Files that already
+    /// exist must have the same column names.
+    ///
+    /// This returns after `count` messages, or runs until an error occurs if `count` is None.
+    /// Messages that can't be deserialized are reported on standard error and skipped.
+    pub fn run(
+        &self,
+        bus: &mut dyn Bus,
+        directory: &Path,
+        count: Option<usize>,
+    ) -> Result<(), RecordError> {
+        let mut files = BTreeMap::new();
+        for (&subject, table) in &self.subjects {
+            let path = directory.join(self.file_name(subject).expect("No file name"));
+            files.insert(subject, open_table(path, &table.header)?);
+        }
+
+        let mut remaining = count;
+        while remaining != Some(0) {
+            let transfer = match bus.receive()? {
+                Some(transfer) => transfer,
+                None => continue,
+            };
+            match self.row(&transfer) {
+                Ok(Some(row)) => {
+                    let file = files.get_mut(&transfer.port_id).expect("No file");
+                    writeln!(file, "{}", row)?;
+                    if let Some(remaining) = &mut remaining {
+                        *remaining -= 1;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "Invalid message on subject {} from node {:?}: {}",
+                    transfer.port_id, transfer.source, e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Opens a file for appending, and writes the column names if the file is empty
+fn open_table(path: PathBuf, header: &str) -> Result<File, RecordError> {
+    let result = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(&path)
+        .and_then(|mut file| {
+            let mut first_line = String::new();
+            BufReader::new(&file).read_line(&mut first_line)?;
+            if first_line.is_empty() {
+                writeln!(file, "{}", header)?;
+            }
+            Ok((file, first_line))
+        });
+    match result {
+        Ok((file, first_line)) => {
+            if first_line.is_empty() || first_line.trim_end_matches(['\r', '\n']) == header {
+                Ok(file)
+            } else {
+                Err(RecordError::Columns { path })
+            }
+        }
+        Err(inner) => Err(RecordError::Open { path, inner }),
+    }
+}
+
+/// How the fields of a value are arranged in columns
+enum Layout {
+    /// A number or boolean in one column
+    Scalar,
+    /// A string, `byte` array, or variable-length array in one column
+    Text,
+    /// The fields of a struct, not including padding
+    Struct(Vec<Layout>),
+    /// The elements of a fixed-length array
+    Array(Vec<Layout>),
+    /// The name of the active variant of a union in one column, followed by the name and layout
+    /// of each variant
+    Union(Vec<(String, Layout)>),
+}
+
+impl Layout {
+    /// Returns the number of columns
+    fn width(&self) -> usize {
+        match self {
+            Layout::Scalar | Layout::Text => 1,
+            Layout::Struct(layouts) | Layout::Array(layouts) => {
+                layouts.iter().map(Layout::width).sum()
+            }
+            Layout::Union(variants) => {
+                1 + variants
+                    .iter()
+                    .map(|(_, layout)| layout.width())
+                    .sum::<usize>()
+            }
+        }
+    }
+}
+
+/// Returns the layout of a message type, and appends its column names to `columns`
+///
+/// The column names start with `prefix`, which is empty for the top-level message.
+fn message_layout(message: &Message, prefix: &str, columns: &mut Vec<String>) -> Layout {
+    match message.kind() {
+        MessageKind::Struct(cyphal_struct) => Layout::Struct(
+            cyphal_struct
+                .fields
+                .iter()
+                .filter_map(|field| match field.kind() {
+                    FieldKind::Padding(_) => None,
+                    FieldKind::Data { ty, name } => {
+                        Some(type_layout(ty, &join(prefix, name), columns))
+                    }
+                })
+                .collect(),
+        ),
+        MessageKind::Union(union) => {
+            columns.push(if prefix.is_empty() {
+                "_variant_".to_owned()
+            } else {
+                prefix.to_owned()
+            });
+            Layout::Union(
+                union
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let path = join(prefix, variant.name());
+                        (
+                            variant.name().to_owned(),
+                            type_layout(variant.ty(), &path, columns),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
+
+fn type_layout(ty: &ResolvedType, path: &str, columns: &mut Vec<String>) -> Layout {
+    match ty {
+        ResolvedType::Scalar(scalar) => scalar_layout(scalar, path, columns),
+        ResolvedType::FixedArray { inner, len } if !is_text(inner) => Layout::Array(
+            (0..*len)
+                .map(|i| scalar_layout(inner, &format!("{}[{}]", path, i), columns))
+                .collect(),
+        ),
+        ResolvedType::FixedArray { .. } | ResolvedType::VariableArray { .. } => {
+            columns.push(path.to_owned());
+            Layout::Text
+        }
+    }
+}
+
+fn scalar_layout(scalar: &ResolvedScalarType, path: &str, columns: &mut Vec<String>) -> Layout {
+    match scalar {
+        ResolvedScalarType::Composite { inner, .. } => message_layout(inner, path, columns),
+        ResolvedScalarType::Primitive(_) => {
+            columns.push(path.to_owned());
+            Layout::Scalar
+        }
+        ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+    }
+}
+
+/// Returns true if arrays of a type are strings or byte strings
+fn is_text(scalar: &ResolvedScalarType) -> bool {
+    matches!(
+        scalar,
+        ResolvedScalarType::Primitive(PrimitiveType::Utf8 | PrimitiveType::Byte)
+    )
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// Appends the cells for a value to `cells`
+fn push_cells(layout: &Layout, value: &Value, cells: &mut Vec<String>) {
+    match (layout, value) {
+        (Layout::Scalar, Value::Bool(value)) => cells.push(value.to_string()),
+        (Layout::Scalar, Value::Int(value)) => cells.push(value.to_string()),
+        (Layout::Scalar, Value::UInt(value)) => cells.push(value.to_string()),
+        (Layout::Scalar, Value::Float(value)) => cells.push(value.to_string()),
+        (Layout::Text, Value::String(value)) => cells.push(value.clone()),
+        (Layout::Text, value) => cells.push(to_json(value).to_string()),
+        (Layout::Struct(layouts), Value::Struct(fields)) if layouts.len() == fields.len() => {
+            for (layout, (_, value)) in layouts.iter().zip(fields) {
+                push_cells(layout, value, cells);
+            }
+        }
+        (Layout::Array(layouts), Value::Array(values)) if layouts.len() == values.len() => {
+            for (layout, value) in layouts.iter().zip(values) {
+                push_cells(layout, value, cells);
+            }
+        }
+        (Layout::Union(variants), Value::Union { variant, value }) => {
+            cells.push(variant.clone());
+            for (name, layout) in variants {
+                if name == variant {
+                    push_cells(layout, value, cells);
+                } else {
+                    push_empty(layout, cells);
+                }
+            }
+        }
+        // Deserialized values always match their types, so this does not normally happen
+        (layout, _) => push_empty(layout, cells),
+    }
+}
+
+fn push_empty(layout: &Layout, cells: &mut Vec<String>) {
+    cells.extend((0..layout.width()).map(|_| String::new()));
+}
+
+/// Appends a CSV field to a line, with quotation marks if required
+fn push_field(line: &mut String, field: &str) {
+    if field.contains([',', '"', '\r', '\n']) {
+        line.push('"');
+        line.push_str(&field.replace('"', "\"\""));
+        line.push('"');
+    } else {
+        line.push_str(field);
+    }
+}
+
+/// Errors that can occur while recording subjects
+#[derive(thiserror::Error, Debug)]
+pub enum RecordError {
+    /// Receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+    /// A file could not be opened or created
+    #[error("Can't open {}", path.display())]
+    Open {
+        /// The path to the file
+        path: PathBuf,
+        /// The reason
+        #[source]
+        inner: io::Error,
+    },
+    /// A file already exists and has different columns
+    #[error(
+        "{} already exists and has different columns, possibly from another data type",
+        path.display()
+    )]
+    Columns {
+        /// The path to the file
+        path: PathBuf,
+    },
+    /// Writing a file failed
+    #[error("Failed to write output")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::Recorder;
+    use crate::bus::ReceivedTransfer;
+    use crate::types::find_subject;
+    use canadensis_core::time::Microseconds32;
+    use canadensis_core::Priority;
+    use canadensis_dsdl_frontend::{Config, Package};
+    use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn registry() -> TypeRegistry {
+        let mut package = Package::new();
+        package
+            .add_string(
+                None,
+                "test.Mode.1.0".parse().unwrap(),
+                "@union\nuint8 idle\nfloat32[2] speed\n@sealed\n".into(),
+            )
+            .unwrap();
+        package
+            .add_string(
+                Some(100),
+                "test.Status.1.0".parse().unwrap(),
+                "bool ok\nvoid7\nint16[2] position\ntest.Mode.1.0 mode\nutf8[<=16] name\n\
+                uint8[<=4] data\n@sealed\n"
+                    .into(),
+            )
+            .unwrap();
+        let config = Config {
+            allow_utf8_and_byte: true,
+            ..Config::default()
+        };
+        TypeRegistry::new(package.compile(&config).unwrap())
+    }
+
+    fn transfer(port_id: u16, payload: Vec<u8>) -> ReceivedTransfer {
+        ReceivedTransfer {
+            kind: PayloadKind::Message,
+            port_id,
+            source: Some(42),
+            destination: None,
+            transfer_id: 3,
+            priority: Priority::Nominal,
+            timestamp: Microseconds32::from_ticks(1_500_000),
+            system_time: UNIX_EPOCH + Duration::from_micros(1_700_000_000_000_250),
+            payload,
+        }
+    }
+
+    #[test]
+    fn format_rows() {
+        let registry = registry();
+        let subjects = vec![find_subject(&registry, "test.Status").unwrap()];
+        let recorder = Recorder::new(&registry, subjects).unwrap();
+
+        assert_eq!(
+            Some("100_test.Status.1.0.csv".to_owned()),
+            recorder.file_name(100)
+        );
+        assert_eq!(
+            Some(
+                "ts_system,ts_monotonic,source_node_id,transfer_id,priority,ok,position[0],\
+                position[1],mode,mode.idle,mode.speed[0],mode.speed[1],name,data"
+            ),
+            recorder.header(100)
+        );
+        assert_eq!(None, recorder.header(101));
+
+        let mut payload = vec![0x01, 0x05, 0x00, 0xfe, 0xff];
+        // mode.speed = [1.5, -2.0]
+        payload.push(1);
+        payload.extend_from_slice(&1.5_f32.to_le_bytes());
+        payload.extend_from_slice(&(-2.0_f32).to_le_bytes());
+        // name = "a, \"b\""
+        payload.push(6);
+        payload.extend_from_slice(b"a, \"b\"");
+        // data = [1, 2]
+        payload.extend_from_slice(&[2, 1, 2]);
+        assert_eq!(
+            "1700000000.000250,1.500000,42,3,nominal,true,5,-2,speed,,1.5,-2,\"a, \"\"b\"\"\",\
+            \"[1,2]\"",
+            recorder.row(&transfer(100, payload)).unwrap().unwrap()
+        );
+
+        let mut anonymous = transfer(100, vec![0x00, 0, 0, 0, 0, 0, 7, 0, 0]);
+        anonymous.source = None;
+        assert_eq!(
+            "1700000000.000250,1.500000,,3,nominal,false,0,0,idle,7,,,,[]",
+            recorder.row(&anonymous).unwrap().unwrap()
+        );
+        assert_eq!(None, recorder.row(&transfer(101, vec![])).unwrap());
+    }
+}
@@ -0,0 +1,520 @@
+//! Listing, reading, and writing the registers of other nodes
+//!
+//! A [`RegisterClient`] uses the `uavcan.register.List` and `uavcan.register.Access` services.
+//! Register values are shown in a plain form, like `yakut` does: a string register is a
+//! string, an array with one element is that element, and other arrays are sequences. A set of
+//! registers is a YAML mapping from register names to plain values:
+//!
+//! ```text
+//! uavcan.node.id: 42
+//! uavcan.node.description: Left motor
+//! uavcan.pub.measurement.id: 1000
+//! gains: [0.5, 0.25, 0]
+//! ```
+//!
+//! When a plain value is written to a register, the client reads the register first and
+//! converts the value to the register's current type. A value can also be written with its
+//! type, like `{natural16: {value: [1, 2]}}`, which is used without conversion.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use canadensis_core::Priority;
+use canadensis_dynamic::json::{from_json, JsonError};
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::yaml::{parse_yaml, to_yaml, YamlConfig, YamlError};
+use canadensis_dynamic::Value;
+use serde_json::json;
+
+use crate::bus::Bus;
+use crate::call::{call, CallError};
+use crate::types::{field, find_service, uint8_text, Port};
+
+/// Lists, reads, and writes the registers of one node
+pub struct RegisterClient<'a> {
+    bus: &'a mut dyn Bus,
+    registry: &'a TypeRegistry,
+    server: u16,
+    priority: Priority,
+    timeout: Duration,
+    list: Port,
+    access: Port,
+}
+
+/// The state of a register
+#[derive(Debug, Clone, PartialEq)]
+pub struct Register {
+    /// If the register can be written
+    pub mutable: bool,
+    /// If the register keeps its value when the node restarts
+    pub persistent: bool,
+    /// The value, of type `uavcan.register.Value`
+    pub value: Value,
+}
+
+impl<'a> RegisterClient<'a> {
+    /// Creates a client for the registers of a server node
+    ///
+    /// Each request has the provided priority, and each request fails if the server does not
+    /// respond within `timeout`.
+    pub fn new(
+        bus: &'a mut dyn Bus,
+        registry: &'a TypeRegistry,
+        server: u16,
+        priority: Priority,
+        timeout: Duration,
+    ) -> Result<Self, RegisterError> {
+        let list =
+            find_service(registry, "uavcan.register.List.1").map_err(RegisterError::Types)?;
+        let access =
+            find_service(registry, "uavcan.register.Access.1").map_err(RegisterError::Types)?;
+        Ok(RegisterClient {
+            bus,
+            registry,
+            server,
+            priority,
+            timeout,
+            list,
+            access,
+        })
+    }
+
+    /// Returns the names of all registers, in the order that the server lists them
+    pub fn list(&mut self) -> Result<Vec<String>, RegisterError> {
+        let mut names = Vec::new();
+        for index in 0..=u16::MAX {
+            let response = self.call(&self.list.clone(), &json!({ "index": index }))?;
+            let name = field(&response, "name")
+                .and_then(|name| field(name, "name"))
+                .and_then(uint8_text)
+                .unwrap_or_default();
+            if name.is_empty() {
+                break;
+            }
+            names.push(name);
+        }
+        Ok(names)
+    }
+
+    /// Reads a register
+    pub fn read(&mut self, name: &str) -> Result<Register, RegisterError> {
+        self.access(name, &json!({ "empty": {} }))
+    }
+
+    /// Writes a value, in the plain form or with its type, to a register and returns the
+    /// new state of the register
+    ///
+    /// This returns an error if the register is not mutable or the server did not accept the
+    /// value.
+    pub fn write(
+        &mut self,
+        name: &str,
+        value: &serde_json::Value,
+    ) -> Result<Register, RegisterError> {
+        let typed = if value.is_object() {
+            value.clone()
+        } else {
+            typed_value(name, value, &self.read(name)?.value)?
+        };
+        let requested = self.register_value(name, &typed)?;
+        let register = self.access(name, &typed)?;
+        if !register.mutable {
+            return Err(RegisterError::Immutable {
+                name: name.to_owned(),
+            });
+        }
+        let value_key = self.value_key()?;
+        let value_payload = |value| {
+            self.registry
+                .serialize(&value_key, PayloadKind::Message, value)
+        };
+        if value_payload(&requested)? != value_payload(&register.value)? {
+            return Err(RegisterError::Rejected {
+                name: name.to_owned(),
+                value: to_yaml(&plain_value(&register.value), &YamlConfig::default()),
+            });
+        }
+        Ok(register)
+    }
+
+    /// Sends an access request and returns the register state from the response
+    fn access(&mut self, name: &str, value: &serde_json::Value) -> Result<Register, RegisterError> {
+        let request = json!({ "name": { "name": name }, "value": value });
+        let response = self.call(&self.access.clone(), &request)?;
+        let flag = |name| matches!(field(&response, name), Some(Value::Bool(true)));
+        let register = Register {
+            mutable: flag("mutable"),
+            persistent: flag("persistent"),
+            value: field(&response, "value")
+                .cloned()
+                .expect("No value in register access response"),
+        };
+        if variant(&register.value) == Some("empty") {
+            return Err(RegisterError::NotFound {
+                name: name.to_owned(),
+            });
+        }
+        Ok(register)
+    }
+
+    fn call(
+        &mut self,
+        service: &Port,
+        request: &serde_json::Value,
+    ) -> Result<Value, RegisterError> {
+        let request = from_json(
+            self.registry.message(&service.key, PayloadKind::Request)?,
+            request,
+        )?;
+        let payload = self
+            .registry
+            .serialize(&service.key, PayloadKind::Request, &request)?;
+        Ok(call(
+            &mut *self.bus,
+            self.registry,
+            service,
+            self.server,
+            &payload,
+            self.priority,
+            self.timeout,
+        )?)
+    }
+
+    /// Converts a typed register value from JSON
+    fn register_value(
+        &self,
+        name: &str,
+        typed: &serde_json::Value,
+    ) -> Result<Value, RegisterError> {
+        from_json(
+            self.registry
+                .message(&self.value_key()?, PayloadKind::Message)?,
+            typed,
+        )
+        .map_err(|inner| RegisterError::Value {
+            name: name.to_owned(),
+            inner,
+        })
+    }
+
+    fn value_key(&self) -> Result<canadensis_dsdl_frontend::TypeKey, RegisterError> {
+        self.registry
+            .find("uavcan.register.Value.1")
+            .cloned()
+            .ok_or_else(|| RegisterError::Types("Type uavcan.register.Value.1 not found".into()))
+    }
+}
+
+/// Returns the name of the active variant of a union
+fn variant(value: &Value) -> Option<&str> {
+    match value {
+        Value::Union { variant, .. } => Some(variant),
+        _ => None,
+    }
+}
+
+/// Converts a `uavcan.register.Value` into its plain form
+pub fn plain_value(value: &Value) -> Value {
+    let (variant, inner) = match value {
+        Value::Union { variant, value } => match field(value, "value") {
+            Some(inner) => (variant.as_str(), inner),
+            None => return Value::Struct(Vec::new()),
+        },
+        _ => return value.clone(),
+    };
+    match (variant, inner) {
+        ("string", Value::Array(elements)) => {
+            let bytes = elements
+                .iter()
+                .map(|element| match element {
+                    Value::UInt(byte) => u8::try_from(*byte).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<u8>>>();
+            match bytes.and_then(|bytes| String::from_utf8(bytes).ok()) {
+                Some(text) => Value::String(text),
+                None => inner.clone(),
+            }
+        }
+        ("unstructured", _) => inner.clone(),
+        (_, Value::Array(elements)) if elements.len() == 1 => elements[0].clone(),
+        _ => inner.clone(),
+    }
+}
+
+/// Converts a plain value into a typed value (as JSON) with the same type as the current
+/// value of a register
+fn typed_value(
+    name: &str,
+    plain: &serde_json::Value,
+    current: &Value,
+) -> Result<serde_json::Value, RegisterError> {
+    let variant = variant(current).expect("Register value is not a union");
+    let value = match (variant, plain) {
+        ("string", serde_json::Value::String(_))
+        | ("unstructured", serde_json::Value::String(_))
+        | (_, serde_json::Value::Array(_)) => plain.clone(),
+        ("string", _) | ("unstructured", _) => {
+            return Err(RegisterError::Mismatch {
+                name: name.to_owned(),
+                variant: variant.to_owned(),
+                value: plain.clone(),
+            })
+        }
+        _ => serde_json::Value::Array(vec![plain.clone()]),
+    };
+    Ok(json!({ variant: { "value": value } }))
+}
+
+/// Formats registers as a YAML mapping from names to plain values
+pub fn format_registers<'v, I>(registers: I) -> String
+where
+    I: IntoIterator<Item = (&'v str, &'v Value)>,
+{
+    let mapping = Value::Struct(
+        registers
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), plain_value(value)))
+            .collect(),
+    );
+    to_yaml(&mapping, &YamlConfig::default())
+}
+
+/// Parses a YAML mapping from register names to values, in the plain form or with their types
+///
+/// The registers are returned in alphabetical order.
+pub fn parse_registers(text: &str) -> Result<Vec<(String, serde_json::Value)>, RegisterError> {
+    match parse_yaml(text)? {
+        serde_json::Value::Object(registers) => Ok(registers.into_iter().collect()),
+        serde_json::Value::Null => Ok(Vec::new()),
+        _ => Err(RegisterError::NotMapping),
+    }
+}
+
+/// Errors that can occur when accessing registers
+#[derive(thiserror::Error, Debug)]
+pub enum RegisterError {
+    /// The register service types could not be found
+    #[error("{0}")]
+    Types(String),
+    /// A request failed
+    #[error(transparent)]
+    Call(#[from] CallError),
+    /// A request could not be serialized or a response could not be deserialized
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    /// A request could not be created
+    #[error(transparent)]
+    Json(#[from] JsonError),
+    /// A register file or value was not valid YAML
+    #[error(transparent)]
+    Yaml(#[from] YamlError),
+    /// A register file was not a mapping from names to values
+    #[error("Registers must be a mapping from register names to values")]
+    NotMapping,
+    /// A value could not be converted into a register value
+    #[error("Invalid value for register {name}")]
+    Value {
+        /// The register name
+        name: String,
+        /// The problem with the value
+        #[source]
+        inner: JsonError,
+    },
+    /// A plain value could not be converted to the type of a register
+    #[error("Register {name} has type {variant}, which can't have the value {value}")]
+    Mismatch {
+        /// The register name
+        name: String,
+        /// The type of the register
+        variant: String,
+        /// The value
+        value: serde_json::Value,
+    },
+    /// The server does not have a register with the name
+    #[error("Register {name} does not exist")]
+    NotFound {
+        /// The register name
+        name: String,
+    },
+    /// The register can't be written
+    #[error("Register {name} is not mutable")]
+    Immutable {
+        /// The register name
+        name: String,
+    },
+    /// The server did not change the register to the requested value
+    #[error("Register {name} was not changed to the requested value, and is now {value}")]
+    Rejected {
+        /// The register name
+        name: String,
+        /// The current value in the plain form, as YAML
+        value: String,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_registers, parse_registers, RegisterClient, RegisterError};
+    use crate::bus::ReceivedTransfer;
+    use crate::mock_bus::{decode, encode, response_to, MockBus, Server};
+    use crate::types::standard_registry;
+    use canadensis_core::Priority;
+    use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+    use serde_json::json;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    /// A server that has some registers
+    struct RegisterServer<'r> {
+        registry: &'r TypeRegistry,
+        /// The name, value (as typed JSON), and mutability of each register
+        registers: Vec<(String, serde_json::Value, bool)>,
+    }
+
+    impl RegisterServer<'_> {
+        fn respond(&mut self, service: u16, request: &serde_json::Value) -> serde_json::Value {
+            if service == 385 {
+                let index = request["index"].as_u64().unwrap() as usize;
+                let name = self
+                    .registers
+                    .get(index)
+                    .map(|(name, _, _)| name.as_str())
+                    .unwrap_or("");
+                return json!({ "name": { "name": name } });
+            }
+            let name: String = request["name"]["name"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|byte| byte.as_u64().unwrap() as u8 as char)
+                .collect();
+            let register = match self.registers.iter_mut().find(|(n, _, _)| *n == name) {
+                Some(register) => register,
+                None => return json!({}),
+            };
+            let requested = &request["value"];
+            // Like a real node, accept only values with the same type
+            let same_type = requested.as_object().unwrap().keys().next()
+                == register.1.as_object().unwrap().keys().next();
+            if register.2 && same_type {
+                register.1 = requested.clone();
+            }
+            json!({ "mutable": register.2, "persistent": true, "value": register.1 })
+        }
+    }
+
+    impl Server for RegisterServer<'_> {
+        fn request(
+            &mut self,
+            request: &ReceivedTransfer,
+            received: &mut VecDeque<ReceivedTransfer>,
+        ) {
+            let registry = self.registry;
+            let response = self.respond(request.port_id, &decode(registry, request));
+            let payload = encode(registry, PayloadKind::Response, request.port_id, &response);
+            received.push_back(response_to(request, payload));
+        }
+    }
+
+    #[test]
+    fn register_access() {
+        let registry = standard_registry();
+        let mut bus = MockBus::new(RegisterServer {
+            registry: &registry,
+            registers: vec![
+                (
+                    "uavcan.node.id".into(),
+                    json!({ "natural16": { "value": [42] } }),
+                    true,
+                ),
+                (
+                    "uavcan.node.description".into(),
+                    json!({ "string": { "value": "Left motor" } }),
+                    true,
+                ),
+                (
+                    "gains".into(),
+                    json!({ "real32": { "value": [0.5, 0.25] } }),
+                    true,
+                ),
+                (
+                    "sys.version".into(),
+                    json!({ "natural8": { "value": [1, 2] } }),
+                    false,
+                ),
+            ],
+        });
+        let mut client = RegisterClient::new(
+            &mut bus,
+            &registry,
+            42,
+            Priority::Nominal,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+
+        let names = client.list().unwrap();
+        assert_eq!(
+            vec![
+                "uavcan.node.id",
+                "uavcan.node.description",
+                "gains",
+                "sys.version"
+            ],
+            names
+        );
+        let values = names
+            .iter()
+            .map(|name| client.read(name).map(|register| register.value))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let text = format_registers(names.iter().map(String::as_str).zip(&values));
+        assert_eq!(
+            "uavcan.node.id: 42\nuavcan.node.description: Left motor\ngains: [0.5, 0.25]\n\
+             sys.version: [1, 2]",
+            text
+        );
+        assert!(matches!(
+            client.read("missing"),
+            Err(RegisterError::NotFound { .. })
+        ));
+
+        // Plain values are converted to the register type
+        for (name, value) in parse_registers("uavcan.node.id: 7\ngains: [1, 2, 3]\n").unwrap() {
+            client.write(&name, &value).unwrap();
+        }
+        let register = client
+            .write("uavcan.node.description", &json!("Right motor"))
+            .unwrap();
+        assert_eq!(
+            "uavcan.node.description: Right motor",
+            format_registers([("uavcan.node.description", &register.value)])
+        );
+        assert!(matches!(
+            client.write("uavcan.node.description", &json!(3)),
+            Err(RegisterError::Mismatch { .. })
+        ));
+        assert!(matches!(
+            client.write("sys.version", &json!([3, 4])),
+            Err(RegisterError::Immutable { .. })
+        ));
+        assert!(matches!(
+            client.write("uavcan.node.id", &json!({ "natural8": { "value": [1] } })),
+            Err(RegisterError::Rejected { .. })
+        ));
+        assert!(matches!(
+            parse_registers("[1, 2]"),
+            Err(RegisterError::NotMapping)
+        ));
+
+        assert_eq!(
+            json!({ "natural16": { "value": [7] } }),
+            bus.server.registers[0].1
+        );
+        assert_eq!(
+            json!({ "real32": { "value": [1.0, 2.0, 3.0] } }),
+            bus.server.registers[2].1
+        );
+    }
+}
@@ -0,0 +1,567 @@
+//! An interactive terminal view of the nodes and subjects on a network
+//!
+//! A [`Dashboard`] shows two tables that update while messages arrive:
+//!
+//! * The nodes that have sent transfers, with the health, mode, and uptime from their heartbeats
+//!   and the subjects from their port lists
+//! * The subjects, with their types, message rates, and publishers
+//!
+//! The heartbeat and port list subjects are always included. The latest message on the
+//! selected subject can be shown as YAML in the same format as [`Monitor`], and a filter hides
+//! the rows that do not contain some text.
+//!
+//! Keys:
+//!
+//! * `Tab` switches between the tables
+//! * The up and down arrow keys select a row
+//! * `Enter` shows or hides the latest message on the selected subject
+//! * `/` starts editing the filter, and `Enter` or `Esc` finishes
+//! * `q` or `Esc` exits
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io;
+use std::time::{Duration, Instant};
+
+use canadensis_dsdl_frontend::TypeKey;
+use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+use canadensis_dynamic::yaml::YamlConfig;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::bus::{Bus, BusError, ReceivedTransfer};
+use crate::monitor::Monitor;
+use crate::node_list::{
+    format_ports, format_uptime, health_name, mode_name, NodeList, NodeListError,
+};
+use crate::types::{find_subject, receive_size, Port};
+
+/// The time over which message rates are averaged
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// The time between updates of the screen while no keys are pressed
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What has been received on a subject
+struct SubjectStats {
+    key: TypeKey,
+    /// The number of messages received
+    count: u64,
+    /// The number of messages that could not be deserialized
+    errors: u64,
+    /// The times when messages were received in the last `RATE_WINDOW`, oldest first
+    recent: VecDeque<Instant>,
+    /// The nodes that have published on this subject (None for anonymous nodes)
+    publishers: BTreeSet<Option<u16>>,
+    /// The latest message
+    latest: Option<ReceivedTransfer>,
+}
+
+impl SubjectStats {
+    /// Returns the number of messages per second in the last `RATE_WINDOW`
+    fn rate(&mut self, now: Instant) -> f64 {
+        while self
+            .recent
+            .front()
+            .is_some_and(|&time| now.saturating_duration_since(time) > RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        self.recent.len() as f64 / RATE_WINDOW.as_secs_f64()
+    }
+}
+
+/// The table that the arrow keys move in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Focus {
+    Nodes,
+    Subjects,
+}
+
+/// Collects information about the nodes and subjects on a network and shows it in a terminal
+pub struct Dashboard<'r> {
+    registry: &'r TypeRegistry,
+    node_list: NodeList<'r>,
+    monitor: Monitor<'r>,
+    subjects: BTreeMap<u16, SubjectStats>,
+    /// The time when the latest transfer from each node was received
+    last_seen: BTreeMap<u16, Instant>,
+    focus: Focus,
+    node_table: TableState,
+    subject_table: TableState,
+    /// Rows that do not contain this text (ignoring case) are hidden
+    filter: String,
+    editing_filter: bool,
+    show_message: bool,
+}
+
+impl<'r> Dashboard<'r> {
+    /// Creates a dashboard for some subjects, in addition to the heartbeat and port list
+    /// subjects
+    ///
+    /// This returns an error if the registry does not have the heartbeat, port list, and
+    /// GetInfo types.
+    pub fn new<I>(registry: &'r TypeRegistry, subjects: I) -> Result<Self, NodeListError>
+    where
+        I: IntoIterator<Item = Port>,
+    {
+        let node_list = NodeList::new(registry)?;
+        let mut ports = Vec::new();
+        for name in ["uavcan.node.Heartbeat.1", "uavcan.node.port.List.1"] {
+            ports.push(find_subject(registry, name).map_err(NodeListError::Types)?);
+        }
+        ports.extend(subjects);
+        let monitor = Monitor::new(registry, ports.clone(), YamlConfig::default());
+        let subjects = ports
+            .into_iter()
+            .map(|port| {
+                (
+                    port.port_id,
+                    SubjectStats {
+                        key: port.key,
+                        count: 0,
+                        errors: 0,
+                        recent: VecDeque::new(),
+                        publishers: BTreeSet::new(),
+                        latest: None,
+                    },
+                )
+            })
+            .collect();
+        Ok(Dashboard {
+            registry,
+            node_list,
+            monitor,
+            subjects,
+            last_seen: BTreeMap::new(),
+            focus: Focus::Nodes,
+            node_table: TableState::default(),
+            subject_table: TableState::default(),
+            filter: String::new(),
+            editing_filter: false,
+            show_message: false,
+        })
+    }
+
+    /// Subscribes to all the subjects on a bus
+    pub fn subscribe(&self, bus: &mut dyn Bus) -> Result<(), NodeListError> {
+        for (&subject, stats) in &self.subjects {
+            let message = self.registry.message(&stats.key, PayloadKind::Message)?;
+            bus.subscribe(PayloadKind::Message, subject, receive_size(message))?;
+        }
+        Ok(())
+    }
+
+    /// Updates the nodes and subjects from a transfer that was received at `now`
+    pub fn handle(&mut self, transfer: &ReceivedTransfer, now: Instant) {
+        if transfer.kind != PayloadKind::Message {
+            return;
+        }
+        if let Some(source) = transfer.source {
+            self.last_seen.insert(source, now);
+        }
+        let stats = match self.subjects.get_mut(&transfer.port_id) {
+            Some(stats) => stats,
+            None => return,
+        };
+        stats.count += 1;
+        stats.recent.push_back(now);
+        stats.publishers.insert(transfer.source);
+        // Errors are counted instead of printed, because they would disturb the screen
+        let valid = self
+            .registry
+            .deserialize(&stats.key, PayloadKind::Message, &transfer.payload)
+            .is_ok()
+            && self.node_list.handle(transfer).is_ok();
+        if valid {
+            stats.latest = Some(transfer.clone());
+        } else {
+            stats.errors += 1;
+        }
+    }
+
+    /// Handles a key press and returns true if the dashboard should exit
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.editing_filter {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => self.editing_filter = false,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => {}
+            }
+            return false;
+        }
+        let table = match self.focus {
+            Focus::Nodes => &mut self.node_table,
+            Focus::Subjects => &mut self.subject_table,
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus = match self.focus {
+                    Focus::Nodes => Focus::Subjects,
+                    Focus::Subjects => Focus::Nodes,
+                }
+            }
+            KeyCode::Up => table.select_previous(),
+            KeyCode::Down => table.select_next(),
+            KeyCode::Enter => {
+                self.show_message = !self.show_message;
+                self.focus = Focus::Subjects;
+            }
+            KeyCode::Char('/') => self.editing_filter = true,
+            _ => {}
+        }
+        false
+    }
+
+    /// Returns the rows of the node table that match the filter, with the node IDs
+    fn node_rows(&self, now: Instant) -> Vec<(u16, [String; 6])> {
+        self.last_seen
+            .iter()
+            .map(|(&node_id, &last_seen)| {
+                let status = self.node_list.nodes().get(&node_id);
+                let unknown = || "?".to_owned();
+                let row = [
+                    node_id.to_string(),
+                    status
+                        .and_then(|status| status.health)
+                        .map_or_else(unknown, |health| health_name(health).to_owned()),
+                    status
+                        .and_then(|status| status.mode)
+                        .map_or_else(unknown, |mode| mode_name(mode).to_owned()),
+                    status
+                        .and_then(|status| status.uptime)
+                        .map_or_else(unknown, format_uptime),
+                    format!(
+                        "{:.1} s",
+                        now.saturating_duration_since(last_seen).as_secs_f64()
+                    ),
+                    status
+                        .and_then(|status| status.ports.as_ref())
+                        .map_or_else(unknown, format_ports),
+                ];
+                (node_id, row)
+            })
+            .filter(|(_, row)| self.matches_filter(row))
+            .collect()
+    }
+
+    /// Returns the rows of the subject table that match the filter, with the subject IDs
+    fn subject_rows(&mut self, now: Instant) -> Vec<(u16, [String; 6])> {
+        let mut rows = Vec::new();
+        for (&subject, stats) in &mut self.subjects {
+            let publishers: Vec<String> = stats
+                .publishers
+                .iter()
+                .map(|publisher| match publisher {
+                    Some(node_id) => node_id.to_string(),
+                    None => "anonymous".to_owned(),
+                })
+                .collect();
+            let row = [
+                subject.to_string(),
+                stats.key.to_string(),
+                format!("{:.1}", stats.rate(now)),
+                stats.count.to_string(),
+                stats.errors.to_string(),
+                publishers.join(","),
+            ];
+            rows.push((subject, row));
+        }
+        rows.retain(|(_, row)| self.matches_filter(row));
+        rows
+    }
+
+    fn matches_filter(&self, row: &[String]) -> bool {
+        let filter = self.filter.to_lowercase();
+        row.iter().any(|cell| cell.to_lowercase().contains(&filter))
+    }
+
+    /// Draws the dashboard
+    pub fn draw(&mut self, frame: &mut Frame<'_>, now: Instant) {
+        let node_rows = self.node_rows(now);
+        let subject_rows = self.subject_rows(now);
+        for (table, len) in [
+            (&mut self.node_table, node_rows.len()),
+            (&mut self.subject_table, subject_rows.len()),
+        ] {
+            let selected = match table.selected() {
+                Some(_) if len == 0 => None,
+                Some(selected) => Some(selected.min(len - 1)),
+                None if len != 0 => Some(0),
+                None => None,
+            };
+            table.select(selected);
+        }
+
+        let [tables_area, message_area, status_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Percentage(if self.show_message { 40 } else { 0 }),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [nodes_area, subjects_area] =
+            Layout::vertical([Constraint::Fill(2), Constraint::Fill(3)]).areas(tables_area);
+
+        let node_table = Table::new(
+            node_rows.into_iter().map(|(_, row)| Row::new(row)),
+            [
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Length(15),
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["NODE", "HEALTH", "MODE", "UPTIME", "LAST SEEN", "PORTS"]).bold())
+        .block(self.block("Nodes", Focus::Nodes))
+        .row_highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(node_table, nodes_area, &mut self.node_table);
+
+        let selected_subject = self
+            .subject_table
+            .selected()
+            .map(|selected| subject_rows[selected].0);
+        let subject_table = Table::new(
+            subject_rows.into_iter().map(|(_, row)| Row::new(row)),
+            [
+                Constraint::Length(7),
+                Constraint::Fill(2),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["SUBJECT", "TYPE", "RATE/S", "COUNT", "ERRORS", "PUBLISHERS"]).bold())
+        .block(self.block("Subjects", Focus::Subjects))
+        .row_highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(subject_table, subjects_area, &mut self.subject_table);
+
+        if self.show_message {
+            let latest = selected_subject
+                .and_then(|subject| self.subjects[&subject].latest.as_ref())
+                .and_then(|transfer| self.monitor.format(transfer).ok().flatten());
+            let text = latest.unwrap_or_else(|| "No message received".to_owned());
+            let title = match selected_subject {
+                Some(subject) => format!("Latest message on {}", subject),
+                None => "Latest message".to_owned(),
+            };
+            frame.render_widget(
+                Paragraph::new(text)
+                    .wrap(Wrap { trim: false })
+                    .block(Block::bordered().title(title)),
+                message_area,
+            );
+        }
+
+        let status = if self.editing_filter {
+            format!("Filter: {}_", self.filter)
+        } else if !self.filter.is_empty() {
+            format!(
+                "Filter: {}    / change filter  Enter show message  Tab switch table  q quit",
+                self.filter
+            )
+        } else {
+            "/ filter  Enter show message  Tab switch table  q quit".to_owned()
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    fn block(&self, title: &'static str, focus: Focus) -> Block<'static> {
+        let block = Block::bordered().title(title);
+        if self.focus == focus {
+            block.border_style(Style::new().bold())
+        } else {
+            block
+        }
+    }
+}
+
+/// Shows a dashboard in the terminal and updates it with transfers from a bus until a key
+/// exits
+pub fn run(dashboard: &mut Dashboard<'_>, bus: &mut dyn Bus) -> Result<(), TuiError> {
+    let mut terminal = ratatui::try_init().map_err(TuiError::Terminal)?;
+    let result = run_terminal(dashboard, bus, &mut terminal);
+    ratatui::restore();
+    result
+}
+
+fn run_terminal(
+    dashboard: &mut Dashboard<'_>,
+    bus: &mut dyn Bus,
+    terminal: &mut DefaultTerminal,
+) -> Result<(), TuiError> {
+    let mut next_draw = Instant::now();
+    loop {
+        if Instant::now() >= next_draw {
+            terminal
+                .draw(|frame| dashboard.draw(frame, Instant::now()))
+                .map_err(TuiError::Terminal)?;
+            next_draw = Instant::now() + REFRESH_INTERVAL;
+        }
+        while event::poll(Duration::ZERO).map_err(TuiError::Terminal)? {
+            if let Event::Key(key) = event::read().map_err(TuiError::Terminal)? {
+                if key.kind == KeyEventKind::Press {
+                    if dashboard.handle_key(key) {
+                        return Ok(());
+                    }
+                    next_draw = Instant::now();
+                }
+            }
+        }
+        // Receiving may block for a short time, which keeps this loop from using all the CPU
+        // time
+        if let Some(transfer) = bus.receive()? {
+            dashboard.handle(&transfer, Instant::now());
+        }
+    }
+}
+
+/// Errors that can occur while showing a dashboard
+#[derive(thiserror::Error, Debug)]
+pub enum TuiError {
+    /// Receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+    /// The terminal could not be used
+    #[error("Terminal error")]
+    Terminal(#[source] io::Error),
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dashboard;
+    use crate::bus::ReceivedTransfer;
+    use crate::types::{find_subject, standard_registry};
+    use canadensis_core::time::Microseconds32;
+    use canadensis_core::Priority;
+    use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+    use canadensis_dynamic::yaml::from_yaml;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+    use ratatui::Terminal;
+    use std::time::{Duration, Instant, SystemTime};
+
+    fn transfer(port_id: u16, source: Option<u16>, payload: Vec<u8>) -> ReceivedTransfer {
+        ReceivedTransfer {
+            kind: PayloadKind::Message,
+            port_id,
+            source,
+            destination: None,
+            transfer_id: 0,
+            priority: Priority::Nominal,
+            timestamp: Microseconds32::from_ticks(0),
+            system_time: SystemTime::now(),
+            payload,
+        }
+    }
+
+    fn payload(registry: &TypeRegistry, name: &str, yaml: &str) -> Vec<u8> {
+        let key = registry.find(name).unwrap();
+        let message = registry.message(key, PayloadKind::Message).unwrap();
+        registry
+            .serialize(
+                key,
+                PayloadKind::Message,
+                &from_yaml(message, yaml).unwrap(),
+            )
+            .unwrap()
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn nodes_and_subjects() {
+        let registry = standard_registry();
+        let status = find_subject(&registry, "100:uavcan.primitive.String.1.0").unwrap();
+        let mut dashboard = Dashboard::new(&registry, vec![status]).unwrap();
+        let start = Instant::now();
+        let heartbeat = payload(
+            &registry,
+            "uavcan.node.Heartbeat",
+            "{uptime: 3725, health: {value: 1}, mode: {value: 0}}",
+        );
+        for i in 0..10 {
+            let time = start + Duration::from_millis(500 * i);
+            dashboard.handle(&transfer(7509, Some(42), heartbeat.clone()), time);
+        }
+        let text = payload(&registry, "uavcan.primitive.String", "{value: motor stall}");
+        dashboard.handle(&transfer(100, None, text), start);
+        // An invalid port list
+        dashboard.handle(&transfer(7510, Some(7), vec![0xff]), start);
+
+        let now = start + Duration::from_millis(4600);
+        assert_eq!(
+            vec![
+                (7, ["7", "?", "?", "?", "4.6 s", "?"].map(String::from)),
+                (
+                    42,
+                    ["42", "advisory", "operational", "1:02:05", "0.1 s", "?"].map(String::from)
+                ),
+            ],
+            dashboard.node_rows(now)
+        );
+        assert_eq!(
+            vec![
+                (
+                    100,
+                    [
+                        "100",
+                        "uavcan.primitive.String.1.0",
+                        "0.2",
+                        "1",
+                        "0",
+                        "anonymous"
+                    ]
+                    .map(String::from)
+                ),
+                (
+                    7509,
+                    ["7509", "uavcan.node.Heartbeat.1.0", "2.0", "10", "0", "42"].map(String::from)
+                ),
+                (
+                    7510,
+                    ["7510", "uavcan.node.port.List.1.0", "0.2", "1", "1", "7"].map(String::from)
+                ),
+            ],
+            dashboard.subject_rows(now)
+        );
+
+        // The filter matches any column
+        for c in "/string".chars() {
+            assert!(!dashboard.handle_key(key(KeyCode::Char(c))));
+        }
+        dashboard.handle_key(key(KeyCode::Enter));
+        let subjects: Vec<u16> = dashboard
+            .subject_rows(now)
+            .into_iter()
+            .map(|(subject, _)| subject)
+            .collect();
+        assert_eq!(vec![100], subjects);
+        assert!(dashboard.node_rows(now).is_empty());
+
+        // Show the latest valid message on the selected subject
+        dashboard.handle_key(key(KeyCode::Enter));
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| dashboard.draw(frame, now)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Latest message on 100"), "{}", screen);
+        assert!(screen.contains("value: [109, 111, 116"), "{}", screen);
+        assert!(dashboard.handle_key(key(KeyCode::Char('q'))));
+    }
+}
@@ -0,0 +1,238 @@
+//! Loading data types and finding the types and port IDs that command-line arguments refer to
+
+use std::convert::TryFrom;
+use std::env;
+use std::path::PathBuf;
+
+use canadensis_codegen_rust::project::{add_cyphal_path, ProjectConfig};
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Extent, Message};
+use canadensis_dsdl_frontend::{Package, TypeKey};
+use canadensis_dynamic::registry::TypeRegistry;
+use canadensis_dynamic::Value;
+
+/// Returns a list of built-in DSDL files, with each path relative to the `dsdl` directory and
+/// the content of the file
+macro_rules! standard_types {
+    ($($path:literal,)*) => {
+        [$(($path, include_str!(concat!("../dsdl/", $path))),)*]
+    };
+}
+
+/// The standard data types that the commands use, which are built in so that the commands work
+/// without any DSDL directories
+///
+/// These are some of the types in the `uavcan` namespace of the public regulated data types.
+const STANDARD_TYPES: [(&str, &str); 38] = standard_types![
+    "uavcan/file/405.GetInfo.0.2.dsdl",
+    "uavcan/file/406.List.0.2.dsdl",
+    "uavcan/file/407.Modify.1.1.dsdl",
+    "uavcan/file/408.Read.1.1.dsdl",
+    "uavcan/file/409.Write.1.1.dsdl",
+    "uavcan/file/Error.1.0.dsdl",
+    "uavcan/file/Path.2.0.dsdl",
+    "uavcan/node/430.GetInfo.1.0.dsdl",
+    "uavcan/node/435.ExecuteCommand.1.1.dsdl",
+    "uavcan/node/7509.Heartbeat.1.0.dsdl",
+    "uavcan/node/Health.1.0.dsdl",
+    "uavcan/node/Mode.1.0.dsdl",
+    "uavcan/node/Version.1.0.dsdl",
+    "uavcan/node/port/7510.List.1.0.dsdl",
+    "uavcan/node/port/ServiceID.1.0.dsdl",
+    "uavcan/node/port/ServiceIDList.1.0.dsdl",
+    "uavcan/node/port/SubjectID.1.0.dsdl",
+    "uavcan/node/port/SubjectIDList.1.0.dsdl",
+    "uavcan/primitive/Empty.1.0.dsdl",
+    "uavcan/primitive/String.1.0.dsdl",
+    "uavcan/primitive/Unstructured.1.0.dsdl",
+    "uavcan/primitive/array/Bit.1.0.dsdl",
+    "uavcan/primitive/array/Integer16.1.0.dsdl",
+    "uavcan/primitive/array/Integer32.1.0.dsdl",
+    "uavcan/primitive/array/Integer64.1.0.dsdl",
+    "uavcan/primitive/array/Integer8.1.0.dsdl",
+    "uavcan/primitive/array/Natural16.1.0.dsdl",
+    "uavcan/primitive/array/Natural32.1.0.dsdl",
+    "uavcan/primitive/array/Natural64.1.0.dsdl",
+    "uavcan/primitive/array/Natural8.1.0.dsdl",
+    "uavcan/primitive/array/Real16.1.0.dsdl",
+    "uavcan/primitive/array/Real32.1.0.dsdl",
+    "uavcan/primitive/array/Real64.1.0.dsdl",
+    "uavcan/register/384.Access.1.0.dsdl",
+    "uavcan/register/385.List.1.0.dsdl",
+    "uavcan/register/Name.1.0.dsdl",
+    "uavcan/register/Value.1.0.dsdl",
+    "uavcan/time/SynchronizedTimestamp.1.0.dsdl",
+];
+
+/// Loads the data types in some DSDL root directories
+///
+/// If `roots` is empty, this uses the root directories in the closest project configuration
+/// file (`canadensis.conf`), if there is one. The directories in the `CYPHAL_PATH`
+/// environment variable are always added.
+///
+/// If none of the root directories has a `uavcan` namespace, this also adds the built-in
+/// standard types (heartbeats, port lists, `GetInfo`, `ExecuteCommand`, registers, and
+/// file services).
+pub fn load_registry(roots: &[PathBuf]) -> Result<TypeRegistry, Box<dyn std::error::Error>> {
+    let mut config = canadensis_dsdl_frontend::Config::default();
+    let mut roots = roots.to_vec();
+    if roots.is_empty() {
+        if let Some(path) = ProjectConfig::find(env::current_dir()?) {
+            let project = ProjectConfig::load(path)?;
+            roots = project.roots;
+            config = project.config;
+        }
+    }
+    add_cyphal_path(&mut roots);
+    let mut package = Package::new();
+    for root in &roots {
+        package.add_files(root)?;
+    }
+    if !roots.iter().any(|root| root.join("uavcan").is_dir()) {
+        package.add_virtual_files(STANDARD_TYPES.iter().copied())?;
+    }
+    Ok(TypeRegistry::new(package.compile(&config)?))
+}
+
+/// Returns a registry with only the built-in standard types
+#[cfg(test)]
+pub(crate) fn standard_registry() -> TypeRegistry {
+    let mut package = Package::new();
+    package
+        .add_virtual_files(STANDARD_TYPES.iter().copied())
+        .unwrap();
+    TypeRegistry::new(package.compile(&Default::default()).unwrap())
+}
+
+/// A port ID and the data type used on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Port {
+    /// The subject ID or service ID
+    pub port_id: u16,
+    /// The data type
+    pub key: TypeKey,
+}
+
+/// Finds the subject ID and message type that an argument refers to
+///
+/// The argument can be a subject ID and a type name separated by `:` (`1234:reg.udral.physics.
+/// kinematics.cartesian.Twist.0.1`), a type name with a fixed subject ID
+/// (`uavcan.node.Heartbeat`), or a fixed subject ID (`7509`). Type names can leave out all or
+/// part of the version, like [`TypeRegistry::find`].
+pub fn find_subject(registry: &TypeRegistry, argument: &str) -> Result<Port, String> {
+    find_port(registry, argument, false)
+}
+
+/// Finds the service ID and service type that an argument refers to
+///
+/// The argument has the same forms as in [`find_subject`], with service IDs and service types.
+pub fn find_service(registry: &TypeRegistry, argument: &str) -> Result<Port, String> {
+    find_port(registry, argument, true)
+}
+
+fn find_port(registry: &TypeRegistry, argument: &str, service: bool) -> Result<Port, String> {
+    let (port_id, type_name) = match argument.split_once(':') {
+        Some((port_id, type_name)) => (Some(port_id), Some(type_name)),
+        None if argument.parse::<u16>().is_ok() => (Some(argument), None),
+        None => (None, Some(argument)),
+    };
+    let port_id = port_id
+        .map(|port_id| {
+            port_id
+                .parse::<u16>()
+                .map_err(|_| format!("Invalid port ID {}", port_id))
+        })
+        .transpose()?;
+    let key = match type_name {
+        Some(type_name) => registry
+            .find(type_name)
+            .ok_or_else(|| format!("Type {} not found", type_name))?
+            .clone(),
+        None => {
+            let port_id = port_id.expect("No port ID or type");
+            let key = if service {
+                registry.service_type(port_id)
+            } else {
+                registry.subject_type(port_id)
+            };
+            key.ok_or_else(|| format!("No type has fixed port ID {}", port_id))?
+                .clone()
+        }
+    };
+    let dsdl = registry
+        .package()
+        .get_by_key(&key)
+        .expect("Type not in registry");
+    match (&dsdl.kind, service) {
+        (DsdlKind::Message(_), true) => return Err(format!("{} is not a service type", key)),
+        (DsdlKind::Service { .. }, false) => return Err(format!("{} is not a message type", key)),
+        _ => {}
+    }
+    let port_id = match port_id.or_else(|| dsdl.fixed_port_id.map(|id| id as u16)) {
+        Some(port_id) => port_id,
+        None => {
+            return Err(format!(
+                "{} does not have a fixed port ID, so a port ID is required (like 1234:{})",
+                key, key
+            ))
+        }
+    };
+    Ok(Port { port_id, key })
+}
+
+/// Returns the value of a field of a struct value
+pub(crate) fn field<'v>(value: &'v Value, name: &str) -> Option<&'v Value> {
+    match value {
+        Value::Struct(fields) => fields
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+/// Converts an array of `uint8`, like the name in `uavcan.node.GetInfo`, into a string
+///
+/// Invalid UTF-8 is replaced with U+FFFD.
+pub(crate) fn uint8_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(elements) => {
+            let bytes = elements
+                .iter()
+                .map(|element| match element {
+                    Value::UInt(byte) => u8::try_from(*byte).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<u8>>>()?;
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the maximum number of bytes that a receiver of a message, request, or response
+/// needs to accept
+pub fn receive_size(message: &Message) -> usize {
+    match message.extent() {
+        Extent::Sealed => message.bit_length().max_value().div_ceil(8) as usize,
+        Extent::Delimited(extent_bits) => extent_bits.div_ceil(8) as usize,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_service, standard_registry};
+
+    #[test]
+    fn standard_types_compile() {
+        let registry = standard_registry();
+        assert_eq!(
+            430,
+            find_service(&registry, "uavcan.node.GetInfo")
+                .unwrap()
+                .port_id
+        );
+        assert_eq!(384, find_service(&registry, "384").unwrap().port_id);
+        assert!(find_service(&registry, "uavcan.node.Heartbeat").is_err());
+        assert!(find_service(&registry, "uavcan.register.Name").is_err());
+    }
+}
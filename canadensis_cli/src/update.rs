@@ -0,0 +1,413 @@
+//! Updating the software of a node
+//!
+//! An update follows the procedure in the Cyphal specification:
+//!
+//! 1. This node serves the directory that contains the image file with a [`FileServer`]
+//! 2. This node sends a `uavcan.node.ExecuteCommand` request with
+//!    `COMMAND_BEGIN_SOFTWARE_UPDATE` and the name of the image file to the target node
+//! 3. The target node switches to the software update mode and reads the image file
+//! 4. The target node installs the image and leaves the software update mode, usually by
+//!    restarting
+//!
+//! The update is successful if the target node reads the whole image before it leaves the
+//! software update mode or restarts.
+
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use canadensis_core::Priority;
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+
+use crate::bus::{Bus, BusError};
+use crate::call::{call, request_payload, CallError};
+use crate::file_server::{FileServer, FileServerError};
+use crate::node_list::{mode_name, nested_uint, uint};
+use crate::types::{field, find_service, find_subject, receive_size, Port};
+
+const COMMAND_BEGIN_SOFTWARE_UPDATE: u16 = 65533;
+/// The heartbeat mode that a node is in while it updates its software
+const MODE_SOFTWARE_UPDATE: u8 = 3;
+
+/// Options for an update
+#[derive(Debug, Clone)]
+pub struct UpdateOptions {
+    /// The priority of the ExecuteCommand request
+    pub priority: Priority,
+    /// The time to wait for the response to the ExecuteCommand request
+    pub request_timeout: Duration,
+    /// The time allowed for the whole update
+    pub timeout: Duration,
+}
+
+/// Updates the software of nodes with an image file
+pub struct Update<'r> {
+    registry: &'r TypeRegistry,
+    server: FileServer<'r>,
+    heartbeat: Port,
+    execute_command: Port,
+    /// The file name of the image, which is its path relative to the root of the server
+    image: String,
+    image_size: u64,
+}
+
+impl<'r> Update<'r> {
+    /// Prepares to send an image file to nodes
+    pub fn new(registry: &'r TypeRegistry, image: &Path) -> Result<Self, UpdateError> {
+        let image_size = image.metadata()?.len();
+        let name = match image.file_name().and_then(|name| name.to_str()) {
+            Some(name) if image.is_file() => name.to_owned(),
+            _ => return Err(UpdateError::NotFile),
+        };
+        let root = match image.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_owned(),
+            _ => ".".into(),
+        };
+        Ok(Update {
+            registry,
+            server: FileServer::new(registry, root, true)?,
+            heartbeat: find_subject(registry, "uavcan.node.Heartbeat.1")
+                .map_err(UpdateError::Types)?,
+            execute_command: find_service(registry, "uavcan.node.ExecuteCommand.1")
+                .map_err(UpdateError::Types)?,
+            image: name,
+            image_size,
+        })
+    }
+
+    /// Subscribes to heartbeats and file service requests
+    pub fn subscribe(&self, bus: &mut dyn Bus) -> Result<(), UpdateError> {
+        self.server.subscribe(bus)?;
+        let heartbeat = self
+            .registry
+            .message(&self.heartbeat.key, PayloadKind::Message)?;
+        bus.subscribe(
+            PayloadKind::Message,
+            self.heartbeat.port_id,
+            receive_size(heartbeat),
+        )?;
+        Ok(())
+    }
+
+    /// Updates the software of the target node and writes progress messages to `output`
+    ///
+    /// This serves files to all nodes until the update finishes.
+    pub fn run<W: Write>(
+        &self,
+        bus: &mut dyn Bus,
+        target: u16,
+        options: &UpdateOptions,
+        mut output: W,
+    ) -> Result<(), UpdateError> {
+        let deadline = Instant::now() + options.timeout;
+        let request = format!(
+            "{{command: {}, parameter: \"{}\"}}",
+            COMMAND_BEGIN_SOFTWARE_UPDATE,
+            self.image.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        let payload = request_payload(self.registry, &self.execute_command, &request)?;
+        let response = call(
+            bus,
+            self.registry,
+            &self.execute_command,
+            target,
+            &payload,
+            options.priority,
+            options.request_timeout,
+        )?;
+        let status = uint(field(&response, "status")).unwrap_or(0) as u8;
+        if status != 0 {
+            return Err(UpdateError::Rejected { target, status });
+        }
+        writeln!(
+            output,
+            "Node {} started updating with {} ({} bytes)",
+            target, self.image, self.image_size
+        )?;
+
+        let mut read_end = 0;
+        let mut reported_tenths = 0;
+        let mut mode = None;
+        let mut uptime = None;
+        let mut updating = false;
+        while Instant::now() < deadline {
+            let transfer = match bus.receive()? {
+                Some(transfer) => transfer,
+                None => continue,
+            };
+            if transfer.kind == PayloadKind::Request {
+                match self.server.handle(&transfer) {
+                    Ok(Some(handled)) => {
+                        bus.send_response(&transfer, &handled.response)?;
+                        match handled.read {
+                            Some(read)
+                                if transfer.source == Some(target)
+                                    && read.path.trim_start_matches('/') == self.image =>
+                            {
+                                read_end = read_end.max(read.end);
+                            }
+                            _ => {}
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => eprintln!(
+                        "Invalid request on service {} from node {:?}: {}",
+                        transfer.port_id, transfer.source, e
+                    ),
+                }
+                let tenths = (read_end * 10).checked_div(self.image_size).unwrap_or(10);
+                if tenths > reported_tenths {
+                    reported_tenths = tenths;
+                    writeln!(
+                        output,
+                        "Read {}% ({} of {} bytes)",
+                        tenths * 10,
+                        read_end,
+                        self.image_size
+                    )?;
+                }
+            } else if transfer.kind == PayloadKind::Message
+                && transfer.port_id == self.heartbeat.port_id
+                && transfer.source == Some(target)
+            {
+                let heartbeat = self.registry.deserialize(
+                    &self.heartbeat.key,
+                    PayloadKind::Message,
+                    &transfer.payload,
+                )?;
+                let new_mode = nested_uint(&heartbeat, "mode");
+                let new_uptime = uint(field(&heartbeat, "uptime"));
+                let restarted = matches!((uptime, new_uptime), (Some(old), Some(new)) if new < old);
+                uptime = new_uptime;
+                if new_mode != mode {
+                    mode = new_mode;
+                    if let Some(mode) = mode {
+                        writeln!(output, "Node {} is in mode {}", target, mode_name(mode))?;
+                    }
+                }
+                if restarted {
+                    writeln!(output, "Node {} restarted", target)?;
+                }
+                updating |= mode == Some(MODE_SOFTWARE_UPDATE);
+                let finished = (updating && mode != Some(MODE_SOFTWARE_UPDATE))
+                    || (restarted && read_end != 0);
+                if finished {
+                    return if read_end >= self.image_size {
+                        writeln!(output, "Update finished")?;
+                        Ok(())
+                    } else {
+                        Err(UpdateError::Incomplete {
+                            target,
+                            read: read_end,
+                            size: self.image_size,
+                        })
+                    };
+                }
+            }
+        }
+        Err(UpdateError::Timeout {
+            target,
+            timeout: options.timeout,
+        })
+    }
+}
+
+fn status_name(status: u8) -> &'static str {
+    match status {
+        1 => "failure",
+        2 => "not authorized",
+        3 => "bad command",
+        4 => "bad parameter",
+        5 => "bad state",
+        6 => "internal error",
+        _ => "unknown status",
+    }
+}
+
+/// Errors that can occur when updating the software of a node
+#[derive(thiserror::Error, Debug)]
+pub enum UpdateError {
+    /// The image path is not a file
+    #[error("The image is not a file")]
+    NotFile,
+    /// The image file could not be opened, or progress messages could not be written
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The heartbeat, ExecuteCommand, or file service types could not be found
+    #[error("{0}")]
+    Types(String),
+    /// The file server could not be created or could not respond
+    #[error(transparent)]
+    FileServer(#[from] FileServerError),
+    /// A heartbeat could not be deserialized
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    /// The ExecuteCommand request failed
+    #[error(transparent)]
+    Request(#[from] CallError),
+    /// Sending or receiving failed
+    #[error(transparent)]
+    Bus(#[from] BusError),
+    /// The target node did not accept the update request
+    #[error("Node {target} rejected the update request ({})", status_name(*.status))]
+    Rejected {
+        /// The target node ID
+        target: u16,
+        /// The status code in the response
+        status: u8,
+    },
+    /// The target node finished updating before it read the whole image
+    #[error("Node {target} stopped updating after reading {read} of {size} bytes")]
+    Incomplete {
+        /// The target node ID
+        target: u16,
+        /// The number of bytes that the node read
+        read: u64,
+        /// The size of the image
+        size: u64,
+    },
+    /// The update did not finish in time
+    #[error("Node {target} did not finish updating within {timeout:?}")]
+    Timeout {
+        /// The target node ID
+        target: u16,
+        /// The time allowed for the update
+        timeout: Duration,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Update, UpdateError, UpdateOptions};
+    use crate::bus::ReceivedTransfer;
+    use crate::mock_bus::{decode, encode, response_to, transfer, MockBus, Server};
+    use crate::types::standard_registry;
+    use canadensis_core::Priority;
+    use canadensis_dynamic::registry::{PayloadKind, TypeRegistry};
+    use serde_json::json;
+    use std::collections::VecDeque;
+    use std::fs;
+    use std::time::Duration;
+
+    /// A target node that reads the image after it receives the update command, and then
+    /// restarts
+    struct Target<'r> {
+        registry: &'r TypeRegistry,
+        /// The number of bytes to read before restarting
+        read_limit: u64,
+    }
+
+    impl Target<'_> {
+        fn transfer(
+            &self,
+            kind: PayloadKind,
+            port_id: u16,
+            json: serde_json::Value,
+        ) -> ReceivedTransfer {
+            transfer(
+                kind,
+                port_id,
+                42,
+                encode(self.registry, kind, port_id, &json),
+            )
+        }
+
+        fn read(&self, offset: u64) -> ReceivedTransfer {
+            self.transfer(
+                PayloadKind::Request,
+                408,
+                json!({ "offset": offset, "path": { "path": "image.bin" } }),
+            )
+        }
+
+        fn heartbeat(&self, uptime: u32, mode: u8) -> ReceivedTransfer {
+            self.transfer(
+                PayloadKind::Message,
+                7509,
+                json!({ "uptime": uptime, "mode": { "value": mode } }),
+            )
+        }
+    }
+
+    impl Server for Target<'_> {
+        fn request(
+            &mut self,
+            request: &ReceivedTransfer,
+            received: &mut VecDeque<ReceivedTransfer>,
+        ) {
+            assert_eq!(435, request.port_id);
+            let payload = encode(
+                self.registry,
+                PayloadKind::Response,
+                435,
+                &json!({ "status": 0 }),
+            );
+            received.push_back(response_to(request, payload));
+            received.push_back(self.heartbeat(100, 3));
+            received.push_back(self.read(0));
+        }
+
+        fn response(
+            &mut self,
+            request: &ReceivedTransfer,
+            response: &ReceivedTransfer,
+            received: &mut VecDeque<ReceivedTransfer>,
+        ) {
+            let request = decode(self.registry, request);
+            let response = decode(self.registry, response);
+            let length = response["data"]["value"].as_array().unwrap().len() as u64;
+            let end = request["offset"].as_u64().unwrap() + length;
+            if length == 256 && end < self.read_limit {
+                received.push_back(self.read(end));
+            } else {
+                received.push_back(self.heartbeat(0, 1));
+            }
+        }
+    }
+
+    #[test]
+    fn update_node() {
+        let directory =
+            std::env::temp_dir().join(format!("canadensis_update_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let image = directory.join("image.bin");
+        fs::write(&image, vec![0xaa; 1000]).unwrap();
+
+        let registry = standard_registry();
+        let update = Update::new(&registry, &image).unwrap();
+        let options = UpdateOptions {
+            priority: Priority::Nominal,
+            request_timeout: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        };
+        let mut bus = MockBus::new(Target {
+            registry: &registry,
+            read_limit: u64::MAX,
+        });
+        let mut output = Vec::new();
+        update.run(&mut bus, 42, &options, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("Node 42 started updating with image.bin (1000 bytes)\n"));
+        assert!(
+            output.contains("Node 42 is in mode software_update\nRead 20% (256 of 1000 bytes)\n")
+        );
+        assert!(output.ends_with(
+            "Read 100% (1000 of 1000 bytes)\nNode 42 is in mode initialization\n\
+             Node 42 restarted\nUpdate finished\n"
+        ));
+
+        // The node stops reading early
+        bus.server.read_limit = 512;
+        let result = update.run(&mut bus, 42, &options, &mut Vec::new());
+        assert!(matches!(
+            result,
+            Err(UpdateError::Incomplete {
+                read: 512,
+                size: 1000,
+                ..
+            })
+        ));
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,762 @@
+//! Generation of a C header with data types and serialization functions
+//!
+//! The header is generated from the same compiled DSDL as the Rust code, so C code in a mixed
+//! project can use the same definitions. Each DSDL type becomes a struct (and each service type
+//! becomes a request struct and a response struct) named after the type with its version, like
+//! `uavcan_node_Heartbeat_1_0` or `uavcan_node_GetInfo_Request_1_0`. Each struct has a
+//! `_serialize_` and a `_deserialize_` function:
+//!
+//! ```c
+//! static inline int8_t uavcan_node_Heartbeat_1_0_serialize_(
+//!     const uavcan_node_Heartbeat_1_0* const obj,
+//!     uint8_t* const buffer,
+//!     size_t* const inout_buffer_size_bytes);
+//! static inline int8_t uavcan_node_Heartbeat_1_0_deserialize_(
+//!     uavcan_node_Heartbeat_1_0* const out_obj,
+//!     const uint8_t* buffer,
+//!     size_t* const inout_buffer_size_bytes);
+//! ```
+//!
+//! Before a call, `*inout_buffer_size_bytes` is the size of the buffer. After a successful call,
+//! it is the number of bytes that were written or read. The functions return zero on success or
+//! a negated `CANADENSIS_ERROR_` constant on failure. The buffer for serializing must be at least
+//! `_SERIALIZATION_BUFFER_SIZE_BYTES_` bytes long. Deserializing follows the implicit zero
+//! extension rule, so the buffer can be shorter than the serialized value.
+//!
+//! Variable-length arrays are structs with an `elements` array and a `count`. Unions are structs
+//! with an anonymous union of the variants and a `_tag_` that identifies the active variant.
+//! `float16` values are stored as `float`s. The header requires C11 (for anonymous unions), or
+//! can be included in C++ code.
+
+use std::collections::BTreeSet;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Extent, FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::constants::ConstantValue;
+use canadensis_dsdl_frontend::types::{
+    ImplicitField, PrimitiveType, ResolvedScalarType, ResolvedType,
+};
+use canadensis_dsdl_frontend::TypeKey;
+use canadensis_dsdl_parser::CastMode;
+
+use crate::namespace_filter::selected_types;
+use crate::{round_up_integer_size, Config};
+
+/// Functions that the serialization code uses, which are included in every generated header
+const PRELUDE: &str = include_str!("c_header_prelude.h");
+
+/// Generates a C header with a struct and serialization functions for each DSDL type in a
+/// package
+///
+/// The include and exclude namespaces in `config` select the types to generate, in the same
+/// way as for Rust code. The other options do not apply to C code.
+///
+/// `header_name` is the name of the header file (like `types.h`), which is used for the
+/// include guard.
+pub fn generate_c_header(package: &CompiledPackage, config: &Config, header_name: &str) -> String {
+    let guard: String = header_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .chain("_INCLUDED".chars())
+        .collect();
+
+    let mut code = Code::default();
+    code.line("// This file was generated by canadensis_codegen_rust. Do not edit it.");
+    code.line(format!("#ifndef {}", guard));
+    code.line(format!("#define {}", guard));
+    code.line("");
+    for header in ["math.h", "stdbool.h", "stddef.h", "stdint.h", "string.h"] {
+        code.line(format!("#include <{}>", header));
+    }
+    code.line("");
+    code.line("#ifdef __cplusplus");
+    code.line("extern \"C\" {");
+    code.indent = 0;
+    code.line("#endif");
+    code.line("");
+    code.text.push_str(PRELUDE);
+
+    for key in dependency_order(package, config) {
+        let dsdl = match package.get_by_key(&key) {
+            Some(dsdl) => dsdl,
+            None => continue,
+        };
+        code.line("");
+        match &dsdl.kind {
+            DsdlKind::Message(message) => {
+                let name = c_type_name(&key, None);
+                if let Some(subject_id) = dsdl.fixed_port_id {
+                    code.line(format!("#define {}_FIXED_PORT_ID_ {}U", name, subject_id));
+                }
+                write_type(&mut code, &key, message, &name);
+            }
+            DsdlKind::Service { request, response } => {
+                if let Some(service_id) = dsdl.fixed_port_id {
+                    code.line(format!(
+                        "#define {}_FIXED_PORT_ID_ {}U",
+                        c_type_name(&key, None),
+                        service_id
+                    ));
+                }
+                write_type(
+                    &mut code,
+                    &key,
+                    request,
+                    &c_type_name(&key, Some("Request")),
+                );
+                code.line("");
+                write_type(
+                    &mut code,
+                    &key,
+                    response,
+                    &c_type_name(&key, Some("Response")),
+                );
+            }
+        }
+    }
+
+    code.line("");
+    code.line("#ifdef __cplusplus");
+    code.line("}");
+    code.indent = 0;
+    code.line("#endif");
+    code.line(format!("#endif // {}", guard));
+    code.text
+}
+
+/// Returns the selected types in an order where each type comes after all the types it
+/// depends on
+fn dependency_order(package: &CompiledPackage, config: &Config) -> Vec<TypeKey> {
+    fn visit(
+        key: &TypeKey,
+        package: &CompiledPackage,
+        visited: &mut BTreeSet<TypeKey>,
+        order: &mut Vec<TypeKey>,
+    ) {
+        if !visited.insert(key.clone()) {
+            return;
+        }
+        if let Some(dsdl) = package.get_by_key(key) {
+            let dependencies = match &dsdl.kind {
+                DsdlKind::Message(message) => message.dependencies(),
+                DsdlKind::Service { request, response } => {
+                    let mut dependencies = request.dependencies();
+                    dependencies.extend(response.dependencies());
+                    dependencies
+                }
+            };
+            for dependency in dependencies {
+                visit(dependency, package, visited, order);
+            }
+        }
+        order.push(key.clone());
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut order = Vec::new();
+    for key in selected_types(package, config) {
+        visit(&key, package, &mut visited, &mut order);
+    }
+    order
+}
+
+/// Writes the macros, struct, and functions for a message or one part of a service
+fn write_type(code: &mut Code, key: &TypeKey, message: &Message, name: &str) {
+    write_comments(code, message.comments());
+    code.line(format!("#define {}_FULL_NAME_ \"{}\"", name, key.name()));
+    code.line(format!(
+        "#define {}_FULL_NAME_AND_VERSION_ \"{}\"",
+        name, key
+    ));
+    let max_size_bytes = message.bit_length().max_value().div_ceil(8);
+    let extent_bytes = match message.extent() {
+        Extent::Sealed => max_size_bytes,
+        Extent::Delimited(extent_bits) => extent_bits / 8,
+    };
+    code.line(format!("#define {}_EXTENT_BYTES_ {}UL", name, extent_bytes));
+    code.line(format!(
+        "#define {}_SERIALIZATION_BUFFER_SIZE_BYTES_ {}UL",
+        name, max_size_bytes
+    ));
+    for (constant_name, constant) in message.constants() {
+        write_comments(code, constant.comments());
+        code.line(format!(
+            "#define {}_{} (({}) {})",
+            name,
+            constant_name,
+            c_primitive_type(constant.ty()),
+            c_literal(constant.ty(), constant.value())
+        ));
+    }
+
+    // Struct
+    code.line("typedef struct");
+    code.line("{");
+    match message.kind() {
+        MessageKind::Struct(mstruct) => {
+            let mut empty = true;
+            for field in &mstruct.fields {
+                if let FieldKind::Data { ty, name } = field.kind() {
+                    write_comments(code, field.comments());
+                    write_declaration(code, ty, &c_identifier(name));
+                    empty = false;
+                }
+            }
+            if empty {
+                // C does not allow empty structs
+                code.line("uint8_t _dummy_;");
+            }
+        }
+        MessageKind::Union(union) => {
+            code.line("union");
+            code.line("{");
+            for variant in &union.variants {
+                write_comments(code, variant.comments());
+                write_declaration(code, variant.ty(), &c_identifier(variant.name()));
+            }
+            code.line("};");
+            code.line("/// The index of the active variant");
+            code.line("uint8_t _tag_;");
+        }
+    }
+    code.line(format!("}} {};", name));
+
+    // Serialize
+    code.line(format!(
+        "static inline int8_t {0}_serialize_(const {0}* const obj, uint8_t* const buffer, size_t* const inout_buffer_size_bytes)",
+        name
+    ));
+    code.line("{");
+    code.line("if ((obj == NULL) || (buffer == NULL) || (inout_buffer_size_bytes == NULL)) { return -CANADENSIS_ERROR_INVALID_ARGUMENT; }");
+    code.line("const size_t capacity_bytes = *inout_buffer_size_bytes;");
+    if max_size_bytes != 0 {
+        code.line(format!(
+            "if (capacity_bytes < {}_SERIALIZATION_BUFFER_SIZE_BYTES_) {{ return -CANADENSIS_ERROR_SERIALIZATION_BUFFER_TOO_SMALL; }}",
+            name
+        ));
+    }
+    code.line("size_t offset_bits = 0U;");
+    match message.kind() {
+        MessageKind::Struct(mstruct) => {
+            for field in &mstruct.fields {
+                match field.kind() {
+                    FieldKind::Padding(bits) => {
+                        code.line(format!(
+                            "canadensis_write_bits(buffer, offset_bits, 0U, {}U);",
+                            bits
+                        ));
+                        code.line(format!("offset_bits += {}U;", bits));
+                    }
+                    FieldKind::Data { ty, name } => {
+                        serialize_value(code, ty, &format!("obj->{}", c_identifier(name)), 0)
+                    }
+                }
+            }
+        }
+        MessageKind::Union(union) => {
+            code.line(format!(
+                "canadensis_write_bits(buffer, offset_bits, obj->_tag_, {}U);",
+                union.discriminant_bits
+            ));
+            code.line(format!("offset_bits += {}U;", union.discriminant_bits));
+            code.line("switch (obj->_tag_)");
+            code.line("{");
+            for (i, variant) in union.variants.iter().enumerate() {
+                code.line(format!("case {}U:", i));
+                code.line("{");
+                serialize_value(
+                    code,
+                    variant.ty(),
+                    &format!("obj->{}", c_identifier(variant.name())),
+                    0,
+                );
+                code.line("break;");
+                code.line("}");
+            }
+            code.line("default:");
+            code.line("{");
+            code.line("return -CANADENSIS_ERROR_REPRESENTATION_BAD_UNION_TAG;");
+            code.line("}");
+            code.line("}");
+        }
+    }
+    code.line("offset_bits = canadensis_pad_to_byte(buffer, offset_bits);");
+    code.line("*inout_buffer_size_bytes = offset_bits / 8U;");
+    code.line("return CANADENSIS_SUCCESS;");
+    code.line("}");
+
+    // Deserialize
+    code.line(format!(
+        "static inline int8_t {0}_deserialize_({0}* const out_obj, const uint8_t* buffer, size_t* const inout_buffer_size_bytes)",
+        name
+    ));
+    code.line("{");
+    code.line("if ((out_obj == NULL) || (inout_buffer_size_bytes == NULL) || ((buffer == NULL) && (*inout_buffer_size_bytes != 0U))) { return -CANADENSIS_ERROR_INVALID_ARGUMENT; }");
+    code.line("if (buffer == NULL) { buffer = (const uint8_t*) \"\"; }");
+    code.line("const size_t capacity_bytes = *inout_buffer_size_bytes;");
+    code.line("size_t offset_bits = 0U;");
+    match message.kind() {
+        MessageKind::Struct(mstruct) => {
+            for field in &mstruct.fields {
+                match field.kind() {
+                    FieldKind::Padding(bits) => code.line(format!("offset_bits += {}U;", bits)),
+                    FieldKind::Data { ty, name } => {
+                        deserialize_value(code, ty, &format!("out_obj->{}", c_identifier(name)), 0)
+                    }
+                }
+            }
+        }
+        MessageKind::Union(union) => {
+            code.line(format!(
+                "out_obj->_tag_ = (uint8_t) canadensis_read_bits(buffer, capacity_bytes, offset_bits, {}U);",
+                union.discriminant_bits
+            ));
+            code.line(format!("offset_bits += {}U;", union.discriminant_bits));
+            code.line("switch (out_obj->_tag_)");
+            code.line("{");
+            for (i, variant) in union.variants.iter().enumerate() {
+                code.line(format!("case {}U:", i));
+                code.line("{");
+                deserialize_value(
+                    code,
+                    variant.ty(),
+                    &format!("out_obj->{}", c_identifier(variant.name())),
+                    0,
+                );
+                code.line("break;");
+                code.line("}");
+            }
+            code.line("default:");
+            code.line("{");
+            code.line("return -CANADENSIS_ERROR_REPRESENTATION_BAD_UNION_TAG;");
+            code.line("}");
+            code.line("}");
+        }
+    }
+    code.line("offset_bits = canadensis_align(offset_bits);");
+    code.line("*inout_buffer_size_bytes = ((offset_bits / 8U) < capacity_bytes) ? (offset_bits / 8U) : capacity_bytes;");
+    code.line("return CANADENSIS_SUCCESS;");
+    code.line("}");
+}
+
+/// Writes the declaration of a struct field or union variant
+fn write_declaration(code: &mut Code, ty: &ResolvedType, name: &str) {
+    match ty {
+        ResolvedType::Scalar(scalar) => code.line(format!("{} {};", c_scalar_type(scalar), name)),
+        ResolvedType::FixedArray { inner, len } => {
+            code.line(format!("{} {}[{}];", c_scalar_type(inner), name, len))
+        }
+        ResolvedType::VariableArray { inner, max_len } => {
+            code.line("struct");
+            code.line("{");
+            // C does not allow arrays with zero elements
+            code.line(format!(
+                "{} elements[{}];",
+                c_scalar_type(inner),
+                (*max_len).max(1)
+            ));
+            code.line("size_t count;");
+            code.line(format!("}} {};", name));
+        }
+    }
+}
+
+/// Writes code that serializes a value and advances `offset_bits`
+///
+/// `depth` is used to make unique names for variables in nested blocks.
+fn serialize_value(code: &mut Code, ty: &ResolvedType, expr: &str, depth: usize) {
+    if ty.alignment() == 8 {
+        code.line("offset_bits = canadensis_pad_to_byte(buffer, offset_bits);");
+    }
+    match ty {
+        ResolvedType::Scalar(scalar) => serialize_scalar(code, scalar, expr, depth),
+        ResolvedType::FixedArray { inner, len } => {
+            code.line(format!(
+                "for (size_t i{0} = 0U; i{0} < {1}U; i{0}++)",
+                depth, len
+            ));
+            code.line("{");
+            serialize_scalar(code, inner, &format!("{}[i{}]", expr, depth), depth + 1);
+            code.line("}");
+        }
+        ResolvedType::VariableArray { inner, max_len } => {
+            let length_bits = array_length_bits(ty);
+            code.line(format!(
+                "if ({}.count > {}U) {{ return -CANADENSIS_ERROR_REPRESENTATION_BAD_ARRAY_LENGTH; }}",
+                expr, max_len
+            ));
+            code.line(format!(
+                "canadensis_write_bits(buffer, offset_bits, {}.count, {}U);",
+                expr, length_bits
+            ));
+            code.line(format!("offset_bits += {}U;", length_bits));
+            code.line(format!(
+                "for (size_t i{0} = 0U; i{0} < {1}.count; i{0}++)",
+                depth, expr
+            ));
+            code.line("{");
+            serialize_scalar(
+                code,
+                inner,
+                &format!("{}.elements[i{}]", expr, depth),
+                depth + 1,
+            );
+            code.line("}");
+        }
+    }
+}
+
+fn serialize_scalar(code: &mut Code, ty: &ResolvedScalarType, expr: &str, depth: usize) {
+    match ty {
+        ResolvedScalarType::Composite { key, inner } => {
+            let name = c_type_name(key, None);
+            code.line("offset_bits = canadensis_pad_to_byte(buffer, offset_bits);");
+            code.line("{");
+            let header_bytes = match inner.extent() {
+                Extent::Sealed => 0,
+                Extent::Delimited(_) => 4,
+            };
+            code.line(format!(
+                "size_t size{0} = capacity_bytes - (offset_bits / 8U) - {1}U;",
+                depth, header_bytes
+            ));
+            code.line(format!(
+                "const int8_t result{0} = {1}_serialize_(&{2}, &buffer[(offset_bits / 8U) + {3}U], &size{0});",
+                depth, name, expr, header_bytes
+            ));
+            code.line(format!("if (result{0} < 0) {{ return result{0}; }}", depth));
+            if header_bytes != 0 {
+                code.line(format!(
+                    "canadensis_write_bits(buffer, offset_bits, size{}, 32U);",
+                    depth
+                ));
+                code.line("offset_bits += 32U;");
+            }
+            code.line(format!("offset_bits += size{} * 8U;", depth));
+            code.line("}");
+        }
+        ResolvedScalarType::Primitive(primitive) => {
+            let bits = primitive.bit_length();
+            let value = match primitive {
+                PrimitiveType::Boolean => format!("({} ? 1U : 0U)", expr),
+                PrimitiveType::Utf8 | PrimitiveType::Byte => expr.to_owned(),
+                PrimitiveType::UInt { bits, mode } => {
+                    if *mode == CastMode::Saturated && *bits != round_up_integer_size(*bits) {
+                        let max = (1u64 << bits) - 1;
+                        format!("(({0} > {1}U) ? {1}U : {0})", expr, max)
+                    } else {
+                        expr.to_owned()
+                    }
+                }
+                PrimitiveType::Int { bits } => {
+                    if *bits != round_up_integer_size(*bits) {
+                        let max = (1i64 << (bits - 1)) - 1;
+                        let min = -max - 1;
+                        format!(
+                            "(uint64_t) (int64_t) (({0} > {1}) ? {1} : (({0} < {2}) ? {2} : {0}))",
+                            expr, max, min
+                        )
+                    } else {
+                        format!("(uint64_t) (int64_t) {}", expr)
+                    }
+                }
+                PrimitiveType::Float16 { mode } => match mode {
+                    CastMode::Saturated => format!(
+                        "canadensis_float16_pack(canadensis_float16_saturate({}))",
+                        expr
+                    ),
+                    CastMode::Truncated => format!("canadensis_float16_pack({})", expr),
+                },
+                PrimitiveType::Float32 { .. } => format!("canadensis_float32_bits({})", expr),
+                PrimitiveType::Float64 { .. } => format!("canadensis_float64_bits({})", expr),
+            };
+            code.line(format!(
+                "canadensis_write_bits(buffer, offset_bits, {}, {}U);",
+                value, bits
+            ));
+            code.line(format!("offset_bits += {}U;", bits));
+        }
+        ResolvedScalarType::Void { bits } => {
+            code.line(format!(
+                "canadensis_write_bits(buffer, offset_bits, 0U, {}U);",
+                bits
+            ));
+            code.line(format!("offset_bits += {}U;", bits));
+        }
+    }
+}
+
+/// Writes code that deserializes a value and advances `offset_bits`
+///
+/// `depth` is used to make unique names for variables in nested blocks.
+fn deserialize_value(code: &mut Code, ty: &ResolvedType, expr: &str, depth: usize) {
+    if ty.alignment() == 8 {
+        code.line("offset_bits = canadensis_align(offset_bits);");
+    }
+    match ty {
+        ResolvedType::Scalar(scalar) => deserialize_scalar(code, scalar, expr, depth),
+        ResolvedType::FixedArray { inner, len } => {
+            code.line(format!(
+                "for (size_t i{0} = 0U; i{0} < {1}U; i{0}++)",
+                depth, len
+            ));
+            code.line("{");
+            deserialize_scalar(code, inner, &format!("{}[i{}]", expr, depth), depth + 1);
+            code.line("}");
+        }
+        ResolvedType::VariableArray { inner, max_len } => {
+            let length_bits = array_length_bits(ty);
+            code.line(format!(
+                "{}.count = (size_t) canadensis_read_bits(buffer, capacity_bytes, offset_bits, {}U);",
+                expr, length_bits
+            ));
+            code.line(format!("offset_bits += {}U;", length_bits));
+            code.line(format!(
+                "if ({}.count > {}U) {{ return -CANADENSIS_ERROR_REPRESENTATION_BAD_ARRAY_LENGTH; }}",
+                expr, max_len
+            ));
+            code.line(format!(
+                "for (size_t i{0} = 0U; i{0} < {1}.count; i{0}++)",
+                depth, expr
+            ));
+            code.line("{");
+            deserialize_scalar(
+                code,
+                inner,
+                &format!("{}.elements[i{}]", expr, depth),
+                depth + 1,
+            );
+            code.line("}");
+        }
+    }
+}
+
+fn deserialize_scalar(code: &mut Code, ty: &ResolvedScalarType, expr: &str, depth: usize) {
+    match ty {
+        ResolvedScalarType::Composite { key, inner } => {
+            let name = c_type_name(key, None);
+            code.line("offset_bits = canadensis_align(offset_bits);");
+            code.line("{");
+            match inner.extent() {
+                Extent::Sealed => {
+                    code.line(format!(
+                        "size_t size{} = canadensis_remaining_bytes(capacity_bytes, offset_bits);",
+                        depth
+                    ));
+                }
+                Extent::Delimited(_) => {
+                    code.line(format!(
+                        "size_t size{} = (size_t) canadensis_read_bits(buffer, capacity_bytes, offset_bits, 32U);",
+                        depth
+                    ));
+                    code.line("offset_bits += 32U;");
+                    code.line(format!(
+                        "if (size{} > canadensis_remaining_bytes(capacity_bytes, offset_bits)) {{ return -CANADENSIS_ERROR_REPRESENTATION_BAD_DELIMITER_HEADER; }}",
+                        depth
+                    ));
+                    code.line(format!("const size_t header{0} = size{0};", depth));
+                }
+            }
+            code.line(format!(
+                "const int8_t result{0} = {1}_deserialize_(&{2}, canadensis_buffer_at(buffer, capacity_bytes, offset_bits), &size{0});",
+                depth, name, expr
+            ));
+            code.line(format!("if (result{0} < 0) {{ return result{0}; }}", depth));
+            match inner.extent() {
+                // Skip any fields that a newer version of the type added
+                Extent::Delimited(_) => code.line(format!("offset_bits += header{} * 8U;", depth)),
+                Extent::Sealed => code.line(format!("offset_bits += size{} * 8U;", depth)),
+            }
+            code.line("}");
+        }
+        ResolvedScalarType::Primitive(primitive) => {
+            let bits = primitive.bit_length();
+            let read = format!(
+                "canadensis_read_bits(buffer, capacity_bytes, offset_bits, {}U)",
+                bits
+            );
+            let value = match primitive {
+                PrimitiveType::Boolean => format!("({} != 0U)", read),
+                PrimitiveType::Utf8
+                | PrimitiveType::Byte
+                | PrimitiveType::UInt { .. }
+                | PrimitiveType::Int { .. } => {
+                    let value = match primitive {
+                        PrimitiveType::Int { .. } => {
+                            format!("canadensis_sign_extend({}, {}U)", read, bits)
+                        }
+                        _ => read,
+                    };
+                    format!("({}) {}", c_primitive_type(primitive), value)
+                }
+                PrimitiveType::Float16 { .. } => {
+                    format!("canadensis_float16_unpack((uint16_t) {})", read)
+                }
+                PrimitiveType::Float32 { .. } => {
+                    format!("canadensis_float32_from_bits((uint32_t) {})", read)
+                }
+                PrimitiveType::Float64 { .. } => {
+                    format!("canadensis_float64_from_bits({})", read)
+                }
+            };
+            code.line(format!("{} = {};", expr, value));
+            code.line(format!("offset_bits += {}U;", bits));
+        }
+        ResolvedScalarType::Void { bits } => code.line(format!("offset_bits += {}U;", bits)),
+    }
+}
+
+/// Returns the number of bits in the length of a variable-length array
+fn array_length_bits(ty: &ResolvedType) -> u8 {
+    match ty.implicit_field() {
+        Some(ImplicitField::ArrayLength { bits }) => bits,
+        _ => unreachable!("Variable-length array does not have a length field"),
+    }
+}
+
+/// Returns the C name of a type, like `uavcan_node_Heartbeat_1_0`, or the name of the request
+/// or response part of a service type, like `uavcan_node_GetInfo_Request_1_0`
+fn c_type_name(key: &TypeKey, role: Option<&str>) -> String {
+    let mut name = String::new();
+    for segment in key.name().path() {
+        name.push_str(segment);
+        name.push('_');
+    }
+    name.push_str(key.name().name());
+    if let Some(role) = role {
+        name.push('_');
+        name.push_str(role);
+    }
+    format!("{}_{}_{}", name, key.version().major, key.version().minor)
+}
+
+fn c_scalar_type(ty: &ResolvedScalarType) -> String {
+    match ty {
+        ResolvedScalarType::Composite { key, .. } => c_type_name(key, None),
+        ResolvedScalarType::Primitive(primitive) => c_primitive_type(primitive),
+        ResolvedScalarType::Void { .. } => unreachable!("Void type in a data field"),
+    }
+}
+
+fn c_primitive_type(ty: &PrimitiveType) -> String {
+    match ty {
+        PrimitiveType::Boolean => "bool".to_owned(),
+        PrimitiveType::Utf8 | PrimitiveType::Byte => "uint8_t".to_owned(),
+        PrimitiveType::Int { bits } => format!("int{}_t", round_up_integer_size(*bits)),
+        PrimitiveType::UInt { bits, .. } => format!("uint{}_t", round_up_integer_size(*bits)),
+        PrimitiveType::Float16 { .. } | PrimitiveType::Float32 { .. } => "float".to_owned(),
+        PrimitiveType::Float64 { .. } => "double".to_owned(),
+    }
+}
+
+/// Returns a C literal with the value of a constant
+fn c_literal(ty: &PrimitiveType, value: &ConstantValue) -> String {
+    let wide = ty.bit_length() > 32;
+    match value {
+        ConstantValue::Boolean(inner) => inner.to_string(),
+        ConstantValue::Int(inner) => match ty {
+            PrimitiveType::Int { .. } if wide => format!("{}LL", inner),
+            PrimitiveType::Int { .. } => inner.to_string(),
+            _ if wide => format!("{}ULL", inner),
+            _ => format!("{}U", inner),
+        },
+        ConstantValue::Float16(inner) => format!("{:?}", f32::from(*inner)),
+        ConstantValue::Float32(inner) => format!("{:?}", inner),
+        ConstantValue::Float64(inner) => format!("{:?}", inner),
+    }
+}
+
+/// Returns a C identifier for a field or variant name, which gets an underscore at the end if it
+/// is a C keyword
+fn c_identifier(name: &str) -> String {
+    const KEYWORDS: [&str; 40] = [
+        "auto",
+        "bool",
+        "break",
+        "case",
+        "char",
+        "const",
+        "continue",
+        "default",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extern",
+        "false",
+        "float",
+        "for",
+        "goto",
+        "if",
+        "inline",
+        "int",
+        "long",
+        "register",
+        "restrict",
+        "return",
+        "short",
+        "signed",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "true",
+        "typedef",
+        "union",
+        "unsigned",
+        "void",
+        "volatile",
+        "while",
+        "_Bool",
+        "_Complex",
+        "_Imaginary",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Writes DSDL comments as C comments
+fn write_comments(code: &mut Code, comments: &str) {
+    for line in comments.lines() {
+        // A backslash at the end of a line would continue the comment on the next line
+        code.line(format!("//{}", line.trim_end_matches('\\')).trim_end());
+    }
+}
+
+/// C code with indentation
+#[derive(Default)]
+struct Code {
+    text: String,
+    indent: usize,
+}
+
+impl Code {
+    /// Adds a line
+    ///
+    /// A line that starts with `}` ends a block, and a line that ends with `{` starts a block.
+    fn line<S: AsRef<str>>(&mut self, line: S) {
+        let line = line.as_ref();
+        if line.starts_with('}') {
+            self.indent = self.indent.saturating_sub(1);
+        }
+        if !line.is_empty() {
+            for _ in 0..self.indent {
+                self.text.push_str("    ");
+            }
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+        if line.ends_with('{') {
+            self.indent += 1;
+        }
+    }
+}
@@ -0,0 +1,215 @@
+// Definitions used by all headers generated by canadensis_codegen_rust
+#ifndef CANADENSIS_C_HEADER_PRELUDE_INCLUDED
+#define CANADENSIS_C_HEADER_PRELUDE_INCLUDED
+
+#define CANADENSIS_SUCCESS 0
+#define CANADENSIS_ERROR_INVALID_ARGUMENT 2
+#define CANADENSIS_ERROR_SERIALIZATION_BUFFER_TOO_SMALL 3
+#define CANADENSIS_ERROR_REPRESENTATION_BAD_ARRAY_LENGTH 10
+#define CANADENSIS_ERROR_REPRESENTATION_BAD_UNION_TAG 11
+#define CANADENSIS_ERROR_REPRESENTATION_BAD_DELIMITER_HEADER 12
+
+// Writes the least significant length_bits bits of value, least significant bit first
+static inline void canadensis_write_bits(uint8_t* const buffer, const size_t offset_bits, const uint64_t value, const uint8_t length_bits)
+{
+    for (uint8_t i = 0U; i < length_bits; i++)
+    {
+        const size_t bit = offset_bits + i;
+        const uint8_t mask = (uint8_t) (1U << (bit % 8U));
+        if (((value >> i) & 1U) != 0U)
+        {
+            buffer[bit / 8U] |= mask;
+        }
+        else
+        {
+            buffer[bit / 8U] &= (uint8_t) ~mask;
+        }
+    }
+}
+
+// Reads length_bits bits, least significant bit first
+//
+// Bits after the end of the buffer are zero (the implicit zero extension rule).
+static inline uint64_t canadensis_read_bits(const uint8_t* const buffer, const size_t capacity_bytes, const size_t offset_bits, const uint8_t length_bits)
+{
+    uint64_t value = 0U;
+    for (uint8_t i = 0U; i < length_bits; i++)
+    {
+        const size_t bit = offset_bits + i;
+        if (((bit / 8U) < capacity_bytes) && (((buffer[bit / 8U] >> (bit % 8U)) & 1U) != 0U))
+        {
+            value |= ((uint64_t) 1U) << i;
+        }
+    }
+    return value;
+}
+
+static inline int64_t canadensis_sign_extend(uint64_t value, const uint8_t length_bits)
+{
+    if ((length_bits < 64U) && (((value >> (length_bits - 1U)) & 1U) != 0U))
+    {
+        value |= UINT64_MAX << length_bits;
+    }
+    return (int64_t) value;
+}
+
+// Writes zero bits up to the next byte boundary and returns the new offset
+static inline size_t canadensis_pad_to_byte(uint8_t* const buffer, size_t offset_bits)
+{
+    while ((offset_bits % 8U) != 0U)
+    {
+        canadensis_write_bits(buffer, offset_bits, 0U, 1U);
+        offset_bits++;
+    }
+    return offset_bits;
+}
+
+static inline size_t canadensis_align(const size_t offset_bits)
+{
+    return (offset_bits + 7U) / 8U * 8U;
+}
+
+// Returns the number of bytes in the buffer after a byte-aligned offset
+static inline size_t canadensis_remaining_bytes(const size_t capacity_bytes, const size_t offset_bits)
+{
+    return ((offset_bits / 8U) < capacity_bytes) ? (capacity_bytes - (offset_bits / 8U)) : 0U;
+}
+
+// Returns a pointer to the byte at a byte-aligned offset, or to the end of the buffer if the
+// offset is after the end
+static inline const uint8_t* canadensis_buffer_at(const uint8_t* const buffer, const size_t capacity_bytes, const size_t offset_bits)
+{
+    return &buffer[((offset_bits / 8U) < capacity_bytes) ? (offset_bits / 8U) : capacity_bytes];
+}
+
+static inline float canadensis_float16_saturate(const float value)
+{
+    // Infinity and NaN are not changed
+    if (isfinite(value))
+    {
+        if (value > 65504.0F)
+        {
+            return 65504.0F;
+        }
+        if (value < -65504.0F)
+        {
+            return -65504.0F;
+        }
+    }
+    return value;
+}
+
+// Converts a float to the bits of a binary16 value, rounding to the nearest value
+static inline uint16_t canadensis_float16_pack(const float value)
+{
+    uint32_t bits;
+    memcpy(&bits, &value, sizeof(bits));
+    const uint32_t sign = (bits >> 16U) & 0x8000U;
+    const uint32_t exponent = (bits >> 23U) & 0xFFU;
+    uint32_t mantissa = bits & 0x7FFFFFU;
+    if (exponent == 0xFFU)
+    {
+        // Infinity or NaN
+        return (uint16_t) (sign | 0x7C00U | ((mantissa != 0U) ? 0x200U : 0U));
+    }
+    const int32_t half_exponent = ((int32_t) exponent) - 127 + 15;
+    if (half_exponent >= 0x1F)
+    {
+        // Too large, becomes infinity
+        return (uint16_t) (sign | 0x7C00U);
+    }
+    uint32_t half;
+    uint32_t remainder;
+    uint32_t halfway;
+    if (half_exponent <= 0)
+    {
+        // Subnormal or zero
+        if (half_exponent < -10)
+        {
+            return (uint16_t) sign;
+        }
+        mantissa |= 0x800000U;
+        const uint32_t shift = (uint32_t) (14 - half_exponent);
+        half = mantissa >> shift;
+        remainder = mantissa & ((((uint32_t) 1U) << shift) - 1U);
+        halfway = ((uint32_t) 1U) << (shift - 1U);
+    }
+    else
+    {
+        half = (((uint32_t) half_exponent) << 10U) | (mantissa >> 13U);
+        remainder = mantissa & 0x1FFFU;
+        halfway = 0x1000U;
+    }
+    // Round to nearest, ties to even (a carry into the exponent is correct)
+    if ((remainder > halfway) || ((remainder == halfway) && ((half & 1U) != 0U)))
+    {
+        half++;
+    }
+    return (uint16_t) (sign | half);
+}
+
+// Converts the bits of a binary16 value to a float
+static inline float canadensis_float16_unpack(const uint16_t value)
+{
+    const uint32_t sign = ((uint32_t) value & 0x8000U) << 16U;
+    uint32_t exponent = ((uint32_t) value >> 10U) & 0x1FU;
+    uint32_t mantissa = (uint32_t) value & 0x3FFU;
+    uint32_t bits;
+    if (exponent == 0x1FU)
+    {
+        // Infinity or NaN
+        bits = sign | 0x7F800000U | (mantissa << 13U);
+    }
+    else if (exponent != 0U)
+    {
+        bits = sign | ((exponent + 112U) << 23U) | (mantissa << 13U);
+    }
+    else if (mantissa == 0U)
+    {
+        bits = sign;
+    }
+    else
+    {
+        // Subnormal, becomes a normal float
+        exponent = 113U;
+        while ((mantissa & 0x400U) == 0U)
+        {
+            mantissa <<= 1U;
+            exponent--;
+        }
+        bits = sign | (exponent << 23U) | ((mantissa & 0x3FFU) << 13U);
+    }
+    float out;
+    memcpy(&out, &bits, sizeof(out));
+    return out;
+}
+
+static inline uint32_t canadensis_float32_bits(const float value)
+{
+    uint32_t bits;
+    memcpy(&bits, &value, sizeof(bits));
+    return bits;
+}
+
+static inline float canadensis_float32_from_bits(const uint32_t bits)
+{
+    float value;
+    memcpy(&value, &bits, sizeof(value));
+    return value;
+}
+
+static inline uint64_t canadensis_float64_bits(const double value)
+{
+    uint64_t bits;
+    memcpy(&bits, &value, sizeof(bits));
+    return bits;
+}
+
+static inline double canadensis_float64_from_bits(const uint64_t bits)
+{
+    double value;
+    memcpy(&value, &bits, sizeof(value));
+    return value;
+}
+
+#endif // CANADENSIS_C_HEADER_PRELUDE_INCLUDED
@@ -0,0 +1,244 @@
+//! A record of the inputs and outputs of code generation
+//!
+//! When the DSDL files, options, and code generator version are the same as the last time code
+//! was generated, and the output files have not been changed, there is no need to compile the
+//! DSDL files and generate code again. When something has changed, output files that would get the
+//! same content are not written again, so their modification times do not change.
+//!
+//! The cache is a text file. Each line is a key followed by a space and a value:
+//! * `version`: The version of this cache format
+//! * `fingerprint`: The fingerprint of the inputs, as a hexadecimal number
+//! * `output`: The hash of the generated content of an output file, the hash of the content
+//!   that was written to the file (after formatting), and the path to the file, separated by spaces
+//!
+//! Hashes use the 64-bit FNV-1a function, which does not change between Rust versions.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// The version of the cache file format
+const CACHE_VERSION: u32 = 1;
+
+/// A record of the inputs and outputs of one code generation run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cache {
+    /// The fingerprint of the inputs
+    fingerprint: u64,
+    /// The hashes of each output file, before and after formatting
+    outputs: BTreeMap<PathBuf, OutputHashes>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OutputHashes {
+    /// The hash of the code that was generated
+    generated: u64,
+    /// The hash of the content that was in the file after it was written and formatted
+    written: u64,
+}
+
+impl Cache {
+    /// Creates an empty cache for inputs with the provided fingerprint
+    pub fn new(fingerprint: u64) -> Self {
+        Cache {
+            fingerprint,
+            outputs: BTreeMap::new(),
+        }
+    }
+
+    /// Reads a cache from a file
+    ///
+    /// This function returns `Ok(None)` if the file does not exist or does not contain a valid
+    /// cache (for example, if it was written by an incompatible version of this library).
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Option<Cache>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Cache::parse(&content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(content: &str) -> Option<Cache> {
+        let mut version = None;
+        let mut fingerprint = None;
+        let mut outputs = BTreeMap::new();
+        for line in content.lines() {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "version" => version = Some(value.parse::<u32>().ok()?),
+                "fingerprint" => fingerprint = Some(u64::from_str_radix(value, 16).ok()?),
+                "output" => {
+                    let mut parts = value.splitn(3, ' ');
+                    let generated = u64::from_str_radix(parts.next()?, 16).ok()?;
+                    let written = u64::from_str_radix(parts.next()?, 16).ok()?;
+                    let path = PathBuf::from(parts.next()?);
+                    outputs.insert(path, OutputHashes { generated, written });
+                }
+                _ => return None,
+            }
+        }
+        if version != Some(CACHE_VERSION) {
+            return None;
+        }
+        Some(Cache {
+            fingerprint: fingerprint?,
+            outputs,
+        })
+    }
+
+    /// Writes this cache to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut content = format!(
+            "version {}\nfingerprint {:016x}\n",
+            CACHE_VERSION, self.fingerprint
+        );
+        for (path, hashes) in &self.outputs {
+            let path = path.to_str().ok_or_else(|| {
+                io::Error::new(ErrorKind::InvalidInput, "Output path is not valid UTF-8")
+            })?;
+            content.push_str(&format!(
+                "output {:016x} {:016x} {}\n",
+                hashes.generated, hashes.written, path
+            ));
+        }
+        fs::write(path, content)
+    }
+
+    /// Returns true if this cache has the provided fingerprint and every output file still has
+    /// the content that was written to it
+    pub fn is_fresh(&self, fingerprint: u64) -> io::Result<bool> {
+        if self.fingerprint != fingerprint || self.outputs.is_empty() {
+            return Ok(false);
+        }
+        for (path, hashes) in &self.outputs {
+            if hash_file(path)? != Some(hashes.written) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns true if an output file was generated with the provided content and still has the
+    /// content that was written to it, so it does not need to be written again
+    pub fn output_unchanged(&self, path: &Path, generated: &str) -> io::Result<bool> {
+        match self.outputs.get(path) {
+            Some(hashes) if hashes.generated == hash_bytes(generated.as_bytes()) => {
+                Ok(hash_file(path)? == Some(hashes.written))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Records an output file that has been written
+    ///
+    /// `generated` is the code that was generated for the file. This function reads the file to
+    /// find the content that was written, which may be different if it was formatted.
+    pub fn add_output(&mut self, path: &Path, generated: &str) -> io::Result<()> {
+        let written = fs::read(path)?;
+        self.outputs.insert(
+            path.to_owned(),
+            OutputHashes {
+                generated: hash_bytes(generated.as_bytes()),
+                written: hash_bytes(&written),
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Reads a file and returns its hash, or None if the file does not exist
+fn hash_file(path: &Path) -> io::Result<Option<u64>> {
+    match fs::read(path) {
+        Ok(content) => Ok(Some(hash_bytes(&content))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub(crate) fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = FingerprintHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// A hasher that produces the same results on all platforms and Rust versions, for calculating
+/// the fingerprints of inputs
+///
+/// This uses the 64-bit FNV-1a function. It is not resistant to collisions that are created
+/// on purpose.
+#[derive(Debug, Clone)]
+pub struct FingerprintHasher(u64);
+
+impl FingerprintHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Creates a hasher with no data
+    pub fn new() -> Self {
+        FingerprintHasher(Self::OFFSET_BASIS)
+    }
+}
+
+impl Default for FingerprintHasher {
+    fn default() -> Self {
+        FingerprintHasher::new()
+    }
+}
+
+impl Hasher for FingerprintHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    // The default implementations of the other write functions use native-endian bytes
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hash_bytes, Cache, OutputHashes};
+    use std::path::PathBuf;
+
+    #[test]
+    fn fnv_1a() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, hash_bytes(b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, hash_bytes(b"a"));
+        assert_eq!(0x0dcd_7ab2_4086_a3c0, hash_bytes(b"canadensis"));
+    }
+
+    #[test]
+    fn parse_save_format() {
+        let mut cache = Cache::new(0x1234);
+        cache.outputs.insert(
+            PathBuf::from("src/uavcan/node file.rs"),
+            OutputHashes {
+                generated: 1,
+                written: 0xffff_ffff_ffff_ffff,
+            },
+        );
+        let content = "version 1\nfingerprint 0000000000001234\noutput 0000000000000001 ffffffffffffffff src/uavcan/node file.rs\n";
+        assert_eq!(Some(cache), Cache::parse(content));
+        assert_eq!(None, Cache::parse("version 0\nfingerprint 0\n"));
+        assert_eq!(None, Cache::parse("something else"));
+    }
+}
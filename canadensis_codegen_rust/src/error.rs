@@ -34,4 +34,8 @@ pub enum Error {
         #[source]
         inner: Box<dyn std::error::Error>,
     },
+    #[error("Derive macro {derive} requires std, which is not allowed with the no_std option")]
+    StdDerive { derive: String },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
 }
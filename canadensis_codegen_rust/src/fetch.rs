@@ -0,0 +1,276 @@
+//! Downloading pinned revisions of DSDL repositories
+//!
+//! Instead of copying the public regulated data types (or another repository of DSDL files) into
+//! a project, a project can record a repository URL, a revision, and a hash of the content.
+//! [`fetch`] downloads an archive of that revision, checks the hash, and unpacks it into a cache
+//! directory. The unpacked directory can be used as a DSDL root directory. Later calls use the
+//! cached files without downloading them again.
+//!
+//! The repository must be on a host that provides archives at
+//! `<repository>/archive/<revision>.tar.gz`, like GitHub. The revision should be a full commit
+//! hash, because branches and tags can move.
+//!
+//! The hash is a SHA-256 hash of the unpacked files, not the archive, so it does not depend on
+//! how the host compresses the archive. It includes the relative path (with `/` separators),
+//! length, and content of each regular file, sorted by path. [`content_hash`] calculates it.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+
+/// The URL of the public regulated data types repository
+pub const PUBLIC_REGULATED_DATA_TYPES: &str =
+    "https://github.com/OpenCyphal/public_regulated_data_types";
+
+/// A revision of a remote repository of DSDL files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remote {
+    /// The URL of the repository, like `https://github.com/OpenCyphal/public_regulated_data_types`
+    pub repository: String,
+    /// The revision to download, preferably a full commit hash
+    pub revision: String,
+    /// The expected SHA-256 hash of the content, as lowercase hexadecimal
+    ///
+    /// If this is `None`, the content is not checked.
+    pub sha256: Option<String>,
+}
+
+impl Remote {
+    /// Creates a remote with a repository URL and revision, and no expected hash
+    pub fn new<R: Into<String>, V: Into<String>>(repository: R, revision: V) -> Self {
+        Remote {
+            repository: repository.into(),
+            revision: revision.into(),
+            sha256: None,
+        }
+    }
+
+    /// Creates a remote for a revision of the public regulated data types
+    pub fn public_regulated_data_types<V: Into<String>>(revision: V) -> Self {
+        Remote::new(PUBLIC_REGULATED_DATA_TYPES, revision)
+    }
+
+    /// Sets the expected SHA-256 hash of the content
+    pub fn with_sha256<S: Into<String>>(mut self, sha256: S) -> Self {
+        self.sha256 = Some(sha256.into());
+        self
+    }
+
+    /// Returns the URL of the archive of this revision
+    pub fn archive_url(&self) -> String {
+        let repository = self.repository.trim_end_matches('/');
+        let repository = repository.strip_suffix(".git").unwrap_or(repository);
+        format!("{}/archive/{}.tar.gz", repository, self.revision)
+    }
+
+    /// Returns the name of the directory in the cache directory that contains this revision
+    fn directory_name(&self) -> String {
+        let repository = self.repository.trim_end_matches('/');
+        let repository = repository.strip_suffix(".git").unwrap_or(repository);
+        let name = repository.rsplit('/').next().unwrap_or(repository);
+        format!("{}-{}", name, self.revision)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Checks that a hash matches the expected hash, if any
+    fn check_hash(&self, actual: &str) -> Result<(), FetchError> {
+        match &self.sha256 {
+            Some(expected) if !expected.eq_ignore_ascii_case(actual) => {
+                Err(FetchError::HashMismatch {
+                    repository: self.repository.clone(),
+                    revision: self.revision.clone(),
+                    expected: expected.clone(),
+                    actual: actual.to_owned(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A revision that has been downloaded and unpacked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    /// The directory that contains the files, which can be used as a DSDL root directory
+    pub path: PathBuf,
+    /// The SHA-256 hash of the content, as lowercase hexadecimal
+    pub sha256: String,
+}
+
+/// Errors that can occur when fetching DSDL files
+#[derive(thiserror::Error, Debug)]
+pub enum FetchError {
+    /// The archive could not be downloaded
+    #[error("Failed to download {url}")]
+    Download {
+        url: String,
+        #[source]
+        inner: Box<ureq::Error>,
+    },
+    /// The content does not have the expected hash
+    #[error("{repository} at revision {revision} has SHA-256 hash {actual}, but {expected} was expected")]
+    HashMismatch {
+        repository: String,
+        revision: String,
+        expected: String,
+        actual: String,
+    },
+    /// A file could not be read or written, or the archive is not valid
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// Downloads and unpacks a revision into a subdirectory of `cache_directory`, or uses a copy
+/// that was already downloaded
+///
+/// This function returns an error if the archive could not be downloaded or unpacked, or the
+/// content (downloaded or cached) does not have the expected hash.
+pub fn fetch<P: AsRef<Path>>(remote: &Remote, cache_directory: P) -> Result<Fetched, FetchError> {
+    let path = cache_directory.as_ref().join(remote.directory_name());
+    if path.is_dir() {
+        // The hash is checked again in case the cached files were modified
+        let sha256 = content_hash(&path)?;
+        remote.check_hash(&sha256)?;
+        return Ok(Fetched { path, sha256 });
+    }
+    let url = remote.archive_url();
+    let response = ureq::get(&url).call().map_err(|e| FetchError::Download {
+        url: url.clone(),
+        inner: Box::new(e),
+    })?;
+    install_archive(remote, response.into_reader(), cache_directory)
+}
+
+/// Unpacks a `.tar.gz` archive of a revision that was already downloaded into a subdirectory of
+/// `cache_directory`, replacing any existing copy
+///
+/// The archive must have one top-level directory that contains the files, like the archives
+/// that GitHub provides. Entries that are not regular files or directories are ignored.
+pub fn install_archive<R: Read, P: AsRef<Path>>(
+    remote: &Remote,
+    archive: R,
+    cache_directory: P,
+) -> Result<Fetched, FetchError> {
+    let cache_directory = cache_directory.as_ref();
+    let path = cache_directory.join(remote.directory_name());
+    // Unpack into a temporary directory first, so that an interrupted download does not leave
+    // files that look complete
+    let partial_path = cache_directory.join(format!(".{}.partial", remote.directory_name()));
+    if partial_path.exists() {
+        fs::remove_dir_all(&partial_path)?;
+    }
+    fs::create_dir_all(&partial_path)?;
+
+    let result = unpack(archive, &partial_path)
+        .map_err(FetchError::from)
+        .and_then(|()| {
+            let sha256 = content_hash(&partial_path)?;
+            remote.check_hash(&sha256)?;
+            Ok(sha256)
+        });
+    let sha256 = match result {
+        Ok(sha256) => sha256,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&partial_path);
+            return Err(e);
+        }
+    };
+    if path.exists() {
+        fs::remove_dir_all(&path)?;
+    }
+    fs::rename(&partial_path, &path)?;
+    Ok(Fetched { path, sha256 })
+}
+
+/// Unpacks the regular files and directories in a `.tar.gz` archive, without the top-level
+/// directory
+fn unpack<R: Read>(archive: R, destination: &Path) -> io::Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(archive));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_type = entry.header().entry_type();
+        if !(entry_type.is_file() || entry_type.is_dir()) {
+            continue;
+        }
+        let entry_path = entry.path()?.into_owned();
+        let mut components = entry_path.components();
+        // Skip the top-level directory
+        components.next();
+        let relative = components.as_path();
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid path in archive: {}", entry_path.display()),
+            ));
+        }
+        let target = destination.join(relative);
+        if entry_type.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            entry.unpack(&target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Calculates the SHA-256 hash of the regular files in a directory and its subdirectories, as
+/// lowercase hexadecimal
+pub fn content_hash<P: AsRef<Path>>(directory: P) -> io::Result<String> {
+    let mut files = Vec::new();
+    find_files(directory.as_ref(), String::new(), &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for (relative_path, path) in files {
+        let content = fs::read(path)?;
+        hasher.update(relative_path.as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Adds the relative path (with `/` separators) and full path of each regular file in a
+/// directory and its subdirectories to `files`
+fn find_files(
+    directory: &Path,
+    prefix: String,
+    files: &mut Vec<(String, PathBuf)>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let relative_path = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if file_type.is_dir() {
+            find_files(&entry.path(), format!("{}/", relative_path), files)?;
+        } else if file_type.is_file() {
+            files.push((relative_path, entry.path()));
+        }
+    }
+    Ok(())
+}
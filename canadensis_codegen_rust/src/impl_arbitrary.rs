@@ -0,0 +1,184 @@
+//! Implements arbitrary::Arbitrary for a type, when the arbitrary feature is enabled
+
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{round_up_integer_size, GeneratedField, GeneratedType, GeneratedTypeKind};
+
+pub(crate) struct ImplementArbitrary<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementArbitrary<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "#[cfg(feature = \"arbitrary\")]")?;
+        writeln!(
+            f,
+            "impl<'a> ::arbitrary::Arbitrary<'a> for {} {{",
+            self.0.name.type_name
+        )?;
+        writeln!(
+            f,
+            "fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {{"
+        )?;
+        match &self.0.kind {
+            GeneratedTypeKind::Struct(gstruct) => {
+                writeln!(f, "Ok(Self {{")?;
+                for field in &gstruct.fields {
+                    if let GeneratedField::Data(field) = field {
+                        writeln!(f, "{}: {},", field.name, ArbitraryValue(field.cyphal_ty))?;
+                    }
+                }
+                writeln!(f, "}})")?;
+            }
+            GeneratedTypeKind::Enum(genum) => {
+                if genum.variants.is_empty() {
+                    writeln!(f, "Err(::arbitrary::Error::IncorrectFormat)")?;
+                } else {
+                    // Choose a variant by its index, which may be different from its discriminant
+                    writeln!(
+                        f,
+                        "Ok(match u.int_in_range(0..={}usize)? {{",
+                        genum.variants.len() - 1
+                    )?;
+                    for (i, variant) in genum.variants.iter().enumerate() {
+                        match &variant.ty {
+                            Some(ty) => writeln!(
+                                f,
+                                "{} => Self::{}({}),",
+                                i,
+                                variant.name,
+                                ArbitraryValue(&ty.cyphal_ty)
+                            )?,
+                            None => writeln!(f, "{} => Self::{},", i, variant.name)?,
+                        }
+                    }
+                    writeln!(f, "_ => unreachable!(),")?;
+                    writeln!(f, "}})")?;
+                }
+            }
+        }
+        // End function
+        writeln!(f, "}}")?;
+        // End impl
+        writeln!(f, "}}")
+    }
+}
+
+/// An expression that creates an arbitrary value of a type using `u`
+///
+/// The value is always in the range of the Cyphal type, so it serializes without saturation or
+/// truncation. Arrays of arbitrary length are no longer than their maximum lengths.
+struct ArbitraryValue<'t>(&'t ResolvedType);
+
+impl Display for ArbitraryValue<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            ResolvedType::Scalar(scalar) => Display::fmt(&ArbitraryScalar(scalar), f),
+            ResolvedType::FixedArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
+                len,
+            } => write!(
+                f,
+                "{{ let mut bits = ::canadensis_encoding::bits::BitArray::new({len}); \
+                for i in 0..{len} {{ bits.set(i, u.arbitrary()?); }} bits }}",
+                len = len
+            ),
+            ResolvedType::VariableArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
+                max_len,
+            } => write!(
+                f,
+                "{{ let length = u.int_in_range(0..={}usize)?; \
+                let mut bits = ::canadensis_encoding::bits::BitArray::new(length); \
+                for i in 0..length {{ bits.set(i, u.arbitrary()?); }} bits }}",
+                max_len
+            ),
+            ResolvedType::FixedArray { inner, len } => {
+                if arbitrary_is_valid(inner) {
+                    // Arbitrary is implemented for arrays of these types
+                    write!(f, "u.arbitrary()?")
+                } else {
+                    write!(
+                        f,
+                        "{{ let mut elements = ::heapless::Vec::<_, {len}>::new(); \
+                        for _ in 0..{len} {{ let _ = elements.push({inner}); }} \
+                        elements.into_array().ok().expect(\"Incorrect array length\") }}",
+                        len = len,
+                        inner = ArbitraryScalar(inner)
+                    )
+                }
+            }
+            ResolvedType::VariableArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Utf8),
+                max_len,
+            } => write!(
+                f,
+                "{{ let length = u.int_in_range(0..={}usize)?; \
+                let mut string = ::heapless::String::new(); \
+                for _ in 0..length {{ if string.push(u.arbitrary()?).is_err() {{ break; }} }} string }}",
+                max_len
+            ),
+            ResolvedType::VariableArray { inner, max_len } => write!(
+                f,
+                "{{ let length = u.int_in_range(0..={}usize)?; \
+                let mut elements = ::heapless::Vec::new(); \
+                for _ in 0..length {{ let _ = elements.push({}); }} elements }}",
+                max_len,
+                ArbitraryScalar(inner)
+            ),
+        }
+    }
+}
+
+struct ArbitraryScalar<'t>(&'t ResolvedScalarType);
+
+impl Display for ArbitraryScalar<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            ResolvedScalarType::Primitive(PrimitiveType::Int { bits })
+                if !arbitrary_is_valid(self.0) =>
+            {
+                let max = (1i64 << (bits - 1)) - 1;
+                let min = -max - 1;
+                let rust_bits = round_up_integer_size(*bits);
+                write!(
+                    f,
+                    "u.int_in_range({}i{}..={}i{})?",
+                    min, rust_bits, max, rust_bits
+                )
+            }
+            ResolvedScalarType::Primitive(PrimitiveType::UInt { bits, .. })
+                if !arbitrary_is_valid(self.0) =>
+            {
+                let max = (1u64 << bits) - 1;
+                write!(
+                    f,
+                    "u.int_in_range(0u{}..={}u{})?",
+                    round_up_integer_size(*bits),
+                    max,
+                    round_up_integer_size(*bits)
+                )
+            }
+            ResolvedScalarType::Primitive(PrimitiveType::Float16 { .. }) => {
+                write!(f, "::half::f16::from_bits(u.arbitrary()?)")
+            }
+            ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+            _ => write!(f, "u.arbitrary()?"),
+        }
+    }
+}
+
+/// Returns true if the Arbitrary implementation of the Rust type that represents this scalar
+/// type always produces valid values of the scalar type
+fn arbitrary_is_valid(scalar: &ResolvedScalarType) -> bool {
+    match scalar {
+        ResolvedScalarType::Primitive(PrimitiveType::Int { bits })
+        | ResolvedScalarType::Primitive(PrimitiveType::UInt { bits, .. }) => {
+            matches!(bits, 8 | 16 | 32 | 64)
+        }
+        // half::f16 may not implement Arbitrary
+        ResolvedScalarType::Primitive(PrimitiveType::Float16 { .. }) => false,
+        // Generated composite types implement Arbitrary
+        ResolvedScalarType::Composite { .. } | ResolvedScalarType::Primitive(_) => true,
+        ResolvedScalarType::Void { .. } => false,
+    }
+}
@@ -0,0 +1,132 @@
+//! Generates a builder for a struct type
+//!
+//! The builder has one const generic `bool` parameter for each field, which becomes true when
+//! that field is set. `build()` is only available when all the parameters are true, so a
+//! missing field is a compile error.
+
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{GeneratedDataField, GeneratedField, GeneratedType, GeneratedTypeKind};
+
+pub(crate) struct ImplementBuilder<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementBuilder<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ty = self.0;
+        let fields: Vec<&GeneratedDataField<'_>> = match &ty.kind {
+            GeneratedTypeKind::Struct(gstruct) => gstruct
+                .fields
+                .iter()
+                .filter_map(|field| match field {
+                    GeneratedField::Data(data) => Some(data),
+                    GeneratedField::Padding(_) => None,
+                })
+                .collect(),
+            GeneratedTypeKind::Enum(_) => return Ok(()),
+        };
+        if fields.is_empty() {
+            return Ok(());
+        }
+        let type_name = &ty.name.type_name;
+        let builder_name = format!("{}Builder", type_name);
+        let parameters: Vec<String> = fields
+            .iter()
+            .map(|field| field.name.to_uppercase())
+            .collect();
+
+        // Entry point
+        writeln!(f, "impl {} {{", type_name)?;
+        writeln!(
+            f,
+            "/// Returns a builder that requires a value for each field"
+        )?;
+        writeln!(
+            f,
+            "pub fn builder() -> {}<{}> {{",
+            builder_name,
+            vec!["false"; fields.len()].join(", ")
+        )?;
+        writeln!(
+            f,
+            "{} {{ value: ::core::default::Default::default() }}",
+            builder_name
+        )?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")?;
+
+        // Builder type
+        writeln!(f, "/// A builder for [`{}`]", type_name)?;
+        writeln!(f, "///")?;
+        writeln!(
+            f,
+            "/// Each parameter is true if the corresponding field has been set."
+        )?;
+        if let Some(deprecated) = &ty.deprecated {
+            writeln!(f, "{}", deprecated)?;
+        }
+        writeln!(
+            f,
+            "pub struct {}<{}> {{",
+            builder_name,
+            parameters
+                .iter()
+                .map(|parameter| format!("const {}: bool", parameter))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(f, "value: {},", type_name)?;
+        writeln!(f, "}}")?;
+
+        // One setter for each field, available only if the field has not been set
+        for (i, field) in fields.iter().enumerate() {
+            let other_parameters: Vec<String> = parameters
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, parameter)| format!("const {}: bool", parameter))
+                .collect();
+            let arguments = |set: &str| -> String {
+                parameters
+                    .iter()
+                    .enumerate()
+                    .map(|(j, parameter)| if j == i { set } else { parameter.as_str() })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writeln!(
+                f,
+                "impl<{}> {}<{}> {{",
+                other_parameters.join(", "),
+                builder_name,
+                arguments("false")
+            )?;
+            writeln!(f, "/// Sets the `{}` field", field.name)?;
+            writeln!(
+                f,
+                "pub fn {name}(self, {name}: {ty}) -> {builder}<{arguments}> {{",
+                name = field.name,
+                ty = field.ty,
+                builder = builder_name,
+                arguments = arguments("true")
+            )?;
+            writeln!(f, "let mut value = self.value;")?;
+            writeln!(f, "value.{name} = {name};", name = field.name)?;
+            writeln!(f, "{} {{ value }}", builder_name)?;
+            writeln!(f, "}}")?;
+            writeln!(f, "}}")?;
+        }
+
+        // Build when all fields have been set
+        writeln!(
+            f,
+            "impl {}<{}> {{",
+            builder_name,
+            vec!["true"; fields.len()].join(", ")
+        )?;
+        writeln!(f, "/// Returns the value with all fields set")?;
+        writeln!(f, "pub fn build(self) -> {} {{", type_name)?;
+        writeln!(f, "self.value")?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")
+    }
+}
@@ -0,0 +1,115 @@
+//! Generates an enum with a variant for each constant of a struct, and conversions between the
+//! enum, the struct, and the field
+
+use std::fmt::{Display, Formatter, Result};
+
+use crate::GeneratedType;
+
+pub(crate) struct ImplementCompanionEnum<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementCompanionEnum<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ty = self.0;
+        let companion = match &ty.companion_enum {
+            Some(companion) => companion,
+            None => return Ok(()),
+        };
+        let type_name = &ty.name.type_name;
+        let enum_name = format!("{}Enum", type_name);
+        let field_ty = &companion.field_ty;
+
+        writeln!(
+            f,
+            "/// The named values of the `{}` field of [`{}`]",
+            companion.field_name, type_name
+        )?;
+        writeln!(f, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]")?;
+        if let Some(deprecated) = &ty.deprecated {
+            writeln!(f, "{}", deprecated)?;
+        }
+        writeln!(f, "pub enum {} {{", enum_name)?;
+        for variant in &companion.variants {
+            Display::fmt(variant, f)?;
+        }
+        writeln!(f, "}}")?;
+
+        // Enum to field value
+        writeln!(
+            f,
+            "impl ::core::convert::From<{}> for {} {{",
+            enum_name, field_ty
+        )?;
+        writeln!(f, "fn from(value: {}) -> Self {{", enum_name)?;
+        writeln!(f, "match value {{")?;
+        for variant in &companion.variants {
+            writeln!(
+                f,
+                "{}::{} => {},",
+                enum_name, variant.name, variant.discriminant
+            )?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")?;
+
+        // Field value to enum, returning the value if it does not match a constant
+        writeln!(
+            f,
+            "impl ::core::convert::TryFrom<{}> for {} {{",
+            field_ty, enum_name
+        )?;
+        writeln!(f, "type Error = {};", field_ty)?;
+        writeln!(
+            f,
+            "fn try_from(value: {}) -> ::core::result::Result<Self, Self::Error> {{",
+            field_ty
+        )?;
+        writeln!(f, "match value {{")?;
+        for variant in &companion.variants {
+            writeln!(
+                f,
+                "{} => Ok({}::{}),",
+                variant.discriminant, enum_name, variant.name
+            )?;
+        }
+        writeln!(f, "_ => Err(value),")?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")?;
+
+        // Enum to struct
+        writeln!(
+            f,
+            "impl ::core::convert::From<{}> for {} {{",
+            enum_name, type_name
+        )?;
+        writeln!(f, "fn from(value: {}) -> Self {{", enum_name)?;
+        writeln!(
+            f,
+            "{} {{ {}: value.into() }}",
+            type_name, companion.field_name
+        )?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")?;
+
+        // Struct to enum
+        writeln!(
+            f,
+            "impl ::core::convert::TryFrom<{}> for {} {{",
+            type_name, enum_name
+        )?;
+        writeln!(f, "type Error = {};", field_ty)?;
+        writeln!(
+            f,
+            "fn try_from(value: {}) -> ::core::result::Result<Self, Self::Error> {{",
+            type_name
+        )?;
+        writeln!(
+            f,
+            "::core::convert::TryFrom::try_from(value.{})",
+            companion.field_name
+        )?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")
+    }
+}
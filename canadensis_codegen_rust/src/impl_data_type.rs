@@ -4,7 +4,7 @@ use std::fmt::{Display, Formatter, Result};
 
 use crate::{GeneratedType, MessageRole};
 
-/// Implements DataType and Message, Request, or Response as appropriate
+/// Implements DataType, SerializedSize, and Message, Request, or Response as appropriate
 pub(crate) struct ImplementDataType<'t, 'c>(pub &'t GeneratedType<'c>);
 
 impl Display for ImplementDataType<'_, '_> {
@@ -15,30 +15,23 @@ impl Display for ImplementDataType<'_, '_> {
             "impl ::canadensis_encoding::DataType for {} {{",
             self.0.name.type_name
         )?;
-        match self.0.extent {
-            Extent::Sealed => {
-                writeln!(f, "/// This type is sealed.")?;
-                writeln!(f, "const EXTENT_BYTES: Option<u32> = None;")?;
-            }
-            Extent::Delimited(extent_bits) => {
-                let extent_bytes = extent_bits / 8;
-                let extent_bytes = u32::try_from(extent_bytes).expect("Extent too large for u32");
-
-                writeln!(
-                    f,
-                    "/// This type is delimited with an extent of {} bytes.",
-                    extent_bytes
-                )?;
-                writeln!(
-                    f,
-                    "const EXTENT_BYTES: Option<u32> = Some({});",
-                    extent_bytes
-                )?;
-            }
-        }
+        write_extent_bytes(f, &self.0.extent)?;
+        writeln!(f, "}}")?;
+
+        // Part 2: Maximum size
+        writeln!(
+            f,
+            "impl ::canadensis_encoding::SerializedSize for {} {{",
+            self.0.name.type_name
+        )?;
+        writeln!(
+            f,
+            "const MAX_SERIALIZED_SIZE_BYTES: usize = {};",
+            (self.0.size.max_value() + 7) / 8
+        )?;
         writeln!(f, "}}")?;
 
-        // Part 2: Role
+        // Part 3: Role
         match &self.0.role {
             MessageRole::Message => writeln!(
                 f,
@@ -60,3 +53,29 @@ impl Display for ImplementDataType<'_, '_> {
         Ok(())
     }
 }
+
+/// Writes the EXTENT_BYTES constant in an implementation of DataType
+pub(crate) fn write_extent_bytes(f: &mut Formatter<'_>, extent: &Extent) -> Result {
+    match extent {
+        Extent::Sealed => {
+            writeln!(f, "/// This type is sealed.")?;
+            writeln!(f, "const EXTENT_BYTES: Option<u32> = None;")?;
+        }
+        Extent::Delimited(extent_bits) => {
+            let extent_bytes = extent_bits / 8;
+            let extent_bytes = u32::try_from(extent_bytes).expect("Extent too large for u32");
+
+            writeln!(
+                f,
+                "/// This type is delimited with an extent of {} bytes.",
+                extent_bytes
+            )?;
+            writeln!(
+                f,
+                "const EXTENT_BYTES: Option<u32> = Some({});",
+                extent_bytes
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Implements Default for a type
+
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{GeneratedField, GeneratedType, GeneratedTypeKind};
+
+/// Implements `Default` for a type
+///
+/// Numbers are zero, booleans are false, variable-length arrays are empty, fixed-length arrays
+/// contain default elements, and enums use their first variant. An enum with no variants does not
+/// get an implementation.
+pub(crate) struct ImplementDefault<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementDefault<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let value = match &self.0.kind {
+            GeneratedTypeKind::Struct(gstruct) => {
+                let mut value = String::from("Self {");
+                for field in &gstruct.fields {
+                    if let GeneratedField::Data(field) = field {
+                        value.push_str(&format!(
+                            "{}: {},",
+                            field.name,
+                            DefaultValue(field.cyphal_ty)
+                        ));
+                    }
+                }
+                value.push('}');
+                value
+            }
+            GeneratedTypeKind::Enum(genum) => match genum.variants.first() {
+                Some(variant) => match &variant.ty {
+                    Some(ty) => format!("Self::{}({})", variant.name, DefaultValue(&ty.cyphal_ty)),
+                    None => format!("Self::{}", variant.name),
+                },
+                None => return Ok(()),
+            },
+        };
+        writeln!(
+            f,
+            "impl ::core::default::Default for {} {{",
+            self.0.name.type_name
+        )?;
+        writeln!(f, "fn default() -> Self {{ {} }}", value)?;
+        writeln!(f, "}}")
+    }
+}
+
+/// An expression that creates the default value of a type
+struct DefaultValue<'t>(&'t ResolvedType);
+
+impl Display for DefaultValue<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            ResolvedType::FixedArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
+                len,
+            } => write!(f, "::canadensis_encoding::bits::BitArray::new({})", len),
+            ResolvedType::FixedArray { .. } => write!(
+                f,
+                "::core::array::from_fn(|_| ::core::default::Default::default())"
+            ),
+            ResolvedType::Scalar(_) | ResolvedType::VariableArray { .. } => {
+                write!(f, "::core::default::Default::default()")
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Implements defmt::Format for a type, when the defmt feature is enabled
+
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{GeneratedField, GeneratedType, GeneratedTypeKind};
+
+/// The maximum number of array elements to format
+///
+/// Longer arrays are shown with only their first elements and their length, so that logging a
+/// large message does not use too much of the logging bandwidth.
+const MAX_ELEMENTS: usize = 16;
+
+pub(crate) struct ImplementDefmt<'t, 'c> {
+    pub ty: &'t GeneratedType<'c>,
+    /// True if the type is packed, so references to its fields can't be created
+    pub packed: bool,
+}
+
+impl Display for ImplementDefmt<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let type_name = &self.ty.name.type_name;
+        writeln!(f, "#[cfg(feature = \"defmt\")]")?;
+        writeln!(f, "impl ::defmt::Format for {} {{", type_name)?;
+        writeln!(f, "fn format(&self, f: ::defmt::Formatter<'_>) {{")?;
+        match &self.ty.kind {
+            GeneratedTypeKind::Struct(gstruct) => {
+                // Like Debug: TypeName { field: value, other_field: value }
+                writeln!(f, "::defmt::write!(f, \"{}\");", type_name)?;
+                let mut first = true;
+                for field in &gstruct.fields {
+                    if let GeneratedField::Data(field) = field {
+                        let separator = if first { " {{" } else { "," };
+                        first = false;
+                        writeln!(
+                            f,
+                            "::defmt::write!(f, \"{} {}: \");",
+                            separator,
+                            field.name.trim_start_matches("r#")
+                        )?;
+                        // Fields of packed structs must be copied before they are used
+                        if self.packed {
+                            writeln!(f, "{{ let value = self.{};", field.name)?;
+                        } else {
+                            writeln!(f, "{{ let value = &self.{};", field.name)?;
+                        }
+                        writeln!(f, "{} }}", FormatValue(field.cyphal_ty))?;
+                    }
+                }
+                if !first {
+                    writeln!(f, "::defmt::write!(f, \" }}}}\");")?;
+                }
+            }
+            GeneratedTypeKind::Enum(genum) => {
+                // Like Debug: Variant(value)
+                if genum.variants.is_empty() {
+                    // A reference to an empty enum can't be matched without any arms
+                    writeln!(f, "match *self {{")?;
+                } else {
+                    writeln!(f, "match self {{")?;
+                }
+                for variant in &genum.variants {
+                    match &variant.ty {
+                        Some(ty) => writeln!(
+                            f,
+                            "Self::{name}(value) => {{ ::defmt::write!(f, \"{name}(\"); {value} ::defmt::write!(f, \")\"); }}",
+                            name = variant.name,
+                            value = FormatValue(&ty.cyphal_ty)
+                        )?,
+                        None => writeln!(
+                            f,
+                            "Self::{name} => ::defmt::write!(f, \"{name}\"),",
+                            name = variant.name
+                        )?,
+                    }
+                }
+                writeln!(f, "}}")?;
+            }
+        }
+        // End function
+        writeln!(f, "}}")?;
+        // End impl
+        writeln!(f, "}}")
+    }
+}
+
+/// Statements that format `value`, which is a value of a type or a reference to one
+struct FormatValue<'t>(&'t ResolvedType);
+
+impl Display for FormatValue<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            ResolvedType::Scalar(scalar) => write!(
+                f,
+                "::defmt::write!(f, \"{{}}\", {});",
+                FormatScalar(scalar, "value")
+            ),
+            ResolvedType::VariableArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Utf8),
+                ..
+            } => {
+                // A heapless::String
+                write!(f, "::defmt::write!(f, \"{{:?}}\", value.as_str());")
+            }
+            ResolvedType::FixedArray { inner, len } if *len <= MAX_ELEMENTS as u64 => {
+                // Short enough to format all the elements
+                write!(
+                    f,
+                    "::defmt::write!(f, \"[\"); \
+                    for (i, element) in value.iter().enumerate() {{ \
+                    if i != 0 {{ ::defmt::write!(f, \", \"); }} \
+                    ::defmt::write!(f, \"{{}}\", {element}); }} \
+                    ::defmt::write!(f, \"]\");",
+                    element = FormatScalar(inner, "element")
+                )
+            }
+            ResolvedType::FixedArray { inner, .. } | ResolvedType::VariableArray { inner, .. } => {
+                // This works for arrays, heapless::Vecs, and BitArrays
+                write!(
+                    f,
+                    "::defmt::write!(f, \"[\"); \
+                    for (i, element) in value.iter().take({max}).enumerate() {{ \
+                    if i != 0 {{ ::defmt::write!(f, \", \"); }} \
+                    ::defmt::write!(f, \"{{}}\", {element}); }} \
+                    if value.len() > {max} {{ ::defmt::write!(f, \", ... ({{}} elements)\", value.len()); }} \
+                    ::defmt::write!(f, \"]\");",
+                    max = MAX_ELEMENTS,
+                    element = FormatScalar(inner, "element")
+                )
+            }
+        }
+    }
+}
+
+/// An expression with a value of a scalar type that implements defmt::Format
+struct FormatScalar<'t>(&'t ResolvedScalarType, &'static str);
+
+impl Display for FormatScalar<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0 {
+            // half::f16 may not implement Format
+            ResolvedScalarType::Primitive(PrimitiveType::Float16 { .. }) => {
+                write!(f, "{}.to_f32()", self.1)
+            }
+            ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+            _ => write!(f, "{}", self.1),
+        }
+    }
+}
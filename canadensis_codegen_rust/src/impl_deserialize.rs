@@ -6,7 +6,8 @@ use canadensis_dsdl_frontend::types::{
 use std::fmt::{Display, Formatter, Result};
 
 use crate::{
-    GeneratedEnum, GeneratedField, GeneratedStruct, GeneratedType, GeneratedTypeKind, RustTypeName,
+    message_needs_view, type_needs_view, GeneratedEnum, GeneratedField, GeneratedStruct,
+    GeneratedType, GeneratedTypeKind,
 };
 
 pub(crate) struct ImplementDeserialize<'t, 'c> {
@@ -25,9 +26,11 @@ impl Display for ImplementDeserialize<'_, '_> {
 
         match &self.ty.kind {
             GeneratedTypeKind::Struct(gstruct) => {
-                deserialize_struct(f, &self.ty.name, gstruct, self.zero_copy)?
+                deserialize_struct(f, &self.ty.name.type_name, gstruct, self.zero_copy, false)?
+            }
+            GeneratedTypeKind::Enum(genum) => {
+                deserialize_enum(f, &self.ty.name.type_name, genum, false)?
             }
-            GeneratedTypeKind::Enum(genum) => deserialize_enum(f, &self.ty.name, genum)?,
         }
 
         // End function
@@ -38,16 +41,20 @@ impl Display for ImplementDeserialize<'_, '_> {
     }
 }
 
-fn deserialize_struct(
+/// Writes the body of a function that deserializes a struct
+///
+/// If `view` is true, the code borrows byte arrays and nested view types from the cursor.
+pub(crate) fn deserialize_struct(
     f: &mut Formatter,
-    name: &RustTypeName,
+    type_name: &str,
     gstruct: &GeneratedStruct,
     zero_copy: bool,
+    view: bool,
 ) -> Result {
     if zero_copy {
         writeln!(f, "Ok(Self::deserialize_zero_copy(cursor))")
     } else {
-        writeln!(f, "Ok( {} {{", name.type_name)?;
+        writeln!(f, "Ok( {} {{", type_name)?;
 
         // The padding from all padding fields before the next data field
         let mut padding_before_data: Vec<u8> = Vec::new();
@@ -64,7 +71,9 @@ fn deserialize_struct(
                         f,
                         "{} }},",
                         ReadUnalignedField {
-                            ty: field.cyphal_ty
+                            ty: field.cyphal_ty,
+                            always_aligned: field.always_aligned,
+                            view: view && type_needs_view(field.cyphal_ty, field.always_aligned),
                         }
                     )?;
                 }
@@ -80,7 +89,15 @@ fn deserialize_struct(
     }
 }
 
-fn deserialize_enum(f: &mut Formatter<'_>, name: &RustTypeName, genum: &GeneratedEnum) -> Result {
+/// Writes the body of a function that deserializes an enum
+///
+/// If `view` is true, the code borrows byte arrays and nested view types from the cursor.
+pub(crate) fn deserialize_enum(
+    f: &mut Formatter<'_>,
+    type_name: &str,
+    genum: &GeneratedEnum,
+    view: bool,
+) -> Result {
     // Match on the discriminant
     writeln!(
         f,
@@ -98,13 +115,18 @@ fn deserialize_enum(f: &mut Formatter<'_>, name: &RustTypeName, genum: &Generate
             writeln!(
                 f,
                 "Ok({}::{}({{ {} }}))",
-                name.type_name,
+                type_name,
                 variant.name,
-                ReadUnalignedField { ty: &ty.cyphal_ty }
+                ReadUnalignedField {
+                    ty: &ty.cyphal_ty,
+                    // Union variants are always aligned
+                    always_aligned: true,
+                    view: view && type_needs_view(&ty.cyphal_ty, true),
+                }
             )?;
         } else {
             // Variant with no data
-            writeln!(f, "Ok({}::{})", name.type_name, variant.name)?;
+            writeln!(f, "Ok({}::{})", type_name, variant.name)?;
         }
 
         // End match arm
@@ -121,14 +143,48 @@ fn deserialize_enum(f: &mut Formatter<'_>, name: &RustTypeName, genum: &Generate
     Ok(())
 }
 
-struct ReadUnalignedField<'t> {
-    ty: &'t ResolvedType,
+pub(crate) struct ReadUnalignedField<'t> {
+    pub ty: &'t ResolvedType,
+    /// True if this field always starts at a byte boundary
+    pub always_aligned: bool,
+    /// True if this field has a different type in a view type, and the value should borrow
+    /// from the cursor
+    pub view: bool,
 }
 
 impl Display for ReadUnalignedField<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match &self.ty {
-            ResolvedType::Scalar(scalar) => Display::fmt(&ReadUnalignedScalar { ty: scalar }, f)?,
+            ResolvedType::VariableArray {
+                inner: ResolvedScalarType::Primitive(primitive),
+                max_len,
+            } if self.view => {
+                // Borrow the bytes
+                let length_bits = match &self.ty.implicit_field() {
+                    Some(ImplicitField::ArrayLength { bits }) => *bits,
+                    _ => unreachable!("Variable-length array does not have a length field"),
+                };
+                let function_name = match primitive {
+                    PrimitiveType::Utf8 => "read_aligned_borrowed_str",
+                    _ => "read_aligned_borrowed_bytes",
+                };
+                writeln!(f, "let length = {};", CallRead { bits: length_bits })?;
+                writeln!(f, "if length <= {} {{", *max_len)?;
+                writeln!(f, "cursor.{}(length)?", function_name)?;
+                writeln!(f, "}} else {{")?;
+                writeln!(
+                    f,
+                    "return Err(::canadensis_encoding::DeserializeError::ArrayLength)"
+                )?;
+                writeln!(f, "}}")?;
+            }
+            ResolvedType::Scalar(scalar) => Display::fmt(
+                &ReadUnalignedScalar {
+                    ty: scalar,
+                    view: self.view,
+                },
+                f,
+            )?,
             ResolvedType::FixedArray {
                 inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
                 len,
@@ -155,11 +211,72 @@ impl Display for ReadUnalignedField<'_> {
                     "::canadensis_encoding::bits::BitArray::deserialize(length, cursor) }}"
                 )?;
             }
+            ResolvedType::FixedArray {
+                inner:
+                    ResolvedScalarType::Primitive(
+                        PrimitiveType::UInt { bits: 8, .. }
+                        | PrimitiveType::Byte
+                        | PrimitiveType::Utf8,
+                    ),
+                len,
+            } => {
+                // Special case for byte arrays: copy all the bytes at once
+                writeln!(f, "let mut bytes = [0u8; {}];", *len)?;
+                writeln!(f, "cursor.read_bytes(&mut bytes);")?;
+                writeln!(f, "bytes")?;
+            }
+            ResolvedType::VariableArray {
+                inner:
+                    ResolvedScalarType::Primitive(
+                        primitive @ (PrimitiveType::UInt { bits: 8, .. }
+                        | PrimitiveType::Byte
+                        | PrimitiveType::Utf8),
+                    ),
+                max_len,
+            } => {
+                // Special case for byte arrays: fill the vector with zeros, then copy all the
+                // bytes at once
+                let length_bits = match &self.ty.implicit_field() {
+                    Some(ImplicitField::ArrayLength { bits }) => *bits,
+                    _ => unreachable!("Variable-length array does not have a length field"),
+                };
+                writeln!(f, "let length = {};", CallRead { bits: length_bits })?;
+                writeln!(f, "if length <= {} {{", *max_len)?;
+                writeln!(f, "let mut elements = ::heapless::Vec::new();")?;
+                // The length is not greater than the capacity, so this can't fail
+                writeln!(f, "let _ = elements.resize(length, 0);")?;
+                writeln!(f, "cursor.read_bytes(&mut elements);")?;
+                if let PrimitiveType::Utf8 = primitive {
+                    // Convert into a heapless::String
+                    writeln!(f, "match ::heapless::String::from_utf8(elements) {{")?;
+                    writeln!(f, "Ok(string) => string,")?;
+                    writeln!(
+                        f,
+                        "Err(_) => return Err(::canadensis_encoding::DeserializeError::Utf8),"
+                    )?;
+                    writeln!(f, "}}")?;
+                } else {
+                    writeln!(f, "elements")?;
+                }
+                writeln!(f, "}} else {{")?;
+                writeln!(
+                    f,
+                    "return Err(::canadensis_encoding::DeserializeError::ArrayLength)"
+                )?;
+                writeln!(f, "}}")?;
+            }
             ResolvedType::FixedArray { inner, len } => {
                 // Make an array literal
                 writeln!(f, "[")?;
                 for _ in 0..*len {
-                    writeln!(f, "{},", ReadUnalignedScalar { ty: inner })?;
+                    writeln!(
+                        f,
+                        "{},",
+                        ReadUnalignedScalar {
+                            ty: inner,
+                            view: self.view
+                        }
+                    )?;
                 }
                 writeln!(f, "]")?;
             }
@@ -171,6 +288,10 @@ impl Display for ReadUnalignedField<'_> {
                     Some(ImplicitField::ArrayLength { bits }) => *bits,
                     _ => unreachable!("Variable-length array does not have a length field"),
                 };
+                if inner.alignment() == 8 && !self.always_aligned {
+                    // An array of composites is aligned before its length
+                    writeln!(f, "cursor.align_to_8_bits();")?;
+                }
                 writeln!(f, "let length = {};", CallRead { bits: length_bits })?;
                 writeln!(f, "if length <= {} {{", *max_len)?;
 
@@ -181,7 +302,10 @@ impl Display for ReadUnalignedField<'_> {
                 writeln!(
                     f,
                     "let _ = elements.push({});",
-                    ReadUnalignedScalar { ty: inner }
+                    ReadUnalignedScalar {
+                        ty: inner,
+                        view: self.view
+                    }
                 )?;
 
                 // End for
@@ -203,13 +327,19 @@ impl Display for ReadUnalignedField<'_> {
 }
 struct ReadUnalignedScalar<'t> {
     ty: &'t ResolvedScalarType,
+    /// True if a composite value should be read as a view type if it has one
+    view: bool,
 }
 
 impl Display for ReadUnalignedScalar<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match &self.ty {
-            ResolvedScalarType::Composite { .. } => {
-                write!(f, "cursor.read_composite()?")?;
+            ResolvedScalarType::Composite { inner, .. } => {
+                if self.view && message_needs_view(inner) {
+                    write!(f, "cursor.read_composite_borrowed()?")?;
+                } else {
+                    write!(f, "cursor.read_composite()?")?;
+                }
             }
             ResolvedScalarType::Primitive(primitive) => match primitive {
                 PrimitiveType::Boolean => write!(f, "cursor.read_bool()")?,
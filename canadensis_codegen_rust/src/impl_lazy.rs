@@ -0,0 +1,161 @@
+//! Generates a lazy view type that keeps a borrowed payload and deserializes each field only
+//! when it is accessed
+
+use std::fmt::{Display, Formatter, Result};
+
+use canadensis_dsdl_frontend::compiled::Extent;
+use canadensis_dsdl_frontend::types::{ResolvedScalarType, ResolvedType};
+
+use crate::impl_deserialize::ReadUnalignedField;
+use crate::{
+    write_doc_comments, GeneratedField, GeneratedStruct, GeneratedType, GeneratedTypeKind,
+};
+
+pub(crate) struct ImplementLazy<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementLazy<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ty = self.0;
+        let gstruct = match &ty.kind {
+            GeneratedTypeKind::Struct(gstruct) => gstruct,
+            GeneratedTypeKind::Enum(_) => unreachable!("Enums can't have lazy views"),
+        };
+        let offsets = lazy_field_offsets(gstruct).expect("Field offsets not fixed");
+        let lazy_name = format!("{}Lazy", ty.name.type_name);
+
+        writeln!(
+            f,
+            "/// `{}` deserialized one field at a time from a borrowed payload",
+            ty.cyphal_name
+        )?;
+        writeln!(f, "///")?;
+        writeln!(
+            f,
+            "/// This is a lazy view of [`{}`]. It can be created from the payload of a transfer \
+            with `From<&[u8]>`, and each method deserializes one field when it is called.",
+            ty.name.type_name
+        )?;
+        if let Some(deprecated) = &ty.deprecated {
+            writeln!(f, "{}", deprecated)?;
+        }
+        writeln!(f, "#[derive(Debug, Clone, Copy)]")?;
+        writeln!(f, "pub struct {}<'a> {{ payload: &'a [u8] }}", lazy_name)?;
+
+        writeln!(
+            f,
+            "impl<'a> ::core::convert::From<&'a [u8]> for {}<'a> {{",
+            lazy_name
+        )?;
+        writeln!(
+            f,
+            "fn from(payload: &'a [u8]) -> Self {{ Self {{ payload }} }}"
+        )?;
+        writeln!(f, "}}")?;
+        writeln!(
+            f,
+            "impl ::core::convert::AsRef<[u8]> for {}<'_> {{",
+            lazy_name
+        )?;
+        writeln!(f, "fn as_ref(&self) -> &[u8] {{ self.payload }}")?;
+        writeln!(f, "}}")?;
+
+        writeln!(f, "impl {}<'_> {{", lazy_name)?;
+        let data_fields = gstruct.fields.iter().filter_map(|field| match field {
+            GeneratedField::Data(data) => Some(data),
+            GeneratedField::Padding(_) => None,
+        });
+        for (data, offset) in data_fields.zip(offsets) {
+            write_doc_comments(f, data.comments)?;
+            writeln!(f, "///")?;
+            writeln!(f, "/// `{}`", data.cyphal_ty)?;
+            let fallible = read_is_fallible(data.cyphal_ty);
+            if fallible {
+                writeln!(f, "pub fn {}(&self) -> ::core::result::Result<{}, ::canadensis_encoding::DeserializeError> {{", data.name, data.ty)?;
+            } else {
+                writeln!(f, "pub fn {}(&self) -> {} {{", data.name, data.ty)?;
+            }
+            writeln!(
+                f,
+                "let cursor = &mut ::canadensis_encoding::ReadCursor::new(self.payload);"
+            )?;
+            if offset != 0 {
+                writeln!(f, "cursor.skip_bits({});", offset)?;
+            }
+            let read = ReadUnalignedField {
+                ty: data.cyphal_ty,
+                always_aligned: data.always_aligned,
+                view: false,
+            };
+            if fallible {
+                writeln!(f, "Ok({{ {} }})", read)?;
+            } else {
+                writeln!(f, "{{ {} }}", read)?;
+            }
+            // End function
+            writeln!(f, "}}")?;
+        }
+        // End impl
+        writeln!(f, "}}")
+    }
+}
+
+/// Returns the offset in bits of each data field of a struct, or None if the offset of any data
+/// field depends on the values of the fields before it
+///
+/// This also returns None if the struct has no data fields.
+pub(crate) fn lazy_field_offsets(gstruct: &GeneratedStruct<'_>) -> Option<Vec<u64>> {
+    let mut offsets = Vec::new();
+    // The offset of the next field, or None if it is not fixed
+    let mut offset = Some(0u64);
+    for field in &gstruct.fields {
+        match field {
+            GeneratedField::Data(data) => {
+                let alignment = u64::from(data.cyphal_ty.alignment());
+                let field_offset = round_up(offset?, alignment);
+                offsets.push(field_offset);
+                offset = fixed_serialized_size(data.cyphal_ty)
+                    .map(|size| round_up(field_offset + size, alignment));
+            }
+            GeneratedField::Padding(bits) => {
+                offset = offset.map(|offset| offset + u64::from(*bits))
+            }
+        }
+    }
+    if offsets.is_empty() {
+        None
+    } else {
+        Some(offsets)
+    }
+}
+
+/// Returns the serialized size of a value of the provided type in bits, or None if it can vary
+///
+/// Variable-length arrays and delimited composite types can have different sizes. Delimited
+/// types can have different sizes even if all their fields have fixed sizes, because a newer
+/// version of the type can have more fields.
+fn fixed_serialized_size(ty: &ResolvedType) -> Option<u64> {
+    let size = ty.size();
+    let sealed = matches!(ty.scalar().extent(), Extent::Sealed);
+    match ty {
+        ResolvedType::Scalar(_) | ResolvedType::FixedArray { .. }
+            if sealed && size.is_fixed_size() =>
+        {
+            Some(size.min_value())
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if deserializing a value of the provided type can fail
+fn read_is_fallible(ty: &ResolvedType) -> bool {
+    match ty {
+        ResolvedType::VariableArray { .. } => true,
+        ResolvedType::Scalar(scalar) | ResolvedType::FixedArray { inner: scalar, .. } => {
+            matches!(scalar, ResolvedScalarType::Composite { .. })
+        }
+    }
+}
+
+fn round_up(value: u64, alignment: u64) -> u64 {
+    value.div_ceil(alignment) * alignment
+}
@@ -0,0 +1,53 @@
+//! Generates functions that reinterpret bytes as a type with a `#[repr(C)]` layout that matches
+//! its serialized form
+//!
+//! Cyphal serializes integers and floating-point values in little-endian byte order, so the
+//! functions are only available on little-endian targets.
+
+use std::fmt::{Display, Formatter, Result};
+
+use crate::GeneratedType;
+
+pub(crate) struct ImplementPlainLayout<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementPlainLayout<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "#[cfg(target_endian = \"little\")]")?;
+        writeln!(f, "impl {} {{", self.0.name.type_name)?;
+
+        writeln!(
+            f,
+            "/// Returns a reference to serialized bytes as a value of this type, without copying"
+        )?;
+        writeln!(f, "///")?;
+        writeln!(f, "/// This returns `None` if `bytes` does not have the same length as this type or is not aligned for it.")?;
+        writeln!(
+            f,
+            "pub fn ref_from_bytes(bytes: &[u8]) -> ::core::option::Option<&Self> {{"
+        )?;
+        writeln!(
+            f,
+            "::zerocopy::LayoutVerified::<_, Self>::new(bytes).map(::zerocopy::LayoutVerified::into_ref)"
+        )?;
+        writeln!(f, "}}")?;
+
+        writeln!(
+            f,
+            "/// Returns a mutable reference to serialized bytes as a value of this type, without copying"
+        )?;
+        writeln!(f, "///")?;
+        writeln!(f, "/// This returns `None` if `bytes` does not have the same length as this type or is not aligned for it.")?;
+        writeln!(
+            f,
+            "pub fn mut_from_bytes(bytes: &mut [u8]) -> ::core::option::Option<&mut Self> {{"
+        )?;
+        writeln!(
+            f,
+            "::zerocopy::LayoutVerified::<_, Self>::new(bytes).map(::zerocopy::LayoutVerified::into_mut)"
+        )?;
+        writeln!(f, "}}")?;
+
+        // End impl
+        writeln!(f, "}}")
+    }
+}
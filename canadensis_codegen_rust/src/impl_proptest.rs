@@ -0,0 +1,219 @@
+//! Implements proptest::arbitrary::Arbitrary for a type, when the proptest feature is enabled
+
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{
+    round_up_integer_size, GeneratedDataField, GeneratedField, GeneratedType, GeneratedTypeKind,
+};
+
+/// The maximum number of elements in a tuple that proptest can use as a strategy
+const MAX_TUPLE_LENGTH: usize = 12;
+
+pub(crate) struct ImplementProptest<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementProptest<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "#[cfg(feature = \"proptest\")]")?;
+        writeln!(
+            f,
+            "impl ::proptest::arbitrary::Arbitrary for {} {{",
+            self.0.name.type_name
+        )?;
+        writeln!(f, "type Parameters = ();")?;
+        writeln!(
+            f,
+            "type Strategy = ::proptest::strategy::BoxedStrategy<Self>;"
+        )?;
+        writeln!(
+            f,
+            "fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {{"
+        )?;
+        writeln!(f, "use ::proptest::strategy::Strategy;")?;
+        match &self.0.kind {
+            GeneratedTypeKind::Struct(gstruct) => {
+                let fields: Vec<&GeneratedDataField> = gstruct
+                    .fields
+                    .iter()
+                    .filter_map(|field| match field {
+                        GeneratedField::Data(data) => Some(data),
+                        GeneratedField::Padding(_) => None,
+                    })
+                    .collect();
+                if fields.is_empty() {
+                    writeln!(f, "::proptest::strategy::LazyJust::new(|| Self {{}})")?;
+                } else {
+                    // Combine the field strategies into a tuple (or nested tuples if there
+                    // are many fields) and map the tuple into a value
+                    let strategies: Vec<String> = fields
+                        .iter()
+                        .map(|field| {
+                            ValueStrategy {
+                                ty: field.cyphal_ty,
+                                element_ty: &field.element_ty,
+                            }
+                            .to_string()
+                        })
+                        .collect();
+                    let names: Vec<String> =
+                        fields.iter().map(|field| field.name.clone()).collect();
+                    writeln!(
+                        f,
+                        "{}.prop_map(|{}| Self {{ {} }})",
+                        nested_tuple(&strategies),
+                        nested_tuple(&names),
+                        names.join(", ")
+                    )?;
+                }
+            }
+            GeneratedTypeKind::Enum(genum) => {
+                writeln!(f, "::proptest::prop_oneof![")?;
+                for variant in &genum.variants {
+                    match &variant.ty {
+                        Some(ty) => writeln!(
+                            f,
+                            "{}.prop_map(Self::{}),",
+                            ValueStrategy {
+                                ty: &ty.cyphal_ty,
+                                element_ty: &ty.element_rust_name,
+                            },
+                            variant.name
+                        )?,
+                        None => writeln!(
+                            f,
+                            "::proptest::strategy::LazyJust::new(|| Self::{}),",
+                            variant.name
+                        )?,
+                    }
+                }
+                writeln!(f, "]")?;
+            }
+        }
+        writeln!(f, ".boxed()")?;
+        // End function
+        writeln!(f, "}}")?;
+        // End impl
+        writeln!(f, "}}")
+    }
+}
+
+/// Formats items as a tuple, nesting tuples so that no tuple is longer than MAX_TUPLE_LENGTH
+fn nested_tuple(items: &[String]) -> String {
+    if items.len() <= MAX_TUPLE_LENGTH {
+        // A one-element tuple needs a trailing comma
+        let trailing_comma = if items.len() == 1 { "," } else { "" };
+        format!("({}{})", items.join(", "), trailing_comma)
+    } else {
+        let chunk_length = items.len().div_ceil(MAX_TUPLE_LENGTH);
+        let chunks: Vec<String> = items.chunks(chunk_length).map(nested_tuple).collect();
+        nested_tuple(&chunks)
+    }
+}
+
+/// An expression that evaluates to a strategy for values of a type
+///
+/// The values are always in the range of the Cyphal type, so they serialize without saturation
+/// or truncation. Variable-length arrays are no longer than their maximum lengths.
+struct ValueStrategy<'t> {
+    ty: &'t ResolvedType,
+    /// The Rust type of the value, or of each element if the type is an array
+    element_ty: &'t str,
+}
+
+impl Display for ValueStrategy<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.ty {
+            ResolvedType::Scalar(scalar) => Display::fmt(&self.scalar(scalar), f),
+            ResolvedType::FixedArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
+                len,
+            } => write!(
+                f,
+                "::proptest::collection::vec(::proptest::prelude::any::<bool>(), {len}).prop_map(|bits| {{ \
+                let mut array = ::canadensis_encoding::bits::BitArray::new({len}); \
+                for (i, bit) in bits.into_iter().enumerate() {{ array.set(i, bit); }} array }})",
+                len = len
+            ),
+            ResolvedType::VariableArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
+                max_len,
+            } => write!(
+                f,
+                "::proptest::collection::vec(::proptest::prelude::any::<bool>(), 0..={}).prop_map(|bits| {{ \
+                let mut array = ::canadensis_encoding::bits::BitArray::new(bits.len()); \
+                for (i, bit) in bits.into_iter().enumerate() {{ array.set(i, bit); }} array }})",
+                max_len
+            ),
+            ResolvedType::FixedArray { inner, len } => write!(
+                f,
+                "::proptest::collection::vec({}, {}).prop_map(|elements| {{ \
+                let array: [{}; {}] = ::core::convert::TryFrom::try_from(elements).ok().expect(\"Incorrect array length\"); \
+                array }})",
+                self.scalar(inner),
+                len,
+                self.element_ty,
+                len
+            ),
+            ResolvedType::VariableArray {
+                inner: ResolvedScalarType::Primitive(PrimitiveType::Utf8),
+                max_len,
+            } => write!(
+                f,
+                "::proptest::collection::vec(::proptest::prelude::any::<char>(), 0..={max_len}).prop_map(|chars| {{ \
+                let mut string = ::heapless::String::<{max_len}>::new(); \
+                for c in chars {{ if string.push(c).is_err() {{ break; }} }} string }})",
+                max_len = max_len
+            ),
+            ResolvedType::VariableArray { inner, max_len } => write!(
+                f,
+                "::proptest::collection::vec({}, 0..={}).prop_map(|elements| \
+                elements.into_iter().collect::<::heapless::Vec<_, {}>>())",
+                self.scalar(inner),
+                max_len,
+                max_len
+            ),
+        }
+    }
+}
+
+impl<'t> ValueStrategy<'t> {
+    fn scalar(&self, scalar: &'t ResolvedScalarType) -> ScalarStrategy<'t> {
+        ScalarStrategy {
+            ty: scalar,
+            rust_ty: self.element_ty,
+        }
+    }
+}
+
+struct ScalarStrategy<'t> {
+    ty: &'t ResolvedScalarType,
+    rust_ty: &'t str,
+}
+
+impl Display for ScalarStrategy<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.ty {
+            ResolvedScalarType::Primitive(PrimitiveType::Int { bits })
+                if !matches!(bits, 8 | 16 | 32 | 64) =>
+            {
+                let max = (1i64 << (bits - 1)) - 1;
+                let min = -max - 1;
+                let rust_bits = round_up_integer_size(*bits);
+                write!(f, "({}i{}..={}i{})", min, rust_bits, max, rust_bits)
+            }
+            ResolvedScalarType::Primitive(PrimitiveType::UInt { bits, .. })
+                if !matches!(bits, 8 | 16 | 32 | 64) =>
+            {
+                let max = (1u64 << bits) - 1;
+                let rust_bits = round_up_integer_size(*bits);
+                write!(f, "(0u{}..={}u{})", rust_bits, max, rust_bits)
+            }
+            ResolvedScalarType::Primitive(PrimitiveType::Float16 { .. }) => write!(
+                f,
+                "::proptest::prelude::any::<u16>().prop_map(::half::f16::from_bits)"
+            ),
+            ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+            _ => write!(f, "::proptest::prelude::any::<{}>()", self.rust_ty),
+        }
+    }
+}
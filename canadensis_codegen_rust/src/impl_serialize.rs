@@ -241,10 +241,17 @@ impl Display for WriteAlignedField<'_> {
             }
             ResolvedType::VariableArray {
                 inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
-                ..
+                max_len,
             } => {
                 // Use BitArray with a length field
                 if let Some(ImplicitField::ArrayLength { bits }) = self.ty.implicit_field() {
+                    Display::fmt(
+                        &CheckBitArrayLength {
+                            array_expr: self.field_expr,
+                            max_len: *max_len,
+                        },
+                        f,
+                    )?;
                     // Write length and then elements
                     Display::fmt(
                         &CallWriteAligned {
@@ -360,10 +367,17 @@ impl Display for WriteUnalignedField<'_> {
             }
             ResolvedType::VariableArray {
                 inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
-                ..
+                max_len,
             } => {
                 // Use BitArray with a length field
                 if let Some(ImplicitField::ArrayLength { bits }) = self.ty.implicit_field() {
+                    Display::fmt(
+                        &CheckBitArrayLength {
+                            array_expr: self.field_expr,
+                            max_len: *max_len,
+                        },
+                        f,
+                    )?;
                     // Write length and then elements
                     Display::fmt(
                         &CallWrite {
@@ -389,6 +403,11 @@ impl Display for WriteUnalignedField<'_> {
             }
             ResolvedType::VariableArray { inner, .. } => {
                 if let Some(ImplicitField::ArrayLength { bits }) = self.ty.implicit_field() {
+                    if inner.alignment() == 8 {
+                        // An array of composites is aligned before its length, even if it
+                        // has no elements
+                        writeln!(f, "cursor.align_to_8_bits();")?;
+                    }
                     // Write length and then elements
                     Display::fmt(
                         &CallWrite {
@@ -415,6 +434,30 @@ impl Display for WriteUnalignedField<'_> {
     }
 }
 
+/// A debug assertion that a BitArray is no longer than the maximum length of its DSDL type
+///
+/// The capacity of a BitArray is a whole number of bytes, so it can hold more bits than the DSDL
+/// type allows. This writes nothing if the capacity is equal to the maximum length.
+struct CheckBitArrayLength<'t> {
+    array_expr: &'t str,
+    max_len: u64,
+}
+
+impl Display for CheckBitArrayLength<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.max_len.div_ceil(8) * 8 == self.max_len {
+            Ok(())
+        } else {
+            writeln!(
+                f,
+                "debug_assert!(({expr}).len() <= {max}, \"Bit array length {{}} is greater than the maximum {max}\", ({expr}).len());",
+                expr = self.array_expr,
+                max = self.max_len
+            )
+        }
+    }
+}
+
 struct WriteArrayElements<'t> {
     element_type: &'t ResolvedScalarType,
     array_expr: &'t str,
@@ -451,10 +494,14 @@ impl Display for WriteArrayElements<'_> {
                     )?;
                     writeln!(f, "}}")
                 }
-                PrimitiveType::UInt { bits: 8, .. } | PrimitiveType::Byte | PrimitiveType::Utf8 => {
+                PrimitiveType::UInt { bits: 8, .. } | PrimitiveType::Byte => {
                     // Special case for byte arrays
                     writeln!(f, "cursor.write_bytes(&({})[..]);", self.array_expr)
                 }
+                PrimitiveType::Utf8 => {
+                    // utf8 arrays are always variable-length, so this is a heapless::String
+                    writeln!(f, "cursor.write_bytes(({}).as_bytes());", self.array_expr)
+                }
                 PrimitiveType::UInt { bits, .. } => {
                     write!(f, "for value in ({}).iter() {{", self.array_expr)?;
                     Display::fmt(
@@ -0,0 +1,225 @@
+//! Generates setters and a checked constructor for a struct type with integer fields that are
+//! narrower than their Rust types
+//!
+//! A `uint12` field is stored in a `u16`, but only values up to 4095 can be serialized. The
+//! setter for that field applies the field's cast mode: a saturated field gets the closest value
+//! in range, and a truncated field keeps only its least significant bits. Signed integers are
+//! always saturated.
+//!
+//! `checked_new()` takes a value for every field, and returns `None` if any of them is out of
+//! range.
+//!
+//! A variable-length `uint8` or `byte` array field is stored in a `heapless::Vec<u8, N>`. Its
+//! setter copies the contents of a slice into the field, and returns an error if the slice is too
+//! long.
+
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use canadensis_dsdl_parser::CastMode;
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{
+    round_up_integer_size, GeneratedDataField, GeneratedField, GeneratedType, GeneratedTypeKind,
+};
+
+pub(crate) struct ImplementSetters<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementSetters<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ty = self.0;
+        let fields: Vec<&GeneratedDataField<'_>> = match &ty.kind {
+            GeneratedTypeKind::Struct(gstruct) => gstruct
+                .fields
+                .iter()
+                .filter_map(|field| match field {
+                    GeneratedField::Data(data) => Some(data),
+                    GeneratedField::Padding(_) => None,
+                })
+                .collect(),
+            GeneratedTypeKind::Enum(_) => return Ok(()),
+        };
+        let narrow_fields: Vec<(&GeneratedDataField<'_>, Range)> = fields
+            .iter()
+            .filter_map(|field| Range::of(field.cyphal_ty).map(|range| (*field, range)))
+            .collect();
+        let byte_fields: Vec<(&GeneratedDataField<'_>, u64)> = fields
+            .iter()
+            .filter_map(|field| byte_array_capacity(field.cyphal_ty).map(|max| (*field, max)))
+            .collect();
+        if narrow_fields.is_empty() && byte_fields.is_empty() {
+            return Ok(());
+        }
+        let type_name = &ty.name.type_name;
+
+        writeln!(f, "impl {} {{", type_name)?;
+        for (field, max_len) in &byte_fields {
+            writeln!(
+                f,
+                "/// Replaces the contents of the `{}` field with a copy of `bytes`",
+                field.name
+            )?;
+            writeln!(f, "///")?;
+            writeln!(
+                f,
+                "/// This returns an error and leaves the field unchanged if `bytes` is longer than {}.",
+                max_len
+            )?;
+            writeln!(
+                f,
+                "pub fn set_{}(&mut self, bytes: &[u8]) -> ::core::result::Result<(), ::canadensis_encoding::CapacityError> {{",
+                field.name.trim_start_matches("r#")
+            )?;
+            writeln!(
+                f,
+                "match ::heapless::Vec::from_slice(bytes) {{ \
+                Ok(bytes) => {{ self.{} = bytes; Ok(()) }} \
+                Err(_) => Err(::canadensis_encoding::CapacityError {{ capacity: {}, length: bytes.len() }}), \
+                }}",
+                field.name, max_len
+            )?;
+            writeln!(f, "}}")?;
+        }
+        for (field, range) in &narrow_fields {
+            let setter_name = format!("set_{}", field.name.trim_start_matches("r#"));
+            match range {
+                Range::Unsigned {
+                    max,
+                    mode: CastMode::Saturated,
+                } => {
+                    writeln!(
+                        f,
+                        "/// Sets the `{}` field, replacing values greater than {} with {}",
+                        field.name, max, max
+                    )?;
+                    writeln!(
+                        f,
+                        "pub fn {}(&mut self, value: {}) {{",
+                        setter_name, field.ty
+                    )?;
+                    writeln!(f, "self.{} = ::core::cmp::min(value, {});", field.name, max)?;
+                }
+                Range::Unsigned {
+                    max,
+                    mode: CastMode::Truncated,
+                } => {
+                    writeln!(
+                        f,
+                        "/// Sets the `{}` field, keeping only the {} least significant bits of the value",
+                        field.name,
+                        max.count_ones()
+                    )?;
+                    writeln!(
+                        f,
+                        "pub fn {}(&mut self, value: {}) {{",
+                        setter_name, field.ty
+                    )?;
+                    writeln!(f, "self.{} = value & {};", field.name, max)?;
+                }
+                Range::Signed { min, max } => {
+                    writeln!(
+                        f,
+                        "/// Sets the `{}` field, replacing values outside the range {}..={} with \
+                        the closest value in the range",
+                        field.name, min, max
+                    )?;
+                    writeln!(
+                        f,
+                        "pub fn {}(&mut self, value: {}) {{",
+                        setter_name, field.ty
+                    )?;
+                    writeln!(
+                        f,
+                        "self.{} = ::core::cmp::Ord::clamp(value, {}, {});",
+                        field.name, min, max
+                    )?;
+                }
+            }
+            writeln!(f, "}}")?;
+        }
+        if narrow_fields.is_empty() {
+            // End impl
+            return writeln!(f, "}}");
+        }
+
+        // Checked constructor
+        writeln!(
+            f,
+            "/// Returns a value with the provided fields, or `None` if any field is out of range \
+            for its DSDL type"
+        )?;
+        writeln!(f, "#[allow(clippy::too_many_arguments)]")?;
+        writeln!(
+            f,
+            "pub fn checked_new({}) -> ::core::option::Option<Self> {{",
+            fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, field.ty))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        let conditions: Vec<String> = narrow_fields
+            .iter()
+            .map(|(field, range)| match range {
+                Range::Unsigned { max, .. } => format!("{} > {}", field.name, max),
+                Range::Signed { min, max } => {
+                    format!("!({}..={}).contains(&{})", min, max, field.name)
+                }
+            })
+            .collect();
+        writeln!(
+            f,
+            "if {} {{ return ::core::option::Option::None; }}",
+            conditions.join(" || ")
+        )?;
+        writeln!(
+            f,
+            "::core::option::Option::Some({} {{ {} }})",
+            type_name,
+            fields
+                .iter()
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(f, "}}")?;
+        writeln!(f, "}}")
+    }
+}
+
+/// Returns the maximum length of a variable-length `uint8` or `byte` array type
+fn byte_array_capacity(ty: &ResolvedType) -> Option<u64> {
+    match ty {
+        ResolvedType::VariableArray {
+            inner:
+                ResolvedScalarType::Primitive(PrimitiveType::UInt { bits: 8, .. } | PrimitiveType::Byte),
+            max_len,
+        } => Some(*max_len),
+        _ => None,
+    }
+}
+
+/// The values that an integer field can hold, if it is narrower than its Rust type
+enum Range {
+    Unsigned { max: u64, mode: CastMode },
+    Signed { min: i64, max: i64 },
+}
+
+impl Range {
+    fn of(ty: &ResolvedType) -> Option<Range> {
+        match ty {
+            ResolvedType::Scalar(ResolvedScalarType::Primitive(PrimitiveType::UInt {
+                bits,
+                mode,
+            })) if *bits != round_up_integer_size(*bits) => Some(Range::Unsigned {
+                max: (1u64 << *bits) - 1,
+                mode: mode.clone(),
+            }),
+            ResolvedType::Scalar(ResolvedScalarType::Primitive(PrimitiveType::Int { bits }))
+                if *bits != round_up_integer_size(*bits) =>
+            {
+                let max = (1i64 << (*bits - 1)) - 1;
+                Some(Range::Signed { min: -max - 1, max })
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Implements From or TryFrom conversions between a type and the previous version of the same
+//! type
+//!
+//! # Field mapping
+//!
+//! Fields and union variants are matched by name. A conversion from one version to another is
+//! generated if every field (or variant) that exists in both versions has a compatible type:
+//!
+//! * The same Rust type: the value is moved
+//! * Integers that Rust can convert without loss (like `u8` to `u16`), and `f16` to `f32` or
+//!   `f32` to `f64`: the value is converted with `From`
+//! * Other integers: the value is converted with `TryFrom`, which makes the whole conversion
+//!   fallible
+//!
+//! Struct fields that only exist in the destination get their default values, and fields that
+//! only exist in the source are dropped. A union variant that only exists in the source makes the
+//! conversion fallible.
+//!
+//! An infallible conversion is a `From` implementation. A fallible conversion is a `TryFrom`
+//! implementation that returns the original value if it can't be converted.
+
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{
+    round_up_integer_size, GeneratedField, GeneratedType, GeneratedTypeKind, GeneratedVariant,
+};
+
+pub(crate) struct ImplementVersionConversions<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementVersionConversions<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(previous) = &self.0.previous_version {
+            write_conversion(f, previous, self.0)?;
+            write_conversion(f, self.0, previous)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a value of one type can be converted into another type
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Conversion {
+    /// The types are the same
+    Move,
+    /// The conversion is done with `From`
+    Into,
+    /// The conversion is done with `TryFrom`
+    TryInto,
+}
+
+/// Writes a From or TryFrom implementation that converts `from` into `to`, if possible
+fn write_conversion(
+    f: &mut Formatter<'_>,
+    from: &GeneratedType<'_>,
+    to: &GeneratedType<'_>,
+) -> Result {
+    match (&from.kind, &to.kind) {
+        (GeneratedTypeKind::Struct(from_struct), GeneratedTypeKind::Struct(to_struct)) => {
+            // For each field in the destination, the source field and conversion
+            let mut mappings = Vec::new();
+            for to_field in &to_struct.fields {
+                if let GeneratedField::Data(to_field) = to_field {
+                    let from_field = from_struct.fields.iter().find_map(|field| match field {
+                        GeneratedField::Data(field) if field.name == to_field.name => Some(field),
+                        _ => None,
+                    });
+                    let conversion = match from_field {
+                        Some(from_field) => Some(
+                            match field_conversion(
+                                from_field.cyphal_ty,
+                                &from_field.ty,
+                                to_field.cyphal_ty,
+                                &to_field.ty,
+                            ) {
+                                Some(conversion) => conversion,
+                                None => return Ok(()),
+                            },
+                        ),
+                        None => None,
+                    };
+                    mappings.push((to_field, conversion));
+                }
+            }
+            let fallible = mappings
+                .iter()
+                .any(|(_, conversion)| *conversion == Some(Conversion::TryInto));
+
+            write_impl_start(f, from, to, fallible)?;
+            let uses_value = mappings.iter().any(|(_, conversion)| conversion.is_some());
+            if !uses_value {
+                writeln!(f, "let _ = value;")?;
+            }
+            if fallible {
+                // Try all fallible conversions before moving anything out of value, so that
+                // value can be returned if a conversion fails
+                let fallible_fields: Vec<_> = mappings
+                    .iter()
+                    .filter(|(_, conversion)| *conversion == Some(Conversion::TryInto))
+                    .map(|(field, _)| field)
+                    .collect();
+                write!(f, "match (")?;
+                for field in &fallible_fields {
+                    write!(
+                        f,
+                        "<{} as ::core::convert::TryFrom<_>>::try_from(value.{}),",
+                        field.ty, field.name
+                    )?;
+                }
+                write!(f, ") {{ (")?;
+                for i in 0..fallible_fields.len() {
+                    write!(f, "::core::result::Result::Ok(converted{}),", i)?;
+                }
+                write!(f, ") => ::core::result::Result::Ok(")?;
+            }
+            writeln!(f, "{} {{", to.name)?;
+            let mut fallible_index = 0;
+            for (field, conversion) in &mappings {
+                match conversion {
+                    Some(Conversion::Move) => writeln!(f, "{0}: value.{0},", field.name)?,
+                    Some(Conversion::Into) => writeln!(
+                        f,
+                        "{0}: ::core::convert::From::from(value.{0}),",
+                        field.name
+                    )?,
+                    Some(Conversion::TryInto) => {
+                        writeln!(f, "{}: converted{},", field.name, fallible_index)?;
+                        fallible_index += 1;
+                    }
+                    None => writeln!(f, "{}: ::core::default::Default::default(),", field.name)?,
+                }
+            }
+            write!(f, "}}")?;
+            if fallible {
+                writeln!(f, "),")?;
+                writeln!(f, "_ => ::core::result::Result::Err(value),")?;
+                write!(f, "}}")?;
+            }
+            writeln!(f)?;
+            write_impl_end(f)
+        }
+        (GeneratedTypeKind::Enum(from_enum), GeneratedTypeKind::Enum(to_enum)) => {
+            if from_enum.variants.is_empty() {
+                return Ok(());
+            }
+            // For each variant in the source, the destination variant and conversion
+            let mut mappings = Vec::new();
+            for from_variant in &from_enum.variants {
+                let to_variant = to_enum
+                    .variants
+                    .iter()
+                    .find(|variant| variant.name == from_variant.name);
+                let conversion = match to_variant {
+                    Some(to_variant) => match (&from_variant.ty, &to_variant.ty) {
+                        (Some(from_ty), Some(to_ty)) => match field_conversion(
+                            &from_ty.cyphal_ty,
+                            &from_ty.rust_name,
+                            &to_ty.cyphal_ty,
+                            &to_ty.rust_name,
+                        ) {
+                            Some(conversion) => Some((to_variant, Some(conversion))),
+                            None => return Ok(()),
+                        },
+                        (None, None) => Some((to_variant, None)),
+                        _ => return Ok(()),
+                    },
+                    None => None,
+                };
+                mappings.push((from_variant, conversion));
+            }
+            let fallible = mappings.iter().any(|(_, conversion)| {
+                !matches!(
+                    conversion,
+                    Some((_, None | Some(Conversion::Move | Conversion::Into)))
+                )
+            });
+
+            write_impl_start(f, from, to, fallible)?;
+            writeln!(f, "match value {{")?;
+            for (from_variant, conversion) in mappings {
+                write_variant_conversion(f, from, to, from_variant, conversion, fallible)?;
+            }
+            writeln!(f, "}}")?;
+            write_impl_end(f)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Writes a match arm that converts one enum variant
+fn write_variant_conversion(
+    f: &mut Formatter<'_>,
+    from: &GeneratedType<'_>,
+    to: &GeneratedType<'_>,
+    from_variant: &GeneratedVariant<'_>,
+    conversion: Option<(&GeneratedVariant<'_>, Option<Conversion>)>,
+    fallible: bool,
+) -> Result {
+    let (ok_start, ok_end) = if fallible {
+        ("::core::result::Result::Ok(", ")")
+    } else {
+        ("", "")
+    };
+    match conversion {
+        Some((to_variant, None)) => writeln!(
+            f,
+            "{}::{} => {}{}::{}{},",
+            from.name, from_variant.name, ok_start, to.name, to_variant.name, ok_end
+        ),
+        Some((to_variant, Some(Conversion::Move))) => writeln!(
+            f,
+            "{}::{}(inner) => {}{}::{}(inner){},",
+            from.name, from_variant.name, ok_start, to.name, to_variant.name, ok_end
+        ),
+        Some((to_variant, Some(Conversion::Into))) => writeln!(
+            f,
+            "{}::{}(inner) => {}{}::{}(::core::convert::From::from(inner)){},",
+            from.name, from_variant.name, ok_start, to.name, to_variant.name, ok_end
+        ),
+        Some((to_variant, Some(Conversion::TryInto))) => {
+            let to_ty = to_variant.ty.as_ref().expect("No variant type");
+            writeln!(
+                f,
+                "{from}::{from_variant}(inner) => match <{to_ty} as ::core::convert::TryFrom<_>>::try_from(inner) {{ \
+                ::core::result::Result::Ok(converted) => ::core::result::Result::Ok({to}::{to_variant}(converted)), \
+                ::core::result::Result::Err(_) => ::core::result::Result::Err({from}::{from_variant}(inner)), }},",
+                from = from.name,
+                from_variant = from_variant.name,
+                to = to.name,
+                to_variant = to_variant.name,
+                to_ty = to_ty.rust_name,
+            )
+        }
+        None => {
+            let pattern = if from_variant.ty.is_some() {
+                "(..)"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "{}::{}{} => ::core::result::Result::Err(value),",
+                from.name, from_variant.name, pattern
+            )
+        }
+    }
+}
+
+fn write_impl_start(
+    f: &mut Formatter<'_>,
+    from: &GeneratedType<'_>,
+    to: &GeneratedType<'_>,
+    fallible: bool,
+) -> Result {
+    // One of the versions may be deprecated
+    writeln!(f, "#[allow(deprecated)]")?;
+    if fallible {
+        writeln!(
+            f,
+            "impl ::core::convert::TryFrom<{}> for {} {{",
+            from.name, to.name
+        )?;
+        writeln!(f, "type Error = {};", from.name)?;
+        writeln!(
+            f,
+            "fn try_from(value: {}) -> ::core::result::Result<Self, Self::Error> {{",
+            from.name
+        )
+    } else {
+        writeln!(
+            f,
+            "impl ::core::convert::From<{}> for {} {{",
+            from.name, to.name
+        )?;
+        writeln!(f, "fn from(value: {}) -> Self {{", from.name)
+    }
+}
+
+fn write_impl_end(f: &mut Formatter<'_>) -> Result {
+    // End function
+    writeln!(f, "}}")?;
+    // End impl
+    writeln!(f, "}}")
+}
+
+/// Returns how a field of one type can be converted into a field of another type, or None if
+/// the types are not compatible
+fn field_conversion(
+    from: &ResolvedType,
+    from_rust: &str,
+    to: &ResolvedType,
+    to_rust: &str,
+) -> Option<Conversion> {
+    if from_rust == to_rust {
+        return Some(Conversion::Move);
+    }
+    match (from, to) {
+        (
+            ResolvedType::Scalar(ResolvedScalarType::Primitive(from)),
+            ResolvedType::Scalar(ResolvedScalarType::Primitive(to)),
+        ) => primitive_conversion(from, to),
+        _ => None,
+    }
+}
+
+fn primitive_conversion(from: &PrimitiveType, to: &PrimitiveType) -> Option<Conversion> {
+    // The number of bits in each Rust integer type, and true if it is signed
+    let integer = |primitive: &PrimitiveType| match primitive {
+        PrimitiveType::Int { bits } => Some((round_up_integer_size(*bits), true)),
+        PrimitiveType::UInt { bits, .. } => Some((round_up_integer_size(*bits), false)),
+        _ => None,
+    };
+    match (integer(from), integer(to)) {
+        (Some((from_bits, from_signed)), Some((to_bits, to_signed))) => {
+            // These are the integer conversions that have From implementations
+            if from_bits < to_bits && (to_signed || !from_signed) {
+                Some(Conversion::Into)
+            } else {
+                Some(Conversion::TryInto)
+            }
+        }
+        _ => match (from, to) {
+            (PrimitiveType::Float16 { .. }, PrimitiveType::Float32 { .. })
+            | (PrimitiveType::Float16 { .. }, PrimitiveType::Float64 { .. })
+            | (PrimitiveType::Float32 { .. }, PrimitiveType::Float64 { .. }) => {
+                Some(Conversion::Into)
+            }
+            _ => None,
+        },
+    }
+}
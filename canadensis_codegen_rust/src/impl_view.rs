@@ -0,0 +1,102 @@
+//! Generates a view type that borrows byte arrays from the payload, and implements
+//! DeserializeBorrowed for it
+
+use std::fmt::{Display, Formatter, Result};
+
+use crate::impl_data_type::write_extent_bytes;
+use crate::impl_deserialize::{deserialize_enum, deserialize_struct};
+use crate::{write_doc_comments, GeneratedField, GeneratedType, GeneratedTypeKind};
+
+pub(crate) struct ImplementView<'t, 'c>(pub &'t GeneratedType<'c>);
+
+impl Display for ImplementView<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ty = self.0;
+        let view_name = format!("{}View", ty.name.type_name);
+
+        writeln!(
+            f,
+            "/// `{}` with variable-length byte arrays borrowed from the payload",
+            ty.cyphal_name
+        )?;
+        writeln!(f, "///")?;
+        writeln!(
+            f,
+            "/// This is a view of [`{}`] for deserializing without copying byte arrays.",
+            ty.name.type_name
+        )?;
+        writeln!(f, "///")?;
+        writeln!(
+            f,
+            "/// Implicit zero extension can't be applied to a borrowed array. If the payload ends \
+            inside a borrowed array, deserializing this type returns \
+            `DeserializeError::ArrayLength`, but deserializing [`{}`] succeeds and fills the \
+            missing bytes with zeros.",
+            ty.name.type_name
+        )?;
+        if let Some(deprecated) = &ty.deprecated {
+            writeln!(f, "{}", deprecated)?;
+        }
+        match &ty.kind {
+            GeneratedTypeKind::Struct(gstruct) => {
+                writeln!(f, "pub struct {}<'a> {{", view_name)?;
+                for field in &gstruct.fields {
+                    match field {
+                        GeneratedField::Data(data) => {
+                            write_doc_comments(f, data.comments)?;
+                            writeln!(f, "///")?;
+                            writeln!(f, "/// `{}`", data.cyphal_ty)?;
+                            writeln!(f, "pub {}: {},", data.name, data.view_ty)?;
+                        }
+                        GeneratedField::Padding(bits) => {
+                            writeln!(f, "// {} bits of padding", *bits)?;
+                        }
+                    }
+                }
+                writeln!(f, "}}")?;
+            }
+            GeneratedTypeKind::Enum(genum) => {
+                writeln!(f, "pub enum {}<'a> {{", view_name)?;
+                for variant in &genum.variants {
+                    write_doc_comments(f, variant.comments)?;
+                    match &variant.ty {
+                        Some(variant_ty) => {
+                            writeln!(f, "///")?;
+                            writeln!(f, "/// {}", variant_ty.cyphal_ty)?;
+                            writeln!(f, "{}({}),", variant.name, variant_ty.view_rust_name)?;
+                        }
+                        None => writeln!(f, "{},", variant.name)?,
+                    }
+                }
+                writeln!(f, "}}")?;
+            }
+        }
+
+        // DataType
+        writeln!(
+            f,
+            "impl ::canadensis_encoding::DataType for {}<'_> {{",
+            view_name
+        )?;
+        write_extent_bytes(f, &ty.extent)?;
+        writeln!(f, "}}")?;
+
+        // DeserializeBorrowed
+        writeln!(
+            f,
+            "impl<'a> ::canadensis_encoding::DeserializeBorrowed<'a> for {}<'a> {{",
+            view_name
+        )?;
+        writeln!(f, "fn deserialize_borrowed(cursor: &mut ::canadensis_encoding::ReadCursor<'a>) -> ::core::result::Result<Self, ::canadensis_encoding::DeserializeError> where Self: Sized {{")?;
+        match &ty.kind {
+            GeneratedTypeKind::Struct(gstruct) => {
+                deserialize_struct(f, &view_name, gstruct, false, true)?
+            }
+            GeneratedTypeKind::Enum(genum) => deserialize_enum(f, &view_name, genum, true)?,
+        }
+        // End function
+        writeln!(f, "}}")?;
+        // End impl
+        writeln!(f, "}}")
+    }
+}
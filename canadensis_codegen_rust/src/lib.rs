@@ -1,17 +1,29 @@
 extern crate canadensis_bit_length_set;
 extern crate canadensis_dsdl_frontend;
+#[cfg(feature = "fetch")]
+extern crate flate2;
 extern crate heck;
 extern crate num_bigint;
 extern crate regex;
+#[cfg(feature = "fetch")]
+extern crate sha2;
+#[cfg(feature = "fetch")]
+extern crate tar;
 extern crate thiserror;
+#[cfg(feature = "fetch")]
+extern crate ureq;
 
-use std::collections::BTreeMap;
+use std::cmp;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryInto;
+use std::io::Write;
 use std::iter;
+use std::path::{Path, PathBuf};
 
 use heck::{ToSnakeCase, ToUpperCamelCase};
 
 use canadensis_bit_length_set::BitLengthSet;
+use canadensis_dsdl_frontend::backend::Backend;
 use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
 use canadensis_dsdl_frontend::compiled::{
     CompiledDsdl, DsdlKind, Extent, FieldKind, Message, MessageKind, Struct, Union,
@@ -22,31 +34,396 @@ use canadensis_dsdl_frontend::TypeKey;
 
 use crate::error::EnumError;
 pub use crate::error::{Error, Result};
+use crate::impl_lazy::lazy_field_offsets;
 use crate::module_tree::ModuleTree;
-use crate::struct_as_enum::{generate_enum_from_struct, has_enum_directive};
+use crate::namespace_filter::selected_types;
+use crate::struct_as_enum::{
+    generate_companion_enum, generate_enum_from_struct, has_enum_directive, CompanionEnum,
+};
 
+pub mod c_header;
+pub mod cache;
 mod error;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+mod impl_arbitrary;
+mod impl_builder;
+mod impl_companion_enum;
 mod impl_constants;
 mod impl_data_type;
+mod impl_default;
+mod impl_defmt;
 mod impl_deserialize;
+mod impl_lazy;
+mod impl_plain_layout;
+mod impl_proptest;
 mod impl_serialize;
+mod impl_setters;
+mod impl_version_conversions;
+mod impl_view;
+pub mod lockfile;
 mod module_tree;
+mod namespace_filter;
+pub mod project;
 mod size_bits;
 mod struct_as_enum;
 
 /// Returns a Cargo.toml fragment with the packages that the generated code depends on
 pub fn generated_code_dependencies() -> String {
-    String::from(
+    generated_code_dependencies_with_config(&Config::default())
+}
+
+/// Returns a Cargo.toml fragment with the packages that the generated code depends on, and the
+/// features that enable the optional parts of code generated with the provided configuration
+///
+/// The fragment has an optional dependency and a feature for each of the `arbitrary`,
+/// `proptest`, `serde`, and `defmt` options that is enabled. If the `no_std` option is enabled, every
+/// dependency has its default features disabled, and the fragment also has `alloc` and `std`
+/// features. The `arbitrary` and `proptest` features enable the `std` feature, because those
+/// libraries require the standard library.
+pub fn generated_code_dependencies_with_config(config: &Config) -> String {
+    let no_default_features = if config.no_std {
+        ", default-features = false"
+    } else {
+        ""
+    };
+    let mut dependencies = format!(
         r#"[dependencies]
-half = { version = ">=2.2, <2.5", default-features = false, features = ["zerocopy"] }
-heapless = "0.8.0"
-zerocopy = "0.6.0"
-canadensis_core = "0.3.0"
-canadensis_encoding = "0.3.0"
-[dev-dependencies]
-memoffset = "0.8.0"
+half = {{ version = ">=2.2, <2.5", default-features = false, features = ["zerocopy"] }}
+heapless = {{ version = "0.8.0"{0} }}
+zerocopy = {{ version = "0.6.0"{0} }}
+canadensis_core = {{ version = "0.3.0"{0} }}
+canadensis_encoding = {{ version = "0.3.0"{0} }}
 "#,
-    )
+        no_default_features
+    );
+    let mut features = String::new();
+    if config.no_std {
+        let serde_features = if config.serde {
+            ("\"serde?/alloc\"", ", \"serde?/std\"")
+        } else {
+            ("", "")
+        };
+        features.push_str(&format!(
+            "alloc = [{}]\nstd = [\"alloc\"{}]\n",
+            serde_features.0, serde_features.1
+        ));
+    }
+    let requires_std = if config.no_std { ", \"std\"" } else { "" };
+    if config.arbitrary {
+        dependencies.push_str("arbitrary = { version = \"1.1.0\", optional = true }\n");
+        features.push_str(&format!(
+            "arbitrary = [\"dep:arbitrary\"{}]\n",
+            requires_std
+        ));
+    }
+    if config.proptest {
+        dependencies.push_str("proptest = { version = \"1.0.0\", optional = true }\n");
+        features.push_str(&format!("proptest = [\"dep:proptest\"{}]\n", requires_std));
+    }
+    if config.serde {
+        dependencies.push_str(&format!(
+            "serde = {{ version = \"1.0.100\"{}, features = [\"derive\"], optional = true }}\n",
+            no_default_features
+        ));
+        features.push_str(
+            "serde = [\"dep:serde\", \"heapless/serde\", \"canadensis_encoding/serde\"]\n",
+        );
+    }
+    if config.defmt {
+        dependencies.push_str("defmt = { version = \"0.3.0\", optional = true }\n");
+        features.push_str("defmt = [\"dep:defmt\"]\n");
+    }
+    dependencies.push_str("[dev-dependencies]\nmemoffset = \"0.8.0\"\n");
+    if !features.is_empty() {
+        dependencies.push_str("[features]\n");
+        dependencies.push_str(&features);
+    }
+    dependencies
+}
+
+/// Code generation configuration
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Generate view types that borrow variable-length byte arrays and strings from the
+    /// transfer payload
+    ///
+    /// For each type that contains a variable-length `uint8`, `byte`, or `utf8` array (directly
+    /// or in a nested composite type), this generates a type with the same name followed by
+    /// `View`. In the view type, these arrays are `&'a [u8]` or `&'a str` instead of
+    /// `heapless::Vec`. View types implement `canadensis_encoding::DeserializeBorrowed`, but not
+    /// `Serialize`.
+    ///
+    /// If a type in an external package contains a byte array, the external code must also have
+    /// been generated with this option enabled.
+    ///
+    /// Default false
+    pub views: bool,
+    /// Generate lazy view types that deserialize each field only when it is accessed
+    ///
+    /// For each struct type whose fields all have fixed offsets, this generates a type with the
+    /// same name followed by `Lazy`. A lazy view type borrows the serialized payload (it
+    /// implements `From<&'a [u8]>` and `AsRef<[u8]>`) and has a method for each field that
+    /// deserializes only that field. This is useful for large messages when only a few fields
+    /// are needed.
+    ///
+    /// A field has a fixed offset if all the fields before it have fixed serialized sizes, so
+    /// a type gets a lazy view if only its last field is a variable-length array or a delimited
+    /// type. Methods for fields that contain variable-length arrays or composite types return
+    /// `Result`s, because deserializing them can fail.
+    ///
+    /// Default false
+    pub lazy_views: bool,
+    /// Implement `arbitrary::Arbitrary` for each generated type, for use in fuzzing
+    ///
+    /// The implementations are only enabled when the crate that contains the generated code has
+    /// a feature called `arbitrary`. That feature must enable an optional dependency on the
+    /// `arbitrary` crate, version 1.1.0 or later.
+    ///
+    /// The generated values are always valid: integers are in the range of their DSDL types,
+    /// arrays are no longer than their maximum lengths, and each value can be serialized and
+    /// deserialized without changes (except for floating-point NaNs).
+    ///
+    /// If a type in an external package is used in a generated type, the external code must
+    /// also have been generated with this option enabled.
+    ///
+    /// Default false
+    pub arbitrary: bool,
+    /// Implement `proptest::arbitrary::Arbitrary` for each generated type, for use in
+    /// property-based tests
+    ///
+    /// The implementations (and `Debug` implementations, which proptest requires) are only
+    /// enabled when the crate that contains the generated code has a feature called `proptest`.
+    /// That feature must enable an optional dependency on the `proptest` crate, version 1.0.0
+    /// or later. Types that support zero-copy deserialization also implement `Clone` and `Copy`
+    /// when that feature is enabled.
+    ///
+    /// Like the `arbitrary` option, the strategies only produce valid values: integers are in
+    /// the range of their DSDL types, arrays are no longer than their maximum lengths, and each
+    /// union variant can be produced.
+    ///
+    /// If a type in an external package is used in a generated type, the external code must
+    /// also have been generated with this option enabled.
+    ///
+    /// Default false
+    pub proptest: bool,
+    /// Implement `serde::Serialize` and `serde::Deserialize` for each generated type
+    ///
+    /// The implementations are only enabled when the crate that contains the generated code has
+    /// a feature called `serde`. That feature must enable an optional dependency on the `serde`
+    /// crate (version 1.0.100 or later, with the `derive` feature) and the `serde` features of
+    /// `heapless` and `canadensis_encoding`. Fixed-length arrays longer than 32 elements use
+    /// `canadensis_encoding::serde_array`. Types that support zero-copy deserialization also
+    /// implement `Clone` and `Copy` when that feature is enabled.
+    ///
+    /// Deserialized values are not checked against the ranges of their DSDL types.
+    ///
+    /// If a type in an external package is used in a generated type, the external code must
+    /// also have been generated with this option enabled.
+    ///
+    /// Default false
+    pub serde: bool,
+    /// Implement `defmt::Format` for each generated type, for logging on embedded devices
+    ///
+    /// The implementations are only enabled when the crate that contains the generated code has
+    /// a feature called `defmt`. That feature must enable an optional dependency on the `defmt`
+    /// crate, version 0.3.0 or later.
+    ///
+    /// The output looks like the output of `Debug`. Only the first 16 elements of each array are
+    /// formatted, followed by the length of the array if it is longer. `float16` values are
+    /// converted to `f32`.
+    ///
+    /// If a type in an external package is used in a generated type, the external code must
+    /// also have been generated with this option enabled.
+    ///
+    /// Default false
+    pub defmt: bool,
+    /// Generate a builder for each struct type
+    ///
+    /// `TypeName::builder()` returns a builder with one method to set each field. The `build()`
+    /// method is only available after every field has been set, so forgetting a field causes a
+    /// compile error instead of a value with an unintended default.
+    ///
+    /// Default false
+    pub builders: bool,
+    /// Generate setters and a checked constructor for each struct type with integer fields that
+    /// are narrower than their Rust types
+    ///
+    /// For a field like `uint12 command`, which is stored in a `u16`, `set_command()` stores a
+    /// value after applying the field's cast mode: values of saturated fields that are out of
+    /// range are replaced with the closest value in range, and truncated fields keep only their
+    /// least significant bits. Signed integer fields are always saturated. `checked_new()` takes
+    /// a value for each field and returns `None` if any of them is out of range.
+    ///
+    /// Fields that are variable-length `uint8` or `byte` arrays also get setters that copy the
+    /// contents of a slice, and return a `CapacityError` if the slice is too long.
+    ///
+    /// Default false
+    pub setters: bool,
+    /// Generate types that support zero-copy serialization with `#[repr(C)]` instead of
+    /// `#[repr(C, packed)]` when their in-memory layout has no padding
+    ///
+    /// A type can use this layout if each field starts at a multiple of its alignment and the
+    /// total size is a multiple of the largest alignment, like a struct with a `uint32` field
+    /// followed by two `uint16` fields. References to the fields of these types are allowed, and
+    /// derives do not require them to be `Copy`. These types also get `ref_from_bytes()` and
+    /// `mut_from_bytes()` functions, which reinterpret a correctly sized and aligned byte slice as
+    /// a value without copying it. Those functions are only available on little-endian targets,
+    /// where the in-memory layout is the same as the serialized form.
+    ///
+    /// Default false
+    pub plain_layout: bool,
+    /// Generate an enum for each struct type whose constants name the values of its field
+    ///
+    /// A struct type gets an enum if it meets the requirements for the `#[canadensis(enum)]`
+    /// comment (one unsigned integer field of up to 32 bits, and constants of the same type with
+    /// distinct values) and has at least one constant. The enum has the name of the struct
+    /// followed by `Enum` and one variant for each constant. It can be converted into the struct
+    /// and the field type with `From`, and from the struct and the field type with `TryFrom`.
+    ///
+    /// Types with the `#[canadensis(enum)]` comment are still generated as enums, without a
+    /// struct.
+    ///
+    /// Default false
+    pub constant_enums: bool,
+    /// Implement conversions between consecutive versions of each type
+    ///
+    /// When two versions of a type with the same major version are generated (like
+    /// `uavcan.node.port.List.1.0` and `uavcan.node.port.List.1.1`, or `1.0` and `1.2` if `1.1`
+    /// is not generated), the newer version gets `From` or `TryFrom` implementations that convert
+    /// it to and from the older version. Fields and union variants are matched by name. Fields
+    /// that are not in the source version get default values, and fields that are not in the
+    /// destination version are dropped.
+    ///
+    /// A conversion is a `TryFrom` implementation if it can fail: if an integer field changed to
+    /// a type that can't represent all its values, or a union variant is not in the destination
+    /// version. The error is the original value. If a field or variant changed to an
+    /// incompatible type (like an array to a scalar), there is no conversion in that direction.
+    ///
+    /// Default false
+    pub version_conversions: bool,
+    /// Derive macros to apply to every generated type
+    ///
+    /// Each item is the path to a derive macro, like `Clone`, `::core::hash::Hash`, or
+    /// `::serde::Serialize`. View types do not get these derives.
+    ///
+    /// Every type in the output must support the derived traits. For example, `Eq` and `Hash`
+    /// can't be derived for a type that contains a floating-point field, and a type can only
+    /// derive a trait if all the types it contains (including types in external packages)
+    /// implement that trait.
+    ///
+    /// Types that support zero-copy serialization are `#[repr(C, packed)]`, and most derived
+    /// traits require packed types to be `Copy`. Those types also derive `Clone` and `Copy`
+    /// whenever they derive anything else.
+    ///
+    /// Default empty
+    pub derives: Vec<String>,
+    /// Derive macros to apply to specific types, in addition to `derives`
+    ///
+    /// Each key is a DSDL type name with a version (like `uavcan.node.Heartbeat.1.0`), which
+    /// matches only that version, or without a version (like `uavcan.node.Heartbeat`), which
+    /// matches all versions. Each value is a list of derive macro paths. For a service type,
+    /// the derives apply to both the request and the response.
+    ///
+    /// Default empty
+    pub type_derives: BTreeMap<String, Vec<String>>,
+    /// Namespaces to generate types from
+    ///
+    /// Each namespace is a dot-separated path like `uavcan.node` or `reg.udral.service.actuator`.
+    /// A type is in a namespace if the namespace is a prefix of its full name, so `uavcan.node`
+    /// contains `uavcan.node.Heartbeat` and `uavcan.node.port.List`, and
+    /// `uavcan.node.Heartbeat` contains only that type. Namespaces are not case-sensitive.
+    ///
+    /// If this is empty, types from all namespaces are generated.
+    ///
+    /// Types that a generated type refers to are always generated, even if they are not in any
+    /// of these namespaces.
+    ///
+    /// Default empty
+    pub include_namespaces: Vec<String>,
+    /// Namespaces to not generate types from
+    ///
+    /// Namespaces work the same way as in `include_namespaces`. A type in one of these
+    /// namespaces is not generated, unless another generated type refers to it.
+    ///
+    /// Default empty
+    pub exclude_namespaces: Vec<String>,
+    /// Generate a crate that is `no_std` unless its `std` feature is enabled
+    ///
+    /// The generated root module starts with
+    /// `#![cfg_attr(not(any(test, feature = "std")), no_std)]`, so it must be the root of a
+    /// crate, and it declares `extern crate alloc` when the `alloc` feature is enabled.
+    /// [`generated_code_dependencies_with_config`] returns the dependencies and features that
+    /// the crate needs.
+    ///
+    /// The generated types never use the standard library or an allocator: arrays are
+    /// `heapless::Vec`s or `canadensis_encoding::bits::BitArray`s, strings are byte arrays, and
+    /// all paths start with `::core`. Code generation fails with [`Error::StdDerive`] if any
+    /// of the derive macros in `derives` or `type_derives` is in the `std` crate.
+    ///
+    /// Default false
+    pub no_std: bool,
+}
+
+impl Config {
+    /// Returns the derive macros to apply to the type with the provided key
+    fn derives_for(&self, key: &TypeKey) -> Vec<String> {
+        let mut derives = self.derives.clone();
+        for type_name in [key.name().to_string(), key.to_string()] {
+            if let Some(type_derives) = self.type_derives.get(&type_name) {
+                for derive in type_derives {
+                    if !derives.contains(derive) {
+                        derives.push(derive.clone());
+                    }
+                }
+            }
+        }
+        derives
+    }
+
+    /// Returns an error if the `no_std` option is enabled and a derive macro requires std
+    fn check_no_std(&self) -> Result<()> {
+        if !self.no_std {
+            return Ok(());
+        }
+        let all_derives = self
+            .derives
+            .iter()
+            .chain(self.type_derives.values().flatten());
+        for derive in all_derives {
+            let path = derive.trim();
+            if path.starts_with("std::") || path.starts_with("::std::") {
+                return Err(Error::StdDerive {
+                    derive: path.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The attribute that makes serde use `canadensis_encoding::serde_array` for a field or variant
+const SERDE_ARRAY_ATTRIBUTE: &str =
+    "#[cfg_attr(feature = \"serde\", serde(with = \"::canadensis_encoding::serde_array\"))]";
+
+/// Returns true if a field or variant of the provided type is a fixed-length array that is too
+/// long for serde's built-in array support
+fn needs_serde_array(ty: &ResolvedType) -> bool {
+    match ty {
+        ResolvedType::FixedArray {
+            inner: ResolvedScalarType::Primitive(PrimitiveType::Boolean),
+            ..
+        } => false,
+        ResolvedType::FixedArray { len, .. } => *len > 32,
+        ResolvedType::Scalar(_) | ResolvedType::VariableArray { .. } => false,
+    }
+}
+
+/// Returns true if a list of derive macro paths contains a macro with the provided name
+fn contains_derive<S: AsRef<str>>(derives: &[S], name: &str) -> bool {
+    derives
+        .iter()
+        .any(|derive| derive.as_ref().rsplit("::").next().map(str::trim) == Some(name))
 }
 
 /// Generates a Rust module from the provided package of DSDL
@@ -54,25 +431,77 @@ memoffset = "0.8.0"
 /// `external_packages` is a map from DSDL package names to Rust module paths. A DSDL type in
 /// one of these packages (or any subpackage) will not have Rust code generated. Instead, any
 /// references to that type will refer to external Rust code in the corresponding module.
+///
+/// This function uses the default configuration.
 pub fn generate_code<'c>(
     package: &'c CompiledPackage,
     external_packages: &BTreeMap<Vec<String>, Vec<String>>,
 ) -> Result<GeneratedModule<'c>> {
+    generate_code_with_config(package, external_packages, &Config::default())
+}
+
+/// Generates a Rust module from the provided package of DSDL, with the provided configuration
+///
+/// `external_packages` works the same way as in [`generate_code`].
+pub fn generate_code_with_config<'c>(
+    package: &'c CompiledPackage,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+    config: &Config,
+) -> Result<GeneratedModule<'c>> {
+    config.check_no_std()?;
     let mut generated_types = Vec::new();
+    let selected = selected_types(package, config);
 
     for (key, dsdl) in package {
-        if external_module(key.name().path(), external_packages).is_none() {
+        if selected.contains(key) && external_module(key.name().path(), external_packages).is_none()
+        {
             // Generate a non-external type
-            generate_from_dsdl(key, dsdl, external_packages, &mut generated_types).map_err(
-                |e| Error::Dsdl {
-                    key: key.to_owned(),
-                    inner: Box::new(e),
-                },
-            )?;
+            let replacement = find_replacement(package, key);
+            let previous = if config.version_conversions {
+                find_previous_version(package, key, &selected, external_packages)
+            } else {
+                None
+            };
+            generate_from_dsdl(
+                key,
+                dsdl,
+                replacement.as_ref(),
+                previous,
+                external_packages,
+                config,
+                &mut generated_types,
+            )
+            .map_err(|e| Error::Dsdl {
+                key: key.to_owned(),
+                inner: Box::new(e),
+            })?;
         }
     }
     let tree: ModuleTree = generated_types.into_iter().collect();
-    Ok(GeneratedModule { tree })
+    Ok(GeneratedModule {
+        tree,
+        no_std: config.no_std,
+    })
+}
+
+/// A [`Backend`] that generates a Rust module, like [`generate_code_with_config`]
+#[derive(Debug, Clone, Default)]
+pub struct RustBackend {
+    /// The code generation options
+    pub config: Config,
+    /// DSDL package names and the Rust modules that contain their types, like the
+    /// `external_packages` argument to [`generate_code`]
+    pub external_packages: BTreeMap<Vec<String>, Vec<String>>,
+}
+
+impl Backend for RustBackend {
+    type Error = Error;
+
+    fn generate(&self, package: &CompiledPackage, output: &mut dyn Write) -> Result<()> {
+        let generated = generate_code_with_config(package, &self.external_packages, &self.config)?;
+        write!(output, "{}", generated)?;
+        Ok(())
+    }
 }
 
 /// If the provided key matches an external package, this function returns the Rust module path
@@ -98,10 +527,56 @@ fn external_module(
     None
 }
 
+/// Finds the newest non-deprecated version of a type that is newer than the provided key
+fn find_replacement(package: &CompiledPackage, key: &TypeKey) -> Option<TypeKey> {
+    package
+        .iter()
+        .filter(|(other_key, other_dsdl)| {
+            other_key.name() == key.name()
+                && other_key.version() > key.version()
+                && !dsdl_deprecated(other_dsdl)
+        })
+        .map(|(other_key, _)| other_key)
+        .max_by(|key1, key2| key1.version().cmp(key2.version()))
+        .cloned()
+}
+
+/// Finds the newest version of a type that is older than the provided key, has the same major
+/// version, and is generated as a non-external type
+fn find_previous_version<'c>(
+    package: &'c CompiledPackage,
+    key: &TypeKey,
+    selected: &BTreeSet<TypeKey>,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+) -> Option<(&'c TypeKey, &'c CompiledDsdl)> {
+    if external_module(key.name().path(), external_packages).is_some() {
+        return None;
+    }
+    package
+        .iter()
+        .filter(|(other_key, _)| {
+            other_key.name() == key.name()
+                && other_key.version().major == key.version().major
+                && other_key.version() < key.version()
+                && selected.contains(*other_key)
+        })
+        .max_by(|(key1, _), (key2, _)| key1.version().cmp(key2.version()))
+}
+
+fn dsdl_deprecated(dsdl: &CompiledDsdl) -> bool {
+    match &dsdl.kind {
+        DsdlKind::Message(message) => message.deprecated(),
+        DsdlKind::Service { request, .. } => request.deprecated(),
+    }
+}
+
 fn generate_from_dsdl<'c>(
     key: &TypeKey,
     dsdl: &'c CompiledDsdl,
+    replacement: Option<&TypeKey>,
+    previous: Option<(&TypeKey, &'c CompiledDsdl)>,
     external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+    config: &Config,
     items: &mut Vec<GeneratedItem<'c>>,
 ) -> std::result::Result<(), EnumError> {
     match &dsdl.kind {
@@ -122,21 +597,29 @@ fn generate_from_dsdl<'c>(
                         "::canadensis_core::SubjectId::from_truncating({})",
                         subject_id
                     ),
-                    deprecated: message.deprecated(),
+                    deprecated: Deprecated::new(message, replacement),
                     comments: "The fixed subject ID for this message type",
                 });
             }
 
-            items.push(GeneratedItem::Type(generate_rust_type(
+            let mut generated = generate_rust_type(
                 key,
                 message,
                 &rust_type,
                 message.extent().clone(),
                 MessageRole::Message,
-                message.deprecated(),
+                Deprecated::new(message, replacement),
                 message.comments(),
                 external_packages,
-            )?));
+                config,
+            )?;
+            generated.previous_version = generate_previous_version(
+                previous,
+                MessageRole::Message,
+                external_packages,
+                config,
+            )?;
+            items.push(GeneratedItem::Type(generated));
         }
         DsdlKind::Service { request, response } => {
             let rust_type = ServiceTypeNames::for_service_type(key, external_packages);
@@ -155,53 +638,171 @@ fn generate_from_dsdl<'c>(
                         "::canadensis_core::ServiceId::from_truncating({})",
                         service_id
                     ),
-                    deprecated: request.deprecated(),
+                    deprecated: Deprecated::new(request, replacement),
                     comments: "The fixed ID of this service",
                 });
             }
 
-            items.push(GeneratedItem::Type(generate_rust_type(
+            let mut generated_request = generate_rust_type(
                 key,
                 request,
                 &rust_type.request,
                 request.extent().clone(),
                 MessageRole::Request,
-                request.deprecated(),
+                Deprecated::new(request, replacement),
                 request.comments(),
                 external_packages,
-            )?));
-            items.push(GeneratedItem::Type(generate_rust_type(
+                config,
+            )?;
+            generated_request.previous_version = generate_previous_version(
+                previous,
+                MessageRole::Request,
+                external_packages,
+                config,
+            )?;
+            items.push(GeneratedItem::Type(generated_request));
+            let mut generated_response = generate_rust_type(
                 key,
                 response,
                 &rust_type.response,
                 response.extent().clone(),
                 MessageRole::Response,
-                response.deprecated(),
+                Deprecated::new(response, replacement),
                 response.comments(),
                 external_packages,
-            )?));
+                config,
+            )?;
+            generated_response.previous_version = generate_previous_version(
+                previous,
+                MessageRole::Response,
+                external_packages,
+                config,
+            )?;
+            items.push(GeneratedItem::Type(generated_response));
         }
     }
     Ok(())
 }
 
+/// Generates the message, request, or response type of a previous version of a type, so that
+/// conversions between the versions can be generated
+///
+/// This returns None if there is no previous version, or it does not have a part with the
+/// provided role (for example, if a message type became a service type).
+fn generate_previous_version<'c>(
+    previous: Option<(&TypeKey, &'c CompiledDsdl)>,
+    role: MessageRole,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+    config: &Config,
+) -> std::result::Result<Option<Box<GeneratedType<'c>>>, EnumError> {
+    let (key, dsdl) = match previous {
+        Some(previous) => previous,
+        None => return Ok(None),
+    };
+    let (message, rust_type) = match (&dsdl.kind, &role) {
+        (DsdlKind::Message(message), MessageRole::Message) => (
+            message,
+            RustTypeName::for_message_type(key, external_packages),
+        ),
+        (DsdlKind::Service { request, .. }, MessageRole::Request) => (
+            request,
+            ServiceTypeNames::for_service_type(key, external_packages).request,
+        ),
+        (DsdlKind::Service { response, .. }, MessageRole::Response) => (
+            response,
+            ServiceTypeNames::for_service_type(key, external_packages).response,
+        ),
+        _ => return Ok(None),
+    };
+    let generated = generate_rust_type(
+        key,
+        message,
+        &rust_type,
+        message.extent().clone(),
+        role,
+        None,
+        message.comments(),
+        external_packages,
+        config,
+    )?;
+    Ok(Some(Box::new(generated)))
+}
+
 /// A module of generated Rust code
+///
+/// The `Display` implementation writes all the code as one file.
 pub struct GeneratedModule<'c> {
     tree: ModuleTree<'c>,
+    /// If the root module should make the crate `no_std`
+    no_std: bool,
 }
 
+impl GeneratedModule<'_> {
+    /// Divides the generated code into files
+    ///
+    /// `root` is the path to the file that contains the top-level modules, which must be the root
+    /// of a crate (normally `lib.rs`). The other files are in the same directory as `root`, or in
+    /// subdirectories. The first returned file is always `root`.
+    pub fn files(&self, root: &Path, layout: Layout) -> Vec<GeneratedFile> {
+        let mut files = vec![GeneratedFile {
+            path: root.to_owned(),
+            contents: RootModule(self, layout).to_string(),
+        }];
+        let directory = root.parent().unwrap_or_else(|| Path::new(""));
+        self.tree.add_files(directory, layout, &mut files);
+        files
+    }
+}
+
+/// How generated code is divided into files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// All modules in one file
+    #[default]
+    SingleFile,
+    /// One file for each namespace, like `uavcan/node.rs`, which contains the modules for the
+    /// types in that namespace
+    FilePerNamespace,
+    /// One file for each module, including a file for each version of each type, like
+    /// `uavcan/node/heartbeat_1_0.rs`
+    FilePerType,
+}
+
+/// A file of generated code
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    path: PathBuf,
+    contents: String,
+}
+
+impl GeneratedFile {
+    /// Returns the path to this file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Returns the code in this file
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+}
+
+/// The root file of generated code with a layout
+struct RootModule<'m, 'c>(&'m GeneratedModule<'c>, Layout);
+
 fn generate_rust_type<'c>(
     key: &TypeKey,
     message: &'c Message,
     rust_type: &RustTypeName,
     extent: Extent,
     role: MessageRole,
-    deprecated: bool,
+    deprecated: Option<Deprecated>,
     comments: &'c str,
     external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+    config: &Config,
 ) -> std::result::Result<GeneratedType<'c>, EnumError> {
     let length = message.bit_length();
-    match message.kind() {
+    let view = config.views && message_needs_view(message);
+    let mut generated = match message.kind() {
         MessageKind::Struct(cyphal_struct) => {
             if has_enum_directive(comments) {
                 generate_enum_from_struct(
@@ -215,9 +816,9 @@ fn generate_rust_type<'c>(
                     deprecated,
                     comments,
                     external_packages,
-                )
+                )?
             } else {
-                Ok(GeneratedType::new_struct(
+                let mut generated = GeneratedType::new_struct(
                     key,
                     rust_type.clone(),
                     length,
@@ -228,14 +829,23 @@ fn generate_rust_type<'c>(
                     deprecated,
                     comments,
                     external_packages,
-                ))
+                );
+                generated.view = view;
+                if config.constant_enums {
+                    generated.companion_enum = generate_companion_enum(
+                        cyphal_struct,
+                        message.constants(),
+                        external_packages,
+                    );
+                }
+                generated
             }
         }
         MessageKind::Union(cyphal_union) => {
             if has_enum_directive(comments) {
                 return Err(EnumError::NotStruct);
             }
-            Ok(GeneratedType::new_enum(
+            let mut generated = GeneratedType::new_enum(
                 key,
                 rust_type.clone(),
                 length,
@@ -246,9 +856,32 @@ fn generate_rust_type<'c>(
                 deprecated,
                 comments,
                 external_packages,
-            ))
+            );
+            generated.view = view;
+            generated
         }
+    };
+    if let GeneratedTypeKind::Struct(gstruct) = &generated.kind {
+        generated.lazy = config.lazy_views && lazy_field_offsets(gstruct).is_some();
     }
+    generated.arbitrary = config.arbitrary;
+    generated.proptest = config.proptest;
+    generated.serde = config.serde;
+    generated.defmt = config.defmt;
+    generated.builder = config.builders;
+    generated.setters = config.setters;
+    if config.plain_layout && generated.supports_zero_copy() {
+        if let GeneratedTypeKind::Struct(gstruct) = &generated.kind {
+            generated.plain_layout =
+                plain_layout_alignment(gstruct.fields.iter().filter_map(|field| match field {
+                    GeneratedField::Data(data) => Some(data.cyphal_ty),
+                    GeneratedField::Padding(_) => None,
+                }))
+                .is_some();
+        }
+    }
+    generated.derives = config.derives_for(key);
+    Ok(generated)
 }
 
 enum GeneratedItem<'c> {
@@ -257,7 +890,7 @@ enum GeneratedItem<'c> {
         name: RustTypeName,
         ty: String,
         value: String,
-        deprecated: bool,
+        deprecated: Option<Deprecated>,
         comments: &'c str,
     },
 }
@@ -273,8 +906,30 @@ impl GeneratedItem<'_> {
     /// Returns true if this item is deprecated
     pub fn deprecated(&self) -> bool {
         match self {
-            GeneratedItem::Type(ty) => ty.deprecated,
-            GeneratedItem::Constant { deprecated, .. } => *deprecated,
+            GeneratedItem::Type(ty) => ty.deprecated.is_some(),
+            GeneratedItem::Constant { deprecated, .. } => deprecated.is_some(),
+        }
+    }
+}
+
+/// Information about a deprecated DSDL type
+///
+/// This is displayed as a `#[deprecated]` attribute.
+#[derive(Debug, Clone)]
+struct Deprecated {
+    /// The newest non-deprecated version of the same type, if one exists
+    replacement: Option<TypeKey>,
+}
+
+impl Deprecated {
+    /// Returns deprecation information if the provided message is deprecated
+    fn new(message: &Message, replacement: Option<&TypeKey>) -> Option<Self> {
+        if message.deprecated() {
+            Some(Deprecated {
+                replacement: replacement.cloned(),
+            })
+        } else {
+            None
         }
     }
 }
@@ -287,8 +942,33 @@ struct GeneratedType<'c> {
     role: MessageRole,
     kind: GeneratedTypeKind<'c>,
     constants: Constants,
-    deprecated: bool,
+    deprecated: Option<Deprecated>,
     comments: &'c str,
+    /// True if a view type should also be generated
+    view: bool,
+    /// True if a lazy view type should also be generated
+    lazy: bool,
+    /// True if arbitrary::Arbitrary should be implemented
+    arbitrary: bool,
+    /// True if proptest::arbitrary::Arbitrary should be implemented
+    proptest: bool,
+    /// True if serde::Serialize and serde::Deserialize should be implemented
+    serde: bool,
+    /// True if defmt::Format should be implemented
+    defmt: bool,
+    /// True if a builder should be generated
+    builder: bool,
+    /// True if setters for narrow integer fields should be generated
+    setters: bool,
+    /// True if this type supports zero-copy and should be `#[repr(C)]` instead of
+    /// `#[repr(C, packed)]`
+    plain_layout: bool,
+    /// An enum to generate with the constants of this struct
+    companion_enum: Option<CompanionEnum<'c>>,
+    /// Paths to derive macros to apply to this type
+    derives: Vec<String>,
+    /// The previous version of this type, if conversions to and from it should be generated
+    previous_version: Option<Box<GeneratedType<'c>>>,
 }
 
 enum GeneratedTypeKind<'c> {
@@ -305,7 +985,7 @@ impl<'c> GeneratedType<'c> {
         role: MessageRole,
         cyphal_struct: &'c Struct,
         constants: Constants,
-        deprecated: bool,
+        deprecated: Option<Deprecated>,
         comments: &'c str,
         external_packages: &BTreeMap<Vec<String>, Vec<String>>,
     ) -> Self {
@@ -343,7 +1023,7 @@ impl<'c> GeneratedType<'c> {
         role: MessageRole,
         cyphal_union: &'c Union,
         constants: Constants,
-        deprecated: bool,
+        deprecated: Option<Deprecated>,
         comments: &'c str,
         external_packages: &BTreeMap<Vec<String>, Vec<String>>,
     ) -> Self {
@@ -385,7 +1065,7 @@ impl<'c> GeneratedType<'c> {
         role: MessageRole,
         kind: GeneratedTypeKind<'c>,
         constants: Constants,
-        deprecated: bool,
+        deprecated: Option<Deprecated>,
         comments: &'c str,
     ) -> Self {
         GeneratedType {
@@ -398,6 +1078,18 @@ impl<'c> GeneratedType<'c> {
             constants,
             deprecated,
             comments,
+            view: false,
+            lazy: false,
+            arbitrary: false,
+            proptest: false,
+            serde: false,
+            defmt: false,
+            builder: false,
+            setters: false,
+            plain_layout: false,
+            companion_enum: None,
+            derives: Vec::new(),
+            previous_version: None,
         }
     }
 
@@ -447,6 +1139,10 @@ enum GeneratedField<'c> {
 struct GeneratedDataField<'c> {
     name: String,
     ty: String,
+    /// The type of each element if this field is an array, or the same as `ty` otherwise
+    element_ty: String,
+    /// The type of this field in a view type
+    view_ty: String,
     cyphal_ty: &'c ResolvedType,
     always_aligned: bool,
     comments: &'c str,
@@ -468,7 +1164,11 @@ fn type_supports_zero_copy(ty: &ResolvedType) -> bool {
 
 fn scalar_supports_zero_copy(scalar: &ResolvedScalarType) -> bool {
     match scalar {
-        ResolvedScalarType::Composite { inner, .. } => message_supports_zero_copy(inner),
+        // A nested delimited type has a delimiter header, which is not part of the in-memory
+        // representation
+        ResolvedScalarType::Composite { inner, .. } => {
+            matches!(inner.extent(), Extent::Sealed) && message_supports_zero_copy(inner)
+        }
         ResolvedScalarType::Primitive(primitive) => match primitive {
             PrimitiveType::Boolean => false,
             PrimitiveType::Int { bits } | PrimitiveType::UInt { bits, .. } => {
@@ -509,6 +1209,58 @@ fn message_supports_zero_copy(message: &Message) -> bool {
     }
 }
 
+/// Returns the alignment of a struct with fields of the provided types if it can be `#[repr(C)]`
+/// with no padding between or after its fields, or None if it must be packed
+///
+/// All the fields must support zero-copy. Nested struct types are assumed to use `#[repr(C)]` if
+/// they can. If one of them is actually packed (for example, because it was generated separately
+/// without the plain layout option), its alignment is smaller and there is still no padding.
+fn plain_layout_alignment<'t, I>(fields: I) -> Option<u64>
+where
+    I: IntoIterator<Item = &'t ResolvedType>,
+{
+    let mut offset_bytes = 0;
+    let mut max_alignment = 1;
+    for ty in fields {
+        let alignment = type_alignment(ty);
+        if offset_bytes % alignment != 0 {
+            return None;
+        }
+        offset_bytes += ty.size().min_value() / 8;
+        max_alignment = cmp::max(max_alignment, alignment);
+    }
+    if offset_bytes % max_alignment == 0 {
+        Some(max_alignment)
+    } else {
+        None
+    }
+}
+
+/// Returns the alignment in bytes of the Rust type of a field that supports zero-copy
+fn type_alignment(ty: &ResolvedType) -> u64 {
+    match ty {
+        ResolvedType::Scalar(scalar) | ResolvedType::FixedArray { inner: scalar, .. } => {
+            match scalar {
+                ResolvedScalarType::Composite { inner, .. } => match inner.kind() {
+                    MessageKind::Struct(mstruct) => {
+                        plain_layout_alignment(mstruct.fields.iter().filter_map(|field| {
+                            match field.kind() {
+                                FieldKind::Data { ty, .. } => Some(ty),
+                                FieldKind::Padding(_) => None,
+                            }
+                        }))
+                        .unwrap_or(1)
+                    }
+                    MessageKind::Union(_) => 1,
+                },
+                ResolvedScalarType::Primitive(primitive) => primitive.bit_length() / 8,
+                ResolvedScalarType::Void { .. } => 1,
+            }
+        }
+        ResolvedType::VariableArray { .. } => 1,
+    }
+}
+
 impl<'c> GeneratedField<'c> {
     pub fn data(
         ty: &'c ResolvedType,
@@ -520,6 +1272,8 @@ impl<'c> GeneratedField<'c> {
         GeneratedField::Data(GeneratedDataField {
             name: make_rust_identifier(name),
             ty: to_rust_type(ty, external_packages),
+            element_ty: scalar_to_rust_type(ty.scalar(), external_packages),
+            view_ty: to_view_rust_type(ty, always_aligned, external_packages),
             cyphal_ty: ty,
             always_aligned,
             comments,
@@ -557,6 +1311,9 @@ impl<'c> GeneratedVariant<'c> {
             name: make_rust_identifier(name).to_upper_camel_case(),
             ty: ty.map(|ty| ReferencedType {
                 rust_name: to_rust_type(&ty, external_packages),
+                element_rust_name: scalar_to_rust_type(ty.scalar(), external_packages),
+                // Union variants are always aligned
+                view_rust_name: to_view_rust_type(&ty, true, external_packages),
                 cyphal_ty: ty,
             }),
             comments,
@@ -577,6 +1334,10 @@ enum MessageRole {
 /// The type of a field or variant
 struct ReferencedType {
     rust_name: String,
+    /// The type of each element if this is an array, or the same as `rust_name` otherwise
+    element_rust_name: String,
+    /// The name of this type in a view type
+    view_rust_name: String,
     cyphal_ty: ResolvedType,
 }
 
@@ -606,6 +1367,14 @@ fn to_rust_type(
                 len
             )
         }
+        ResolvedType::VariableArray {
+            inner: ResolvedScalarType::Primitive(PrimitiveType::Utf8),
+            max_len,
+        } => {
+            // Use a String, which has the same representation as a Vec<u8> but is always
+            // valid UTF-8
+            format!("::heapless::String<{}>", max_len)
+        }
         ResolvedType::VariableArray { inner, max_len } => {
             format!(
                 "::heapless::Vec<{}, {}>",
@@ -637,6 +1406,104 @@ fn scalar_to_rust_type(
     }
 }
 
+/// Returns true if a variable-length array of this type can be borrowed from the payload in a
+/// view type
+fn primitive_can_borrow(primitive: &PrimitiveType) -> bool {
+    matches!(
+        primitive,
+        PrimitiveType::UInt { bits: 8, .. } | PrimitiveType::Byte | PrimitiveType::Utf8
+    )
+}
+
+/// Returns true if a field of the provided type would be different in a view type
+///
+/// Only byte arrays that are always aligned can be borrowed.
+fn type_needs_view(ty: &ResolvedType, always_aligned: bool) -> bool {
+    match ty {
+        ResolvedType::VariableArray {
+            inner: ResolvedScalarType::Primitive(primitive),
+            ..
+        } => always_aligned && primitive_can_borrow(primitive),
+        ResolvedType::Scalar(ResolvedScalarType::Composite { inner, .. })
+        | ResolvedType::FixedArray {
+            inner: ResolvedScalarType::Composite { inner, .. },
+            ..
+        }
+        | ResolvedType::VariableArray {
+            inner: ResolvedScalarType::Composite { inner, .. },
+            ..
+        } => message_needs_view(inner),
+        _ => false,
+    }
+}
+
+/// Returns true if a message contains any byte arrays (directly or in nested types) that a view
+/// type can borrow
+fn message_needs_view(message: &Message) -> bool {
+    match message.kind() {
+        MessageKind::Struct(mstruct) => mstruct.fields.iter().any(|field| match field.kind() {
+            FieldKind::Padding(_) => false,
+            FieldKind::Data { ty, .. } => type_needs_view(ty, field.always_aligned()),
+        }),
+        // Union variants are always aligned
+        MessageKind::Union(union) => union
+            .variants
+            .iter()
+            .any(|variant| type_needs_view(variant.ty(), true)),
+    }
+}
+
+/// Returns the type of a field in a view type
+fn to_view_rust_type(
+    ty: &ResolvedType,
+    always_aligned: bool,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+) -> String {
+    if !type_needs_view(ty, always_aligned) {
+        return to_rust_type(ty, external_packages);
+    }
+    match ty {
+        ResolvedType::VariableArray {
+            inner: ResolvedScalarType::Primitive(PrimitiveType::Utf8),
+            ..
+        } => "&'a str".to_owned(),
+        ResolvedType::VariableArray {
+            inner: ResolvedScalarType::Primitive(_),
+            ..
+        } => "&'a [u8]".to_owned(),
+        ResolvedType::Scalar(scalar) => scalar_to_view_rust_type(scalar, external_packages),
+        ResolvedType::FixedArray { inner, len } => {
+            format!(
+                "[{}; {}]",
+                scalar_to_view_rust_type(inner, external_packages),
+                len
+            )
+        }
+        ResolvedType::VariableArray { inner, max_len } => {
+            format!(
+                "::heapless::Vec<{}, {}>",
+                scalar_to_view_rust_type(inner, external_packages),
+                max_len
+            )
+        }
+    }
+}
+
+fn scalar_to_view_rust_type(
+    scalar: &ResolvedScalarType,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+) -> String {
+    match scalar {
+        ResolvedScalarType::Composite { key, inner } if message_needs_view(inner) => {
+            format!(
+                "{}View<'a>",
+                RustTypeName::for_message_type(key, external_packages)
+            )
+        }
+        _ => scalar_to_rust_type(scalar, external_packages),
+    }
+}
+
 fn round_up_integer_size(bits: u8) -> u8 {
     match bits {
         0..=8 => 8,
@@ -741,12 +1608,26 @@ mod fmt_impl {
     use std::convert::TryFrom;
     use std::fmt::{Display, Formatter, Result, Write};
 
+    use crate::impl_arbitrary::ImplementArbitrary;
+    use crate::impl_builder::ImplementBuilder;
+    use crate::impl_companion_enum::ImplementCompanionEnum;
     use crate::impl_constants::ImplementConstants;
     use crate::impl_data_type::ImplementDataType;
+    use crate::impl_default::ImplementDefault;
+    use crate::impl_defmt::ImplementDefmt;
     use crate::impl_deserialize::ImplementDeserialize;
+    use crate::impl_lazy::ImplementLazy;
+    use crate::impl_plain_layout::ImplementPlainLayout;
+    use crate::impl_proptest::ImplementProptest;
     use crate::impl_serialize::ImplementSerialize;
+    use crate::impl_setters::ImplementSetters;
+    use crate::impl_version_conversions::ImplementVersionConversions;
+    use crate::impl_view::ImplementView;
+    use crate::module_tree::ModuleContents;
     use crate::{
-        write_doc_comments, GeneratedItem, GeneratedModule, GeneratedTypeKind, GeneratedVariant,
+        contains_derive, needs_serde_array, write_doc_comments, Deprecated, GeneratedItem,
+        GeneratedModule, GeneratedTypeKind, GeneratedVariant, Layout, RootModule,
+        SERDE_ARRAY_ATTRIBUTE,
     };
 
     use super::{GeneratedField, GeneratedType, RustTypeName};
@@ -786,19 +1667,79 @@ mod fmt_impl {
 
             // Derive zerocopy traits if possible
             let supports_zero_copy = self.supports_zero_copy();
+            let packed = supports_zero_copy && !self.plain_layout;
             if supports_zero_copy {
                 writeln!(f, "#[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]")?;
-                writeln!(f, "#[repr(C, packed)]")?;
+                if packed {
+                    writeln!(f, "#[repr(C, packed)]")?;
+                } else {
+                    writeln!(f, "#[repr(C)]")?;
+                }
+            }
+
+            // Configured derives, plus Clone and Copy for packed types
+            let mut derives: Vec<&str> = self.derives.iter().map(String::as_str).collect();
+            if packed && !derives.is_empty() {
+                for name in ["Clone", "Copy"] {
+                    if !contains_derive(&derives, name) {
+                        derives.push(name);
+                    }
+                }
+            }
+            if !derives.is_empty() {
+                writeln!(f, "#[derive({})]", derives.join(", "))?;
+            }
+
+            // Deriving most traits for a packed struct requires all its fields to be Copy.
+            // proptest and serde need that, but only when their features are enabled.
+            if packed && !contains_derive(&derives, "Copy") {
+                let features: Vec<&str> = [(self.proptest, "proptest"), (self.serde, "serde")]
+                    .iter()
+                    .filter(|(enabled, _)| *enabled)
+                    .map(|(_, feature)| *feature)
+                    .collect();
+                let condition = match features.as_slice() {
+                    [] => None,
+                    [feature] => Some(format!("feature = \"{}\"", feature)),
+                    _ => Some(format!(
+                        "any({})",
+                        features
+                            .iter()
+                            .map(|feature| format!("feature = \"{}\"", feature))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                };
+                if let Some(condition) = condition {
+                    writeln!(f, "#[cfg_attr({}, derive(Clone, Copy))]", condition)?;
+                }
+            }
+
+            // proptest requires Debug for all values
+            if self.proptest && !contains_derive(&derives, "Debug") {
+                writeln!(f, "#[cfg_attr(feature = \"proptest\", derive(Debug))]")?;
+            }
+
+            if self.serde {
+                writeln!(
+                    f,
+                    "#[cfg_attr(feature = \"serde\", derive(::serde::Serialize, ::serde::Deserialize))]"
+                )?;
             }
 
-            if self.deprecated {
-                writeln!(f, "#[deprecated]")?;
+            if let Some(deprecated) = &self.deprecated {
+                writeln!(f, "{}", deprecated)?;
             }
 
             match &self.kind {
                 GeneratedTypeKind::Struct(inner) => {
                     writeln!(f, "pub struct {} {{", self.name.type_name)?;
                     for field in &inner.fields {
+                        if let GeneratedField::Data(data) = field {
+                            if self.serde && needs_serde_array(data.cyphal_ty) {
+                                writeln!(f, "{}", SERDE_ARRAY_ATTRIBUTE)?;
+                            }
+                        }
                         field.fmt(f)?;
                     }
                     writeln!(f, "}}")?;
@@ -806,12 +1747,26 @@ mod fmt_impl {
                 GeneratedTypeKind::Enum(inner) => {
                     writeln!(f, "pub enum {} {{", self.name.type_name)?;
                     for variant in &inner.variants {
+                        if let Some(ty) = &variant.ty {
+                            if self.serde && needs_serde_array(&ty.cyphal_ty) {
+                                writeln!(f, "{}", SERDE_ARRAY_ATTRIBUTE)?;
+                            }
+                        }
                         variant.fmt(f)?;
                     }
                     writeln!(f, "}}")?;
                 }
             }
 
+            Display::fmt(&ImplementDefault(self), f)?;
+            if self.builder {
+                Display::fmt(&ImplementBuilder(self), f)?;
+            }
+            if self.setters {
+                Display::fmt(&ImplementSetters(self), f)?;
+            }
+            Display::fmt(&ImplementCompanionEnum(self), f)?;
+            Display::fmt(&ImplementVersionConversions(self), f)?;
             Display::fmt(&ImplementDataType(self), f)?;
             Display::fmt(&ImplementConstants(self), f)?;
 
@@ -831,6 +1786,30 @@ mod fmt_impl {
                 f,
             )?;
 
+            if self.plain_layout {
+                Display::fmt(&ImplementPlainLayout(self), f)?;
+            }
+
+            if self.view {
+                Display::fmt(&ImplementView(self), f)?;
+            }
+
+            if self.lazy {
+                Display::fmt(&ImplementLazy(self), f)?;
+            }
+
+            if self.arbitrary {
+                Display::fmt(&ImplementArbitrary(self), f)?;
+            }
+
+            if self.proptest {
+                Display::fmt(&ImplementProptest(self), f)?;
+            }
+
+            if self.defmt {
+                Display::fmt(&ImplementDefmt { ty: self, packed }, f)?;
+            }
+
             if supports_zero_copy {
                 // Add some assertions about the type size and field layout
                 writeln!(f, "#[test] fn test_layout() {{")?;
@@ -929,25 +1908,43 @@ mod fmt_impl {
 
     impl Display for GeneratedModule<'_> {
         fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            Display::fmt(&RootModule(self, Layout::SingleFile), f)
+        }
+    }
+
+    impl Display for RootModule<'_, '_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            let RootModule(module, layout) = *self;
+            if module.no_std {
+                writeln!(
+                    f,
+                    r#"#![cfg_attr(not(any(test, feature = "std")), no_std)]"#
+                )?;
+                writeln!(f, r#"#[cfg(feature = "alloc")] extern crate alloc;"#)?;
+            }
             writeln!(
                 f,
                 r#"#[cfg(not(target_endian = "little"))] compile_error!("Zero-copy serialization requires a little-endian target");"#
             )?;
             assert!(
-                self.tree.items.is_empty(),
+                module.tree.items.is_empty(),
                 "Top-level types are not allowed"
             );
-            for (sub_name, submodule) in &self.tree.children {
+            for (sub_name, submodule) in &module.tree.children {
                 // Adjust lints for every top-level module
                 writeln!(
                     f,
                     "#[allow(unused_variables, unused_braces, unused_parens)]"
                 )?;
-                writeln!(f, "#[allow(clippy::identity_op)]")?;
+                writeln!(f, "#[allow(clippy::identity_op, clippy::derivable_impls)]")?;
 
-                writeln!(f, "pub mod {} {{", sub_name)?;
-                Display::fmt(submodule, f)?;
-                writeln!(f, "}}")?;
+                if submodule.in_own_file(layout) {
+                    writeln!(f, "pub mod {};", sub_name)?;
+                } else {
+                    writeln!(f, "pub mod {} {{", sub_name)?;
+                    Display::fmt(&ModuleContents(submodule, layout), f)?;
+                    writeln!(f, "}}")?;
+                }
             }
 
             Ok(())
@@ -966,13 +1963,22 @@ mod fmt_impl {
                     comments,
                 } => {
                     write_doc_comments(f, comments)?;
-                    let deprecated_attr = if *deprecated { "#[deprecated]" } else { "" };
-                    writeln!(
-                        f,
-                        "{} pub const {}: {} = {};",
-                        deprecated_attr, name.type_name, ty, value
-                    )
+                    if let Some(deprecated) = deprecated {
+                        writeln!(f, "{}", deprecated)?;
+                    }
+                    writeln!(f, "pub const {}: {} = {};", name.type_name, ty, value)
+                }
+            }
+        }
+    }
+
+    impl Display for Deprecated {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            match &self.replacement {
+                Some(replacement) => {
+                    write!(f, "#[deprecated(note = \"Replaced by {}\")]", replacement)
                 }
+                None => write!(f, "#[deprecated]"),
             }
         }
     }
@@ -0,0 +1,144 @@
+//! A record of the serialized layout of each type, used to detect changes that should have
+//! come with a new version
+//!
+//! Once a version of a type has been published, its layout must not change. A lockfile records
+//! a hash of the [layout description](canadensis_dsdl_frontend::compiled::Message::layout_description)
+//! of each type. Comparing a lockfile from an earlier compilation with the current types shows any
+//! types whose layout changed but whose version stayed the same.
+//!
+//! The lockfile is a text file. The first line is `version` followed by a space and the
+//! version of the lockfile format. Each other line is the name and version of a type, a space,
+//! and the hash of its layout as a hexadecimal number. Hashes use the same FNV-1a function as the
+//! [cache](crate::cache).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::compiled::DsdlKind;
+
+use crate::cache::hash_bytes;
+
+/// The version of the lockfile format
+const LOCKFILE_VERSION: u32 = 1;
+
+/// The layout hashes of a set of types
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lockfile {
+    /// The name and version of each type, and the hash of its layout
+    hashes: BTreeMap<String, u64>,
+}
+
+impl Lockfile {
+    /// Calculates the layout hashes of the types in a package
+    pub fn from_package(package: &CompiledPackage) -> Self {
+        let hashes = package
+            .iter()
+            .map(|(key, dsdl)| {
+                let description = match &dsdl.kind {
+                    DsdlKind::Message(message) => message.layout_description(),
+                    DsdlKind::Service { request, response } => format!(
+                        "request: {}\nresponse: {}",
+                        request.layout_description(),
+                        response.layout_description()
+                    ),
+                };
+                (key.to_string(), hash_bytes(description.as_bytes()))
+            })
+            .collect();
+        Lockfile { hashes }
+    }
+
+    /// Reads a lockfile
+    ///
+    /// This function returns `Ok(None)` if the file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Option<Lockfile>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Lockfile::parse(&content)
+                .map(Some)
+                .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "Invalid lockfile")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(content: &str) -> Option<Lockfile> {
+        let mut lines = content.lines();
+        let version = lines
+            .next()?
+            .strip_prefix("version ")?
+            .parse::<u32>()
+            .ok()?;
+        if version != LOCKFILE_VERSION {
+            return None;
+        }
+        let mut hashes = BTreeMap::new();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let (key, hash) = line.split_once(' ')?;
+            hashes.insert(key.to_owned(), u64::from_str_radix(hash, 16).ok()?);
+        }
+        Some(Lockfile { hashes })
+    }
+
+    /// Writes this lockfile
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut content = format!("version {}\n", LOCKFILE_VERSION);
+        for (key, hash) in &self.hashes {
+            content.push_str(&format!("{} {:016x}\n", key, hash));
+        }
+        fs::write(path, content)
+    }
+
+    /// Adds the types from an older lockfile that are not in this lockfile
+    ///
+    /// Keeping types that have been removed makes it possible to detect a type that is removed
+    /// and then added again with the same version and a different layout.
+    pub fn keep_removed(&mut self, older: &Lockfile) {
+        for (key, hash) in &older.hashes {
+            self.hashes.entry(key.clone()).or_insert(*hash);
+        }
+    }
+
+    /// Returns the names and versions of the types that are in this lockfile and a newer
+    /// lockfile, but have different layouts
+    ///
+    /// Types that have been added or removed are not included.
+    pub fn changed_types<'l>(&self, newer: &'l Lockfile) -> Vec<&'l str> {
+        newer
+            .hashes
+            .iter()
+            .filter(
+                |(key, hash)| matches!(self.hashes.get(*key), Some(old_hash) if old_hash != *hash),
+            )
+            .map(|(key, _)| key.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lockfile;
+
+    #[test]
+    fn parse_and_compare() {
+        let old = Lockfile::parse(
+            "version 1\ntest.Kept.1.0 00000000000000aa\ntest.Changed.1.0 0000000000000001\ntest.Removed.1.0 0000000000000002\n",
+        )
+        .unwrap();
+        let new = Lockfile::parse(
+            "version 1\ntest.Kept.1.0 00000000000000aa\ntest.Changed.1.0 0000000000000003\ntest.Added.1.0 0000000000000004\n",
+        )
+        .unwrap();
+        assert_eq!(vec!["test.Changed.1.0"], old.changed_types(&new));
+
+        let mut merged = new.clone();
+        merged.keep_removed(&old);
+        assert_eq!(Some(&3), merged.hashes.get("test.Changed.1.0"));
+        assert_eq!(Some(&2), merged.hashes.get("test.Removed.1.0"));
+        assert_eq!(4, merged.hashes.len());
+        assert_eq!(None, Lockfile::parse("version 2\n"));
+        assert_eq!(None, Lockfile::parse("test.Kept.1.0 00000000000000aa\n"));
+    }
+}
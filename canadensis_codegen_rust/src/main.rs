@@ -1,14 +1,31 @@
 extern crate canadensis_codegen_rust;
 extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dsdl_parser;
 extern crate clap;
 
+use canadensis_codegen_rust::cache::{Cache, FingerprintHasher};
+#[cfg(feature = "fetch")]
+use canadensis_codegen_rust::fetch::{self, Remote};
+use canadensis_codegen_rust::lockfile::Lockfile;
+use canadensis_codegen_rust::project::{
+    add_cyphal_path, ProjectConfig, CONFIG_FILE_NAME, CYPHAL_PATH,
+};
+use canadensis_codegen_rust::Layout;
+use canadensis_dsdl_frontend::compatibility;
+use canadensis_dsdl_frontend::graph::DependencyGraph;
+use canadensis_dsdl_frontend::manifest::Manifest;
+use canadensis_dsdl_frontend::markdown::MarkdownDocument;
+use canadensis_dsdl_frontend::report::LayoutReport;
+use canadensis_dsdl_frontend::warning::{Warning, WarningRule};
 use canadensis_dsdl_frontend::{Config, Package};
-use clap::{value_parser, Arg, Command};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 use std::collections::BTreeMap;
-use std::fs::File;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
-use std::{env, process};
+use std::time::Duration;
+use std::{env, process, thread};
 
 fn main() {
     match run() {
@@ -21,52 +38,453 @@ fn main() {
 }
 
 fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let args = get_args();
+    let args = get_args()?;
     match args {
-        Args::Compile {
+        Args::Compile(args) => {
+            if args.watch {
+                watch(&args)?;
+            } else {
+                compile(&args)?;
+            }
+        }
+        Args::Graph {
             input_folders,
-            output_file: output_path,
-            external_packages,
-            rustfmt,
+            output_file,
+            format,
             config,
         } => {
             let mut package = Package::new();
             for path in input_folders {
                 package.add_files(path)?;
             }
-            let package = match package.compile_with_warnings(&config) {
-                Ok(package) => package,
-                Err((e, warnings)) => {
-                    for warning in warnings {
-                        eprintln!("Warning: {}", warning);
-                    }
-                    return Err(e.into());
-                }
+            let package = package.compile(&config)?;
+            let graph = DependencyGraph::new(&package);
+            let text = match format {
+                GraphFormat::Dot => graph.dot().to_string(),
+                GraphFormat::Json => graph.json().to_string(),
+            };
+            match output_file {
+                Some(output_file) => fs::write(output_file, text)?,
+                None => print!("{}", text),
+            }
+        }
+        Args::Report {
+            input_folders,
+            output_file,
+            json,
+            config,
+        } => {
+            let mut package = Package::new();
+            for path in input_folders {
+                package.add_files(path)?;
+            }
+            let package = package.compile(&config)?;
+            let report = LayoutReport::new(&package);
+            let text = if json {
+                report.json().to_string()
+            } else {
+                report.text().to_string()
             };
+            match output_file {
+                Some(output_file) => fs::write(output_file, text)?,
+                None => print!("{}", text),
+            }
+        }
+        Args::Docs {
+            input_folders,
+            output_file,
+            title,
+            config,
+        } => {
+            let mut package = Package::new();
+            for path in input_folders {
+                package.add_files(path)?;
+            }
+            let package = package.compile(&config)?;
+            let mut document = MarkdownDocument::new(&package);
+            if let Some(title) = title {
+                document = document.with_title(title);
+            }
+            let text = document.to_string();
+            match output_file {
+                Some(output_file) => fs::write(output_file, text)?,
+                None => print!("{}", text),
+            }
+        }
+        #[cfg(feature = "fetch")]
+        Args::Fetch {
+            remote,
+            cache_directory,
+        } => {
+            let fetched = fetch::fetch(&remote, &cache_directory)?;
+            if remote.sha256.is_none() {
+                eprintln!(
+                    "SHA-256 hash: {} (use --sha256 {} to check it)",
+                    fetched.sha256, fetched.sha256
+                );
+            }
+            println!("{}", fetched.path.display());
+        }
+        Args::Compat {
+            input_folders,
+            baseline_folders,
+            config,
+        } => compat(&input_folders, &baseline_folders, &config)?,
+        Args::Lint {
+            input_folders,
+            json,
+            config,
+            warning_rules,
+        } => lint(&input_folders, json, &config, &warning_rules)?,
+        Args::Format {
+            input_folders,
+            check,
+            config,
+        } => format(&input_folders, check, &config)?,
+        Args::PrintDependencies { codegen_config } => {
+            print!(
+                "{}",
+                canadensis_codegen_rust::generated_code_dependencies_with_config(&codegen_config)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Compiles the DSDL files and generates code, or only checks the DSDL files if `args.check`
+/// is true
+fn compile(args: &CompileArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = find_files(args)?;
+
+    // Skip everything if nothing has changed since the cache was written
+    let fingerprint = {
+        let mut hasher = FingerprintHasher::new();
+        package.hash_sources(&mut hasher)?;
+        format!(
+            "{} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+            clap::crate_version!(),
+            args.output_file,
+            args.layout,
+            args.c_header,
+            args.manifest,
+            args.lockfile,
+            args.update_lockfile,
+            args.external_packages,
+            args.rustfmt,
+            args.config,
+            args.codegen_config,
+            args.warning_rules,
+            args.deny_warnings
+        )
+        .hash(&mut hasher);
+        hasher.finish()
+    };
+    let old_cache = match &args.cache {
+        Some(cache_path) => Cache::load(cache_path)?,
+        None => None,
+    };
+    if let Some(old_cache) = &old_cache {
+        if old_cache.is_fresh(fingerprint)? {
+            return Ok(());
+        }
+    }
+    for &(rule, enabled) in &args.warning_rules {
+        package.set_warning_enabled(rule, enabled);
+    }
+    let package = match package.compile_with_warnings(&args.config) {
+        Ok(package) => package,
+        Err((e, warnings)) => {
+            for warning in &warnings {
+                print_warning(warning);
+            }
+            return Err(e.into());
+        }
+    };
+
+    // Report warnings
+    for warning in package.warnings() {
+        print_warning(warning);
+    }
+    if args.deny_warnings && !package.warnings().is_empty() {
+        return Err(Box::new(StringError(format!(
+            "{} warnings reported, and --deny-warnings is enabled",
+            package.warnings().len()
+        ))));
+    }
 
-            // Report warnings
-            for warning in package.warnings() {
-                eprintln!("Warning: {}", warning);
+    // Check for types that changed without a new version
+    if let Some(lockfile_path) = &args.lockfile {
+        let mut lockfile = Lockfile::from_package(&package);
+        if let Some(old_lockfile) = Lockfile::load(lockfile_path)? {
+            if !args.update_lockfile {
+                let changed = old_lockfile.changed_types(&lockfile);
+                if !changed.is_empty() {
+                    return Err(Box::new(StringError(format!(
+                        "The layouts of these types changed without a new version: {}\n\
+                        If the types have not been published, use --update-lockfile to accept the changes.",
+                        changed.join(", ")
+                    ))));
+                }
             }
+            lockfile.keep_removed(&old_lockfile);
+        }
+        if !args.check {
+            lockfile.save(lockfile_path)?;
+        }
+    }
+    if args.check {
+        return Ok(());
+    }
 
-            // Generate code
-            let generated = canadensis_codegen_rust::generate_code(&package, &external_packages)?;
+    // Generate code
+    let generated = canadensis_codegen_rust::generate_code_with_config(
+        &package,
+        &args.external_packages,
+        &args.codegen_config,
+    )?;
 
-            let mut output_file = BufWriter::new(File::create(&output_path)?);
-            writeln!(output_file, "{}", generated)?;
-            drop(output_file);
-            // Format
-            if rustfmt {
-                run_rustfmt(&output_path)?;
+    let files = generated.files(&args.output_file, args.layout);
+    let c_header = args.c_header.as_ref().map(|c_header_path| {
+        let header_name = c_header_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let header = canadensis_codegen_rust::c_header::generate_c_header(
+            &package,
+            &args.codegen_config,
+            &header_name,
+        );
+        (c_header_path.as_path(), header)
+    });
+    let manifest = args
+        .manifest
+        .as_ref()
+        .map(|manifest_path| (manifest_path.as_path(), Manifest::new(&package).to_string()));
+    let outputs: Vec<(&Path, &str)> = files
+        .iter()
+        .map(|file| (file.path(), file.contents()))
+        .chain(
+            c_header
+                .iter()
+                .chain(&manifest)
+                .map(|(path, contents)| (*path, contents.as_str())),
+        )
+        .collect();
+    for &(path, contents) in &outputs {
+        if let Some(old_cache) = &old_cache {
+            if old_cache.output_unchanged(path, contents)? {
+                continue;
             }
         }
-        Args::PrintDependencies => {
-            print!("{}", canadensis_codegen_rust::generated_code_dependencies());
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let mut output_file = BufWriter::new(File::create(path)?);
+        writeln!(output_file, "{}", contents)?;
+        output_file.flush()?;
+    }
+    // Format
+    if args.rustfmt {
+        run_rustfmt(&args.output_file)?;
+    }
+
+    if let Some(cache_path) = &args.cache {
+        let mut cache = Cache::new(fingerprint);
+        for &(path, contents) in &outputs {
+            cache.add_output(path, contents)?;
+        }
+        cache.save(cache_path)?;
+    }
+    Ok(())
+}
+
+/// Compiles the DSDL files in the input folders and reports any warnings to standard output
+///
+/// This returns an error if the files could not be compiled or any warnings were reported.
+fn lint(
+    input_folders: &[PathBuf],
+    json: bool,
+    config: &Config,
+    warning_rules: &[(WarningRule, bool)],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for path in input_folders {
+        package.add_files(path)?;
+    }
+    for &(rule, enabled) in warning_rules {
+        package.set_warning_enabled(rule, enabled);
+    }
+    let package = package.compile(config)?;
+    let warnings = package.warnings();
+    if json {
+        let warnings: Vec<String> = warnings
+            .iter()
+            .map(|warning| {
+                format!(
+                    "{{\"rule\":\"{}\",\"type\":{},\"message\":{}}}",
+                    warning.rule(),
+                    match warning.key() {
+                        Some(key) => format!("\"{}\"", key),
+                        None => "null".to_owned(),
+                    },
+                    json_string(&warning.to_string())
+                )
+            })
+            .collect();
+        println!("{{\"warnings\":[{}]}}", warnings.join(","));
+    } else {
+        for warning in warnings {
+            println!("Warning: {} [{}]", warning, warning.rule());
+        }
+    }
+    if !warnings.is_empty() {
+        return Err(Box::new(StringError(format!(
+            "{} warnings reported",
+            warnings.len()
+        ))));
+    }
+    Ok(())
+}
+
+/// Checks that the versions of the types in the input folders follow the versioning rules, and
+/// reports any problems to standard output
+///
+/// If there are baseline folders, this also compares the types with the types in the baseline
+/// folders. This returns an error if the files could not be compiled or there are any problems.
+fn compat(
+    input_folders: &[PathBuf],
+    baseline_folders: &[PathBuf],
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for path in input_folders {
+        package.add_files(path)?;
+    }
+    let package = package.compile(config)?;
+    let incompatibilities = if baseline_folders.is_empty() {
+        compatibility::check_package(&package)
+    } else {
+        let mut baseline = Package::new();
+        for path in baseline_folders {
+            baseline.add_files(path)?;
+        }
+        let baseline = baseline.compile(config)?;
+        compatibility::check_snapshots(&baseline, &package)
+    };
+    for incompatibility in &incompatibilities {
+        println!("{}", incompatibility);
+    }
+    if !incompatibilities.is_empty() {
+        return Err(Box::new(StringError(format!(
+            "{} compatibility problems found",
+            incompatibilities.len()
+        ))));
+    }
+    Ok(())
+}
+
+/// Returns a string as a JSON string literal, with quotation marks
+fn json_string(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Formats the DSDL files in the input folders
+///
+/// If `check` is true, this prints the paths of the files that are not formatted and returns an
+/// error if there are any, instead of changing the files.
+fn format(
+    input_folders: &[PathBuf],
+    check: bool,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for path in input_folders {
+        package.add_files(path)?;
+    }
+    let mut unformatted = 0;
+    for path in package.paths() {
+        let dsdl = fs::read_to_string(path)?;
+        let formatted = canadensis_dsdl_parser::format(&dsdl, config)
+            .map_err(|e| e.with_path(&path.display().to_string()))?;
+        if formatted != dsdl {
+            if check {
+                println!("{}", path.display());
+                unformatted += 1;
+            } else {
+                fs::write(path, formatted)?;
+            }
         }
     }
+    if unformatted != 0 {
+        return Err(Box::new(StringError(format!(
+            "{} files are not formatted",
+            unformatted
+        ))));
+    }
     Ok(())
 }
 
+/// Finds the DSDL files in the input folders
+fn find_files(args: &CompileArgs) -> Result<Package, Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for path in &args.input_folders {
+        package.add_files(path)?;
+    }
+    Ok(package)
+}
+
+/// How often to check the input folders for changes in watch mode
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Generates code, and then generates code again every time a DSDL file is added, removed,
+/// or changed
+///
+/// Errors are reported, but do not stop watching. This function only returns if it can't print
+/// to standard error.
+fn watch(args: &CompileArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // The hash of the DSDL files, or the error message from finding or reading them,
+    // the last time code was generated
+    let mut last_sources: Option<Result<u64, String>> = None;
+    loop {
+        let sources = find_files(args).and_then(|package| {
+            let mut hasher = FingerprintHasher::new();
+            package.hash_sources(&mut hasher)?;
+            Ok(hasher.finish())
+        });
+        let sources = sources.map_err(|e| e.to_string());
+        if last_sources.as_ref() != Some(&sources) {
+            let mut stderr = std::io::stderr();
+            match &sources {
+                Ok(_) => {
+                    writeln!(stderr, "Compiling...")?;
+                    match compile(args) {
+                        Ok(()) => {
+                            writeln!(stderr, "Generated code in {}", args.output_file.display())?
+                        }
+                        Err(e) => print_error(&*e),
+                    }
+                }
+                Err(message) => writeln!(stderr, "{}", message)?,
+            }
+            writeln!(stderr, "Watching for changes...")?;
+            last_sources = Some(sources);
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
 /// Finds rustfmt in the default path and runs it to format the code at the provided path
 fn run_rustfmt(output_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let start_status = std::process::Command::new("rustfmt")
@@ -92,37 +510,139 @@ fn run_rustfmt(output_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 enum Args {
-    Compile {
+    Compile(Box<CompileArgs>),
+    Graph {
+        /// Input folder paths with DSDL files to read
+        input_folders: Vec<PathBuf>,
+        /// Output file path, or None to write to standard output
+        output_file: Option<PathBuf>,
+        /// Output format
+        format: GraphFormat,
+        /// Parser configuration
+        config: Config,
+    },
+    Report {
+        /// Input folder paths with DSDL files to read
+        input_folders: Vec<PathBuf>,
+        /// Output file path, or None to write to standard output
+        output_file: Option<PathBuf>,
+        /// Write the report as JSON instead of text
+        json: bool,
+        /// Parser configuration
+        config: Config,
+    },
+    Docs {
         /// Input folder paths with DSDL files to read
         input_folders: Vec<PathBuf>,
-        /// Output file path
-        output_file: PathBuf,
-        /// DSDL packages that should not be generated, but instead refer to some other Rust module
-        ///
-        /// Each key is a list of Cyphal package name segments (like ["uavcan", "node"]).
-        /// Each value is the path to a Rust module
-        external_packages: BTreeMap<Vec<String>, Vec<String>>,
-        /// Run rustfmt on the generated code
-        rustfmt: bool,
+        /// Output file path, or None to write to standard output
+        output_file: Option<PathBuf>,
+        /// The title of the document, or None to use the default title
+        title: Option<String>,
+        /// Parser configuration
+        config: Config,
+    },
+    #[cfg(feature = "fetch")]
+    Fetch {
+        /// The repository and revision to download
+        remote: Remote,
+        /// The directory to unpack the files into
+        cache_directory: PathBuf,
+    },
+    Compat {
+        /// Input folder paths with DSDL files to check
+        input_folders: Vec<PathBuf>,
+        /// Input folder paths with an earlier snapshot of the DSDL files to compare with
+        baseline_folders: Vec<PathBuf>,
+        /// Parser configuration
+        config: Config,
+    },
+    Lint {
+        /// Input folder paths with DSDL files to check
+        input_folders: Vec<PathBuf>,
+        /// Report warnings as JSON instead of text
+        json: bool,
         /// Parser configuration
         config: Config,
+        /// Warning rules to enable (true) or disable (false), in order
+        warning_rules: Vec<(WarningRule, bool)>,
     },
-    PrintDependencies,
+    Format {
+        /// Input folder paths with DSDL files to format
+        input_folders: Vec<PathBuf>,
+        /// Only check that the files are formatted
+        check: bool,
+        /// Parser configuration
+        config: Config,
+    },
+    PrintDependencies {
+        /// Code generation configuration (only the options that affect dependencies are used)
+        codegen_config: canadensis_codegen_rust::Config,
+    },
+}
+
+enum GraphFormat {
+    Dot,
+    Json,
 }
 
-fn get_args() -> Args {
+struct CompileArgs {
+    /// Input folder paths with DSDL files to read
+    input_folders: Vec<PathBuf>,
+    /// Output file path
+    output_file: PathBuf,
+    /// How to divide the generated code into files
+    layout: Layout,
+    /// Path to a C header to generate in addition to the Rust code
+    c_header: Option<PathBuf>,
+    /// Path to a JSON manifest of the compiled types to write in addition to the code
+    manifest: Option<PathBuf>,
+    /// Path to the cache file, if caching is enabled
+    cache: Option<PathBuf>,
+    /// Path to the lockfile, if layout changes should be checked
+    lockfile: Option<PathBuf>,
+    /// Accept layout changes and update the lockfile
+    update_lockfile: bool,
+    /// Only check the DSDL files and the lockfile, without generating code or writing files
+    check: bool,
+    /// Generate code again whenever the DSDL files change
+    watch: bool,
+    /// DSDL packages that should not be generated, but instead refer to some other Rust module
+    ///
+    /// Each key is a list of Cyphal package name segments (like ["uavcan", "node"]).
+    /// Each value is the path to a Rust module
+    external_packages: BTreeMap<Vec<String>, Vec<String>>,
+    /// Run rustfmt on the generated code
+    rustfmt: bool,
+    /// Parser configuration
+    config: Config,
+    /// Code generation configuration
+    codegen_config: canadensis_codegen_rust::Config,
+    /// Warning rules to enable (true) or disable (false), in order
+    warning_rules: Vec<(WarningRule, bool)>,
+    /// Treat warnings as errors
+    deny_warnings: bool,
+}
+
+fn get_args() -> Result<Args, Box<dyn std::error::Error>> {
     let app = clap::Command::new("canadensis_generate_code")
         .version(clap::crate_version!())
         .about("Generates Rust data types and serialization code from Cyphal DSDL files")
         .subcommand_required(true)
+        .arg(
+            Arg::new("project_config")
+                .long("config")
+                .global(true)
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("A project configuration file with root directories and options (default: canadensis.conf in the current directory or the closest parent directory that has one)"),
+        )
         .subcommand(Command::new("compile").about("Parses DSDL files and generates Rust code")
         .arg(
             Arg::new("input")
                 .index(1)
-                .required(true)
                 .num_args(1..)
                 .value_parser(value_parser!(PathBuf))
-                .help("One or more paths to directories with DSDL files"),
+                .help("Paths to directories with DSDL files (default: the root directories in the project configuration file), in addition to the directories in CYPHAL_PATH"),
         )
         .arg(
             Arg::new("output_file")
@@ -133,6 +653,61 @@ fn get_args() -> Args {
                 .default_value("lib.rs")
                 .help("The file to write the generated code to"),
         )
+        .arg(
+            Arg::new("layout")
+                .long("layout")
+                .value_parser(["single-file", "file-per-namespace", "file-per-type"])
+                .default_value("single-file")
+                .help("How to divide the generated code into files (with file-per-namespace or file-per-type, the output file must be the root of a crate and the other files are placed next to it)"),
+        )
+        .arg(
+            Arg::new("c_header")
+                .long("c-header")
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("Also generate a C header with structs and serialization functions for the same types"),
+        )
+        .arg(
+            Arg::new("manifest")
+                .long("manifest")
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("Also write a JSON manifest that lists each type with its version, fixed port ID, extent, and sizes"),
+        )
+        .arg(
+            Arg::new("cache")
+                .long("cache")
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("A file that records the inputs and outputs, so code is only generated again if something has changed"),
+        )
+        .arg(
+            Arg::new("lockfile")
+                .long("lockfile")
+                .value_parser(value_parser!(PathBuf))
+                .value_name("path")
+                .help("A file that records the layout of each type, and fails if a type's layout changes without a new version"),
+        )
+        .arg(
+            Arg::new("update_lockfile")
+                .long("update-lockfile")
+                .action(ArgAction::SetTrue)
+                .requires("lockfile")
+                .help("Accept layout changes and update the lockfile"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["watch", "cache", "update_lockfile"])
+                .help("Compile the DSDL files, report problems, and check the lockfile, but do not generate code or write any files"),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .action(ArgAction::SetTrue)
+                .help("Keep running and generate code again whenever a DSDL file is added, removed, or changed"),
+        )
         .arg(
             Arg::new("external_package")
                 .long("external-package")
@@ -143,48 +718,664 @@ fn get_args() -> Args {
         )
             .arg(Arg::new("rustfmt")
                 .long("rustfmt")
-                .num_args(0)
+                .action(ArgAction::SetTrue)
                 .help("Run rustfmt to format the generated code")
         )
             .arg(Arg::new("allow_utf8_and_byte")
                 .long("unstable-allow-utf8-and-byte")
-                .num_args(0)
+                .action(ArgAction::SetTrue)
                 .help("Allow utf8 and byte DSDL types (this option is unstable)")
             ).arg(Arg::new("forbid_saturated_bool")
             .long("unstable-forbid-saturated-bool")
-            .num_args(0)
+            .action(ArgAction::SetTrue)
             .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+        ).arg(Arg::new("views")
+            .long("views")
+            .action(ArgAction::SetTrue)
+            .help("Also generate view types that borrow variable-length byte arrays and strings from the payload")
+        ).arg(Arg::new("lazy_views")
+            .long("lazy-views")
+            .action(ArgAction::SetTrue)
+            .help("Also generate lazy view types that deserialize each field from a borrowed payload only when it is accessed")
+        ).arg(Arg::new("arbitrary")
+            .long("arbitrary")
+            .action(ArgAction::SetTrue)
+            .help("Implement arbitrary::Arbitrary for generated types when the arbitrary feature is enabled")
+        ).arg(Arg::new("proptest")
+            .long("proptest")
+            .action(ArgAction::SetTrue)
+            .help("Implement proptest::arbitrary::Arbitrary for generated types when the proptest feature is enabled")
+        ).arg(Arg::new("serde")
+            .long("serde")
+            .action(ArgAction::SetTrue)
+            .help("Implement serde::Serialize and serde::Deserialize for generated types when the serde feature is enabled")
+        ).arg(Arg::new("defmt")
+            .long("defmt")
+            .action(ArgAction::SetTrue)
+            .help("Implement defmt::Format for generated types when the defmt feature is enabled")
+        ).arg(Arg::new("no_std")
+            .long("no-std")
+            .action(ArgAction::SetTrue)
+            .help("Generate the root of a crate that is no_std unless its std feature is enabled (use print-dependencies --no-std for its Cargo.toml)")
+        ).arg(Arg::new("builders")
+            .long("builders")
+            .action(ArgAction::SetTrue)
+            .help("Generate a builder for each struct type")
+        ).arg(Arg::new("setters")
+            .long("setters")
+            .action(ArgAction::SetTrue)
+            .help("Generate setters that apply cast modes and a checked constructor for each struct type with narrow integer fields")
+        ).arg(Arg::new("plain_layout")
+            .long("plain-layout")
+            .action(ArgAction::SetTrue)
+            .help("Use #[repr(C)] instead of #[repr(C, packed)] for zero-copy types whose layout has no padding, and generate functions that reinterpret bytes as those types")
+        ).arg(Arg::new("constant_enums")
+            .long("constant-enums")
+            .action(ArgAction::SetTrue)
+            .help("Generate an enum for each struct type whose constants name the values of its only field")
+        ).arg(Arg::new("version_conversions")
+            .long("version-conversions")
+            .action(ArgAction::SetTrue)
+            .help("Implement From or TryFrom conversions between consecutive minor versions of each type")
+        ).arg(Arg::new("derive")
+            .long("derive")
+            .action(ArgAction::Append)
+            .value_delimiter(',')
+            .value_name("paths")
+            .help("Derive macros to apply to all generated types, separated by commas")
+        ).arg(Arg::new("type_derive")
+            .long("type-derive")
+            .action(ArgAction::Append)
+            .value_parser(validate_type_derive)
+            .value_name("cyphal-type=paths")
+            .help("A DSDL type name, with or without a version, and derive macros to apply to that type, separated by commas")
+        ).arg(Arg::new("include")
+            .long("include")
+            .action(ArgAction::Append)
+            .value_delimiter(',')
+            .value_name("namespaces")
+            .help("Only generate types in these DSDL namespaces (like uavcan.node), separated by commas, and the types they depend on")
+        ).arg(Arg::new("exclude")
+            .long("exclude")
+            .action(ArgAction::Append)
+            .value_delimiter(',')
+            .value_name("namespaces")
+            .help("Do not generate types in these DSDL namespaces, separated by commas, unless other generated types depend on them")
+        ).args(warning_rule_args()
+        ).arg(Arg::new("deny_warnings")
+            .long("deny-warnings")
+            .action(ArgAction::SetTrue)
+            .help("Fail without generating code if any warnings are reported")
         ))
+        .subcommand(Command::new("graph")
+            .about("Parses DSDL files and prints a graph of the dependencies between types")
+            .arg(
+                Arg::new("input")
+                    .index(1)
+                    .num_args(1..)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Paths to directories with DSDL files (default: the root directories in the project configuration file), in addition to the directories in CYPHAL_PATH"),
+            )
+            .arg(
+                Arg::new("output_file")
+                    .short('o')
+                    .long("output-file")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("The file to write the graph to (if not specified, the graph is written to standard output)"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_parser(["dot", "json"])
+                    .default_value("dot")
+                    .help("The format of the graph: Graphviz DOT or JSON"),
+            )
+            .arg(Arg::new("allow_utf8_and_byte")
+                .long("unstable-allow-utf8-and-byte")
+                .action(ArgAction::SetTrue)
+                .help("Allow utf8 and byte DSDL types (this option is unstable)")
+            ).arg(Arg::new("forbid_saturated_bool")
+                .long("unstable-forbid-saturated-bool")
+                .action(ArgAction::SetTrue)
+                .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+            ))
+        .subcommand(Command::new("report")
+            .about("Parses DSDL files and prints the offset and size of each field and the size and extent of each type")
+            .arg(
+                Arg::new("input")
+                    .index(1)
+                    .num_args(1..)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Paths to directories with DSDL files (default: the root directories in the project configuration file), in addition to the directories in CYPHAL_PATH"),
+            )
+            .arg(
+                Arg::new("output_file")
+                    .short('o')
+                    .long("output-file")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("The file to write the report to (if not specified, the report is written to standard output)"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_parser(["text", "json"])
+                    .default_value("text")
+                    .help("The format of the report"),
+            )
+            .arg(Arg::new("allow_utf8_and_byte")
+                .long("unstable-allow-utf8-and-byte")
+                .action(ArgAction::SetTrue)
+                .help("Allow utf8 and byte DSDL types (this option is unstable)")
+            ).arg(Arg::new("forbid_saturated_bool")
+                .long("unstable-forbid-saturated-bool")
+                .action(ArgAction::SetTrue)
+                .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+            ))
+        .subcommand(Command::new("docs")
+            .about("Parses DSDL files and writes Markdown documentation of the types")
+            .arg(
+                Arg::new("input")
+                    .index(1)
+                    .num_args(1..)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Paths to directories with DSDL files (default: the root directories in the project configuration file), in addition to the directories in CYPHAL_PATH"),
+            )
+            .arg(
+                Arg::new("output_file")
+                    .short('o')
+                    .long("output-file")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("The file to write the documentation to (if not specified, the documentation is written to standard output)"),
+            )
+            .arg(
+                Arg::new("title")
+                    .long("title")
+                    .help("The title of the document"),
+            )
+            .arg(Arg::new("allow_utf8_and_byte")
+                .long("unstable-allow-utf8-and-byte")
+                .action(ArgAction::SetTrue)
+                .help("Allow utf8 and byte DSDL types (this option is unstable)")
+            ).arg(Arg::new("forbid_saturated_bool")
+                .long("unstable-forbid-saturated-bool")
+                .action(ArgAction::SetTrue)
+                .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+            ))
+        .subcommand(Command::new("compat")
+            .about("Checks that versions of DSDL types follow the versioning rules")
+            .arg(
+                Arg::new("input")
+                    .index(1)
+                    .num_args(1..)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Paths to directories with DSDL files (default: the root directories in the project configuration file), in addition to the directories in CYPHAL_PATH"),
+            )
+            .arg(
+                Arg::new("baseline")
+                    .long("baseline")
+                    .action(ArgAction::Append)
+                    .value_parser(value_parser!(PathBuf))
+                    .value_name("path")
+                    .help("A directory with an earlier snapshot of the DSDL files to compare with (can be used more than once)"),
+            )
+            .arg(Arg::new("allow_utf8_and_byte")
+                .long("unstable-allow-utf8-and-byte")
+                .action(ArgAction::SetTrue)
+                .help("Allow utf8 and byte DSDL types (this option is unstable)")
+            ).arg(Arg::new("forbid_saturated_bool")
+                .long("unstable-forbid-saturated-bool")
+                .action(ArgAction::SetTrue)
+                .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+            ))
+        .subcommand(Command::new("lint")
+            .about("Parses DSDL files and reports problems without generating code")
+            .arg(
+                Arg::new("input")
+                    .index(1)
+                    .num_args(1..)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Paths to directories with DSDL files (default: the root directories in the project configuration file), in addition to the directories in CYPHAL_PATH"),
+            )
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_parser(["text", "json"])
+                    .default_value("text")
+                    .help("The format of the reported warnings"),
+            )
+            .args(warning_rule_args())
+            .arg(Arg::new("allow_utf8_and_byte")
+                .long("unstable-allow-utf8-and-byte")
+                .action(ArgAction::SetTrue)
+                .help("Allow utf8 and byte DSDL types (this option is unstable)")
+            ).arg(Arg::new("forbid_saturated_bool")
+                .long("unstable-forbid-saturated-bool")
+                .action(ArgAction::SetTrue)
+                .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+            ))
+        .subcommand(Command::new("fmt")
+            .about("Formats DSDL files in a consistent style")
+            .arg(
+                Arg::new("input")
+                    .index(1)
+                    .required(true)
+                    .num_args(1..)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("One or more paths to directories with DSDL files"),
+            )
+            .arg(
+                Arg::new("check")
+                    .long("check")
+                    .action(ArgAction::SetTrue)
+                    .help("Print the paths of files that are not formatted and fail if there are any, instead of changing the files"),
+            )
+            .arg(Arg::new("allow_utf8_and_byte")
+                .long("unstable-allow-utf8-and-byte")
+                .action(ArgAction::SetTrue)
+                .help("Allow utf8 and byte DSDL types (this option is unstable)")
+            ).arg(Arg::new("forbid_saturated_bool")
+                .long("unstable-forbid-saturated-bool")
+                .action(ArgAction::SetTrue)
+                .help("Forbid the saturated bool DSDL type (this option is unstable and may become the default)")
+            ))
         .subcommand(Command::new("print-dependencies")
-            .about("Prints the packages that the generated code depends on (for use in Cargo.toml)"));
+            .about("Prints the packages that the generated code depends on (for use in Cargo.toml)")
+            .arg(Arg::new("arbitrary")
+                .long("arbitrary")
+                .action(ArgAction::SetTrue)
+                .help("Include the optional arbitrary dependency and feature")
+            ).arg(Arg::new("proptest")
+                .long("proptest")
+                .action(ArgAction::SetTrue)
+                .help("Include the optional proptest dependency and feature")
+            ).arg(Arg::new("serde")
+                .long("serde")
+                .action(ArgAction::SetTrue)
+                .help("Include the optional serde dependency and feature")
+            ).arg(Arg::new("defmt")
+                .long("defmt")
+                .action(ArgAction::SetTrue)
+                .help("Include the optional defmt dependency and feature")
+            ).arg(Arg::new("no_std")
+                .long("no-std")
+                .action(ArgAction::SetTrue)
+                .help("Disable the default features of all dependencies and add alloc and std features")
+            ));
+    #[cfg(feature = "fetch")]
+    let app = app.subcommand(Command::new("fetch")
+        .about("Downloads a pinned revision of a repository of DSDL files, or finds a copy that was already downloaded, and prints its path")
+        .arg(
+            Arg::new("revision")
+                .index(1)
+                .required(true)
+                .help("The revision to download, preferably a full commit hash"),
+        )
+        .arg(
+            Arg::new("repository")
+                .long("repository")
+                .value_name("url")
+                .default_value(fetch::PUBLIC_REGULATED_DATA_TYPES)
+                .help("The URL of the repository"),
+        )
+        .arg(
+            Arg::new("sha256")
+                .long("sha256")
+                .value_name("hash")
+                .help("The expected SHA-256 hash of the content"),
+        )
+        .arg(
+            Arg::new("cache_dir")
+                .long("cache-dir")
+                .value_name("path")
+                .value_parser(value_parser!(PathBuf))
+                .default_value(".dsdl")
+                .help("The directory to unpack downloaded files into"),
+        ));
     let matches = app.get_matches();
+    let project = project_config(&matches)?;
 
-    match matches.subcommand() {
-        Some(("compile", matches)) => Args::Compile {
+    Ok(match matches.subcommand() {
+        Some(("compile", matches)) => Args::Compile(Box::new(CompileArgs {
+            input_folders: input_folders(matches, &project)?,
+            output_file: matches.get_one::<PathBuf>("output_file").unwrap().clone(),
+            c_header: matches.get_one::<PathBuf>("c_header").cloned(),
+            manifest: matches.get_one::<PathBuf>("manifest").cloned(),
+            cache: matches.get_one::<PathBuf>("cache").cloned(),
+            lockfile: matches.get_one::<PathBuf>("lockfile").cloned(),
+            update_lockfile: matches.get_flag("update_lockfile"),
+            check: matches.get_flag("check"),
+            watch: matches.get_flag("watch"),
+            layout: match matches.get_one::<String>("layout").unwrap().as_str() {
+                "file-per-namespace" => Layout::FilePerNamespace,
+                "file-per-type" => Layout::FilePerType,
+                _ => Layout::SingleFile,
+            },
+            external_packages: {
+                let mut external_packages = project.external_packages.clone();
+                if let Some(values) = matches.get_many::<ExternalPackage>("external_package") {
+                    external_packages
+                        .extend(values.map(|ext| (ext.package.clone(), ext.rust_module.clone())));
+                }
+                external_packages
+            },
+            rustfmt: matches.get_flag("rustfmt"),
+            config: parser_config(matches, &project),
+            codegen_config: codegen_config(matches, &project),
+            warning_rules: project
+                .warning_rules
+                .iter()
+                .cloned()
+                .chain(warning_rules(matches))
+                .collect(),
+            deny_warnings: project.deny_warnings || matches.get_flag("deny_warnings"),
+        })),
+        Some(("graph", matches)) => Args::Graph {
+            input_folders: input_folders(matches, &project)?,
+            output_file: matches.get_one::<PathBuf>("output_file").cloned(),
+            format: match matches.get_one::<String>("format").unwrap().as_str() {
+                "json" => GraphFormat::Json,
+                _ => GraphFormat::Dot,
+            },
+            config: parser_config(matches, &project),
+        },
+        Some(("report", matches)) => Args::Report {
+            input_folders: input_folders(matches, &project)?,
+            output_file: matches.get_one::<PathBuf>("output_file").cloned(),
+            json: matches.get_one::<String>("format").unwrap() == "json",
+            config: parser_config(matches, &project),
+        },
+        Some(("docs", matches)) => Args::Docs {
+            input_folders: input_folders(matches, &project)?,
+            output_file: matches.get_one::<PathBuf>("output_file").cloned(),
+            title: matches.get_one::<String>("title").cloned(),
+            config: parser_config(matches, &project),
+        },
+        #[cfg(feature = "fetch")]
+        Some(("fetch", matches)) => Args::Fetch {
+            remote: Remote {
+                repository: matches.get_one::<String>("repository").unwrap().clone(),
+                revision: matches.get_one::<String>("revision").unwrap().clone(),
+                sha256: matches.get_one::<String>("sha256").cloned(),
+            },
+            cache_directory: matches.get_one::<PathBuf>("cache_dir").unwrap().clone(),
+        },
+        Some(("compat", matches)) => Args::Compat {
+            input_folders: input_folders(matches, &project)?,
+            baseline_folders: matches
+                .get_many::<PathBuf>("baseline")
+                .map(|values| values.cloned().collect())
+                .unwrap_or_default(),
+            config: parser_config(matches, &project),
+        },
+        Some(("lint", matches)) => Args::Lint {
+            input_folders: input_folders(matches, &project)?,
+            json: matches.get_one::<String>("format").unwrap() == "json",
+            config: parser_config(matches, &project),
+            warning_rules: project
+                .warning_rules
+                .iter()
+                .cloned()
+                .chain(warning_rules(matches))
+                .collect(),
+        },
+        Some(("fmt", matches)) => Args::Format {
             input_folders: matches
                 .get_many::<PathBuf>("input")
                 .unwrap()
                 .cloned()
                 .collect(),
-            output_file: matches.get_one::<PathBuf>("output_file").unwrap().clone(),
-            external_packages: matches
-                .get_many::<String>("external_package")
-                .map(|values| {
-                    values
-                        .map(|s| ExternalPackage::parse(s).expect("Invalid external package"))
-                        .map(|ext| (ext.package, ext.rust_module))
-                        .collect()
-                })
-                .unwrap_or_else(BTreeMap::new),
-            rustfmt: matches.contains_id("rustfmt"),
-            config: Config {
-                allow_utf8_and_byte: matches.contains_id("allow_utf8_and_byte"),
-                allow_saturated_bool: !matches.contains_id("forbid_saturated_bool"),
-            },
+            check: matches.get_flag("check"),
+            config: parser_config(matches, &project),
+        },
+        Some(("print-dependencies", matches)) => Args::PrintDependencies {
+            codegen_config: codegen_config(matches, &project),
         },
-        Some(("print-dependencies", _)) => Args::PrintDependencies,
         _ => panic!("Unrecognized Subcommand"),
+    })
+}
+
+/// Reads the project configuration file from the --config option, or the closest
+/// canadensis.conf file
+///
+/// If there is no --config option and no file, this returns the default configuration.
+fn project_config(matches: &ArgMatches) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
+    let path = match matches.get_one::<PathBuf>("project_config") {
+        Some(path) => Some(path.clone()),
+        None => ProjectConfig::find(env::current_dir()?),
+    };
+    match path {
+        Some(path) => Ok(ProjectConfig::load(path)?),
+        None => Ok(ProjectConfig::default()),
+    }
+}
+
+/// Returns the input folders from the command line, or the root directories in the project
+/// configuration if there are none on the command line, followed by the directories in
+/// CYPHAL_PATH
+fn input_folders(
+    matches: &ArgMatches,
+    project: &ProjectConfig,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let mut input_folders: Vec<PathBuf> = match matches.get_many::<PathBuf>("input") {
+        Some(values) => values.cloned().collect(),
+        None => project.roots.clone(),
+    };
+    add_cyphal_path(&mut input_folders);
+    if input_folders.is_empty() {
+        return Err(Box::new(StringError(format!(
+            "No input folders (provide them on the command line, as root lines in {}, or in {})",
+            CONFIG_FILE_NAME, CYPHAL_PATH
+        ))));
+    }
+    Ok(input_folders)
+}
+
+/// Combines the parser options on the command line with the options in the project
+/// configuration
+fn parser_config(matches: &ArgMatches, project: &ProjectConfig) -> Config {
+    Config {
+        allow_utf8_and_byte: project.config.allow_utf8_and_byte
+            || matches.get_flag("allow_utf8_and_byte"),
+        allow_saturated_bool: project.config.allow_saturated_bool
+            && !matches.get_flag("forbid_saturated_bool"),
+    }
+}
+
+/// Combines the code generation options on the command line with the options in the project
+/// configuration
+///
+/// Options that are only defined for some subcommands are skipped when they are not defined.
+fn codegen_config(
+    matches: &ArgMatches,
+    project: &ProjectConfig,
+) -> canadensis_codegen_rust::Config {
+    let mut config = project.codegen_config.clone();
+    let flags = [
+        ("views", &mut config.views),
+        ("lazy_views", &mut config.lazy_views),
+        ("arbitrary", &mut config.arbitrary),
+        ("proptest", &mut config.proptest),
+        ("serde", &mut config.serde),
+        ("defmt", &mut config.defmt),
+        ("no_std", &mut config.no_std),
+        ("builders", &mut config.builders),
+        ("setters", &mut config.setters),
+        ("plain_layout", &mut config.plain_layout),
+        ("constant_enums", &mut config.constant_enums),
+        ("version_conversions", &mut config.version_conversions),
+    ];
+    for (id, flag) in flags {
+        if let Ok(Some(true)) = matches.try_get_one::<bool>(id) {
+            *flag = true;
+        }
+    }
+    for (id, values) in [
+        ("derive", &mut config.derives),
+        ("include", &mut config.include_namespaces),
+        ("exclude", &mut config.exclude_namespaces),
+    ] {
+        if let Ok(Some(new_values)) = matches.try_get_many::<String>(id) {
+            values.extend(new_values.cloned());
+        }
+    }
+    if let Ok(Some(_)) = matches.try_get_many::<TypeDerive>("type_derive") {
+        for (type_name, derives) in type_derives(matches) {
+            config
+                .type_derives
+                .entry(type_name)
+                .or_default()
+                .extend(derives);
+        }
     }
+    config
+}
+
+/// Returns the --warn, --allow, and --rules arguments
+fn warning_rule_args() -> [Arg; 3] {
+    [
+        Arg::new("warn")
+            .long("warn")
+            .short('W')
+            .action(ArgAction::Append)
+            .value_parser(validate_warning_rule)
+            .value_name("rule")
+            .help(warning_rule_help("Enable a warning rule")),
+        Arg::new("allow")
+            .long("allow")
+            .short('A')
+            .action(ArgAction::Append)
+            .value_parser(validate_warning_rule)
+            .value_name("rule")
+            .help(warning_rule_help("Disable a warning rule")),
+        Arg::new("rules")
+            .long("rules")
+            .value_parser(read_rules_file)
+            .value_name("path")
+            .help("A file that enables or disables warning rules, with a line like \"warn rule\" or \"allow rule\" for each rule (--warn and --allow override it)"),
+    ]
+}
+
+/// Reads a file with a warning rule setting on each line
+///
+/// Each line is `warn` or `allow` followed by the name of a rule. Empty lines and lines that
+/// start with `#` are ignored.
+fn read_rules_file(path: &str) -> Result<Vec<(WarningRule, bool)>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let (setting, name) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("{}:{}: Expected a setting and a rule", path, i + 1))?;
+            let enabled = match setting {
+                "warn" => true,
+                "allow" => false,
+                _ => {
+                    return Err(format!(
+                        "{}:{}: Unknown setting {}, expected warn or allow",
+                        path,
+                        i + 1,
+                        setting
+                    ))
+                }
+            };
+            let rule = validate_warning_rule(name.trim())
+                .map_err(|e| format!("{}:{}: {}", path, i + 1, e))?;
+            Ok((rule, enabled))
+        })
+        .collect()
+}
+
+/// Collects the rules file settings and the --warn and --allow options in the order they were
+/// provided, so later options override earlier ones
+///
+/// The settings in a rules file come before all --warn and --allow options.
+fn warning_rules(matches: &ArgMatches) -> Vec<(WarningRule, bool)> {
+    let mut rules: Vec<(usize, WarningRule, bool)> = Vec::new();
+    for (id, enabled) in [("warn", true), ("allow", false)] {
+        if let (Some(values), Some(indices)) =
+            (matches.get_many::<WarningRule>(id), matches.indices_of(id))
+        {
+            rules.extend(
+                values
+                    .zip(indices)
+                    .map(|(rule, index)| (index, *rule, enabled)),
+            );
+        }
+    }
+    rules.sort_by_key(|(index, _, _)| *index);
+    let file_rules = matches
+        .get_one::<Vec<(WarningRule, bool)>>("rules")
+        .cloned()
+        .unwrap_or_default();
+    file_rules
+        .into_iter()
+        .chain(rules.into_iter().map(|(_, rule, enabled)| (rule, enabled)))
+        .collect()
+}
+
+/// Collects the --type-derive options into a map from type names to derive macro paths
+fn type_derives(matches: &ArgMatches) -> BTreeMap<String, Vec<String>> {
+    let mut type_derives: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if let Some(values) = matches.get_many::<TypeDerive>("type_derive") {
+        for value in values {
+            type_derives
+                .entry(value.type_name.clone())
+                .or_default()
+                .extend(value.derives.iter().cloned());
+        }
+    }
+    type_derives
+}
+
+/// Validates a type name and derive macro list
+fn validate_type_derive(value: &str) -> Result<TypeDerive, String> {
+    TypeDerive::parse(value).ok_or_else(|| {
+        "Invalid type derive, expected [cyphal-type]=[derive-path],[derive-path]...".into()
+    })
+}
+
+#[derive(Debug, Clone)]
+struct TypeDerive {
+    type_name: String,
+    derives: Vec<String>,
+}
+
+impl TypeDerive {
+    fn parse(value: &str) -> Option<Self> {
+        let (type_name, derives) = value.split_once('=')?;
+        let derives: Vec<String> = derives
+            .split(',')
+            .map(str::trim)
+            .filter(|derive| !derive.is_empty())
+            .map(String::from)
+            .collect();
+        if type_name.is_empty() || derives.is_empty() {
+            return None;
+        }
+        Some(TypeDerive {
+            type_name: type_name.to_owned(),
+            derives,
+        })
+    }
+}
+
+/// Parses the name of a warning rule
+fn validate_warning_rule(name: &str) -> Result<WarningRule, String> {
+    WarningRule::from_name(name).ok_or_else(|| format!("Unknown warning rule {}", name))
+}
+
+/// Appends the names of all warning rules to some help text
+fn warning_rule_help(help: &str) -> String {
+    let names: Vec<&str> = WarningRule::ALL.iter().map(WarningRule::name).collect();
+    format!("{} (one of {})", help, names.join(", "))
+}
+
+fn print_warning(warning: &Warning) {
+    eprintln!("Warning: {} [{}]", warning, warning.rule());
 }
 
 /// Validates an external package name pair
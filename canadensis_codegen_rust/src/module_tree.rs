@@ -1,6 +1,7 @@
-use crate::GeneratedItem;
+use crate::{GeneratedFile, GeneratedItem, Layout};
 use std::collections::BTreeMap;
 use std::iter::FromIterator;
+use std::path::Path;
 
 /// A tree of Rust modules that may contain items
 #[derive(Default)]
@@ -24,6 +25,41 @@ impl<'c> ModuleTree<'c> {
             }
         }
     }
+
+    /// Returns true if this module should be marked as deprecated
+    ///
+    /// A module is deprecated if it has no child modules and all its items are deprecated.
+    pub fn deprecated(&self) -> bool {
+        self.children.is_empty()
+            && !self.items.is_empty()
+            && self.items.iter().all(GeneratedItem::deprecated)
+    }
+
+    /// Returns true if this module goes in its own file with the provided layout
+    pub fn in_own_file(&self, layout: Layout) -> bool {
+        match layout {
+            Layout::SingleFile => false,
+            // Modules for namespaces have child modules for types, and modules for types
+            // have no children
+            Layout::FilePerNamespace => !self.children.is_empty(),
+            Layout::FilePerType => true,
+        }
+    }
+
+    /// Adds a file for each descendant of this module that goes in its own file
+    ///
+    /// `directory` is the directory that contains the files of the child modules.
+    pub fn add_files(&self, directory: &Path, layout: Layout, files: &mut Vec<GeneratedFile>) {
+        for (sub_name, submodule) in &self.children {
+            if submodule.in_own_file(layout) {
+                files.push(GeneratedFile {
+                    path: directory.join(format!("{}.rs", sub_name)),
+                    contents: ModuleContents(submodule, layout).to_string(),
+                });
+                submodule.add_files(&directory.join(sub_name), layout, files);
+            }
+        }
+    }
 }
 
 impl<'c> FromIterator<GeneratedItem<'c>> for ModuleTree<'c> {
@@ -39,31 +75,33 @@ impl<'c> FromIterator<GeneratedItem<'c>> for ModuleTree<'c> {
     }
 }
 
+/// The contents of a module, with child modules inline or declared with a path to another file
+/// depending on the layout
+pub(crate) struct ModuleContents<'t, 'c>(pub &'t ModuleTree<'c>, pub Layout);
+
 mod fmt_impl {
-    use super::ModuleTree;
-    use crate::GeneratedItem;
+    use super::ModuleContents;
     use std::fmt::{Display, Formatter, Result};
 
-    impl Display for ModuleTree<'_> {
+    impl Display for ModuleContents<'_, '_> {
         fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-            for generated_item in &self.items {
+            let ModuleContents(tree, layout) = *self;
+            for generated_item in &tree.items {
                 writeln!(f, "{}", generated_item)?;
             }
-            for (sub_name, submodule) in &self.children {
-                // If the submodule has no child modules and all its items are deprecated,
-                // mark the submodule as deprecated
-                let deprecated = submodule.children.is_empty()
-                    && !submodule.items.is_empty()
-                    && submodule.items.iter().all(GeneratedItem::deprecated);
-
-                if deprecated {
+            for (sub_name, submodule) in &tree.children {
+                if submodule.deprecated() {
                     // Allow use of the deprecated type in this module only
                     writeln!(f, "#[allow(deprecated)]")?;
                     writeln!(f, "#[cfg_attr(not(test), deprecated)]")?;
                 }
-                writeln!(f, "pub mod {} {{", sub_name)?;
-                Display::fmt(submodule, f)?;
-                writeln!(f, "}}")?;
+                if submodule.in_own_file(layout) {
+                    writeln!(f, "pub mod {};", sub_name)?;
+                } else {
+                    writeln!(f, "pub mod {} {{", sub_name)?;
+                    Display::fmt(&ModuleContents(submodule, layout), f)?;
+                    writeln!(f, "}}")?;
+                }
             }
             Ok(())
         }
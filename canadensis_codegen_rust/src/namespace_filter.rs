@@ -0,0 +1,76 @@
+//! Selects the types to generate using the included and excluded namespaces
+
+use std::collections::BTreeSet;
+use std::iter;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::compiled::DsdlKind;
+use canadensis_dsdl_frontend::{TypeFullName, TypeKey};
+
+use crate::Config;
+
+/// Returns the keys of the types in a package that should be generated
+///
+/// This includes every type that the include and exclude namespaces select, and every type
+/// that a selected type refers to (directly or indirectly).
+pub(crate) fn selected_types(package: &CompiledPackage, config: &Config) -> BTreeSet<TypeKey> {
+    let mut selected = BTreeSet::new();
+    let mut pending: Vec<&TypeKey> = package
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| is_selected(key.name(), config))
+        .collect();
+    while let Some(key) = pending.pop() {
+        if selected.contains(key) {
+            continue;
+        }
+        selected.insert(key.clone());
+        // Types in external packages are not in the package, and have no dependencies to generate
+        if let Some(dsdl) = package.get_by_key(key) {
+            match &dsdl.kind {
+                DsdlKind::Message(message) => pending.extend(message.dependencies()),
+                DsdlKind::Service { request, response } => {
+                    pending.extend(request.dependencies());
+                    pending.extend(response.dependencies());
+                }
+            }
+        }
+    }
+    selected
+}
+
+/// Returns true if a type is in at least one included namespace (or no namespaces are included)
+/// and is not in any excluded namespace
+fn is_selected(name: &TypeFullName, config: &Config) -> bool {
+    let included = config.include_namespaces.is_empty()
+        || config
+            .include_namespaces
+            .iter()
+            .any(|namespace| in_namespace(name, namespace));
+    included
+        && !config
+            .exclude_namespaces
+            .iter()
+            .any(|namespace| in_namespace(name, namespace))
+}
+
+/// Returns true if a type is in a namespace or one of its sub-namespaces, or if the namespace is
+/// the name of the type
+///
+/// The namespace is a dot-separated path like `uavcan.node`. Like DSDL names, it is not
+/// case-sensitive.
+fn in_namespace(name: &TypeFullName, namespace: &str) -> bool {
+    if namespace.is_empty() {
+        return false;
+    }
+    let mut type_segments = name
+        .path()
+        .iter()
+        .map(String::as_str)
+        .chain(iter::once(name.name()));
+    namespace.split('.').all(|namespace_segment| {
+        type_segments
+            .next()
+            .is_some_and(|type_segment| type_segment.eq_ignore_ascii_case(namespace_segment))
+    })
+}
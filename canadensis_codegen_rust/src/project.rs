@@ -0,0 +1,347 @@
+//! Project configuration files and the `CYPHAL_PATH` environment variable
+//!
+//! A project configuration file records the DSDL root directories and compiler options for a
+//! project, so they do not need to be repeated in each command or build script. The
+//! command-line tool uses the file called [`CONFIG_FILE_NAME`] in the current directory or the
+//! closest parent directory that has one, and `canadensis_build` can start from one with
+//! `Builder::from_config_file`.
+//!
+//! The file is a text file. Each line is the name of a setting, optionally followed by
+//! whitespace and a value. Empty lines and lines that start with `#` are ignored. The settings
+//! have the same names as the command-line options:
+//! * `root`: A directory with DSDL files. Relative paths are relative to the directory that
+//!   contains the configuration file.
+//! * `external-package`: A DSDL package name and the Rust module with its code, separated by a
+//!   comma, like `uavcan.node,canadensis_data_types::uavcan::node`
+//! * `derive`: A derive macro to apply to every generated type
+//! * `type-derive`: A DSDL type name, `=`, and derive macros separated by commas
+//! * `include` and `exclude`: Namespaces to generate types from or not generate types from
+//! * `warn` and `allow`: Enable or disable a warning rule
+//! * `deny-warnings`, `unstable-allow-utf8-and-byte`, `unstable-forbid-saturated-bool`,
+//!   `views`, `lazy-views`, `arbitrary`, `proptest`, `serde`, `defmt`, `no-std`, `builders`,
+//!   `setters`, `plain-layout`, `constant-enums`, and `version-conversions`: Enable an option
+//!   (these settings have no value)
+//!
+//! ```text
+//! root dsdl/public_regulated_data_types
+//! root dsdl/my_company
+//! external-package uavcan,canadensis_data_types::uavcan
+//! serde
+//! derive Clone
+//! allow unused-constant
+//! ```
+//!
+//! The `CYPHAL_PATH` environment variable is a list of DSDL root directories, separated by `:`
+//! (or `;` on Windows), that other Cyphal tools also use. [`add_cyphal_path`] adds those
+//! directories to a list of root directories.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use canadensis_dsdl_frontend::warning::WarningRule;
+
+use crate::Config;
+
+/// The name of the project configuration file
+pub const CONFIG_FILE_NAME: &str = "canadensis.conf";
+
+/// The name of the environment variable with more DSDL root directories
+pub const CYPHAL_PATH: &str = "CYPHAL_PATH";
+
+/// The names of the settings that have values
+const VALUE_SETTINGS: [&str; 8] = [
+    "root",
+    "external-package",
+    "derive",
+    "type-derive",
+    "include",
+    "exclude",
+    "warn",
+    "allow",
+];
+
+/// The settings from a project configuration file
+#[derive(Debug, Clone, Default)]
+pub struct ProjectConfig {
+    /// The DSDL root directories, in the order they appear in the file
+    ///
+    /// Relative paths have been joined to the path of the directory that contains the file.
+    pub roots: Vec<PathBuf>,
+    /// Parser configuration
+    pub config: canadensis_dsdl_frontend::Config,
+    /// Code generation configuration
+    pub codegen_config: Config,
+    /// DSDL packages that refer to existing Rust modules instead of being generated
+    ///
+    /// Each key is a list of package name segments, and each value is a list of Rust module
+    /// path segments.
+    pub external_packages: BTreeMap<Vec<String>, Vec<String>>,
+    /// Warning rules to enable (true) or disable (false), in order
+    pub warning_rules: Vec<(WarningRule, bool)>,
+    /// Treat warnings as errors
+    pub deny_warnings: bool,
+}
+
+impl ProjectConfig {
+    /// Reads a project configuration file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ProjectConfig, ProjectError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|inner| ProjectError::Io {
+            path: path.to_owned(),
+            inner,
+        })?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        ProjectConfig::parse(&content, base).map_err(|(line, message)| ProjectError::Syntax {
+            path: path.to_owned(),
+            line,
+            message,
+        })
+    }
+
+    /// Looks for a project configuration file in a directory and its parents, and returns
+    /// the path to the first one found
+    pub fn find<P: AsRef<Path>>(directory: P) -> Option<PathBuf> {
+        directory
+            .as_ref()
+            .ancestors()
+            .map(|directory| directory.join(CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// Parses the content of a configuration file
+    ///
+    /// Relative root paths are joined to `base`. On failure, this returns the line number
+    /// (starting at 1) and a description of the problem.
+    fn parse(content: &str, base: &Path) -> Result<ProjectConfig, (usize, String)> {
+        let mut project = ProjectConfig::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = match line.split_once(char::is_whitespace) {
+                Some((name, value)) => (name, Some(value.trim())),
+                None => (line, None),
+            };
+            project
+                .apply(name, value, base)
+                .map_err(|message| (i + 1, message))?;
+        }
+        Ok(project)
+    }
+
+    /// Applies one setting
+    fn apply(&mut self, name: &str, value: Option<&str>, base: &Path) -> Result<(), String> {
+        let codegen = &mut self.codegen_config;
+        let flag = match name {
+            "deny-warnings" => &mut self.deny_warnings,
+            "unstable-allow-utf8-and-byte" => &mut self.config.allow_utf8_and_byte,
+            "views" => &mut codegen.views,
+            "lazy-views" => &mut codegen.lazy_views,
+            "arbitrary" => &mut codegen.arbitrary,
+            "proptest" => &mut codegen.proptest,
+            "serde" => &mut codegen.serde,
+            "defmt" => &mut codegen.defmt,
+            "no-std" => &mut codegen.no_std,
+            "builders" => &mut codegen.builders,
+            "setters" => &mut codegen.setters,
+            "plain-layout" => &mut codegen.plain_layout,
+            "constant-enums" => &mut codegen.constant_enums,
+            "version-conversions" => &mut codegen.version_conversions,
+            "unstable-forbid-saturated-bool" => {
+                no_value(name, value)?;
+                self.config.allow_saturated_bool = false;
+                return Ok(());
+            }
+            _ if !VALUE_SETTINGS.contains(&name) => {
+                return Err(format!("Unknown setting {}", name));
+            }
+            _ => {
+                let value = value.ok_or_else(|| format!("Expected a value after {}", name))?;
+                return self.apply_value(name, value, base);
+            }
+        };
+        no_value(name, value)?;
+        *flag = true;
+        Ok(())
+    }
+
+    /// Applies one setting that has a value
+    fn apply_value(&mut self, name: &str, value: &str, base: &Path) -> Result<(), String> {
+        match name {
+            "root" => self.roots.push(base.join(value)),
+            "external-package" => {
+                let (package, rust_module) = value.split_once(',').ok_or_else(|| {
+                    "Invalid external package, expected [cyphal-package],[rust-module-path]"
+                        .to_owned()
+                })?;
+                self.external_packages.insert(
+                    package.trim().split('.').map(String::from).collect(),
+                    rust_module.trim().split("::").map(String::from).collect(),
+                );
+            }
+            "derive" => self.codegen_config.derives.push(value.to_owned()),
+            "type-derive" => {
+                let invalid = || {
+                    "Invalid type derive, expected [cyphal-type]=[derive-path],[derive-path]..."
+                        .to_owned()
+                };
+                let (type_name, derives) = value.split_once('=').ok_or_else(invalid)?;
+                let derives: Vec<String> = derives
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|derive| !derive.is_empty())
+                    .map(String::from)
+                    .collect();
+                if type_name.trim().is_empty() || derives.is_empty() {
+                    return Err(invalid());
+                }
+                self.codegen_config
+                    .type_derives
+                    .entry(type_name.trim().to_owned())
+                    .or_default()
+                    .extend(derives);
+            }
+            "include" => self
+                .codegen_config
+                .include_namespaces
+                .push(value.to_owned()),
+            "exclude" => self
+                .codegen_config
+                .exclude_namespaces
+                .push(value.to_owned()),
+            "warn" | "allow" => {
+                let rule = WarningRule::from_name(value)
+                    .ok_or_else(|| format!("Unknown warning rule {}", value))?;
+                self.warning_rules.push((rule, name == "warn"));
+            }
+            _ => unreachable!("Unknown setting {}", name),
+        }
+        Ok(())
+    }
+}
+
+/// Returns an error if a setting that does not take a value has one
+fn no_value(name: &str, value: Option<&str>) -> Result<(), String> {
+    match value {
+        Some(value) => Err(format!("Unexpected value {} after {}", value, name)),
+        None => Ok(()),
+    }
+}
+
+/// Returns the directories in the `CYPHAL_PATH` environment variable
+///
+/// This returns an empty list if the variable is not set.
+pub fn cyphal_path() -> Vec<PathBuf> {
+    env::var_os(CYPHAL_PATH)
+        .map(|paths| {
+            env::split_paths(&paths)
+                .filter(|path| !path.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Adds the directories in the `CYPHAL_PATH` environment variable to the end of a list of
+/// root directories
+///
+/// Directories that are already in the list are not added again, so the same types are not
+/// found twice.
+pub fn add_cyphal_path(roots: &mut Vec<PathBuf>) {
+    let mut existing: Vec<PathBuf> = roots.iter().map(|root| normalize(root)).collect();
+    for path in cyphal_path() {
+        let normalized = normalize(&path);
+        if !existing.contains(&normalized) {
+            existing.push(normalized);
+            roots.push(path);
+        }
+    }
+}
+
+/// Returns the canonical form of a path if it exists, or the path itself
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_owned())
+}
+
+/// Errors that can occur when reading a project configuration file
+#[derive(thiserror::Error, Debug)]
+pub enum ProjectError {
+    /// The file could not be read
+    #[error("Failed to read {}", .path.display())]
+    Io {
+        /// The path to the file
+        path: PathBuf,
+        /// The error from reading the file
+        #[source]
+        inner: io::Error,
+    },
+    /// A line of the file was not valid
+    #[error("{}:{line}: {message}", .path.display())]
+    Syntax {
+        /// The path to the file
+        path: PathBuf,
+        /// The line number, starting at 1
+        line: usize,
+        /// A description of the problem
+        message: String,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::ProjectConfig;
+    use canadensis_dsdl_frontend::warning::WarningRule;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn parse_settings() {
+        let project = ProjectConfig::parse(
+            "# Roots\nroot public_regulated_data_types\nroot /opt/dsdl\n\n\
+            external-package uavcan.node, canadensis_data_types::uavcan::node\n\
+            serde\nunstable-forbid-saturated-bool\ntype-derive uavcan.node.Heartbeat=Hash,Eq\n\
+            allow unused-constant\n",
+            Path::new("project"),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                PathBuf::from("project/public_regulated_data_types"),
+                PathBuf::from("/opt/dsdl")
+            ],
+            project.roots
+        );
+        assert_eq!(
+            Some(&vec![
+                "canadensis_data_types".to_owned(),
+                "uavcan".to_owned(),
+                "node".to_owned()
+            ]),
+            project
+                .external_packages
+                .get(&vec!["uavcan".to_owned(), "node".to_owned()])
+        );
+        assert!(project.codegen_config.serde);
+        assert!(!project.codegen_config.views);
+        assert!(!project.config.allow_saturated_bool);
+        assert_eq!(
+            Some(&vec!["Hash".to_owned(), "Eq".to_owned()]),
+            project
+                .codegen_config
+                .type_derives
+                .get("uavcan.node.Heartbeat")
+        );
+        assert_eq!(
+            vec![(WarningRule::from_name("unused-constant").unwrap(), false)],
+            project.warning_rules
+        );
+
+        let error = |content| ProjectConfig::parse(content, Path::new("")).unwrap_err();
+        assert_eq!(2, error("views\nrustfmt\n").0);
+        assert_eq!(1, error("views yes").0);
+        assert_eq!(1, error("root").0);
+        assert_eq!(1, error("warn not-a-rule").0);
+    }
+}
@@ -5,7 +5,7 @@ use canadensis_dsdl_frontend::compiled::Extent;
 use canadensis_dsdl_frontend::types::{
     ImplicitField, PrimitiveType, ResolvedScalarType, ResolvedType,
 };
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{Display, Formatter, Result, Write};
 
 pub(crate) struct SizeBitsExpr<'t, 'c>(pub &'t GeneratedType<'c>);
 
@@ -28,25 +28,40 @@ fn write_complex_size_expression(f: &mut Formatter, ty: &GeneratedType) -> Resul
 
     match &ty.kind {
         GeneratedTypeKind::Struct(gstruct) => {
+            // The size of the fields so far
+            let mut expr = String::new();
+            // True if the fields so far always end at a byte boundary
+            let mut aligned = true;
             for field in &gstruct.fields {
                 match field {
                     GeneratedField::Data(field) => {
-                        Display::fmt(
-                            &WriteFieldSize {
+                        if field.cyphal_ty.alignment() == 8 && !aligned {
+                            // Composite fields start at a byte boundary, so the serialize code
+                            // skips up to 7 bits of padding before them
+                            expr = format!("({}0_usize).next_multiple_of(8) + ", expr);
+                        }
+                        write!(
+                            expr,
+                            "{}",
+                            WriteFieldSize {
                                 ty: field.cyphal_ty,
                                 expr: &format!("self.{}", field.name),
-                            },
-                            f,
+                            }
                         )?;
+                        aligned = field.cyphal_ty.alignment() == 8
+                            || (aligned && field.cyphal_ty.size().is_byte_aligned());
+                    }
+                    GeneratedField::Padding(bits) => {
+                        write!(expr, "{}", *bits)?;
+                        aligned = aligned && *bits % 8 == 0;
                     }
-                    GeneratedField::Padding(bits) => write!(f, "{}", *bits)?,
                 }
 
                 // End of field
-                write!(f, " + ")?;
+                expr.push_str(" + ");
             }
             // Last field, make the expression end in + 0
-            write!(f, "0")?;
+            write!(f, "{}0", expr)?;
         }
         GeneratedTypeKind::Enum(genum) => {
             write!(f, "{} + match self {{", genum.discriminant_bits)?;
@@ -178,8 +193,9 @@ impl Display for WriteScalarSize<'_> {
                     // Fixed-size type, use a literal
                     write!(f, "{}", inner_min_size)?;
                 } else {
-                    // Call size_bits() on the inner type
-                    write!(f, "({}).size_bits()", self.expr)?;
+                    // Call size_bits() on the inner type. The serialize code pads each composite
+                    // to a byte boundary, but size_bits() does not include that padding.
+                    write!(f, "({}).size_bits().next_multiple_of(8)", self.expr)?;
                 }
             }
             ResolvedScalarType::Primitive(primitive) => match primitive {
@@ -15,7 +15,8 @@
 
 use crate::error::EnumError;
 use crate::{
-    GeneratedEnum, GeneratedType, GeneratedTypeKind, GeneratedVariant, MessageRole, RustTypeName,
+    make_rust_identifier, round_up_integer_size, Deprecated, GeneratedEnum, GeneratedType,
+    GeneratedTypeKind, GeneratedVariant, MessageRole, RustTypeName,
 };
 use canadensis_dsdl_frontend::compiled::{Extent, FieldKind, Message, Struct};
 use canadensis_dsdl_frontend::constants::{ConstantValue, Constants};
@@ -44,10 +45,82 @@ pub(crate) fn generate_enum_from_struct<'a>(
     message: &'a Message,
     cyphal_struct: &Struct,
     constants: &'a Constants,
-    deprecated: bool,
+    deprecated: Option<Deprecated>,
     comments: &'a str,
     external_packages: &BTreeMap<Vec<String>, Vec<String>>,
 ) -> Result<GeneratedType<'a>, EnumError> {
+    let (discriminant_bits, variants) = enum_variants(cyphal_struct, constants, external_packages)?;
+
+    Ok(GeneratedType::new(
+        key,
+        rust_type.clone(),
+        message.bit_length(),
+        extent,
+        role,
+        GeneratedTypeKind::Enum(GeneratedEnum {
+            discriminant_bits,
+            variants,
+        }),
+        // Constants were all consumed to make the variants
+        Constants::default(),
+        deprecated,
+        comments,
+    ))
+}
+
+/// Creates an enum that can be converted to and from the only field of a struct, if the struct
+/// and its constants meet the requirements for enum generation
+///
+/// This returns None if the struct does not meet the requirements, has no constants, or has a
+/// field with more than 32 bits.
+pub(crate) fn generate_companion_enum<'a>(
+    cyphal_struct: &Struct,
+    constants: &'a Constants,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+) -> Option<CompanionEnum<'a>> {
+    // Check the field size first, because enum_variants requires discriminants to fit into u32
+    let field_name = match cyphal_struct.fields.as_slice() {
+        [field] => match field.kind() {
+            FieldKind::Data {
+                ty:
+                    ResolvedType::Scalar(ResolvedScalarType::Primitive(PrimitiveType::UInt {
+                        bits,
+                        ..
+                    })),
+                name,
+            } if *bits <= 32 => make_rust_identifier(name.clone()),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let (discriminant_bits, variants) =
+        enum_variants(cyphal_struct, constants, external_packages).ok()?;
+    if variants.is_empty() {
+        return None;
+    }
+    Some(CompanionEnum {
+        field_name,
+        field_ty: format!("u{}", round_up_integer_size(discriminant_bits)),
+        variants,
+    })
+}
+
+/// An enum generated in addition to a struct, with one variant for each constant
+pub(crate) struct CompanionEnum<'c> {
+    /// The name of the only field of the struct
+    pub field_name: String,
+    /// The Rust type of the field
+    pub field_ty: String,
+    pub variants: Vec<GeneratedVariant<'c>>,
+}
+
+/// Checks that a struct and its constants meet the requirements for enum generation, and returns
+/// the number of bits in the discriminant and the enum variants
+fn enum_variants<'a>(
+    cyphal_struct: &Struct,
+    constants: &'a Constants,
+    external_packages: &BTreeMap<Vec<String>, Vec<String>>,
+) -> Result<(u8, Vec<GeneratedVariant<'a>>), EnumError> {
     let field = match cyphal_struct.fields.as_slice() {
         [field] => field,
         _ => return Err(EnumError::EnumMultipleFields),
@@ -122,19 +195,5 @@ pub(crate) fn generate_enum_from_struct<'a>(
         })
         .collect();
 
-    Ok(GeneratedType::new(
-        key,
-        rust_type.clone(),
-        message.bit_length(),
-        extent,
-        role,
-        GeneratedTypeKind::Enum(GeneratedEnum {
-            discriminant_bits,
-            variants,
-        }),
-        // Constants were all consumed to make the variants
-        Constants::default(),
-        deprecated,
-        comments,
-    ))
+    Ok((discriminant_bits, variants))
 }
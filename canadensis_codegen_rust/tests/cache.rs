@@ -0,0 +1,112 @@
+//! Runs the code generator with a cache file and checks when it generates code again
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// A directory with one DSDL type, an output file, and a cache file
+struct Project {
+    root: PathBuf,
+}
+
+impl Project {
+    fn new(name: &str) -> Self {
+        let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+        let _ = fs::remove_dir_all(&root);
+        let project = Project { root };
+        project.write_type("uint8 a\n@sealed\n");
+        project
+    }
+
+    fn write_type(&self, content: &str) {
+        let path = self.root.join("dsdl/demo/Message.1.0.dsdl");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn output(&self) -> PathBuf {
+        self.root.join("out/lib.rs")
+    }
+
+    /// Runs the code generator and panics if it fails
+    fn generate(&self) {
+        let status = Command::new(env!("CARGO_BIN_EXE_canadensis_codegen_rust"))
+            .env_remove("CYPHAL_PATH")
+            .arg("compile")
+            .arg(self.root.join("dsdl"))
+            .arg("--output-file")
+            .arg(self.output())
+            .arg("--cache")
+            .arg(self.root.join("cache"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Sets the modification time of the output file to a time in the past, so that a later
+    /// write can be detected
+    fn backdate_output(&self) -> SystemTime {
+        let time = SystemTime::now() - Duration::from_secs(3600);
+        File::options()
+            .write(true)
+            .open(self.output())
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+        time
+    }
+}
+
+fn modified(path: &Path) -> SystemTime {
+    fs::metadata(path).unwrap().modified().unwrap()
+}
+
+#[test]
+fn fresh_output_not_written() {
+    let project = Project::new("cache_fresh");
+    project.generate();
+    let generated = fs::read_to_string(project.output()).unwrap();
+    let old_time = project.backdate_output();
+
+    project.generate();
+    assert_eq!(old_time, modified(&project.output()));
+    assert_eq!(generated, fs::read_to_string(project.output()).unwrap());
+}
+
+#[test]
+fn stale_input_generates_again() {
+    let project = Project::new("cache_stale");
+    project.generate();
+    assert!(!fs::read_to_string(project.output())
+        .unwrap()
+        .contains("pub b: u16"));
+
+    project.write_type("uint8 a\nuint16 b\n@sealed\n");
+    project.generate();
+    assert!(fs::read_to_string(project.output())
+        .unwrap()
+        .contains("pub b: u16"));
+
+    // Changing the type back restores the original code
+    project.write_type("uint8 a\n@sealed\n");
+    project.generate();
+    assert!(!fs::read_to_string(project.output())
+        .unwrap()
+        .contains("pub b: u16"));
+}
+
+#[test]
+fn edited_output_generates_again() {
+    let project = Project::new("cache_edited");
+    project.generate();
+    let generated = fs::read_to_string(project.output()).unwrap();
+
+    fs::write(project.output(), "// Edited\n").unwrap();
+    project.generate();
+    assert_eq!(generated, fs::read_to_string(project.output()).unwrap());
+
+    fs::remove_file(project.output()).unwrap();
+    project.generate();
+    assert_eq!(generated, fs::read_to_string(project.output()).unwrap());
+}
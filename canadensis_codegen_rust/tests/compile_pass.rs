@@ -1,6 +1,7 @@
 extern crate canadensis_codegen_rust;
 extern crate canadensis_dsdl_frontend;
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
@@ -36,6 +37,665 @@ fn compile_namespace_from_multiple_folders() -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+/// Checks that this library can generate view types for the Canadensis test types
+#[test]
+fn compile_views() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        views: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that this library can generate Arbitrary implementations for the Canadensis test types
+#[test]
+fn compile_arbitrary() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        arbitrary: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that this library can generate defmt::Format implementations for the test types
+#[test]
+fn compile_defmt() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        defmt: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that this library can generate proptest strategies for the Canadensis test types
+/// Checks that this library can generate conversions between versions of the test types
+#[test]
+fn compile_version_conversions() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        version_conversions: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that this library can generate proptest strategies for the Canadensis test types
+#[test]
+fn compile_proptest() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        proptest: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that this library can generate serde derives for the Canadensis test types
+#[test]
+fn compile_serde() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        serde: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that this library can generate builders for the Canadensis test types
+#[test]
+fn compile_builders() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        builders: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that the Rust backend writes the same code as generate_code_with_config
+#[test]
+fn rust_backend() -> Result<(), Box<dyn std::error::Error>> {
+    use canadensis_dsdl_frontend::backend::Backend;
+    let paths = ["../canadensis_dsdl_frontend/tests/simple_dsdl"];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let backend = canadensis_codegen_rust::RustBackend {
+        config: canadensis_codegen_rust::Config {
+            builders: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut output = Vec::new();
+    backend.generate(&package, &mut output)?;
+    let expected = canadensis_codegen_rust::generate_code_with_config(
+        &package,
+        &BTreeMap::new(),
+        &backend.config,
+    )?;
+    assert_eq!(String::from_utf8(output)?, expected.to_string());
+    Ok(())
+}
+
+/// Checks that this library can generate setters for the Canadensis test types
+#[test]
+fn compile_setters() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        setters: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    // NestedBytes has a uint8[<=4] not_always_aligned field
+    assert!(generated
+        .to_string()
+        .contains("pub fn set_not_always_aligned(&mut self, bytes: &[u8])"));
+    Ok(())
+}
+
+/// Checks that this library can generate plain-layout types for the Canadensis test types
+#[test]
+fn compile_plain_layout() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let config = canadensis_codegen_rust::Config {
+        plain_layout: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let mut sink = std::io::sink();
+    write!(sink, "{}", generated)?;
+    Ok(())
+}
+
+/// Checks that only types with no padding in their in-memory layout use `#[repr(C)]`
+#[test]
+fn plain_layout_padding() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        ("test.Plain.1.0", "uint32 a\nuint16 b\nuint16 c\n@sealed\n"),
+        (
+            "test.Nested.1.0",
+            "test.Plain.1.0 inner\nuint32 d\n@sealed\n",
+        ),
+        ("test.Misaligned.1.0", "uint8 a\nuint32 b\n@sealed\n"),
+        ("test.TrailingPadding.1.0", "uint32 a\nuint8 b\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let config = canadensis_codegen_rust::Config {
+        plain_layout: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let code = generated.to_string();
+
+    assert_eq!(2, code.matches("#[repr(C)]\n").count());
+    assert_eq!(2, code.matches("#[repr(C, packed)]\n").count());
+    assert_eq!(2, code.matches("pub fn ref_from_bytes(").count());
+    Ok(())
+}
+
+/// Checks that deprecated types name the newest non-deprecated version that replaces them
+#[test]
+fn deprecated_replacement() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        ("test.Replaced.1.0", "@deprecated\nuint8 value\n@sealed\n"),
+        ("test.Replaced.1.1", "@deprecated\nuint16 value\n@sealed\n"),
+        ("test.Replaced.2.0", "uint32 value\n@sealed\n"),
+        ("test.Abandoned.1.0", "@deprecated\nuint8 value\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let generated = canadensis_codegen_rust::generate_code(&package, &Default::default())?;
+    let code = generated.to_string();
+
+    assert_eq!(
+        2,
+        code.matches("#[deprecated(note = \"Replaced by test.Replaced.2.0\")]")
+            .count()
+    );
+    assert_eq!(1, code.matches("#[deprecated]\n").count());
+    Ok(())
+}
+
+/// Checks that comments on types, constants, and fields become documentation
+#[test]
+fn doc_comments() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    package.add_string(
+        None,
+        "test.Documented.1.0".parse()?,
+        "# Type docs\nuint8 LIMIT = 3\n# Constant docs\nuint8 value\n# Field docs\n@sealed\n"
+            .to_owned(),
+    )?;
+    let package = package.compile(&Config::default())?;
+    let generated = canadensis_codegen_rust::generate_code(&package, &Default::default())?;
+    let code = generated.to_string();
+
+    for docs in ["Type docs", "Constant docs", "Field docs"] {
+        let attribute = format!("#[cfg_attr(not(doctest), doc = \" {}\")]", docs);
+        assert!(code.contains(&attribute), "Missing {}", attribute);
+    }
+    Ok(())
+}
+
+/// Checks that configured derives are applied to all types or specific types
+#[test]
+fn configured_derives() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        ("test.Packed.1.0", "uint8 value\n@sealed\n"),
+        ("test.Unpacked.1.0", "uint8[<=4] values\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let config = canadensis_codegen_rust::Config {
+        derives: vec!["Debug".to_owned()],
+        type_derives: BTreeMap::from([(
+            "test.Unpacked".to_owned(),
+            vec!["Clone".to_owned(), "::core::hash::Hash".to_owned()],
+        )]),
+        proptest: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let code = generated.to_string();
+
+    // The packed type gets Clone and Copy, so proptest has nothing else to derive
+    assert!(code.contains("#[derive(Debug, Clone, Copy)]\npub struct Packed"));
+    assert!(code.contains("#[derive(Debug, Clone, ::core::hash::Hash)]\npub struct Unpacked"));
+    assert!(!code.contains("feature = \"proptest\", derive("));
+    Ok(())
+}
+
+/// Checks that DSDL constants become typed associated constants, including constants in
+/// service requests and responses
+#[test]
+fn typed_constants() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        (
+            "test.Constants.1.0",
+            "uint16 MAX_PUBLICATION_PERIOD = 60\nbool ENABLED = true\nint8 OFFSET = -3\n\
+            float32 SCALE = 0.5\nfloat16 SMALL = 2\n@sealed\n",
+        ),
+        (
+            "test.Service.1.0",
+            "uint3 REQUEST_CONSTANT = 7\n@sealed\n---\nuint64 RESPONSE_CONSTANT = 18446744073709551615\n@sealed\n",
+        ),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let generated = canadensis_codegen_rust::generate_code(&package, &Default::default())?;
+    let code = generated.to_string();
+
+    for constant in [
+        "pub const MAX_PUBLICATION_PERIOD: u16 = 60;",
+        "pub const ENABLED: bool = true;",
+        "pub const OFFSET: i8 = -3;",
+        "pub const SCALE: f32 = 0.5_f32;",
+        "pub const SMALL: ::half::f16 = ::half::f16::from_bits(16384);",
+        "impl ServiceRequest {\npub const REQUEST_CONSTANT: u8 = 7;",
+        "impl ServiceResponse {\npub const RESPONSE_CONSTANT: u64 = 18446744073709551615;",
+    ] {
+        assert!(code.contains(constant), "Missing {}", constant);
+    }
+    Ok(())
+}
+
+/// Checks that companion enums are generated only for types that follow the enum rules
+#[test]
+fn constant_enums() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        (
+            "test.Mode.1.0",
+            "uint8 IDLE = 0\nuint8 RUNNING = 1\nuint8 STOPPED = 7\nuint8 mode\n@sealed\n",
+        ),
+        // Two fields
+        (
+            "test.Pair.1.0",
+            "uint8 ONE = 1\nuint8 first\nuint8 second\n@sealed\n",
+        ),
+        // No constants
+        ("test.Plain.1.0", "uint8 value\n@sealed\n"),
+        // Too large
+        ("test.Large.1.0", "uint64 BIG = 1\nuint64 value\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let config = canadensis_codegen_rust::Config {
+        constant_enums: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let code = generated.to_string();
+
+    assert!(code.contains("pub enum ModeEnum {"));
+    assert!(code.contains("7 => Ok(ModeEnum::Stopped),"));
+    assert!(code.contains("impl ::core::convert::From<ModeEnum> for Mode {"));
+    assert!(code.contains("impl ::core::convert::TryFrom<Mode> for ModeEnum {"));
+    for name in ["PairEnum", "PlainEnum", "LargeEnum"] {
+        assert!(!code.contains(name), "Unexpected {}", name);
+    }
+    Ok(())
+}
+
+/// Checks that only types in the included namespaces (and their dependencies) are generated
+#[test]
+fn namespace_filter() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        (
+            "app.node.Status.1.0",
+            "app.unit.Volt.1.0 voltage\n@sealed\n",
+        ),
+        ("app.node.port.Ports.1.0", "uint16 port\n@sealed\n"),
+        ("app.node.internal.Debug.1.0", "uint8 value\n@sealed\n"),
+        ("app.unit.Volt.1.0", "float32 volt\n@sealed\n"),
+        ("app.unit.Ampere.1.0", "float32 ampere\n@sealed\n"),
+        ("other.Unrelated.1.0", "uint8 value\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let config = canadensis_codegen_rust::Config {
+        include_namespaces: vec!["App.Node".to_owned()],
+        exclude_namespaces: vec!["app.node.internal".to_owned(), "app.unit".to_owned()],
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let code = generated.to_string();
+
+    for name in ["pub struct Status", "pub struct Ports", "pub struct Volt"] {
+        assert!(code.contains(name), "Missing {}", name);
+    }
+    for name in [
+        "pub struct Debug",
+        "pub struct Ampere",
+        "pub struct Unrelated",
+    ] {
+        assert!(!code.contains(name), "Unexpected {}", name);
+    }
+    Ok(())
+}
+
+/// Checks that the generated code can be divided into files in different ways
+#[test]
+fn output_layouts() -> Result<(), Box<dyn std::error::Error>> {
+    use canadensis_codegen_rust::Layout;
+    use std::path::Path;
+
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        ("app.node.Status.1.0", "uint8 value\n@sealed\n"),
+        ("app.node.Status.1.1", "uint16 value\n@sealed\n"),
+        ("app.unit.Volt.1.0", "float32 volt\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let generated = canadensis_codegen_rust::generate_code(&package, &Default::default())?;
+    let root = Path::new("src/lib.rs");
+    let paths = |layout| -> Vec<String> {
+        generated
+            .files(root, layout)
+            .iter()
+            .map(|file| file.path().to_string_lossy().replace('\\', "/"))
+            .collect()
+    };
+
+    let single = generated.files(root, Layout::SingleFile);
+    assert_eq!(1, single.len());
+    assert_eq!(generated.to_string(), single[0].contents());
+
+    assert_eq!(
+        vec![
+            "src/lib.rs",
+            "src/app.rs",
+            "src/app/node.rs",
+            "src/app/unit.rs"
+        ],
+        paths(Layout::FilePerNamespace)
+    );
+    assert_eq!(
+        vec![
+            "src/lib.rs",
+            "src/app.rs",
+            "src/app/node.rs",
+            "src/app/node/status_1_0.rs",
+            "src/app/node/status_1_1.rs",
+            "src/app/unit.rs",
+            "src/app/unit/volt_1_0.rs",
+        ],
+        paths(Layout::FilePerType)
+    );
+
+    let files = generated.files(root, Layout::FilePerNamespace);
+    assert!(files[0].contents().contains("pub mod app;"));
+    assert!(files[1].contents().contains("pub mod node;"));
+    assert!(files[2].contents().contains("pub mod status_1_0 {"));
+    Ok(())
+}
+
+/// Checks that lazy view types are generated only for structs whose fields have fixed offsets
+#[test]
+fn lazy_views() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        ("test.Point.1.0", "int16 x\nint16 y\n@sealed\n"),
+        (
+            "test.Frame.1.0",
+            "uint8 kind\nvoid4\nuint12 id\ntest.Point.1.0 origin\nbool[3] flags\nuint8[<=64] data\n@sealed\n",
+        ),
+        ("test.Variable.1.0", "uint8[<=4] first\nuint8 second\n@sealed\n"),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let config = canadensis_codegen_rust::Config {
+        lazy_views: true,
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let code = generated.to_string();
+
+    assert!(code.contains("pub struct FrameLazy<'a>"));
+    assert!(code.contains("pub struct PointLazy<'a>"));
+    assert!(!code.contains("VariableLazy"));
+    assert!(code.contains("pub fn kind(&self) -> u8 {\nlet cursor = &mut ::canadensis_encoding::ReadCursor::new(self.payload);\n{"));
+    assert!(code.contains("pub fn id(&self) -> u16 {\nlet cursor = &mut ::canadensis_encoding::ReadCursor::new(self.payload);\ncursor.skip_bits(12);"));
+    assert!(code.contains("cursor.skip_bits(24);\nOk({ cursor.read_composite()? })"));
+    assert!(code.contains("cursor.skip_bits(56);"));
+    assert!(code.contains("cursor.skip_bits(59);"));
+    Ok(())
+}
+
+/// Checks that the no_std profile makes a no_std crate root, rejects derives from std, and
+/// gates the optional dependencies behind features
+#[test]
+fn no_std_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let paths = [
+        "tests/compile_pass",
+        "../canadensis_dsdl_frontend/tests/simple_dsdl",
+    ];
+    let absolute_paths =
+        paths.map(|relative| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative));
+
+    let package = try_compile_package(&absolute_paths)?;
+    let mut config = canadensis_codegen_rust::Config {
+        no_std: true,
+        serde: true,
+        proptest: true,
+        derives: vec!["::core::fmt::Debug".to_owned()],
+        ..Default::default()
+    };
+    let generated =
+        canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)?;
+    let code = generated.to_string();
+    assert!(code.starts_with(
+        "#![cfg_attr(not(any(test, feature = \"std\")), no_std)]\n#[cfg(feature = \"alloc\")] extern crate alloc;\n"
+    ));
+    assert!(!code.contains("std::"));
+    // heapless::String does not require an allocator
+    assert!(!code.replace("::heapless::String", "").contains("String"));
+
+    let dependencies = canadensis_codegen_rust::generated_code_dependencies_with_config(&config);
+    assert!(dependencies.contains("heapless = { version = \"0.8.0\", default-features = false }"));
+    assert!(
+        dependencies.contains("alloc = [\"serde?/alloc\"]\nstd = [\"alloc\", \"serde?/std\"]\n")
+    );
+    assert!(dependencies.contains("proptest = [\"dep:proptest\", \"std\"]\n"));
+
+    config.type_derives = BTreeMap::from([(
+        "canadensis.Test".to_owned(),
+        vec!["::std::hash::Hash".to_owned()],
+    )]);
+    match canadensis_codegen_rust::generate_code_with_config(&package, &Default::default(), &config)
+    {
+        Err(canadensis_codegen_rust::Error::StdDerive { derive }) => {
+            assert_eq!("::std::hash::Hash", derive)
+        }
+        _ => panic!("Expected a derive error"),
+    }
+
+    // Without the profile, nothing changes
+    let code = canadensis_codegen_rust::generate_code(&package, &Default::default())?.to_string();
+    assert!(!code.contains("no_std"));
+    Ok(())
+}
+
+/// Checks that the C header has the types in dependency order, with names that do not conflict
+/// with C keywords
+#[test]
+fn c_header() -> Result<(), Box<dyn std::error::Error>> {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        (
+            "test.Outer.1.0",
+            "test.Inner.1.0[<=2] inner\nsaturated uint3 small\n@extent 256\n",
+        ),
+        (
+            "test.Inner.1.0",
+            "# An inner type\nfloat16 default\n@sealed\n",
+        ),
+        (
+            "test.Choice.1.0",
+            "@union\nuint8 a\ntest.Outer.1.0 b\n@sealed\n",
+        ),
+    ] {
+        package.add_string(None, key.parse()?, dsdl.to_owned())?;
+    }
+    let package = package.compile(&Config::default())?;
+    let header = canadensis_codegen_rust::c_header::generate_c_header(
+        &package,
+        &Default::default(),
+        "test-types.h",
+    );
+
+    assert!(header.contains("#ifndef TEST_TYPES_H_INCLUDED\n"));
+    let inner = header.find("} test_Inner_1_0;").unwrap();
+    let outer = header.find("} test_Outer_1_0;").unwrap();
+    let choice = header.find("} test_Choice_1_0;").unwrap();
+    assert!(inner < outer && outer < choice);
+    assert!(header.contains("// An inner type\n"));
+    assert!(header.contains("    float default_;\n"));
+    assert!(header
+        .contains("        test_Inner_1_0 elements[2];\n        size_t count;\n    } inner;\n"));
+    assert!(header.contains("#define test_Outer_1_0_EXTENT_BYTES_ 32UL\n"));
+    assert!(header.contains("((obj->small > 7U) ? 7U : obj->small)"));
+    assert!(header.contains("    uint8_t _tag_;\n} test_Choice_1_0;"));
+
+    let config = canadensis_codegen_rust::Config {
+        exclude_namespaces: vec!["test.Choice".to_owned()],
+        ..Default::default()
+    };
+    let header = canadensis_codegen_rust::c_header::generate_c_header(&package, &config, "types.h");
+    assert!(header.contains("test_Outer_1_0"));
+    assert!(!header.contains("test_Choice_1_0"));
+    Ok(())
+}
+
 fn try_compile_package(
     paths: &[PathBuf],
 ) -> Result<CompiledPackage, Box<canadensis_dsdl_frontend::Error>> {
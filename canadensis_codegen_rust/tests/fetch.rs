@@ -0,0 +1,92 @@
+#![cfg(feature = "fetch")]
+
+extern crate canadensis_codegen_rust;
+extern crate flate2;
+extern crate tar;
+
+use std::fs;
+use std::path::PathBuf;
+
+use canadensis_codegen_rust::fetch::{content_hash, install_archive, FetchError, Remote};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// Creates a .tar.gz archive like the archives that GitHub provides
+fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(
+                &mut header,
+                format!("repository-0123abcd/{}", path),
+                content.as_bytes(),
+            )
+            .unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+fn cache_directory(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&path);
+    path
+}
+
+#[test]
+fn install_and_check_hash() {
+    let cache = cache_directory("fetch_install");
+    let archive = archive(&[
+        (
+            "uavcan/node/7509.Heartbeat.1.0.dsdl",
+            "uint32 uptime\n@sealed\n",
+        ),
+        ("README.md", "Data types\n"),
+    ]);
+    let remote = Remote::new("https://example.com/dsdl/repository.git", "0123abcd");
+
+    let fetched = install_archive(&remote, archive.as_slice(), &cache).unwrap();
+    assert_eq!(fetched.path, cache.join("repository-0123abcd"));
+    assert_eq!(
+        fs::read_to_string(fetched.path.join("uavcan/node/7509.Heartbeat.1.0.dsdl")).unwrap(),
+        "uint32 uptime\n@sealed\n"
+    );
+    assert_eq!(fetched.sha256, content_hash(&fetched.path).unwrap());
+    assert_eq!(fetched.sha256.len(), 64);
+
+    // The same content with the expected hash
+    let pinned = remote.clone().with_sha256(fetched.sha256.clone());
+    assert_eq!(
+        install_archive(&pinned, archive.as_slice(), &cache).unwrap(),
+        fetched
+    );
+    // Different content
+    let modified = self::archive(&[
+        (
+            "uavcan/node/7509.Heartbeat.1.0.dsdl",
+            "uint64 uptime\n@sealed\n",
+        ),
+        ("README.md", "Data types\n"),
+    ]);
+    assert!(matches!(
+        install_archive(&pinned, modified.as_slice(), &cache),
+        Err(FetchError::HashMismatch { .. })
+    ));
+    // The copy that matched is still there
+    assert_eq!(content_hash(&fetched.path).unwrap(), fetched.sha256);
+}
+
+#[test]
+fn archive_url() {
+    assert_eq!(
+        Remote::public_regulated_data_types("f9f6790").archive_url(),
+        "https://github.com/OpenCyphal/public_regulated_data_types/archive/f9f6790.tar.gz"
+    );
+    assert_eq!(
+        Remote::new("https://example.com/dsdl.git/", "v1").archive_url(),
+        "https://example.com/dsdl/archive/v1.tar.gz"
+    );
+}
@@ -2,6 +2,7 @@
 //! Error definitions
 //!
 
+use core::fmt;
 use fallible_collections::TryReserveError;
 
 /// An error indicating that memory could not be allocated
@@ -14,6 +15,14 @@ impl From<TryReserveError> for OutOfMemoryError {
     }
 }
 
+impl fmt::Display for OutOfMemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Out of memory")
+    }
+}
+
+impl core::error::Error for OutOfMemoryError {}
+
 /// An error that may occur when subscribing to a service
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum ServiceSubscribeError<E> {
@@ -28,3 +37,26 @@ impl<E> From<E> for ServiceSubscribeError<E> {
         ServiceSubscribeError::Transport(inner)
     }
 }
+
+impl<E> fmt::Display for ServiceSubscribeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceSubscribeError::Anonymous => {
+                f.write_str("An anonymous node can't subscribe to a service")
+            }
+            ServiceSubscribeError::Transport(_) => f.write_str("Transport error"),
+        }
+    }
+}
+
+impl<E> core::error::Error for ServiceSubscribeError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ServiceSubscribeError::Anonymous => None,
+            ServiceSubscribeError::Transport(inner) => Some(inner),
+        }
+    }
+}
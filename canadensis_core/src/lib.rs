@@ -9,10 +9,16 @@ extern crate alloc;
 extern crate fallible_collections;
 extern crate fugit;
 extern crate heapless;
+#[cfg(feature = "log")]
 extern crate log;
 pub extern crate nb;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 
 mod error;
+pub mod profile;
 pub mod session;
 pub mod subscription;
 pub mod time;
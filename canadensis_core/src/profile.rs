@@ -0,0 +1,70 @@
+//!
+//! Hooks for measuring how long transport operations take
+//!
+//! Transports that support profiling call a [`Profiler`] before and after each operation.
+//! On a microcontroller, a profiler can read a cycle counter (such as the DWT cycle counter on
+//! Cortex-M) to check that the operations fit into a worst-case execution time budget.
+//!
+
+/// An operation that a transmitter or receiver measures
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Operation {
+    /// Handling an incoming frame that did not complete a transfer
+    Accept,
+    /// Handling an incoming frame that completed a transfer
+    ///
+    /// This includes checking the transfer CRC and moving the payload into the transfer.
+    Reassemble,
+    /// Breaking an outgoing transfer into frames and putting them in the transmit queue
+    ///
+    /// For a streaming payload, this includes serializing the payload.
+    Serialize,
+    /// Sending frames from the transmit queue to the driver
+    Flush,
+}
+
+/// Measures the duration of transport operations
+pub trait Profiler {
+    /// Returns the current value of a free-running counter, such as a cycle counter
+    ///
+    /// The counter may wrap around.
+    fn counter(&mut self) -> u32;
+
+    /// Records that an operation took `duration` counter ticks
+    fn record(&mut self, operation: Operation, duration: u32);
+
+    /// Records an operation that started when the counter had the value `start`
+    fn finish(&mut self, operation: Operation, start: u32) {
+        let duration = self.counter().wrapping_sub(start);
+        self.record(operation, duration);
+    }
+}
+
+/// A profiler that does nothing
+///
+/// This is the default profiler for transports that support profiling.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoProfiler;
+
+impl Profiler for NoProfiler {
+    #[inline(always)]
+    fn counter(&mut self) -> u32 {
+        0
+    }
+
+    #[inline(always)]
+    fn record(&mut self, _operation: Operation, _duration: u32) {}
+}
+
+impl<P> Profiler for &mut P
+where
+    P: Profiler + ?Sized,
+{
+    fn counter(&mut self) -> u32 {
+        (**self).counter()
+    }
+
+    fn record(&mut self, operation: Operation, duration: u32) {
+        (**self).record(operation, duration)
+    }
+}
@@ -221,6 +221,8 @@ where
             match expired_node_id {
                 Some(id) => {
                     self.sessions.remove(&id);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(reason = "timeout", "Session destroyed");
                 }
                 None => break,
             }
@@ -286,6 +288,8 @@ where
             }
             if remove {
                 *entry = None;
+                #[cfg(feature = "tracing")]
+                tracing::debug!(reason = "timeout", "Session destroyed");
             }
         }
     }
@@ -356,8 +360,11 @@ where
             }
             match expired_node_id {
                 Some(id) => {
+                    #[cfg(feature = "log")]
                     log::debug!("Removing expired session from node {:?}", id);
                     self.sessions.remove(&id);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(node = ?id, reason = "timeout", "Session destroyed");
                 }
                 None => break,
             }
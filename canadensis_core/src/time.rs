@@ -10,7 +10,14 @@
 //! when overflow has happened once. If overflow has happened more than once between two instants,
 //! the calculated duration will be too short.
 //!
+//! # Clocks
+//!
+//! [`TickClock`] extends a wrapping hardware counter into a 64-bit time, and [`FugitClock`]
+//! adapts monotonic timers that return `fugit` instants. With the `std` feature, `StdClock`
+//! measures time using the standard library.
+//!
 
+use core::fmt;
 use fugit::{Instant, MicrosDurationU32, MillisDurationU32};
 
 /// A duration represented as a 32-bit number of microseconds
@@ -41,3 +48,221 @@ pub const fn milliseconds(milliseconds: u32) -> MicrosecondDuration32 {
         .const_try_into()
         .expect("Number of milliseconds out of range")
 }
+
+/// An instant represented as a 64-bit number of microseconds
+///
+/// This type does not overflow for more than 500,000 years.
+pub type Microseconds64 = Instant<u64, 1, 1_000_000>;
+
+/// Converts a number of ticks at `NOM / DENOM` seconds per tick into microseconds
+///
+/// The intermediate calculation uses 128 bits, so it does not overflow.
+fn ticks_to_microseconds<const NOM: u32, const DENOM: u32>(ticks: u64) -> u64 {
+    (u128::from(ticks) * u128::from(NOM) * 1_000_000 / u128::from(DENOM)) as u64
+}
+
+/// Truncates a 64-bit instant to 32 bits
+///
+/// The truncated instant wraps around in the way that [`Microseconds32`] arithmetic expects.
+fn truncate(instant: Microseconds64) -> Microseconds32 {
+    Microseconds32::from_ticks(instant.ticks() as u32)
+}
+
+/// A clock that measures the time since it was created, using the standard library
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct StdClock {
+    start_time: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdClock {
+    /// Creates a clock that starts at zero now
+    pub fn new() -> Self {
+        StdClock {
+            start_time: std::time::Instant::now(),
+        }
+    }
+
+    /// Returns the current time as a 64-bit instant, which does not overflow
+    pub fn now_64(&self) -> Microseconds64 {
+        Microseconds64::from_ticks(self.start_time.elapsed().as_micros() as u64)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    fn now(&mut self) -> Microseconds32 {
+        truncate(self.now_64())
+    }
+}
+
+/// A clock that extends a wrapping 32-bit counter into a 64-bit number of microseconds
+///
+/// The tick function returns the value of a free-running hardware counter that increments `HZ`
+/// times per second and wraps around to zero after `u32::MAX`. The default rate is 1 MHz.
+///
+/// The clock can only detect one wrap-around between two readings, so [`now`](Clock::now) or
+/// [`now_64`](TickClock::now_64) must be called at least once per counter period
+/// (2<sup>32</sup> / `HZ` seconds, or about 71 minutes at 1 MHz). Otherwise, the clock will
+/// fall behind.
+///
+/// A monotonic timer that returns 32-bit `fugit` instants (such as an RTIC monotonic) can be
+/// used like this:
+///
+/// ```ignore
+/// let clock = TickClock::<_, 32_768>::new(|| Mono::now().ticks());
+/// ```
+pub struct TickClock<F, const HZ: u32 = 1_000_000> {
+    /// The function that reads the counter
+    read_ticks: F,
+    /// The counter value from the last reading
+    last_ticks: u32,
+    /// The total number of ticks, including wrap-arounds
+    total_ticks: u64,
+}
+
+impl<F, const HZ: u32> TickClock<F, HZ>
+where
+    F: FnMut() -> u32,
+{
+    /// Creates a clock that reads a counter using the provided function
+    ///
+    /// The clock starts at the current counter value.
+    pub fn new(mut read_ticks: F) -> Self {
+        let last_ticks = read_ticks();
+        TickClock {
+            read_ticks,
+            last_ticks,
+            total_ticks: u64::from(last_ticks),
+        }
+    }
+
+    /// Returns the current time as a 64-bit instant, which does not overflow
+    pub fn now_64(&mut self) -> Microseconds64 {
+        let ticks = (self.read_ticks)();
+        let elapsed = ticks.wrapping_sub(self.last_ticks);
+        self.last_ticks = ticks;
+        self.total_ticks = self.total_ticks.wrapping_add(u64::from(elapsed));
+        Microseconds64::from_ticks(ticks_to_microseconds::<1, HZ>(self.total_ticks))
+    }
+}
+
+impl<F, const HZ: u32> Clock for TickClock<F, HZ>
+where
+    F: FnMut() -> u32,
+{
+    fn now(&mut self) -> Microseconds32 {
+        truncate(self.now_64())
+    }
+}
+
+impl<F, const HZ: u32> fmt::Debug for TickClock<F, HZ> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TickClock")
+            .field("last_ticks", &self.last_ticks)
+            .field("total_ticks", &self.total_ticks)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A clock that reads 64-bit `fugit` instants from a monotonic timer
+///
+/// The instants can have any tick rate, and are converted into microseconds. This works with
+/// RTIC monotonic timers that return 64-bit instants:
+///
+/// ```ignore
+/// let clock = FugitClock::new(|| Mono::now());
+/// ```
+///
+/// Other timers that count microseconds in a `u64`, such as `embassy_time`, can be adapted
+/// by converting the count:
+///
+/// ```ignore
+/// let clock = FugitClock::new(|| Microseconds64::from_ticks(embassy_time::Instant::now().as_micros()));
+/// ```
+///
+/// For timers that return 32-bit instants, use [`TickClock`], which handles wrap-around.
+pub struct FugitClock<F> {
+    /// The function that reads the current instant
+    read_instant: F,
+}
+
+impl<F, const NOM: u32, const DENOM: u32> FugitClock<F>
+where
+    F: FnMut() -> Instant<u64, NOM, DENOM>,
+{
+    /// Creates a clock that reads instants using the provided function
+    pub fn new(read_instant: F) -> Self {
+        FugitClock { read_instant }
+    }
+
+    /// Returns the current time as a 64-bit instant
+    pub fn now_64(&mut self) -> Microseconds64 {
+        let ticks = (self.read_instant)().ticks();
+        Microseconds64::from_ticks(ticks_to_microseconds::<NOM, DENOM>(ticks))
+    }
+}
+
+impl<F, const NOM: u32, const DENOM: u32> Clock for FugitClock<F>
+where
+    F: FnMut() -> Instant<u64, NOM, DENOM>,
+{
+    fn now(&mut self) -> Microseconds32 {
+        truncate(self.now_64())
+    }
+}
+
+impl<F> fmt::Debug for FugitClock<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FugitClock").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn tick_clock_wraps() {
+        let counter = Cell::new(u32::MAX - 9);
+        let mut clock = TickClock::<_>::new(|| counter.get());
+        assert_eq!(u64::from(u32::MAX - 9), clock.now_64().ticks());
+        counter.set(10);
+        assert_eq!(u64::from(u32::MAX) + 11, clock.now_64().ticks());
+        // The 32-bit instants wrap around, and durations across the wrap-around are correct
+        let before = Microseconds32::from_ticks(u32::MAX - 9);
+        assert_eq!(
+            MicrosecondDuration32::from_ticks(20),
+            clock.now().checked_duration_since(before).unwrap()
+        );
+    }
+
+    #[test]
+    fn tick_clock_rate() {
+        let counter = Cell::new(0u32);
+        let mut clock = TickClock::<_, 32_768>::new(|| counter.get());
+        counter.set(32_768);
+        assert_eq!(1_000_000, clock.now_64().ticks());
+        counter.set(0);
+        // 2^32 ticks at 32768 Hz is 131072 seconds
+        assert_eq!(131_072_000_000, clock.now_64().ticks());
+    }
+
+    #[test]
+    fn fugit_clock() {
+        let mut clock =
+            FugitClock::new(|| Instant::<u64, 1, 1000>::from_ticks(u64::from(u32::MAX)));
+        let expected_micros = u64::from(u32::MAX) * 1000;
+        assert_eq!(expected_micros, clock.now_64().ticks());
+        assert_eq!(expected_micros as u32, clock.now().ticks());
+    }
+}
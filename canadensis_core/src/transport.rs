@@ -8,6 +8,7 @@ use alloc::vec::Vec;
 use core::convert::TryFrom;
 use core::fmt::Debug;
 use core::hash::Hash;
+use fallible_collections::FallibleVec;
 
 /// Basic requirements for a transport that can be used to send and receive transfers
 ///
@@ -48,6 +49,38 @@ where
     where
         A: AsRef<[u8]>;
 
+    /// Starts the process of sending an outgoing transfer with a payload that is produced in
+    /// pieces
+    ///
+    /// Transports that split transfers into frames can override this function to write the
+    /// payload directly into frames. The default implementation collects the payload into a
+    /// temporary buffer and calls [`push`](#tymethod.push).
+    fn push_streaming<P>(
+        &mut self,
+        transfer: Transfer<&P, Self::Transport>,
+        clock: &mut C,
+        driver: &mut Self::Driver,
+    ) -> nb::Result<(), Self::Error>
+    where
+        P: StreamingPayload + ?Sized,
+    {
+        let mut payload: Vec<u8> = Vec::new();
+        FallibleVec::try_reserve(&mut payload, transfer.payload.payload_length())
+            .map_err(|e| nb::Error::Other(OutOfMemoryError::from(e).into()))?;
+        transfer
+            .payload
+            .write_to(&mut |piece| payload.extend_from_slice(piece));
+        self.push(
+            Transfer {
+                header: transfer.header,
+                loopback: transfer.loopback,
+                payload,
+            },
+            clock,
+            driver,
+        )
+    }
+
     /// Attempts to send all queued outgoing frames
     ///
     /// If ths transport's `push` implementation blocks until all frames have been sent,
@@ -71,6 +104,37 @@ where
     fn mtu(&self) -> usize;
 }
 
+/// A transfer payload that is produced in pieces instead of being stored in one buffer
+///
+/// This is usually a value that will be serialized as it is sent.
+pub trait StreamingPayload {
+    /// Returns the total length of the payload in bytes
+    fn payload_length(&self) -> usize;
+    /// Passes the payload to `sink` in one or more consecutive pieces
+    ///
+    /// The total length of the pieces must be equal to the value that
+    /// [`payload_length`](#tymethod.payload_length) returns.
+    fn write_to(&self, sink: &mut dyn FnMut(&[u8]));
+}
+
+/// A payload made of several byte slices, which are concatenated when the payload is sent
+///
+/// This allows an application that already has parts of a payload in separate buffers (for
+/// example, a header and an image) to send them without copying them into one buffer.
+impl StreamingPayload for [&[u8]] {
+    fn payload_length(&self) -> usize {
+        self.iter().map(|piece| piece.len()).sum()
+    }
+
+    fn write_to(&self, sink: &mut dyn FnMut(&[u8])) {
+        for piece in self {
+            if !piece.is_empty() {
+                sink(piece);
+            }
+        }
+    }
+}
+
 /// A receiver that can assemble incoming frames into transfers
 pub trait Receiver<C>
 where
@@ -132,6 +196,26 @@ where
     /// Unsubscribes from messages on a subject
     fn unsubscribe_message(&mut self, subject: SubjectId, driver: &mut Self::Driver);
 
+    /// Sets whether the subscription to a subject accepts transfers from anonymous nodes
+    ///
+    /// New subscriptions accept anonymous transfers. Some subjects, like plug-and-play node ID
+    /// allocation requests, need them, but subjects that carry commands should usually reject
+    /// them.
+    ///
+    /// Subscribing to the subject again resets this option. Rejected transfers are ignored and are
+    /// not counted as errors.
+    ///
+    /// This function returns true if the option was applied. It returns false and has no effect
+    /// if this receiver is not subscribed to the subject or does not support this option.
+    ///
+    /// The default implementation does nothing and returns false, so a receiver that does not
+    /// override it always accepts anonymous transfers.
+    #[must_use]
+    fn set_accept_anonymous(&mut self, subject: SubjectId, accept: bool) -> bool {
+        let _ = (subject, accept);
+        false
+    }
+
     /// Subscribes to requests for a service
     ///
     /// This will enable incoming service request transfers from all nodes on the specified service
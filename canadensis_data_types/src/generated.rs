@@ -1,7 +1,7 @@
 #[cfg(not(target_endian = "little"))]
 compile_error!("Zero-copy serialization requires a little-endian target");
 #[allow(unused_variables, unused_braces, unused_parens)]
-#[allow(clippy::identity_op)]
+#[allow(clippy::identity_op, clippy::derivable_impls)]
 pub mod reg {
     pub mod udral {
         pub mod physics {
@@ -37,10 +37,22 @@ pub mod reg {
                         /// size 32 bits
                         pub acoustic_power: crate::uavcan::si::unit::power::scalar_1_0::Scalar,
                     }
+                    impl ::core::default::Default for Note {
+                        fn default() -> Self {
+                            Self {
+                                frequency: ::core::default::Default::default(),
+                                duration: ::core::default::Default::default(),
+                                acoustic_power: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Note {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Note {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Note {}
                     impl Note {}
                     impl ::canadensis_encoding::Serialize for Note {
@@ -98,10 +110,21 @@ pub kinematics: crate::reg::udral::physics::kinematics::rotation::planar_0_1::Pl
 /// size 32 bits
 pub torque: crate::uavcan::si::unit::torque::scalar_1_0::Scalar,
 }
+                        impl ::core::default::Default for Planar {
+                            fn default() -> Self {
+                                Self {
+                                    kinematics: ::core::default::Default::default(),
+                                    torque: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Planar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Planar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 16;
+                        }
                         impl ::canadensis_encoding::Message for Planar {}
                         impl Planar {}
                         impl ::canadensis_encoding::Serialize for Planar {
@@ -151,10 +174,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 128 bits
 pub value: crate::reg::udral::physics::dynamics::rotation::planar_0_1::Planar,
 }
+                        impl ::core::default::Default for PlanarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PlanarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PlanarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 23;
+                        }
                         impl ::canadensis_encoding::Message for PlanarTs {}
                         impl PlanarTs {}
                         impl ::canadensis_encoding::Serialize for PlanarTs {
@@ -211,10 +245,21 @@ pub kinematics: crate::reg::udral::physics::kinematics::translation::linear_0_1:
 /// size 32 bits
 pub force: crate::uavcan::si::unit::force::scalar_1_0::Scalar,
 }
+                        impl ::core::default::Default for Linear {
+                            fn default() -> Self {
+                                Self {
+                                    kinematics: ::core::default::Default::default(),
+                                    force: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Linear {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Linear {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 16;
+                        }
                         impl ::canadensis_encoding::Message for Linear {}
                         impl Linear {}
                         impl ::canadensis_encoding::Serialize for Linear {
@@ -264,10 +309,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 128 bits
 pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear,
 }
+                        impl ::core::default::Default for LinearTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for LinearTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for LinearTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 23;
+                        }
                         impl ::canadensis_encoding::Message for LinearTs {}
                         impl LinearTs {}
                         impl ::canadensis_encoding::Serialize for LinearTs {
@@ -324,10 +380,21 @@ pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear
                         /// size 32 bits
                         pub voltage: crate::uavcan::si::unit::voltage::scalar_1_0::Scalar,
                     }
+                    impl ::core::default::Default for Power {
+                        fn default() -> Self {
+                            Self {
+                                current: ::core::default::Default::default(),
+                                voltage: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Power {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Power {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                    }
                     impl ::canadensis_encoding::Message for Power {}
                     impl Power {}
                     impl ::canadensis_encoding::Serialize for Power {
@@ -375,10 +442,21 @@ pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear
                         /// size 64 bits
                         pub value: crate::reg::udral::physics::electricity::power_0_1::Power,
                     }
+                    impl ::core::default::Default for PowerTs {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                value: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for PowerTs {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for PowerTs {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 15;
+                    }
                     impl ::canadensis_encoding::Message for PowerTs {}
                     impl PowerTs {}
                     impl ::canadensis_encoding::Serialize for PowerTs {
@@ -447,10 +525,22 @@ pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear
                         /// size 32 bits
                         pub full_energy: crate::uavcan::si::unit::energy::scalar_1_0::Scalar,
                     }
+                    impl ::core::default::Default for Source {
+                        fn default() -> Self {
+                            Self {
+                                power: ::core::default::Default::default(),
+                                energy: ::core::default::Default::default(),
+                                full_energy: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Source {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Source {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 16;
+                    }
                     impl ::canadensis_encoding::Message for Source {}
                     impl Source {}
                     impl ::canadensis_encoding::Serialize for Source {
@@ -499,10 +589,21 @@ pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear
                         /// size 128 bits
                         pub value: crate::reg::udral::physics::electricity::source_0_1::Source,
                     }
+                    impl ::core::default::Default for SourceTs {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                value: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for SourceTs {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for SourceTs {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 23;
+                    }
                     impl ::canadensis_encoding::Message for SourceTs {}
                     impl SourceTs {}
                     impl ::canadensis_encoding::Serialize for SourceTs {
@@ -551,10 +652,20 @@ pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear
                             pub value:
                                 crate::uavcan::si::unit::length::wide_vector3_1_0::WideVector3,
                         }
+                        impl ::core::default::Default for Point {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Point {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Point {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 24;
+                        }
                         impl ::canadensis_encoding::Message for Point {}
                         impl Point {}
                         impl ::canadensis_encoding::Serialize for Point {
@@ -610,10 +721,21 @@ pub value: crate::reg::udral::physics::dynamics::translation::linear_0_1::Linear
                             /// size 96 bits
                             pub velocity: crate::uavcan::si::unit::velocity::vector3_1_0::Vector3,
                         }
+                        impl ::core::default::Default for PointState {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    velocity: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointState {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointState {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 36;
+                        }
                         impl ::canadensis_encoding::Message for PointState {}
                         impl PointState {}
                         impl ::canadensis_encoding::Serialize for PointState {
@@ -666,10 +788,21 @@ pub position: crate::reg::udral::physics::kinematics::cartesian::point_var_0_1::
 /// size 192 bits
 pub velocity: crate::reg::udral::physics::kinematics::translation::velocity3_var_0_2::Velocity3Var,
 }
+                        impl ::core::default::Default for PointStateVar {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    velocity: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointStateVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointStateVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 60;
+                        }
                         impl ::canadensis_encoding::Message for PointStateVar {}
                         impl PointStateVar {}
                         impl ::canadensis_encoding::Serialize for PointStateVar {
@@ -719,10 +852,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 480 bits
 pub value: crate::reg::udral::physics::kinematics::cartesian::point_state_var_0_1::PointStateVar,
 }
+                        impl ::core::default::Default for PointStateVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointStateVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointStateVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 67;
+                        }
                         impl ::canadensis_encoding::Message for PointStateVarTs {}
                         impl PointStateVarTs {}
                         impl ::canadensis_encoding::Serialize for PointStateVarTs {
@@ -777,10 +921,23 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::point_state_var_0_
                             /// size 96 bits
                             pub covariance_urt: [::half::f16; 6],
                         }
+                        impl ::core::default::Default for PointVar {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 36;
+                        }
                         impl ::canadensis_encoding::Message for PointVar {}
                         impl PointVar {}
                         impl ::canadensis_encoding::Serialize for PointVar {
@@ -834,10 +991,21 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::point_state_var_0_
                             pub orientation:
                                 crate::uavcan::si::unit::angle::quaternion_1_0::Quaternion,
                         }
+                        impl ::core::default::Default for Pose {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    orientation: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Pose {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Pose {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 40;
+                        }
                         impl ::canadensis_encoding::Message for Pose {}
                         impl Pose {}
                         impl ::canadensis_encoding::Serialize for Pose {
@@ -894,10 +1062,23 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::point_state_var_0_
                             /// size 336 bits
                             pub covariance_urt: [::half::f16; 21],
                         }
+                        impl ::core::default::Default for PoseVar {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PoseVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PoseVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 82;
+                        }
                         impl ::canadensis_encoding::Message for PoseVar {}
                         impl PoseVar {}
                         impl ::canadensis_encoding::Serialize for PoseVar {
@@ -947,10 +1128,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 656 bits
 pub value: crate::reg::udral::physics::kinematics::cartesian::pose_var_0_1::PoseVar,
 }
+                        impl ::core::default::Default for PoseVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PoseVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PoseVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 89;
+                        }
                         impl ::canadensis_encoding::Message for PoseVarTs {}
                         impl PoseVarTs {}
                         impl ::canadensis_encoding::Serialize for PoseVarTs {
@@ -1006,10 +1198,21 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::pose_var_0_1::Pose
                             pub twist:
                                 crate::reg::udral::physics::kinematics::cartesian::twist_0_1::Twist,
                         }
+                        impl ::core::default::Default for State {
+                            fn default() -> Self {
+                                Self {
+                                    pose: ::core::default::Default::default(),
+                                    twist: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for State {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for State {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 64;
+                        }
                         impl ::canadensis_encoding::Message for State {}
                         impl State {}
                         impl ::canadensis_encoding::Serialize for State {
@@ -1065,10 +1268,21 @@ pub pose: crate::reg::udral::physics::kinematics::cartesian::pose_var_0_1::PoseV
 /// size 528 bits
 pub twist: crate::reg::udral::physics::kinematics::cartesian::twist_var_0_1::TwistVar,
 }
+                        impl ::core::default::Default for StateVar {
+                            fn default() -> Self {
+                                Self {
+                                    pose: ::core::default::Default::default(),
+                                    twist: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for StateVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for StateVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 148;
+                        }
                         impl ::canadensis_encoding::Message for StateVar {}
                         impl StateVar {}
                         impl ::canadensis_encoding::Serialize for StateVar {
@@ -1118,10 +1332,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 1184 bits
 pub value: crate::reg::udral::physics::kinematics::cartesian::state_var_0_1::StateVar,
 }
+                        impl ::core::default::Default for StateVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for StateVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for StateVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 155;
+                        }
                         impl ::canadensis_encoding::Message for StateVarTs {}
                         impl StateVarTs {}
                         impl ::canadensis_encoding::Serialize for StateVarTs {
@@ -1181,10 +1406,21 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::state_var_0_1::Sta
                             pub angular:
                                 crate::uavcan::si::unit::angular_velocity::vector3_1_0::Vector3,
                         }
+                        impl ::core::default::Default for Twist {
+                            fn default() -> Self {
+                                Self {
+                                    linear: ::core::default::Default::default(),
+                                    angular: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Twist {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Twist {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 24;
+                        }
                         impl ::canadensis_encoding::Message for Twist {}
                         impl Twist {}
                         impl ::canadensis_encoding::Serialize for Twist {
@@ -1241,10 +1477,23 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::state_var_0_1::Sta
                             /// size 336 bits
                             pub covariance_urt: [::half::f16; 21],
                         }
+                        impl ::core::default::Default for TwistVar {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for TwistVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for TwistVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 66;
+                        }
                         impl ::canadensis_encoding::Message for TwistVar {}
                         impl TwistVar {}
                         impl ::canadensis_encoding::Serialize for TwistVar {
@@ -1294,10 +1543,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 528 bits
 pub value: crate::reg::udral::physics::kinematics::cartesian::twist_var_0_1::TwistVar,
 }
+                        impl ::core::default::Default for TwistVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for TwistVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for TwistVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 73;
+                        }
                         impl ::canadensis_encoding::Message for TwistVarTs {}
                         impl TwistVarTs {}
                         impl ::canadensis_encoding::Serialize for TwistVarTs {
@@ -1366,10 +1626,22 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::twist_var_0_1::Twi
                             pub altitude:
                                 crate::uavcan::si::unit::length::wide_scalar_1_0::WideScalar,
                         }
+                        impl ::core::default::Default for Point {
+                            fn default() -> Self {
+                                Self {
+                                    latitude: ::core::default::Default::default(),
+                                    longitude: ::core::default::Default::default(),
+                                    altitude: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Point {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Point {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 24;
+                        }
                         impl ::canadensis_encoding::Message for Point {}
                         impl Point {}
                         impl ::canadensis_encoding::Serialize for Point {
@@ -1427,10 +1699,21 @@ pub value: crate::reg::udral::physics::kinematics::cartesian::twist_var_0_1::Twi
                             /// size 96 bits
                             pub velocity: crate::uavcan::si::unit::velocity::vector3_1_0::Vector3,
                         }
+                        impl ::core::default::Default for PointState {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    velocity: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointState {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointState {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 36;
+                        }
                         impl ::canadensis_encoding::Message for PointState {}
                         impl PointState {}
                         impl ::canadensis_encoding::Serialize for PointState {
@@ -1483,10 +1766,21 @@ pub position: crate::reg::udral::physics::kinematics::geodetic::point_var_0_1::P
 /// size 192 bits
 pub velocity: crate::reg::udral::physics::kinematics::translation::velocity3_var_0_2::Velocity3Var,
 }
+                        impl ::core::default::Default for PointStateVar {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    velocity: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointStateVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointStateVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 60;
+                        }
                         impl ::canadensis_encoding::Message for PointStateVar {}
                         impl PointStateVar {}
                         impl ::canadensis_encoding::Serialize for PointStateVar {
@@ -1536,10 +1830,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 480 bits
 pub value: crate::reg::udral::physics::kinematics::geodetic::point_state_var_0_1::PointStateVar,
 }
+                        impl ::core::default::Default for PointStateVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointStateVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointStateVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 67;
+                        }
                         impl ::canadensis_encoding::Message for PointStateVarTs {}
                         impl PointStateVarTs {}
                         impl ::canadensis_encoding::Serialize for PointStateVarTs {
@@ -1594,10 +1899,23 @@ pub value: crate::reg::udral::physics::kinematics::geodetic::point_state_var_0_1
                             /// size 96 bits
                             pub covariance_urt: [::half::f16; 6],
                         }
+                        impl ::core::default::Default for PointVar {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PointVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PointVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 36;
+                        }
                         impl ::canadensis_encoding::Message for PointVar {}
                         impl PointVar {}
                         impl ::canadensis_encoding::Serialize for PointVar {
@@ -1655,10 +1973,21 @@ pub value: crate::reg::udral::physics::kinematics::geodetic::point_state_var_0_1
                             pub orientation:
                                 crate::uavcan::si::unit::angle::quaternion_1_0::Quaternion,
                         }
+                        impl ::core::default::Default for Pose {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    orientation: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Pose {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Pose {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 40;
+                        }
                         impl ::canadensis_encoding::Message for Pose {}
                         impl Pose {}
                         impl ::canadensis_encoding::Serialize for Pose {
@@ -1715,10 +2044,23 @@ pub value: crate::reg::udral::physics::kinematics::geodetic::point_state_var_0_1
                             /// size 336 bits
                             pub covariance_urt: [::half::f16; 21],
                         }
+                        impl ::core::default::Default for PoseVar {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PoseVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PoseVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 82;
+                        }
                         impl ::canadensis_encoding::Message for PoseVar {}
                         impl PoseVar {}
                         impl ::canadensis_encoding::Serialize for PoseVar {
@@ -1776,10 +2118,21 @@ pub value: crate::reg::udral::physics::kinematics::geodetic::point_state_var_0_1
                             pub twist:
                                 crate::reg::udral::physics::kinematics::cartesian::twist_0_1::Twist,
                         }
+                        impl ::core::default::Default for State {
+                            fn default() -> Self {
+                                Self {
+                                    pose: ::core::default::Default::default(),
+                                    twist: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for State {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for State {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 64;
+                        }
                         impl ::canadensis_encoding::Message for State {}
                         impl State {}
                         impl ::canadensis_encoding::Serialize for State {
@@ -1835,10 +2188,21 @@ pub pose: crate::reg::udral::physics::kinematics::geodetic::pose_var_0_1::PoseVa
 /// size 528 bits
 pub twist: crate::reg::udral::physics::kinematics::cartesian::twist_var_0_1::TwistVar,
 }
+                        impl ::core::default::Default for StateVar {
+                            fn default() -> Self {
+                                Self {
+                                    pose: ::core::default::Default::default(),
+                                    twist: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for StateVar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for StateVar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 148;
+                        }
                         impl ::canadensis_encoding::Message for StateVar {}
                         impl StateVar {}
                         impl ::canadensis_encoding::Serialize for StateVar {
@@ -1888,10 +2252,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 1184 bits
 pub value: crate::reg::udral::physics::kinematics::geodetic::state_var_0_1::StateVar,
 }
+                        impl ::core::default::Default for StateVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for StateVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for StateVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 155;
+                        }
                         impl ::canadensis_encoding::Message for StateVarTs {}
                         impl StateVarTs {}
                         impl ::canadensis_encoding::Serialize for StateVarTs {
@@ -1953,10 +2328,22 @@ pub value: crate::reg::udral::physics::kinematics::geodetic::state_var_0_1::Stat
                             pub angular_acceleration:
                                 crate::uavcan::si::unit::angular_acceleration::scalar_1_0::Scalar,
                         }
+                        impl ::core::default::Default for Planar {
+                            fn default() -> Self {
+                                Self {
+                                    angular_position: ::core::default::Default::default(),
+                                    angular_velocity: ::core::default::Default::default(),
+                                    angular_acceleration: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Planar {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Planar {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                        }
                         impl ::canadensis_encoding::Message for Planar {}
                         impl Planar {}
                         impl ::canadensis_encoding::Serialize for Planar {
@@ -2010,10 +2397,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 96 bits
 pub value: crate::reg::udral::physics::kinematics::rotation::planar_0_1::Planar,
 }
+                        impl ::core::default::Default for PlanarTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for PlanarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for PlanarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                        }
                         impl ::canadensis_encoding::Message for PlanarTs {}
                         impl PlanarTs {}
                         impl ::canadensis_encoding::Serialize for PlanarTs {
@@ -2073,10 +2471,22 @@ pub value: crate::reg::udral::physics::kinematics::rotation::planar_0_1::Planar,
                             pub acceleration:
                                 crate::uavcan::si::unit::acceleration::scalar_1_0::Scalar,
                         }
+                        impl ::core::default::Default for Linear {
+                            fn default() -> Self {
+                                Self {
+                                    position: ::core::default::Default::default(),
+                                    velocity: ::core::default::Default::default(),
+                                    acceleration: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Linear {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Linear {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                        }
                         impl ::canadensis_encoding::Message for Linear {}
                         impl Linear {}
                         impl ::canadensis_encoding::Serialize for Linear {
@@ -2127,10 +2537,21 @@ pub timestamp: crate::uavcan::time::synchronized_timestamp_1_0::SynchronizedTime
 /// size 96 bits
 pub value: crate::reg::udral::physics::kinematics::translation::linear_0_1::Linear,
 }
+                        impl ::core::default::Default for LinearTs {
+                            fn default() -> Self {
+                                Self {
+                                    timestamp: ::core::default::Default::default(),
+                                    value: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for LinearTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for LinearTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                        }
                         impl ::canadensis_encoding::Message for LinearTs {}
                         impl LinearTs {}
                         impl ::canadensis_encoding::Serialize for LinearTs {
@@ -2197,10 +2618,24 @@ pub velocity_error_variance: ::half::f16,
 /// size 16 bits
 pub acceleration_error_variance: ::half::f16,
 }
+                        impl ::core::default::Default for LinearVarTs {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    position_error_variance: ::core::default::Default::default(),
+                                    velocity_error_variance: ::core::default::Default::default(),
+                                    acceleration_error_variance: ::core::default::Default::default(
+                                    ),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for LinearVarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for LinearVarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 25;
+                        }
                         impl ::canadensis_encoding::Message for LinearVarTs {}
                         impl LinearVarTs {}
                         impl ::canadensis_encoding::Serialize for LinearVarTs {
@@ -2257,10 +2692,21 @@ pub acceleration_error_variance: ::half::f16,
                             /// size 16 bits
                             pub error_variance: ::half::f16,
                         }
+                        impl ::core::default::Default for Velocity1VarTs {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    error_variance: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Velocity1VarTs {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Velocity1VarTs {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 13;
+                        }
                         impl ::canadensis_encoding::Message for Velocity1VarTs {}
                         impl Velocity1VarTs {}
                         impl ::canadensis_encoding::Serialize for Velocity1VarTs {
@@ -2300,7 +2746,9 @@ pub acceleration_error_variance: ::half::f16,
                             not(doctest),
                             doc = " Linear velocity with covariance.\n Observe that this is a structural subtype of uavcan.si.unit.velocity.Scalar.1.0."
                         )]
-                        #[deprecated]
+                        #[deprecated(
+                            note = "Replaced by reg.udral.physics.kinematics.translation.Velocity3Var.0.2"
+                        )]
                         pub struct Velocity3Var {
                             ///
                             /// `uavcan.si.sample.velocity.Vector3.1.0`
@@ -2319,10 +2767,23 @@ pub acceleration_error_variance: ::half::f16,
                             /// size 96 bits
                             pub covariance_urt: [::half::f16; 6],
                         }
+                        impl ::core::default::Default for Velocity3Var {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Velocity3Var {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Velocity3Var {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 31;
+                        }
                         impl ::canadensis_encoding::Message for Velocity3Var {}
                         impl Velocity3Var {}
                         impl ::canadensis_encoding::Serialize for Velocity3Var {
@@ -2391,10 +2852,23 @@ pub acceleration_error_variance: ::half::f16,
                             /// size 96 bits
                             pub covariance_urt: [::half::f16; 6],
                         }
+                        impl ::core::default::Default for Velocity3Var {
+                            fn default() -> Self {
+                                Self {
+                                    value: ::core::default::Default::default(),
+                                    covariance_urt: ::core::array::from_fn(|_| {
+                                        ::core::default::Default::default()
+                                    }),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Velocity3Var {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for Velocity3Var {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 24;
+                        }
                         impl ::canadensis_encoding::Message for Velocity3Var {}
                         impl Velocity3Var {}
                         impl ::canadensis_encoding::Serialize for Velocity3Var {
@@ -2460,10 +2934,22 @@ pub acceleration_error_variance: ::half::f16,
                         /// size 5 bits
                         pub blue: u8,
                     }
+                    impl ::core::default::Default for HighColor {
+                        fn default() -> Self {
+                            Self {
+                                red: ::core::default::Default::default(),
+                                green: ::core::default::Default::default(),
+                                blue: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for HighColor {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for HighColor {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                    }
                     impl ::canadensis_encoding::Message for HighColor {}
                     impl HighColor {
                         pub const MAX_RED: u8 = 31;
@@ -2537,10 +3023,25 @@ pub acceleration_error_variance: ::half::f16,
                         /// size 48 bits
                         pub covariance_urt: [::half::f16; 3],
                     }
+                    impl ::core::default::Default for PressureTempVarTs {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                pressure: ::core::default::Default::default(),
+                                temperature: ::core::default::Default::default(),
+                                covariance_urt: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for PressureTempVarTs {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for PressureTempVarTs {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 21;
+                    }
                     impl ::canadensis_encoding::Message for PressureTempVarTs {}
                     impl PressureTempVarTs {}
                     impl ::canadensis_encoding::Serialize for PressureTempVarTs {
@@ -2599,10 +3100,20 @@ pub acceleration_error_variance: ::half::f16,
                         /// size 64 bits
                         pub tai64n: i64,
                     }
+                    impl ::core::default::Default for TAI64 {
+                        fn default() -> Self {
+                            Self {
+                                tai64n: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for TAI64 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for TAI64 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                    }
                     impl ::canadensis_encoding::Message for TAI64 {}
                     impl TAI64 {}
                     impl ::canadensis_encoding::Serialize for TAI64 {
@@ -2654,10 +3165,21 @@ pub acceleration_error_variance: ::half::f16,
                         /// size 32 bits
                         pub error_variance: f32,
                     }
+                    impl ::core::default::Default for TAI64Var {
+                        fn default() -> Self {
+                            Self {
+                                value: ::core::default::Default::default(),
+                                error_variance: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for TAI64Var {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for TAI64Var {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for TAI64Var {}
                     impl TAI64Var {}
                     impl ::canadensis_encoding::Serialize for TAI64Var {
@@ -2705,10 +3227,21 @@ pub acceleration_error_variance: ::half::f16,
                         /// size 96 bits
                         pub value: crate::reg::udral::physics::time::tai64_var_0_1::TAI64Var,
                     }
+                    impl ::core::default::Default for TAI64VarTs {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                value: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for TAI64VarTs {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for TAI64VarTs {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for TAI64VarTs {}
                     impl TAI64VarTs {}
                     impl ::canadensis_encoding::Serialize for TAI64VarTs {
@@ -2751,10 +3284,18 @@ pub acceleration_error_variance: ::half::f16,
                         #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                         #[repr(C, packed)]
                         pub struct _0 {}
+                        impl ::core::default::Default for _0 {
+                            fn default() -> Self {
+                                Self {}
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for _0 {
                             /// This type is delimited with an extent of 0 bytes.
                             const EXTENT_BYTES: Option<u32> = Some(0);
                         }
+                        impl ::canadensis_encoding::SerializedSize for _0 {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                        }
                         impl ::canadensis_encoding::Message for _0 {}
                         impl _0 {
                             #[cfg_attr(
@@ -2902,10 +3443,29 @@ pub acceleration_error_variance: ::half::f16,
                             /// size 1 bits
                             pub other: bool,
                         }
+                        impl ::core::default::Default for FaultFlags {
+                            fn default() -> Self {
+                                Self {
+                                    overload: ::core::default::Default::default(),
+                                    voltage: ::core::default::Default::default(),
+                                    motor_temperature: ::core::default::Default::default(),
+                                    controller_temperature: ::core::default::Default::default(),
+                                    velocity: ::core::default::Default::default(),
+                                    mechanical: ::core::default::Default::default(),
+                                    vibration: ::core::default::Default::default(),
+                                    configuration: ::core::default::Default::default(),
+                                    control_mode: ::core::default::Default::default(),
+                                    other: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for FaultFlags {
                             /// This type is sealed.
                             const EXTENT_BYTES: Option<u32> = None;
                         }
+                        impl ::canadensis_encoding::SerializedSize for FaultFlags {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                        }
                         impl ::canadensis_encoding::Message for FaultFlags {}
                         impl FaultFlags {}
                         impl ::canadensis_encoding::Serialize for FaultFlags {
@@ -2986,10 +3546,21 @@ pub acceleration_error_variance: ::half::f16,
                             /// size 8 bits
                             pub demand_factor_pct: i8,
                         }
+                        impl ::core::default::Default for Feedback {
+                            fn default() -> Self {
+                                Self {
+                                    heartbeat: ::core::default::Default::default(),
+                                    demand_factor_pct: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Feedback {
                             /// This type is delimited with an extent of 63 bytes.
                             const EXTENT_BYTES: Option<u32> = Some(63);
                         }
+                        impl ::canadensis_encoding::SerializedSize for Feedback {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 3;
+                        }
                         impl ::canadensis_encoding::Message for Feedback {}
                         impl Feedback {}
                         impl ::canadensis_encoding::Serialize for Feedback {
@@ -3031,10 +3602,18 @@ pub acceleration_error_variance: ::half::f16,
                             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                             #[repr(C, packed)]
                             pub struct _0 {}
+                            impl ::core::default::Default for _0 {
+                                fn default() -> Self {
+                                    Self {}
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for _0 {
                                 /// This type is delimited with an extent of 0 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(0);
                             }
+                            impl ::canadensis_encoding::SerializedSize for _0 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                            }
                             impl ::canadensis_encoding::Message for _0 {}
                             impl _0 {
                                 #[cfg_attr(
@@ -3087,10 +3666,20 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 16 bits
                                 pub value: ::half::f16,
                             }
+                            impl ::core::default::Default for Scalar {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::default::Default::default(),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Scalar {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Scalar {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                            }
                             impl ::canadensis_encoding::Message for Scalar {}
                             impl Scalar {}
                             impl ::canadensis_encoding::Serialize for Scalar {
@@ -3138,10 +3727,22 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 32 bits
                                 pub value: [::half::f16; 2],
                             }
+                            impl ::core::default::Default for Vector2 {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::array::from_fn(|_| {
+                                            ::core::default::Default::default()
+                                        }),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Vector2 {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Vector2 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                            }
                             impl ::canadensis_encoding::Message for Vector2 {}
                             impl Vector2 {}
                             impl ::canadensis_encoding::Serialize for Vector2 {
@@ -3189,10 +3790,22 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 496 bits
                                 pub value: [::half::f16; 31],
                             }
+                            impl ::core::default::Default for Vector31 {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::array::from_fn(|_| {
+                                            ::core::default::Default::default()
+                                        }),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Vector31 {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Vector31 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 62;
+                            }
                             impl ::canadensis_encoding::Message for Vector31 {}
                             impl Vector31 {}
                             impl ::canadensis_encoding::Serialize for Vector31 {
@@ -3240,10 +3853,22 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 48 bits
                                 pub value: [::half::f16; 3],
                             }
+                            impl ::core::default::Default for Vector3 {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::array::from_fn(|_| {
+                                            ::core::default::Default::default()
+                                        }),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Vector3 {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Vector3 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 6;
+                            }
                             impl ::canadensis_encoding::Message for Vector3 {}
                             impl Vector3 {}
                             impl ::canadensis_encoding::Serialize for Vector3 {
@@ -3291,10 +3916,22 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 64 bits
                                 pub value: [::half::f16; 4],
                             }
+                            impl ::core::default::Default for Vector4 {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::array::from_fn(|_| {
+                                            ::core::default::Default::default()
+                                        }),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Vector4 {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Vector4 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                            }
                             impl ::canadensis_encoding::Message for Vector4 {}
                             impl Vector4 {}
                             impl ::canadensis_encoding::Serialize for Vector4 {
@@ -3342,10 +3979,22 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 96 bits
                                 pub value: [::half::f16; 6],
                             }
+                            impl ::core::default::Default for Vector6 {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::array::from_fn(|_| {
+                                            ::core::default::Default::default()
+                                        }),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Vector6 {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Vector6 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                            }
                             impl ::canadensis_encoding::Message for Vector6 {}
                             impl Vector6 {}
                             impl ::canadensis_encoding::Serialize for Vector6 {
@@ -3393,10 +4042,22 @@ pub acceleration_error_variance: ::half::f16,
                                 /// size 128 bits
                                 pub value: [::half::f16; 8],
                             }
+                            impl ::core::default::Default for Vector8 {
+                                fn default() -> Self {
+                                    Self {
+                                        value: ::core::array::from_fn(|_| {
+                                            ::core::default::Default::default()
+                                        }),
+                                    }
+                                }
+                            }
                             impl ::canadensis_encoding::DataType for Vector8 {
                                 /// This type is delimited with an extent of 512 bytes.
                                 const EXTENT_BYTES: Option<u32> = Some(512);
                             }
+                            impl ::canadensis_encoding::SerializedSize for Vector8 {
+                                const MAX_SERIALIZED_SIZE_BYTES: usize = 16;
+                            }
                             impl ::canadensis_encoding::Message for Vector8 {}
                             impl Vector8 {}
                             impl ::canadensis_encoding::Serialize for Vector8 {
@@ -3468,10 +4129,23 @@ pub error_count: u32,
 /// size 16 bits
 pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::FaultFlags,
 }
+                        impl ::core::default::Default for Status {
+                            fn default() -> Self {
+                                Self {
+                                    motor_temperature: ::core::default::Default::default(),
+                                    controller_temperature: ::core::default::Default::default(),
+                                    error_count: ::core::default::Default::default(),
+                                    fault_flags: ::core::default::Default::default(),
+                                }
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for Status {
                             /// This type is delimited with an extent of 63 bytes.
                             const EXTENT_BYTES: Option<u32> = Some(63);
                         }
+                        impl ::canadensis_encoding::SerializedSize for Status {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 14;
+                        }
                         impl ::canadensis_encoding::Message for Status {}
                         impl Status {}
                         impl ::canadensis_encoding::Serialize for Status {
@@ -3518,10 +4192,18 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                         #[repr(C, packed)]
                         pub struct _0 {}
+                        impl ::core::default::Default for _0 {
+                            fn default() -> Self {
+                                Self {}
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for _0 {
                             /// This type is delimited with an extent of 0 bytes.
                             const EXTENT_BYTES: Option<u32> = Some(0);
                         }
+                        impl ::canadensis_encoding::SerializedSize for _0 {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                        }
                         impl ::canadensis_encoding::Message for _0 {}
                         impl _0 {}
                         impl ::canadensis_encoding::Serialize for _0 {
@@ -3564,10 +4246,18 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                         #[repr(C, packed)]
                         pub struct _0 {}
+                        impl ::core::default::Default for _0 {
+                            fn default() -> Self {
+                                Self {}
+                            }
+                        }
                         impl ::canadensis_encoding::DataType for _0 {
                             /// This type is delimited with an extent of 0 bytes.
                             const EXTENT_BYTES: Option<u32> = Some(0);
                         }
+                        impl ::canadensis_encoding::SerializedSize for _0 {
+                            const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                        }
                         impl ::canadensis_encoding::Message for _0 {}
                         impl _0 {}
                         impl ::canadensis_encoding::Serialize for _0 {
@@ -3611,10 +4301,18 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                     #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                     #[repr(C, packed)]
                     pub struct _0 {}
+                    impl ::core::default::Default for _0 {
+                        fn default() -> Self {
+                            Self {}
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for _0 {
                         /// This type is delimited with an extent of 0 bytes.
                         const EXTENT_BYTES: Option<u32> = Some(0);
                     }
+                    impl ::canadensis_encoding::SerializedSize for _0 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                    }
                     impl ::canadensis_encoding::Message for _0 {}
                     impl _0 {}
                     impl ::canadensis_encoding::Serialize for _0 {
@@ -3659,10 +4357,20 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         /// size 8 bits
                         pub value: u8,
                     }
+                    impl ::core::default::Default for Error {
+                        fn default() -> Self {
+                            Self {
+                                value: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Error {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Error {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+                    }
                     impl ::canadensis_encoding::Message for Error {}
                     impl Error {
                         #[cfg_attr(not(doctest), doc = " Normal operation.")]
@@ -3930,10 +4638,38 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         /// size ranges from 0 to 512 bits
                         pub name: ::heapless::Vec<u8, 64>,
                     }
+                    impl ::core::default::Default for Parameters {
+                        fn default() -> Self {
+                            Self {
+                                unique_id: ::core::default::Default::default(),
+                                mass: ::core::default::Default::default(),
+                                design_capacity: ::core::default::Default::default(),
+                                design_cell_voltage_min_max: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                                discharge_current: ::core::default::Default::default(),
+                                discharge_current_burst: ::core::default::Default::default(),
+                                charge_current: ::core::default::Default::default(),
+                                charge_current_fast: ::core::default::Default::default(),
+                                charge_termination_threshold: ::core::default::Default::default(),
+                                charge_voltage: ::core::default::Default::default(),
+                                cycle_count: ::core::default::Default::default(),
+                                series_cell_count: ::core::default::Default::default(),
+                                state_of_health_pct: ::core::default::Default::default(),
+                                technology: ::core::default::Default::default(),
+                                nominal_voltage: ::core::default::Default::default(),
+                                unix_manufacture_time: ::core::default::Default::default(),
+                                name: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Parameters {
                         /// This type is delimited with an extent of 300 bytes.
                         const EXTENT_BYTES: Option<u32> = Some(300);
                     }
+                    impl ::canadensis_encoding::SerializedSize for Parameters {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 128;
+                    }
                     impl ::canadensis_encoding::Message for Parameters {}
                     impl Parameters {}
                     impl ::canadensis_encoding::Serialize for Parameters {
@@ -4020,9 +4756,8 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                                     let length = cursor.read_u8() as _;
                                     if length <= 64 {
                                         let mut elements = ::heapless::Vec::new();
-                                        for _ in 0..length {
-                                            let _ = elements.push(cursor.read_u8() as _);
-                                        }
+                                        let _ = elements.resize(length, 0);
+                                        cursor.read_bytes(&mut elements);
                                         elements
                                     } else {
                                         return Err(
@@ -4093,10 +4828,26 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         /// size ranges from 0 to 4080 bits
                         pub cell_voltages: ::heapless::Vec<::half::f16, 255>,
                     }
+                    impl ::core::default::Default for Status {
+                        fn default() -> Self {
+                            Self {
+                                heartbeat: ::core::default::Default::default(),
+                                temperature_min_max: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                                available_charge: ::core::default::Default::default(),
+                                error: ::core::default::Default::default(),
+                                cell_voltages: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Status {
                         /// This type is delimited with an extent of 600 bytes.
                         const EXTENT_BYTES: Option<u32> = Some(600);
                     }
+                    impl ::canadensis_encoding::SerializedSize for Status {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 526;
+                    }
                     impl ::canadensis_encoding::Message for Status {}
                     impl Status {
                         pub const MAX_CELLS: u8 = 255;
@@ -4174,10 +4925,20 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         /// size 8 bits
                         pub value: u8,
                     }
-                    impl ::canadensis_encoding::DataType for Technology {
-                        /// This type is sealed.
+                    impl ::core::default::Default for Technology {
+                        fn default() -> Self {
+                            Self {
+                                value: ::core::default::Default::default(),
+                            }
+                        }
+                    }
+                    impl ::canadensis_encoding::DataType for Technology {
+                        /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Technology {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+                    }
                     impl ::canadensis_encoding::Message for Technology {}
                     impl Technology {
                         #[cfg_attr(
@@ -4305,10 +5066,21 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         /// size 8 bits
                         pub health: crate::uavcan::node::health_1_0::Health,
                     }
+                    impl ::core::default::Default for Heartbeat {
+                        fn default() -> Self {
+                            Self {
+                                readiness: ::core::default::Default::default(),
+                                health: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Heartbeat {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Heartbeat {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                    }
                     impl ::canadensis_encoding::Message for Heartbeat {}
                     impl Heartbeat {
                         #[cfg_attr(
@@ -4357,10 +5129,20 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         /// size 2 bits
                         pub value: u8,
                     }
+                    impl ::core::default::Default for Readiness {
+                        fn default() -> Self {
+                            Self {
+                                value: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Readiness {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Readiness {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+                    }
                     impl ::canadensis_encoding::Message for Readiness {}
                     impl Readiness {
                         #[cfg_attr(
@@ -4447,10 +5229,22 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
                         pub sensor_temperature:
                             crate::uavcan::si::unit::temperature::scalar_1_0::Scalar,
                     }
+                    impl ::core::default::Default for Status {
+                        fn default() -> Self {
+                            Self {
+                                data_validity_period: ::core::default::Default::default(),
+                                error_count: ::core::default::Default::default(),
+                                sensor_temperature: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Status {
                         /// This type is delimited with an extent of 63 bytes.
                         const EXTENT_BYTES: Option<u32> = Some(63);
                     }
+                    impl ::canadensis_encoding::SerializedSize for Status {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Status {}
                     impl Status {
                         #[cfg_attr(not(doctest), doc = " [second]")]
@@ -4487,14 +5281,14 @@ pub fault_flags: crate::reg::udral::service::actuator::common::fault_flags_0_1::
     }
 }
 #[allow(unused_variables, unused_braces, unused_parens)]
-#[allow(clippy::identity_op)]
+#[allow(clippy::identity_op, clippy::derivable_impls)]
 pub mod uavcan {
     pub mod diagnostic {
         #[allow(deprecated)]
         #[cfg_attr(not(test), deprecated)]
         pub mod record_1_0 {
             #[cfg_attr(not(doctest), doc = "The fixed subject ID for this message type")]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.diagnostic.Record.1.1")]
             pub const SUBJECT: ::canadensis_core::SubjectId =
                 ::canadensis_core::SubjectId::from_truncating(8184);
 
@@ -4506,7 +5300,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " Generic human-readable text message for logging and displaying purposes.\n Generally, it should be published at the lowest priority level."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.diagnostic.Record.1.1")]
             pub struct Record {
                 #[cfg_attr(
                     not(doctest),
@@ -4536,10 +5330,22 @@ pub mod uavcan {
                 /// size ranges from 0 to 896 bits
                 pub text: ::heapless::Vec<u8, 112>,
             }
+            impl ::core::default::Default for Record {
+                fn default() -> Self {
+                    Self {
+                        timestamp: ::core::default::Default::default(),
+                        severity: ::core::default::Default::default(),
+                        text: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Record {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for Record {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 121;
+            }
             impl ::canadensis_encoding::Message for Record {}
             impl Record {}
             impl ::canadensis_encoding::Serialize for Record {
@@ -4567,9 +5373,8 @@ pub mod uavcan {
                             let length = cursor.read_u8() as _;
                             if length <= 112 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -4621,10 +5426,22 @@ pub mod uavcan {
                 /// size ranges from 0 to 2040 bits
                 pub text: ::heapless::Vec<u8, 255>,
             }
+            impl ::core::default::Default for Record {
+                fn default() -> Self {
+                    Self {
+                        timestamp: ::core::default::Default::default(),
+                        severity: ::core::default::Default::default(),
+                        text: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Record {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for Record {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 264;
+            }
             impl ::canadensis_encoding::Message for Record {}
             impl Record {}
             impl ::canadensis_encoding::Serialize for Record {
@@ -4652,9 +5469,8 @@ pub mod uavcan {
                             let length = cursor.read_u8() as _;
                             if length <= 255 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -4682,10 +5498,20 @@ pub mod uavcan {
                 /// size 3 bits
                 pub value: u8,
             }
+            impl ::core::default::Default for Severity {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Severity {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Severity {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Message for Severity {}
             impl Severity {
                 #[cfg_attr(
@@ -4771,10 +5597,20 @@ pub mod uavcan {
                 /// size 16 bits
                 pub value: u16,
             }
+            impl ::core::default::Default for Error {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Error {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Error {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Message for Error {}
             impl Error {
                 pub const OK: u16 = 0;
@@ -4824,7 +5660,7 @@ pub mod uavcan {
         #[cfg_attr(not(test), deprecated)]
         pub mod get_info_0_1 {
             #[cfg_attr(not(doctest), doc = "The fixed ID of this service")]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.GetInfo.0.2")]
             pub const SERVICE: ::canadensis_core::ServiceId =
                 ::canadensis_core::ServiceId::from_truncating(405);
 
@@ -4836,7 +5672,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " Information about a remote file system entry (file, directory, etc)."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.GetInfo.0.2")]
             pub struct GetInfoRequest {
                 ///
                 /// `uavcan.file.Path.1.0`
@@ -4845,15 +5681,25 @@ pub mod uavcan {
                 /// size ranges from 8 to 904 bits
                 pub path: crate::uavcan::file::path_1_0::Path,
             }
+            impl ::core::default::Default for GetInfoRequest {
+                fn default() -> Self {
+                    Self {
+                        path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetInfoRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for GetInfoRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 113;
+            }
             impl ::canadensis_encoding::Request for GetInfoRequest {}
             impl GetInfoRequest {}
             impl ::canadensis_encoding::Serialize for GetInfoRequest {
                 fn size_bits(&self) -> usize {
-                    (self.path).size_bits() + 0
+                    (self.path).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_composite(&self.path);
@@ -4876,7 +5722,7 @@ pub mod uavcan {
             ///
             /// Fixed size 13 bytes
             ///
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.GetInfo.0.2")]
             pub struct GetInfoResponse {
                 #[cfg_attr(not(doctest), doc = " Result of the operation.")]
                 ///
@@ -4944,10 +5790,26 @@ pub mod uavcan {
                 pub is_writeable: bool,
                 // 4 bits of padding
             }
+            impl ::core::default::Default for GetInfoResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                        size: ::core::default::Default::default(),
+                        unix_timestamp_of_last_modification: ::core::default::Default::default(),
+                        is_file_not_directory: ::core::default::Default::default(),
+                        is_link: ::core::default::Default::default(),
+                        is_readable: ::core::default::Default::default(),
+                        is_writeable: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetInfoResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for GetInfoResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 13;
+            }
             impl ::canadensis_encoding::Response for GetInfoResponse {}
             impl GetInfoResponse {}
             impl ::canadensis_encoding::Serialize for GetInfoResponse {
@@ -5005,15 +5867,25 @@ pub mod uavcan {
                 /// size ranges from 8 to 2048 bits
                 pub path: crate::uavcan::file::path_2_0::Path,
             }
+            impl ::core::default::Default for GetInfoRequest {
+                fn default() -> Self {
+                    Self {
+                        path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetInfoRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for GetInfoRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 256;
+            }
             impl ::canadensis_encoding::Request for GetInfoRequest {}
             impl GetInfoRequest {}
             impl ::canadensis_encoding::Serialize for GetInfoRequest {
                 fn size_bits(&self) -> usize {
-                    (self.path).size_bits() + 0
+                    (self.path).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_composite(&self.path);
@@ -5103,10 +5975,26 @@ pub mod uavcan {
                 pub is_writeable: bool,
                 // 4 bits of padding
             }
+            impl ::core::default::Default for GetInfoResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                        size: ::core::default::Default::default(),
+                        unix_timestamp_of_last_modification: ::core::default::Default::default(),
+                        is_file_not_directory: ::core::default::Default::default(),
+                        is_link: ::core::default::Default::default(),
+                        is_readable: ::core::default::Default::default(),
+                        is_writeable: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetInfoResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for GetInfoResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 13;
+            }
             impl ::canadensis_encoding::Response for GetInfoResponse {}
             impl GetInfoResponse {}
             impl ::canadensis_encoding::Serialize for GetInfoResponse {
@@ -5147,7 +6035,7 @@ pub mod uavcan {
         #[cfg_attr(not(test), deprecated)]
         pub mod list_0_1 {
             #[cfg_attr(not(doctest), doc = "The fixed ID of this service")]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.List.0.2")]
             pub const SERVICE: ::canadensis_core::ServiceId =
                 ::canadensis_core::ServiceId::from_truncating(406);
 
@@ -5159,7 +6047,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " This service can be used to list a remote directory, one entry per request.\n\n The client should query each entry independently, iterating 'entry_index' from 0 until the last entry.\n When the index reaches the number of elements in the directory, the server will report that there is\n no such entry by returning an empty name.\n\n The field entry_index shall be applied to an ordered list of directory entries (e.g. alphabetically ordered).\n The exact sorting criteria does not matter as long as it provides the same ordering for subsequent service calls.\n\n Observe that this listing operation is fundamentally non-atomic. The caller shall beware of possible race conditions\n and is responsible for handling them properly. Particularly, consider what happens if a new item is inserted into\n the directory between two subsequent calls: if the item happened to be inserted at the index that is lower than the\n index of the next request, the next returned item (or several, if more items were inserted) will repeat the ones\n that were listed earlier. The caller should handle that properly, either by ignoring the repeated items or by\n restarting the listing operation from the beginning (index 0)."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.List.0.2")]
             pub struct ListRequest {
                 ///
                 /// `saturated uint32`
@@ -5175,15 +6063,26 @@ pub mod uavcan {
                 /// size ranges from 8 to 904 bits
                 pub directory_path: crate::uavcan::file::path_1_0::Path,
             }
+            impl ::core::default::Default for ListRequest {
+                fn default() -> Self {
+                    Self {
+                        entry_index: ::core::default::Default::default(),
+                        directory_path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ListRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ListRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 121;
+            }
             impl ::canadensis_encoding::Request for ListRequest {}
             impl ListRequest {}
             impl ::canadensis_encoding::Serialize for ListRequest {
                 fn size_bits(&self) -> usize {
-                    32 + 32 + (self.directory_path).size_bits() + 0
+                    32 + 32 + (self.directory_path).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_aligned_u32(self.entry_index);
@@ -5212,7 +6111,7 @@ pub mod uavcan {
             ///
             /// Size ranges from 5 to 117 bytes
             ///
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.List.0.2")]
             pub struct ListResponse {
                 // 32 bits of padding
                 #[cfg_attr(
@@ -5226,15 +6125,25 @@ pub mod uavcan {
                 /// size ranges from 8 to 904 bits
                 pub entry_base_name: crate::uavcan::file::path_1_0::Path,
             }
+            impl ::core::default::Default for ListResponse {
+                fn default() -> Self {
+                    Self {
+                        entry_base_name: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ListResponse {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ListResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 117;
+            }
             impl ::canadensis_encoding::Response for ListResponse {}
             impl ListResponse {}
             impl ::canadensis_encoding::Serialize for ListResponse {
                 fn size_bits(&self) -> usize {
-                    32 + (self.entry_base_name).size_bits() + 0
+                    32 + (self.entry_base_name).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.skip_32();
@@ -5285,15 +6194,26 @@ pub mod uavcan {
                 /// size ranges from 8 to 2048 bits
                 pub directory_path: crate::uavcan::file::path_2_0::Path,
             }
+            impl ::core::default::Default for ListRequest {
+                fn default() -> Self {
+                    Self {
+                        entry_index: ::core::default::Default::default(),
+                        directory_path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ListRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ListRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 264;
+            }
             impl ::canadensis_encoding::Request for ListRequest {}
             impl ListRequest {}
             impl ::canadensis_encoding::Serialize for ListRequest {
                 fn size_bits(&self) -> usize {
-                    32 + 32 + (self.directory_path).size_bits() + 0
+                    32 + 32 + (self.directory_path).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_aligned_u32(self.entry_index);
@@ -5335,15 +6255,25 @@ pub mod uavcan {
                 /// size ranges from 8 to 2048 bits
                 pub entry_base_name: crate::uavcan::file::path_2_0::Path,
             }
+            impl ::core::default::Default for ListResponse {
+                fn default() -> Self {
+                    Self {
+                        entry_base_name: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ListResponse {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ListResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 260;
+            }
             impl ::canadensis_encoding::Response for ListResponse {}
             impl ListResponse {}
             impl ::canadensis_encoding::Serialize for ListResponse {
                 fn size_bits(&self) -> usize {
-                    32 + (self.entry_base_name).size_bits() + 0
+                    32 + (self.entry_base_name).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.skip_32();
@@ -5370,7 +6300,7 @@ pub mod uavcan {
         #[cfg_attr(not(test), deprecated)]
         pub mod modify_1_0 {
             #[cfg_attr(not(doctest), doc = "The fixed ID of this service")]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Modify.1.1")]
             pub const SERVICE: ::canadensis_core::ServiceId =
                 ::canadensis_core::ServiceId::from_truncating(407);
 
@@ -5382,7 +6312,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " Manipulate a remote file system entry. Applies to files, directories, and links alike.\n If the remote entry is a directory, all nested entries will be affected, too.\n\n The server should perform all operations atomically, unless atomicity is not supported by\n the underlying file system.\n\n Atomic copying can be effectively employed by remote nodes before reading or after writing\n the file to minimize the possibility of race conditions.\n For example, before reading a large file from the server, the cilent might opt to create\n a temporary copy of it first, then read the copy, and delete it upon completion. Likewise,\n a similar strategy can be employed for writing, where the file is first written at a\n temporary location, and then moved to its final destination. These approaches, however,\n may lead to creation of dangling temporary files if the client failed to dispose of them\n properly, so that risk should be taken into account.\n\n Move/Copy\n   Specify the source path and the destination path.\n   If the source does not exist, the operation will fail.\n   Set the preserve_source flag to copy rather than move.\n   If the destination exists and overwrite_destination is not set, the operation will fail.\n   If the target path includes non-existent directories, they will be created (like \"mkdir -p\").\n\n Touch\n   Specify the destination path and make the source path empty.\n   If the path exists (file/directory/link), its modification time will be updated.\n   If the path does not exist, an empty file will be created.\n   If the target path includes non-existent directories, they will be created (like \"mkdir -p\").\n   Flags are ignored.\n\n Remove\n   Specify the source path (file/directory/link) and make the destination path empty.\n   Fails if the path does not exist.\n   Flags are ignored."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Modify.1.1")]
             pub struct ModifyRequest {
                 #[cfg_attr(
                     not(doctest),
@@ -5418,15 +6348,32 @@ pub mod uavcan {
                 /// size ranges from 8 to 904 bits
                 pub destination: crate::uavcan::file::path_1_0::Path,
             }
+            impl ::core::default::Default for ModifyRequest {
+                fn default() -> Self {
+                    Self {
+                        preserve_source: ::core::default::Default::default(),
+                        overwrite_destination: ::core::default::Default::default(),
+                        source: ::core::default::Default::default(),
+                        destination: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ModifyRequest {
                 /// This type is delimited with an extent of 600 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(600);
             }
+            impl ::canadensis_encoding::SerializedSize for ModifyRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 230;
+            }
             impl ::canadensis_encoding::Request for ModifyRequest {}
             impl ModifyRequest {}
             impl ::canadensis_encoding::Serialize for ModifyRequest {
                 fn size_bits(&self) -> usize {
-                    1 + 1 + 30 + (self.source).size_bits() + (self.destination).size_bits() + 0
+                    1 + 1
+                        + 30
+                        + (self.source).size_bits().next_multiple_of(8)
+                        + (self.destination).size_bits().next_multiple_of(8)
+                        + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_bool(self.preserve_source);
@@ -5461,7 +6408,7 @@ pub mod uavcan {
             ///
             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
             #[repr(C, packed)]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Modify.1.1")]
             pub struct ModifyResponse {
                 ///
                 /// `uavcan.file.Error.1.0`
@@ -5470,10 +6417,20 @@ pub mod uavcan {
                 /// size 16 bits
                 pub error: crate::uavcan::file::error_1_0::Error,
             }
+            impl ::core::default::Default for ModifyResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ModifyResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for ModifyResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Response for ModifyResponse {}
             impl ModifyResponse {}
             impl ::canadensis_encoding::Serialize for ModifyResponse {
@@ -5548,15 +6505,32 @@ pub mod uavcan {
                 /// size ranges from 8 to 2048 bits
                 pub destination: crate::uavcan::file::path_2_0::Path,
             }
+            impl ::core::default::Default for ModifyRequest {
+                fn default() -> Self {
+                    Self {
+                        preserve_source: ::core::default::Default::default(),
+                        overwrite_destination: ::core::default::Default::default(),
+                        source: ::core::default::Default::default(),
+                        destination: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ModifyRequest {
                 /// This type is delimited with an extent of 600 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(600);
             }
+            impl ::canadensis_encoding::SerializedSize for ModifyRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 516;
+            }
             impl ::canadensis_encoding::Request for ModifyRequest {}
             impl ModifyRequest {}
             impl ::canadensis_encoding::Serialize for ModifyRequest {
                 fn size_bits(&self) -> usize {
-                    1 + 1 + 30 + (self.source).size_bits() + (self.destination).size_bits() + 0
+                    1 + 1
+                        + 30
+                        + (self.source).size_bits().next_multiple_of(8)
+                        + (self.destination).size_bits().next_multiple_of(8)
+                        + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_bool(self.preserve_source);
@@ -5599,10 +6573,20 @@ pub mod uavcan {
                 /// size 16 bits
                 pub error: crate::uavcan::file::error_1_0::Error,
             }
+            impl ::core::default::Default for ModifyResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ModifyResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for ModifyResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Response for ModifyResponse {}
             impl ModifyResponse {}
             impl ::canadensis_encoding::Serialize for ModifyResponse {
@@ -5640,7 +6624,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " Nested type.\n A file system path encoded in UTF8. The only valid separator is the forward slash \"/\".\n A single slash (\"/\") refers to the root directory (the location of which is defined by the server).\n Relative references (e.g. \"..\") are not defined and not permitted (although this may change in the future).\n Conventions (not enforced):\n   - A path pointing to a file or a link to file should not end with a separator.\n   - A path pointing to a directory or to a link to directory should end with a separator.\n\n The maximum path length limit is chosen as a trade-off between compatibility with deep directory structures and\n the worst-case transfer length. The limit is 112 bytes, which allows all transfers containing a single instance\n of path and no other large data chunks to fit into two CAN FD frames."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Path.2.0")]
             pub struct Path {
                 ///
                 /// `saturated uint8[<=112]`
@@ -5649,10 +6633,20 @@ pub mod uavcan {
                 /// size ranges from 0 to 896 bits
                 pub path: ::heapless::Vec<u8, 112>,
             }
+            impl ::core::default::Default for Path {
+                fn default() -> Self {
+                    Self {
+                        path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Path {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Path {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 113;
+            }
             impl ::canadensis_encoding::Message for Path {}
             impl Path {
                 pub const SEPARATOR: u8 = 47;
@@ -5679,9 +6673,8 @@ pub mod uavcan {
                             let length = cursor.read_u8() as _;
                             if length <= 112 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -5708,10 +6701,20 @@ pub mod uavcan {
                 /// size ranges from 0 to 2040 bits
                 pub path: ::heapless::Vec<u8, 255>,
             }
+            impl ::core::default::Default for Path {
+                fn default() -> Self {
+                    Self {
+                        path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Path {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Path {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 256;
+            }
             impl ::canadensis_encoding::Message for Path {}
             impl Path {
                 pub const SEPARATOR: u8 = 47;
@@ -5738,9 +6741,8 @@ pub mod uavcan {
                             let length = cursor.read_u8() as _;
                             if length <= 255 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -5754,7 +6756,7 @@ pub mod uavcan {
         #[cfg_attr(not(test), deprecated)]
         pub mod read_1_0 {
             #[cfg_attr(not(doctest), doc = "The fixed ID of this service")]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Read.1.1")]
             pub const SERVICE: ::canadensis_core::ServiceId =
                 ::canadensis_core::ServiceId::from_truncating(408);
 
@@ -5766,7 +6768,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " Read file from a remote node.\n\n There are two possible outcomes of a successful call:\n  1. Data array size equals its capacity. This means that the end of the file is not reached yet.\n  2. Data array size is less than its capacity, possibly zero. This means that the end of the file is reached.\n\n Thus, if the client needs to fetch the entire file, it should repeatedly call this service while increasing the\n offset, until a non-full data array is returned.\n\n If the object pointed by 'path' cannot be read (e.g. it is a directory or it does not exist), an appropriate error\n code will be returned, and the data array will be empty.\n\n It is easy to see that this protocol is prone to race conditions because the remote file can be modified\n between read operations which might result in the client obtaining a damaged file. To combat this,\n application designers are recommended to adhere to the following convention. Let every file whose integrity\n is of interest have a hash or a digital signature, which is stored in an adjacent file under the same name\n suffixed with the appropriate extension according to the type of hash or digital signature used.\n For example, let there be file \"image.bin\", integrity of which shall be ensured by the client upon downloading.\n Suppose that the file is hashed using SHA-256, so the appropriate file extension for the hash would be\n \".sha256\". Following this convention, the hash of \"image.bin\" would be stored in \"image.bin.sha256\".\n After downloading the file, the client would read the hash (being small, the hash can be read in a single\n request) and check it against a locally computed value. Some servers may opt to generate such hash files\n automatically as necessary; for example, if such file is requested but it does not exist, the server would\n compute the necessary signature or hash (the type of hash/signature can be deduced from the requested file\n extension) and return it as if the file existed. Obviously, this would be impractical for very large files;\n in that case, hash/signature should be pre-computed and stored in a real file. If this approach is followed,\n implementers are advised to use only SHA-256 for hashing, in order to reduce the number of fielded\n incompatible implementations."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Read.1.1")]
             pub struct ReadRequest {
                 ///
                 /// `truncated uint40`
@@ -5781,15 +6783,26 @@ pub mod uavcan {
                 /// size ranges from 8 to 904 bits
                 pub path: crate::uavcan::file::path_1_0::Path,
             }
+            impl ::core::default::Default for ReadRequest {
+                fn default() -> Self {
+                    Self {
+                        offset: ::core::default::Default::default(),
+                        path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ReadRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ReadRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 118;
+            }
             impl ::canadensis_encoding::Request for ReadRequest {}
             impl ReadRequest {}
             impl ::canadensis_encoding::Serialize for ReadRequest {
                 fn size_bits(&self) -> usize {
-                    40 + (self.path).size_bits() + 0
+                    40 + (self.path).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_u40(self.offset);
@@ -5814,7 +6827,7 @@ pub mod uavcan {
             ///
             /// Size ranges from 4 to 260 bytes
             ///
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Read.1.1")]
             pub struct ReadResponse {
                 ///
                 /// `uavcan.file.Error.1.0`
@@ -5829,10 +6842,21 @@ pub mod uavcan {
                 /// size ranges from 0 to 2048 bits
                 pub data: ::heapless::Vec<u8, 256>,
             }
+            impl ::core::default::Default for ReadResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                        data: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ReadResponse {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ReadResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 260;
+            }
             impl ::canadensis_encoding::Response for ReadResponse {}
             impl ReadResponse {}
             impl ::canadensis_encoding::Serialize for ReadResponse {
@@ -5858,9 +6882,8 @@ pub mod uavcan {
                             let length = cursor.read_u16() as _;
                             if length <= 256 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -5897,15 +6920,26 @@ pub mod uavcan {
                 /// size ranges from 8 to 2048 bits
                 pub path: crate::uavcan::file::path_2_0::Path,
             }
+            impl ::core::default::Default for ReadRequest {
+                fn default() -> Self {
+                    Self {
+                        offset: ::core::default::Default::default(),
+                        path: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ReadRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ReadRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 261;
+            }
             impl ::canadensis_encoding::Request for ReadRequest {}
             impl ReadRequest {}
             impl ::canadensis_encoding::Serialize for ReadRequest {
                 fn size_bits(&self) -> usize {
-                    40 + (self.path).size_bits() + 0
+                    40 + (self.path).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_u40(self.offset);
@@ -5944,15 +6978,26 @@ pub mod uavcan {
                 /// size ranges from 16 to 2064 bits
                 pub data: crate::uavcan::primitive::unstructured_1_0::Unstructured,
             }
+            impl ::core::default::Default for ReadResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                        data: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ReadResponse {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ReadResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 260;
+            }
             impl ::canadensis_encoding::Response for ReadResponse {}
             impl ReadResponse {}
             impl ::canadensis_encoding::Serialize for ReadResponse {
                 fn size_bits(&self) -> usize {
-                    16 + (self.data).size_bits() + 0
+                    16 + (self.data).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_composite(&self.error);
@@ -5977,7 +7022,7 @@ pub mod uavcan {
         #[cfg_attr(not(test), deprecated)]
         pub mod write_1_0 {
             #[cfg_attr(not(doctest), doc = "The fixed ID of this service")]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Write.1.1")]
             pub const SERVICE: ::canadensis_core::ServiceId =
                 ::canadensis_core::ServiceId::from_truncating(409);
 
@@ -5989,7 +7034,7 @@ pub mod uavcan {
                 not(doctest),
                 doc = " Write into a remote file.\n The server shall place the contents of the field 'data' into the file pointed by 'path' at the offset specified by\n the field 'offset'.\n\n When writing a file, the client should repeatedly call this service with data while advancing the offset until the\n file is written completely. When the write sequence is completed, the client shall call the service one last time,\n with the offset set to the size of the file and with the data field empty, which will signal the server that the\n transfer is finished.\n\n When the write operation is complete, the server shall truncate the resulting file past the specified offset."
             )]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Write.1.1")]
             pub struct WriteRequest {
                 ///
                 /// `truncated uint40`
@@ -6014,15 +7059,27 @@ pub mod uavcan {
                 /// size ranges from 0 to 1536 bits
                 pub data: ::heapless::Vec<u8, 192>,
             }
+            impl ::core::default::Default for WriteRequest {
+                fn default() -> Self {
+                    Self {
+                        offset: ::core::default::Default::default(),
+                        path: ::core::default::Default::default(),
+                        data: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for WriteRequest {
                 /// This type is delimited with an extent of 600 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(600);
             }
+            impl ::canadensis_encoding::SerializedSize for WriteRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 311;
+            }
             impl ::canadensis_encoding::Request for WriteRequest {}
             impl WriteRequest {}
             impl ::canadensis_encoding::Serialize for WriteRequest {
                 fn size_bits(&self) -> usize {
-                    40 + (self.path).size_bits() + 8 + (self.data).len() * 8 + 0
+                    40 + (self.path).size_bits().next_multiple_of(8) + 8 + (self.data).len() * 8 + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_u40(self.offset);
@@ -6045,9 +7102,8 @@ pub mod uavcan {
                             let length = cursor.read_u8() as _;
                             if length <= 192 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -6063,7 +7119,7 @@ pub mod uavcan {
             ///
             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
             #[repr(C, packed)]
-            #[deprecated]
+            #[deprecated(note = "Replaced by uavcan.file.Write.1.1")]
             pub struct WriteResponse {
                 ///
                 /// `uavcan.file.Error.1.0`
@@ -6072,10 +7128,20 @@ pub mod uavcan {
                 /// size 16 bits
                 pub error: crate::uavcan::file::error_1_0::Error,
             }
+            impl ::core::default::Default for WriteResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for WriteResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for WriteResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Response for WriteResponse {}
             impl WriteResponse {}
             impl ::canadensis_encoding::Serialize for WriteResponse {
@@ -6135,15 +7201,29 @@ pub mod uavcan {
                 /// size ranges from 16 to 2064 bits
                 pub data: crate::uavcan::primitive::unstructured_1_0::Unstructured,
             }
+            impl ::core::default::Default for WriteRequest {
+                fn default() -> Self {
+                    Self {
+                        offset: ::core::default::Default::default(),
+                        path: ::core::default::Default::default(),
+                        data: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for WriteRequest {
                 /// This type is delimited with an extent of 600 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(600);
             }
+            impl ::canadensis_encoding::SerializedSize for WriteRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 519;
+            }
             impl ::canadensis_encoding::Request for WriteRequest {}
             impl WriteRequest {}
             impl ::canadensis_encoding::Serialize for WriteRequest {
                 fn size_bits(&self) -> usize {
-                    40 + (self.path).size_bits() + (self.data).size_bits() + 0
+                    40 + (self.path).size_bits().next_multiple_of(8)
+                        + (self.data).size_bits().next_multiple_of(8)
+                        + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_u40(self.offset);
@@ -6180,10 +7260,20 @@ pub mod uavcan {
                 /// size 16 bits
                 pub error: crate::uavcan::file::error_1_0::Error,
             }
+            impl ::core::default::Default for WriteResponse {
+                fn default() -> Self {
+                    Self {
+                        error: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for WriteResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for WriteResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Response for WriteResponse {}
             impl WriteResponse {}
             impl ::canadensis_encoding::Serialize for WriteResponse {
@@ -6217,7 +7307,7 @@ pub mod uavcan {
             #[cfg_attr(not(test), deprecated)]
             pub mod handle_incoming_packet_0_1 {
                 #[cfg_attr(not(doctest), doc = "The fixed ID of this service")]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.internet.udp.HandleIncomingPacket.0.2")]
                 pub const SERVICE: ::canadensis_core::ServiceId =
                     ::canadensis_core::ServiceId::from_truncating(500);
 
@@ -6229,7 +7319,7 @@ pub mod uavcan {
                     not(doctest),
                     doc = " This message carries UDP packets sent from a remote host on the Internet or a LAN to a node on the local Cyphal bus.\n Please refer to the definition of the message type OutgoingPacket for a general overview of the packet forwarding\n logic.\n\n This data type has been made a service type rather than a message type in order to make its transfers addressable,\n allowing nodes to employ hardware acceptance filters for filtering out forwarded datagrams that are not addressed\n to them. Additionally, requiring the destination nodes to always respond upon reception of the forwarded datagram\n opens interesting opportunities for future extensions of the forwarding protocol. If the service invocation times\n out, the modem node is permitted to remove the corresponding entry from the NAT table immediately, not waiting\n for its TTL to expire.\n\n It should be noted that this data type definition intentionally leaves out the source address. This is done in\n order to simplify the implementation, reduce the bus traffic overhead, and because the nature of the\n communication patterns proposed by this set of messages does not provide a valid way to implement server hosts\n on the local Cyphal bus. It is assumed that local nodes can be only clients, and therefore, they will be able to\n determine the address of the sender simply by mapping the field session_id to their internally maintained states.\n Furthermore, it is uncertain what is the optimal way of representing the source address for\n client nodes: it is assumed that the local nodes will mostly use DNS names rather than IP addresses, so if there\n was a source address field, modem nodes would have to perform reverse mapping from the IP address they received\n the datagram from to the corresponding DNS name that was used by the local node with the outgoing message. This\n approach creates a number of troubling corner cases and adds a fair amount of hidden complexities to the\n implementation of modem nodes.\n\n It is recommended to perform service invocations at the same transfer priority level as was used for broadcasting\n the latest matching message of type OutgoingPacket. However, meeting this recommendation would require the modem\n node to implement additional logic, which may be undesirable. Therefore, implementers are free to deviate from\n this recommendation and resort to a fixed priority level instead. In the case of a fixed priority level, it is\n advised to use the lowest transfer priority level."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.internet.udp.HandleIncomingPacket.0.2")]
                 pub struct HandleIncomingPacketRequest {
                     #[cfg_attr(
                         not(doctest),
@@ -6252,10 +7342,21 @@ pub mod uavcan {
                     /// size ranges from 0 to 2472 bits
                     pub payload: ::heapless::Vec<u8, 309>,
                 }
+                impl ::core::default::Default for HandleIncomingPacketRequest {
+                    fn default() -> Self {
+                        Self {
+                            session_id: ::core::default::Default::default(),
+                            payload: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for HandleIncomingPacketRequest {
                     /// This type is delimited with an extent of 600 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(600);
                 }
+                impl ::canadensis_encoding::SerializedSize for HandleIncomingPacketRequest {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 313;
+                }
                 impl ::canadensis_encoding::Request for HandleIncomingPacketRequest {}
                 impl HandleIncomingPacketRequest {}
                 impl ::canadensis_encoding::Serialize for HandleIncomingPacketRequest {
@@ -6281,9 +7382,8 @@ pub mod uavcan {
                                 let length = cursor.read_u16() as _;
                                 if length <= 309 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6301,12 +7401,20 @@ pub mod uavcan {
                 ///
                 #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                 #[repr(C, packed)]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.internet.udp.HandleIncomingPacket.0.2")]
                 pub struct HandleIncomingPacketResponse {}
+                impl ::core::default::Default for HandleIncomingPacketResponse {
+                    fn default() -> Self {
+                        Self {}
+                    }
+                }
                 impl ::canadensis_encoding::DataType for HandleIncomingPacketResponse {
                     /// This type is delimited with an extent of 63 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(63);
                 }
+                impl ::canadensis_encoding::SerializedSize for HandleIncomingPacketResponse {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                }
                 impl ::canadensis_encoding::Response for HandleIncomingPacketResponse {}
                 impl HandleIncomingPacketResponse {}
                 impl ::canadensis_encoding::Serialize for HandleIncomingPacketResponse {
@@ -6370,10 +7478,21 @@ pub mod uavcan {
                     /// size ranges from 0 to 4064 bits
                     pub payload: ::heapless::Vec<u8, 508>,
                 }
+                impl ::core::default::Default for HandleIncomingPacketRequest {
+                    fn default() -> Self {
+                        Self {
+                            session_id: ::core::default::Default::default(),
+                            payload: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for HandleIncomingPacketRequest {
                     /// This type is delimited with an extent of 600 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(600);
                 }
+                impl ::canadensis_encoding::SerializedSize for HandleIncomingPacketRequest {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 512;
+                }
                 impl ::canadensis_encoding::Request for HandleIncomingPacketRequest {}
                 impl HandleIncomingPacketRequest {}
                 impl ::canadensis_encoding::Serialize for HandleIncomingPacketRequest {
@@ -6399,9 +7518,8 @@ pub mod uavcan {
                                 let length = cursor.read_u16() as _;
                                 if length <= 508 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6420,10 +7538,18 @@ pub mod uavcan {
                 #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                 #[repr(C, packed)]
                 pub struct HandleIncomingPacketResponse {}
+                impl ::core::default::Default for HandleIncomingPacketResponse {
+                    fn default() -> Self {
+                        Self {}
+                    }
+                }
                 impl ::canadensis_encoding::DataType for HandleIncomingPacketResponse {
                     /// This type is delimited with an extent of 63 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(63);
                 }
+                impl ::canadensis_encoding::SerializedSize for HandleIncomingPacketResponse {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+                }
                 impl ::canadensis_encoding::Response for HandleIncomingPacketResponse {}
                 impl HandleIncomingPacketResponse {}
                 impl ::canadensis_encoding::Serialize for HandleIncomingPacketResponse {
@@ -6456,7 +7582,7 @@ pub mod uavcan {
             #[cfg_attr(not(test), deprecated)]
             pub mod outgoing_packet_0_1 {
                 #[cfg_attr(not(doctest), doc = "The fixed subject ID for this message type")]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.internet.udp.OutgoingPacket.0.2")]
                 pub const SUBJECT: ::canadensis_core::SubjectId =
                     ::canadensis_core::SubjectId::from_truncating(8174);
 
@@ -6468,7 +7594,7 @@ pub mod uavcan {
                     not(doctest),
                     doc = " This message carries UDP packets from a node on the local bus to a remote host on the Internet or a LAN.\n\n Any node can broadcast a message of this type.\n\n All nodes that are capable of communication with the Internet or a LAN should subscribe to messages\n of this type and forward the payload to the indicated host and port using exactly one UDP datagram\n per message (i.e. additional fragmentation is to be avoided). Such nodes will be referred to as\n \"modem nodes\".\n\n It is expected that some systems will have more than one modem node available.\n Each modem node is supposed to forward every message it sees, which will naturally create\n some degree of modular redundancy and fault tolerance. The remote host should therefore be able to\n properly handle possibly duplicated messages from different source addresses, in addition to\n possible duplications introduced by the UDP/IP protocol itself. There are at least two obvious\n strategies that can be employed by the remote host:\n\n   - Accept only the first message, ignore duplicates. This approach requires that the UDP stream\n     should contain some metadata necessary for the remote host to determine the source and ordering\n     of each received datum. This approach works best for periodic data, such as telemetry, where\n     the sender does not expect any responses.\n\n   - Process all messages, including duplicates. This approach assumes that the remote host acts\n     as a server, processing all received requests and providing responses to each. This arrangement\n     implies that the client may receive duplicated responses. It is therefore the client's\n     responsibility to resolve the possible ambiguity. An obvious solution is to accept the first\n     arrived response and ignore the later ones.\n\n Applications are free to choose whatever redundancy management strategy works best for them.\n\n If the source node expects that the remote host will send some data back, it shall explicitly notify\n the modem nodes about this, so that they could prepare to perform reverse forwarding when the\n expected data arrives from the remote host. The technique of reverse forwarding is known in\n networking as IP Masquerading, or (in general) Network Address Translation (NAT). The notification\n is performed by means of setting one of the corresponding flags defined below.\n\n In order to be able to match datagrams received from remote hosts and the local nodes they should\n be forwarded to, modem nodes are required to keep certain metadata about outgoing datagrams. Such\n metadata is stored in a data structure referred to as \"NAT table\", where every entry would normally\n contain at least the following fields:\n   - The local UDP port number that was used to send the outgoing datagram from.\n     Per RFC 4787, the port number is chosen by the modem node automatically.\n   - The node-ID of the local node that has sent the outgoing datagram.\n   - Value of the field session_id defined below.\n   - Possibly some other data, depending on the implementation.\n\n The modem nodes are required to keep each NAT table entry for at least NAT_ENTRY_MIN_TTL seconds\n since the last reverse forwarding action was performed. Should the memory resources of the modem node\n be exhausted, it is allowed to remove old NAT entries earlier, following the policy of least recent use.\n\n Having received a UDP packet from a remote host, the modem node would check the NAT table in order\n to determine where on the Cyphal bus the received data should be forwarded to. If the NAT table\n contains no matches, the received data should be silently dropped. If a match is found, the\n modem node will forward the data to the recipient node using the service HandleIncomingPacket.\n If the service invocation times out, the modem node is permitted to remove the corresponding entry from\n the NAT table immediately (but it is not required). This will ensure that the modem nodes will not be\n tasked with translations for client nodes that are no longer online or are unreachable.\n Additionally, client nodes will be able to hint the modem nodes to remove translation entries they no\n longer need by simply refusing to respond to the corresponding service invocation. Please refer to\n the definition of that service data type for a more in-depth review of the reverse forwarding process.\n\n Modem nodes can also perform traffic shaping, if needed, by means of delaying or dropping UDP\n datagrams that exceed the quota.\n\n To summarize, a typical data exchange occurrence should amount to the following actions:\n\n   - A local Cyphal node broadcasts a message of type OutgoingPacket with the payload it needs\n     to forward. If the node expects the remote host to send any data back, it sets the masquerading flag.\n\n   - Every modem node on the bus receives the message and performs the following actions:\n\n       - The domain name is resolved, unless the destination address provided in the message\n         is already an IP address, in which case this step should be skipped.\n\n       - The domain name to IP address mapping is added to the local DNS cache, although this\n         part is entirely implementation defined and is not required.\n\n       - The masquerading flag is checked. If it is set, a new entry is added to the NAT table.\n         If such entry already existed, its expiration timeout is reset. If no such entry existed\n         and a new one cannot be added because of memory limitations, the least recently used\n         (i.e. oldest) entry of the NAT table is replaced with the new one.\n\n       - The payload is forwarded to the determined IP address.\n\n   - At this point, direct forwarding is complete. Should any of the modem nodes receive an incoming\n     packet, they would attempt to perform a reverse forwarding according to the above provided algorithm.\n\n It is recommended to use the lowest transport priority level when broadcasting messages of this type,\n in order to avoid interference with a real-time traffic on the bus. Usage of higher priority levels is\n unlikely to be practical because the latency and throughput limitations introduced by the on-board radio\n communication equipment are likely to vastly exceed those of the local CAN bus."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.internet.udp.OutgoingPacket.0.2")]
                 pub struct OutgoingPacket {
                     #[cfg_attr(
                         not(doctest),
@@ -6529,10 +7655,25 @@ pub mod uavcan {
                     /// size ranges from 0 to 2080 bits
                     pub payload: ::heapless::Vec<u8, 260>,
                 }
+                impl ::core::default::Default for OutgoingPacket {
+                    fn default() -> Self {
+                        Self {
+                            session_id: ::core::default::Default::default(),
+                            destination_port: ::core::default::Default::default(),
+                            destination_address: ::core::default::Default::default(),
+                            use_masquerading: ::core::default::Default::default(),
+                            use_dtls: ::core::default::Default::default(),
+                            payload: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for OutgoingPacket {
                     /// This type is delimited with an extent of 600 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(600);
                 }
+                impl ::canadensis_encoding::SerializedSize for OutgoingPacket {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 313;
+                }
                 impl ::canadensis_encoding::Message for OutgoingPacket {}
                 impl OutgoingPacket {
                     #[cfg_attr(
@@ -6579,9 +7720,8 @@ pub mod uavcan {
                                 let length = cursor.read_u8() as _;
                                 if length <= 45 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6596,9 +7736,8 @@ pub mod uavcan {
                                 let length = cursor.read_u16() as _;
                                 if length <= 260 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6683,10 +7822,25 @@ pub mod uavcan {
                     /// size ranges from 0 to 4064 bits
                     pub payload: ::heapless::Vec<u8, 508>,
                 }
+                impl ::core::default::Default for OutgoingPacket {
+                    fn default() -> Self {
+                        Self {
+                            session_id: ::core::default::Default::default(),
+                            destination_port: ::core::default::Default::default(),
+                            destination_address: ::core::default::Default::default(),
+                            use_masquerading: ::core::default::Default::default(),
+                            use_dtls: ::core::default::Default::default(),
+                            payload: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for OutgoingPacket {
                     /// This type is delimited with an extent of 600 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(600);
                 }
+                impl ::canadensis_encoding::SerializedSize for OutgoingPacket {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 561;
+                }
                 impl ::canadensis_encoding::Message for OutgoingPacket {}
                 impl OutgoingPacket {
                     #[cfg_attr(
@@ -6733,9 +7887,8 @@ pub mod uavcan {
                                 let length = cursor.read_u8() as _;
                                 if length <= 45 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6750,9 +7903,8 @@ pub mod uavcan {
                                 let length = cursor.read_u16() as _;
                                 if length <= 508 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6784,10 +7936,18 @@ Base
 Extended
 (crate::uavcan::metatransport::can::extended_arbitration_id_0_1::ExtendedArbitrationID)
 ,}
+                impl ::core::default::Default for ArbitrationID {
+                    fn default() -> Self {
+                        Self::Base(::core::default::Default::default())
+                    }
+                }
                 impl ::canadensis_encoding::DataType for ArbitrationID {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for ArbitrationID {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 5;
+                }
                 impl ::canadensis_encoding::Message for ArbitrationID {}
                 impl ArbitrationID {}
                 impl ::canadensis_encoding::Serialize for ArbitrationID {
@@ -6837,10 +7997,20 @@ Extended
                     pub value: u16,
                     // 21 bits of padding
                 }
+                impl ::core::default::Default for BaseArbitrationID {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for BaseArbitrationID {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for BaseArbitrationID {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                }
                 impl ::canadensis_encoding::Message for BaseArbitrationID {}
                 impl BaseArbitrationID {}
                 impl ::canadensis_encoding::Serialize for BaseArbitrationID {
@@ -6886,10 +8056,21 @@ Extended
                     /// size ranges from 0 to 64 bits
                     pub data: ::heapless::Vec<u8, 8>,
                 }
+                impl ::core::default::Default for DataClassic {
+                    fn default() -> Self {
+                        Self {
+                            arbitration_id: ::core::default::Default::default(),
+                            data: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for DataClassic {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for DataClassic {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 14;
+                }
                 impl ::canadensis_encoding::Message for DataClassic {}
                 impl DataClassic {}
                 impl ::canadensis_encoding::Serialize for DataClassic {
@@ -6915,9 +8096,8 @@ Extended
                                 let length = cursor.read_u8() as _;
                                 if length <= 8 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -6950,10 +8130,21 @@ Extended
                     /// size ranges from 0 to 512 bits
                     pub data: ::heapless::Vec<u8, 64>,
                 }
+                impl ::core::default::Default for DataFD {
+                    fn default() -> Self {
+                        Self {
+                            arbitration_id: ::core::default::Default::default(),
+                            data: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for DataFD {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for DataFD {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 70;
+                }
                 impl ::canadensis_encoding::Message for DataFD {}
                 impl DataFD {}
                 impl ::canadensis_encoding::Serialize for DataFD {
@@ -6979,9 +8170,8 @@ Extended
                                 let length = cursor.read_u8() as _;
                                 if length <= 64 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -7005,10 +8195,18 @@ Extended
                 pub struct Error {
                     // 32 bits of padding
                 }
+                impl ::core::default::Default for Error {
+                    fn default() -> Self {
+                        Self {}
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Error {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Error {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                }
                 impl ::canadensis_encoding::Message for Error {}
                 impl Error {}
                 impl ::canadensis_encoding::Serialize for Error {
@@ -7045,10 +8243,20 @@ Extended
                     pub value: u32,
                     // 3 bits of padding
                 }
+                impl ::core::default::Default for ExtendedArbitrationID {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for ExtendedArbitrationID {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for ExtendedArbitrationID {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                }
                 impl ::canadensis_encoding::Message for ExtendedArbitrationID {}
                 impl ExtendedArbitrationID {}
                 impl ::canadensis_encoding::Serialize for ExtendedArbitrationID {
@@ -7084,7 +8292,7 @@ Extended
                     not(doctest),
                     doc = " CAN 2.0 or CAN FD frame representation. This is the top-level data type in its namespace.\n See next version."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.metatransport.can.Frame.0.2")]
                 pub struct Frame {
                     ///
                     /// `uavcan.time.SynchronizedTimestamp.1.0`
@@ -7101,15 +8309,26 @@ Extended
                     pub manifestation:
                         crate::uavcan::metatransport::can::manifestation_0_1::Manifestation,
                 }
+                impl ::core::default::Default for Frame {
+                    fn default() -> Self {
+                        Self {
+                            timestamp: ::core::default::Default::default(),
+                            manifestation: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Frame {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Frame {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 78;
+                }
                 impl ::canadensis_encoding::Message for Frame {}
                 impl Frame {}
                 impl ::canadensis_encoding::Serialize for Frame {
                     fn size_bits(&self) -> usize {
-                        56 + (self.manifestation).size_bits() + 0
+                        56 + (self.manifestation).size_bits().next_multiple_of(8) + 0
                     }
                     fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                         cursor.write_composite(&self.timestamp);
@@ -7160,18 +8379,26 @@ Extended
                     /// uavcan.metatransport.can.RTR.0.1
                     RemoteTransmissionRequest(crate::uavcan::metatransport::can::rtr_0_1::RTR),
                 }
+                impl ::core::default::Default for Frame {
+                    fn default() -> Self {
+                        Self::Error(::core::default::Default::default())
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Frame {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Frame {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 71;
+                }
                 impl ::canadensis_encoding::Message for Frame {}
                 impl Frame {}
                 impl ::canadensis_encoding::Serialize for Frame {
                     fn size_bits(&self) -> usize {
                         8 + match self {
                             Frame::Error(inner) => 32,
-                            Frame::DataFd(inner) => (inner).size_bits(),
-                            Frame::DataClassic(inner) => (inner).size_bits(),
+                            Frame::DataFd(inner) => (inner).size_bits().next_multiple_of(8),
+                            Frame::DataClassic(inner) => (inner).size_bits().next_multiple_of(8),
                             Frame::RemoteTransmissionRequest(inner) => 40,
                         }
                     }
@@ -7245,18 +8472,28 @@ Extended
                     /// uavcan.metatransport.can.RTR.0.1
                     RemoteTransmissionRequest(crate::uavcan::metatransport::can::rtr_0_1::RTR),
                 }
+                impl ::core::default::Default for Manifestation {
+                    fn default() -> Self {
+                        Self::Error(::core::default::Default::default())
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Manifestation {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Manifestation {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 71;
+                }
                 impl ::canadensis_encoding::Message for Manifestation {}
                 impl Manifestation {}
                 impl ::canadensis_encoding::Serialize for Manifestation {
                     fn size_bits(&self) -> usize {
                         8 + match self {
                             Manifestation::Error(inner) => 32,
-                            Manifestation::DataFd(inner) => (inner).size_bits(),
-                            Manifestation::DataClassic(inner) => (inner).size_bits(),
+                            Manifestation::DataFd(inner) => (inner).size_bits().next_multiple_of(8),
+                            Manifestation::DataClassic(inner) => {
+                                (inner).size_bits().next_multiple_of(8)
+                            }
                             Manifestation::RemoteTransmissionRequest(inner) => 40,
                         }
                     }
@@ -7318,10 +8555,20 @@ Extended
                     pub arbitration_id:
                         crate::uavcan::metatransport::can::arbitration_id_0_1::ArbitrationID,
                 }
+                impl ::core::default::Default for RTR {
+                    fn default() -> Self {
+                        Self {
+                            arbitration_id: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for RTR {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for RTR {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 5;
+                }
                 impl ::canadensis_encoding::Message for RTR {}
                 impl RTR {}
                 impl ::canadensis_encoding::Serialize for RTR {
@@ -7366,10 +8613,20 @@ Extended
                     /// size 16 bits
                     pub value: u16,
                 }
+                impl ::core::default::Default for EtherType {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for EtherType {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for EtherType {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                }
                 impl ::canadensis_encoding::Message for EtherType {}
                 impl EtherType {
                     pub const IP_V4: u16 = 2048;
@@ -7440,10 +8697,25 @@ Extended
                     /// size ranges from 0 to 73728 bits
                     pub payload: ::heapless::Vec<u8, 9216>,
                 }
+                impl ::core::default::Default for Frame {
+                    fn default() -> Self {
+                        Self {
+                            destination: ::core::array::from_fn(|_| {
+                                ::core::default::Default::default()
+                            }),
+                            source: ::core::array::from_fn(|_| ::core::default::Default::default()),
+                            ethertype: ::core::default::Default::default(),
+                            payload: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Frame {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Frame {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 9232;
+                }
                 impl ::canadensis_encoding::Message for Frame {}
                 impl Frame {}
                 impl ::canadensis_encoding::Serialize for Frame {
@@ -7472,33 +8744,22 @@ Extended
                     {
                         Ok(Frame {
                             destination: {
-                                [
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                ]
+                                let mut bytes = [0u8; 6];
+                                cursor.read_bytes(&mut bytes);
+                                bytes
                             },
                             source: {
-                                [
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                ]
+                                let mut bytes = [0u8; 6];
+                                cursor.read_bytes(&mut bytes);
+                                bytes
                             },
                             ethertype: { cursor.read_composite()? },
                             payload: {
                                 let length = cursor.read_u16() as _;
                                 if length <= 9216 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -7523,7 +8784,7 @@ Extended
                     not(doctest),
                     doc = " A chunk of raw bytes exchanged over a serial transport. Serial links do not support framing natively.\n The chunk may be of arbitrary size.\n See next version."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.metatransport.serial.Fragment.0.2")]
                 pub struct Fragment {
                     ///
                     /// `uavcan.time.SynchronizedTimestamp.1.0`
@@ -7539,10 +8800,21 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub data: ::heapless::Vec<u8, 256>,
                 }
+                impl ::core::default::Default for Fragment {
+                    fn default() -> Self {
+                        Self {
+                            timestamp: ::core::default::Default::default(),
+                            data: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Fragment {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Fragment {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 265;
+                }
                 impl ::canadensis_encoding::Message for Fragment {}
                 impl Fragment {
                     pub const CAPACITY_BYTES: u16 = 256;
@@ -7570,9 +8842,8 @@ Extended
                                 let length = cursor.read_u16() as _;
                                 if length <= 256 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -7601,10 +8872,20 @@ Extended
                     /// size ranges from 0 to 16384 bits
                     pub data: ::heapless::Vec<u8, 2048>,
                 }
+                impl ::core::default::Default for Fragment {
+                    fn default() -> Self {
+                        Self {
+                            data: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Fragment {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Fragment {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2050;
+                }
                 impl ::canadensis_encoding::Message for Fragment {}
                 impl Fragment {
                     pub const CAPACITY_BYTES: u16 = 2048;
@@ -7630,9 +8911,8 @@ Extended
                                 let length = cursor.read_u16() as _;
                                 if length <= 2048 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -7688,10 +8968,26 @@ Extended
                     pub port: u16,
                     // 64 bits of padding
                 }
+                impl ::core::default::Default for Endpoint {
+                    fn default() -> Self {
+                        Self {
+                            ip_address: ::core::array::from_fn(|_| {
+                                ::core::default::Default::default()
+                            }),
+                            mac_address: ::core::array::from_fn(|_| {
+                                ::core::default::Default::default()
+                            }),
+                            port: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Endpoint {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Endpoint {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 32;
+                }
                 impl ::canadensis_encoding::Message for Endpoint {}
                 impl Endpoint {}
                 impl ::canadensis_encoding::Serialize for Endpoint {
@@ -7714,34 +9010,14 @@ Extended
                     {
                         Ok(Endpoint {
                             ip_address: {
-                                [
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                ]
+                                let mut bytes = [0u8; 16];
+                                cursor.read_bytes(&mut bytes);
+                                bytes
                             },
                             mac_address: {
-                                [
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                    cursor.read_u8() as _,
-                                ]
+                                let mut bytes = [0u8; 6];
+                                cursor.read_bytes(&mut bytes);
+                                bytes
                             },
                             port: { cursor.read_u16() as _ },
                         })
@@ -7788,10 +9064,23 @@ Extended
                     /// size ranges from 0 to 73504 bits
                     pub data: ::heapless::Vec<u8, 9188>,
                 }
+                impl ::core::default::Default for Frame {
+                    fn default() -> Self {
+                        Self {
+                            timestamp: ::core::default::Default::default(),
+                            source: ::core::default::Default::default(),
+                            destination: ::core::default::Default::default(),
+                            data: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Frame {
                     /// This type is delimited with an extent of 10240 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(10240);
                 }
+                impl ::canadensis_encoding::SerializedSize for Frame {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 9262;
+                }
                 impl ::canadensis_encoding::Message for Frame {}
                 impl Frame {
                     #[cfg_attr(
@@ -7831,9 +9120,8 @@ Extended
                                 let length = cursor.read_u16() as _;
                                 if length <= 9188 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -7887,10 +9175,21 @@ Extended
                 /// size ranges from 0 to 896 bits
                 pub parameter: ::heapless::Vec<u8, 112>,
             }
+            impl ::core::default::Default for ExecuteCommandRequest {
+                fn default() -> Self {
+                    Self {
+                        command: ::core::default::Default::default(),
+                        parameter: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ExecuteCommandRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ExecuteCommandRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 115;
+            }
             impl ::canadensis_encoding::Request for ExecuteCommandRequest {}
             impl ExecuteCommandRequest {
                 #[cfg_attr(
@@ -7947,9 +9246,8 @@ Extended
                             let length = cursor.read_u8() as _;
                             if length <= 112 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -7975,10 +9273,20 @@ Extended
                 /// size 8 bits
                 pub status: u8,
             }
+            impl ::core::default::Default for ExecuteCommandResponse {
+                fn default() -> Self {
+                    Self {
+                        status: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ExecuteCommandResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for ExecuteCommandResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Response for ExecuteCommandResponse {}
             impl ExecuteCommandResponse {
                 #[cfg_attr(not(doctest), doc = " Started or executed successfully")]
@@ -8075,10 +9383,21 @@ Extended
                 /// size ranges from 0 to 2040 bits
                 pub parameter: ::heapless::Vec<u8, 255>,
             }
+            impl ::core::default::Default for ExecuteCommandRequest {
+                fn default() -> Self {
+                    Self {
+                        command: ::core::default::Default::default(),
+                        parameter: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ExecuteCommandRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ExecuteCommandRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+            }
             impl ::canadensis_encoding::Request for ExecuteCommandRequest {}
             impl ExecuteCommandRequest {
                 #[cfg_attr(
@@ -8135,9 +9454,8 @@ Extended
                             let length = cursor.read_u8() as _;
                             if length <= 255 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -8163,10 +9481,20 @@ Extended
                 /// size 8 bits
                 pub status: u8,
             }
+            impl ::core::default::Default for ExecuteCommandResponse {
+                fn default() -> Self {
+                    Self {
+                        status: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ExecuteCommandResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for ExecuteCommandResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Response for ExecuteCommandResponse {}
             impl ExecuteCommandResponse {
                 #[cfg_attr(not(doctest), doc = " Started or executed successfully")]
@@ -8265,10 +9593,21 @@ Extended
                 /// size ranges from 0 to 2040 bits
                 pub parameter: ::heapless::Vec<u8, 255>,
             }
+            impl ::core::default::Default for ExecuteCommandRequest {
+                fn default() -> Self {
+                    Self {
+                        command: ::core::default::Default::default(),
+                        parameter: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ExecuteCommandRequest {
                 /// This type is delimited with an extent of 300 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(300);
             }
+            impl ::canadensis_encoding::SerializedSize for ExecuteCommandRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+            }
             impl ::canadensis_encoding::Request for ExecuteCommandRequest {}
             impl ExecuteCommandRequest {
                 #[cfg_attr(
@@ -8330,9 +9669,8 @@ Extended
                             let length = cursor.read_u8() as _;
                             if length <= 255 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -8358,10 +9696,20 @@ Extended
                 /// size 8 bits
                 pub status: u8,
             }
+            impl ::core::default::Default for ExecuteCommandResponse {
+                fn default() -> Self {
+                    Self {
+                        status: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ExecuteCommandResponse {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for ExecuteCommandResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Response for ExecuteCommandResponse {}
             impl ExecuteCommandResponse {
                 #[cfg_attr(not(doctest), doc = " Started or executed successfully")]
@@ -8437,10 +9785,18 @@ Extended
             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
             #[repr(C, packed)]
             pub struct GetInfoRequest {}
+            impl ::core::default::Default for GetInfoRequest {
+                fn default() -> Self {
+                    Self {}
+                }
+            }
             impl ::canadensis_encoding::DataType for GetInfoRequest {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for GetInfoRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+            }
             impl ::canadensis_encoding::Request for GetInfoRequest {}
             impl GetInfoRequest {}
             impl ::canadensis_encoding::Serialize for GetInfoRequest {
@@ -8548,10 +9904,27 @@ Extended
                 /// size ranges from 0 to 1776 bits
                 pub certificate_of_authenticity: ::heapless::Vec<u8, 222>,
             }
+            impl ::core::default::Default for GetInfoResponse {
+                fn default() -> Self {
+                    Self {
+                        protocol_version: ::core::default::Default::default(),
+                        hardware_version: ::core::default::Default::default(),
+                        software_version: ::core::default::Default::default(),
+                        software_vcs_revision_id: ::core::default::Default::default(),
+                        unique_id: ::core::array::from_fn(|_| ::core::default::Default::default()),
+                        name: ::core::default::Default::default(),
+                        software_image_crc: ::core::default::Default::default(),
+                        certificate_of_authenticity: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetInfoResponse {
                 /// This type is delimited with an extent of 448 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(448);
             }
+            impl ::canadensis_encoding::SerializedSize for GetInfoResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 313;
+            }
             impl ::canadensis_encoding::Response for GetInfoResponse {}
             impl GetInfoResponse {}
             impl ::canadensis_encoding::Serialize for GetInfoResponse {
@@ -8597,32 +9970,16 @@ Extended
                         software_version: { cursor.read_composite()? },
                         software_vcs_revision_id: { cursor.read_u64() as _ },
                         unique_id: {
-                            [
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                                cursor.read_u8() as _,
-                            ]
+                            let mut bytes = [0u8; 16];
+                            cursor.read_bytes(&mut bytes);
+                            bytes
                         },
                         name: {
                             let length = cursor.read_u8() as _;
                             if length <= 50 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -8644,9 +10001,8 @@ Extended
                             let length = cursor.read_u8() as _;
                             if length <= 222 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -8672,10 +10028,18 @@ Extended
             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
             #[repr(C, packed)]
             pub struct GetTransportStatisticsRequest {}
+            impl ::core::default::Default for GetTransportStatisticsRequest {
+                fn default() -> Self {
+                    Self {}
+                }
+            }
             impl ::canadensis_encoding::DataType for GetTransportStatisticsRequest {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for GetTransportStatisticsRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+            }
             impl ::canadensis_encoding::Request for GetTransportStatisticsRequest {}
             impl GetTransportStatisticsRequest {}
             impl ::canadensis_encoding::Serialize for GetTransportStatisticsRequest {
@@ -8731,10 +10095,21 @@ Extended
                 pub network_interface_statistics:
                     ::heapless::Vec<crate::uavcan::node::io_statistics_0_1::IOStatistics, 3>,
             }
+            impl ::core::default::Default for GetTransportStatisticsResponse {
+                fn default() -> Self {
+                    Self {
+                        transfer_statistics: ::core::default::Default::default(),
+                        network_interface_statistics: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetTransportStatisticsResponse {
                 /// This type is delimited with an extent of 192 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(192);
             }
+            impl ::canadensis_encoding::SerializedSize for GetTransportStatisticsResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 61;
+            }
             impl ::canadensis_encoding::Response for GetTransportStatisticsResponse {}
             impl GetTransportStatisticsResponse {
                 #[cfg_attr(
@@ -8797,10 +10172,20 @@ Extended
                 /// size 2 bits
                 pub value: u8,
             }
+            impl ::core::default::Default for Health {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Health {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Health {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Message for Health {}
             impl Health {
                 #[cfg_attr(
@@ -8897,10 +10282,23 @@ Extended
                 /// size 8 bits
                 pub vendor_specific_status_code: u8,
             }
+            impl ::core::default::Default for Heartbeat {
+                fn default() -> Self {
+                    Self {
+                        uptime: ::core::default::Default::default(),
+                        health: ::core::default::Default::default(),
+                        mode: ::core::default::Default::default(),
+                        vendor_specific_status_code: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Heartbeat {
                 /// This type is delimited with an extent of 12 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(12);
             }
+            impl ::canadensis_encoding::SerializedSize for Heartbeat {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 7;
+            }
             impl ::canadensis_encoding::Message for Heartbeat {}
             impl Heartbeat {
                 #[cfg_attr(
@@ -8960,10 +10358,20 @@ Extended
                 /// size 16 bits
                 pub value: u16,
             }
+            impl ::core::default::Default for ID {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ID {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for ID {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Message for ID {}
             impl ID {}
             impl ::canadensis_encoding::Serialize for ID {
@@ -9025,10 +10433,22 @@ Extended
                 /// size 40 bits
                 pub num_errored: u64,
             }
+            impl ::core::default::Default for IOStatistics {
+                fn default() -> Self {
+                    Self {
+                        num_emitted: ::core::default::Default::default(),
+                        num_received: ::core::default::Default::default(),
+                        num_errored: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for IOStatistics {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for IOStatistics {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 15;
+            }
             impl ::canadensis_encoding::Message for IOStatistics {}
             impl IOStatistics {}
             impl ::canadensis_encoding::Serialize for IOStatistics {
@@ -9073,10 +10493,20 @@ Extended
                 /// size 3 bits
                 pub value: u8,
             }
+            impl ::core::default::Default for Mode {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Mode {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Mode {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Message for Mode {}
             impl Mode {
                 #[cfg_attr(not(doctest), doc = " Normal operating mode.")]
@@ -9133,10 +10563,18 @@ Extended
                     /// uavcan.node.port.ServiceID.1.0
                     ServiceId(crate::uavcan::node::port::service_id_1_0::ServiceID),
                 }
+                impl ::core::default::Default for ID {
+                    fn default() -> Self {
+                        Self::SubjectId(::core::default::Default::default())
+                    }
+                }
                 impl ::canadensis_encoding::DataType for ID {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for ID {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 3;
+                }
                 impl ::canadensis_encoding::Message for ID {}
                 impl ID {}
                 impl ::canadensis_encoding::Serialize for ID {
@@ -9175,7 +10613,7 @@ Extended
             #[cfg_attr(not(test), deprecated)]
             pub mod list_0_1 {
                 #[cfg_attr(not(doctest), doc = "The fixed subject ID for this message type")]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.node.port.List.1.0")]
                 pub const SUBJECT: ::canadensis_core::SubjectId =
                     ::canadensis_core::SubjectId::from_truncating(7510);
 
@@ -9187,7 +10625,7 @@ Extended
                     not(doctest),
                     doc = " A list of ports that this node is using:\n - Subjects published by this node (whether periodically or ad-hoc).\n - Subjects that this node is subscribed to (a datalogger or a debugger would typically subscribe to all subjects).\n - RPC services consumed by this node (i.e., service clients).\n - RPC services provided by this node (i.e., service servers).\n\n All nodes should implement this capability to provide network introspection and diagnostic capabilities.\n This message should be published using the fixed subject-ID as follows:\n - At the OPTIONAL priority level at least every MAX_PUBLICATION_PERIOD seconds.\n - At the OPTIONAL or SLOW priority level within MAX_PUBLICATION_PERIOD after the port configuration is changed.\n Replaced with v1."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.node.port.List.1.0")]
                 pub struct List {
                     ///
                     /// `uavcan.node.port.SubjectIDList.0.1`
@@ -9214,10 +10652,23 @@ Extended
                     /// size 512 bits
                     pub servers: crate::uavcan::node::port::service_id_list_0_1::ServiceIDList,
                 }
+                impl ::core::default::Default for List {
+                    fn default() -> Self {
+                        Self {
+                            publishers: ::core::default::Default::default(),
+                            subscribers: ::core::default::Default::default(),
+                            clients: ::core::default::Default::default(),
+                            servers: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for List {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for List {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2194;
+                }
                 impl ::canadensis_encoding::Message for List {}
                 impl List {
                     #[cfg_attr(
@@ -9228,9 +10679,9 @@ Extended
                 }
                 impl ::canadensis_encoding::Serialize for List {
                     fn size_bits(&self) -> usize {
-                        32 + (self.publishers).size_bits()
+                        32 + (self.publishers).size_bits().next_multiple_of(8)
                             + 32
-                            + (self.subscribers).size_bits()
+                            + (self.subscribers).size_bits().next_multiple_of(8)
                             + 32
                             + 512
                             + 32
@@ -9299,10 +10750,23 @@ Extended
                     /// size 512 bits
                     pub servers: crate::uavcan::node::port::service_id_list_1_0::ServiceIDList,
                 }
+                impl ::core::default::Default for List {
+                    fn default() -> Self {
+                        Self {
+                            publishers: ::core::default::Default::default(),
+                            subscribers: ::core::default::Default::default(),
+                            clients: ::core::default::Default::default(),
+                            servers: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for List {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for List {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2194;
+                }
                 impl ::canadensis_encoding::Message for List {}
                 impl List {
                     #[cfg_attr(
@@ -9313,9 +10777,9 @@ Extended
                 }
                 impl ::canadensis_encoding::Serialize for List {
                     fn size_bits(&self) -> usize {
-                        32 + (self.publishers).size_bits()
+                        32 + (self.publishers).size_bits().next_multiple_of(8)
                             + 32
-                            + (self.subscribers).size_bits()
+                            + (self.subscribers).size_bits().next_multiple_of(8)
                             + 32
                             + 512
                             + 32
@@ -9362,10 +10826,20 @@ Extended
                     /// size 9 bits
                     pub value: u16,
                 }
+                impl ::core::default::Default for ServiceID {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for ServiceID {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for ServiceID {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                }
                 impl ::canadensis_encoding::Message for ServiceID {}
                 impl ServiceID {
                     pub const MAX: u16 = 511;
@@ -9402,7 +10876,7 @@ Extended
                     not(doctest),
                     doc = " A list of service identifiers.\n This is a trivial constant-size bitmask with some reserved space in case the range of service-ID is increased\n in a future revision of the protocol.\n Replaced with v1."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.node.port.ServiceIDList.1.0")]
                 pub struct ServiceIDList {
                     #[cfg_attr(
                         not(doctest),
@@ -9415,10 +10889,20 @@ Extended
                     /// size 512 bits
                     pub mask: ::canadensis_encoding::bits::BitArray<64>,
                 }
+                impl ::core::default::Default for ServiceIDList {
+                    fn default() -> Self {
+                        Self {
+                            mask: ::canadensis_encoding::bits::BitArray::new(512),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for ServiceIDList {
                     /// This type is delimited with an extent of 128 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(128);
                 }
+                impl ::canadensis_encoding::SerializedSize for ServiceIDList {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 64;
+                }
                 impl ::canadensis_encoding::Message for ServiceIDList {}
                 impl ServiceIDList {
                     pub const CAPACITY: u16 = 512;
@@ -9469,10 +10953,20 @@ Extended
                     /// size 512 bits
                     pub mask: ::canadensis_encoding::bits::BitArray<64>,
                 }
+                impl ::core::default::Default for ServiceIDList {
+                    fn default() -> Self {
+                        Self {
+                            mask: ::canadensis_encoding::bits::BitArray::new(512),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for ServiceIDList {
                     /// This type is delimited with an extent of 128 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(128);
                 }
+                impl ::canadensis_encoding::SerializedSize for ServiceIDList {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 64;
+                }
                 impl ::canadensis_encoding::Message for ServiceIDList {}
                 impl ServiceIDList {
                     pub const CAPACITY: u16 = 512;
@@ -9519,10 +11013,20 @@ Extended
                     /// size 13 bits
                     pub value: u16,
                 }
+                impl ::core::default::Default for SubjectID {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for SubjectID {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for SubjectID {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                }
                 impl ::canadensis_encoding::Message for SubjectID {}
                 impl SubjectID {
                     pub const MAX: u16 = 8191;
@@ -9559,7 +11063,7 @@ Extended
                     not(doctest),
                     doc = " A list of subject identifiers.\n The range of subject-ID is large, so using a fixed-size bitmask would make this type difficult to handle on\n resource-constrained systems. To address that, we provide two extra options: a simple variable-length list,\n and a special case that indicates that every subject-ID is in use.\n Replaced with v1."
                 )]
-                #[deprecated]
+                #[deprecated(note = "Replaced by uavcan.node.port.SubjectIDList.1.0")]
                 pub enum SubjectIDList {
                     #[cfg_attr(
                         not(doctest),
@@ -9585,10 +11089,18 @@ Extended
                     /// uavcan.primitive.Empty.1.0
                     Total(crate::uavcan::primitive::empty_1_0::Empty),
                 }
+                impl ::core::default::Default for SubjectIDList {
+                    fn default() -> Self {
+                        Self::Mask(::core::default::Default::default())
+                    }
+                }
                 impl ::canadensis_encoding::DataType for SubjectIDList {
                     /// This type is delimited with an extent of 4097 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(4097);
                 }
+                impl ::canadensis_encoding::SerializedSize for SubjectIDList {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 1025;
+                }
                 impl ::canadensis_encoding::Message for SubjectIDList {}
                 impl SubjectIDList {
                     pub const CAPACITY: u16 = 8192;
@@ -9688,10 +11200,18 @@ Extended
                     /// uavcan.primitive.Empty.1.0
                     Total(crate::uavcan::primitive::empty_1_0::Empty),
                 }
+                impl ::core::default::Default for SubjectIDList {
+                    fn default() -> Self {
+                        Self::Mask(::core::default::Default::default())
+                    }
+                }
                 impl ::canadensis_encoding::DataType for SubjectIDList {
                     /// This type is delimited with an extent of 4097 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(4097);
                 }
+                impl ::canadensis_encoding::SerializedSize for SubjectIDList {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 1025;
+                }
                 impl ::canadensis_encoding::Message for SubjectIDList {}
                 impl SubjectIDList {
                     pub const CAPACITY: u16 = 8192;
@@ -9783,10 +11303,21 @@ Extended
                 /// size 8 bits
                 pub minor: u8,
             }
+            impl ::core::default::Default for Version {
+                fn default() -> Self {
+                    Self {
+                        major: ::core::default::Default::default(),
+                        minor: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Version {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Version {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Message for Version {}
             impl Version {}
             impl ::canadensis_encoding::Serialize for Version {
@@ -9867,10 +11398,24 @@ Extended
                     /// size ranges from 0 to 176 bits
                     pub entries: ::heapless::Vec<crate::uavcan::pnp::cluster::entry_1_0::Entry, 1>,
                 }
+                impl ::core::default::Default for AppendEntriesRequest {
+                    fn default() -> Self {
+                        Self {
+                            term: ::core::default::Default::default(),
+                            prev_log_term: ::core::default::Default::default(),
+                            prev_log_index: ::core::default::Default::default(),
+                            leader_commit: ::core::default::Default::default(),
+                            entries: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for AppendEntriesRequest {
                     /// This type is delimited with an extent of 96 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(96);
                 }
+                impl ::canadensis_encoding::SerializedSize for AppendEntriesRequest {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 35;
+                }
                 impl ::canadensis_encoding::Request for AppendEntriesRequest {}
                 impl AppendEntriesRequest {
                     #[cfg_attr(not(doctest), doc = " [second]")]
@@ -9945,10 +11490,21 @@ Extended
                     /// size 1 bits
                     pub success: bool,
                 }
+                impl ::core::default::Default for AppendEntriesResponse {
+                    fn default() -> Self {
+                        Self {
+                            term: ::core::default::Default::default(),
+                            success: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for AppendEntriesResponse {
                     /// This type is delimited with an extent of 48 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(48);
                 }
+                impl ::canadensis_encoding::SerializedSize for AppendEntriesResponse {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 5;
+                }
                 impl ::canadensis_encoding::Response for AppendEntriesResponse {}
                 impl AppendEntriesResponse {}
                 impl ::canadensis_encoding::Serialize for AppendEntriesResponse {
@@ -10010,10 +11566,21 @@ Extended
                     /// size ranges from 0 to 80 bits
                     pub known_nodes: ::heapless::Vec<crate::uavcan::node::id_1_0::ID, 5>,
                 }
+                impl ::core::default::Default for Discovery {
+                    fn default() -> Self {
+                        Self {
+                            configured_cluster_size: ::core::default::Default::default(),
+                            known_nodes: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Discovery {
                     /// This type is delimited with an extent of 96 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(96);
                 }
+                impl ::canadensis_encoding::SerializedSize for Discovery {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                }
                 impl ::canadensis_encoding::Message for Discovery {}
                 impl Discovery {
                     #[cfg_attr(
@@ -10105,10 +11672,24 @@ Extended
                     /// size 16 bits
                     pub node_id: crate::uavcan::node::id_1_0::ID,
                 }
+                impl ::core::default::Default for Entry {
+                    fn default() -> Self {
+                        Self {
+                            term: ::core::default::Default::default(),
+                            unique_id: ::core::array::from_fn(|_| {
+                                ::core::default::Default::default()
+                            }),
+                            node_id: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Entry {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Entry {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 22;
+                }
                 impl ::canadensis_encoding::Message for Entry {}
                 impl Entry {}
                 impl ::canadensis_encoding::Serialize for Entry {
@@ -10173,10 +11754,22 @@ Extended
                     /// size 16 bits
                     pub last_log_index: u16,
                 }
+                impl ::core::default::Default for RequestVoteRequest {
+                    fn default() -> Self {
+                        Self {
+                            term: ::core::default::Default::default(),
+                            last_log_term: ::core::default::Default::default(),
+                            last_log_index: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for RequestVoteRequest {
                     /// This type is delimited with an extent of 48 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(48);
                 }
+                impl ::canadensis_encoding::SerializedSize for RequestVoteRequest {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 10;
+                }
                 impl ::canadensis_encoding::Request for RequestVoteRequest {}
                 impl RequestVoteRequest {}
                 impl ::canadensis_encoding::Serialize for RequestVoteRequest {
@@ -10230,10 +11823,21 @@ Extended
                     /// size 1 bits
                     pub vote_granted: bool,
                 }
+                impl ::core::default::Default for RequestVoteResponse {
+                    fn default() -> Self {
+                        Self {
+                            term: ::core::default::Default::default(),
+                            vote_granted: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for RequestVoteResponse {
                     /// This type is delimited with an extent of 48 bytes.
                     const EXTENT_BYTES: Option<u32> = Some(48);
                 }
+                impl ::canadensis_encoding::SerializedSize for RequestVoteResponse {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 5;
+                }
                 impl ::canadensis_encoding::Response for RequestVoteResponse {}
                 impl RequestVoteResponse {}
                 impl ::canadensis_encoding::Serialize for RequestVoteResponse {
@@ -10295,10 +11899,21 @@ Extended
                 /// size ranges from 0 to 16 bits
                 pub allocated_node_id: ::heapless::Vec<crate::uavcan::node::id_1_0::ID, 1>,
             }
+            impl ::core::default::Default for NodeIDAllocationData {
+                fn default() -> Self {
+                    Self {
+                        unique_id_hash: ::core::default::Default::default(),
+                        allocated_node_id: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for NodeIDAllocationData {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for NodeIDAllocationData {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 9;
+            }
             impl ::canadensis_encoding::Message for NodeIDAllocationData {}
             impl NodeIDAllocationData {}
             impl ::canadensis_encoding::Serialize for NodeIDAllocationData {
@@ -10375,10 +11990,21 @@ Extended
                 /// size 128 bits
                 pub unique_id: [u8; 16],
             }
+            impl ::core::default::Default for NodeIDAllocationData {
+                fn default() -> Self {
+                    Self {
+                        node_id: ::core::default::Default::default(),
+                        unique_id: ::core::array::from_fn(|_| ::core::default::Default::default()),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for NodeIDAllocationData {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for NodeIDAllocationData {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 18;
+            }
             impl ::canadensis_encoding::Message for NodeIDAllocationData {}
             impl NodeIDAllocationData {}
             impl ::canadensis_encoding::Serialize for NodeIDAllocationData {
@@ -10432,10 +12058,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::canadensis_encoding::bits::BitArray<256>,
                 }
+                impl ::core::default::Default for Bit {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Bit {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Bit {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+                }
                 impl ::canadensis_encoding::Message for Bit {}
                 impl Bit {}
                 impl ::canadensis_encoding::Serialize for Bit {
@@ -10480,10 +12116,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<i16, 128>,
                 }
+                impl ::core::default::Default for Integer16 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer16 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer16 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Integer16 {}
                 impl Integer16 {}
                 impl ::canadensis_encoding::Serialize for Integer16 {
@@ -10536,10 +12182,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<i32, 64>,
                 }
+                impl ::core::default::Default for Integer32 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer32 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer32 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Integer32 {}
                 impl Integer32 {}
                 impl ::canadensis_encoding::Serialize for Integer32 {
@@ -10592,10 +12248,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<i64, 32>,
                 }
+                impl ::core::default::Default for Integer64 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer64 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer64 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Integer64 {}
                 impl Integer64 {}
                 impl ::canadensis_encoding::Serialize for Integer64 {
@@ -10648,10 +12314,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<i8, 256>,
                 }
+                impl ::core::default::Default for Integer8 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer8 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer8 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+                }
                 impl ::canadensis_encoding::Message for Integer8 {}
                 impl Integer8 {}
                 impl ::canadensis_encoding::Serialize for Integer8 {
@@ -10704,10 +12380,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<u16, 128>,
                 }
+                impl ::core::default::Default for Natural16 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural16 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural16 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Natural16 {}
                 impl Natural16 {}
                 impl ::canadensis_encoding::Serialize for Natural16 {
@@ -10760,10 +12446,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<u32, 64>,
                 }
+                impl ::core::default::Default for Natural32 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural32 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural32 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Natural32 {}
                 impl Natural32 {}
                 impl ::canadensis_encoding::Serialize for Natural32 {
@@ -10816,10 +12512,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<u64, 32>,
                 }
+                impl ::core::default::Default for Natural64 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural64 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural64 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Natural64 {}
                 impl Natural64 {}
                 impl ::canadensis_encoding::Serialize for Natural64 {
@@ -10872,10 +12578,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<u8, 256>,
                 }
+                impl ::core::default::Default for Natural8 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural8 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural8 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+                }
                 impl ::canadensis_encoding::Message for Natural8 {}
                 impl Natural8 {}
                 impl ::canadensis_encoding::Serialize for Natural8 {
@@ -10899,9 +12615,8 @@ Extended
                                 let length = cursor.read_u16() as _;
                                 if length <= 256 {
                                     let mut elements = ::heapless::Vec::new();
-                                    for _ in 0..length {
-                                        let _ = elements.push(cursor.read_u8() as _);
-                                    }
+                                    let _ = elements.resize(length, 0);
+                                    cursor.read_bytes(&mut elements);
                                     elements
                                 } else {
                                     return Err(
@@ -10927,10 +12642,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<::half::f16, 128>,
                 }
+                impl ::core::default::Default for Real16 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Real16 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Real16 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Real16 {}
                 impl Real16 {}
                 impl ::canadensis_encoding::Serialize for Real16 {
@@ -10987,10 +12712,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<f32, 64>,
                 }
+                impl ::core::default::Default for Real32 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Real32 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Real32 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Real32 {}
                 impl Real32 {}
                 impl ::canadensis_encoding::Serialize for Real32 {
@@ -11047,10 +12782,20 @@ Extended
                     /// size ranges from 0 to 2048 bits
                     pub value: ::heapless::Vec<f64, 32>,
                 }
+                impl ::core::default::Default for Real64 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Real64 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Real64 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 257;
+                }
                 impl ::canadensis_encoding::Message for Real64 {}
                 impl Real64 {}
                 impl ::canadensis_encoding::Serialize for Real64 {
@@ -11099,10 +12844,18 @@ Extended
             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
             #[repr(C, packed)]
             pub struct Empty {}
+            impl ::core::default::Default for Empty {
+                fn default() -> Self {
+                    Self {}
+                }
+            }
             impl ::canadensis_encoding::DataType for Empty {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Empty {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+            }
             impl ::canadensis_encoding::Message for Empty {}
             impl Empty {}
             impl ::canadensis_encoding::Serialize for Empty {
@@ -11142,10 +12895,20 @@ Extended
                     /// size 1 bits
                     pub value: bool,
                 }
+                impl ::core::default::Default for Bit {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Bit {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Bit {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+                }
                 impl ::canadensis_encoding::Message for Bit {}
                 impl Bit {}
                 impl ::canadensis_encoding::Serialize for Bit {
@@ -11184,10 +12947,20 @@ Extended
                     /// size 16 bits
                     pub value: i16,
                 }
+                impl ::core::default::Default for Integer16 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer16 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer16 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                }
                 impl ::canadensis_encoding::Message for Integer16 {}
                 impl Integer16 {}
                 impl ::canadensis_encoding::Serialize for Integer16 {
@@ -11229,10 +13002,20 @@ Extended
                     /// size 32 bits
                     pub value: i32,
                 }
+                impl ::core::default::Default for Integer32 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer32 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer32 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                }
                 impl ::canadensis_encoding::Message for Integer32 {}
                 impl Integer32 {}
                 impl ::canadensis_encoding::Serialize for Integer32 {
@@ -11274,10 +13057,20 @@ Extended
                     /// size 64 bits
                     pub value: i64,
                 }
+                impl ::core::default::Default for Integer64 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer64 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer64 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                }
                 impl ::canadensis_encoding::Message for Integer64 {}
                 impl Integer64 {}
                 impl ::canadensis_encoding::Serialize for Integer64 {
@@ -11319,10 +13112,20 @@ Extended
                     /// size 8 bits
                     pub value: i8,
                 }
+                impl ::core::default::Default for Integer8 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Integer8 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Integer8 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+                }
                 impl ::canadensis_encoding::Message for Integer8 {}
                 impl Integer8 {}
                 impl ::canadensis_encoding::Serialize for Integer8 {
@@ -11364,10 +13167,20 @@ Extended
                     /// size 16 bits
                     pub value: u16,
                 }
+                impl ::core::default::Default for Natural16 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural16 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural16 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                }
                 impl ::canadensis_encoding::Message for Natural16 {}
                 impl Natural16 {}
                 impl ::canadensis_encoding::Serialize for Natural16 {
@@ -11409,10 +13222,20 @@ Extended
                     /// size 32 bits
                     pub value: u32,
                 }
+                impl ::core::default::Default for Natural32 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural32 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural32 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                }
                 impl ::canadensis_encoding::Message for Natural32 {}
                 impl Natural32 {}
                 impl ::canadensis_encoding::Serialize for Natural32 {
@@ -11454,10 +13277,20 @@ Extended
                     /// size 64 bits
                     pub value: u64,
                 }
+                impl ::core::default::Default for Natural64 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural64 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural64 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                }
                 impl ::canadensis_encoding::Message for Natural64 {}
                 impl Natural64 {}
                 impl ::canadensis_encoding::Serialize for Natural64 {
@@ -11499,10 +13332,20 @@ Extended
                     /// size 8 bits
                     pub value: u8,
                 }
+                impl ::core::default::Default for Natural8 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Natural8 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Natural8 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+                }
                 impl ::canadensis_encoding::Message for Natural8 {}
                 impl Natural8 {}
                 impl ::canadensis_encoding::Serialize for Natural8 {
@@ -11548,10 +13391,20 @@ Extended
                     /// size 16 bits
                     pub value: ::half::f16,
                 }
+                impl ::core::default::Default for Real16 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Real16 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Real16 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+                }
                 impl ::canadensis_encoding::Message for Real16 {}
                 impl Real16 {}
                 impl ::canadensis_encoding::Serialize for Real16 {
@@ -11597,10 +13450,20 @@ Extended
                     /// size 32 bits
                     pub value: f32,
                 }
+                impl ::core::default::Default for Real32 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Real32 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Real32 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                }
                 impl ::canadensis_encoding::Message for Real32 {}
                 impl Real32 {}
                 impl ::canadensis_encoding::Serialize for Real32 {
@@ -11646,10 +13509,20 @@ Extended
                     /// size 64 bits
                     pub value: f64,
                 }
+                impl ::core::default::Default for Real64 {
+                    fn default() -> Self {
+                        Self {
+                            value: ::core::default::Default::default(),
+                        }
+                    }
+                }
                 impl ::canadensis_encoding::DataType for Real64 {
                     /// This type is sealed.
                     const EXTENT_BYTES: Option<u32> = None;
                 }
+                impl ::canadensis_encoding::SerializedSize for Real64 {
+                    const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                }
                 impl ::canadensis_encoding::Message for Real64 {}
                 impl Real64 {}
                 impl ::canadensis_encoding::Serialize for Real64 {
@@ -11694,10 +13567,20 @@ Extended
                 /// size ranges from 0 to 2048 bits
                 pub value: ::heapless::Vec<u8, 256>,
             }
+            impl ::core::default::Default for String {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for String {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for String {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+            }
             impl ::canadensis_encoding::Message for String {}
             impl String {}
             impl ::canadensis_encoding::Serialize for String {
@@ -11721,9 +13604,8 @@ Extended
                             let length = cursor.read_u16() as _;
                             if length <= 256 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -11750,10 +13632,20 @@ Extended
                 /// size ranges from 0 to 2048 bits
                 pub value: ::heapless::Vec<u8, 256>,
             }
+            impl ::core::default::Default for Unstructured {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Unstructured {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Unstructured {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 258;
+            }
             impl ::canadensis_encoding::Message for Unstructured {}
             impl Unstructured {}
             impl ::canadensis_encoding::Serialize for Unstructured {
@@ -11777,9 +13669,8 @@ Extended
                             let length = cursor.read_u16() as _;
                             if length <= 256 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -11826,15 +13717,28 @@ Extended
                 /// size ranges from 8 to 2072 bits
                 pub value: crate::uavcan::register::value_1_0::Value,
             }
+            impl ::core::default::Default for AccessRequest {
+                fn default() -> Self {
+                    Self {
+                        name: ::core::default::Default::default(),
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for AccessRequest {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for AccessRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 515;
+            }
             impl ::canadensis_encoding::Request for AccessRequest {}
             impl AccessRequest {}
             impl ::canadensis_encoding::Serialize for AccessRequest {
                 fn size_bits(&self) -> usize {
-                    (self.name).size_bits() + (self.value).size_bits() + 0
+                    (self.name).size_bits().next_multiple_of(8)
+                        + (self.value).size_bits().next_multiple_of(8)
+                        + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_composite(&self.name);
@@ -11903,15 +13807,28 @@ Extended
                 /// size ranges from 8 to 2072 bits
                 pub value: crate::uavcan::register::value_1_0::Value,
             }
+            impl ::core::default::Default for AccessResponse {
+                fn default() -> Self {
+                    Self {
+                        timestamp: ::core::default::Default::default(),
+                        mutable: ::core::default::Default::default(),
+                        persistent: ::core::default::Default::default(),
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for AccessResponse {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for AccessResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 267;
+            }
             impl ::canadensis_encoding::Response for AccessResponse {}
             impl AccessResponse {}
             impl ::canadensis_encoding::Serialize for AccessResponse {
                 fn size_bits(&self) -> usize {
-                    56 + 1 + 1 + 6 + (self.value).size_bits() + 0
+                    56 + 1 + 1 + 6 + (self.value).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_composite(&self.timestamp);
@@ -11963,10 +13880,20 @@ Extended
                 /// size 16 bits
                 pub index: u16,
             }
+            impl ::core::default::Default for ListRequest {
+                fn default() -> Self {
+                    Self {
+                        index: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ListRequest {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for ListRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Request for ListRequest {}
             impl ListRequest {}
             impl ::canadensis_encoding::Serialize for ListRequest {
@@ -12009,15 +13936,25 @@ Extended
                 /// size ranges from 8 to 2048 bits
                 pub name: crate::uavcan::register::name_1_0::Name,
             }
+            impl ::core::default::Default for ListResponse {
+                fn default() -> Self {
+                    Self {
+                        name: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for ListResponse {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for ListResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 256;
+            }
             impl ::canadensis_encoding::Response for ListResponse {}
             impl ListResponse {}
             impl ::canadensis_encoding::Serialize for ListResponse {
                 fn size_bits(&self) -> usize {
-                    (self.name).size_bits() + 0
+                    (self.name).size_bits().next_multiple_of(8) + 0
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
                     cursor.write_composite(&self.name);
@@ -12050,10 +13987,20 @@ Extended
                 /// size ranges from 0 to 2040 bits
                 pub name: ::heapless::Vec<u8, 255>,
             }
+            impl ::core::default::Default for Name {
+                fn default() -> Self {
+                    Self {
+                        name: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Name {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Name {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 256;
+            }
             impl ::canadensis_encoding::Message for Name {}
             impl Name {}
             impl ::canadensis_encoding::Serialize for Name {
@@ -12077,9 +14024,8 @@ Extended
                             let length = cursor.read_u8() as _;
                             if length <= 255 {
                                 let mut elements = ::heapless::Vec::new();
-                                for _ in 0..length {
-                                    let _ = elements.push(cursor.read_u8() as _);
-                                }
+                                let _ = elements.resize(length, 0);
+                                cursor.read_bytes(&mut elements);
                                 elements
                             } else {
                                 return Err(::canadensis_encoding::DeserializeError::ArrayLength);
@@ -12169,30 +14115,38 @@ Extended
                 /// uavcan.primitive.array.Real16.1.0
                 Real16(crate::uavcan::primitive::array::real16_1_0::Real16),
             }
+            impl ::core::default::Default for Value {
+                fn default() -> Self {
+                    Self::Empty(::core::default::Default::default())
+                }
+            }
             impl ::canadensis_encoding::DataType for Value {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Value {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 259;
+            }
             impl ::canadensis_encoding::Message for Value {}
             impl Value {}
             impl ::canadensis_encoding::Serialize for Value {
                 fn size_bits(&self) -> usize {
                     8 + match self {
                         Value::Empty(inner) => 0,
-                        Value::String(inner) => (inner).size_bits(),
-                        Value::Unstructured(inner) => (inner).size_bits(),
-                        Value::Bit(inner) => (inner).size_bits(),
-                        Value::Integer64(inner) => (inner).size_bits(),
-                        Value::Integer32(inner) => (inner).size_bits(),
-                        Value::Integer16(inner) => (inner).size_bits(),
-                        Value::Integer8(inner) => (inner).size_bits(),
-                        Value::Natural64(inner) => (inner).size_bits(),
-                        Value::Natural32(inner) => (inner).size_bits(),
-                        Value::Natural16(inner) => (inner).size_bits(),
-                        Value::Natural8(inner) => (inner).size_bits(),
-                        Value::Real64(inner) => (inner).size_bits(),
-                        Value::Real32(inner) => (inner).size_bits(),
-                        Value::Real16(inner) => (inner).size_bits(),
+                        Value::String(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Unstructured(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Bit(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Integer64(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Integer32(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Integer16(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Integer8(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Natural64(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Natural32(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Natural16(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Natural8(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Real64(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Real32(inner) => (inner).size_bits().next_multiple_of(8),
+                        Value::Real16(inner) => (inner).size_bits().next_multiple_of(8),
                     }
                 }
                 fn serialize(&self, cursor: &mut ::canadensis_encoding::WriteCursor<'_>) {
@@ -12312,10 +14266,21 @@ Extended
                         /// size 32 bits
                         pub meter_per_second_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter_per_second_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12361,10 +14326,23 @@ Extended
                         /// size 96 bits
                         pub meter_per_second_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter_per_second_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -12416,10 +14394,23 @@ Extended
                         /// size 128 bits
                         pub wxyz: [f32; 4],
                     }
+                    impl ::core::default::Default for Quaternion {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                wxyz: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Quaternion {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Quaternion {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 23;
+                    }
                     impl ::canadensis_encoding::Message for Quaternion {}
                     impl Quaternion {}
                     impl ::canadensis_encoding::Serialize for Quaternion {
@@ -12474,10 +14465,21 @@ Extended
                         /// size 32 bits
                         pub radian: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                radian: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12525,10 +14527,21 @@ Extended
                         /// size 32 bits
                         pub radian_per_second_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                radian_per_second_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12574,10 +14587,23 @@ Extended
                         /// size 96 bits
                         pub radian_per_second_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                radian_per_second_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -12629,10 +14655,21 @@ Extended
                         /// size 32 bits
                         pub radian_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                radian_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12678,10 +14715,23 @@ Extended
                         /// size 96 bits
                         pub radian_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                radian_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -12733,10 +14783,21 @@ Extended
                         /// size 32 bits
                         pub second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12782,10 +14843,21 @@ Extended
                         /// size 64 bits
                         pub second: f64,
                     }
+                    impl ::core::default::Default for WideScalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for WideScalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for WideScalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 15;
+                    }
                     impl ::canadensis_encoding::Message for WideScalar {}
                     impl WideScalar {}
                     impl ::canadensis_encoding::Serialize for WideScalar {
@@ -12833,10 +14905,21 @@ Extended
                         /// size 32 bits
                         pub coulomb: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                coulomb: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12884,10 +14967,21 @@ Extended
                         /// size 32 bits
                         pub ampere: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                ampere: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12935,10 +15029,21 @@ Extended
                         /// size 32 bits
                         pub joule: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                joule: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -12986,10 +15091,21 @@ Extended
                         /// size 32 bits
                         pub newton: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                newton: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13035,10 +15151,23 @@ Extended
                         /// size 96 bits
                         pub newton: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                newton: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -13090,10 +15219,21 @@ Extended
                         /// size 32 bits
                         pub hertz: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                hertz: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13141,10 +15281,21 @@ Extended
                         /// size 32 bits
                         pub meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13190,10 +15341,23 @@ Extended
                         /// size 96 bits
                         pub meter: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -13243,10 +15407,21 @@ Extended
                         /// size 64 bits
                         pub meter: f64,
                     }
+                    impl ::core::default::Default for WideScalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for WideScalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for WideScalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 15;
+                    }
                     impl ::canadensis_encoding::Message for WideScalar {}
                     impl WideScalar {}
                     impl ::canadensis_encoding::Serialize for WideScalar {
@@ -13292,10 +15467,23 @@ Extended
                         /// size 192 bits
                         pub meter: [f64; 3],
                     }
+                    impl ::core::default::Default for WideVector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for WideVector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for WideVector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 31;
+                    }
                     impl ::canadensis_encoding::Message for WideVector3 {}
                     impl WideVector3 {}
                     impl ::canadensis_encoding::Serialize for WideVector3 {
@@ -13347,10 +15535,21 @@ Extended
                         /// size 32 bits
                         pub candela_per_square_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                candela_per_square_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13389,7 +15588,9 @@ Extended
                         not(doctest),
                         doc = " Use v1.1 instead where the unit of measure is named correctly."
                     )]
-                    #[deprecated]
+                    #[deprecated(
+                        note = "Replaced by uavcan.si.sample.magnetic_field_strength.Scalar.1.1"
+                    )]
                     pub struct Scalar {
                         ///
                         /// `uavcan.time.SynchronizedTimestamp.1.0`
@@ -13405,10 +15606,21 @@ Extended
                         /// size 32 bits
                         pub tesla: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                tesla: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13454,10 +15666,21 @@ Extended
                         /// size 32 bits
                         pub ampere_per_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                ampere_per_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13494,7 +15717,9 @@ Extended
                         not(doctest),
                         doc = " Use v1.1 instead where the unit of measure is named correctly."
                     )]
-                    #[deprecated]
+                    #[deprecated(
+                        note = "Replaced by uavcan.si.sample.magnetic_field_strength.Vector3.1.1"
+                    )]
                     pub struct Vector3 {
                         ///
                         /// `uavcan.time.SynchronizedTimestamp.1.0`
@@ -13510,10 +15735,23 @@ Extended
                         /// size 96 bits
                         pub tesla: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                tesla: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -13563,10 +15801,23 @@ Extended
                         /// size 96 bits
                         pub ampere_per_meter: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                ampere_per_meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -13618,10 +15869,21 @@ Extended
                         /// size 32 bits
                         pub tesla: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                tesla: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13667,10 +15929,23 @@ Extended
                         /// size 96 bits
                         pub tesla: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                tesla: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -13722,10 +15997,21 @@ Extended
                         /// size 32 bits
                         pub kilogram: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                kilogram: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13773,10 +16059,21 @@ Extended
                         /// size 32 bits
                         pub watt: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                watt: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13824,10 +16121,21 @@ Extended
                         /// size 32 bits
                         pub pascal: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                pascal: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13875,10 +16183,21 @@ Extended
                         /// size 32 bits
                         pub kelvin: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                kelvin: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13926,10 +16245,21 @@ Extended
                         /// size 32 bits
                         pub newton_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                newton_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -13975,10 +16305,23 @@ Extended
                         /// size 96 bits
                         pub newton_meter: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                newton_meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -14030,10 +16373,21 @@ Extended
                         /// size 32 bits
                         pub meter_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14079,10 +16433,23 @@ Extended
                         /// size 96 bits
                         pub meter_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                meter_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 19;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -14134,10 +16501,21 @@ Extended
                         /// size 32 bits
                         pub volt: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                volt: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14185,10 +16563,21 @@ Extended
                         /// size 32 bits
                         pub cubic_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                cubic_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14236,10 +16625,21 @@ Extended
                         /// size 32 bits
                         pub cubic_meter_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                timestamp: ::core::default::Default::default(),
+                                cubic_meter_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 11;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14284,10 +16684,20 @@ Extended
                         /// size 32 bits
                         pub meter_per_second_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                meter_per_second_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14332,10 +16742,22 @@ Extended
                         /// size 96 bits
                         pub meter_per_second_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                meter_per_second_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -14382,10 +16804,22 @@ Extended
                         /// size 128 bits
                         pub wxyz: [f32; 4],
                     }
+                    impl ::core::default::Default for Quaternion {
+                        fn default() -> Self {
+                            Self {
+                                wxyz: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Quaternion {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Quaternion {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 16;
+                    }
                     impl ::canadensis_encoding::Message for Quaternion {}
                     impl Quaternion {}
                     impl ::canadensis_encoding::Serialize for Quaternion {
@@ -14427,10 +16861,20 @@ Extended
                         /// size 32 bits
                         pub radian: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                radian: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14474,10 +16918,20 @@ Extended
                         /// size 32 bits
                         pub radian_per_second_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                radian_per_second_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14522,10 +16976,22 @@ Extended
                         /// size 96 bits
                         pub radian_per_second_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                radian_per_second_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -14572,10 +17038,20 @@ Extended
                         /// size 32 bits
                         pub radian_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                radian_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14617,10 +17093,22 @@ Extended
                         /// size 96 bits
                         pub radian_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                radian_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -14664,10 +17152,20 @@ Extended
                         /// size 32 bits
                         pub second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14709,10 +17207,20 @@ Extended
                         /// size 64 bits
                         pub second: f64,
                     }
+                    impl ::core::default::Default for WideScalar {
+                        fn default() -> Self {
+                            Self {
+                                second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for WideScalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for WideScalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                    }
                     impl ::canadensis_encoding::Message for WideScalar {}
                     impl WideScalar {}
                     impl ::canadensis_encoding::Serialize for WideScalar {
@@ -14756,10 +17264,20 @@ Extended
                         /// size 32 bits
                         pub coulomb: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                coulomb: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14803,10 +17321,20 @@ Extended
                         /// size 32 bits
                         pub ampere: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                ampere: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14850,10 +17378,20 @@ Extended
                         /// size 32 bits
                         pub joule: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                joule: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14897,10 +17435,20 @@ Extended
                         /// size 32 bits
                         pub newton: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                newton: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -14942,10 +17490,22 @@ Extended
                         /// size 96 bits
                         pub newton: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                newton: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -14989,10 +17549,20 @@ Extended
                         /// size 32 bits
                         pub hertz: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                hertz: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15036,10 +17606,20 @@ Extended
                         /// size 32 bits
                         pub meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15081,10 +17661,22 @@ Extended
                         /// size 96 bits
                         pub meter: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -15126,10 +17718,20 @@ Extended
                         /// size 64 bits
                         pub meter: f64,
                     }
+                    impl ::core::default::Default for WideScalar {
+                        fn default() -> Self {
+                            Self {
+                                meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for WideScalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for WideScalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 8;
+                    }
                     impl ::canadensis_encoding::Message for WideScalar {}
                     impl WideScalar {}
                     impl ::canadensis_encoding::Serialize for WideScalar {
@@ -15171,10 +17773,22 @@ Extended
                         /// size 192 bits
                         pub meter: [f64; 3],
                     }
+                    impl ::core::default::Default for WideVector3 {
+                        fn default() -> Self {
+                            Self {
+                                meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for WideVector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for WideVector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 24;
+                    }
                     impl ::canadensis_encoding::Message for WideVector3 {}
                     impl WideVector3 {}
                     impl ::canadensis_encoding::Serialize for WideVector3 {
@@ -15218,10 +17832,20 @@ Extended
                         /// size 32 bits
                         pub candela_per_square_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                candela_per_square_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15266,7 +17890,9 @@ Extended
                     )]
                     #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                     #[repr(C, packed)]
-                    #[deprecated]
+                    #[deprecated(
+                        note = "Replaced by uavcan.si.unit.magnetic_field_strength.Scalar.1.1"
+                    )]
                     pub struct Scalar {
                         ///
                         /// `saturated float32`
@@ -15275,10 +17901,20 @@ Extended
                         /// size 32 bits
                         pub tesla: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                tesla: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15320,10 +17956,20 @@ Extended
                         /// size 32 bits
                         pub ampere_per_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                ampere_per_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15363,7 +18009,9 @@ Extended
                     )]
                     #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
                     #[repr(C, packed)]
-                    #[deprecated]
+                    #[deprecated(
+                        note = "Replaced by uavcan.si.unit.magnetic_field_strength.Vector3.1.1"
+                    )]
                     pub struct Vector3 {
                         ///
                         /// `saturated float32[3]`
@@ -15372,10 +18020,22 @@ Extended
                         /// size 96 bits
                         pub tesla: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                tesla: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -15417,10 +18077,22 @@ Extended
                         /// size 96 bits
                         pub ampere_per_meter: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                ampere_per_meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -15464,10 +18136,20 @@ Extended
                         /// size 32 bits
                         pub tesla: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                tesla: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15509,10 +18191,22 @@ Extended
                         /// size 96 bits
                         pub tesla: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                tesla: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -15556,10 +18250,20 @@ Extended
                         /// size 32 bits
                         pub kilogram: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                kilogram: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15603,10 +18307,20 @@ Extended
                         /// size 32 bits
                         pub watt: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                watt: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15650,10 +18364,20 @@ Extended
                         /// size 32 bits
                         pub pascal: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                pascal: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15697,10 +18421,20 @@ Extended
                         /// size 32 bits
                         pub kelvin: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                kelvin: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15744,10 +18478,20 @@ Extended
                         /// size 32 bits
                         pub newton_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                newton_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15789,10 +18533,22 @@ Extended
                         /// size 96 bits
                         pub newton_meter: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                newton_meter: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -15836,10 +18592,20 @@ Extended
                         /// size 32 bits
                         pub meter_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                meter_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15881,10 +18647,22 @@ Extended
                         /// size 96 bits
                         pub meter_per_second: [f32; 3],
                     }
+                    impl ::core::default::Default for Vector3 {
+                        fn default() -> Self {
+                            Self {
+                                meter_per_second: ::core::array::from_fn(|_| {
+                                    ::core::default::Default::default()
+                                }),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Vector3 {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Vector3 {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 12;
+                    }
                     impl ::canadensis_encoding::Message for Vector3 {}
                     impl Vector3 {}
                     impl ::canadensis_encoding::Serialize for Vector3 {
@@ -15928,10 +18706,20 @@ Extended
                         /// size 32 bits
                         pub volt: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                volt: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -15975,10 +18763,20 @@ Extended
                         /// size 32 bits
                         pub cubic_meter: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                cubic_meter: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -16022,10 +18820,20 @@ Extended
                         /// size 32 bits
                         pub cubic_meter_per_second: f32,
                     }
+                    impl ::core::default::Default for Scalar {
+                        fn default() -> Self {
+                            Self {
+                                cubic_meter_per_second: ::core::default::Default::default(),
+                            }
+                        }
+                    }
                     impl ::canadensis_encoding::DataType for Scalar {
                         /// This type is sealed.
                         const EXTENT_BYTES: Option<u32> = None;
                     }
+                    impl ::canadensis_encoding::SerializedSize for Scalar {
+                        const MAX_SERIALIZED_SIZE_BYTES: usize = 4;
+                    }
                     impl ::canadensis_encoding::Message for Scalar {}
                     impl Scalar {}
                     impl ::canadensis_encoding::Serialize for Scalar {
@@ -16075,10 +18883,18 @@ Extended
             #[derive(::zerocopy::FromBytes, ::zerocopy::AsBytes)]
             #[repr(C, packed)]
             pub struct GetSynchronizationMasterInfoRequest {}
+            impl ::core::default::Default for GetSynchronizationMasterInfoRequest {
+                fn default() -> Self {
+                    Self {}
+                }
+            }
             impl ::canadensis_encoding::DataType for GetSynchronizationMasterInfoRequest {
                 /// This type is delimited with an extent of 48 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(48);
             }
+            impl ::canadensis_encoding::SerializedSize for GetSynchronizationMasterInfoRequest {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 0;
+            }
             impl ::canadensis_encoding::Request for GetSynchronizationMasterInfoRequest {}
             impl GetSynchronizationMasterInfoRequest {}
             impl ::canadensis_encoding::Serialize for GetSynchronizationMasterInfoRequest {
@@ -16143,10 +18959,22 @@ Extended
                 /// size 16 bits
                 pub tai_info: crate::uavcan::time::tai_info_0_1::TAIInfo,
             }
+            impl ::core::default::Default for GetSynchronizationMasterInfoResponse {
+                fn default() -> Self {
+                    Self {
+                        error_variance: ::core::default::Default::default(),
+                        time_system: ::core::default::Default::default(),
+                        tai_info: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for GetSynchronizationMasterInfoResponse {
                 /// This type is delimited with an extent of 192 bytes.
                 const EXTENT_BYTES: Option<u32> = Some(192);
             }
+            impl ::canadensis_encoding::SerializedSize for GetSynchronizationMasterInfoResponse {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 7;
+            }
             impl ::canadensis_encoding::Response for GetSynchronizationMasterInfoResponse {}
             impl GetSynchronizationMasterInfoResponse {}
             impl ::canadensis_encoding::Serialize for GetSynchronizationMasterInfoResponse {
@@ -16199,10 +19027,21 @@ Extended
                 /// size 56 bits
                 pub previous_transmission_timestamp_microsecond: u64,
             }
+            impl ::core::default::Default for Synchronization {
+                fn default() -> Self {
+                    Self {
+                        previous_transmission_timestamp_microsecond:
+                            ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for Synchronization {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for Synchronization {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 7;
+            }
             impl ::canadensis_encoding::Message for Synchronization {}
             impl Synchronization {
                 #[cfg_attr(
@@ -16258,10 +19097,20 @@ Extended
                 /// size 56 bits
                 pub microsecond: u64,
             }
+            impl ::core::default::Default for SynchronizedTimestamp {
+                fn default() -> Self {
+                    Self {
+                        microsecond: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for SynchronizedTimestamp {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for SynchronizedTimestamp {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 7;
+            }
             impl ::canadensis_encoding::Message for SynchronizedTimestamp {}
             impl SynchronizedTimestamp {
                 #[cfg_attr(not(doctest), doc = " Zero means that the time is not known.")]
@@ -16309,10 +19158,20 @@ Extended
                 /// size 10 bits
                 pub difference_tai_minus_utc: u16,
             }
+            impl ::core::default::Default for TAIInfo {
+                fn default() -> Self {
+                    Self {
+                        difference_tai_minus_utc: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for TAIInfo {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for TAIInfo {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 2;
+            }
             impl ::canadensis_encoding::Message for TAIInfo {}
             impl TAIInfo {
                 #[cfg_attr(
@@ -16360,10 +19219,20 @@ Extended
                 /// size 4 bits
                 pub value: u8,
             }
+            impl ::core::default::Default for TimeSystem {
+                fn default() -> Self {
+                    Self {
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
             impl ::canadensis_encoding::DataType for TimeSystem {
                 /// This type is sealed.
                 const EXTENT_BYTES: Option<u32> = None;
             }
+            impl ::canadensis_encoding::SerializedSize for TimeSystem {
+                const MAX_SERIALIZED_SIZE_BYTES: usize = 1;
+            }
             impl ::canadensis_encoding::Message for TimeSystem {}
             impl TimeSystem {
                 #[cfg_attr(
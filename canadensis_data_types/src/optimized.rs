@@ -22,6 +22,10 @@ impl<const N: usize> canadensis_encoding::DataType for SubjectIdList<N> {
     const EXTENT_BYTES: Option<u32> =
         crate::uavcan::node::port::subject_id_list_1_0::SubjectIDList::EXTENT_BYTES;
 }
+impl<const N: usize> canadensis_encoding::SerializedSize for SubjectIdList<N> {
+    // 1 byte of union discriminant, 1 byte of length, 2 bytes for each ID
+    const MAX_SERIALIZED_SIZE_BYTES: usize = 2 + N * 2;
+}
 impl<const N: usize> canadensis_encoding::Message for SubjectIdList<N> {}
 impl<const N: usize> canadensis_encoding::Serialize for SubjectIdList<N> {
     fn size_bits(&self) -> usize {
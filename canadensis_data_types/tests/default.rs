@@ -0,0 +1,34 @@
+//! Checks the Default implementations of some generated types
+
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+
+use canadensis_data_types::uavcan::node::get_info_1_0::GetInfoResponse;
+use canadensis_data_types::uavcan::register::value_1_0::Value;
+use canadensis_encoding::Serialize;
+
+fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = vec![0u8; value.size_bits().div_ceil(8)];
+    value.serialize_to_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn default_struct() {
+    let info = GetInfoResponse::default();
+    assert_eq!(info.software_vcs_revision_id, 0);
+    assert_eq!(info.unique_id, [0; 16]);
+    assert!(info.name.is_empty());
+    assert!(info.software_image_crc.is_empty());
+    assert!(info.certificate_of_authenticity.is_empty());
+
+    // Everything is zero, including the lengths of the variable-length arrays
+    assert_eq!(serialize(&info), [0u8; 33]);
+}
+
+#[test]
+fn default_union() {
+    // The first variant is Empty
+    assert!(matches!(Value::default(), Value::Empty(_)));
+    assert_eq!(serialize(&Value::default()), [0]);
+}
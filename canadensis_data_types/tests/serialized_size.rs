@@ -0,0 +1,40 @@
+//! Checks the SerializedSize implementations of some generated types
+
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+
+use canadensis_data_types::uavcan::node::get_info_1_0::GetInfoResponse;
+use canadensis_data_types::uavcan::node::heartbeat_1_0::Heartbeat;
+use canadensis_data_types::uavcan::node::port::subject_id_list_1_0::SubjectIDList;
+use canadensis_data_types::uavcan::register::access_1_0::AccessRequest;
+use canadensis_encoding::{Serialize, SerializedSize};
+
+#[test]
+fn fixed_size() {
+    assert_eq!(7, Heartbeat::MAX_SERIALIZED_SIZE_BYTES);
+    // Heartbeat is delimited, so receivers accept the extent
+    assert_eq!(12, Heartbeat::RECEIVE_SIZE_BYTES);
+}
+
+#[test]
+fn variable_size() {
+    assert_eq!(313, GetInfoResponse::MAX_SERIALIZED_SIZE_BYTES);
+    assert_eq!(448, GetInfoResponse::RECEIVE_SIZE_BYTES);
+    assert_eq!(1025, SubjectIDList::MAX_SERIALIZED_SIZE_BYTES);
+    assert_eq!(4097, SubjectIDList::RECEIVE_SIZE_BYTES);
+    assert_eq!(515, AccessRequest::MAX_SERIALIZED_SIZE_BYTES);
+}
+
+#[test]
+fn largest_value() {
+    let mut info = GetInfoResponse::default();
+    info.name.extend_from_slice(&[b'a'; 50]).unwrap();
+    info.software_image_crc.push(0).unwrap();
+    info.certificate_of_authenticity
+        .extend_from_slice(&[0; 222])
+        .unwrap();
+    assert_eq!(
+        GetInfoResponse::MAX_SERIALIZED_SIZE_BYTES,
+        info.size_bits().div_ceil(8)
+    );
+}
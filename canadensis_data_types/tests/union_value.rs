@@ -0,0 +1,64 @@
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+extern crate heapless;
+
+use canadensis_data_types::uavcan::primitive::array::natural16_1_0::Natural16;
+use canadensis_data_types::uavcan::primitive::empty_1_0::Empty;
+use canadensis_data_types::uavcan::primitive::string_1_0::String as PrimitiveString;
+use canadensis_data_types::uavcan::register::value_1_0::Value;
+use canadensis_encoding::{Deserialize, DeserializeError, Serialize};
+
+fn serialize(value: &Value) -> Vec<u8> {
+    let mut bytes = vec![0u8; value.size_bits().div_ceil(8)];
+    value.serialize_to_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn union_empty() {
+    let value = Value::Empty(Empty {});
+    assert_eq!(serialize(&value), [0]);
+    assert!(matches!(
+        Value::deserialize_from_bytes(&[0]),
+        Ok(Value::Empty(_))
+    ));
+}
+
+#[test]
+fn union_string() {
+    let value = Value::String(PrimitiveString {
+        value: heapless::Vec::from_slice(b"hi").unwrap(),
+    });
+    // Tag 1, length 2 (uint16), then the bytes
+    let expected = [1, 2, 0, b'h', b'i'];
+    assert_eq!(serialize(&value), expected);
+    match Value::deserialize_from_bytes(&expected) {
+        Ok(Value::String(string)) => assert_eq!(string.value.as_slice(), b"hi"),
+        other => panic!("Unexpected result {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn union_natural16() {
+    let value = Value::Natural16(Natural16 {
+        value: heapless::Vec::from_slice(&[1, 0x0203]).unwrap(),
+    });
+    // Tag 10, length 2, then two little-endian values
+    let expected = [10, 2, 0x01, 0x00, 0x03, 0x02];
+    assert_eq!(serialize(&value), expected);
+    match Value::deserialize_from_bytes(&expected) {
+        Ok(Value::Natural16(natural)) => assert_eq!(natural.value.as_slice(), [1, 0x0203]),
+        other => panic!("Unexpected result {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn union_invalid_tag() {
+    // Value has 15 variants, so tags 15 and above are invalid
+    for tag in [15u8, 16, 255] {
+        assert!(matches!(
+            Value::deserialize_from_bytes(&[tag]),
+            Err(DeserializeError::UnionTag)
+        ));
+    }
+}
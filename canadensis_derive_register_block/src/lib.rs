@@ -26,6 +26,7 @@ fn implement_register_block(input: DeriveInput) -> proc_macro2::TokenStream {
     let im_field_indices = field_indices.clone();
 
     let name_field_names = field_names.clone();
+    let register_count = field_names.len();
 
     quote! {
         impl ::canadensis::register::RegisterBlock for #struct_name {
@@ -47,6 +48,9 @@ fn implement_register_block(input: DeriveInput) -> proc_macro2::TokenStream {
                     _ => None,
                 }
             }
+            fn register_count(&self) -> usize {
+                #register_count
+            }
         }
     }
 }
@@ -0,0 +1,75 @@
+//! An interface for code generators and other tools that produce output from compiled types
+//!
+//! After all DSDL files in a [`Package`](crate::Package) have been compiled, a backend gets the
+//! [`CompiledPackage`]. Each type in the package is a [`CompiledDsdl`](crate::compiled::CompiledDsdl)
+//! with a [`DsdlKind`](crate::compiled::DsdlKind), which is a message or a service with a request
+//! and a response. Each [`Message`](crate::compiled::Message) has its fields or variants, its
+//! constants, its extent, the set of its possible serialized lengths in bits
+//! ([`Message::bit_length`](crate::compiled::Message::bit_length)), and the offset and size of
+//! each field ([`Message::layout`](crate::compiled::Message::layout)).
+//!
+//! # Example
+//!
+//! ```
+//! use canadensis_dsdl_frontend::backend::Backend;
+//! use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+//! use canadensis_dsdl_frontend::compiled::DsdlKind;
+//! use std::io::{self, Write};
+//!
+//! /// Writes the name and maximum serialized size of each message type
+//! struct SizeList;
+//!
+//! impl Backend for SizeList {
+//!     type Error = io::Error;
+//!
+//!     fn generate(&self, package: &CompiledPackage, output: &mut dyn Write) -> io::Result<()> {
+//!         for (key, dsdl) in package {
+//!             if let DsdlKind::Message(message) = &dsdl.kind {
+//!                 writeln!(output, "{} {}", key, message.bit_length().max_value())?;
+//!             }
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//! ```
+
+use crate::compiled::package::CompiledPackage;
+use std::io::Write;
+
+/// Something that generates output (code in another language, documentation, or anything else)
+/// from a package of compiled types
+pub trait Backend {
+    /// The error that can occur when generating output
+    type Error;
+
+    /// Generates output for the types in a package and writes it to `output`
+    fn generate(
+        &self,
+        package: &CompiledPackage,
+        output: &mut dyn Write,
+    ) -> Result<(), Self::Error>;
+}
+
+impl<B: Backend + ?Sized> Backend for &B {
+    type Error = B::Error;
+
+    fn generate(
+        &self,
+        package: &CompiledPackage,
+        output: &mut dyn Write,
+    ) -> Result<(), Self::Error> {
+        (**self).generate(package, output)
+    }
+}
+
+impl<B: Backend + ?Sized> Backend for Box<B> {
+    type Error = B::Error;
+
+    fn generate(
+        &self,
+        package: &CompiledPackage,
+        output: &mut dyn Write,
+    ) -> Result<(), Self::Error> {
+        (**self).generate(package, output)
+    }
+}
@@ -0,0 +1,301 @@
+//! Checks of the DSDL versioning rules
+//!
+//! Versions of a type with the same major version and different minor versions must be
+//! bit-compatible, so that nodes that use different minor versions can communicate. These
+//! checks find changes between minor versions that break compatibility:
+//!
+//! * A different fixed port ID
+//! * A change from sealed to delimited or from delimited to sealed, or a different extent
+//! * A change from a struct to a union or from a union to a struct, or a different number of
+//!   union discriminant bits
+//! * A field or variant that was removed, or whose type or position changed
+//! * A field or variant added to a sealed type
+//!
+//! Fields and variants may be renamed, and fields and variants may be added to the end of a
+//! delimited type. A field whose type is a different minor version of the same composite type
+//! is allowed, because the versions of that type are checked separately.
+//!
+//! Versions with major version 0 are experimental and are not checked.
+//!
+//! When comparing two snapshots of a package (like two releases of a repository of DSDL files),
+//! this also finds versions whose layouts changed without a new version number.
+
+use crate::compiled::package::CompiledPackage;
+use crate::compiled::{CompiledDsdl, DsdlKind, Extent, FieldKind, Message, MessageKind};
+use crate::type_key::TypeKey;
+use crate::types::{ResolvedScalarType, ResolvedType};
+use std::fmt::{self, Display, Formatter};
+
+/// A change between two versions of a type that breaks compatibility
+#[derive(Debug, Clone)]
+pub struct Incompatibility {
+    /// The newer version of the type
+    pub key: TypeKey,
+    /// The version that the newer version was compared with
+    ///
+    /// This is equal to `key` if the layout of one version changed between two snapshots.
+    pub previous: TypeKey,
+    /// A description of the problem
+    pub message: String,
+}
+
+impl Display for Incompatibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.key.version() == self.previous.version() {
+            write!(
+                f,
+                "{} changed without a new version: {}",
+                self.key, self.message
+            )
+        } else {
+            write!(
+                f,
+                "{} is not compatible with {}: {}",
+                self.key, self.previous, self.message
+            )
+        }
+    }
+}
+
+/// Checks that a newer minor version of a type is compatible with an older version that has
+/// the same major version
+///
+/// This function returns an empty list if the versions are compatible, if the types have
+/// different names or major versions, or if the major version is 0.
+pub fn check_versions(
+    previous_key: &TypeKey,
+    previous: &CompiledDsdl,
+    key: &TypeKey,
+    dsdl: &CompiledDsdl,
+) -> Vec<Incompatibility> {
+    let mut incompatibilities = Vec::new();
+    if previous_key.name() != key.name()
+        || previous_key.version().major != key.version().major
+        || key.version().major == 0
+    {
+        return incompatibilities;
+    }
+    let mut report = |message: String| {
+        incompatibilities.push(Incompatibility {
+            key: key.clone(),
+            previous: previous_key.clone(),
+            message,
+        })
+    };
+
+    if previous.fixed_port_id != dsdl.fixed_port_id {
+        report(format!(
+            "The fixed port ID changed from {} to {}",
+            PortId(previous.fixed_port_id),
+            PortId(dsdl.fixed_port_id)
+        ));
+    }
+    match (&previous.kind, &dsdl.kind) {
+        (DsdlKind::Message(previous), DsdlKind::Message(message)) => {
+            check_messages(previous, message, "", &mut report)
+        }
+        (
+            DsdlKind::Service {
+                request: previous_request,
+                response: previous_response,
+            },
+            DsdlKind::Service { request, response },
+        ) => {
+            check_messages(previous_request, request, "Request: ", &mut report);
+            check_messages(previous_response, response, "Response: ", &mut report);
+        }
+        (DsdlKind::Message(_), DsdlKind::Service { .. }) => {
+            report("A message type became a service type".to_owned())
+        }
+        (DsdlKind::Service { .. }, DsdlKind::Message(_)) => {
+            report("A service type became a message type".to_owned())
+        }
+    }
+    incompatibilities
+}
+
+/// Checks each version of each type in a package against the previous minor version with the
+/// same major version
+pub fn check_package(package: &CompiledPackage) -> Vec<Incompatibility> {
+    check(None, package)
+}
+
+/// Checks two snapshots of a package
+///
+/// This finds versions in `old` whose layouts are different in `new`, and checks each version
+/// of each type in `new` against the previous minor version with the same major version (from
+/// `new` or, if it was removed, from `old`).
+pub fn check_snapshots(old: &CompiledPackage, new: &CompiledPackage) -> Vec<Incompatibility> {
+    check(Some(old), new)
+}
+
+fn check(old: Option<&CompiledPackage>, new: &CompiledPackage) -> Vec<Incompatibility> {
+    let mut incompatibilities = Vec::new();
+    for (key, dsdl) in new.iter() {
+        if let Some(old_dsdl) = old.and_then(|old| old.get_by_key(key)) {
+            if layout_description(old_dsdl) != layout_description(dsdl) {
+                incompatibilities.push(Incompatibility {
+                    key: key.clone(),
+                    previous: key.clone(),
+                    message: "The layout is different".to_owned(),
+                });
+            }
+        }
+
+        // max_by_key returns the last maximum, so a version in new replaces the same version in old
+        let previous = old
+            .into_iter()
+            .flat_map(CompiledPackage::iter)
+            .chain(new.iter())
+            .filter(|(other, _)| {
+                other.name() == key.name()
+                    && other.version().major == key.version().major
+                    && other.version().minor < key.version().minor
+            })
+            .max_by_key(|(other, _)| other.version().minor);
+        if let Some((previous_key, previous)) = previous {
+            incompatibilities.extend(check_versions(previous_key, previous, key, dsdl));
+        }
+    }
+    incompatibilities
+}
+
+fn check_messages<F>(previous: &Message, message: &Message, prefix: &str, report: &mut F)
+where
+    F: FnMut(String),
+{
+    let sealed = match (previous.extent(), message.extent()) {
+        (Extent::Sealed, Extent::Sealed) => true,
+        (Extent::Delimited(previous_extent), Extent::Delimited(extent)) => {
+            if previous_extent != extent {
+                report(format!(
+                    "{}The extent changed from {} to {} bits",
+                    prefix, previous_extent, extent
+                ));
+            }
+            false
+        }
+        (Extent::Sealed, Extent::Delimited(_)) => {
+            report(format!("{}A sealed type became delimited", prefix));
+            return;
+        }
+        (Extent::Delimited(_), Extent::Sealed) => {
+            report(format!("{}A delimited type became sealed", prefix));
+            return;
+        }
+    };
+    let (previous_elements, elements, element_kind) = match (previous.kind(), message.kind()) {
+        (MessageKind::Struct(_), MessageKind::Struct(_)) => {
+            (elements(previous), elements(message), "field")
+        }
+        (MessageKind::Union(previous_union), MessageKind::Union(union)) => {
+            if previous_union.discriminant_bits != union.discriminant_bits {
+                report(format!(
+                    "{}The number of union discriminant bits changed from {} to {}",
+                    prefix, previous_union.discriminant_bits, union.discriminant_bits
+                ));
+                return;
+            }
+            (elements(previous), elements(message), "variant")
+        }
+        (MessageKind::Struct(_), MessageKind::Union(_)) => {
+            report(format!("{}A struct became a union", prefix));
+            return;
+        }
+        (MessageKind::Union(_), MessageKind::Struct(_)) => {
+            report(format!("{}A union became a struct", prefix));
+            return;
+        }
+    };
+
+    for (i, previous_element) in previous_elements.iter().enumerate() {
+        match elements.get(i) {
+            Some(element) if element.ty == previous_element.ty => {}
+            Some(element) => report(format!(
+                "{}The type of {} {} ({}) changed from {} to {}",
+                prefix, element_kind, i, element.name, previous_element.ty, element.ty
+            )),
+            None => report(format!(
+                "{}The {} {} ({}) was removed",
+                prefix, element_kind, i, previous_element.name
+            )),
+        }
+    }
+    if sealed && elements.len() > previous_elements.len() {
+        report(format!(
+            "{}A {} was added to a sealed type",
+            prefix, element_kind
+        ));
+    }
+}
+
+/// A field or variant, with a type description that does not include the minor versions of
+/// composite types
+struct Element {
+    name: String,
+    ty: String,
+}
+
+fn elements(message: &Message) -> Vec<Element> {
+    match message.kind() {
+        MessageKind::Struct(dsdl_struct) => dsdl_struct
+            .fields
+            .iter()
+            .map(|field| match field.kind() {
+                FieldKind::Data { ty, name } => Element {
+                    name: name.clone(),
+                    ty: type_without_minor_version(ty),
+                },
+                FieldKind::Padding(bits) => Element {
+                    name: "padding".to_owned(),
+                    ty: format!("void{}", bits),
+                },
+            })
+            .collect(),
+        MessageKind::Union(union) => union
+            .variants
+            .iter()
+            .map(|variant| Element {
+                name: variant.name().to_owned(),
+                ty: type_without_minor_version(variant.ty()),
+            })
+            .collect(),
+    }
+}
+
+fn type_without_minor_version(ty: &ResolvedType) -> String {
+    let scalar = match ty.scalar() {
+        ResolvedScalarType::Composite { key, .. } => {
+            format!("{}.{}.x", key.name(), key.version().major)
+        }
+        other => other.to_string(),
+    };
+    match ty {
+        ResolvedType::Scalar(_) => scalar,
+        ResolvedType::FixedArray { len, .. } => format!("{}[{}]", scalar, len),
+        ResolvedType::VariableArray { max_len, .. } => format!("{}[<={}]", scalar, max_len),
+    }
+}
+
+fn layout_description(dsdl: &CompiledDsdl) -> String {
+    match &dsdl.kind {
+        DsdlKind::Message(message) => message.layout_description(),
+        DsdlKind::Service { request, response } => format!(
+            "request: {}\nresponse: {}",
+            request.layout_description(),
+            response.layout_description()
+        ),
+    }
+}
+
+/// Displays an optional fixed port ID
+struct PortId(Option<u32>);
+
+impl Display for PortId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(port_id) => write!(f, "{}", port_id),
+            None => write!(f, "none"),
+        }
+    }
+}
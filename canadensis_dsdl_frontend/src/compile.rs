@@ -4,20 +4,25 @@ use crate::compiled::{
 use crate::constants::Constants;
 use crate::error::Error;
 use crate::package::DsdlFile;
+use crate::print::{PrintHandler, PrintOutput};
+use crate::suggest::closest_match;
 use crate::type_key::{TypeFullName, TypeKey};
 use crate::types::constant::Constant;
 use crate::types::directive::evaluate_directive;
 use crate::types::expression::convert_type;
 use crate::types::{array_length_bits, PrimitiveType, ResolvedScalarType, ResolvedType};
-use crate::warning::Warnings;
+use crate::warning::{WarningRule, Warnings};
 use canadensis_bit_length_set::BitLengthSet;
-use canadensis_dsdl_parser::{Config, Identifier, Span, Statement};
+use canadensis_dsdl_parser::{Config, Definition, Identifier, Span, Statement};
 use once_cell::sync::Lazy;
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryInto;
 use std::mem;
+use std::num::NonZeroUsize;
+use std::panic;
 use std::path::PathBuf;
+use std::thread;
 
 /// The minimum number of variants in a union
 const UNION_MIN_VARIANTS: usize = 2;
@@ -32,14 +37,138 @@ static BIT_LENGTH_ZERO: Lazy<BitLengthSet> = Lazy::new(|| BitLengthSet::single(0
 ///
 /// This function returns the compiled DSDL or an error. In either case, it also returns
 /// a set of warnings.
-pub(crate) fn compile(files: BTreeMap<TypeKey, DsdlFile>, config: &Config) -> CompileOutput {
+pub(crate) fn compile(
+    files: BTreeMap<TypeKey, DsdlFile>,
+    config: &Config,
+    enabled_warnings: BTreeSet<WarningRule>,
+    print_handler: PrintHandler,
+) -> CompileOutput {
+    // Remember where the types with fixed port IDs came from, in case they conflict
+    let fixed_port_id_paths: BTreeMap<TypeKey, Option<PathBuf>> = files
+        .iter()
+        .filter(|(_, file)| file.fixed_port_id().is_some())
+        .map(|(key, file)| (key.clone(), file.display_path().map(PathBuf::from)))
+        .collect();
+    // Reading and parsing a file does not depend on any other files, so that happens on several
+    // threads. Each type still gets compiled after its dependencies, on this thread.
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let entries: Vec<(&TypeKey, &DsdlFile)> = files.iter().collect();
+    let texts = map_parallel(&entries, threads, |(_, file)| file.read());
+    let mut read_errors = BTreeMap::new();
+    let mut readable: Vec<(TypeKey, String)> = Vec::with_capacity(texts.len());
+    for ((key, _), text) in entries.into_iter().zip(texts) {
+        match text {
+            Ok(text) => readable.push((key.clone(), text)),
+            Err(e) => {
+                read_errors.insert(key.clone(), e);
+            }
+        }
+    }
+    let definitions = map_parallel(&readable, threads, |(_, text)| parse(text, config));
+    let mut parsed: BTreeMap<TypeKey, Result<Definition<'_>, Box<Error>>> = readable
+        .iter()
+        .map(|(key, _)| key.clone())
+        .zip(definitions)
+        .collect();
+    parsed.extend(read_errors.into_iter().map(|(key, e)| (key, Err(e))));
+
     let context = PersistentContext {
-        config,
         pending: files,
+        parsed,
         done: BTreeMap::new(),
-        warnings: Warnings::new(),
+        failed: BTreeSet::new(),
+        warnings: Warnings::new(enabled_warnings),
+        print_handler,
     };
-    context.compile()
+    let mut output = context.compile();
+    if let Ok(types) = &output.dsdl {
+        if let Err(e) = check_fixed_port_id_conflicts(types, &fixed_port_id_paths) {
+            output.dsdl = Err(e);
+        }
+    }
+    output
+}
+
+/// Parses the text of a file
+fn parse<'t>(text: &'t str, config: &Config) -> Result<Definition<'t>, Box<Error>> {
+    canadensis_dsdl_parser::parse_with_recovery(text, config).map_err(|mut errors| {
+        if errors.len() == 1 {
+            Box::new(Error::Compile(errors.remove(0)))
+        } else {
+            Box::new(Error::Multiple(
+                errors.into_iter().map(Error::Compile).collect(),
+            ))
+        }
+    })
+}
+
+/// Applies a function to each item, splitting the items between up to `threads` threads, and
+/// returns the results in the same order as the items
+fn map_parallel<'a, T, R, F>(items: &'a [T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&'a T) -> R + Sync,
+{
+    if threads <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| panic::resume_unwind(payload))
+            })
+            .collect()
+    })
+}
+
+/// Checks that no two types have the same fixed port ID, unless they are allowed to
+///
+/// Subject IDs and service IDs are separate, so a message type and a service type can have
+/// the same fixed port ID. Different minor versions of a type with the same major version can
+/// also have the same fixed port ID, and so can different versions of a type with major
+/// version 0.
+fn check_fixed_port_id_conflicts(
+    types: &BTreeMap<TypeKey, CompiledDsdl>,
+    paths: &BTreeMap<TypeKey, Option<PathBuf>>,
+) -> Result<(), Box<Error>> {
+    // Key: (true if service, fixed port ID), value: types with that fixed port ID
+    let mut by_port_id: BTreeMap<(bool, u32), Vec<&TypeKey>> = BTreeMap::new();
+    for (key, dsdl) in types {
+        if let Some(port_id) = dsdl.fixed_port_id {
+            let service = matches!(dsdl.kind, DsdlKind::Service { .. });
+            by_port_id.entry((service, port_id)).or_default().push(key);
+        }
+    }
+    for ((service, port_id), keys) in by_port_id {
+        for (i, &first) in keys.iter().enumerate() {
+            for &second in &keys[i + 1..] {
+                let same_name = first.name() == second.name();
+                let same_major = first.version().major == second.version().major;
+                let both_v0 = first.version().major == 0 && second.version().major == 0;
+                if !same_name || !(same_major || both_v0) {
+                    return Err(Box::new(Error::FixedPortIdConflict {
+                        port_id,
+                        service,
+                        first: first.clone(),
+                        first_path: paths.get(first).cloned().flatten(),
+                        second: second.clone(),
+                        second_path: paths.get(second).cloned().flatten(),
+                    }));
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 /// The output of a compile operation
@@ -62,12 +191,23 @@ pub(crate) struct CompileContext<'p, 'c: 'p> {
 }
 
 impl<'p, 'c: 'p> CompileContext<'p, 'c> {
-    /// Returns the constants that have been declared in the current file
+    /// Looks up a constant that has been declared in the current file and records that it has
+    /// been used
     ///
     /// If the current file defines a service type, constants declared in the request section
     /// are not available in the response section.
-    pub fn constants(&self) -> &Constants {
-        &self.current_file.constants
+    pub fn use_constant(&mut self, name: &str) -> Option<&Constant> {
+        let constant = self.current_file.constants.get_mut(name)?;
+        constant.mark_used();
+        Some(constant)
+    }
+
+    /// Returns the names of the constants that have been declared in the current file
+    pub fn constant_names(&self) -> impl Iterator<Item = &str> {
+        self.current_file
+            .constants
+            .iter()
+            .map(|(name, _)| name.as_str())
     }
 
     /// Returns a bit length set covering the fields that have been processed so far
@@ -131,7 +271,10 @@ impl<'p, 'c: 'p> CompileContext<'p, 'c> {
         // Look in the current package if the package is not specified
         if key.name().path().is_empty() {
             let canonical_key = TypeKey::new(
-                TypeFullName::new(self.current_file.path.clone(), key.name().name().to_owned()),
+                TypeFullName::new(
+                    self.current_file.key.name().path().to_vec(),
+                    key.name().name().to_owned(),
+                ),
                 key.version().clone(),
             );
             let result = self.persistent.type_by_key(&canonical_key)?;
@@ -142,6 +285,17 @@ impl<'p, 'c: 'p> CompileContext<'p, 'c> {
         }
     }
 
+    /// Handles a @print directive by passing the text to the print handler
+    pub fn print(&mut self, span: Span<'_>, text: &str) {
+        let (line, _) = span.start_pos().line_col();
+        self.persistent.print_handler.print(&PrintOutput {
+            key: &self.current_file.key,
+            path: self.current_file.file.as_deref(),
+            line,
+            text,
+        });
+    }
+
     /// Handles a @union directive
     pub fn handle_union(&mut self, span: Span<'_>) -> Result<(), Box<Error>> {
         // @union may only be before the first field in a message (or request or response)
@@ -233,17 +387,25 @@ fn ctx<'p, 'c: 'p>(
 
 /// A context used during the compilation process
 struct PersistentContext<'c> {
-    config: &'c Config,
     /// Files that have not been compiled
     pending: BTreeMap<TypeKey, DsdlFile>,
+    /// The syntax trees of the files that have not been compiled, or the errors from reading
+    /// or parsing them
+    parsed: BTreeMap<TypeKey, Result<Definition<'c>, Box<Error>>>,
     /// Files that have been compiled
     done: BTreeMap<TypeKey, CompiledDsdl>,
+    /// Files that could not be compiled
+    failed: BTreeSet<TypeKey>,
     /// Any reported warnings
     warnings: Warnings,
+    /// The function that receives the output of @print directives
+    print_handler: PrintHandler,
 }
 
 impl PersistentContext<'_> {
     fn compile(mut self) -> CompileOutput {
+        // After an error, continue with the other files to find as many errors as possible
+        let mut errors = Vec::new();
         while let Some(key) = self.pending.keys().next().cloned() {
             let input = self.pending.remove(&key).unwrap();
             match self.compile_one(&key, input) {
@@ -251,28 +413,49 @@ impl PersistentContext<'_> {
                     let existing = self.done.insert(key, output);
                     assert!(existing.is_none(), "Duplicate type in done");
                 }
-                Err(e) => {
-                    return CompileOutput {
-                        dsdl: Err(e),
-                        warnings: self.warnings,
-                    }
-                }
+                Err(e) => match *e {
+                    Error::Multiple(inner) => errors.extend(inner),
+                    // The error in the dependency has already been reported
+                    e if e.is_failed_dependency() => {}
+                    e => errors.push(e),
+                },
             }
         }
+        let dsdl = match errors.len() {
+            0 => Ok(self.done),
+            1 => Err(Box::new(errors.remove(0))),
+            _ => Err(Box::new(Error::Multiple(errors))),
+        };
         CompileOutput {
-            dsdl: Ok(self.done),
+            dsdl,
             warnings: self.warnings,
         }
     }
 
     fn compile_one(&mut self, key: &TypeKey, input: DsdlFile) -> Result<CompiledDsdl, Box<Error>> {
-        let input_path = input.path().map(PathBuf::from);
+        let input_path = input.display_path().map(PathBuf::from);
         self.compile_one_inner(key, input).map_err(|e| {
-            Box::new(Error::CompileFile {
-                key: key.clone(),
-                path: input_path,
-                inner: e,
-            })
+            self.failed.insert(key.clone());
+            let in_file = |e: Error| {
+                // Errors in this file show the path next to the source code
+                let inner = match (e, &input_path) {
+                    (Error::Compile(e), Some(path)) => {
+                        Box::new(Error::Compile(e.with_path(&path.display().to_string())))
+                    }
+                    (e, _) => Box::new(e),
+                };
+                Error::CompileFile {
+                    key: key.clone(),
+                    path: input_path.clone(),
+                    inner,
+                }
+            };
+            match *e {
+                Error::Multiple(errors) => {
+                    Box::new(Error::Multiple(errors.into_iter().map(in_file).collect()))
+                }
+                e => Box::new(in_file(e)),
+            }
         })
     }
 
@@ -284,10 +467,12 @@ impl PersistentContext<'_> {
         self.warnings.check_pre_compile(key);
 
         // Create a new state for this file
-        let mut state = FileState::new(key.name().path());
+        let mut state = FileState::new(key.clone(), input.display_path().map(PathBuf::from));
 
-        let text = input.read()?;
-        let ast = canadensis_dsdl_parser::parse(&text, self.config).map_err(Error::Compile)?;
+        let ast = self
+            .parsed
+            .remove(key)
+            .expect("No parse result for a pending file")?;
 
         for statement in ast.statements {
             match statement {
@@ -295,11 +480,12 @@ impl PersistentContext<'_> {
                     evaluate_directive(&mut ctx(self, &mut state), name, value)?
                 }
                 Statement::Constant { ty, name, value } => {
-                    if state.constants.contains_key(name.name) {
+                    if let Some(existing) = state.constants.get(name.name) {
                         return Err(span_error!(
                             name.span,
-                            "A constant attribute named {} has already been defined",
-                            name.name
+                            "A constant attribute named {} has already been defined\n  = note: The first definition is on line {}",
+                            name.name,
+                            existing.line()
                         ));
                     }
                     let name_str = name.name;
@@ -308,6 +494,7 @@ impl PersistentContext<'_> {
                     state.constants.insert(name_str.to_owned(), new_constant);
                 }
                 Statement::Field { ty, name, span } => {
+                    self.warnings.check_field_statement(key, name.name, &ty);
                     let ty = convert_type(&mut ctx(self, &mut state), ty)?;
                     let ty = ty.resolve(&mut ctx(self, &mut state), span)?;
                     let ty_alignment = ty.alignment();
@@ -373,10 +560,32 @@ impl PersistentContext<'_> {
                         ),
                     }
                 }
-                None => Err(Box::new(Error::UnknownType(key.clone()))),
+                None if self.failed.contains(key) => {
+                    Err(Box::new(Error::FailedDependency(key.clone())))
+                }
+                None => Err(Box::new(Error::UnknownType {
+                    key: key.clone(),
+                    suggestion: self.similar_type(key),
+                })),
             }
         }
     }
+
+    /// Returns the type in this package with the name and version that are most similar to
+    /// those of a type that could not be found
+    fn similar_type(&self, key: &TypeKey) -> Option<TypeKey> {
+        let keys: Vec<(String, &TypeKey)> = self
+            .pending
+            .keys()
+            .chain(self.done.keys())
+            .chain(self.failed.iter())
+            .map(|key| (key.to_string(), key))
+            .collect();
+        let suggestion = closest_match(&key.to_string(), keys.iter().map(|(s, _)| s.as_str()))?;
+        keys.iter()
+            .find(|(s, _)| s == suggestion)
+            .map(|(_, key)| (*key).clone())
+    }
 }
 
 /// Checks a type about to be added as a field or variant to this type, and returns an error
@@ -425,8 +634,10 @@ impl MapExt for BTreeMap<TypeKey, CompiledDsdl> {
 /// All state needed when parsing a file
 #[derive(Debug)]
 struct FileState {
-    /// The path components of the package that the file is in
-    path: Vec<String>,
+    /// The key of the type that the file defines
+    key: TypeKey,
+    /// The path to the file, if it is a file
+    file: Option<PathBuf>,
     /// Constants defined in the file so far
     constants: Constants,
     /// True if this type is deprecated
@@ -441,25 +652,17 @@ struct FileState {
     comments: String,
 }
 
-impl Default for FileState {
-    fn default() -> Self {
+impl FileState {
+    fn new(key: TypeKey, file: Option<PathBuf>) -> Self {
         FileState {
-            path: vec![],
+            key,
+            file,
             constants: Constants::default(),
             deprecated: false,
             state: Some(State::Message),
             comments: String::new(),
         }
     }
-}
-
-impl FileState {
-    fn new(path: &[String]) -> Self {
-        FileState {
-            path: path.to_vec(),
-            ..FileState::default()
-        }
-    }
 
     fn handle_service_response_marker(&mut self, span: Span<'_>) -> Result<(), Box<Error>> {
         match self.state.take().expect("No state") {
@@ -597,8 +800,16 @@ impl FileState {
             }
             // Add a field to a struct
             State::MessageStruct(StructState::Collecting(mut fields), length) => {
-                if fields.iter().any(|existing| existing.name() == Some(&name)) {
-                    return Err(span_error!(span, "A field named {} already exists", name));
+                if let Some(existing) = fields
+                    .iter()
+                    .find(|existing| existing.name() == Some(&name))
+                {
+                    return Err(span_error!(
+                        span,
+                        "A field named {} already exists\n  = note: The first definition is on line {}",
+                        name,
+                        existing.line()
+                    ));
                 }
 
                 fields.push(Field::data(ty, name, length.is_byte_aligned(), span));
@@ -609,8 +820,16 @@ impl FileState {
                 Ok(())
             }
             State::ResponseStruct(req, StructState::Collecting(mut fields), length) => {
-                if fields.iter().any(|existing| existing.name() == Some(&name)) {
-                    return Err(span_error!(span, "A field named {} already exists", name));
+                if let Some(existing) = fields
+                    .iter()
+                    .find(|existing| existing.name() == Some(&name))
+                {
+                    return Err(span_error!(
+                        span,
+                        "A field named {} already exists\n  = note: The first definition is on line {}",
+                        name,
+                        existing.line()
+                    ));
                 }
 
                 fields.push(Field::data(ty, name, length.is_byte_aligned(), span));
@@ -623,8 +842,13 @@ impl FileState {
             }
             // Add a variant to a union
             State::MessageUnion(UnionState::Collecting(mut variants)) => {
-                if variants.iter().any(|existing| existing.name() == name) {
-                    return Err(span_error!(span, "A variant named {} already exists", name));
+                if let Some(existing) = variants.iter().find(|existing| existing.name() == name) {
+                    return Err(span_error!(
+                        span,
+                        "A variant named {} already exists\n  = note: The first definition is on line {}",
+                        name,
+                        existing.line()
+                    ));
                 }
 
                 variants.push(Variant::new(ty, name, span));
@@ -632,8 +856,13 @@ impl FileState {
                 Ok(())
             }
             State::ResponseUnion(req, UnionState::Collecting(mut variants)) => {
-                if variants.iter().any(|existing| existing.name() == name) {
-                    return Err(span_error!(span, "A variant named {} already exists", name));
+                if let Some(existing) = variants.iter().find(|existing| existing.name() == name) {
+                    return Err(span_error!(
+                        span,
+                        "A variant named {} already exists\n  = note: The first definition is on line {}",
+                        name,
+                        existing.line()
+                    ));
                 }
 
                 variants.push(Variant::new(ty, name, span));
@@ -681,7 +910,9 @@ impl FileState {
                 apply_comment_to_constant_or_field(last_constant, fields.last_mut(), comment);
             }
             State::MessageUnion(
-                UnionState::Collecting(variants) | UnionState::End(variants, _, _),
+                UnionState::Collecting(variants)
+                | UnionState::UsedOffset(variants, _)
+                | UnionState::End(variants, _, _),
             ) => {
                 apply_comment_to_constant_or_variant(last_constant, variants.last_mut(), comment);
             }
@@ -1006,3 +1237,18 @@ fn make_union_bit_length(variants: &[Variant]) -> BitLengthSet {
     // Concatenate the discriminant and the variant lengths
     discriminant_length.concatenate([variant_lengths])
 }
+
+#[cfg(test)]
+mod test {
+    use super::map_parallel;
+
+    #[test]
+    fn map_parallel_order() {
+        let items: Vec<u32> = (0..100).collect();
+        for threads in [0, 1, 3, 8, 200] {
+            let doubled = map_parallel(&items, threads, |item| item * 2);
+            assert_eq!(doubled, (0..200).step_by(2).collect::<Vec<u32>>());
+        }
+        assert!(map_parallel(&[] as &[u32], 4, |item| *item).is_empty());
+    }
+}
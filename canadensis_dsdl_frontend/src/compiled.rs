@@ -3,7 +3,9 @@
 pub mod package;
 
 use crate::constants::Constants;
-use crate::types::ResolvedType;
+use crate::report::{message_layout, MessageLayout};
+use crate::type_key::TypeKey;
+use crate::types::{ResolvedScalarType, ResolvedType};
 use canadensis_bit_length_set::BitLengthSet;
 use canadensis_dsdl_parser::Span;
 
@@ -71,6 +73,79 @@ impl Message {
     pub fn comments(&self) -> &str {
         &self.comments
     }
+    /// Returns the keys of the composite types that the fields or variants of this message
+    /// refer to directly, in order of appearance
+    ///
+    /// A type that is used more than once appears more than once.
+    pub fn dependencies(&self) -> Vec<&TypeKey> {
+        let types: Vec<&ResolvedType> = match &self.kind {
+            MessageKind::Struct(dsdl_struct) => dsdl_struct
+                .fields
+                .iter()
+                .filter_map(|field| match field.kind() {
+                    FieldKind::Data { ty, .. } => Some(ty),
+                    FieldKind::Padding(_) => None,
+                })
+                .collect(),
+            MessageKind::Union(union) => union.variants.iter().map(Variant::ty).collect(),
+        };
+        types
+            .into_iter()
+            .filter_map(|ty| {
+                let scalar = match ty {
+                    ResolvedType::Scalar(scalar)
+                    | ResolvedType::FixedArray { inner: scalar, .. }
+                    | ResolvedType::VariableArray { inner: scalar, .. } => scalar,
+                };
+                match scalar {
+                    ResolvedScalarType::Composite { key, .. } => Some(key),
+                    ResolvedScalarType::Primitive(_) | ResolvedScalarType::Void { .. } => None,
+                }
+            })
+            .collect()
+    }
+    /// Returns a description of the serialized layout of this message
+    ///
+    /// The description includes the extent, the kind (struct or union), the number of union
+    /// discriminant bits, and the type (including the cast mode) of each field, padding field,
+    /// or variant. It does not include names, constants, comments, or the layouts of
+    /// composite types that this message contains (only their names and versions).
+    ///
+    /// If a change to a message type changes its layout description, data serialized with the old
+    /// definition may not be compatible with the new definition. The format of the description
+    /// may change in future versions of this library.
+    pub fn layout_description(&self) -> String {
+        let mut description = match self.extent {
+            Extent::Sealed => String::from("sealed"),
+            Extent::Delimited(extent) => format!("extent {}", extent),
+        };
+        match &self.kind {
+            MessageKind::Struct(dsdl_struct) => {
+                description.push_str("; struct");
+                for field in &dsdl_struct.fields {
+                    match field.kind() {
+                        FieldKind::Data { ty, .. } => description.push_str(&format!("; {}", ty)),
+                        FieldKind::Padding(bits) => {
+                            description.push_str(&format!("; void{}", bits))
+                        }
+                    }
+                }
+            }
+            MessageKind::Union(union) => {
+                description.push_str(&format!("; union {}", union.discriminant_bits));
+                for variant in &union.variants {
+                    description.push_str(&format!("; {}", variant.ty()));
+                }
+            }
+        }
+        description
+    }
+
+    /// Returns the serialized layout of this message, with the possible bit offsets and sizes of
+    /// each field or variant
+    pub fn layout(&self) -> MessageLayout {
+        message_layout(self)
+    }
 }
 
 /// The extent of a type
@@ -112,6 +187,8 @@ pub struct Field {
     comments: String,
     /// The offset, in bytes into the file, of the end of this field definition
     end_offset: usize,
+    /// The line number (starting at 1) where this field is defined
+    line: usize,
 }
 
 impl Field {
@@ -138,6 +215,10 @@ impl Field {
     pub(crate) fn end_offset(&self) -> usize {
         self.end_offset
     }
+    /// Returns the line number (starting at 1) where this field is defined
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -159,6 +240,7 @@ impl Field {
             always_aligned,
             comments: String::new(),
             end_offset: span.end(),
+            line: span.start_pos().line_col().0,
         }
     }
 
@@ -169,6 +251,7 @@ impl Field {
             always_aligned,
             comments: String::new(),
             end_offset: span.end(),
+            line: span.start_pos().line_col().0,
         }
     }
 
@@ -194,6 +277,8 @@ pub struct Variant {
     comments: String,
     /// The offset, in bytes into the file, of the end of this variant definition
     end_offset: usize,
+    /// The line number (starting at 1) where this variant is defined
+    line: usize,
 }
 
 impl Variant {
@@ -204,6 +289,7 @@ impl Variant {
             name,
             comments: String::new(),
             end_offset: span.end(),
+            line: span.start_pos().line_col().0,
         }
     }
     /// Returns the type of this variant's value
@@ -231,4 +317,8 @@ impl Variant {
     pub(crate) fn end_offset(&self) -> usize {
         self.end_offset
     }
+    /// Returns the line number (starting at 1) where this variant is defined
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
 }
@@ -10,11 +10,6 @@ pub use crate::types::constant::{Constant, ConstantValue};
 pub struct Constants(Vec<(String, Constant)>);
 
 impl Constants {
-    /// Returns true if a constant with the provided name is already stored
-    pub(crate) fn contains_key(&self, name: &str) -> bool {
-        self.0.iter().any(|(entry_name, _)| entry_name == name)
-    }
-
     /// Inserts a constant
     ///
     /// If a constant with the provided name already exists, the new constant replaces it.
@@ -38,6 +33,14 @@ impl Constants {
             .map(|(_, constant)| constant)
     }
 
+    /// Returns a mutable reference to the constant with the provided name, if one exists
+    pub(crate) fn get_mut(&mut self, name: &str) -> Option<&mut Constant> {
+        self.0
+            .iter_mut()
+            .find(|(entry_name, _)| entry_name == name)
+            .map(|(_, constant)| constant)
+    }
+
     /// Returns a mutable reference to the constant added most recently
     pub(crate) fn last_mut(&mut self) -> Option<&mut Constant> {
         self.0.last_mut().map(|(_, constant)| constant)
@@ -1,7 +1,8 @@
 use crate::package::TYPE_NAME_LENGTH_MAX;
+use crate::suggest::DidYouMean;
 use crate::type_key::TypeKey;
-use std::io;
 use std::path::PathBuf;
+use std::{fmt, io};
 use thiserror::Error;
 
 /// Errors that may occur when compiling
@@ -39,15 +40,46 @@ pub enum Error {
     VersionZero(TypeKey),
     #[error("Type {0} is not in a namespace")]
     TypeNotInNamespace(TypeKey),
-    #[error("Can't add a type named {old}: another type with a conflicting name {new} has already been added")]
-    DuplicateKey { old: TypeKey, new: TypeKey },
+    #[error(
+        "Can't add a type named {new}{}: another type with a conflicting name {old} has already been added{}",
+        FromFile(.new_path),
+        FromFile(.old_path)
+    )]
+    DuplicateKey {
+        old: TypeKey,
+        new: TypeKey,
+        /// The file that contains the type that was already added
+        old_path: Option<PathBuf>,
+        /// The file that contains the conflicting type
+        new_path: Option<PathBuf>,
+    },
+    #[error(
+        "Types {first}{} and {second}{} have the same fixed {} ID {port_id}",
+        FromFile(.first_path),
+        FromFile(.second_path),
+        if *.service { "service" } else { "subject" }
+    )]
+    FixedPortIdConflict {
+        /// The conflicting fixed port ID
+        port_id: u32,
+        /// True if the conflicting types are service types
+        service: bool,
+        /// One type with the fixed port ID
+        first: TypeKey,
+        /// The file that contains the first type
+        first_path: Option<PathBuf>,
+        /// Another type with the same fixed port ID
+        second: TypeKey,
+        /// The file that contains the second type
+        second_path: Option<PathBuf>,
+    },
     #[error("Non-deprecated type {outer} uses deprecated type {inner}")]
     DeprecatedInNonDeprecated { outer: TypeKey, inner: TypeKey },
     /// An error triggered by a particular file
     ///
     /// Because files are compiled recursively, this may contain any other error type caused by
     /// another file.
-    #[error("Error processing type {key} from file {path:?}")]
+    #[error("Error processing type {key}{}", FromFile(.path))]
     CompileFile {
         key: TypeKey,
         path: Option<PathBuf>,
@@ -64,8 +96,60 @@ pub enum Error {
     ///
     /// This sometimes indicates a cyclic dependency between DSDL types if the type not found
     /// is also being compiled further up the call stack.
-    #[error("Type {0} not found")]
-    UnknownType(TypeKey),
+    #[error("Type {key} not found{}", DidYouMean(.suggestion.as_ref()))]
+    UnknownType {
+        key: TypeKey,
+        /// A type with a similar name, which may be the intended type
+        suggestion: Option<TypeKey>,
+    },
     #[error("Input/output error")]
     Io(#[from] io::Error),
+    /// A type could not be used because compiling it failed
+    ///
+    /// The error from compiling that type is reported separately.
+    #[error("Type {0} could not be compiled")]
+    FailedDependency(TypeKey),
+    /// More than one error, from one or more files
+    #[error("{} errors:{}", .0.len(), ErrorList(.0))]
+    Multiple(Vec<Error>),
+}
+
+impl Error {
+    /// Returns true if this error was caused only by a type that could not be compiled
+    pub(crate) fn is_failed_dependency(&self) -> bool {
+        match self {
+            Error::FailedDependency(_) => true,
+            Error::CompileFile { inner, .. } => inner.is_failed_dependency(),
+            _ => false,
+        }
+    }
+}
+
+/// Displays each error in a list and its sources
+struct ErrorList<'e>(&'e [Error]);
+
+impl fmt::Display for ErrorList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for error in self.0 {
+            write!(f, "\n\n{}", error)?;
+            let mut source = std::error::Error::source(error);
+            while let Some(cause) = source {
+                write!(f, "\ncaused by: {}", cause)?;
+                source = cause.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays " from file [path]" if the path is known, or nothing otherwise
+struct FromFile<'p>(&'p Option<PathBuf>);
+
+impl fmt::Display for FromFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(path) => write!(f, " from file {}", path.display()),
+            None => Ok(()),
+        }
+    }
 }
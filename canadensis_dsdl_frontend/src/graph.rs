@@ -0,0 +1,189 @@
+//! A graph of the dependencies between compiled types
+//!
+//! Each node of the graph is a type. There are two kinds of edges:
+//! * A dependency edge goes from a type to another type that one of its fields or variants uses
+//! * A version edge goes from a type to the next newer version of the same type
+
+use crate::compiled::package::CompiledPackage;
+use crate::compiled::DsdlKind;
+use crate::type_key::TypeKey;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// A graph of the dependencies between the types in a package
+#[derive(Debug)]
+pub struct DependencyGraph<'p> {
+    nodes: BTreeMap<&'p TypeKey, Node<'p>>,
+}
+
+/// A type in a dependency graph
+#[derive(Debug)]
+pub struct Node<'p> {
+    /// The fixed port ID of the type, if it has one
+    pub fixed_port_id: Option<u32>,
+    /// True if the type is a service type
+    pub service: bool,
+    /// True if the type is deprecated
+    pub deprecated: bool,
+    /// The types that this type uses directly (for a service type, in its request or response)
+    pub dependencies: BTreeSet<&'p TypeKey>,
+    /// The next newer version of this type, if any
+    pub next_version: Option<&'p TypeKey>,
+}
+
+impl<'p> DependencyGraph<'p> {
+    /// Creates a graph of the types in a package
+    pub fn new(package: &'p CompiledPackage) -> Self {
+        let mut nodes: BTreeMap<&'p TypeKey, Node<'p>> = package
+            .iter()
+            .map(|(key, dsdl)| {
+                let (service, deprecated, dependencies) = match &dsdl.kind {
+                    DsdlKind::Message(message) => (
+                        false,
+                        message.deprecated(),
+                        message.dependencies().into_iter().collect(),
+                    ),
+                    DsdlKind::Service { request, response } => (
+                        true,
+                        request.deprecated(),
+                        request
+                            .dependencies()
+                            .into_iter()
+                            .chain(response.dependencies())
+                            .collect(),
+                    ),
+                };
+                let node = Node {
+                    fixed_port_id: dsdl.fixed_port_id,
+                    service,
+                    deprecated,
+                    dependencies,
+                    next_version: None,
+                };
+                (key, node)
+            })
+            .collect();
+
+        // The keys are sorted by name and then by version, so each version of a type is
+        // immediately followed by the next newer version
+        let keys: Vec<&'p TypeKey> = nodes.keys().copied().collect();
+        for pair in keys.windows(2) {
+            if pair[0].name() == pair[1].name() {
+                if let Some(node) = nodes.get_mut(pair[0]) {
+                    node.next_version = Some(pair[1]);
+                }
+            }
+        }
+        DependencyGraph { nodes }
+    }
+
+    /// Returns an iterator over the types in this graph and their nodes, sorted by name and then
+    /// version
+    pub fn nodes(&self) -> impl Iterator<Item = (&'p TypeKey, &Node<'p>)> + '_ {
+        self.nodes.iter().map(|(key, node)| (*key, node))
+    }
+
+    /// Returns an object that displays this graph in the Graphviz DOT language
+    ///
+    /// Service types are boxes and message types are ellipses. Deprecated types have dashed
+    /// outlines. Version edges are dashed.
+    pub fn dot(&self) -> Dot<'_, 'p> {
+        Dot(self)
+    }
+
+    /// Returns an object that displays this graph as JSON
+    ///
+    /// The JSON is an object with a `types` field, which is an array with an object for each
+    /// type. Each type object has these fields:
+    /// * `key`: The name and version of the type, like `"uavcan.node.Heartbeat.1.0"`
+    /// * `name`: The name of the type, like `"uavcan.node.Heartbeat"`
+    /// * `version`: The version of the type, like `"1.0"`
+    /// * `kind`: `"message"` or `"service"`
+    /// * `fixed_port_id`: The fixed port ID, or `null`
+    /// * `deprecated`: `true` or `false`
+    /// * `dependencies`: An array of the keys of the types that this type uses directly
+    /// * `next_version`: The key of the next newer version of this type, or `null`
+    pub fn json(&self) -> Json<'_, 'p> {
+        Json(self)
+    }
+}
+
+/// Displays a dependency graph in the Graphviz DOT language
+pub struct Dot<'g, 'p>(&'g DependencyGraph<'p>);
+
+impl Display for Dot<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph dsdl {{")?;
+        for (key, node) in self.0.nodes() {
+            let mut label = key.to_string();
+            if let Some(port_id) = node.fixed_port_id {
+                write!(label, "\\nport {}", port_id)?;
+            }
+            write!(
+                f,
+                "    \"{}\" [label=\"{}\", shape={}",
+                key,
+                label,
+                if node.service { "box" } else { "ellipse" }
+            )?;
+            if node.deprecated {
+                write!(f, ", style=dashed")?;
+            }
+            writeln!(f, "];")?;
+        }
+        for (key, node) in self.0.nodes() {
+            for dependency in &node.dependencies {
+                writeln!(f, "    \"{}\" -> \"{}\";", key, dependency)?;
+            }
+            if let Some(next_version) = node.next_version {
+                writeln!(
+                    f,
+                    "    \"{}\" -> \"{}\" [style=dashed, label=\"next version\"];",
+                    key, next_version
+                )?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// Displays a dependency graph as JSON
+pub struct Json<'g, 'p>(&'g DependencyGraph<'p>);
+
+impl Display for Json<'_, '_> {
+    // Type names and versions contain only letters, digits, underscores, and dots, so they
+    // do not need to be escaped.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"types\":[")?;
+        for (i, (key, node)) in self.0.nodes().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(
+                f,
+                "{{\"key\":\"{}\",\"name\":\"{}\",\"version\":\"{}\",\"kind\":\"{}\",",
+                key,
+                key.name(),
+                key.version(),
+                if node.service { "service" } else { "message" }
+            )?;
+            match node.fixed_port_id {
+                Some(port_id) => write!(f, "\"fixed_port_id\":{},", port_id)?,
+                None => write!(f, "\"fixed_port_id\":null,")?,
+            }
+            write!(f, "\"deprecated\":{},\"dependencies\":[", node.deprecated)?;
+            for (j, dependency) in node.dependencies.iter().enumerate() {
+                if j != 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "\"{}\"", dependency)?;
+            }
+            write!(f, "],")?;
+            match node.next_version {
+                Some(next_version) => write!(f, "\"next_version\":\"{}\"}}", next_version)?,
+                None => write!(f, "\"next_version\":null}}")?,
+            }
+        }
+        writeln!(f, "]}}")
+    }
+}
@@ -19,12 +19,20 @@ macro_rules! span_error {
     };
 }
 
+pub mod backend;
+pub mod compatibility;
 pub(crate) mod compile;
 pub mod compiled;
 pub mod constants;
 pub(crate) mod error;
+pub mod graph;
+pub mod manifest;
+pub mod markdown;
 pub(crate) mod operators;
 mod package;
+pub mod print;
+pub mod report;
+mod suggest;
 mod type_key;
 pub mod types;
 pub mod warning;
@@ -0,0 +1,108 @@
+//! Machine-readable manifests of compiled types
+//!
+//! A manifest is a JSON document that lists each type in a package with its name, version,
+//! fixed port ID, extent, and serialized sizes. Other tools, like configuration user interfaces
+//! and code generators for other languages, can read it instead of compiling the DSDL files.
+//!
+//! The JSON is an object with these fields:
+//! * `manifest_version`: The version of the manifest format, which is currently `1`
+//! * `types`: An array with an object for each type, sorted by name and then version
+//!
+//! Each type object has these fields:
+//! * `name`: The full name of the type, like `"uavcan.node.Heartbeat"`
+//! * `version`: An object with `major` and `minor` fields
+//! * `fixed_port_id`: The fixed port ID, or `null`
+//! * `kind`: `"message"` or `"service"`
+//! * `deprecated`: `true` if the type is deprecated
+//! * `message` (for a message type), or `request` and `response` (for a service type)
+//!
+//! Each message, request, or response is an object with these fields:
+//! * `sealed`: `true` if the type is sealed
+//! * `extent_bytes`: The extent in bytes (for a sealed type, this is the maximum size)
+//! * `min_size_bytes`: The minimum serialized size in bytes
+//! * `max_size_bytes`: The maximum serialized size in bytes
+//!
+//! Fields may be added in later versions without changing `manifest_version`.
+
+use crate::compiled::package::CompiledPackage;
+use crate::compiled::{DsdlKind, Extent, Message};
+use std::fmt::{self, Display, Formatter};
+
+/// The version of the manifest format
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// A manifest of the types in a package, which displays as JSON
+#[derive(Debug)]
+pub struct Manifest<'p> {
+    package: &'p CompiledPackage,
+}
+
+impl<'p> Manifest<'p> {
+    /// Creates a manifest of the types in a package
+    pub fn new(package: &'p CompiledPackage) -> Self {
+        Manifest { package }
+    }
+}
+
+impl Display for Manifest<'_> {
+    // Type names contain only letters, digits, underscores, and dots, so they do not need to be
+    // escaped.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"manifest_version\":{},\"types\":[", MANIFEST_VERSION)?;
+        for (i, (key, dsdl)) in self.package.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(
+                f,
+                "{{\"name\":\"{}\",\"version\":{{\"major\":{},\"minor\":{}}},",
+                key.name(),
+                key.version().major,
+                key.version().minor
+            )?;
+            match dsdl.fixed_port_id {
+                Some(port_id) => write!(f, "\"fixed_port_id\":{},", port_id)?,
+                None => write!(f, "\"fixed_port_id\":null,")?,
+            }
+            match &dsdl.kind {
+                DsdlKind::Message(message) => {
+                    write!(
+                        f,
+                        "\"kind\":\"message\",\"deprecated\":{},\"message\":",
+                        message.deprecated()
+                    )?;
+                    write_message(f, message)?;
+                }
+                DsdlKind::Service { request, response } => {
+                    write!(
+                        f,
+                        "\"kind\":\"service\",\"deprecated\":{},\"request\":",
+                        request.deprecated()
+                    )?;
+                    write_message(f, request)?;
+                    write!(f, ",\"response\":")?;
+                    write_message(f, response)?;
+                }
+            }
+            write!(f, "}}")?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+fn write_message(f: &mut Formatter<'_>, message: &Message) -> fmt::Result {
+    let lengths = message.bit_length();
+    let max_size_bytes = lengths.max_value().div_ceil(8);
+    let (sealed, extent_bytes) = match message.extent() {
+        Extent::Sealed => (true, max_size_bytes),
+        Extent::Delimited(extent) => (false, extent / 8),
+    };
+    write!(
+        f,
+        "{{\"sealed\":{},\"extent_bytes\":{},\"min_size_bytes\":{},\"max_size_bytes\":{}}}",
+        sealed,
+        extent_bytes,
+        lengths.min_value().div_ceil(8),
+        max_size_bytes
+    )
+}
@@ -0,0 +1,374 @@
+//! Markdown documentation of compiled types
+//!
+//! The document has a section for each type name, which lists the versions of the type and has
+//! a subsection for each version. Each version shows the fixed port ID, the extent, the possible
+//! serialized sizes, the comments from the DSDL file, and tables of fields (or variants) and
+//! constants. Each field has its offset, type, unit, and description.
+//!
+//! The unit of a field comes from the first text in square brackets in its comment that
+//! contains a letter and no commas, like `[second]` or `[m/s]`. For a field whose type is in the
+//! `uavcan.si.unit` namespace, the unit is the name of the first field of that type, like
+//! `meter_per_second`.
+
+use crate::backend::Backend;
+use crate::compiled::package::CompiledPackage;
+use crate::compiled::{CompiledDsdl, DsdlKind, Extent, FieldKind, Message, MessageKind};
+use crate::report::{message_layout, BitRange, FieldLayout, RangeText};
+use crate::type_key::TypeKey;
+use crate::types::{ResolvedScalarType, ResolvedType};
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+
+/// Markdown documentation of the types in a package
+#[derive(Debug)]
+pub struct MarkdownDocument<'p> {
+    package: &'p CompiledPackage,
+    title: String,
+}
+
+impl<'p> MarkdownDocument<'p> {
+    /// Creates documentation of the types in a package, with the title `DSDL data types`
+    pub fn new(package: &'p CompiledPackage) -> Self {
+        MarkdownDocument {
+            package,
+            title: "DSDL data types".to_owned(),
+        }
+    }
+
+    /// Sets the title of the document, which is its top-level heading
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        MarkdownDocument {
+            title: title.into(),
+            ..self
+        }
+    }
+}
+
+/// A backend that writes a [`MarkdownDocument`]
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownBackend {
+    title: Option<String>,
+}
+
+impl MarkdownBackend {
+    /// Creates a backend that writes documents with the title `DSDL data types`
+    pub fn new() -> Self {
+        MarkdownBackend::default()
+    }
+
+    /// Sets the title of the documents
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        MarkdownBackend {
+            title: Some(title.into()),
+        }
+    }
+}
+
+impl Backend for MarkdownBackend {
+    type Error = io::Error;
+
+    fn generate(&self, package: &CompiledPackage, output: &mut dyn Write) -> io::Result<()> {
+        let mut document = MarkdownDocument::new(package);
+        if let Some(title) = &self.title {
+            document = document.with_title(title.clone());
+        }
+        write!(output, "{}", document)
+    }
+}
+
+impl Display for MarkdownDocument<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // The keys are sorted by name and then by version, so all the versions of a type are
+        // together
+        let mut groups: Vec<Vec<(&TypeKey, &CompiledDsdl)>> = Vec::new();
+        for (key, dsdl) in self.package.iter() {
+            match groups.last_mut() {
+                Some(group) if group[0].0.name() == key.name() => group.push((key, dsdl)),
+                _ => groups.push(vec![(key, dsdl)]),
+            }
+        }
+
+        writeln!(f, "# {}", escape(&self.title))?;
+        writeln!(f)?;
+        for group in &groups {
+            let name = group[0].0.name().to_string();
+            writeln!(f, "* [{}](#{})", name, anchor(&name))?;
+        }
+
+        for group in &groups {
+            writeln!(f)?;
+            writeln!(f, "## {}", group[0].0.name())?;
+            writeln!(f)?;
+            // Version history, newest first
+            write!(f, "Versions:")?;
+            for (i, (key, dsdl)) in group.iter().rev().enumerate() {
+                if i != 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, " [{}](#{})", key.version(), anchor(&key.to_string()))?;
+                if is_deprecated(dsdl) {
+                    write!(f, " (deprecated)")?;
+                }
+            }
+            writeln!(f)?;
+
+            for (key, dsdl) in group.iter().rev() {
+                write_type(f, key, dsdl)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_type(f: &mut Formatter<'_>, key: &TypeKey, dsdl: &CompiledDsdl) -> fmt::Result {
+    writeln!(f)?;
+    writeln!(f, "### {}", key)?;
+    writeln!(f)?;
+    if is_deprecated(dsdl) {
+        writeln!(f, "**Deprecated**")?;
+        writeln!(f)?;
+    }
+    match &dsdl.kind {
+        DsdlKind::Message(message) => {
+            write_comments(f, message.comments())?;
+            writeln!(f, "| Property | Value |")?;
+            writeln!(f, "|---|---|")?;
+            writeln!(f, "| Kind | Message |")?;
+            if let Some(port_id) = dsdl.fixed_port_id {
+                writeln!(f, "| Fixed subject ID | {} |", port_id)?;
+            }
+            write_properties(f, message)?;
+            write_message_tables(f, message, "####")?;
+        }
+        DsdlKind::Service { request, response } => {
+            write_comments(f, request.comments())?;
+            writeln!(f, "| Property | Value |")?;
+            writeln!(f, "|---|---|")?;
+            writeln!(f, "| Kind | Service |")?;
+            if let Some(port_id) = dsdl.fixed_port_id {
+                writeln!(f, "| Fixed service ID | {} |", port_id)?;
+            }
+            for (role, message) in [("Request", request), ("Response", response)] {
+                writeln!(f)?;
+                writeln!(f, "#### {}", role)?;
+                writeln!(f)?;
+                if role == "Response" {
+                    write_comments(f, message.comments())?;
+                }
+                writeln!(f, "| Property | Value |")?;
+                writeln!(f, "|---|---|")?;
+                write_properties(f, message)?;
+                write_message_tables(f, message, "#####")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the extent and size rows of a property table
+fn write_properties(f: &mut Formatter<'_>, message: &Message) -> fmt::Result {
+    match message.extent() {
+        Extent::Sealed => writeln!(f, "| Extent | Sealed |")?,
+        Extent::Delimited(extent) => {
+            writeln!(f, "| Extent | {} bits ({} bytes) |", extent, extent / 8)?
+        }
+    }
+    writeln!(
+        f,
+        "| Size | {} bits |",
+        RangeText(BitRange::new(message.bit_length()))
+    )
+}
+
+/// Writes the table of fields or variants and the table of constants of a message
+fn write_message_tables(f: &mut Formatter<'_>, message: &Message, heading: &str) -> fmt::Result {
+    let layout = message_layout(message);
+    match message.kind() {
+        MessageKind::Struct(mstruct) => {
+            if !mstruct.fields.is_empty() {
+                writeln!(f)?;
+                writeln!(f, "{} Fields", heading)?;
+                writeln!(f)?;
+                writeln!(f, "| Offset (bits) | Name | Type | Unit | Description |")?;
+                writeln!(f, "|---|---|---|---|---|")?;
+                for (field, field_layout) in mstruct.fields.iter().zip(&layout.fields) {
+                    let ty = match field.kind() {
+                        FieldKind::Data { ty, .. } => Some(ty),
+                        FieldKind::Padding(_) => None,
+                    };
+                    write_row(
+                        f,
+                        &RangeText(field_layout.offset).to_string(),
+                        field_layout,
+                        ty,
+                        field.comments(),
+                    )?;
+                }
+            }
+        }
+        MessageKind::Union(union) => {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{} Variants ({}-bit tag)",
+                heading, union.discriminant_bits
+            )?;
+            writeln!(f)?;
+            writeln!(f, "| Tag | Name | Type | Unit | Description |")?;
+            writeln!(f, "|---|---|---|---|---|")?;
+            for (i, (variant, variant_layout)) in
+                union.variants.iter().zip(&layout.fields).enumerate()
+            {
+                write_row(
+                    f,
+                    &i.to_string(),
+                    variant_layout,
+                    Some(variant.ty()),
+                    variant.comments(),
+                )?;
+            }
+        }
+    }
+
+    let mut constants = message.constants().into_iter().peekable();
+    if constants.peek().is_some() {
+        writeln!(f)?;
+        writeln!(f, "{} Constants", heading)?;
+        writeln!(f)?;
+        writeln!(f, "| Name | Type | Value | Description |")?;
+        writeln!(f, "|---|---|---|---|")?;
+        for (name, constant) in constants {
+            writeln!(
+                f,
+                "| `{}` | `{}` | {} | {} |",
+                name,
+                constant.ty(),
+                constant.value(),
+                description(constant.comments())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a row of a field or variant table
+fn write_row(
+    f: &mut Formatter<'_>,
+    position: &str,
+    layout: &FieldLayout,
+    ty: Option<&ResolvedType>,
+    comments: &str,
+) -> fmt::Result {
+    let name = match &layout.name {
+        Some(name) => format!("`{}`", name),
+        None => String::new(),
+    };
+    let unit = unit_from_comments(comments)
+        .map(escape)
+        .or_else(|| ty.and_then(si_unit))
+        .unwrap_or_default();
+    writeln!(
+        f,
+        "| {} | {} | `{}` | {} | {} |",
+        position,
+        name,
+        layout.ty,
+        unit,
+        description(comments)
+    )
+}
+
+/// Returns the unit in square brackets in a comment, if any
+fn unit_from_comments(comments: &str) -> Option<&str> {
+    let mut rest = comments;
+    while let Some(start) = rest.find('[') {
+        let after = &rest[start + 1..];
+        let end = after.find(']')?;
+        let candidate = after[..end].trim();
+        if candidate.chars().any(|c| c.is_alphabetic()) && !candidate.contains(',') {
+            return Some(candidate);
+        }
+        rest = &after[end + 1..];
+    }
+    None
+}
+
+/// Returns the unit of a field whose type is in the `uavcan.si.unit` namespace
+fn si_unit(ty: &ResolvedType) -> Option<String> {
+    match ty.scalar() {
+        ResolvedScalarType::Composite { key, inner } => {
+            let path = key.name().path();
+            if path.len() < 3 || path[..3] != ["uavcan", "si", "unit"] {
+                return None;
+            }
+            match inner.kind() {
+                MessageKind::Struct(mstruct) => mstruct
+                    .fields
+                    .iter()
+                    .find_map(|field| field.name())
+                    .map(escape),
+                MessageKind::Union(_) => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_deprecated(dsdl: &CompiledDsdl) -> bool {
+    match &dsdl.kind {
+        DsdlKind::Message(message) => message.deprecated(),
+        DsdlKind::Service { request, .. } => request.deprecated(),
+    }
+}
+
+/// Writes the comments of a type as a paragraph
+fn write_comments(f: &mut Formatter<'_>, comments: &str) -> fmt::Result {
+    // Comments are often formatted with line breaks and indentation, so they are written as
+    // preformatted text
+    if !comments.trim().is_empty() {
+        writeln!(f, "```text")?;
+        for line in comments.lines() {
+            writeln!(f, "{}", line.strip_prefix(' ').unwrap_or(line).trim_end())?;
+        }
+        writeln!(f, "```")?;
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// Returns the comments of a field, variant, or constant joined into one line for a table cell
+fn description(comments: &str) -> String {
+    let words: Vec<&str> = comments.split_whitespace().collect();
+    escape(&words.join(" "))
+}
+
+/// Escapes characters that have special meanings in Markdown tables and text
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '|' | '\\' | '*' | '_' | '`' | '<' | '>' | '[' | ']' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Returns the anchor of a heading, in the form that GitHub and most other Markdown renderers
+/// use
+fn anchor(heading: &str) -> String {
+    heading
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' '))
+        .map(|c| {
+            if c == ' ' {
+                '-'
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
@@ -1,7 +1,10 @@
 use crate::error::Error;
 use crate::operators::calculate_elementwise_binary;
 use crate::types::Value;
+use canadensis_bit_length_set::BitLengthSet;
 use canadensis_dsdl_parser::Span;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
 
 /// Evaluates the addition operator `expr + expr`
 pub(crate) fn evaluate(lhs: Value, rhs: Value, span: Span<'_>) -> Result<Value, Box<Error>> {
@@ -11,8 +14,27 @@ pub(crate) fn evaluate(lhs: Value, rhs: Value, span: Span<'_>) -> Result<Value,
             lhs.push_str(&rhs);
             Ok(Value::String(lhs))
         }
+        // Adding a length to each value in a bit length set does not require expanding it
+        (Value::BitLengthSet(lengths), Value::Rational(length))
+        | (Value::Rational(length), Value::BitLengthSet(lengths))
+            if as_length(&length).is_some() =>
+        {
+            let length = as_length(&length).unwrap();
+            Ok(Value::BitLengthSet(
+                lengths.concatenate([BitLengthSet::single(length)]),
+            ))
+        }
         (lhs, rhs) => calculate_elementwise_binary(lhs, rhs, span, "+", |lhs, rhs, _| {
             Ok(Value::Rational(lhs + rhs))
         }),
     }
 }
+
+/// Returns the value of a rational as a u64, if it is a non-negative integer that fits
+fn as_length(value: &BigRational) -> Option<u64> {
+    if value.is_integer() {
+        value.numer().to_u64()
+    } else {
+        None
+    }
+}
@@ -1,10 +1,16 @@
 use crate::compile::CompileContext;
 use crate::compiled::DsdlKind;
 use crate::error::Error;
+use crate::suggest::{closest_match, DidYouMean};
 use crate::types::set::Set;
+use crate::types::string::StringValue;
 use crate::types::{ExprType, ScalarType, Type, Value};
 use canadensis_bit_length_set::BitLengthSet;
 use canadensis_dsdl_parser::Span;
+use std::iter;
+
+/// The attributes of sets and bit length sets
+const SET_ATTRIBUTES: [&str; 3] = ["min", "max", "count"];
 
 /// Evaluates the attribute operator `expr.attribute`
 pub(crate) fn evaluate(
@@ -17,7 +23,8 @@ pub(crate) fn evaluate(
         Value::Set(lhs) => evaluate_set_attr(lhs, rhs, span),
         Value::BitLengthSet(lhs) => evaluate_bit_length_set_attr(lhs, rhs, span),
         Value::Type(ty) => evaluate_type_attr(cx, ty, rhs, span),
-        Value::Rational(_) | Value::String(_) | Value::Boolean(_) => {
+        Value::String(lhs) => evaluate_string_attr(lhs, rhs, span),
+        Value::Rational(_) | Value::Boolean(_) => {
             Err(span_error!(span, "{} has no attribute {}", lhs.ty(), rhs))
         }
     }
@@ -30,7 +37,26 @@ fn evaluate_set_attr(lhs: Set, rhs: &str, span: Span<'_>) -> Result<Value, Box<E
         "min" => evaluate_set_min(lhs, span),
         "max" => evaluate_set_max(lhs, span),
         "count" => Ok(lhs.len().into()),
-        _ => Err(span_error!(span, "Set does not have a {} attribute", rhs)),
+        _ => Err(span_error!(
+            span,
+            "Set does not have a {} attribute{}",
+            rhs,
+            DidYouMean(closest_match(rhs, SET_ATTRIBUTES))
+        )),
+    }
+}
+
+/// Evaluates an attribute of a string
+fn evaluate_string_attr(lhs: StringValue, rhs: &str, span: Span<'_>) -> Result<Value, Box<Error>> {
+    // Strings have a length attribute, which is the number of code points
+    match rhs {
+        "length" => Ok(lhs.chars().count().into()),
+        _ => Err(span_error!(
+            span,
+            "String does not have a {} attribute{}",
+            rhs,
+            DidYouMean(closest_match(rhs, ["length"]))
+        )),
     }
 }
 
@@ -45,7 +71,12 @@ fn evaluate_bit_length_set_attr(
         "min" => Ok(lhs.min_value().into()),
         "max" => Ok(lhs.max_value().into()),
         "count" => Ok(lhs.expand().len().into()),
-        _ => Err(span_error!(span, "Set does not have a {} attribute", rhs)),
+        _ => Err(span_error!(
+            span,
+            "Set does not have a {} attribute{}",
+            rhs,
+            DidYouMean(closest_match(rhs, SET_ATTRIBUTES))
+        )),
     }
 }
 
@@ -112,12 +143,20 @@ fn evaluate_type_attr(
                                 // Look up the constant
                                 match message.constants().get(rhs) {
                                     Some(constant) => Ok(constant.dsdl_value().clone()),
-                                    None => Err(span_error!(
-                                        span,
-                                        "Type {} has no attribute {}",
-                                        ty,
-                                        rhs
-                                    )),
+                                    None => {
+                                        let names = message
+                                            .constants()
+                                            .iter()
+                                            .map(|(name, _)| name.as_str())
+                                            .chain(iter::once("_bit_length_"));
+                                        Err(span_error!(
+                                            span,
+                                            "Type {} has no attribute {}{}",
+                                            ty,
+                                            rhs,
+                                            DidYouMean(closest_match(rhs, names))
+                                        ))
+                                    }
                                 }
                             }
                             DsdlKind::Service { .. } => {
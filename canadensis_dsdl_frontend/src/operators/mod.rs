@@ -17,6 +17,8 @@ use crate::types::{ExprType, Value};
 use canadensis_bit_length_set::BitLengthSet;
 use canadensis_dsdl_parser::Span;
 use num_rational::BigRational;
+use num_traits::ToPrimitive;
+use std::collections::BTreeSet;
 
 /// Calculates the result of a binary operation that can take the form
 /// `rational op rational -> rational`, `set<rational> op rational -> set<rational>`, or
@@ -130,11 +132,77 @@ where
             Ok(Value::Set(result))
         }
         (Value::BitLengthSet(lhs), Value::BitLengthSet(rhs)) => Ok(bit_length_set_op(lhs, rhs)),
+        // bit length set op set<rational>: Expand the bit length set into a normal set
+        (Value::BitLengthSet(lhs), Value::Set(rhs)) => calculate_rational_or_set_binary(
+            Value::Set(Set::from(lhs.expand())),
+            Value::Set(rhs),
+            span,
+            symbol,
+            rational_op,
+            set_op,
+            bit_length_set_op,
+        ),
+        (Value::Set(lhs), Value::BitLengthSet(rhs)) => calculate_rational_or_set_binary(
+            Value::Set(lhs),
+            Value::Set(Set::from(rhs.expand())),
+            span,
+            symbol,
+            rational_op,
+            set_op,
+            bit_length_set_op,
+        ),
         // Fall back and check rational/string possibilities
         (lhs, rhs) => calculate_rational_binary(lhs, rhs, span, symbol, rational_op),
     }
 }
 
+/// A comparison between two sets
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SetComparison {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<=`, true if the left set is a subset of the right set
+    Subset,
+    /// `>=`, true if the left set is a superset of the right set
+    Superset,
+    /// `<`, true if the left set is a proper subset of the right set
+    ProperSubset,
+    /// `>`, true if the left set is a proper superset of the right set
+    ProperSuperset,
+}
+
+impl SetComparison {
+    /// Applies this comparison to two sets
+    fn apply<T: Ord>(self, lhs: &BTreeSet<T>, rhs: &BTreeSet<T>) -> bool {
+        match self {
+            SetComparison::Equal => lhs == rhs,
+            SetComparison::NotEqual => lhs != rhs,
+            SetComparison::Subset => lhs.is_subset(rhs),
+            SetComparison::Superset => lhs.is_superset(rhs),
+            SetComparison::ProperSubset => lhs.len() < rhs.len() && lhs.is_subset(rhs),
+            SetComparison::ProperSuperset => lhs.len() > rhs.len() && lhs.is_superset(rhs),
+        }
+    }
+
+    /// Determines the result of this comparison from only the minimum and maximum values of two
+    /// non-empty sets, if possible
+    fn apply_to_bounds(self, lhs: (u64, u64), rhs: (u64, u64)) -> Option<bool> {
+        let within =
+            |inner: (u64, u64), outer: (u64, u64)| outer.0 <= inner.0 && inner.1 <= outer.1;
+        match self {
+            SetComparison::Equal if lhs != rhs => Some(false),
+            SetComparison::NotEqual if lhs != rhs => Some(true),
+            SetComparison::Subset | SetComparison::ProperSubset if !within(lhs, rhs) => Some(false),
+            SetComparison::Superset | SetComparison::ProperSuperset if !within(rhs, lhs) => {
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Calculates the result of a comparison operator that evaluates to a boolean
 ///
 /// The operator takes the form `rational op rational -> bool` or `set<T> op set<T> -> bool`,
@@ -145,31 +213,65 @@ where
 /// `rational_op` should be a function that applies the operator to two rational values and returns
 /// the result.
 ///
-/// `set_op` should be a function that applies the operator to two sets and returns
-/// the result.
-pub(crate) fn calculate_rational_or_set_comparison<F, G>(
+/// `set_comparison` is the operation on two sets.
+///
+/// A bit length set can have a very large number of values. When a bit length set is compared
+/// with another bit length set or a set of integers, this function first checks if the minimum
+/// and maximum values determine the result. If they do not, it expands the bit length set into
+/// integers, but not into a set of values.
+pub(crate) fn calculate_rational_or_set_comparison<F>(
     lhs: Value,
     rhs: Value,
     span: Span<'_>,
     symbol: &str,
     mut rational_op: F,
-    mut set_op: G,
+    set_comparison: SetComparison,
 ) -> Result<bool, Box<Error>>
 where
     F: FnMut(BigRational, BigRational) -> bool,
-    G: FnMut(Set, Set) -> bool,
 {
     match (lhs, rhs) {
         // set<T> op set<T> -> bool
-        (Value::Set(lhs), Value::Set(rhs)) if lhs.is_compatible(&rhs) => Ok(set_op(lhs, rhs)),
-        // If either side is a BitLengthSet, expand it into a normal set and try again
+        (Value::Set(lhs), Value::Set(rhs)) if lhs.is_compatible(&rhs) => {
+            Ok(set_comparison.apply(lhs.values(), rhs.values()))
+        }
+        (Value::BitLengthSet(lhs), Value::BitLengthSet(rhs)) => Ok(compare_lengths(
+            Lengths::Symbolic(&lhs),
+            Lengths::Symbolic(&rhs),
+            set_comparison,
+        )),
+        (Value::BitLengthSet(lhs), Value::Set(rhs)) if is_length_set(&rhs) => Ok(compare_lengths(
+            Lengths::Symbolic(&lhs),
+            Lengths::Expanded(length_set(&rhs)),
+            set_comparison,
+        )),
+        (Value::Set(lhs), Value::BitLengthSet(rhs)) if is_length_set(&lhs) => Ok(compare_lengths(
+            Lengths::Expanded(length_set(&lhs)),
+            Lengths::Symbolic(&rhs),
+            set_comparison,
+        )),
+        // Otherwise, expand a BitLengthSet into a normal set and try again
         (Value::BitLengthSet(lhs), rhs) => {
             let lhs = Value::Set(Set::from(lhs.expand()));
-            calculate_rational_or_set_comparison(lhs, rhs, span, symbol, rational_op, set_op)
+            calculate_rational_or_set_comparison(
+                lhs,
+                rhs,
+                span,
+                symbol,
+                rational_op,
+                set_comparison,
+            )
         }
         (lhs, Value::BitLengthSet(rhs)) => {
             let rhs = Value::Set(Set::from(rhs.expand()));
-            calculate_rational_or_set_comparison(lhs, rhs, span, symbol, rational_op, set_op)
+            calculate_rational_or_set_comparison(
+                lhs,
+                rhs,
+                span,
+                symbol,
+                rational_op,
+                set_comparison,
+            )
         }
         // Fall back and try rational/string-as-integer possibilities
         (lhs, rhs) => {
@@ -184,6 +286,58 @@ where
     }
 }
 
+/// A set of bit lengths, which may not have been expanded
+enum Lengths<'s> {
+    Symbolic(&'s BitLengthSet),
+    Expanded(BTreeSet<u64>),
+}
+
+impl Lengths<'_> {
+    /// Returns the minimum and maximum values, or None if this set is empty
+    fn bounds(&self) -> Option<(u64, u64)> {
+        match self {
+            Lengths::Symbolic(lengths) => Some((lengths.min_value(), lengths.max_value())),
+            Lengths::Expanded(lengths) => Some((*lengths.first()?, *lengths.last()?)),
+        }
+    }
+
+    fn expand(self) -> BTreeSet<u64> {
+        match self {
+            Lengths::Symbolic(lengths) => lengths.expand(),
+            Lengths::Expanded(lengths) => lengths,
+        }
+    }
+}
+
+/// Compares two sets of bit lengths, expanding them only if their minimum and maximum values do
+/// not determine the result
+fn compare_lengths(lhs: Lengths<'_>, rhs: Lengths<'_>, set_comparison: SetComparison) -> bool {
+    if let (Some(lhs_bounds), Some(rhs_bounds)) = (lhs.bounds(), rhs.bounds()) {
+        if let Some(result) = set_comparison.apply_to_bounds(lhs_bounds, rhs_bounds) {
+            return result;
+        }
+    }
+    set_comparison.apply(&lhs.expand(), &rhs.expand())
+}
+
+/// Returns true if every value in a set is an integer that can be a bit length
+fn is_length_set(set: &Set) -> bool {
+    set.iter().all(|value| match value {
+        Value::Rational(value) => value.is_integer() && value.numer().to_u64().is_some(),
+        _ => false,
+    })
+}
+
+/// Converts a set that contains only integers in the range of u64 into a set of u64s
+fn length_set(set: &Set) -> BTreeSet<u64> {
+    set.iter()
+        .map(|value| match value {
+            Value::Rational(value) => value.numer().to_u64().expect("Not a length"),
+            _ => panic!("Not a length"),
+        })
+        .collect()
+}
+
 /// Calculates the result of a binary operation that can take the form
 /// `rational op rational -> rational`
 ///
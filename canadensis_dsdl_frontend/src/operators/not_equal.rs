@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::operators::calculate_rational_or_set_comparison;
+use crate::operators::{calculate_rational_or_set_comparison, SetComparison};
 use crate::types::Value;
 use canadensis_dsdl_parser::Span;
 
@@ -20,7 +20,7 @@ pub(crate) fn evaluate(lhs: Value, rhs: Value, span: Span<'_>) -> Result<Value,
             span,
             "!=",
             |lhs, rhs| lhs != rhs,
-            |lhs, rhs| lhs != rhs,
+            SetComparison::NotEqual,
         ),
     }?;
     Ok(Value::Boolean(result))
@@ -1,14 +1,16 @@
 use crate::compile::CompileOutput;
 use crate::compiled::package::CompiledPackage;
 use crate::error::Error;
+use crate::print::{PrintHandler, PrintOutput};
 use crate::type_key::{TypeFullName, TypeKey};
 use crate::types::keywords::{is_reserved_keyword, is_valid_identifier};
-use crate::warning::Warnings;
+use crate::warning::{WarningRule, Warnings};
 use canadensis_dsdl_parser::{Config, TypeVersion};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::{fs, iter};
@@ -28,12 +30,38 @@ pub struct Package {
     /// Because TypeKey compares using lowercase forms of the path and name, this does not allow
     /// multiple definitions with names that differ only in case.
     files: BTreeMap<TypeKey, DsdlFile>,
+    /// The warning rules that can report warnings
+    enabled_warnings: BTreeSet<WarningRule>,
+    /// The function that receives the output of @print directives
+    print_handler: PrintHandler,
 }
 
 impl Package {
     pub fn new() -> Self {
         Package {
             files: BTreeMap::new(),
+            enabled_warnings: WarningRule::defaults(),
+            print_handler: PrintHandler::default(),
+        }
+    }
+    /// Sets the function that receives the output of `@print` directives
+    ///
+    /// By default, the output of each directive is written to standard error.
+    pub fn set_print_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&PrintOutput<'_>) + 'static,
+    {
+        self.print_handler = PrintHandler::new(handler);
+    }
+    /// Enables or disables a warning rule
+    ///
+    /// Initially, the rules that are [enabled by default](WarningRule::enabled_by_default)
+    /// are enabled.
+    pub fn set_warning_enabled(&mut self, rule: WarningRule, enabled: bool) {
+        if enabled {
+            self.enabled_warnings.insert(rule);
+        } else {
+            self.enabled_warnings.remove(&rule);
         }
     }
     /// Scans for DSDL files in the provided root directory and adds them to this package
@@ -127,6 +155,50 @@ impl Package {
         )
     }
 
+    /// Adds DSDL files from memory to this package
+    ///
+    /// Each item is a path and the content of the file at that path. A path is relative to a
+    /// root directory, like `uavcan/node/7509.Heartbeat.1.0.dsdl`, and the type key and fixed
+    /// port ID come from the path in the same way as for [`add_files`](Self::add_files). Paths
+    /// that do not end with `.uavcan` or `.dsdl` are ignored.
+    ///
+    /// The paths appear in error messages, but [`paths`](Self::paths) does not return them.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    /// * A file name does not have the correct format
+    /// * Any part of the namespace or name is not a valid identifier, or is a reserved keyword
+    /// * The combined path and name of a data type are too long
+    /// * A data type version is 0.0
+    /// * A file represents a data type that is already in this package
+    ///
+    pub fn add_virtual_files<I, P, S>(&mut self, files: I) -> Result<(), Box<Error>>
+    where
+        I: IntoIterator<Item = (P, S)>,
+        P: AsRef<Path>,
+        S: Into<String>,
+    {
+        for (path, content) in files {
+            let path = path.as_ref();
+            if !has_dsdl_extension(path) {
+                continue;
+            }
+            let (key, fixed_port_id) = info_from_path(Path::new(""), path)?;
+            self.try_insert(
+                key,
+                DsdlFile {
+                    source: FileSource::Virtual {
+                        path: path.to_owned(),
+                        content: content.into(),
+                    },
+                    fixed_port_id,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
     fn add_file_from_path(&mut self, root: &Path, file: DirEntry) -> Result<(), Box<Error>> {
         let (key, fixed_port_id) = info_from_path(root, file.path())?;
 
@@ -150,10 +222,37 @@ impl Package {
             Entry::Occupied(entry) => Err(Box::new(Error::DuplicateKey {
                 old: entry.key().clone(),
                 new: key,
+                old_path: entry.get().display_path().map(PathBuf::from),
+                new_path: dsdl.display_path().map(PathBuf::from),
             })),
         }
     }
 
+    /// Returns the paths of the DSDL files in this package, in the order of their type keys
+    ///
+    /// DSDL that was added as a string has no path and is not included.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.files.values().filter_map(DsdlFile::path)
+    }
+
+    /// Feeds the key, fixed port ID, and content of each DSDL file in this package into a hasher
+    ///
+    /// If nothing has been added, removed, or changed, this feeds the same data into the hasher
+    /// every time. This can be used to skip compiling a package that has not changed since it was
+    /// last compiled. The settings of this package (like enabled warnings) are not included.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if any DSDL file could not be read.
+    pub fn hash_sources<H: Hasher>(&self, state: &mut H) -> Result<(), Box<Error>> {
+        for (key, file) in &self.files {
+            key.to_string().hash(state);
+            file.fixed_port_id.hash(state);
+            file.read()?.hash(state);
+        }
+        Ok(())
+    }
+
     /// Compiles all input files that were previously added
     ///
     /// # Errors
@@ -179,7 +278,12 @@ impl Package {
         self,
         config: &Config,
     ) -> Result<CompiledPackage, (Box<Error>, Warnings)> {
-        match crate::compile::compile(self.files, config) {
+        match crate::compile::compile(
+            self.files,
+            config,
+            self.enabled_warnings,
+            self.print_handler,
+        ) {
             CompileOutput {
                 dsdl: Ok(types),
                 warnings,
@@ -233,14 +337,13 @@ fn validate_full_key(key: &TypeKey) -> Result<(), Box<Error>> {
 
 /// Returns true if the provided entry is a DSDL file
 fn is_dsdl(entry: &DirEntry) -> bool {
-    if entry.file_type().is_file() {
-        entry
-            .path()
-            .extension()
-            .is_some_and(|extension| extension == "uavcan" || extension == "dsdl")
-    } else {
-        false
-    }
+    entry.file_type().is_file() && has_dsdl_extension(entry.path())
+}
+
+/// Returns true if a path has one of the file extensions for DSDL files
+fn has_dsdl_extension(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "uavcan" || extension == "dsdl")
 }
 
 /// Returns a function that converts a walkdir error to an Error associated with the provided path
@@ -268,6 +371,11 @@ pub(crate) struct DsdlFile {
 enum FileSource {
     File(PathBuf),
     String(String),
+    /// A file in memory, with a path that is not on disk
+    Virtual {
+        path: PathBuf,
+        content: String,
+    },
 }
 
 impl DsdlFile {
@@ -280,17 +388,26 @@ impl DsdlFile {
                     inner: e,
                 })
             }),
-            FileSource::String(content) => Ok(content.clone()),
+            FileSource::String(content) | FileSource::Virtual { content, .. } => {
+                Ok(content.clone())
+            }
         }
     }
     /// Returns the fixed port ID for this type, if it has one
     pub(crate) fn fixed_port_id(&self) -> Option<u32> {
         self.fixed_port_id
     }
-    /// Returns the path to this file, or None if this DSDL is not from a file
+    /// Returns the path to this file, or None if this DSDL is not from a file on disk
     pub(crate) fn path(&self) -> Option<&Path> {
         match &self.source {
             FileSource::File(path) => Some(path),
+            FileSource::String(_) | FileSource::Virtual { .. } => None,
+        }
+    }
+    /// Returns the path to show in error messages, which may be a virtual path
+    pub(crate) fn display_path(&self) -> Option<&Path> {
+        match &self.source {
+            FileSource::File(path) | FileSource::Virtual { path, .. } => Some(path),
             FileSource::String(_) => None,
         }
     }
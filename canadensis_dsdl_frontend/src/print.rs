@@ -0,0 +1,68 @@
+//! Output from `@print` directives
+
+use crate::TypeKey;
+use std::fmt;
+use std::path::Path;
+
+/// The output of one `@print` directive
+#[derive(Debug, Clone)]
+pub struct PrintOutput<'a> {
+    pub(crate) key: &'a TypeKey,
+    pub(crate) path: Option<&'a Path>,
+    pub(crate) line: usize,
+    pub(crate) text: &'a str,
+}
+
+impl<'a> PrintOutput<'a> {
+    /// Returns the key of the type that contains the directive
+    pub fn key(&self) -> &'a TypeKey {
+        self.key
+    }
+    /// Returns the path to the file that contains the directive, or None if the type was not
+    /// read from a file
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+    /// Returns the line number (starting at 1) of the directive
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    /// Returns the value of the expression, formatted as text
+    ///
+    /// This is empty if the directive does not have an expression.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+}
+
+/// A function that receives the output of `@print` directives
+pub(crate) struct PrintHandler(Box<dyn FnMut(&PrintOutput<'_>)>);
+
+impl PrintHandler {
+    pub(crate) fn new<F>(handler: F) -> Self
+    where
+        F: FnMut(&PrintOutput<'_>) + 'static,
+    {
+        PrintHandler(Box::new(handler))
+    }
+
+    pub(crate) fn print(&mut self, output: &PrintOutput<'_>) {
+        (self.0)(output)
+    }
+}
+
+impl Default for PrintHandler {
+    /// Returns a handler that writes each output to standard error
+    ///
+    /// Standard output is not used because it may be read by another program, like Cargo when
+    /// the compiler runs in a build script.
+    fn default() -> Self {
+        PrintHandler::new(|output| eprintln!("{}", output.text))
+    }
+}
+
+impl fmt::Debug for PrintHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrintHandler").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,406 @@
+//! Reports of the serialized layouts of compiled types
+//!
+//! A layout report lists the types in a package. For each type, it shows the fixed port ID,
+//! the extent, the minimum and maximum serialized sizes, and the bit offset and size of
+//! each field or variant.
+//!
+//! Offsets and sizes are in bits. A variable-length array, a delimited composite type, or a field
+//! that follows one of these can have more than one possible offset, so offsets and sizes are
+//! ranges with a minimum and a maximum. Sizes include the length field of a variable-length array
+//! and the delimiter header of a delimited composite type.
+
+use crate::compiled::package::CompiledPackage;
+use crate::compiled::{DsdlKind, Extent, FieldKind, Message, MessageKind};
+use crate::type_key::TypeKey;
+use crate::types::{PrimitiveType, ResolvedType};
+use canadensis_bit_length_set::BitLengthSet;
+use std::fmt::{self, Display, Formatter};
+
+/// A report of the layouts of the types in a package
+#[derive(Debug)]
+pub struct LayoutReport<'p> {
+    types: Vec<TypeLayout<'p>>,
+}
+
+/// The layout of one type
+#[derive(Debug)]
+pub struct TypeLayout<'p> {
+    /// The name and version of the type
+    pub key: &'p TypeKey,
+    /// The fixed port ID of the type, if it has one
+    pub fixed_port_id: Option<u32>,
+    /// The layout of a message type, or the layouts of the request and response of a service type
+    pub kind: TypeLayoutKind,
+}
+
+/// The layouts of the messages in a message or service type
+#[derive(Debug)]
+pub enum TypeLayoutKind {
+    /// A message type
+    Message(MessageLayout),
+    /// A service type
+    Service {
+        /// The request
+        request: MessageLayout,
+        /// The response
+        response: MessageLayout,
+    },
+}
+
+/// The layout of a message type, or the request or response of a service type
+#[derive(Debug)]
+pub struct MessageLayout {
+    /// The extent in bits, or None if the message is sealed
+    pub extent: Option<u64>,
+    /// The number of bits in the discriminant, if this is a union
+    pub discriminant_bits: Option<u8>,
+    /// The range of possible serialized sizes in bits
+    pub size: BitRange,
+    /// The fields of a struct, or the variants of a union
+    pub fields: Vec<FieldLayout>,
+}
+
+/// The layout of a field or variant
+#[derive(Debug)]
+pub struct FieldLayout {
+    /// The name of the field or variant, or None for a padding field
+    pub name: Option<String>,
+    /// The type of the field or variant, like `saturated uint8[<=4]`
+    pub ty: String,
+    /// The range of possible offsets in bits from the beginning of the message
+    pub offset: BitRange,
+    /// The range of possible sizes in bits
+    pub size: BitRange,
+    /// The maximum number of bits of implicit padding that may be inserted before this field to
+    /// align it
+    pub alignment_padding: u64,
+}
+
+/// A range of possible bit offsets or lengths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitRange {
+    /// The minimum value
+    pub min: u64,
+    /// The maximum value
+    pub max: u64,
+}
+
+impl BitRange {
+    pub(crate) fn new(lengths: &BitLengthSet) -> Self {
+        BitRange {
+            min: lengths.min_value(),
+            max: lengths.max_value(),
+        }
+    }
+}
+
+impl<'p> LayoutReport<'p> {
+    /// Creates a report of the types in a package
+    pub fn new(package: &'p CompiledPackage) -> Self {
+        let types = package
+            .iter()
+            .map(|(key, dsdl)| TypeLayout {
+                key,
+                fixed_port_id: dsdl.fixed_port_id,
+                kind: match &dsdl.kind {
+                    DsdlKind::Message(message) => TypeLayoutKind::Message(message_layout(message)),
+                    DsdlKind::Service { request, response } => TypeLayoutKind::Service {
+                        request: message_layout(request),
+                        response: message_layout(response),
+                    },
+                },
+            })
+            .collect();
+        LayoutReport { types }
+    }
+
+    /// Returns the layouts of the types in this report, sorted by name and then version
+    pub fn types(&self) -> &[TypeLayout<'p>] {
+        &self.types
+    }
+
+    /// Returns an object that displays this report as human-readable text
+    pub fn text(&self) -> Text<'_, 'p> {
+        Text(self)
+    }
+
+    /// Returns an object that displays this report as JSON
+    ///
+    /// The JSON is an object with a `types` field, which is an array with an object for each
+    /// type. Each type object has these fields:
+    /// * `key`: The name and version of the type, like `"uavcan.node.Heartbeat.1.0"`
+    /// * `fixed_port_id`: The fixed port ID, or `null`
+    /// * `kind`: `"message"` or `"service"`
+    /// * `message` (for a message type), or `request` and `response` (for a service type)
+    ///
+    /// Each message, request, or response is an object with these fields:
+    /// * `extent_bits`: The extent in bits, or `null` if the type is sealed
+    /// * `discriminant_bits`: The length of the union discriminant in bits, or `null` if the
+    ///   type is not a union
+    /// * `size_bits`: The range of possible serialized sizes
+    /// * `fields`: An array with an object for each field or variant
+    ///
+    /// Each field object has these fields:
+    /// * `name`: The name of the field or variant, or `null` for a padding field
+    /// * `type`: The type of the field or variant, like `"saturated uint8[<=4]"`
+    /// * `offset_bits`: The range of possible offsets from the beginning of the message
+    /// * `size_bits`: The range of possible sizes
+    /// * `alignment_padding_bits`: The maximum number of bits of implicit padding before the field
+    ///
+    /// Each range is an object with `min` and `max` fields.
+    pub fn json(&self) -> Json<'_, 'p> {
+        Json(self)
+    }
+}
+
+pub(crate) fn message_layout(message: &Message) -> MessageLayout {
+    let extent = match message.extent() {
+        Extent::Sealed => None,
+        Extent::Delimited(extent) => Some(*extent),
+    };
+    match message.kind() {
+        MessageKind::Struct(struct_data) => {
+            // This follows the same steps as the compiler to find the offset of each field
+            let mut length = BitLengthSet::single(0);
+            let fields = struct_data
+                .fields
+                .iter()
+                .map(|field| match field.kind() {
+                    FieldKind::Padding(bits) => {
+                        let layout = FieldLayout {
+                            name: None,
+                            ty: format!("void{}", bits),
+                            offset: BitRange::new(&length),
+                            size: BitRange {
+                                min: u64::from(*bits),
+                                max: u64::from(*bits),
+                            },
+                            alignment_padding: 0,
+                        };
+                        length = length
+                            .clone()
+                            .concatenate([BitLengthSet::single((*bits).into())]);
+                        layout
+                    }
+                    FieldKind::Data { ty, name } => {
+                        let (size, alignment) = size_and_alignment(ty);
+                        let start = length.clone().pad_to_alignment(alignment);
+                        let layout = FieldLayout {
+                            name: Some(name.clone()),
+                            ty: ty.to_string(),
+                            offset: BitRange::new(&start),
+                            size: BitRange::new(&size),
+                            alignment_padding: max_alignment_padding(&length, alignment),
+                        };
+                        length = start.concatenate([size]).pad_to_alignment(alignment);
+                        layout
+                    }
+                })
+                .collect();
+            MessageLayout {
+                extent,
+                discriminant_bits: None,
+                size: BitRange::new(message.bit_length()),
+                fields,
+            }
+        }
+        MessageKind::Union(union_data) => {
+            let discriminant = BitLengthSet::single(union_data.discriminant_bits.into());
+            let fields = union_data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let (size, alignment) = size_and_alignment(variant.ty());
+                    let start = discriminant.clone().pad_to_alignment(alignment);
+                    FieldLayout {
+                        name: Some(variant.name().to_owned()),
+                        ty: variant.ty().to_string(),
+                        offset: BitRange::new(&start),
+                        size: BitRange::new(&size),
+                        alignment_padding: max_alignment_padding(&discriminant, alignment),
+                    }
+                })
+                .collect();
+            MessageLayout {
+                extent,
+                discriminant_bits: Some(union_data.discriminant_bits),
+                size: BitRange::new(message.bit_length()),
+                fields,
+            }
+        }
+    }
+}
+
+/// Returns the possible lengths of a field, including any array length or delimiter header, and
+/// its alignment
+fn size_and_alignment(ty: &ResolvedType) -> (BitLengthSet, u32) {
+    match ty.implicit_field() {
+        Some(implicit) => {
+            let implicit = PrimitiveType::from(implicit);
+            (
+                BitLengthSet::single(implicit.bit_length()).concatenate([ty.size()]),
+                std::cmp::max(ty.alignment(), implicit.alignment()),
+            )
+        }
+        None => (ty.size(), ty.alignment()),
+    }
+}
+
+/// Returns the maximum number of padding bits needed to align a field that follows some fields
+/// with the provided possible lengths
+fn max_alignment_padding(length: &BitLengthSet, alignment: u32) -> u64 {
+    let alignment = u64::from(alignment);
+    // If all the possible lengths need the same amount of padding, this finds it
+    for padding in 0..alignment {
+        let padded = length.clone().concatenate([BitLengthSet::single(padding)]);
+        if padded.is_aligned(alignment) {
+            return padding;
+        }
+    }
+    // Without expanding the set of lengths, which may be very large, this is the only limit
+    // that is known
+    alignment - 1
+}
+
+/// Displays a layout report as human-readable text
+pub struct Text<'r, 'p>(&'r LayoutReport<'p>);
+
+impl Display for Text<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, ty) in self.0.types.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", ty.key)?;
+            if let Some(port_id) = ty.fixed_port_id {
+                write!(f, ", fixed port ID {}", port_id)?;
+            }
+            writeln!(f)?;
+            match &ty.kind {
+                TypeLayoutKind::Message(message) => write_message_text(f, "message", message)?,
+                TypeLayoutKind::Service { request, response } => {
+                    write_message_text(f, "request", request)?;
+                    write_message_text(f, "response", response)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_message_text(f: &mut Formatter<'_>, role: &str, message: &MessageLayout) -> fmt::Result {
+    write!(f, "  {}: ", role)?;
+    match message.extent {
+        Some(extent) => write!(f, "extent {} bits", extent)?,
+        None => write!(f, "sealed")?,
+    }
+    write!(f, ", size {} bits", RangeText(message.size))?;
+    if let Some(discriminant_bits) = message.discriminant_bits {
+        write!(f, ", union with {}-bit discriminant", discriminant_bits)?;
+    }
+    writeln!(f)?;
+    for field in &message.fields {
+        write!(
+            f,
+            "    offset {}, size {}",
+            RangeText(field.offset),
+            RangeText(field.size)
+        )?;
+        if field.alignment_padding != 0 {
+            write!(f, ", up to {} bits padding before", field.alignment_padding)?;
+        }
+        write!(f, ": {}", field.ty)?;
+        if let Some(name) = &field.name {
+            write!(f, " {}", name)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// Displays a range as one number if the minimum and maximum are equal, or min..=max otherwise
+pub(crate) struct RangeText(pub BitRange);
+
+impl Display for RangeText {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.min == self.0.max {
+            write!(f, "{}", self.0.min)
+        } else {
+            write!(f, "{}..={}", self.0.min, self.0.max)
+        }
+    }
+}
+
+/// Displays a layout report as JSON
+pub struct Json<'r, 'p>(&'r LayoutReport<'p>);
+
+impl Display for Json<'_, '_> {
+    // Type names, field names, and types contain only letters, digits, underscores, dots,
+    // spaces, and brackets, so they do not need to be escaped.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"types\":[")?;
+        for (i, ty) in self.0.types.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{{\"key\":\"{}\",", ty.key)?;
+            match ty.fixed_port_id {
+                Some(port_id) => write!(f, "\"fixed_port_id\":{},", port_id)?,
+                None => write!(f, "\"fixed_port_id\":null,")?,
+            }
+            match &ty.kind {
+                TypeLayoutKind::Message(message) => {
+                    write!(f, "\"kind\":\"message\",\"message\":")?;
+                    write_message_json(f, message)?;
+                }
+                TypeLayoutKind::Service { request, response } => {
+                    write!(f, "\"kind\":\"service\",\"request\":")?;
+                    write_message_json(f, request)?;
+                    write!(f, ",\"response\":")?;
+                    write_message_json(f, response)?;
+                }
+            }
+            write!(f, "}}")?;
+        }
+        writeln!(f, "]}}")
+    }
+}
+
+fn write_message_json(f: &mut Formatter<'_>, message: &MessageLayout) -> fmt::Result {
+    match message.extent {
+        Some(extent) => write!(f, "{{\"extent_bits\":{},", extent)?,
+        None => write!(f, "{{\"extent_bits\":null,")?,
+    }
+    match message.discriminant_bits {
+        Some(bits) => write!(f, "\"discriminant_bits\":{},", bits)?,
+        None => write!(f, "\"discriminant_bits\":null,")?,
+    }
+    write!(f, "\"size_bits\":{},\"fields\":[", RangeJson(message.size))?;
+    for (i, field) in message.fields.iter().enumerate() {
+        if i != 0 {
+            write!(f, ",")?;
+        }
+        match &field.name {
+            Some(name) => write!(f, "{{\"name\":\"{}\",", name)?,
+            None => write!(f, "{{\"name\":null,")?,
+        }
+        write!(
+            f,
+            "\"type\":\"{}\",\"offset_bits\":{},\"size_bits\":{},\"alignment_padding_bits\":{}}}",
+            field.ty,
+            RangeJson(field.offset),
+            RangeJson(field.size),
+            field.alignment_padding
+        )?;
+    }
+    write!(f, "]}}")
+}
+
+/// Displays a range as a JSON object
+struct RangeJson(BitRange);
+
+impl Display for RangeJson {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{\"min\":{},\"max\":{}}}", self.0.min, self.0.max)
+    }
+}
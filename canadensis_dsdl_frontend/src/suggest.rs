@@ -0,0 +1,91 @@
+//! Suggestions of similar names, for errors about names that could not be found
+
+use std::cmp;
+use std::fmt::{self, Display, Formatter};
+
+/// Returns the candidate that is most similar to `name`, if any candidate is similar enough to be
+/// a likely typo
+///
+/// Names are compared without case, using the number of characters that need to be inserted,
+/// removed, or replaced to change one name into the other. A candidate is similar enough if that
+/// number is at most a third of the length of `name` (or 1, for short names). If several
+/// candidates are equally similar, this function returns the first one.
+pub(crate) fn closest_match<'c, I>(name: &str, candidates: I) -> Option<&'c str>
+where
+    I: IntoIterator<Item = &'c str>,
+{
+    let max_distance = cmp::max(1, name.chars().count() / 3);
+    let name_lower = name.to_lowercase();
+    candidates
+        .into_iter()
+        .filter(|&candidate| candidate != name)
+        .map(|candidate| {
+            let distance = edit_distance(&name_lower, &candidate.to_lowercase());
+            (distance, candidate)
+        })
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Calculates the Levenshtein distance between two strings, counting characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // The distances from a prefix of a to each prefix of b
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = cmp::min(substitution, cmp::min(previous[j + 1], current[j]) + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Displays a note with a suggested name, or nothing if there is no suggestion
+pub(crate) struct DidYouMean<T>(pub Option<T>);
+
+impl<T: Display> Display for DidYouMean<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(suggestion) => write!(f, "\n  = help: Did you mean {}?", suggestion),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{closest_match, edit_distance};
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("heartbeat", "hearbeat"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("crème", "creme"), 1);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = ["MAX_LENGTH", "MIN_LENGTH", "Heartbeat"];
+        assert_eq!(
+            closest_match("MAX_LENGHT", candidates.iter().copied()),
+            Some("MAX_LENGTH")
+        );
+        assert_eq!(
+            closest_match("heartbeat", candidates.iter().copied()),
+            Some("Heartbeat")
+        );
+        assert_eq!(closest_match("LENGTH", candidates.iter().copied()), None);
+        assert_eq!(
+            closest_match("Heartbeat", ["Heartbeat"].iter().copied()),
+            None
+        );
+    }
+}
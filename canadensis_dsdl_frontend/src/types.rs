@@ -5,7 +5,7 @@ pub(crate) mod directive;
 pub(crate) mod expression;
 pub(crate) mod keywords;
 pub(crate) mod set;
-mod string;
+pub(crate) mod string;
 
 use crate::compile::CompileContext;
 use crate::compiled::{DsdlKind, Extent, Message};
@@ -510,9 +510,9 @@ impl ResolvedType {
     pub fn size(&self) -> BitLengthSet {
         match self {
             ResolvedType::Scalar(scalar) => scalar.size(),
-            ResolvedType::FixedArray { inner, len } => inner.size().repeat(*len),
+            ResolvedType::FixedArray { inner, len } => inner.element_size().repeat(*len),
             ResolvedType::VariableArray { inner, max_len } => {
-                inner.size().repeat_range(..=*max_len)
+                inner.element_size().repeat_range(..=*max_len)
             }
         }
     }
@@ -587,6 +587,20 @@ impl ResolvedScalarType {
             ResolvedScalarType::Void { bits } => BitLengthSet::single(u64::from(*bits)),
         }
     }
+    /// Returns the possible bit lengths of this type as an array element
+    ///
+    /// This includes the delimiter header that comes before each element of a delimited
+    /// composite type.
+    fn element_size(&self) -> BitLengthSet {
+        match self.implicit_field() {
+            Some(implicit) => {
+                let header_length = PrimitiveType::from(implicit).bit_length();
+                self.size()
+                    .concatenate([BitLengthSet::single(header_length)])
+            }
+            None => self.size(),
+        }
+    }
     /// Returns the required alignment of this type, in bits
     pub fn alignment(&self) -> u32 {
         match self {
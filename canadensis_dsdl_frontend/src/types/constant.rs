@@ -26,6 +26,10 @@ pub struct Constant {
     comments: String,
     /// The offset, in bytes into the file, of the end of this constant definition
     end_offset: usize,
+    /// The line number (starting at 1) where this constant is defined
+    line: usize,
+    /// True if an expression in the same file has used the value of this constant
+    used: bool,
 }
 
 impl Constant {
@@ -39,6 +43,7 @@ impl Constant {
         let ty: PrimitiveType = ty.into();
         let value_span = value.span;
         let end_offset = value_span.end();
+        let (line, _) = name.span.start_pos().line_col();
         if is_reserved_keyword(name.name) {
             return Err(span_error!(
                 name.span,
@@ -55,6 +60,8 @@ impl Constant {
             value,
             comments: String::new(),
             end_offset,
+            line,
+            used: false,
         })
     }
 
@@ -91,6 +98,18 @@ impl Constant {
     pub(crate) fn end_offset(&self) -> usize {
         self.end_offset
     }
+    /// Returns the line number (starting at 1) where this constant is defined
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
+    /// Records that an expression has used the value of this constant
+    pub(crate) fn mark_used(&mut self) {
+        self.used = true;
+    }
+    /// Returns true if an expression in the same file has used the value of this constant
+    pub(crate) fn is_used(&self) -> bool {
+        self.used
+    }
 }
 
 /// Checks a declared type and an expression value to determine if they are compatible
@@ -1,6 +1,6 @@
 use crate::compile::CompileContext;
 use crate::error::Error;
-use crate::types::expression::evaluate_expression;
+use crate::types::expression::{evaluate_expression, evaluate_expression_and_operands};
 use crate::types::Value;
 use canadensis_dsdl_parser::{Expression, Identifier};
 use num_traits::{Signed, ToPrimitive};
@@ -87,10 +87,21 @@ pub(crate) fn evaluate_directive(
         "assert" => match expression {
             Some(expr) => {
                 let expr_span = expr.span;
-                match evaluate_expression(cx, expr)? {
-                    Value::Boolean(true) => Ok(()),
-                    Value::Boolean(false) => Err(span_error!(expr_span, "Assertion failed")),
-                    other => Err(span_error!(
+                match evaluate_expression_and_operands(cx, expr)? {
+                    (Value::Boolean(true), _) => Ok(()),
+                    (Value::Boolean(false), Some((lhs, rhs))) => Err(span_error!(
+                        expr_span,
+                        "Assertion failed: {}\n  = note: The left operand is {}\n  = note: The right operand is {}",
+                        expr_span.as_str(),
+                        lhs,
+                        rhs
+                    )),
+                    (Value::Boolean(false), None) => Err(span_error!(
+                        expr_span,
+                        "Assertion failed: {}",
+                        expr_span.as_str()
+                    )),
+                    (other, _) => Err(span_error!(
                         expr_span,
                         "Assert expression evaluated to non-boolean value {}",
                         other
@@ -100,15 +111,12 @@ pub(crate) fn evaluate_directive(
             None => Err(span_error!(name.span, "Assert directive has no expression")),
         },
         "print" => {
-            if let Some(expr) = expression {
-                let value = evaluate_expression(cx, expr)?;
-                // TODO: Should this be printed in some other way?
-                println!("{}", value);
-                Ok(())
-            } else {
-                // Print with no expression has no effect (perhaps it should print a new line)
-                Ok(())
-            }
+            let text = match expression {
+                Some(expr) => evaluate_expression(cx, expr)?.to_string(),
+                None => String::new(),
+            };
+            cx.print(name.span, &text);
+            Ok(())
         }
         _ => Err(span_error!(
             name.span,
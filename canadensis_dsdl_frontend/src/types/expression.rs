@@ -2,6 +2,7 @@
 
 use std::collections::BTreeSet;
 use std::convert::TryInto;
+use std::iter;
 use std::ops::RangeInclusive;
 
 use num_rational::BigRational;
@@ -17,8 +18,9 @@ use crate::error::Error;
 use crate::operators::{
     add, attribute, bit_and, bit_or, calculate_elementwise_binary,
     calculate_rational_or_set_binary, calculate_rational_or_set_comparison, equal, exponent,
-    make_set_error, not_equal, unary_minus, unary_not, unary_plus,
+    make_set_error, not_equal, unary_minus, unary_not, unary_plus, SetComparison,
 };
+use crate::suggest::{closest_match, DidYouMean};
 use crate::types::set::{Set, TypeError};
 use crate::types::string::StringValue;
 use crate::types::{ScalarType, Type, Value};
@@ -28,47 +30,128 @@ pub(crate) fn evaluate_expression(
     expression: Expression<'_>,
 ) -> Result<Value, Box<Error>> {
     let span = expression.span;
-    match expression.expression {
-        ExpressionType::Atom(atom) => evaluate_atom(cx, *atom, span),
-        ExpressionType::UnaryPlus(inner) => {
+    match split_binary(expression.expression) {
+        Ok((operator, lhs, rhs)) => {
+            let lhs = evaluate_expression(cx, *lhs)?;
+            let rhs = evaluate_expression(cx, *rhs)?;
+            evaluate_binary(operator, lhs, rhs, span)
+        }
+        Err(ExpressionType::Atom(atom)) => evaluate_atom(cx, *atom, span),
+        Err(ExpressionType::UnaryPlus(inner)) => {
             let inner = evaluate_expression(cx, *inner)?;
             unary_plus::evaluate(inner, span)
         }
-        ExpressionType::UnaryMinus(inner) => {
+        Err(ExpressionType::UnaryMinus(inner)) => {
             let inner = evaluate_expression(cx, *inner)?;
             unary_minus::evaluate(inner, span)
         }
-        ExpressionType::UnaryNot(inner) => {
+        Err(ExpressionType::UnaryNot(inner)) => {
             let inner = evaluate_expression(cx, *inner)?;
             unary_not::evaluate(inner, span)
         }
-        ExpressionType::Attribute(lhs, rhs) => {
+        Err(ExpressionType::Attribute(lhs, rhs)) => {
             let lhs = evaluate_expression(cx, *lhs)?;
             attribute::evaluate(cx, lhs, rhs, span)
         }
-        ExpressionType::Exponent(base, exponent) => {
-            let base = evaluate_expression(cx, *base)?;
-            let exponent = evaluate_expression(cx, *exponent)?;
-            exponent::evaluate(base, exponent, span)
-        }
-        ExpressionType::Multiply(lhs, rhs) => {
+        Err(_) => unreachable!("Binary operator not handled by split_binary"),
+    }
+}
+
+/// The values of the left and right operands of a binary operator
+pub(crate) type Operands = (Value, Value);
+
+/// Evaluates an expression, and also returns the values of its operands if it is a binary
+/// operator
+pub(crate) fn evaluate_expression_and_operands(
+    cx: &mut CompileContext<'_, '_>,
+    expression: Expression<'_>,
+) -> Result<(Value, Option<Operands>), Box<Error>> {
+    let span = expression.span;
+    match split_binary(expression.expression) {
+        Ok((operator, lhs, rhs)) => {
             let lhs = evaluate_expression(cx, *lhs)?;
             let rhs = evaluate_expression(cx, *rhs)?;
+            let value = evaluate_binary(operator, lhs.clone(), rhs.clone(), span)?;
+            Ok((value, Some((lhs, rhs))))
+        }
+        Err(expression) => {
+            let value = evaluate_expression(cx, Expression { expression, span })?;
+            Ok((value, None))
+        }
+    }
+}
+
+/// An operator with two operands that are both expressions
+#[derive(Debug, Clone, Copy)]
+enum BinaryOperator {
+    Exponent,
+    Multiply,
+    Divide,
+    Modulo,
+    Add,
+    Subtract,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Equal,
+    NotEqual,
+    LessOrEqual,
+    GreaterOrEqual,
+    Less,
+    Greater,
+    LogicalOr,
+    LogicalAnd,
+}
+
+/// A binary operator and its left and right operands
+type BinaryParts<'i> = (BinaryOperator, Box<Expression<'i>>, Box<Expression<'i>>);
+
+/// If an expression is a binary operator, returns the operator and its operands
+///
+/// Otherwise, this function returns the expression in an error.
+fn split_binary(expression: ExpressionType<'_>) -> Result<BinaryParts<'_>, ExpressionType<'_>> {
+    match expression {
+        ExpressionType::Exponent(lhs, rhs) => Ok((BinaryOperator::Exponent, lhs, rhs)),
+        ExpressionType::Multiply(lhs, rhs) => Ok((BinaryOperator::Multiply, lhs, rhs)),
+        ExpressionType::Divide(lhs, rhs) => Ok((BinaryOperator::Divide, lhs, rhs)),
+        ExpressionType::Modulo(lhs, rhs) => Ok((BinaryOperator::Modulo, lhs, rhs)),
+        ExpressionType::Add(lhs, rhs) => Ok((BinaryOperator::Add, lhs, rhs)),
+        ExpressionType::Subtract(lhs, rhs) => Ok((BinaryOperator::Subtract, lhs, rhs)),
+        ExpressionType::BitOr(lhs, rhs) => Ok((BinaryOperator::BitOr, lhs, rhs)),
+        ExpressionType::BitXor(lhs, rhs) => Ok((BinaryOperator::BitXor, lhs, rhs)),
+        ExpressionType::BitAnd(lhs, rhs) => Ok((BinaryOperator::BitAnd, lhs, rhs)),
+        ExpressionType::Equal(lhs, rhs) => Ok((BinaryOperator::Equal, lhs, rhs)),
+        ExpressionType::NotEqual(lhs, rhs) => Ok((BinaryOperator::NotEqual, lhs, rhs)),
+        ExpressionType::LessOrEqual(lhs, rhs) => Ok((BinaryOperator::LessOrEqual, lhs, rhs)),
+        ExpressionType::GreaterOrEqual(lhs, rhs) => Ok((BinaryOperator::GreaterOrEqual, lhs, rhs)),
+        ExpressionType::Less(lhs, rhs) => Ok((BinaryOperator::Less, lhs, rhs)),
+        ExpressionType::Greater(lhs, rhs) => Ok((BinaryOperator::Greater, lhs, rhs)),
+        ExpressionType::LogicalOr(lhs, rhs) => Ok((BinaryOperator::LogicalOr, lhs, rhs)),
+        ExpressionType::LogicalAnd(lhs, rhs) => Ok((BinaryOperator::LogicalAnd, lhs, rhs)),
+        other => Err(other),
+    }
+}
+
+/// Applies a binary operator to two values
+fn evaluate_binary(
+    operator: BinaryOperator,
+    lhs: Value,
+    rhs: Value,
+    span: Span<'_>,
+) -> Result<Value, Box<Error>> {
+    match operator {
+        BinaryOperator::Exponent => exponent::evaluate(lhs, rhs, span),
+        BinaryOperator::Multiply => {
             calculate_elementwise_binary(lhs, rhs, span, "*", |lhs, rhs, _| {
                 Ok(Value::Rational(lhs * rhs))
             })
         }
-        ExpressionType::Divide(lhs, rhs) => {
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
+        BinaryOperator::Divide => {
             calculate_elementwise_binary(lhs, rhs, span, "/", |lhs, rhs, _| {
                 Ok(Value::Rational(lhs / rhs))
             })
         }
-        ExpressionType::Modulo(lhs, rhs) => {
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-
+        BinaryOperator::Modulo => {
             match (lhs, rhs) {
                 // Special case for BitLengthSet % integer
                 (Value::BitLengthSet(lhs), Value::Rational(rhs)) if is_u64_integer(&rhs) => {
@@ -85,27 +168,15 @@ pub(crate) fn evaluate_expression(
                 }),
             }
         }
-        ExpressionType::Add(lhs, rhs) => {
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            add::evaluate(lhs, rhs, span)
-        }
-        ExpressionType::Subtract(lhs, rhs) => {
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
+        BinaryOperator::Add => add::evaluate(lhs, rhs, span),
+        BinaryOperator::Subtract => {
             calculate_elementwise_binary(lhs, rhs, span, "-", |lhs, rhs, _| {
                 Ok(Value::Rational(lhs - rhs))
             })
         }
-        ExpressionType::BitOr(lhs, rhs) => {
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            bit_or::evaluate(lhs, rhs, span)
-        }
-        ExpressionType::BitXor(lhs, rhs) => {
+        BinaryOperator::BitOr => bit_or::evaluate(lhs, rhs, span),
+        BinaryOperator::BitXor => {
             // a ^ b: Bitwise xor on integers, or disjunctive union of sets of the same type
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
             calculate_rational_or_set_binary(
                 lhs,
                 rhs,
@@ -124,84 +195,78 @@ pub(crate) fn evaluate_expression(
                 },
             )
         }
-        ExpressionType::BitAnd(lhs, rhs) => {
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            bit_and::evaluate(lhs, rhs, span)
-        }
-        ExpressionType::Equal(lhs, rhs) => {
+        BinaryOperator::BitAnd => bit_and::evaluate(lhs, rhs, span),
+        BinaryOperator::Equal => {
             // a == b: Rationals exactly equal, strings NFC equal, sets equal
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
             equal::evaluate(lhs, rhs, span)
         }
-        ExpressionType::NotEqual(lhs, rhs) => {
+        BinaryOperator::NotEqual => {
             // Opposite of equal
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
             not_equal::evaluate(lhs, rhs, span)
         }
-        ExpressionType::LessOrEqual(lhs, rhs) => {
-            // a <= b: Less or equal for rationals, subset for sets
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            let result = calculate_rational_or_set_comparison(
-                lhs,
-                rhs,
-                span,
-                "<=",
-                |lhs, rhs| lhs <= rhs,
-                |lhs, rhs| lhs.is_subset(&rhs),
-            )?;
+        BinaryOperator::LessOrEqual => {
+            // a <= b: Less or equal for rationals and strings, subset for sets
+            let result = match (lhs, rhs) {
+                (Value::String(lhs), Value::String(rhs)) => lhs <= rhs,
+                (lhs, rhs) => calculate_rational_or_set_comparison(
+                    lhs,
+                    rhs,
+                    span,
+                    "<=",
+                    |lhs, rhs| lhs <= rhs,
+                    SetComparison::Subset,
+                )?,
+            };
             Ok(Value::Boolean(result))
         }
-        ExpressionType::GreaterOrEqual(lhs, rhs) => {
-            // a >= b: Greater than or equal for rationals, superset for sets
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            let result = calculate_rational_or_set_comparison(
-                lhs,
-                rhs,
-                span,
-                ">=",
-                |lhs, rhs| lhs >= rhs,
-                |lhs, rhs| lhs.is_superset(&rhs),
-            )?;
+        BinaryOperator::GreaterOrEqual => {
+            // a >= b: Greater than or equal for rationals and strings, superset for sets
+            let result = match (lhs, rhs) {
+                (Value::String(lhs), Value::String(rhs)) => lhs >= rhs,
+                (lhs, rhs) => calculate_rational_or_set_comparison(
+                    lhs,
+                    rhs,
+                    span,
+                    ">=",
+                    |lhs, rhs| lhs >= rhs,
+                    SetComparison::Superset,
+                )?,
+            };
             Ok(Value::Boolean(result))
         }
-        ExpressionType::Less(lhs, rhs) => {
-            // a < b: Less for rationals, proper subset for sets
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            let result = calculate_rational_or_set_comparison(
-                lhs,
-                rhs,
-                span,
-                "<",
-                |lhs, rhs| lhs < rhs,
-                |lhs, rhs| lhs.is_subset(&rhs) && lhs != rhs,
-            )?;
+        BinaryOperator::Less => {
+            // a < b: Less for rationals and strings, proper subset for sets
+            let result = match (lhs, rhs) {
+                (Value::String(lhs), Value::String(rhs)) => lhs < rhs,
+                (lhs, rhs) => calculate_rational_or_set_comparison(
+                    lhs,
+                    rhs,
+                    span,
+                    "<",
+                    |lhs, rhs| lhs < rhs,
+                    SetComparison::ProperSubset,
+                )?,
+            };
             Ok(Value::Boolean(result))
         }
-        ExpressionType::Greater(lhs, rhs) => {
-            // a > b: Greater for rationals, proper superset for sets
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
-            let result = calculate_rational_or_set_comparison(
-                lhs,
-                rhs,
-                span,
-                ">",
-                |lhs, rhs| lhs > rhs,
-                |lhs, rhs| lhs.is_superset(&rhs) && lhs != rhs,
-            )?;
+        BinaryOperator::Greater => {
+            // a > b: Greater for rationals and strings, proper superset for sets
+            let result = match (lhs, rhs) {
+                (Value::String(lhs), Value::String(rhs)) => lhs > rhs,
+                (lhs, rhs) => calculate_rational_or_set_comparison(
+                    lhs,
+                    rhs,
+                    span,
+                    ">",
+                    |lhs, rhs| lhs > rhs,
+                    SetComparison::ProperSuperset,
+                )?,
+            };
             Ok(Value::Boolean(result))
         }
-        ExpressionType::LogicalOr(lhs, rhs) => {
+        BinaryOperator::LogicalOr => {
             // a || b for booleans only
             // Don't short circuit (the specification doesn't specify this)
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
             match (lhs, rhs) {
                 (Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs || rhs)),
                 (lhs, rhs) => Err(span_error!(
@@ -212,11 +277,9 @@ pub(crate) fn evaluate_expression(
                 )),
             }
         }
-        ExpressionType::LogicalAnd(lhs, rhs) => {
+        BinaryOperator::LogicalAnd => {
             // a && b for booleans only
             // Don't short circuit (the specification doesn't specify this)
-            let lhs = evaluate_expression(cx, *lhs)?;
-            let rhs = evaluate_expression(cx, *rhs)?;
             match (lhs, rhs) {
                 (Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs && rhs)),
                 (lhs, rhs) => Err(span_error!(
@@ -264,9 +327,17 @@ fn evaluate_atom(
                 }
                 _ => {
                     // Try constants
-                    match cx.constants().get(identifier) {
+                    match cx.use_constant(identifier) {
                         Some(constant) => Ok(constant.dsdl_value().clone()),
-                        None => Err(span_error!(span, "Identifier {} not found", identifier)),
+                        None => {
+                            let names = cx.constant_names().chain(iter::once("_offset_"));
+                            Err(span_error!(
+                                span,
+                                "Identifier {} not found{}",
+                                identifier,
+                                DidYouMean(closest_match(identifier, names))
+                            ))
+                        }
                     }
                 }
             }
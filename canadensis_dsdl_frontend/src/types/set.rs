@@ -21,6 +21,11 @@ impl Set {
         self.0.len()
     }
 
+    /// Returns the values in this set
+    pub fn values(&self) -> &BTreeSet<Value> {
+        &self.0
+    }
+
     /// Returns a borrowed iterator over values in this set
     pub fn iter(&self) -> Iter<'_> {
         Iter(self.0.iter())
@@ -46,10 +51,8 @@ impl Set {
                 Some(Value::Set(self.sets_sorted().remove(0)))
             }
             Some(ExprType::String) => {
-                // This is defined only if all the strings can be implicitly converted to integers
-                let sorted_values = self.string_as_ints_sorted()?;
-                let min = sorted_values[0];
-                Some(Value::String(single_character_string(min).into()))
+                // Strings are ordered by their code points, so they're also already sorted
+                self.0.iter().next().cloned()
             }
             _ => {
                 // Not supported
@@ -73,10 +76,8 @@ impl Set {
                 Some(Value::Set(self.sets_sorted().pop().unwrap()))
             }
             Some(ExprType::String) => {
-                // This is defined only if all the strings can be implicitly converted to integers
-                let sorted_values = self.string_as_ints_sorted()?;
-                let max = *sorted_values.last().unwrap();
-                Some(Value::String(single_character_string(max).into()))
+                // Strings are ordered by their code points, so they're also already sorted
+                self.0.iter().next_back().cloned()
             }
             _ => {
                 // Not supported
@@ -113,23 +114,6 @@ impl Set {
         inner_sets
     }
 
-    /// If every value in this set is a string with an implicit integer value, this function returns
-    /// a sorted vector of those values.
-    fn string_as_ints_sorted(&self) -> Option<Vec<u8>> {
-        let mut as_ints = Vec::with_capacity(self.len());
-        for value in &self.0 {
-            match value {
-                Value::String(s) => match s.implicit_int() {
-                    Some(int_value) => as_ints.push(int_value),
-                    None => return None,
-                },
-                _ => return None,
-            }
-        }
-        as_ints.sort_unstable();
-        Some(as_ints)
-    }
-
     /// Attempts to insert a value into this set
     ///
     /// If this set is empty, or if this set is not empty and has a type that matches the type
@@ -208,10 +192,6 @@ impl Set {
     pub fn is_subset(&self, other: &Set) -> bool {
         self.0.is_subset(&other.0)
     }
-    /// Returns true if this is a superset of another set
-    pub fn is_superset(&self, other: &Set) -> bool {
-        self.0.is_superset(&other.0)
-    }
 
     /// Calculates and returns the union of this set and another set
     pub fn union(&self, other: &Set) -> Result<Set, TypeError> {
@@ -326,12 +306,6 @@ impl Default for Set {
     }
 }
 
-fn single_character_string(code: u8) -> String {
-    let mut string = String::with_capacity(1);
-    string.push(code as char);
-    string
-}
-
 /// An error resulting from an operation with two sets of incompatible element types
 #[derive(Debug, PartialEq)]
 pub(crate) struct TypeError {
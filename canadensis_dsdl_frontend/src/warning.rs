@@ -1,16 +1,49 @@
 use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
 
+use canadensis_dsdl_parser::{
+    ArrayLength, CastMode, ExpressionAtom, ExpressionType, LiteralType, Type,
+};
 use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::compiled::{CompiledDsdl, DsdlKind, Message, MessageKind};
+use crate::compiled::{CompiledDsdl, DsdlKind, Extent, FieldKind, Message, MessageKind};
+use crate::types::{ResolvedScalarType, ResolvedType};
 use crate::TypeKey;
 
+/// If the extent of a type is more than this many times its maximum size, the extent is
+/// considered too large
+const LARGE_EXTENT_RATIO: u64 = 8;
+/// If the extent of a type is more than this many bytes larger than its maximum size, the extent
+/// may be considered too large
+const LARGE_EXTENT_MARGIN_BYTES: u64 = 64;
+
+/// The fixed subject IDs that standard types (in the `uavcan` root namespace) can use
+const STANDARD_SUBJECT_IDS: RangeInclusive<u32> = 7168..=8191;
+/// The fixed subject IDs that non-standard types can use
+const VENDOR_SUBJECT_IDS: RangeInclusive<u32> = 6144..=7167;
+/// The fixed service IDs that standard types (in the `uavcan` root namespace) can use
+const STANDARD_SERVICE_IDS: RangeInclusive<u32> = 384..=511;
+/// The fixed service IDs that non-standard types can use
+const VENDOR_SERVICE_IDS: RangeInclusive<u32> = 256..=383;
+
 /// A non-fatal warning encountered while processing DSDL
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Warning(WarningKind);
 
+impl Warning {
+    /// Returns the rule that produced this warning
+    pub fn rule(&self) -> WarningRule {
+        self.0.rule()
+    }
+
+    /// Returns the type that this warning is about, or None if it is about a package
+    pub fn key(&self) -> Option<&TypeKey> {
+        self.0.key()
+    }
+}
+
 impl std::fmt::Display for Warning {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -18,10 +51,99 @@ impl std::fmt::Display for Warning {
     }
 }
 
+/// A category of warnings that can be enabled or disabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum WarningRule {
+    /// A package, type, field, variant, or constant name does not follow the naming conventions
+    Naming,
+    /// A constant is not used in any expression in the same file
+    ///
+    /// Constants are often part of the interface of a type, so this rule is disabled by default.
+    UnusedConstant,
+    /// A field or variant has the `truncated` cast mode, so out-of-range values are silently
+    /// wrapped instead of saturated
+    ///
+    /// Some standard types use `truncated` intentionally, so this rule is disabled by default.
+    TruncatedCast,
+    /// A field or variant has a deprecated type
+    DeprecatedType,
+    /// The extent of a type is much larger than its maximum serialized size
+    LargeExtent,
+    /// A field with a size that is a multiple of 8 bits follows padding, but does not always start
+    /// at a multiple of 8 bits
+    ///
+    /// This usually means that the padding has the wrong length.
+    MisalignedField,
+    /// The length of an array is a number instead of a named constant
+    ///
+    /// Many standard types have array lengths that are numbers, so this rule is disabled by
+    /// default.
+    MagicNumber,
+    /// A fixed port ID is not in the range of regulated IDs for standard or non-standard types
+    PortIdRange,
+}
+
+impl WarningRule {
+    /// All warning rules
+    pub const ALL: [WarningRule; 8] = [
+        WarningRule::Naming,
+        WarningRule::UnusedConstant,
+        WarningRule::TruncatedCast,
+        WarningRule::DeprecatedType,
+        WarningRule::LargeExtent,
+        WarningRule::MisalignedField,
+        WarningRule::MagicNumber,
+        WarningRule::PortIdRange,
+    ];
+
+    /// Returns the name of this rule, in kebab-case
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarningRule::Naming => "naming",
+            WarningRule::UnusedConstant => "unused-constant",
+            WarningRule::TruncatedCast => "truncated-cast",
+            WarningRule::DeprecatedType => "deprecated-type",
+            WarningRule::LargeExtent => "large-extent",
+            WarningRule::MisalignedField => "misaligned-field",
+            WarningRule::MagicNumber => "magic-number",
+            WarningRule::PortIdRange => "port-id-range",
+        }
+    }
+
+    /// Finds a rule by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        WarningRule::ALL
+            .iter()
+            .copied()
+            .find(|rule| rule.name() == name)
+    }
+
+    /// Returns true if this rule is enabled by default
+    pub fn enabled_by_default(&self) -> bool {
+        !matches!(
+            self,
+            WarningRule::UnusedConstant | WarningRule::TruncatedCast | WarningRule::MagicNumber
+        )
+    }
+
+    /// Returns the rules that are enabled by default
+    pub(crate) fn defaults() -> BTreeSet<WarningRule> {
+        WarningRule::ALL
+            .iter()
+            .copied()
+            .filter(WarningRule::enabled_by_default)
+            .collect()
+    }
+}
+
+impl std::fmt::Display for WarningRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// Detailed warning variants
-// False positive warning: All of these end with `Case`, but different non-case-related warnings
-// may be added in the future.
-#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum WarningKind {
     /// Part of a package name is not in all-lowercase with _ separators
@@ -56,6 +178,97 @@ enum WarningKind {
         /// Suggested alternative name
         suggestion: String,
     },
+    /// A constant is not used in any expression in the same file
+    UnusedConstant {
+        /// Type that contains the constant
+        key: TypeKey,
+        /// Constant name
+        name: String,
+    },
+    /// A field or variant has the `truncated` cast mode
+    TruncatedCast {
+        /// Type that contains the field or variant
+        key: TypeKey,
+        /// Field or variant name
+        name: String,
+    },
+    /// A field or variant has a deprecated type
+    DeprecatedType {
+        /// Type that contains the field or variant
+        key: TypeKey,
+        /// Field or variant name
+        name: String,
+        /// The deprecated type
+        deprecated: TypeKey,
+    },
+    /// The extent of a type is much larger than its maximum serialized size
+    LargeExtent {
+        /// Type with the large extent
+        key: TypeKey,
+        /// The extent in bytes
+        extent_bytes: u64,
+        /// The maximum serialized size in bytes
+        max_size_bytes: u64,
+    },
+    /// A field that follows padding is not always aligned
+    MisalignedField {
+        /// Type that contains the field
+        key: TypeKey,
+        /// Field name
+        name: String,
+    },
+    /// The length of an array field is a number
+    MagicNumber {
+        /// Type that contains the field
+        key: TypeKey,
+        /// Field name
+        name: String,
+    },
+    /// A fixed port ID is not in the regulated range
+    PortIdRange {
+        /// Type with the fixed port ID
+        key: TypeKey,
+        /// The fixed port ID
+        port_id: u32,
+        /// True if the type is a service type
+        service: bool,
+        /// True if the type is in the standard root namespace
+        standard: bool,
+    },
+}
+
+impl WarningKind {
+    fn rule(&self) -> WarningRule {
+        match self {
+            WarningKind::PackageCase { .. }
+            | WarningKind::FieldCase { .. }
+            | WarningKind::ConstantCase { .. }
+            | WarningKind::TypeNameCase { .. } => WarningRule::Naming,
+            WarningKind::UnusedConstant { .. } => WarningRule::UnusedConstant,
+            WarningKind::TruncatedCast { .. } => WarningRule::TruncatedCast,
+            WarningKind::DeprecatedType { .. } => WarningRule::DeprecatedType,
+            WarningKind::LargeExtent { .. } => WarningRule::LargeExtent,
+            WarningKind::MisalignedField { .. } => WarningRule::MisalignedField,
+            WarningKind::MagicNumber { .. } => WarningRule::MagicNumber,
+            WarningKind::PortIdRange { .. } => WarningRule::PortIdRange,
+        }
+    }
+
+    fn key(&self) -> Option<&TypeKey> {
+        match self {
+            WarningKind::PackageCase { .. } => None,
+            WarningKind::FieldCase { key, .. }
+            | WarningKind::ConstantCase { key, .. }
+            | WarningKind::TypeNameCase { key, .. }
+            | WarningKind::UnusedConstant { key, .. }
+            | WarningKind::TruncatedCast { key, .. }
+            | WarningKind::DeprecatedType { key, .. }
+            | WarningKind::LargeExtent { key, .. }
+            | WarningKind::MisalignedField { key, .. }
+            | WarningKind::MagicNumber { key, .. }
+            | WarningKind::PortIdRange { key, .. } => Some(key),
+        }
+    }
 }
 
 impl std::fmt::Display for WarningKind {
@@ -99,6 +312,60 @@ impl std::fmt::Display for WarningKind {
                     ty, alternative
                 )
             }
+            WarningKind::UnusedConstant { key, name } => {
+                write!(f, "In type {}, the constant \"{}\" is never used", key, name)
+            }
+            WarningKind::TruncatedCast { key, name } => write!(
+                f,
+                "In type {}, the field or variant \"{}\" is truncated, so values that are out of range will wrap around",
+                key, name
+            ),
+            WarningKind::DeprecatedType {
+                key,
+                name,
+                deprecated,
+            } => write!(
+                f,
+                "In type {}, the field or variant \"{}\" has the deprecated type {}",
+                key, name, deprecated
+            ),
+            WarningKind::LargeExtent {
+                key,
+                extent_bytes,
+                max_size_bytes,
+            } => write!(
+                f,
+                "The type {} has an extent of {} bytes, but its maximum size is only {} bytes",
+                key, extent_bytes, max_size_bytes
+            ),
+            WarningKind::MisalignedField { key, name } => write!(
+                f,
+                "In type {}, the field \"{}\" follows padding but is not always aligned to a multiple of 8 bits",
+                key, name
+            ),
+            WarningKind::MagicNumber { key, name } => write!(
+                f,
+                "In type {}, the length of the array field \"{}\" is a number instead of a named constant",
+                key, name
+            ),
+            WarningKind::PortIdRange {
+                key,
+                port_id,
+                service,
+                standard,
+            } => {
+                let range = regulated_port_ids(*service, *standard);
+                write!(
+                    f,
+                    "The type {} has fixed port ID {}, but fixed {} IDs for {} types should be in the range {}..={}",
+                    key,
+                    port_id,
+                    if *service { "service" } else { "subject" },
+                    if *standard { "standard" } else { "non-standard" },
+                    range.start(),
+                    range.end()
+                )
+            }
         }
     }
 }
@@ -107,13 +374,16 @@ impl std::fmt::Display for WarningKind {
 #[derive(Debug, Clone)]
 pub struct Warnings {
     warnings: BTreeSet<Warning>,
+    /// The rules that can add warnings to this collection
+    enabled: BTreeSet<WarningRule>,
 }
 
 impl Warnings {
-    /// Creates an empty collection of warnings
-    pub(crate) fn new() -> Self {
+    /// Creates an empty collection of warnings that will contain warnings from the enabled rules
+    pub(crate) fn new(enabled: BTreeSet<WarningRule>) -> Self {
         Warnings {
             warnings: BTreeSet::new(),
+            enabled,
         }
     }
 
@@ -122,10 +392,17 @@ impl Warnings {
         self.warnings.is_empty()
     }
 
+    /// Returns the number of warnings in this collection
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
     /// Inserts a warning, or does nothing if this set of warnings already contains the provided
-    /// warning
+    /// warning or the rule that produced the warning is disabled
     fn insert(&mut self, kind: WarningKind) {
-        self.warnings.insert(Warning(kind));
+        if self.enabled.contains(&kind.rule()) {
+            self.warnings.insert(Warning(kind));
+        }
     }
 
     /// Checks for warnings on a type before it is compiled and adds any warnings to this collection
@@ -151,8 +428,42 @@ impl Warnings {
         }
     }
 
+    /// Checks for warnings on a field before its type is resolved and adds any warnings to this
+    /// collection
+    pub(crate) fn check_field_statement(&mut self, key: &TypeKey, name: &str, ty: &Type<'_>) {
+        if let Type::Array(array) = ty {
+            let length = match &array.length {
+                ArrayLength::Fixed(length)
+                | ArrayLength::Inclusive(length)
+                | ArrayLength::Exclusive(length) => length,
+            };
+            if let ExpressionType::Atom(atom) = &length.expression {
+                if let ExpressionAtom::Literal(literal) = &**atom {
+                    if let LiteralType::Number(_) = literal.literal {
+                        self.insert(WarningKind::MagicNumber {
+                            key: key.to_owned(),
+                            name: name.to_owned(),
+                        })
+                    }
+                }
+            }
+        }
+    }
+
     /// Checks for warnings on a compiled type and adds any warnings to this collection
     pub(crate) fn check_post_compile(&mut self, key: &TypeKey, dsdl: &CompiledDsdl) {
+        if let Some(port_id) = dsdl.fixed_port_id {
+            let service = matches!(dsdl.kind, DsdlKind::Service { .. });
+            let standard = key.name().path().first().map(String::as_str) == Some("uavcan");
+            if !regulated_port_ids(service, standard).contains(&port_id) {
+                self.insert(WarningKind::PortIdRange {
+                    key: key.to_owned(),
+                    port_id,
+                    service,
+                    standard,
+                })
+            }
+        }
         match &dsdl.kind {
             DsdlKind::Message(message) => self.check_message(key, message),
             DsdlKind::Service { request, response } => {
@@ -163,23 +474,36 @@ impl Warnings {
     }
 
     fn check_message(&mut self, key: &TypeKey, message: &Message) {
-        // Fields should be snake_case
         match &message.kind {
             MessageKind::Struct(struct_data) => {
+                let mut after_padding = false;
                 for field in &struct_data.fields {
-                    if let Some(name) = field.name() {
-                        self.check_field_name(key, name);
+                    match field.kind() {
+                        FieldKind::Data { ty, name } => {
+                            if after_padding
+                                && !field.always_aligned()
+                                && ty.size().is_byte_aligned()
+                            {
+                                self.insert(WarningKind::MisalignedField {
+                                    key: key.to_owned(),
+                                    name: name.to_owned(),
+                                })
+                            }
+                            self.check_field(key, name, ty);
+                            after_padding = false;
+                        }
+                        FieldKind::Padding(_) => after_padding = true,
                     }
                 }
             }
             MessageKind::Union(union_data) => {
                 for variant in &union_data.variants {
-                    self.check_field_name(key, variant.name());
+                    self.check_field(key, variant.name(), variant.ty());
                 }
             }
         }
-        // Constants should be SCREAMING_SNAKE_CASE
-        for (name, _) in &message.constants {
+        for (name, constant) in &message.constants {
+            // Constants should be SCREAMING_SNAKE_CASE
             let suggestion = name.to_shouty_snake_case();
             if name != &suggestion {
                 self.insert(WarningKind::ConstantCase {
@@ -188,6 +512,53 @@ impl Warnings {
                     suggestion,
                 })
             }
+            if !constant.is_used() {
+                self.insert(WarningKind::UnusedConstant {
+                    key: key.to_owned(),
+                    name: name.to_owned(),
+                })
+            }
+        }
+        if let Extent::Delimited(extent_bits) = message.extent {
+            let extent_bytes = extent_bits / 8;
+            let max_size_bytes = message.bit_length.max_value().div_ceil(8);
+            if extent_bytes > max_size_bytes.saturating_mul(LARGE_EXTENT_RATIO)
+                && extent_bytes - max_size_bytes > LARGE_EXTENT_MARGIN_BYTES
+            {
+                self.insert(WarningKind::LargeExtent {
+                    key: key.to_owned(),
+                    extent_bytes,
+                    max_size_bytes,
+                })
+            }
+        }
+    }
+
+    fn check_field(&mut self, key: &TypeKey, name: &str, ty: &ResolvedType) {
+        // Fields and variants should be snake_case
+        self.check_field_name(key, name);
+        match ty.scalar() {
+            ResolvedScalarType::Primitive(primitive) => {
+                if matches!(primitive.cast_mode(), CastMode::Truncated) {
+                    self.insert(WarningKind::TruncatedCast {
+                        key: key.to_owned(),
+                        name: name.to_owned(),
+                    })
+                }
+            }
+            ResolvedScalarType::Composite {
+                key: inner_key,
+                inner,
+            } => {
+                if inner.deprecated {
+                    self.insert(WarningKind::DeprecatedType {
+                        key: key.to_owned(),
+                        name: name.to_owned(),
+                        deprecated: inner_key.to_owned(),
+                    })
+                }
+            }
+            ResolvedScalarType::Void { .. } => {}
         }
     }
 
@@ -210,6 +581,16 @@ impl Warnings {
     }
 }
 
+/// Returns the range of regulated fixed port IDs for standard or non-standard subjects or services
+fn regulated_port_ids(service: bool, standard: bool) -> RangeInclusive<u32> {
+    match (service, standard) {
+        (false, true) => STANDARD_SUBJECT_IDS,
+        (false, false) => VENDOR_SUBJECT_IDS,
+        (true, true) => STANDARD_SERVICE_IDS,
+        (true, false) => VENDOR_SERVICE_IDS,
+    }
+}
+
 fn is_permissive_snake_case(s: &str) -> bool {
     // Should contain _, a-z, or 0-9
     s.chars()
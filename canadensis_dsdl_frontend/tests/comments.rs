@@ -0,0 +1,68 @@
+extern crate canadensis_dsdl_frontend;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Message, MessageKind};
+use canadensis_dsdl_frontend::{Config, Package};
+
+fn compile(key: &str, dsdl: &str) -> CompiledPackage {
+    let mut package = Package::new();
+    package
+        .add_string(None, key.parse().unwrap(), dsdl.to_owned())
+        .unwrap();
+    package.compile(&Config::default()).unwrap()
+}
+
+fn message<'p>(package: &'p CompiledPackage, key: &str) -> &'p Message {
+    match &package.get_by_key(&key.parse().unwrap()).unwrap().kind {
+        DsdlKind::Message(message) => message,
+        DsdlKind::Service { .. } => panic!("Expected a message"),
+    }
+}
+
+#[test]
+fn struct_comments() {
+    let package = compile(
+        "test.Commented.1.0",
+        "# Type comment\n# continued\nuint8 LIMIT = 3\n# Limit comment\nuint8 value\n# Value comment\nuint16 other\n@sealed\n",
+    );
+    let message = message(&package, "test.Commented.1.0");
+    assert_eq!(" Type comment\n continued", message.comments());
+    assert_eq!(
+        " Limit comment",
+        message.constants().get("LIMIT").unwrap().comments()
+    );
+    match message.kind() {
+        MessageKind::Struct(dsdl_struct) => {
+            let comments: Vec<(&str, &str)> = dsdl_struct
+                .fields
+                .iter()
+                .map(|field| (field.name().unwrap(), field.comments()))
+                .collect();
+            assert_eq!(vec![("value", " Value comment"), ("other", "")], comments);
+        }
+        MessageKind::Union(_) => panic!("Expected a struct"),
+    }
+}
+
+#[test]
+fn union_comments_after_offset() {
+    let package = compile(
+        "test.CommentedUnion.1.0",
+        "@union\nuint8 small\n# Small comment\nuint16 large\n@assert _offset_ == {16, 24}\n# Large comment\n@sealed\n",
+    );
+    let message = message(&package, "test.CommentedUnion.1.0");
+    match message.kind() {
+        MessageKind::Union(union) => {
+            let comments: Vec<(&str, &str)> = union
+                .variants
+                .iter()
+                .map(|variant| (variant.name(), variant.comments()))
+                .collect();
+            assert_eq!(
+                vec![("small", " Small comment"), ("large", " Large comment")],
+                comments
+            );
+        }
+        MessageKind::Struct(_) => panic!("Expected a union"),
+    }
+}
@@ -0,0 +1,120 @@
+extern crate canadensis_dsdl_frontend;
+
+use canadensis_dsdl_frontend::compatibility::{check_package, check_snapshots};
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::{Config, Package};
+
+fn compile(types: &[(Option<u32>, &str, &str)]) -> CompiledPackage {
+    let mut package = Package::new();
+    for &(port_id, key, dsdl) in types {
+        package
+            .add_string(port_id, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    package.compile(&Config::default()).unwrap()
+}
+
+fn problems(types: &[(Option<u32>, &str, &str)]) -> Vec<String> {
+    check_package(&compile(types))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+}
+
+#[test]
+fn compatible_versions() {
+    assert!(problems(&[
+        (Some(100), "test.A.1.0", "uint8 a\n@extent 32\n"),
+        (
+            Some(100),
+            "test.A.1.1",
+            "uint8 renamed\nuint8 b\n@extent 32\n"
+        ),
+        (None, "test.A.2.0", "uint64 a\n@sealed\n"),
+        (None, "test.B.0.1", "uint8 a\n@sealed\n"),
+        (None, "test.B.0.2", "uint16 a\nuint8 b\n@sealed\n"),
+    ])
+    .is_empty());
+}
+
+#[test]
+fn field_added_to_sealed_type() {
+    assert_eq!(
+        problems(&[
+            (None, "test.A.1.0", "uint8 a\n@sealed\n"),
+            (None, "test.A.1.1", "uint8 a\nuint8 b\n@sealed\n"),
+        ]),
+        ["test.A.1.1 is not compatible with test.A.1.0: A field was added to a sealed type"]
+    );
+}
+
+#[test]
+fn extent_changed() {
+    assert_eq!(
+        problems(&[
+            (None, "test.A.1.0", "uint8 a\n@extent 32\n"),
+            (None, "test.A.1.1", "uint8 a\n@extent 64\n"),
+        ]),
+        ["test.A.1.1 is not compatible with test.A.1.0: The extent changed from 32 to 64 bits"]
+    );
+}
+
+#[test]
+fn field_type_changed() {
+    assert_eq!(
+        problems(&[
+            (None, "test.A.1.0", "uint8 a\nuint8 b\n@extent 32\n"),
+            (None, "test.A.1.1", "uint8 a\nint8 b\n@extent 32\n"),
+            (None, "test.A.1.2", "uint8 a\n@extent 32\n"),
+        ]),
+        [
+            "test.A.1.1 is not compatible with test.A.1.0: The type of field 1 (b) changed from saturated uint8 to saturated int8",
+            "test.A.1.2 is not compatible with test.A.1.1: The field 1 (b) was removed",
+        ]
+    );
+}
+
+#[test]
+fn port_id_changed() {
+    assert_eq!(
+        problems(&[
+            (Some(100), "test.A.1.0", "@sealed\n"),
+            (None, "test.A.1.1", "@sealed\n"),
+        ]),
+        ["test.A.1.1 is not compatible with test.A.1.0: The fixed port ID changed from 100 to none"]
+    );
+}
+
+#[test]
+fn service_response_changed() {
+    assert_eq!(
+        problems(&[
+            (None, "test.S.1.0", "@sealed\n---\nuint8 a\n@sealed\n"),
+            (None, "test.S.1.1", "@sealed\n---\nuint8 a\n@extent 32\n"),
+        ]),
+        ["test.S.1.1 is not compatible with test.S.1.0: Response: A sealed type became delimited"]
+    );
+}
+
+#[test]
+fn snapshot_layout_changed() {
+    let old = compile(&[(None, "test.A.1.0", "uint8 a\n@sealed\n")]);
+    let new = compile(&[
+        (None, "test.A.1.0", "uint16 a\n@sealed\n"),
+        (None, "test.A.1.1", "uint8 a\n@sealed\n"),
+    ]);
+    let problems = check_snapshots(&old, &new)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        problems,
+        [
+            "test.A.1.0 changed without a new version: The layout is different",
+            "test.A.1.1 is not compatible with test.A.1.0: The type of field 0 (a) changed from saturated uint16 to saturated uint8",
+        ]
+    );
+    // A version that was removed from the new snapshot is still used as the previous version
+    let new = compile(&[(None, "test.A.1.1", "uint8 a\nuint8 b\n@sealed\n")]);
+    assert_eq!(check_snapshots(&old, &new).len(), 1);
+}
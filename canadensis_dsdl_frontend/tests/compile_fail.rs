@@ -97,6 +97,66 @@ fn compile_fail_long_name() -> io::Result<()> {
     }
 }
 
+/// Checks that an error in a file shows the path to the file, the source code, and where a
+/// conflicting definition is
+#[test]
+fn compile_fail_diagnostics() {
+    let case_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compile_fail/duplicate_fields");
+    let error = try_compile_package(&case_path).expect_err("Compiled successfully");
+    let error_text = format!("{}", PrintCause(&*error));
+
+    let file_path = case_path.join("canadensis/DuplicateFields.1.0.uavcan");
+    assert!(
+        error_text.contains(&format!("--> {}:4:1", file_path.display())),
+        "{}",
+        error_text
+    );
+    assert!(error_text.contains("4 | uint8 a"), "{}", error_text);
+    assert!(
+        error_text.contains("= note: The first definition is on line 3"),
+        "{}",
+        error_text
+    );
+}
+
+/// Checks that errors in several lines and several files are all reported, and types that
+/// depend on a type with errors do not cause more errors
+#[test]
+fn compile_fail_multiple_errors() {
+    let mut package = Package::new();
+    for (key, dsdl) in [
+        ("canadensis.A.1.0", "uint8 = x\nuint99 y\n@sealed\n"),
+        ("canadensis.B.1.0", "canadensis.A.1.0 a\n@sealed\n"),
+        ("canadensis.C.1.0", "canadensis.A.1.0 a\n@sealed\n"),
+        ("canadensis.D.1.0", "uint8 a\n"),
+    ] {
+        package
+            .add_string(None, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    let error = package
+        .compile(&Default::default())
+        .expect_err("Compiled successfully");
+    let errors = match &*error {
+        Error::Multiple(errors) => errors,
+        other => panic!("Expected multiple errors, got {}", PrintCause(other)),
+    };
+    let error_texts: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{}", PrintCause(e)))
+        .collect();
+    assert_eq!(error_texts.len(), 3, "{:#?}", error_texts);
+    assert!(error_texts[0].contains(" --> 1:"), "{}", error_texts[0]);
+    assert!(error_texts[1].contains(" --> 2:"), "{}", error_texts[1]);
+    assert!(
+        error_texts[2].contains("canadensis.D.1.0"),
+        "{}",
+        error_texts[2]
+    );
+    assert!(!format!("{}", error).contains("could not be compiled"));
+}
+
 fn write_long_name_file(path: &Path) -> io::Result<()> {
     fs::write(
         path,
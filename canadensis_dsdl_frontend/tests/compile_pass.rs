@@ -77,7 +77,7 @@ fn test_cycle() {
             match &*e {
                 Error::CompileFile { inner, .. } => match inner.deref() {
                     Error::CompileFile { inner, .. } => match inner.deref() {
-                        Error::UnknownType(_) => { /* OK */ }
+                        Error::UnknownType { .. } => { /* OK */ }
                         _ => panic!("Unexpected error {:#?}", e),
                     },
                     _ => panic!("Unexpected error {:#?}", e),
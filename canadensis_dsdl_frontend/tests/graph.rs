@@ -0,0 +1,106 @@
+extern crate canadensis_dsdl_frontend;
+extern crate serde_json;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::graph::DependencyGraph;
+use canadensis_dsdl_frontend::{Config, Package};
+use serde_json::{json, Value};
+
+fn compile() -> CompiledPackage {
+    let mut package = Package::new();
+    for (port_id, key, dsdl) in [
+        (None, "test.Point.1.0", "float32 x\nfloat32 y\n@sealed\n"),
+        (
+            None,
+            "test.Shape.1.0",
+            "@deprecated\ntest.Point.1.0[<=4] points\n@sealed\n",
+        ),
+        (
+            None,
+            "test.Shape.1.1",
+            "test.Point.1.0[<=8] points\n@sealed\n",
+        ),
+        (
+            Some(100),
+            "test.Draw.1.0",
+            "test.Shape.1.1 shape\n@sealed\n---\nbool ok\ntest.Point.1.0 center\n@sealed\n",
+        ),
+    ] {
+        package
+            .add_string(port_id, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    package.compile(&Config::default()).unwrap()
+}
+
+#[test]
+fn graph_json() {
+    let package = compile();
+    let graph = DependencyGraph::new(&package);
+    let value: Value = serde_json::from_str(&graph.json().to_string()).unwrap();
+    assert_eq!(
+        json!({
+            "types": [
+                {
+                    "key": "test.Draw.1.0",
+                    "name": "test.Draw",
+                    "version": "1.0",
+                    "kind": "service",
+                    "fixed_port_id": 100,
+                    "deprecated": false,
+                    "dependencies": ["test.Point.1.0", "test.Shape.1.1"],
+                    "next_version": null
+                },
+                {
+                    "key": "test.Point.1.0",
+                    "name": "test.Point",
+                    "version": "1.0",
+                    "kind": "message",
+                    "fixed_port_id": null,
+                    "deprecated": false,
+                    "dependencies": [],
+                    "next_version": null
+                },
+                {
+                    "key": "test.Shape.1.0",
+                    "name": "test.Shape",
+                    "version": "1.0",
+                    "kind": "message",
+                    "fixed_port_id": null,
+                    "deprecated": true,
+                    "dependencies": ["test.Point.1.0"],
+                    "next_version": "test.Shape.1.1"
+                },
+                {
+                    "key": "test.Shape.1.1",
+                    "name": "test.Shape",
+                    "version": "1.1",
+                    "kind": "message",
+                    "fixed_port_id": null,
+                    "deprecated": false,
+                    "dependencies": ["test.Point.1.0"],
+                    "next_version": null
+                }
+            ]
+        }),
+        value
+    );
+}
+
+#[test]
+fn graph_dot() {
+    let package = compile();
+    let dot = DependencyGraph::new(&package).dot().to_string();
+    assert!(dot.starts_with("digraph dsdl {\n"));
+    assert!(
+        dot.contains("    \"test.Draw.1.0\" [label=\"test.Draw.1.0\\nport 100\", shape=box];\n")
+    );
+    assert!(dot.contains(
+        "    \"test.Shape.1.0\" [label=\"test.Shape.1.0\", shape=ellipse, style=dashed];\n"
+    ));
+    assert!(dot.contains("    \"test.Draw.1.0\" -> \"test.Shape.1.1\";\n"));
+    assert!(dot.contains(
+        "    \"test.Shape.1.0\" -> \"test.Shape.1.1\" [style=dashed, label=\"next version\"];\n"
+    ));
+    assert!(dot.ends_with("}\n"));
+}
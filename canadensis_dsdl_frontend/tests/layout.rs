@@ -0,0 +1,53 @@
+extern crate canadensis_dsdl_frontend;
+
+use canadensis_dsdl_frontend::compiled::DsdlKind;
+use canadensis_dsdl_frontend::{Config, Package};
+
+fn layout_description(dsdl: &str) -> String {
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "test.Point.1.0".parse().unwrap(),
+            "float32 x\n@sealed\n".to_owned(),
+        )
+        .unwrap();
+    package
+        .add_string(None, "test.Layout.1.0".parse().unwrap(), dsdl.to_owned())
+        .unwrap();
+    let package = package.compile(&Config::default()).unwrap();
+    match &package
+        .get_by_key(&"test.Layout.1.0".parse().unwrap())
+        .unwrap()
+        .kind
+    {
+        DsdlKind::Message(message) => message.layout_description(),
+        DsdlKind::Service { .. } => panic!("Expected a message"),
+    }
+}
+
+#[test]
+fn struct_layout() {
+    assert_eq!(
+        "sealed; struct; saturated uint8; void3; truncated uint5; test.Point.1.0[<=4]",
+        layout_description(
+            "uint8 A = 1\n# Comment\nuint8 count\nvoid3\ntruncated uint5 offset\ntest.Point.1.0[<=4] points\n@sealed\n"
+        )
+    );
+}
+
+#[test]
+fn union_layout() {
+    assert_eq!(
+        "extent 64; union 8; bool; test.Point.1.0",
+        layout_description("@union\nbool flag\ntest.Point.1.0 point\n@extent 64\n")
+    );
+}
+
+#[test]
+fn names_do_not_change_layout() {
+    assert_eq!(
+        layout_description("uint16 first\nbool second\n@sealed\n"),
+        layout_description("uint16 renamed\nbool other\n@sealed\n")
+    );
+}
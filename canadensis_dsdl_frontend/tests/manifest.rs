@@ -0,0 +1,64 @@
+extern crate canadensis_dsdl_frontend;
+extern crate serde_json;
+
+use canadensis_dsdl_frontend::manifest::Manifest;
+use canadensis_dsdl_frontend::{Config, Package};
+use serde_json::{json, Value};
+
+#[test]
+fn manifest_json() {
+    let mut package = Package::new();
+    for (port_id, key, dsdl) in [
+        (
+            Some(7000),
+            "test.Status.1.0",
+            "uint8 mode\nuint8[<=3] data\n@extent 16 * 8\n",
+        ),
+        (None, "test.Old.0.1", "@deprecated\nuint16 value\n@sealed\n"),
+        (
+            Some(300),
+            "test.Service.1.0",
+            "@sealed\n---\nfloat32 value\n@sealed\n",
+        ),
+    ] {
+        package
+            .add_string(port_id, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    let package = package.compile(&Config::default()).unwrap();
+
+    let manifest: Value = serde_json::from_str(&Manifest::new(&package).to_string()).unwrap();
+    assert_eq!(
+        manifest,
+        json!({
+            "manifest_version": 1,
+            "types": [
+                {
+                    "name": "test.Old",
+                    "version": { "major": 0, "minor": 1 },
+                    "fixed_port_id": null,
+                    "kind": "message",
+                    "deprecated": true,
+                    "message": { "sealed": true, "extent_bytes": 2, "min_size_bytes": 2, "max_size_bytes": 2 }
+                },
+                {
+                    "name": "test.Service",
+                    "version": { "major": 1, "minor": 0 },
+                    "fixed_port_id": 300,
+                    "kind": "service",
+                    "deprecated": false,
+                    "request": { "sealed": true, "extent_bytes": 0, "min_size_bytes": 0, "max_size_bytes": 0 },
+                    "response": { "sealed": true, "extent_bytes": 4, "min_size_bytes": 4, "max_size_bytes": 4 }
+                },
+                {
+                    "name": "test.Status",
+                    "version": { "major": 1, "minor": 0 },
+                    "fixed_port_id": 7000,
+                    "kind": "message",
+                    "deprecated": false,
+                    "message": { "sealed": false, "extent_bytes": 16, "min_size_bytes": 2, "max_size_bytes": 5 }
+                }
+            ]
+        })
+    );
+}
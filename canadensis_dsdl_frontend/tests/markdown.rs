@@ -0,0 +1,88 @@
+extern crate canadensis_dsdl_frontend;
+
+use canadensis_dsdl_frontend::backend::Backend;
+use canadensis_dsdl_frontend::markdown::{MarkdownBackend, MarkdownDocument};
+use canadensis_dsdl_frontend::{Config, Package};
+
+#[test]
+fn markdown_document() {
+    let mut package = Package::new();
+    for (port_id, key, dsdl) in [
+        (
+            Some(7000),
+            "test.Status.1.0",
+            "# Status of a node\nuint16 MAX = 1000 # Maximum | value\nuint32 uptime # [second] Time since start\nvoid3\nsaturated uint5 mode\n# See [1, 2]\nuavcan.si.unit.length.Scalar.1.0 distance\n@extent 128\n",
+        ),
+        (None, "test.Status.0.1", "@deprecated\nuint32 uptime\n@sealed\n"),
+        (None, "uavcan.si.unit.length.Scalar.1.0", "float32 meter\n@sealed\n"),
+        (
+            Some(300),
+            "test.Service.1.0",
+            "@union\nuint8 small\nfloat32 speed # [m/s]\n@sealed\n---\nbool ok\n@sealed\n",
+        ),
+    ] {
+        package
+            .add_string(port_id, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    let package = package.compile(&Config::default()).unwrap();
+    let document = MarkdownDocument::new(&package)
+        .with_title("Test types")
+        .to_string();
+
+    assert!(document.starts_with(
+        "# Test types\n\n* [test.Service](#testservice)\n* [test.Status](#teststatus)\n"
+    ));
+    assert!(document.contains(
+        "## test.Status\n\nVersions: [1.0](#teststatus10), [0.1](#teststatus01) (deprecated)\n"
+    ));
+    // Newest version first
+    assert!(
+        document.find("### test.Status.1.0").unwrap()
+            < document.find("### test.Status.0.1").unwrap()
+    );
+    assert!(document.contains("### test.Status.0.1\n\n**Deprecated**\n"));
+    assert!(document.contains("```text\nStatus of a node\n```\n"));
+    assert!(document.contains(
+        "| Fixed subject ID | 7000 |\n| Extent | 128 bits (16 bytes) |\n| Size | 72 bits |\n"
+    ));
+    assert!(document.contains(
+        "| 0 | `uptime` | `saturated uint32` | second | \\[second\\] Time since start |\n\
+        | 32 |  | `void3` |  |  |\n\
+        | 35 | `mode` | `saturated uint5` |  | See \\[1, 2\\] |\n\
+        | 40 | `distance` | `uavcan.si.unit.length.Scalar.1.0` | meter |  |\n"
+    ));
+    assert!(document.contains("| `MAX` | `saturated uint16` | 1000 | Maximum \\| value |\n"));
+
+    assert!(document.contains("| Kind | Service |\n| Fixed service ID | 300 |\n"));
+    assert!(document.contains("#### Request\n"));
+    assert!(document.contains("##### Variants (8-bit tag)\n"));
+    assert!(document.contains("| 1 | `speed` | `saturated float32` | m/s | \\[m/s\\] |\n"));
+    assert!(document.contains("#### Response\n"));
+    assert!(document.contains("| 0 | `ok` | `bool` |  |  |\n"));
+}
+
+#[test]
+fn markdown_backend() {
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "test.Empty.1.0".parse().unwrap(),
+            "@sealed\n".to_owned(),
+        )
+        .unwrap();
+    let package = package.compile(&Config::default()).unwrap();
+
+    let mut output = Vec::new();
+    MarkdownBackend::new()
+        .with_title("Test types")
+        .generate(&package, &mut output)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        MarkdownDocument::new(&package)
+            .with_title("Test types")
+            .to_string()
+    );
+}
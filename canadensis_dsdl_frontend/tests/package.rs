@@ -1,6 +1,6 @@
 extern crate canadensis_dsdl_frontend;
 
-use canadensis_dsdl_frontend::{Error, Package};
+use canadensis_dsdl_frontend::{Config, Error, Package};
 use std::fs;
 use std::io;
 use std::path::PathBuf;
@@ -27,3 +27,165 @@ fn package_add_not_directory() -> io::Result<()> {
         Err(other) => panic!("Unexpected error {:?}", other),
     }
 }
+
+fn compile_with_port_ids(types: &[(u32, &str, &str)]) -> Result<(), Box<Error>> {
+    let mut package = Package::new();
+    for &(port_id, key, dsdl) in types {
+        package
+            .add_string(Some(port_id), key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    package.compile(&Config::default()).map(|_| ())
+}
+
+#[test]
+fn fixed_port_id_conflicts() {
+    let message = "@sealed\n";
+    let service = "@sealed\n---\n@sealed\n";
+    // Allowed: a message and a service, and minor versions of the same type
+    compile_with_port_ids(&[
+        (300, "vendor_a.Status.1.0", message),
+        (300, "vendor_a.Status.1.1", message),
+        (300, "vendor_b.Command.1.0", service),
+    ])
+    .unwrap();
+
+    let e = compile_with_port_ids(&[
+        (7000, "vendor_a.Status.1.0", message),
+        (7000, "vendor_b.Other.1.0", message),
+    ])
+    .unwrap_err();
+    match &*e {
+        Error::FixedPortIdConflict {
+            port_id: 7000,
+            service: false,
+            first,
+            second,
+            ..
+        } => {
+            assert_eq!("vendor_a.Status.1.0", first.to_string());
+            assert_eq!("vendor_b.Other.1.0", second.to_string());
+        }
+        other => panic!("Unexpected error {:?}", other),
+    }
+    assert_eq!(
+        "Types vendor_a.Status.1.0 and vendor_b.Other.1.0 have the same fixed subject ID 7000",
+        e.to_string()
+    );
+
+    // Different major versions of the same type
+    let e = compile_with_port_ids(&[
+        (400, "vendor_a.Command.1.0", service),
+        (400, "vendor_a.Command.2.0", service),
+    ])
+    .unwrap_err();
+    assert!(matches!(
+        *e,
+        Error::FixedPortIdConflict {
+            port_id: 400,
+            service: true,
+            ..
+        }
+    ));
+}
+
+/// Checks that types can be added from paths and contents in memory
+#[test]
+fn package_add_virtual_files() {
+    let mut package = Package::new();
+    package
+        .add_virtual_files(vec![
+            (
+                "example/7000.Status.1.0.dsdl",
+                "example.Mode.0.1 mode\n@sealed\n",
+            ),
+            ("example/Mode.0.1.dsdl", "uint8 value\n@sealed\n"),
+            ("example/README.md", "Not a DSDL file"),
+        ])
+        .unwrap();
+    // Virtual files are not on disk
+    assert_eq!(package.paths().count(), 0);
+    let compiled = package.compile(&Config::default()).unwrap();
+
+    let status = compiled
+        .get_by_key(&"example.Status.1.0".parse().unwrap())
+        .expect("No Status type");
+    assert_eq!(status.fixed_port_id, Some(7000));
+    assert!(compiled
+        .get_by_key(&"example.Mode.0.1".parse().unwrap())
+        .is_some());
+}
+
+/// Checks that errors in virtual files show their paths
+#[test]
+fn package_virtual_file_error_path() {
+    let mut package = Package::new();
+    package
+        .add_virtual_files([("example/Broken.1.0.dsdl", "uint8 = a\n@sealed\n")])
+        .unwrap();
+    let error = package.compile(&Config::default()).unwrap_err();
+    match &*error {
+        Error::CompileFile {
+            path: Some(path),
+            inner,
+            ..
+        } => {
+            assert_eq!(path, &PathBuf::from("example/Broken.1.0.dsdl"));
+            assert!(inner.to_string().contains("Invalid DSDL"));
+            let source = std::error::Error::source(&**inner).unwrap().to_string();
+            assert!(
+                source.contains("--> example/Broken.1.0.dsdl:1:7"),
+                "{}",
+                source
+            );
+        }
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
+
+/// Checks that a virtual file with an invalid name causes an error
+#[test]
+fn package_virtual_file_bad_name() {
+    let mut package = Package::new();
+    let status = package.add_virtual_files([("example/Status.dsdl", "@sealed\n")]);
+    match status {
+        Err(e) if matches!(*e, Error::FileName(_)) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+/// Checks that compiling reports an error for a file that can't be read, and still compiles the
+/// other files
+#[test]
+fn package_missing_file() {
+    let missing_path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("Missing.1.0.dsdl");
+    let mut package = Package::new();
+    package
+        .add_file(None, "test.Missing.1.0".parse().unwrap(), &missing_path)
+        .unwrap();
+    package
+        .add_string(
+            None,
+            "test.Uses.1.0".parse().unwrap(),
+            "test.Missing.1.0 missing\n@sealed\n".to_owned(),
+        )
+        .unwrap();
+    package
+        .add_string(
+            None,
+            "test.Valid.1.0".parse().unwrap(),
+            "uint8 a\n@sealed\n".to_owned(),
+        )
+        .unwrap();
+    let (e, _warnings) = package
+        .compile_with_warnings(&Config::default())
+        .unwrap_err();
+    // The error in test.Uses.1.0 is not reported, because it comes from test.Missing.1.0
+    match *e {
+        Error::CompileFile { key, inner, .. } => {
+            assert_eq!(key, "test.Missing.1.0".parse().unwrap());
+            assert!(matches!(*inner, Error::FileRead { path, .. } if path == missing_path));
+        }
+        other => panic!("Unexpected error {:?}", other),
+    }
+}
@@ -0,0 +1,38 @@
+extern crate canadensis_dsdl_frontend;
+
+use canadensis_dsdl_frontend::{Config, Package};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn print_handler() {
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "test.Printing.1.0".parse().unwrap(),
+            "uint8 A = 3\n@print A * 2\nuint16 value\n@print\n@print _offset_\n@sealed\n"
+                .to_owned(),
+        )
+        .unwrap();
+
+    let outputs = Rc::new(RefCell::new(Vec::new()));
+    let handler_outputs = Rc::clone(&outputs);
+    package.set_print_handler(move |output| {
+        assert_eq!("test.Printing.1.0", output.key().to_string());
+        assert!(output.path().is_none());
+        handler_outputs
+            .borrow_mut()
+            .push((output.line(), output.text().to_owned()));
+    });
+    package.compile(&Config::default()).unwrap();
+
+    assert_eq!(
+        vec![
+            (2, "6".to_owned()),
+            (4, String::new()),
+            (5, "{16}".to_owned())
+        ],
+        *outputs.borrow()
+    );
+}
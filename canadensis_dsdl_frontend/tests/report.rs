@@ -0,0 +1,105 @@
+extern crate canadensis_dsdl_frontend;
+extern crate serde_json;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::report::LayoutReport;
+use canadensis_dsdl_frontend::{Config, Package};
+use serde_json::{json, Value};
+
+fn compile() -> CompiledPackage {
+    let mut package = Package::new();
+    for (port_id, key, dsdl) in [
+        (
+            None,
+            "test.Fields.1.0",
+            "bool flag\nvoid3\nuint8[<=2] bytes\nbool last\ntest.Choice.1.0 choice\n@extent 32 * 8\n",
+        ),
+        (
+            None,
+            "test.Choice.1.0",
+            "@union\nuint8 small\nuint16 large\n@sealed\n",
+        ),
+        (
+            Some(300),
+            "test.Service.1.0",
+            "@sealed\n---\nfloat32 value\n@sealed\n",
+        ),
+    ] {
+        package
+            .add_string(port_id, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    package.compile(&Config::default()).unwrap()
+}
+
+#[test]
+fn report_text() {
+    let package = compile();
+    let report = LayoutReport::new(&package);
+    assert_eq!(
+        "test.Choice.1.0
+  message: sealed, size 16..=24 bits, union with 8-bit discriminant
+    offset 8, size 8: saturated uint8 small
+    offset 8, size 16: saturated uint16 large
+
+test.Fields.1.0
+  message: extent 256 bits, size 32..=56 bits
+    offset 0, size 1: bool flag
+    offset 1, size 3: void3
+    offset 4, size 8..=24: saturated uint8[<=2] bytes
+    offset 12..=28, size 1: bool last
+    offset 16..=32, size 16..=24, up to 3 bits padding before: test.Choice.1.0 choice
+
+test.Service.1.0, fixed port ID 300
+  request: sealed, size 0 bits
+  response: sealed, size 32 bits
+    offset 0, size 32: saturated float32 value
+",
+        report.text().to_string()
+    );
+}
+
+#[test]
+fn report_json() {
+    let package = compile();
+    let report = LayoutReport::new(&package);
+    let value: Value = serde_json::from_str(&report.json().to_string()).unwrap();
+    assert_eq!(
+        json!({
+            "key": "test.Service.1.0",
+            "fixed_port_id": 300,
+            "kind": "service",
+            "request": {
+                "extent_bits": null,
+                "discriminant_bits": null,
+                "size_bits": { "min": 0, "max": 0 },
+                "fields": []
+            },
+            "response": {
+                "extent_bits": null,
+                "discriminant_bits": null,
+                "size_bits": { "min": 32, "max": 32 },
+                "fields": [
+                    {
+                        "name": "value",
+                        "type": "saturated float32",
+                        "offset_bits": { "min": 0, "max": 0 },
+                        "size_bits": { "min": 32, "max": 32 },
+                        "alignment_padding_bits": 0
+                    }
+                ]
+            }
+        }),
+        value["types"][2]
+    );
+    assert_eq!(
+        json!({
+            "name": null,
+            "type": "void3",
+            "offset_bits": { "min": 1, "max": 1 },
+            "size_bits": { "min": 3, "max": 3 },
+            "alignment_padding_bits": 0
+        }),
+        value["types"][1]["message"]["fields"][1]
+    );
+}
@@ -0,0 +1,150 @@
+extern crate canadensis_dsdl_frontend;
+
+use canadensis_dsdl_frontend::warning::WarningRule;
+use canadensis_dsdl_frontend::{Config, Package};
+
+/// Compiles some DSDL files and returns the rule and text of each warning
+fn compile_warnings(package: Package) -> Vec<(WarningRule, String)> {
+    let compiled = package
+        .compile(&Config::default())
+        .expect("Failed to compile");
+    compiled
+        .warnings()
+        .iter()
+        .map(|warning| (warning.rule(), warning.to_string()))
+        .collect()
+}
+
+fn add(package: &mut Package, key: &str, dsdl: &str) {
+    package
+        .add_string(None, key.parse().unwrap(), dsdl.to_owned())
+        .unwrap();
+}
+
+fn package_with_warnings() -> Package {
+    let mut package = Package::new();
+    add(
+        &mut package,
+        "test.Old.1.0",
+        "@deprecated\nuint8 value\n@sealed\n",
+    );
+    add(
+        &mut package,
+        "test.UsesOld.1.0",
+        "@deprecated\ntest.Old.1.0 old\n@sealed\n",
+    );
+    add(
+        &mut package,
+        "test.Wrapping.1.0",
+        "truncated uint8 wrapping\nsaturated uint8 saturating\n@sealed\n",
+    );
+    add(
+        &mut package,
+        "test.Constants.1.0",
+        "uint8 USED = 4\nuint8 UNUSED = 5\nuint8[USED] values\n@sealed\n",
+    );
+    add(
+        &mut package,
+        "test.Large.1.0",
+        "uint8 value\n@extent 128 * 8\n",
+    );
+    package
+}
+
+#[test]
+fn default_warnings() {
+    let warnings = compile_warnings(package_with_warnings());
+    assert_eq!(
+        vec![
+            (
+                WarningRule::DeprecatedType,
+                "In type test.UsesOld.1.0, the field or variant \"old\" has the deprecated type test.Old.1.0".to_owned()
+            ),
+            (
+                WarningRule::LargeExtent,
+                "The type test.Large.1.0 has an extent of 128 bytes, but its maximum size is only 1 bytes".to_owned()
+            ),
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn enable_and_disable_warnings() {
+    let mut package = package_with_warnings();
+    package.set_warning_enabled(WarningRule::UnusedConstant, true);
+    package.set_warning_enabled(WarningRule::TruncatedCast, true);
+    package.set_warning_enabled(WarningRule::DeprecatedType, false);
+    package.set_warning_enabled(WarningRule::LargeExtent, false);
+    let warnings = compile_warnings(package);
+    assert_eq!(
+        vec![
+            (
+                WarningRule::UnusedConstant,
+                "In type test.Constants.1.0, the constant \"UNUSED\" is never used".to_owned()
+            ),
+            (
+                WarningRule::TruncatedCast,
+                "In type test.Wrapping.1.0, the field or variant \"wrapping\" is truncated, so values that are out of range will wrap around".to_owned()
+            ),
+        ],
+        warnings
+    );
+}
+
+#[test]
+fn rule_names() {
+    for rule in WarningRule::ALL {
+        assert_eq!(Some(rule), WarningRule::from_name(rule.name()));
+    }
+    assert_eq!(None, WarningRule::from_name("not-a-rule"));
+}
+
+#[test]
+fn lint_warnings() {
+    let mut package = Package::new();
+    package.set_warning_enabled(WarningRule::MagicNumber, true);
+    add(
+        &mut package,
+        "test.Padded.1.0",
+        "bool flag\nvoid3\nuint8 misaligned\nbool other\nvoid4\nbool reserved_bits\nvoid6\nuint16 aligned\n@sealed\n",
+    );
+    add(
+        &mut package,
+        "test.Arrays.1.0",
+        "uint8 CAPACITY = 8\nuint8[<=16] magic\nuint8[CAPACITY] named\n@sealed\n",
+    );
+    for (port_id, key, dsdl) in [
+        (100, "test.Unregulated.1.0", "@sealed\n"),
+        (7000, "test.Vendor.1.0", "@sealed\n"),
+        (7001, "uavcan.NotStandard.1.0", "@sealed\n"),
+        (300, "test.VendorService.1.0", "@sealed\n---\n@sealed\n"),
+        (400, "uavcan.StandardService.1.0", "@sealed\n---\n@sealed\n"),
+    ] {
+        package
+            .add_string(Some(port_id), key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    let warnings = compile_warnings(package);
+    assert_eq!(
+        vec![
+            (
+                WarningRule::MisalignedField,
+                "In type test.Padded.1.0, the field \"misaligned\" follows padding but is not always aligned to a multiple of 8 bits".to_owned()
+            ),
+            (
+                WarningRule::MagicNumber,
+                "In type test.Arrays.1.0, the length of the array field \"magic\" is a number instead of a named constant".to_owned()
+            ),
+            (
+                WarningRule::PortIdRange,
+                "The type test.Unregulated.1.0 has fixed port ID 100, but fixed subject IDs for non-standard types should be in the range 6144..=7167".to_owned()
+            ),
+            (
+                WarningRule::PortIdRange,
+                "The type uavcan.NotStandard.1.0 has fixed port ID 7001, but fixed subject IDs for standard types should be in the range 7168..=8191".to_owned()
+            ),
+        ],
+        warnings
+    );
+}
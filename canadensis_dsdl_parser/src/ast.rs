@@ -20,37 +20,58 @@ pub(crate) fn parse_to_ast<'i>(
     statements: Pairs<'i, Rule>,
     config: &Config,
 ) -> Result<Definition<'i>, Error> {
+    let (definition, errors) =
+        parse_to_ast_with_recovery(statements, config, |span| make_error("Invalid line", span));
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(definition),
+    }
+}
+
+/// Converts a Pest parse tree into an abstract syntax tree, continuing after errors
+///
+/// A statement that can't be converted is left out of the returned definition, and its error
+/// is added to the returned errors. `syntax_error` is called to make an error for each
+/// `invalid_line` in the parse tree.
+pub(crate) fn parse_to_ast_with_recovery<'i, F>(
+    statements: Pairs<'i, Rule>,
+    config: &Config,
+    mut syntax_error: F,
+) -> (Definition<'i>, Vec<Error>)
+where
+    F: FnMut(Span<'i>) -> Error,
+{
     let mut ast_statements: Vec<Statement> = Vec::new();
+    let mut errors = Vec::new();
     let mut eof_span = None;
 
     for statement in statements {
-        match statement.as_rule() {
-            Rule::statement_directive => {
-                ast_statements.push(parse_directive(statement, config)?);
-            }
+        let ast_statement = match statement.as_rule() {
+            Rule::statement_directive => parse_directive(statement, config),
             Rule::statement_service_response_marker => {
-                ast_statements.push(Statement::ServiceResponseMarker(statement.as_span()));
-            }
-            Rule::statement_constant => {
-                ast_statements.push(parse_constant(statement, config)?);
-            }
-            Rule::statement_field => {
-                ast_statements.push(parse_field(statement, config)?);
-            }
-            Rule::statement_padding_field => {
-                ast_statements.push(parse_padding_field(statement)?);
+                Ok(Statement::ServiceResponseMarker(statement.as_span()))
             }
-            Rule::comment_content => ast_statements.push(Statement::Comment(statement.as_span())),
+            Rule::statement_constant => parse_constant(statement, config),
+            Rule::statement_field => parse_field(statement, config),
+            Rule::statement_padding_field => parse_padding_field(statement),
+            Rule::comment_content => Ok(Statement::Comment(statement.as_span())),
+            Rule::invalid_line => Err(syntax_error(statement.as_span())),
             Rule::EOI => {
                 eof_span = Some(statement.as_span());
+                continue;
             }
             other => unreachable!("Unexpected statement rule {:?}", other),
+        };
+        match ast_statement {
+            Ok(ast_statement) => ast_statements.push(ast_statement),
+            Err(e) => errors.push(e),
         }
     }
-    Ok(Definition {
+    let definition = Definition {
         statements: ast_statements,
         eof_span: eof_span.expect("Didn't get an EOI at the end"),
-    })
+    };
+    (definition, errors)
 }
 
 fn parse_directive<'i>(directive: Pair<'i, Rule>, config: &Config) -> Result<Statement<'i>, Error> {
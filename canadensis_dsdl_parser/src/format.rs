@@ -0,0 +1,216 @@
+//! Formatting of DSDL files in a consistent style
+
+use crate::parser::{DsdlParser, Rule};
+use crate::{ast, Config, Error};
+use pest::iterators::{Pair, Pairs};
+use pest::Parser;
+
+/// The minimum number of spaces between a statement and a comment on the same line
+const COMMENT_GAP: usize = 2;
+
+/// Formats the text of a DSDL file
+///
+/// The formatted text has the same statements and comments as the original text. The formatter
+/// makes these changes:
+/// * Whitespace in each statement is normalized. Binary operators and the `=` of each constant
+///   have one space on each side, and there are no spaces inside brackets and parentheses or
+///   before commas.
+/// * Comments that follow statements on consecutive lines are aligned to the same column, at
+///   least two spaces after the longest statement
+/// * Comments on their own lines are not indented, and trailing whitespace is removed from
+///   all comments
+/// * An `@extent` or `@sealed` directive that immediately follows an attribute or another
+///   directive is separated from it by an empty line
+/// * Multiple consecutive empty lines are replaced with one empty line, empty lines at the
+///   beginning and end are removed, and the text ends with one line feed
+/// * Line endings are converted to line feeds
+///
+/// Formatting text that has already been formatted does not change it.
+///
+/// # Errors
+///
+/// This function returns an error if the DSDL has invalid syntax.
+pub fn format(dsdl: &str, config: &Config) -> Result<String, Error> {
+    let parse_tree = DsdlParser::parse(Rule::definition, dsdl).map_err(|e| Error(Box::new(e)))?;
+    // Check everything that the parser checks, not only the grammar
+    ast::parse_to_ast(parse_tree.clone(), config)?;
+
+    let mut lines: Vec<Line> = std::iter::repeat_with(Line::default)
+        .take(dsdl.split('\n').count())
+        .collect();
+    for pair in parse_tree {
+        let line_index = pair.as_span().start_pos().line_col().0 - 1;
+        match pair.as_rule() {
+            Rule::comment_content => lines[line_index].comment = Some(pair.as_str().trim_end()),
+            Rule::EOI => {}
+            _ => lines[line_index].statement = Some(render(pair)),
+        }
+    }
+
+    let mut output: Vec<Line> = Vec::with_capacity(lines.len());
+    for line in lines {
+        if line.is_empty() {
+            // Keep at most one empty line, and only between non-empty lines
+            if output.last().is_some_and(|previous| !previous.is_empty()) {
+                output.push(line);
+            }
+            continue;
+        }
+        if line.is_closing_directive() {
+            if let Some(previous) = output.last() {
+                if previous.has_attribute_or_directive() {
+                    output.push(Line::default());
+                }
+            }
+        }
+        output.push(line);
+    }
+    while output.last().is_some_and(Line::is_empty) {
+        output.pop();
+    }
+
+    let mut formatted = String::new();
+    let mut block_start = 0;
+    while block_start < output.len() {
+        // Find the block of consecutive lines that have statements and comments
+        let block_end = output[block_start..]
+            .iter()
+            .position(|line| !line.has_trailing_comment())
+            .map_or(output.len(), |offset| block_start + offset);
+        if block_end == block_start {
+            output[block_start].write(&mut formatted, 0);
+            block_start += 1;
+        } else {
+            let block = &output[block_start..block_end];
+            let comment_column = block
+                .iter()
+                .filter_map(|line| line.statement.as_ref())
+                .map(|statement| statement.chars().count())
+                .max()
+                .unwrap_or(0)
+                + COMMENT_GAP;
+            for line in block {
+                line.write(&mut formatted, comment_column);
+            }
+            block_start = block_end;
+        }
+    }
+    Ok(formatted)
+}
+
+/// The content of one line of a DSDL file
+#[derive(Default)]
+struct Line<'i> {
+    /// The statement on this line, formatted
+    statement: Option<String>,
+    /// The text of the comment on this line, after the `#`
+    comment: Option<&'i str>,
+}
+
+impl Line<'_> {
+    fn is_empty(&self) -> bool {
+        self.statement.is_none() && self.comment.is_none()
+    }
+
+    /// Returns true if this line has a statement other than a service response marker
+    fn has_attribute_or_directive(&self) -> bool {
+        match &self.statement {
+            Some(statement) => !statement.starts_with("---"),
+            None => false,
+        }
+    }
+
+    fn has_trailing_comment(&self) -> bool {
+        self.statement.is_some() && self.comment.is_some()
+    }
+
+    /// Returns true if this line has an `@extent` or `@sealed` directive, which ends the fields
+    /// of a message or a service request or response
+    fn is_closing_directive(&self) -> bool {
+        match &self.statement {
+            Some(statement) => statement == "@sealed" || statement.starts_with("@extent "),
+            None => false,
+        }
+    }
+
+    /// Writes this line, with any comment that follows a statement starting at `comment_column`
+    fn write(&self, out: &mut String, comment_column: usize) {
+        if let Some(statement) = &self.statement {
+            out.push_str(statement);
+            if self.comment.is_some() {
+                let padding = comment_column.saturating_sub(statement.chars().count());
+                out.push_str(&" ".repeat(padding));
+            }
+        }
+        if let Some(comment) = self.comment {
+            out.push('#');
+            out.push_str(comment);
+        }
+        out.push('\n');
+    }
+}
+
+/// Formats a statement, data type, or expression
+fn render(pair: Pair<'_, Rule>) -> String {
+    let rule = pair.as_rule();
+    let text = pair.as_str();
+    let mut inner = pair.into_inner();
+    match rule {
+        Rule::statement_constant => {
+            let ty = render(inner.next().unwrap());
+            let name = render(inner.next().unwrap());
+            let value = render(inner.next().unwrap());
+            format!("{} {} = {}", ty, name, value)
+        }
+        Rule::statement_field => {
+            let ty = render(inner.next().unwrap());
+            let name = render(inner.next().unwrap());
+            format!("{} {}", ty, name)
+        }
+        Rule::statement_directive_with_expression => {
+            let name = render(inner.next().unwrap());
+            let value = render(inner.next().unwrap());
+            format!("@{} {}", name, value)
+        }
+        Rule::type_array_variable_inclusive => render_array(inner, "<="),
+        Rule::type_array_variable_exclusive => render_array(inner, "<"),
+        Rule::type_array_fixed => render_array(inner, ""),
+        Rule::type_primitive_truncated => format!("truncated {}", render(inner.next().unwrap())),
+        Rule::type_primitive_saturated if text.starts_with("saturated") => {
+            format!("saturated {}", render(inner.next().unwrap()))
+        }
+        Rule::type_primitive_saturated_bool => "saturated bool".to_owned(),
+        Rule::ex_logical
+        | Rule::ex_comparison
+        | Rule::ex_bitwise
+        | Rule::ex_additive
+        | Rule::ex_multiplicative
+        | Rule::ex_exponential => inner.map(render).collect::<Vec<_>>().join(" "),
+        Rule::ex_attribute => inner.map(render).collect(),
+        Rule::op1_form_log_not => format!("!{}", render(inner.next().unwrap())),
+        Rule::op1_form_inv_pos => format!("+{}", render(inner.next().unwrap())),
+        Rule::op1_form_inv_neg => format!("-{}", render(inner.next().unwrap())),
+        Rule::expression_parenthesized => format!("({})", render(inner.next().unwrap())),
+        Rule::literal_set => format!("{{{}}}", render(inner.next().unwrap())),
+        Rule::expression_list => inner.map(render).collect::<Vec<_>>().join(", "),
+        // Rules that have one inner rule and no other text
+        Rule::statement_directive
+        | Rule::dtype
+        | Rule::type_array
+        | Rule::type_scalar
+        | Rule::type_primitive
+        | Rule::type_primitive_bool
+        | Rule::type_primitive_saturated
+        | Rule::expression
+        | Rule::expression_atom
+        | Rule::literal => render(inner.next().unwrap()),
+        // Everything else can't contain whitespace
+        _ => text.to_owned(),
+    }
+}
+
+fn render_array(mut inner: Pairs<'_, Rule>, operator: &str) -> String {
+    let element = render(inner.next().unwrap());
+    let length = render(inner.next().unwrap());
+    format!("{}[{}{}]", element, operator, length)
+}
@@ -9,11 +9,13 @@ extern crate pest;
 extern crate pest_derive;
 
 mod ast;
+mod format;
 
 use crate::parser::{DsdlParser, Rule};
 use pest::Parser;
 
 pub use crate::ast::types::*;
+pub use crate::format::format;
 
 use pest::error::ErrorVariant;
 /// A range of characters in the input text
@@ -34,6 +36,16 @@ mod parser {
 #[derive(Debug, Clone)]
 pub struct Error(Box<pest::error::Error<Rule>>);
 
+impl Error {
+    /// Sets the path of the file that this error came from
+    ///
+    /// The path is displayed before the line and column numbers, above the line of source code
+    /// that caused the error.
+    pub fn with_path(self, path: &str) -> Self {
+        Error(Box::new(self.0.with_path(path)))
+    }
+}
+
 /// Attempts to parse the text of a DSDL file into an abstract syntax tree
 ///
 /// # Errors
@@ -44,6 +56,43 @@ pub fn parse<'i>(dsdl: &'i str, config: &Config) -> Result<Definition<'i>, Error
     ast::parse_to_ast(parse_tree, config)
 }
 
+/// Parses the text of a DSDL file into an abstract syntax tree, continuing after errors
+///
+/// When a line has invalid syntax or a statement is not valid, this function records the
+/// error and continues with the next line. This finds all the errors in a file in one run.
+///
+/// # Errors
+///
+/// If the DSDL has any errors, this function returns all of them in the order that they
+/// appear in the file.
+pub fn parse_with_recovery<'i>(
+    dsdl: &'i str,
+    config: &Config,
+) -> Result<Definition<'i>, Vec<Error>> {
+    let parse_tree = DsdlParser::parse(Rule::definition_with_recovery, dsdl)
+        .map_err(|e| vec![Error(Box::new(e))])?;
+    let (definition, errors) =
+        ast::parse_to_ast_with_recovery(parse_tree, config, |span| line_syntax_error(dsdl, span));
+    if errors.is_empty() {
+        Ok(definition)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the syntax error in a line of DSDL that could not be parsed
+fn line_syntax_error(dsdl: &str, line: Span<'_>) -> Error {
+    // Parse only this line, with all the other lines made empty so that the line number and
+    // column in the error are correct
+    let mut masked = String::with_capacity(line.end());
+    masked.extend(dsdl[..line.start()].chars().filter(|&c| c == '\n'));
+    masked.push_str(line.as_str());
+    match DsdlParser::parse(Rule::definition, &masked) {
+        Err(e) => Error(Box::new(e)),
+        Ok(_) => make_error("Invalid syntax", line),
+    }
+}
+
 /// Convenience function to make an error value with a custom message
 pub fn make_error<S>(message: S, span: Span<'_>) -> Error
 where
@@ -0,0 +1,44 @@
+extern crate canadensis_dsdl_parser;
+
+use canadensis_dsdl_parser::{format, Config};
+
+fn check_format(input: &str, expected: &str) {
+    let config = Config::default();
+    let formatted = format(input, &config).unwrap();
+    assert_eq!(expected, formatted);
+    // Formatting again does not change anything
+    assert_eq!(expected, format(&formatted, &config).unwrap());
+}
+
+#[test]
+fn format_empty() {
+    check_format("", "");
+    check_format("\n\n \n", "");
+}
+
+#[test]
+fn format_statements() {
+    check_format(
+        "uint8  VALUE=1+2*  3\nsaturated   uint8[ <= 4 ]   values\ntest.Point.1.0[<VALUE] points\r\n@assert _offset_ .count=={ 8 ,16 }\n@sealed",
+        "uint8 VALUE = 1 + 2 * 3\nsaturated uint8[<=4] values\ntest.Point.1.0[<VALUE] points\n@assert _offset_.count == {8, 16}\n\n@sealed\n",
+    );
+    check_format(
+        "@print   'two  spaces'\nfloat32 X = -( 1.5 ** 2 )\nvoid3\n@extent  64*8",
+        "@print 'two  spaces'\nfloat32 X = -(1.5 ** 2)\nvoid3\n\n@extent 64 * 8\n",
+    );
+}
+
+#[test]
+fn format_comments() {
+    check_format(
+        "\n\n# Header   \n    #indented\n\n\n\nuint8 a # first\nuint16[<=32] longer_name    # second\n# separate\nbool c   #third\n---\n@sealed # sealed\n\n",
+        "# Header\n#indented\n\nuint8 a                   # first\nuint16[<=32] longer_name  # second\n# separate\nbool c  #third\n---\n@sealed  # sealed\n",
+    );
+}
+
+#[test]
+fn format_invalid() {
+    let config = Config::default();
+    assert!(format("uint8 = 3", &config).is_err());
+    assert!(format("uint0 value", &config).is_err());
+}
@@ -0,0 +1,32 @@
+extern crate canadensis_dsdl_parser;
+
+use canadensis_dsdl_parser::{parse_with_recovery, Config};
+
+#[test]
+fn valid_definition() {
+    let definition = parse_with_recovery("uint8 a\n# Comment\n@sealed\n", &Config::default())
+        .expect("Valid DSDL");
+    assert_eq!(definition.statements.len(), 3);
+}
+
+#[test]
+fn errors_on_several_lines() {
+    let dsdl = "uint8 a\nuint8 = b\nint3 c\r\nuint99 d\nuint8[<=] e\n@sealed\n";
+    let errors = parse_with_recovery(dsdl, &Config::default())
+        .expect_err("Should have errors")
+        .into_iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 3, "{:#?}", errors);
+    // Syntax errors and other errors are reported at the correct lines, in order
+    assert!(errors[0].contains(" --> 2:"), "{}", errors[0]);
+    assert!(errors[1].contains(" --> 4:"), "{}", errors[1]);
+    assert!(errors[2].contains(" --> 5:"), "{}", errors[2]);
+}
+
+#[test]
+fn error_on_last_line() {
+    let errors = parse_with_recovery("uint8 a\nuint8 b c", &Config::default()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains(" --> 2:9"), "{}", errors[0]);
+}
@@ -0,0 +1,391 @@
+//!
+//! Conversion between dynamic values and CBOR (RFC 8949)
+//!
+//! The CBOR representation is self-describing and uses the same structure as the JSON
+//! representation:
+//!
+//! * A struct is a map with one entry for each field (padding fields are not included)
+//! * A union is a map with one entry, where the key is the name of the active variant
+//! * A `utf8` array is a text string
+//! * A `byte` array is a byte string
+//! * Other arrays are arrays of their elements
+//! * Floating-point values use the shortest encoding that preserves their values
+//!
+//! When decoding, fields that are not present get their default values, and `uint8` arrays
+//! may also be written as byte strings. Tags are ignored. Indefinite-length items are not
+//! supported.
+//!
+
+use canadensis_dsdl_frontend::compiled::{FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use half::f16;
+use std::convert::TryFrom;
+use thiserror::Error;
+
+use crate::Value;
+
+/// Errors that can occur when converting CBOR into a value
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum CborError {
+    #[error("Unexpected end of input")]
+    UnexpectedEnd,
+    #[error("Extra bytes after the end of the value")]
+    TrailingBytes,
+    #[error("Unsupported or invalid item with initial byte {0:#04x}")]
+    InvalidItem(u8),
+    #[error("Items are nested too deeply")]
+    NestingDepth,
+    #[error("Text string is not valid UTF-8")]
+    Utf8,
+    #[error("Expected an item for type {0}")]
+    TypeMismatch(String),
+    #[error("Union must be a map with exactly one text key")]
+    UnionEntries,
+    #[error("Union has no variant named {0}")]
+    UnknownVariant(String),
+    #[error("Struct has no field named {0}")]
+    UnknownField(String),
+}
+
+/// Encodes a value into CBOR
+pub fn to_cbor(value: &Value) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    encode(value, &mut bytes);
+    bytes
+}
+
+/// Decodes CBOR into a value of a message type
+///
+/// The bytes must contain exactly one CBOR data item.
+pub fn from_cbor(message: &Message, bytes: &[u8]) -> Result<Value, CborError> {
+    let mut decoder = Decoder { bytes, depth: 0 };
+    let item = decoder.item()?;
+    if !decoder.bytes.is_empty() {
+        return Err(CborError::TrailingBytes);
+    }
+    from_item(message, &item)
+}
+
+// Major types
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+fn encode(value: &Value, bytes: &mut Vec<u8>) {
+    match value {
+        Value::Bool(false) => bytes.push(0xf4),
+        Value::Bool(true) => bytes.push(0xf5),
+        Value::Int(value) if *value < 0 => encode_head(NEGATIVE, !*value as u64, bytes),
+        Value::Int(value) => encode_head(UNSIGNED, *value as u64, bytes),
+        Value::UInt(value) => encode_head(UNSIGNED, *value, bytes),
+        Value::Float(value) => encode_float(*value, bytes),
+        Value::String(string) => {
+            encode_head(TEXT, string.len() as u64, bytes);
+            bytes.extend_from_slice(string.as_bytes());
+        }
+        Value::Bytes(value) => {
+            encode_head(BYTES, value.len() as u64, bytes);
+            bytes.extend_from_slice(value);
+        }
+        Value::Array(values) => {
+            encode_head(ARRAY, values.len() as u64, bytes);
+            values.iter().for_each(|value| encode(value, bytes));
+        }
+        Value::Struct(fields) => {
+            encode_head(MAP, fields.len() as u64, bytes);
+            for (name, value) in fields {
+                encode(&Value::String(name.clone()), bytes);
+                encode(value, bytes);
+            }
+        }
+        Value::Union { variant, value } => {
+            encode_head(MAP, 1, bytes);
+            encode(&Value::String(variant.clone()), bytes);
+            encode(value, bytes);
+        }
+    }
+}
+
+/// Encodes the major type and argument of an item, using the shortest argument encoding
+fn encode_head(major: u8, argument: u64, bytes: &mut Vec<u8>) {
+    let major = major << 5;
+    if argument < 24 {
+        bytes.push(major | argument as u8);
+    } else if argument <= u64::from(u8::MAX) {
+        bytes.extend_from_slice(&[major | 24, argument as u8]);
+    } else if argument <= u64::from(u16::MAX) {
+        bytes.push(major | 25);
+        bytes.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u64::from(u32::MAX) {
+        bytes.push(major | 26);
+        bytes.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        bytes.push(major | 27);
+        bytes.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+fn encode_float(value: f64, bytes: &mut Vec<u8>) {
+    let half = f16::from_f64(value);
+    if half.to_f64() == value || value.is_nan() {
+        bytes.push(0xf9);
+        bytes.extend_from_slice(&half.to_bits().to_be_bytes());
+    } else if f64::from(value as f32) == value {
+        bytes.push(0xfa);
+        bytes.extend_from_slice(&(value as f32).to_be_bytes());
+    } else {
+        bytes.push(0xfb);
+        bytes.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// A decoded CBOR data item, before it has been matched to a type
+enum Item {
+    Integer(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Item>),
+    Map(Vec<(Item, Item)>),
+    Bool(bool),
+    Float(f64),
+    /// null or undefined
+    Null,
+}
+
+/// The maximum depth of nested arrays, maps, and tags that the decoder accepts
+///
+/// This prevents a stack overflow when decoding untrusted input.
+const MAX_DEPTH: u32 = 128;
+
+struct Decoder<'b> {
+    bytes: &'b [u8],
+    /// The number of arrays, maps, and tags that contain the current item
+    depth: u32,
+}
+
+impl<'b> Decoder<'b> {
+    fn take(&mut self, length: usize) -> Result<&'b [u8], CborError> {
+        if length > self.bytes.len() {
+            return Err(CborError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], CborError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    /// Returns the number of items or bytes from an argument, checking that it is plausible
+    /// for the remaining input
+    fn length(&self, argument: u64) -> Result<usize, CborError> {
+        // Every item takes at least one byte
+        usize::try_from(argument)
+            .ok()
+            .filter(|length| *length <= self.bytes.len())
+            .ok_or(CborError::UnexpectedEnd)
+    }
+
+    fn item(&mut self) -> Result<Item, CborError> {
+        if self.depth > MAX_DEPTH {
+            return Err(CborError::NestingDepth);
+        }
+        self.depth += 1;
+        let item = self.item_inner();
+        self.depth -= 1;
+        item
+    }
+
+    fn item_inner(&mut self) -> Result<Item, CborError> {
+        let initial = self.take_array::<1>()?[0];
+        let major = initial >> 5;
+        let additional = initial & 0x1f;
+        if major == SIMPLE {
+            return match additional {
+                20 => Ok(Item::Bool(false)),
+                21 => Ok(Item::Bool(true)),
+                22 | 23 => Ok(Item::Null),
+                25 => Ok(Item::Float(
+                    f16::from_bits(u16::from_be_bytes(self.take_array()?)).to_f64(),
+                )),
+                26 => Ok(Item::Float(f64::from(f32::from_be_bytes(
+                    self.take_array()?,
+                )))),
+                27 => Ok(Item::Float(f64::from_be_bytes(self.take_array()?))),
+                _ => Err(CborError::InvalidItem(initial)),
+            };
+        }
+        let argument = match additional {
+            0..=23 => u64::from(additional),
+            24 => u64::from(self.take_array::<1>()?[0]),
+            25 => u64::from(u16::from_be_bytes(self.take_array()?)),
+            26 => u64::from(u32::from_be_bytes(self.take_array()?)),
+            27 => u64::from_be_bytes(self.take_array()?),
+            _ => return Err(CborError::InvalidItem(initial)),
+        };
+        match major {
+            UNSIGNED => Ok(Item::Integer(i128::from(argument))),
+            NEGATIVE => Ok(Item::Integer(-1 - i128::from(argument))),
+            BYTES => {
+                let length = self.length(argument)?;
+                Ok(Item::Bytes(self.take(length)?.to_vec()))
+            }
+            TEXT => {
+                let length = self.length(argument)?;
+                let text = std::str::from_utf8(self.take(length)?).map_err(|_| CborError::Utf8)?;
+                Ok(Item::Text(text.to_owned()))
+            }
+            ARRAY => {
+                let length = self.length(argument)?;
+                (0..length)
+                    .map(|_| self.item())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Item::Array)
+            }
+            MAP => {
+                let length = self.length(argument)?;
+                (0..length)
+                    .map(|_| Ok((self.item()?, self.item()?)))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Item::Map)
+            }
+            TAG => self.item(),
+            _ => unreachable!("Major type has only 3 bits"),
+        }
+    }
+}
+
+fn from_item(message: &Message, item: &Item) -> Result<Value, CborError> {
+    match message.kind() {
+        MessageKind::Struct(cyphal_struct) => {
+            let entries = match item {
+                Item::Map(entries) => entries,
+                _ => return Err(CborError::TypeMismatch("struct".into())),
+            };
+            let mut named_entries = Vec::with_capacity(entries.len());
+            for (key, value) in entries {
+                let key = match key {
+                    Item::Text(key) => key,
+                    _ => return Err(CborError::TypeMismatch("struct".into())),
+                };
+                if !cyphal_struct
+                    .fields
+                    .iter()
+                    .any(|field| field.name() == Some(key.as_str()))
+                {
+                    return Err(CborError::UnknownField(key.clone()));
+                }
+                named_entries.push((key, value));
+            }
+            let mut fields = Vec::with_capacity(cyphal_struct.fields.len());
+            for field in &cyphal_struct.fields {
+                if let FieldKind::Data { ty, name } = field.kind() {
+                    let value = match named_entries.iter().find(|(key, _)| *key == name) {
+                        Some((_, field_item)) => from_item_type(ty, field_item)?,
+                        None => Value::default_for_type(ty),
+                    };
+                    fields.push((name.clone(), value));
+                }
+            }
+            Ok(Value::Struct(fields))
+        }
+        MessageKind::Union(union) => {
+            let (name, variant_item) = match item {
+                Item::Map(entries) => match entries.as_slice() {
+                    [(Item::Text(name), variant_item)] => (name, variant_item),
+                    _ => return Err(CborError::UnionEntries),
+                },
+                _ => return Err(CborError::UnionEntries),
+            };
+            let variant = union
+                .variants
+                .iter()
+                .find(|variant| variant.name() == name)
+                .ok_or_else(|| CborError::UnknownVariant(name.clone()))?;
+            Ok(Value::Union {
+                variant: name.clone(),
+                value: Box::new(from_item_type(variant.ty(), variant_item)?),
+            })
+        }
+    }
+}
+
+fn from_item_type(ty: &ResolvedType, item: &Item) -> Result<Value, CborError> {
+    let inner = match ty {
+        ResolvedType::Scalar(scalar) => return from_item_scalar(scalar, item),
+        ResolvedType::FixedArray { inner, .. } | ResolvedType::VariableArray { inner, .. } => inner,
+    };
+    // Array lengths are checked when serializing
+    match (inner, item) {
+        (ResolvedScalarType::Primitive(PrimitiveType::Utf8), Item::Text(text)) => {
+            Ok(Value::String(text.clone()))
+        }
+        (ResolvedScalarType::Primitive(PrimitiveType::Byte), Item::Bytes(bytes)) => {
+            Ok(Value::Bytes(bytes.clone()))
+        }
+        (
+            ResolvedScalarType::Primitive(PrimitiveType::UInt { bits: 8, .. }),
+            Item::Bytes(bytes),
+        ) => Ok(Value::Array(
+            bytes
+                .iter()
+                .map(|byte| Value::UInt((*byte).into()))
+                .collect(),
+        )),
+        (ResolvedScalarType::Primitive(PrimitiveType::Utf8 | PrimitiveType::Byte), _) => {
+            Err(mismatch(ty))
+        }
+        (_, Item::Array(items)) => items
+            .iter()
+            .map(|element| from_item_scalar(inner, element))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        _ => Err(mismatch(ty)),
+    }
+}
+
+fn from_item_scalar(scalar: &ResolvedScalarType, item: &Item) -> Result<Value, CborError> {
+    match scalar {
+        ResolvedScalarType::Composite { inner, .. } => from_item(inner, item),
+        ResolvedScalarType::Primitive(primitive) => {
+            let value = match (primitive, item) {
+                (PrimitiveType::Boolean, Item::Bool(value)) => Some(Value::Bool(*value)),
+                (PrimitiveType::Int { .. }, Item::Integer(value)) => {
+                    i64::try_from(*value).ok().map(Value::Int)
+                }
+                (
+                    PrimitiveType::UInt { .. } | PrimitiveType::Utf8 | PrimitiveType::Byte,
+                    Item::Integer(value),
+                ) => u64::try_from(*value).ok().map(Value::UInt),
+                (
+                    PrimitiveType::Float16 { .. }
+                    | PrimitiveType::Float32 { .. }
+                    | PrimitiveType::Float64 { .. },
+                    Item::Float(value),
+                ) => Some(Value::Float(*value)),
+                (
+                    PrimitiveType::Float16 { .. }
+                    | PrimitiveType::Float32 { .. }
+                    | PrimitiveType::Float64 { .. },
+                    Item::Integer(value),
+                ) => Some(Value::Float(*value as f64)),
+                _ => None,
+            };
+            value.ok_or_else(|| mismatch(primitive))
+        }
+        ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+    }
+}
+
+fn mismatch<T: ToString + ?Sized>(expected: &T) -> CborError {
+    CborError::TypeMismatch(expected.to_string())
+}
@@ -0,0 +1,138 @@
+//! Deserialization driven by type information
+
+use canadensis_dsdl_frontend::compiled::{Extent, FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::types::{
+    ImplicitField, PrimitiveType, ResolvedScalarType, ResolvedType,
+};
+use canadensis_encoding::{DeserializeError, ReadCursor};
+use std::convert::TryFrom;
+
+use crate::Value;
+
+/// Deserializes a value of a message type from a transfer payload
+///
+/// Like generated code, this applies implicit truncation and implicit zero extension to the
+/// payload.
+pub fn deserialize(message: &Message, bytes: &[u8]) -> Result<Value, DeserializeError> {
+    let mut cursor = ReadCursor::new(bytes);
+    read_message(message, &mut cursor)
+}
+
+fn read_message(message: &Message, cursor: &mut ReadCursor<'_>) -> Result<Value, DeserializeError> {
+    match message.kind() {
+        MessageKind::Struct(cyphal_struct) => {
+            let mut fields = Vec::with_capacity(cyphal_struct.fields.len());
+            for field in &cyphal_struct.fields {
+                match field.kind() {
+                    FieldKind::Padding(bits) => cursor.skip_bits(usize::from(*bits)),
+                    FieldKind::Data { ty, name } => {
+                        fields.push((name.clone(), read_type(ty, cursor)?));
+                    }
+                }
+            }
+            Ok(Value::Struct(fields))
+        }
+        MessageKind::Union(union) => {
+            let discriminant = cursor.read_bits(union.discriminant_bits);
+            let variant = usize::try_from(discriminant)
+                .ok()
+                .and_then(|index| union.variants.get(index))
+                .ok_or(DeserializeError::UnionTag)?;
+            Ok(Value::Union {
+                variant: variant.name().to_owned(),
+                value: Box::new(read_type(variant.ty(), cursor)?),
+            })
+        }
+    }
+}
+
+fn read_type(ty: &ResolvedType, cursor: &mut ReadCursor<'_>) -> Result<Value, DeserializeError> {
+    match ty {
+        ResolvedType::Scalar(scalar) => read_scalar(scalar, cursor),
+        ResolvedType::FixedArray { inner, len } => read_array(inner, *len, cursor),
+        ResolvedType::VariableArray { inner, max_len } => {
+            let length_bits = match ty.implicit_field() {
+                Some(ImplicitField::ArrayLength { bits }) => bits,
+                _ => unreachable!("Variable-length array does not have an implicit length field"),
+            };
+            let length = cursor.read_bits(length_bits);
+            if length > *max_len {
+                return Err(DeserializeError::ArrayLength);
+            }
+            read_array(inner, length, cursor)
+        }
+    }
+}
+
+fn read_array(
+    inner: &ResolvedScalarType,
+    length: u64,
+    cursor: &mut ReadCursor<'_>,
+) -> Result<Value, DeserializeError> {
+    match inner {
+        ResolvedScalarType::Primitive(PrimitiveType::Utf8) => {
+            let bytes = read_byte_array(length, cursor);
+            String::from_utf8(bytes)
+                .map(Value::String)
+                .map_err(|_| DeserializeError::Utf8)
+        }
+        ResolvedScalarType::Primitive(PrimitiveType::Byte) => {
+            Ok(Value::Bytes(read_byte_array(length, cursor)))
+        }
+        _ => (0..length)
+            .map(|_| read_scalar(inner, cursor))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+    }
+}
+
+fn read_byte_array(length: u64, cursor: &mut ReadCursor<'_>) -> Vec<u8> {
+    let mut bytes = vec![0; length as usize];
+    cursor.read_bytes(&mut bytes);
+    bytes
+}
+
+fn read_scalar(
+    scalar: &ResolvedScalarType,
+    cursor: &mut ReadCursor<'_>,
+) -> Result<Value, DeserializeError> {
+    match scalar {
+        ResolvedScalarType::Composite { inner, .. } => {
+            cursor.align_to_byte();
+            let value = match inner.extent() {
+                Extent::Sealed => read_message(inner, cursor),
+                Extent::Delimited(_) => {
+                    let length = cursor.read_aligned_u32() as usize;
+                    let remaining = cursor.as_bytes().expect("Cursor not aligned");
+                    if length > remaining.len() {
+                        return Err(DeserializeError::DelimitedLength);
+                    }
+                    let status = read_message(inner, &mut ReadCursor::new(&remaining[..length]));
+                    cursor.advance_bytes(length);
+                    status
+                }
+            };
+            cursor.align_to_byte();
+            value
+        }
+        ResolvedScalarType::Primitive(primitive) => Ok(read_primitive(primitive, cursor)),
+        ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+    }
+}
+
+fn read_primitive(primitive: &PrimitiveType, cursor: &mut ReadCursor<'_>) -> Value {
+    match primitive {
+        PrimitiveType::Boolean => Value::Bool(cursor.read_bool()),
+        PrimitiveType::Int { bits } => {
+            // Sign-extend to 64 bits
+            let unused_bits = 64 - u32::from(*bits);
+            let value = cursor.read_bits(*bits) << unused_bits;
+            Value::Int((value as i64) >> unused_bits)
+        }
+        PrimitiveType::UInt { bits, .. } => Value::UInt(cursor.read_bits(*bits)),
+        PrimitiveType::Utf8 | PrimitiveType::Byte => Value::UInt(cursor.read_bits(8)),
+        PrimitiveType::Float16 { .. } => Value::Float(cursor.read_f16().to_f64()),
+        PrimitiveType::Float32 { .. } => Value::Float(f64::from(cursor.read_f32())),
+        PrimitiveType::Float64 { .. } => Value::Float(cursor.read_f64()),
+    }
+}
@@ -0,0 +1,193 @@
+//!
+//! Conversion between dynamic values and JSON
+//!
+//! The JSON representation follows the conventions of pycyphal and yakut:
+//!
+//! * A struct is an object with one entry for each field (padding fields are not included)
+//! * A union is an object with one entry, where the key is the name of the active variant
+//! * A `utf8` array is a string
+//! * Other arrays, including `byte` arrays, are arrays of their elements
+//! * Non-finite floating-point values are `null`
+//!
+//! When converting from JSON, fields that are not present get their default values, and
+//! `uint8` and `byte` arrays may also be written as strings.
+//!
+
+use canadensis_dsdl_frontend::compiled::{FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use serde_json::{Map, Number};
+use std::convert::TryFrom;
+use thiserror::Error;
+
+use crate::Value;
+
+/// Errors that can occur when converting JSON into a value
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum JsonError {
+    #[error("Expected a JSON value for type {expected}, got {actual}")]
+    TypeMismatch {
+        expected: String,
+        actual: serde_json::Value,
+    },
+    #[error("Union must be an object with exactly one entry, got {0}")]
+    UnionEntries(serde_json::Value),
+    #[error("Union has no variant named {0}")]
+    UnknownVariant(String),
+    #[error("Struct has no field named {0}")]
+    UnknownField(String),
+}
+
+/// Converts a value into JSON
+pub fn to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Bool(value) => serde_json::Value::Bool(*value),
+        Value::Int(value) => serde_json::Value::from(*value),
+        Value::UInt(value) => serde_json::Value::from(*value),
+        Value::Float(value) => Number::from_f64(*value)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::String(value) => serde_json::Value::String(value.clone()),
+        Value::Bytes(bytes) => bytes.iter().copied().map(serde_json::Value::from).collect(),
+        Value::Array(values) => values.iter().map(to_json).collect(),
+        Value::Struct(fields) => serde_json::Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), to_json(value)))
+                .collect(),
+        ),
+        Value::Union { variant, value } => {
+            let mut object = Map::new();
+            object.insert(variant.clone(), to_json(value));
+            serde_json::Value::Object(object)
+        }
+    }
+}
+
+/// Converts JSON into a value of a message type
+pub fn from_json(message: &Message, json: &serde_json::Value) -> Result<Value, JsonError> {
+    match message.kind() {
+        MessageKind::Struct(cyphal_struct) => {
+            let object = json.as_object().ok_or_else(|| mismatch("struct", json))?;
+            if let Some(unknown) = object.keys().find(|key| {
+                !cyphal_struct
+                    .fields
+                    .iter()
+                    .any(|field| field.name() == Some(key.as_str()))
+            }) {
+                return Err(JsonError::UnknownField(unknown.clone()));
+            }
+            let mut fields = Vec::with_capacity(cyphal_struct.fields.len());
+            for field in &cyphal_struct.fields {
+                if let FieldKind::Data { ty, name } = field.kind() {
+                    let value = match object.get(name) {
+                        Some(field_json) => from_json_type(ty, field_json)?,
+                        None => Value::default_for_type(ty),
+                    };
+                    fields.push((name.clone(), value));
+                }
+            }
+            Ok(Value::Struct(fields))
+        }
+        MessageKind::Union(union) => {
+            let (name, variant_json) = match json.as_object() {
+                Some(object) if object.len() == 1 => object.iter().next().unwrap(),
+                _ => return Err(JsonError::UnionEntries(json.clone())),
+            };
+            let variant = union
+                .variants
+                .iter()
+                .find(|variant| variant.name() == name)
+                .ok_or_else(|| JsonError::UnknownVariant(name.clone()))?;
+            Ok(Value::Union {
+                variant: name.clone(),
+                value: Box::new(from_json_type(variant.ty(), variant_json)?),
+            })
+        }
+    }
+}
+
+fn from_json_type(ty: &ResolvedType, json: &serde_json::Value) -> Result<Value, JsonError> {
+    let inner = match ty {
+        ResolvedType::Scalar(scalar) => return from_json_scalar(scalar, json),
+        ResolvedType::FixedArray { inner, .. } | ResolvedType::VariableArray { inner, .. } => inner,
+    };
+    // Array lengths are checked when serializing
+    match (inner, json) {
+        (ResolvedScalarType::Primitive(PrimitiveType::Utf8), serde_json::Value::String(string)) => {
+            Ok(Value::String(string.clone()))
+        }
+        (ResolvedScalarType::Primitive(PrimitiveType::Byte), serde_json::Value::String(string)) => {
+            Ok(Value::Bytes(string.as_bytes().to_vec()))
+        }
+        (
+            ResolvedScalarType::Primitive(PrimitiveType::UInt { bits: 8, .. }),
+            serde_json::Value::String(string),
+        ) => Ok(Value::Array(
+            string
+                .bytes()
+                .map(|byte| Value::UInt(byte.into()))
+                .collect(),
+        )),
+        (_, serde_json::Value::Array(elements)) => {
+            let values = elements
+                .iter()
+                .map(|element| from_json_scalar(inner, element))
+                .collect::<Result<Vec<_>, _>>()?;
+            match inner {
+                ResolvedScalarType::Primitive(PrimitiveType::Utf8) => {
+                    let bytes = values.iter().map(byte_value).collect::<Option<Vec<u8>>>();
+                    bytes
+                        .and_then(|bytes| String::from_utf8(bytes).ok())
+                        .map(Value::String)
+                        .ok_or_else(|| mismatch(ty, json))
+                }
+                ResolvedScalarType::Primitive(PrimitiveType::Byte) => values
+                    .iter()
+                    .map(byte_value)
+                    .collect::<Option<Vec<u8>>>()
+                    .map(Value::Bytes)
+                    .ok_or_else(|| mismatch(ty, json)),
+                _ => Ok(Value::Array(values)),
+            }
+        }
+        _ => Err(mismatch(ty, json)),
+    }
+}
+
+fn byte_value(value: &Value) -> Option<u8> {
+    value.as_u64().and_then(|value| u8::try_from(value).ok())
+}
+
+fn from_json_scalar(
+    scalar: &ResolvedScalarType,
+    json: &serde_json::Value,
+) -> Result<Value, JsonError> {
+    match scalar {
+        ResolvedScalarType::Composite { inner, .. } => from_json(inner, json),
+        ResolvedScalarType::Primitive(primitive) => {
+            let value = match primitive {
+                PrimitiveType::Boolean => json.as_bool().map(Value::Bool),
+                PrimitiveType::Int { .. } => json.as_i64().map(Value::Int),
+                PrimitiveType::UInt { .. } | PrimitiveType::Utf8 | PrimitiveType::Byte => {
+                    json.as_u64().map(Value::UInt)
+                }
+                PrimitiveType::Float16 { .. }
+                | PrimitiveType::Float32 { .. }
+                | PrimitiveType::Float64 { .. } => match json {
+                    serde_json::Value::Null => Some(Value::Float(f64::NAN)),
+                    _ => json.as_f64().map(Value::Float),
+                },
+            };
+            value.ok_or_else(|| mismatch(primitive, json))
+        }
+        ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+    }
+}
+
+fn mismatch<T: ToString + ?Sized>(expected: &T, actual: &serde_json::Value) -> JsonError {
+    JsonError::TypeMismatch {
+        expected: expected.to_string(),
+        actual: actual.clone(),
+    }
+}
@@ -0,0 +1,63 @@
+//!
+//! # Dynamic Cyphal values
+//!
+//! This library serializes and deserializes values of Cyphal data types that are not known
+//! at compile time. Instead of generated Rust types, it uses the type information from
+//! `canadensis_dsdl_frontend` and represents values as a tree of [`Value`]s.
+//!
+//! This is useful for generic tools, like monitors and bridges, that handle many data types.
+//! The [`registry`] module loads DSDL files at run time and finds types by name or port ID.
+//!
+//! ```
+//! # use canadensis_dsdl_frontend::compiled::DsdlKind;
+//! # use canadensis_dsdl_frontend::{Config, Package};
+//! # use canadensis_dynamic::Value;
+//! let mut package = Package::new();
+//! package
+//!     .add_string(
+//!         None,
+//!         "example.Point.1.0".parse().unwrap(),
+//!         "int16 x\nint16 y\n@sealed\n".into(),
+//!     )
+//!     .unwrap();
+//! let compiled = package.compile(&Config::default()).unwrap();
+//! let point_type = match &compiled
+//!     .get_by_key(&"example.Point.1.0".parse().unwrap())
+//!     .unwrap()
+//!     .kind
+//! {
+//!     DsdlKind::Message(message) => message,
+//!     DsdlKind::Service { .. } => unreachable!(),
+//! };
+//!
+//! let point = canadensis_dynamic::deserialize(point_type, &[0x01, 0x00, 0xff, 0xff]).unwrap();
+//! assert_eq!(point.field("x"), Some(&Value::Int(1)));
+//! assert_eq!(point.field("y"), Some(&Value::Int(-1)));
+//! assert_eq!(
+//!     canadensis_dynamic::serialize(point_type, &point).unwrap(),
+//!     [0x01, 0x00, 0xff, 0xff]
+//! );
+//! ```
+//!
+
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dsdl_parser;
+extern crate canadensis_encoding;
+extern crate half;
+#[cfg(feature = "json")]
+extern crate serde_json;
+extern crate thiserror;
+
+pub mod cbor;
+mod deserialize;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod registry;
+mod serialize;
+mod value;
+pub mod yaml;
+
+pub use crate::deserialize::deserialize;
+pub use crate::serialize::{serialize, SerializeError};
+pub use crate::value::Value;
+pub use canadensis_encoding::DeserializeError;
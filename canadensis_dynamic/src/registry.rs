@@ -0,0 +1,188 @@
+//! Loading DSDL files at run time and looking up their types
+//!
+//! A [`TypeRegistry`] contains the compiled types from one or more DSDL root directories.
+//! It finds types by name or by fixed port ID, and serializes and deserializes values of those
+//! types. This is useful for tools that handle data types chosen by the user at run time.
+//!
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Message};
+use canadensis_dsdl_frontend::{Config, Package, TypeKey};
+use canadensis_encoding::DeserializeError;
+use std::collections::BTreeMap;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::{SerializeError, Value};
+
+/// The part of a data type that a transfer payload contains
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PayloadKind {
+    /// A message
+    Message,
+    /// A service request
+    Request,
+    /// A service response
+    Response,
+}
+
+/// Errors that can occur when serializing or deserializing with a registry
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum RegistryError {
+    #[error("Unknown type {0}")]
+    UnknownType(String),
+    #[error("Type {0} is a service type, not a message type")]
+    NotMessage(String),
+    #[error("Type {0} is a message type, not a service type")]
+    NotService(String),
+    #[error(transparent)]
+    Serialize(#[from] SerializeError),
+    #[error("Can't deserialize: {0:?}")]
+    Deserialize(DeserializeError),
+}
+
+/// A collection of compiled data types, which can be loaded at run time
+#[derive(Debug)]
+pub struct TypeRegistry {
+    package: CompiledPackage,
+    /// The newest message type with each fixed subject ID
+    subjects: BTreeMap<u16, TypeKey>,
+    /// The newest service type with each fixed service ID
+    services: BTreeMap<u16, TypeKey>,
+}
+
+impl TypeRegistry {
+    /// Loads and compiles the DSDL files in one or more root directories
+    ///
+    /// Each subdirectory of a root directory is a namespace, like `uavcan` or `reg` in the
+    /// public regulated data types.
+    pub fn load<I, P>(
+        roots: I,
+        config: &Config,
+    ) -> Result<Self, Box<canadensis_dsdl_frontend::Error>>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut package = Package::new();
+        for root in roots {
+            package.add_files(root)?;
+        }
+        Ok(TypeRegistry::new(package.compile(config)?))
+    }
+
+    /// Creates a registry with the types in a compiled package
+    pub fn new(package: CompiledPackage) -> Self {
+        let mut subjects = BTreeMap::new();
+        let mut services = BTreeMap::new();
+        // The package is sorted by name and then by version, so a newer version replaces
+        // an older version with the same fixed port ID
+        for (key, dsdl) in package.iter() {
+            if let Some(port_id) = dsdl.fixed_port_id {
+                let ids = match dsdl.kind {
+                    DsdlKind::Message(_) => &mut subjects,
+                    DsdlKind::Service { .. } => &mut services,
+                };
+                ids.insert(port_id as u16, key.clone());
+            }
+        }
+        TypeRegistry {
+            package,
+            subjects,
+            services,
+        }
+    }
+
+    /// Returns the compiled package that contains the types
+    pub fn package(&self) -> &CompiledPackage {
+        &self.package
+    }
+
+    /// Finds a type by name
+    ///
+    /// The name can include a full version (`uavcan.node.Heartbeat.1.0`), only a major version
+    /// (`uavcan.node.Heartbeat.1`), or no version (`uavcan.node.Heartbeat`). If the version is
+    /// incomplete, this returns the newest matching version. Names are case-sensitive.
+    pub fn find(&self, name: &str) -> Option<&TypeKey> {
+        // Split off up to two version numbers from the end
+        let mut type_name = name;
+        let mut version = Vec::new();
+        while version.len() < 2 {
+            match type_name.rsplit_once('.') {
+                Some((before, last)) => match last.parse::<u8>() {
+                    Ok(number) => {
+                        version.insert(0, number);
+                        type_name = before;
+                    }
+                    Err(_) => break,
+                },
+                None => break,
+            }
+        }
+        self.package
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| {
+                key.name().to_string() == type_name
+                    && match *version {
+                        [] => true,
+                        [major] => key.version().major == major,
+                        [major, minor] => {
+                            key.version().major == major && key.version().minor == minor
+                        }
+                        _ => unreachable!(),
+                    }
+            })
+            .last()
+    }
+
+    /// Returns the newest message type with a fixed subject ID
+    pub fn subject_type(&self, subject_id: u16) -> Option<&TypeKey> {
+        self.subjects.get(&subject_id)
+    }
+
+    /// Returns the newest service type with a fixed service ID
+    pub fn service_type(&self, service_id: u16) -> Option<&TypeKey> {
+        self.services.get(&service_id)
+    }
+
+    /// Returns the message, request, or response part of a type
+    pub fn message(&self, key: &TypeKey, kind: PayloadKind) -> Result<&Message, RegistryError> {
+        let dsdl = self
+            .package
+            .get_by_key(key)
+            .ok_or_else(|| RegistryError::UnknownType(key.to_string()))?;
+        match (&dsdl.kind, kind) {
+            (DsdlKind::Message(message), PayloadKind::Message) => Ok(message),
+            (DsdlKind::Service { request, .. }, PayloadKind::Request) => Ok(request),
+            (DsdlKind::Service { response, .. }, PayloadKind::Response) => Ok(response),
+            (DsdlKind::Message(_), _) => Err(RegistryError::NotService(key.to_string())),
+            (DsdlKind::Service { .. }, PayloadKind::Message) => {
+                Err(RegistryError::NotMessage(key.to_string()))
+            }
+        }
+    }
+
+    /// Serializes a value of a type into a transfer payload
+    pub fn serialize(
+        &self,
+        key: &TypeKey,
+        kind: PayloadKind,
+        value: &Value,
+    ) -> Result<Vec<u8>, RegistryError> {
+        let message = self.message(key, kind)?;
+        Ok(crate::serialize(message, value)?)
+    }
+
+    /// Deserializes a value of a type from a transfer payload
+    pub fn deserialize(
+        &self,
+        key: &TypeKey,
+        kind: PayloadKind,
+        bytes: &[u8],
+    ) -> Result<Value, RegistryError> {
+        let message = self.message(key, kind)?;
+        crate::deserialize(message, bytes).map_err(RegistryError::Deserialize)
+    }
+}
@@ -0,0 +1,265 @@
+//! Serialization driven by type information
+
+use canadensis_dsdl_frontend::compiled::{Extent, FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::types::{
+    ImplicitField, PrimitiveType, ResolvedScalarType, ResolvedType,
+};
+use canadensis_dsdl_parser::CastMode;
+use canadensis_encoding::WriteCursor;
+use half::f16;
+use thiserror::Error;
+
+use crate::Value;
+
+/// Errors that can occur when serializing a value
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SerializeError {
+    #[error("Expected a value of type {expected}, got {actual:?}")]
+    TypeMismatch { expected: String, actual: Value },
+    #[error("Missing a value for field {0}")]
+    MissingField(String),
+    #[error("Union has no variant named {0}")]
+    UnknownVariant(String),
+    #[error("Array has {length} elements, but the type allows at most {max_length}")]
+    ArrayTooLong { length: usize, max_length: u64 },
+    #[error("Array has {length} elements, but the type requires exactly {expected_length}")]
+    ArrayLength { length: usize, expected_length: u64 },
+}
+
+/// Serializes a value of a message type into a transfer payload
+///
+/// Integer and floating-point values that do not fit into their DSDL types are saturated or
+/// truncated according to the cast mode of each type. Fields of structs may be in any order,
+/// and fields with unknown names are ignored.
+pub fn serialize(message: &Message, value: &Value) -> Result<Vec<u8>, SerializeError> {
+    // Allocate enough space for the largest possible value and truncate afterwards
+    let max_bytes = (message.bit_length().max_value() + 7) / 8;
+    let mut bytes = vec![0u8; max_bytes as usize];
+    let mut cursor = WriteCursor::new(&mut bytes);
+    write_message(message, value, &mut cursor)?;
+    let length = (cursor.bits_written() + 7) / 8;
+    bytes.truncate(length);
+    Ok(bytes)
+}
+
+fn write_message(
+    message: &Message,
+    value: &Value,
+    cursor: &mut WriteCursor<'_>,
+) -> Result<(), SerializeError> {
+    match message.kind() {
+        MessageKind::Struct(cyphal_struct) => {
+            if !matches!(value, Value::Struct(_)) {
+                return Err(mismatch("struct", value));
+            }
+            for field in &cyphal_struct.fields {
+                match field.kind() {
+                    FieldKind::Padding(bits) => cursor.skip_bits(usize::from(*bits)),
+                    FieldKind::Data { ty, name } => {
+                        let field_value = value
+                            .field(name)
+                            .ok_or_else(|| SerializeError::MissingField(name.clone()))?;
+                        write_type(ty, field_value, cursor)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        MessageKind::Union(union) => {
+            let (variant_name, variant_value) = match value {
+                Value::Union { variant, value } => (variant, value),
+                _ => return Err(mismatch("union", value)),
+            };
+            let (index, variant) = union
+                .variants
+                .iter()
+                .enumerate()
+                .find(|(_, variant)| variant.name() == variant_name)
+                .ok_or_else(|| SerializeError::UnknownVariant(variant_name.clone()))?;
+            cursor.write_bits(index as u64, union.discriminant_bits);
+            write_type(variant.ty(), variant_value, cursor)
+        }
+    }
+}
+
+fn write_type(
+    ty: &ResolvedType,
+    value: &Value,
+    cursor: &mut WriteCursor<'_>,
+) -> Result<(), SerializeError> {
+    match ty {
+        ResolvedType::Scalar(scalar) => write_scalar(scalar, value, cursor),
+        ResolvedType::FixedArray { inner, len } => {
+            let elements = Elements::new(inner, ty, value)?;
+            if elements.len() as u64 != *len {
+                return Err(SerializeError::ArrayLength {
+                    length: elements.len(),
+                    expected_length: *len,
+                });
+            }
+            elements.write(inner, cursor)
+        }
+        ResolvedType::VariableArray { inner, max_len } => {
+            let elements = Elements::new(inner, ty, value)?;
+            if elements.len() as u64 > *max_len {
+                return Err(SerializeError::ArrayTooLong {
+                    length: elements.len(),
+                    max_length: *max_len,
+                });
+            }
+            let length_bits = match ty.implicit_field() {
+                Some(ImplicitField::ArrayLength { bits }) => bits,
+                _ => unreachable!("Variable-length array does not have an implicit length field"),
+            };
+            cursor.write_bits(elements.len() as u64, length_bits);
+            elements.write(inner, cursor)
+        }
+    }
+}
+
+/// The elements of an array value
+enum Elements<'v> {
+    /// Bytes from a `String` or `Bytes`
+    Bytes(&'v [u8]),
+    /// Values from an `Array`
+    Values(&'v [Value]),
+}
+
+impl<'v> Elements<'v> {
+    fn new(
+        inner: &ResolvedScalarType,
+        ty: &ResolvedType,
+        value: &'v Value,
+    ) -> Result<Self, SerializeError> {
+        let byte_sized = matches!(
+            inner,
+            ResolvedScalarType::Primitive(
+                PrimitiveType::Utf8 | PrimitiveType::Byte | PrimitiveType::UInt { bits: 8, .. }
+            )
+        );
+        match value {
+            Value::String(string) if byte_sized => Ok(Elements::Bytes(string.as_bytes())),
+            Value::Bytes(bytes) if byte_sized => Ok(Elements::Bytes(bytes)),
+            Value::Array(values) => Ok(Elements::Values(values)),
+            _ => Err(mismatch(ty, value)),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Elements::Bytes(bytes) => bytes.len(),
+            Elements::Values(values) => values.len(),
+        }
+    }
+
+    fn write(
+        &self,
+        inner: &ResolvedScalarType,
+        cursor: &mut WriteCursor<'_>,
+    ) -> Result<(), SerializeError> {
+        match self {
+            Elements::Bytes(bytes) => {
+                cursor.write_bytes(bytes);
+                Ok(())
+            }
+            Elements::Values(values) => values
+                .iter()
+                .try_for_each(|value| write_scalar(inner, value, cursor)),
+        }
+    }
+}
+
+fn write_scalar(
+    scalar: &ResolvedScalarType,
+    value: &Value,
+    cursor: &mut WriteCursor<'_>,
+) -> Result<(), SerializeError> {
+    match scalar {
+        ResolvedScalarType::Composite { inner, .. } => {
+            cursor.align_to_byte();
+            match inner.extent() {
+                Extent::Sealed => write_message(inner, value, cursor)?,
+                Extent::Delimited(_) => {
+                    let bytes = serialize(inner, value)?;
+                    cursor.write_aligned_u32(bytes.len() as u32);
+                    cursor.write_aligned_bytes(&bytes);
+                }
+            }
+            cursor.align_to_byte();
+            Ok(())
+        }
+        ResolvedScalarType::Primitive(primitive) => write_primitive(primitive, value, cursor),
+        ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+    }
+}
+
+fn write_primitive(
+    primitive: &PrimitiveType,
+    value: &Value,
+    cursor: &mut WriteCursor<'_>,
+) -> Result<(), SerializeError> {
+    match primitive {
+        PrimitiveType::Boolean => {
+            let value = value.as_bool().ok_or_else(|| mismatch(primitive, value))?;
+            cursor.write_bool(value);
+        }
+        PrimitiveType::Int { bits } => {
+            let integer = integer(primitive, value)?;
+            let max = (1i128 << (bits - 1)) - 1;
+            let min = -(1i128 << (bits - 1));
+            cursor.write_bits(integer.clamp(min, max) as u64, *bits);
+        }
+        PrimitiveType::UInt { bits, mode } => {
+            let integer = integer(primitive, value)?;
+            let integer = match mode {
+                CastMode::Saturated => integer.clamp(0, (1i128 << bits) - 1),
+                // The cursor uses only the least significant bits
+                CastMode::Truncated => integer,
+            };
+            cursor.write_bits(integer as u64, *bits);
+        }
+        PrimitiveType::Utf8 | PrimitiveType::Byte => {
+            let integer = integer(primitive, value)?;
+            cursor.write_u8(integer.clamp(0, 255) as u8);
+        }
+        PrimitiveType::Float16 { mode } => {
+            let mut float = float(primitive, value)?;
+            if *mode == CastMode::Saturated && float.is_finite() {
+                float = float.clamp(f16::MIN.to_f64(), f16::MAX.to_f64());
+            }
+            cursor.write_f16(f16::from_f64(float));
+        }
+        PrimitiveType::Float32 { mode } => {
+            let mut float = float(primitive, value)?;
+            if *mode == CastMode::Saturated && float.is_finite() {
+                float = float.clamp(f64::from(f32::MIN), f64::from(f32::MAX));
+            }
+            cursor.write_f32(float as f32);
+        }
+        PrimitiveType::Float64 { .. } => {
+            let float = float(primitive, value)?;
+            cursor.write_f64(float);
+        }
+    }
+    Ok(())
+}
+
+fn integer(primitive: &PrimitiveType, value: &Value) -> Result<i128, SerializeError> {
+    match value {
+        Value::Int(value) => Ok(i128::from(*value)),
+        Value::UInt(value) => Ok(i128::from(*value)),
+        _ => Err(mismatch(primitive, value)),
+    }
+}
+
+fn float(primitive: &PrimitiveType, value: &Value) -> Result<f64, SerializeError> {
+    value.as_f64().ok_or_else(|| mismatch(primitive, value))
+}
+
+fn mismatch<T: ToString + ?Sized>(expected: &T, actual: &Value) -> SerializeError {
+    SerializeError::TypeMismatch {
+        expected: expected.to_string(),
+        actual: actual.clone(),
+    }
+}
@@ -0,0 +1,163 @@
+//! The dynamic value representation
+
+use canadensis_dsdl_frontend::compiled::{FieldKind, Message, MessageKind};
+use canadensis_dsdl_frontend::types::{PrimitiveType, ResolvedScalarType, ResolvedType};
+use std::convert::TryFrom;
+
+/// A value of any DSDL type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A `bool`
+    Bool(bool),
+    /// A signed integer
+    Int(i64),
+    /// An unsigned integer, `byte`, or `utf8`
+    UInt(u64),
+    /// A `float16`, `float32`, or `float64`
+    Float(f64),
+    /// An array of `utf8`
+    String(String),
+    /// An array of `byte`
+    Bytes(Vec<u8>),
+    /// An array of any other type
+    Array(Vec<Value>),
+    /// A value of a non-union composite type
+    ///
+    /// This contains the name and value of each field (excluding padding) in the order that
+    /// the fields are declared.
+    Struct(Vec<(String, Value)>),
+    /// A value of a union type
+    Union {
+        /// The name of the active variant
+        variant: String,
+        /// The value of the active variant
+        value: Box<Value>,
+    },
+}
+
+impl Value {
+    /// Returns the default value of a message type
+    ///
+    /// All numbers are zero, all booleans are false, variable-length arrays are empty,
+    /// and unions have their first variant active.
+    pub fn default_for(message: &Message) -> Value {
+        match message.kind() {
+            MessageKind::Struct(cyphal_struct) => Value::Struct(
+                cyphal_struct
+                    .fields
+                    .iter()
+                    .filter_map(|field| match field.kind() {
+                        FieldKind::Padding(_) => None,
+                        FieldKind::Data { ty, name } => {
+                            Some((name.clone(), Value::default_for_type(ty)))
+                        }
+                    })
+                    .collect(),
+            ),
+            MessageKind::Union(union) => {
+                let variant = union
+                    .variants
+                    .first()
+                    .expect("Union must have at least one variant");
+                Value::Union {
+                    variant: variant.name().to_owned(),
+                    value: Box::new(Value::default_for_type(variant.ty())),
+                }
+            }
+        }
+    }
+
+    /// Returns the default value of a field type
+    pub(crate) fn default_for_type(ty: &ResolvedType) -> Value {
+        let length = match ty {
+            ResolvedType::Scalar(scalar) => return Value::default_for_scalar(scalar),
+            ResolvedType::FixedArray { len, .. } => *len as usize,
+            ResolvedType::VariableArray { .. } => 0,
+        };
+        match ty.scalar() {
+            ResolvedScalarType::Primitive(PrimitiveType::Utf8) => {
+                Value::String("\0".repeat(length))
+            }
+            ResolvedScalarType::Primitive(PrimitiveType::Byte) => Value::Bytes(vec![0; length]),
+            scalar => Value::Array(vec![Value::default_for_scalar(scalar); length]),
+        }
+    }
+
+    fn default_for_scalar(scalar: &ResolvedScalarType) -> Value {
+        match scalar {
+            ResolvedScalarType::Composite { inner, .. } => Value::default_for(inner),
+            ResolvedScalarType::Primitive(primitive) => match primitive {
+                PrimitiveType::Boolean => Value::Bool(false),
+                PrimitiveType::Int { .. } => Value::Int(0),
+                PrimitiveType::UInt { .. } | PrimitiveType::Utf8 | PrimitiveType::Byte => {
+                    Value::UInt(0)
+                }
+                PrimitiveType::Float16 { .. }
+                | PrimitiveType::Float32 { .. }
+                | PrimitiveType::Float64 { .. } => Value::Float(0.0),
+            },
+            ResolvedScalarType::Void { .. } => unreachable!("Void type used as a value"),
+        }
+    }
+
+    /// If this is a struct, returns the value of the field with the provided name
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) => fields
+                .iter()
+                .find(|(field_name, _)| field_name == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// If this is a struct, returns a mutable reference to the value of the field with the
+    /// provided name
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut Value> {
+        match self {
+            Value::Struct(fields) => fields
+                .iter_mut()
+                .find(|(field_name, _)| field_name == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// If this is a `Bool`, returns its value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// If this is an `Int` or `UInt` that fits into an i64, returns its value
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            Value::UInt(value) => i64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// If this is an `Int` or `UInt` that fits into a u64, returns its value
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Int(value) => u64::try_from(*value).ok(),
+            Value::UInt(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// If this is a number, returns its value as an f64
+    ///
+    /// Large integers may lose precision.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::UInt(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
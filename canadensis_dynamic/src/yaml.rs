@@ -0,0 +1,223 @@
+//!
+//! Human-readable YAML rendering of dynamic values
+//!
+//! The output follows the style of `yakut sub`:
+//!
+//! * Structs and unions are block mappings, with nested composites indented by two spaces
+//! * A union is a mapping with one entry, where the key is the name of the active variant
+//! * Arrays of numbers and booleans are flow sequences on one line, like `[1, 2, 3]`
+//! * Arrays of composites are block sequences
+//! * A `utf8` array is a string, which is quoted only if necessary
+//! * Non-finite floating-point values are `.nan`, `.inf`, and `-.inf`
+//!
+//! Long arrays can be truncated to make the output easier to read. When an array is truncated,
+//! a comment after it shows how many elements were left out.
+//!
+//! With the `json` feature, [`from_yaml`] reads YAML that people write by hand, including the
+//! output of [`to_yaml`], into a value of a message type.
+//!
+
+use canadensis_dsdl_frontend::compiled::Message;
+use canadensis_encoding::DeserializeError;
+use std::fmt::Write;
+
+use crate::Value;
+
+#[cfg(feature = "json")]
+mod parse;
+#[cfg(feature = "json")]
+pub use self::parse::{from_yaml, parse_yaml, YamlError};
+
+/// Options for YAML rendering
+#[derive(Debug, Clone, Default)]
+pub struct YamlConfig {
+    /// The maximum number of elements to show for each array, or None to show all elements
+    ///
+    /// This also applies to `byte` arrays, but not to `utf8` arrays.
+    pub max_array_elements: Option<usize>,
+}
+
+/// Renders a value as YAML
+///
+/// The returned string does not end with a newline.
+pub fn to_yaml(value: &Value, config: &YamlConfig) -> String {
+    match inline(value, config) {
+        Some(inline) => inline,
+        None => block_lines(value, config).join("\n"),
+    }
+}
+
+/// Deserializes a transfer payload and renders it as a YAML document, like `yakut sub` does
+///
+/// The document starts with `---` and contains a mapping from the port ID to the value.
+pub fn transfer_to_yaml(
+    port_id: u16,
+    message: &Message,
+    payload: &[u8],
+    config: &YamlConfig,
+) -> Result<String, DeserializeError> {
+    let value = crate::deserialize(message, payload)?;
+    let mut document = format!("---\n{}:", port_id);
+    match inline(&value, config) {
+        Some(inline) => write!(document, " {}", inline).unwrap(),
+        None => {
+            for line in block_lines(&value, config) {
+                write!(document, "\n  {}", line).unwrap();
+            }
+        }
+    }
+    Ok(document)
+}
+
+/// Renders a value that fits on one line, or returns None if the value needs block style
+fn inline(value: &Value, config: &YamlConfig) -> Option<String> {
+    match value {
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Int(value) => Some(value.to_string()),
+        Value::UInt(value) => Some(value.to_string()),
+        Value::Float(value) => Some(float(*value)),
+        Value::String(string) => Some(string_scalar(string)),
+        Value::Bytes(bytes) => Some(flow_sequence(
+            bytes.iter().map(u8::to_string),
+            bytes.len(),
+            config,
+        )),
+        Value::Array(values) => {
+            let flow = values
+                .iter()
+                .all(|value| !matches!(value, Value::Struct(_) | Value::Union { .. }));
+            if flow {
+                let elements = values
+                    .iter()
+                    .map(|value| inline(value, config).expect("Array element is not a scalar"));
+                Some(flow_sequence(elements, values.len(), config))
+            } else {
+                None
+            }
+        }
+        Value::Struct(fields) if fields.is_empty() => Some("{}".to_owned()),
+        Value::Struct(_) | Value::Union { .. } => None,
+    }
+}
+
+/// Renders a value in block style, returning lines that are not indented relative to the value
+fn block_lines(value: &Value, config: &YamlConfig) -> Vec<String> {
+    let mut lines = Vec::new();
+    match value {
+        Value::Struct(fields) => {
+            for (name, value) in fields {
+                mapping_entry(name, value, config, &mut lines);
+            }
+        }
+        Value::Union { variant, value } => mapping_entry(variant, value, config, &mut lines),
+        Value::Array(values) => {
+            let shown = shown_elements(values.len(), config);
+            for value in &values[..shown] {
+                match inline(value, config) {
+                    Some(inline) => lines.push(format!("- {}", inline)),
+                    None => {
+                        for (i, line) in block_lines(value, config).into_iter().enumerate() {
+                            let prefix = if i == 0 { "- " } else { "  " };
+                            lines.push(format!("{}{}", prefix, line));
+                        }
+                    }
+                }
+            }
+            if shown != values.len() {
+                lines.push(format!("# {}", omitted_comment(values.len() - shown)));
+            }
+        }
+        _ => lines.push(inline(value, config).expect("Value is not a scalar")),
+    }
+    lines
+}
+
+fn mapping_entry(name: &str, value: &Value, config: &YamlConfig, lines: &mut Vec<String>) {
+    match inline(value, config) {
+        Some(inline) => lines.push(format!("{}: {}", name, inline)),
+        None => {
+            lines.push(format!("{}:", name));
+            lines.extend(
+                block_lines(value, config)
+                    .into_iter()
+                    .map(|line| format!("  {}", line)),
+            );
+        }
+    }
+}
+
+fn flow_sequence<I>(elements: I, length: usize, config: &YamlConfig) -> String
+where
+    I: Iterator<Item = String>,
+{
+    let shown = shown_elements(length, config);
+    let elements: Vec<String> = elements.take(shown).collect();
+    let mut sequence = format!("[{}]", elements.join(", "));
+    if shown != length {
+        write!(sequence, "  # {}", omitted_comment(length - shown)).unwrap();
+    }
+    sequence
+}
+
+fn shown_elements(length: usize, config: &YamlConfig) -> usize {
+    config
+        .max_array_elements
+        .map(|max| max.min(length))
+        .unwrap_or(length)
+}
+
+fn omitted_comment(omitted: usize) -> String {
+    if omitted == 1 {
+        "1 more element".to_owned()
+    } else {
+        format!("{} more elements", omitted)
+    }
+}
+
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        ".nan".to_owned()
+    } else if value == f64::INFINITY {
+        ".inf".to_owned()
+    } else if value == f64::NEG_INFINITY {
+        "-.inf".to_owned()
+    } else {
+        // Debug formatting always includes a decimal point or exponent
+        format!("{:?}", value)
+    }
+}
+
+/// Renders a string as a plain scalar if that is unambiguous, or as a double-quoted scalar
+fn string_scalar(string: &str) -> String {
+    let plain = string
+        .chars()
+        .next()
+        .map(|first| first.is_alphabetic() || first == '_' || first == '/')
+        .unwrap_or(false)
+        && !string.ends_with(' ')
+        && string
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | '/' | ' '))
+        && !matches!(
+            string.to_ascii_lowercase().as_str(),
+            "true" | "false" | "null" | "yes" | "no" | "on" | "off" | "y" | "n"
+        );
+    if plain {
+        return string.to_owned();
+    }
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\0' => quoted.push_str("\\0"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,555 @@
+//!
+//! Reading YAML into JSON values
+//!
+//! This reads the parts of YAML that people usually write by hand and the output of
+//! [`to_yaml`](super::to_yaml):
+//!
+//! * Block mappings and block sequences, nested by indentation
+//! * Flow mappings and flow sequences, like `{a: 1, b: [1, 2]}` (so JSON also works)
+//! * Plain, single-quoted, and double-quoted scalars
+//! * Comments and a `---` line at the start of the document
+//!
+//! Anchors, aliases, tags, multi-line scalars, and multiple documents are not supported.
+//!
+
+use canadensis_dsdl_frontend::compiled::Message;
+use serde_json::{Map, Number};
+use thiserror::Error;
+
+use crate::json::{from_json, JsonError};
+use crate::Value;
+
+/// Errors that can occur when reading YAML
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum YamlError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+    #[error(transparent)]
+    Json(#[from] JsonError),
+}
+
+/// Parses a YAML document into a JSON value
+///
+/// Plain scalars that look like numbers, booleans, or null become those JSON values, and other
+/// scalars become strings. `.nan` becomes null, which [`from_json`] reads as NaN. An empty
+/// document is null.
+pub fn parse_yaml(text: &str) -> Result<serde_json::Value, YamlError> {
+    let lines = document_lines(text)?;
+    if lines.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+    let mut parser = BlockParser { lines, position: 0 };
+    let indent = parser.lines[0].indent;
+    let value = parser.node(indent)?;
+    match parser.lines.get(parser.position) {
+        Some(line) => Err(syntax(line.number, "Unexpected indentation")),
+        None => Ok(value),
+    }
+}
+
+/// Parses a YAML document and converts it into a value of a message type
+///
+/// This uses the same conversions as [`from_json`], so fields that are not present get their
+/// default values. An empty document is the same as an empty mapping.
+pub fn from_yaml(message: &Message, text: &str) -> Result<Value, YamlError> {
+    let json = match parse_yaml(text)? {
+        serde_json::Value::Null => serde_json::Value::Object(Map::new()),
+        json => json,
+    };
+    Ok(from_json(message, &json)?)
+}
+
+/// A line with its comment and indentation removed
+struct Line {
+    /// The line number, starting at 1
+    number: usize,
+    /// The number of spaces before the content
+    indent: usize,
+    content: String,
+}
+
+/// Splits a document into non-empty lines
+fn document_lines(text: &str) -> Result<Vec<Line>, YamlError> {
+    let mut lines = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let number = i + 1;
+        let content = strip_comment(line);
+        let trimmed = content.trim_start_matches(' ');
+        if trimmed.starts_with('\t') {
+            return Err(syntax(number, "Tabs can't be used for indentation"));
+        }
+        let trimmed = trimmed.trim_end();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed == "---" || trimmed == "..." {
+            if lines.is_empty() {
+                continue;
+            }
+            return Err(syntax(number, "Only one document is supported"));
+        }
+        lines.push(Line {
+            number,
+            indent: content.len() - content.trim_start_matches(' ').len(),
+            content: trimmed.to_owned(),
+        });
+    }
+    Ok(lines)
+}
+
+/// Removes a comment (`#` at the start or after whitespace, outside quotes) from a line
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '#' && previous.is_whitespace() => return &line[..i],
+            None if (c == '"' || c == '\'') && !is_plain_char(previous) => quote = Some(c),
+            None => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+/// Returns true if a character can be part of a plain scalar before a quote character
+fn is_plain_char(c: char) -> bool {
+    !(c.is_whitespace() || matches!(c, '[' | '{' | ',' | ':'))
+}
+
+/// Parses block collections, which are nested by indentation
+struct BlockParser {
+    lines: Vec<Line>,
+    position: usize,
+}
+
+impl BlockParser {
+    /// Parses the node that starts on the current line, which has the provided indentation
+    fn node(&mut self, indent: usize) -> Result<serde_json::Value, YamlError> {
+        let line = &self.lines[self.position];
+        if is_sequence_item(&line.content) {
+            self.sequence(indent)
+        } else if mapping_colon(&line.content).is_some() {
+            self.mapping(indent)
+        } else {
+            let number = line.number;
+            let content = line.content.clone();
+            self.position += 1;
+            self.scalar_or_flow(number, content)
+        }
+    }
+
+    /// Parses a block sequence
+    fn sequence(&mut self, indent: usize) -> Result<serde_json::Value, YamlError> {
+        let mut items = Vec::new();
+        while let Some(line) = self.lines.get_mut(self.position) {
+            if line.indent != indent || !is_sequence_item(&line.content) {
+                break;
+            }
+            let rest = line.content[1..].trim_start_matches(' ');
+            if rest.is_empty() {
+                self.position += 1;
+                items.push(self.nested(indent)?);
+            } else {
+                // Parse the rest of the line as if it were a line of its own, so a mapping
+                // can continue on the next lines with the same indentation as its first key
+                line.indent += line.content.len() - rest.len();
+                line.content = rest.to_owned();
+                let item_indent = line.indent;
+                items.push(self.node(item_indent)?);
+            }
+        }
+        Ok(serde_json::Value::Array(items))
+    }
+
+    /// Parses a block mapping
+    fn mapping(&mut self, indent: usize) -> Result<serde_json::Value, YamlError> {
+        let mut entries = Map::new();
+        while let Some(line) = self.lines.get(self.position) {
+            if line.indent != indent {
+                break;
+            }
+            let number = line.number;
+            let colon = mapping_colon(&line.content)
+                .ok_or_else(|| syntax(number, "Expected a mapping entry"))?;
+            let key = parse_key(number, line.content[..colon].trim_end())?;
+            let rest = line.content[colon + 1..].trim_start().to_owned();
+            self.position += 1;
+            let value = if rest.is_empty() {
+                match self.lines.get(self.position) {
+                    // A sequence can be a mapping value without more indentation
+                    Some(next) if next.indent == indent && is_sequence_item(&next.content) => {
+                        self.sequence(indent)?
+                    }
+                    _ => self.nested(indent)?,
+                }
+            } else {
+                self.scalar_or_flow(number, rest)?
+            };
+            if entries.insert(key.clone(), value).is_some() {
+                return Err(syntax(number, format!("Duplicate key {}", key)));
+            }
+        }
+        Ok(serde_json::Value::Object(entries))
+    }
+
+    /// Parses a node that is more indented than `indent`, or returns null if the next line is
+    /// not more indented
+    fn nested(&mut self, indent: usize) -> Result<serde_json::Value, YamlError> {
+        match self.lines.get(self.position) {
+            Some(next) if next.indent > indent => {
+                let next_indent = next.indent;
+                self.node(next_indent)
+            }
+            _ => Ok(serde_json::Value::Null),
+        }
+    }
+
+    /// Parses a scalar or a flow collection, which may continue on the following lines
+    fn scalar_or_flow(
+        &mut self,
+        number: usize,
+        mut content: String,
+    ) -> Result<serde_json::Value, YamlError> {
+        match content.chars().next() {
+            Some('[') | Some('{') => {
+                while !is_balanced(&content) {
+                    let next = self
+                        .lines
+                        .get(self.position)
+                        .ok_or_else(|| syntax(number, "Unclosed flow collection"))?;
+                    content.push(' ');
+                    content.push_str(&next.content);
+                    self.position += 1;
+                }
+            }
+            Some('"') | Some('\'') => {}
+            // A plain scalar in block context continues to the end of the line
+            _ => return resolve_plain(number, &content),
+        }
+        let mut parser = FlowParser {
+            chars: content.chars().collect(),
+            position: 0,
+            line: number,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position != parser.chars.len() {
+            return Err(syntax(number, "Unexpected characters after value"));
+        }
+        Ok(value)
+    }
+}
+
+/// Returns true if a line starts a block sequence item
+fn is_sequence_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+/// Returns the index of the `:` that separates a key from its value in a block mapping entry
+fn mapping_colon(content: &str) -> Option<usize> {
+    let mut chars = content.char_indices().peekable();
+    match chars.peek() {
+        Some((_, '[')) | Some((_, '{')) => return None,
+        Some(&(_, quote)) if quote == '"' || quote == '\'' => {
+            chars.next();
+            let mut escaped = false;
+            for (_, c) in chars.by_ref() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' if quote == '"' => escaped = true,
+                    _ if c == quote => break,
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+    while let Some((i, c)) = chars.next() {
+        if c == ':' && chars.peek().map(|&(_, next)| next == ' ').unwrap_or(true) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Returns true if all brackets and braces outside quotes are closed
+fn is_balanced(content: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote = None;
+    let mut escaped = false;
+    for c in content.chars() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    depth <= 0
+}
+
+/// Parses a mapping key, which is a quoted or plain scalar
+fn parse_key(number: usize, text: &str) -> Result<String, YamlError> {
+    if text.starts_with('"') || text.starts_with('\'') {
+        let mut parser = FlowParser {
+            chars: text.chars().collect(),
+            position: 0,
+            line: number,
+        };
+        let key = parser.quoted()?;
+        if parser.position == parser.chars.len() {
+            return Ok(key);
+        }
+        Err(syntax(number, "Unexpected characters after key"))
+    } else if text.is_empty() {
+        Err(syntax(number, "Empty key"))
+    } else {
+        Ok(text.to_owned())
+    }
+}
+
+/// Parses flow collections and scalars in one line of text
+struct FlowParser {
+    chars: Vec<char>,
+    position: usize,
+    /// The line number where the text starts
+    line: usize,
+}
+
+impl FlowParser {
+    fn value(&mut self) -> Result<serde_json::Value, YamlError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.sequence(),
+            Some('{') => self.mapping(),
+            Some('"') | Some('\'') => Ok(serde_json::Value::String(self.quoted()?)),
+            Some(_) => {
+                let text = self.plain(&[',', ']', '}']);
+                resolve_plain(self.line, &text)
+            }
+            None => Err(self.error("Expected a value")),
+        }
+    }
+
+    fn sequence(&mut self) -> Result<serde_json::Value, YamlError> {
+        self.position += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.position += 1;
+                return Ok(serde_json::Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(serde_json::Value::Array(items)),
+                _ => return Err(self.error("Expected , or ] in flow sequence")),
+            }
+        }
+    }
+
+    fn mapping(&mut self) -> Result<serde_json::Value, YamlError> {
+        self.position += 1;
+        let mut entries = Map::new();
+        loop {
+            self.skip_whitespace();
+            let key = match self.peek() {
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(serde_json::Value::Object(entries));
+                }
+                Some('"') | Some('\'') => self.quoted()?,
+                Some(_) => self.plain(&[':', ',', '}']),
+                None => return Err(self.error("Unclosed flow mapping")),
+            };
+            if key.is_empty() {
+                return Err(self.error("Empty key"));
+            }
+            self.skip_whitespace();
+            if self.next() != Some(':') {
+                return Err(self.error(format!("Expected : after key {}", key)));
+            }
+            self.skip_whitespace();
+            let value = match self.peek() {
+                Some(',') | Some('}') => serde_json::Value::Null,
+                _ => self.value()?,
+            };
+            if entries.insert(key.clone(), value).is_some() {
+                return Err(self.error(format!("Duplicate key {}", key)));
+            }
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(serde_json::Value::Object(entries)),
+                _ => return Err(self.error("Expected , or } in flow mapping")),
+            }
+        }
+    }
+
+    /// Reads a plain scalar up to one of the terminators or the end, and trims it
+    fn plain(&mut self, terminators: &[char]) -> String {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if terminators.contains(&c) {
+                break;
+            }
+            self.position += 1;
+        }
+        self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_owned()
+    }
+
+    /// Reads a single-quoted or double-quoted scalar
+    fn quoted(&mut self) -> Result<String, YamlError> {
+        let quote = self.next().expect("No quote");
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("Unclosed quoted string")),
+                // In single quotes, '' is one quote
+                Some('\'') if quote == '\'' && self.peek() == Some('\'') => {
+                    self.position += 1;
+                    string.push('\'');
+                }
+                Some(c) if c == quote => return Ok(string),
+                Some('\\') if quote == '"' => string.push(self.escape()?),
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    /// Reads the rest of an escape sequence in a double-quoted scalar
+    fn escape(&mut self) -> Result<char, YamlError> {
+        let digits = match self.next() {
+            Some('n') => return Ok('\n'),
+            Some('t') => return Ok('\t'),
+            Some('r') => return Ok('\r'),
+            Some('0') => return Ok('\0'),
+            Some('b') => return Ok('\u{8}'),
+            Some('f') => return Ok('\u{c}'),
+            Some('e') => return Ok('\u{1b}'),
+            Some(c @ '"') | Some(c @ '\\') | Some(c @ '/') | Some(c @ ' ') => return Ok(c),
+            Some('x') => 2,
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("Invalid escape sequence")),
+        };
+        let end = self.position + digits;
+        let code = self
+            .chars
+            .get(self.position..end)
+            .map(|hex| hex.iter().collect::<String>())
+            .and_then(|hex| u32::from_str_radix(&hex, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("Invalid escape sequence"))?;
+        self.position = end;
+        Ok(code)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map(char::is_whitespace).unwrap_or(false) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.position += 1;
+        }
+        c
+    }
+
+    fn error<S: Into<String>>(&self, message: S) -> YamlError {
+        syntax(self.line, message)
+    }
+}
+
+/// Converts a plain scalar into null, a boolean, a number, or a string
+fn resolve_plain(number: usize, text: &str) -> Result<serde_json::Value, YamlError> {
+    let value = match text {
+        "" | "~" | "null" | "Null" | "NULL" => serde_json::Value::Null,
+        "true" | "True" | "TRUE" => serde_json::Value::Bool(true),
+        "false" | "False" | "FALSE" => serde_json::Value::Bool(false),
+        ".nan" | ".NaN" | ".NAN" => serde_json::Value::Null,
+        ".inf" | ".Inf" | ".INF" | "+.inf" | "+.Inf" | "+.INF" | "-.inf" | "-.Inf" | "-.INF" => {
+            return Err(syntax(number, "Infinite values are not supported"));
+        }
+        _ => match integer(text) {
+            Some(value) => value,
+            None => match float(text) {
+                Some(value) => value,
+                None => serde_json::Value::String(text.to_owned()),
+            },
+        },
+    };
+    Ok(value)
+}
+
+/// Parses a decimal, hexadecimal (`0x`), octal (`0o`), or binary (`0b`) integer
+fn integer(text: &str) -> Option<serde_json::Value> {
+    let (negative, unsigned) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    let magnitude = u64::from_str_radix(digits, radix).ok()?;
+    if negative {
+        0i64.checked_sub_unsigned(magnitude)
+            .map(serde_json::Value::from)
+    } else {
+        Some(serde_json::Value::from(magnitude))
+    }
+}
+
+/// Parses a finite floating-point number
+fn float(text: &str) -> Option<serde_json::Value> {
+    let valid_chars = text
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'));
+    if !valid_chars || !text.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse::<f64>()
+        .ok()
+        .and_then(Number::from_f64)
+        .map(serde_json::Value::Number)
+}
+
+fn syntax<S: Into<String>>(line: usize, message: S) -> YamlError {
+    YamlError::Syntax {
+        line,
+        message: message.into(),
+    }
+}
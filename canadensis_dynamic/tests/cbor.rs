@@ -0,0 +1,168 @@
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dynamic;
+
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Message};
+use canadensis_dsdl_frontend::{Config, Package};
+use canadensis_dynamic::cbor::{from_cbor, to_cbor, CborError};
+use canadensis_dynamic::{deserialize, serialize, Value};
+
+fn with_message<F: FnOnce(&Message)>(operation: F) {
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "example.Choice.1.0".parse().unwrap(),
+            "@union\nuint16 number\nfloat32 real\n@sealed\n".into(),
+        )
+        .unwrap();
+    package
+        .add_string(
+            None,
+            "example.Record.1.0".parse().unwrap(),
+            "int8 level\nuint8[<=16] name\nutf8[<=16] text\nbyte[<=4] data\n\
+             example.Choice.1.0 choice\nfloat64 big\n@sealed\n"
+                .into(),
+        )
+        .unwrap();
+    let config = Config {
+        allow_utf8_and_byte: true,
+        ..Config::default()
+    };
+    let package = package.compile(&config).unwrap();
+    match &package
+        .get_by_key(&"example.Record.1.0".parse().unwrap())
+        .unwrap()
+        .kind
+    {
+        DsdlKind::Message(message) => operation(message),
+        DsdlKind::Service { .. } => unreachable!(),
+    }
+}
+
+#[test]
+fn cbor_encoding() {
+    // Examples from RFC 8949 appendix A
+    assert_eq!(
+        to_cbor(&Value::UInt(1_000_000)),
+        [0x1a, 0x00, 0x0f, 0x42, 0x40]
+    );
+    assert_eq!(to_cbor(&Value::Int(-1000)), [0x39, 0x03, 0xe7]);
+    assert_eq!(to_cbor(&Value::Float(1.5)), [0xf9, 0x3e, 0x00]);
+    assert_eq!(
+        to_cbor(&Value::Float(100000.0)),
+        [0xfa, 0x47, 0xc3, 0x50, 0x00]
+    );
+    assert_eq!(
+        to_cbor(&Value::Float(1.1)),
+        [0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]
+    );
+    assert_eq!(to_cbor(&Value::String("IETF".into())), b"\x64IETF");
+    assert_eq!(to_cbor(&Value::Bytes(vec![1, 2])), [0x42, 1, 2]);
+    assert_eq!(
+        to_cbor(&Value::Union {
+            variant: "a".into(),
+            value: Box::new(Value::Bool(true)),
+        }),
+        [0xa1, 0x61, b'a', 0xf5]
+    );
+}
+
+#[test]
+fn cbor_round_trip() {
+    with_message(|record| {
+        let value = Value::Struct(vec![
+            ("level".into(), Value::Int(-3)),
+            (
+                "name".into(),
+                Value::Array(vec![Value::UInt(104), Value::UInt(105)]),
+            ),
+            ("text".into(), Value::String("hello".into())),
+            ("data".into(), Value::Bytes(vec![1, 2])),
+            (
+                "choice".into(),
+                Value::Union {
+                    variant: "real".into(),
+                    value: Box::new(Value::Float(0.5)),
+                },
+            ),
+            ("big".into(), Value::Float(1e100)),
+        ]);
+        let cbor = to_cbor(&value);
+        assert_eq!(value, from_cbor(record, &cbor).unwrap());
+
+        let bytes = serialize(record, &value).unwrap();
+        assert_eq!(cbor, to_cbor(&deserialize(record, &bytes).unwrap()));
+    });
+}
+
+#[test]
+fn cbor_defaults_and_byte_strings() {
+    with_message(|record| {
+        // {"name": h'6869', "big": 2}
+        let cbor = b"\xa2\x64name\x42hi\x63big\x02";
+        let value = from_cbor(record, cbor).unwrap();
+        assert_eq!(
+            Value::Struct(vec![
+                ("level".into(), Value::Int(0)),
+                (
+                    "name".into(),
+                    Value::Array(vec![Value::UInt(104), Value::UInt(105)])
+                ),
+                ("text".into(), Value::String(String::new())),
+                ("data".into(), Value::Bytes(vec![])),
+                (
+                    "choice".into(),
+                    Value::Union {
+                        variant: "number".into(),
+                        value: Box::new(Value::UInt(0)),
+                    }
+                ),
+                ("big".into(), Value::Float(2.0)),
+            ]),
+            value
+        );
+    });
+}
+
+#[test]
+fn cbor_errors() {
+    with_message(|record| {
+        assert_eq!(
+            Err(CborError::UnknownField("size".into())),
+            from_cbor(record, b"\xa1\x64size\x03")
+        );
+        assert_eq!(
+            Err(CborError::UnknownVariant("text".into())),
+            from_cbor(record, b"\xa1\x66choice\xa1\x64text\x00")
+        );
+        assert_eq!(
+            Err(CborError::UnionEntries),
+            from_cbor(record, b"\xa1\x66choice\xa0")
+        );
+        assert!(matches!(
+            from_cbor(record, b"\xa1\x65level\xf5"),
+            Err(CborError::TypeMismatch(_))
+        ));
+        // Out of range for int8 is not an error here, but out of range for i64 is
+        assert!(matches!(
+            from_cbor(record, b"\xa1\x65level\x3b\xff\xff\xff\xff\xff\xff\xff\xff"),
+            Err(CborError::TypeMismatch(_))
+        ));
+        assert_eq!(
+            Err(CborError::UnexpectedEnd),
+            from_cbor(record, b"\xa1\x64name\x5a\xff\xff\xff\xff")
+        );
+        assert_eq!(
+            Err(CborError::TrailingBytes),
+            from_cbor(record, b"\xa0\x00")
+        );
+        assert_eq!(
+            Err(CborError::InvalidItem(0x9f)),
+            from_cbor(record, b"\x9f")
+        );
+        assert_eq!(
+            Err(CborError::NestingDepth),
+            from_cbor(record, &[0x81; 100_000])
+        );
+    });
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "json")]
+
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dynamic;
+extern crate serde_json;
+
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Message};
+use canadensis_dsdl_frontend::{Config, Package};
+use canadensis_dynamic::json::{from_json, to_json, JsonError};
+use canadensis_dynamic::{deserialize, serialize, Value};
+use serde_json::json;
+
+fn with_message<F: FnOnce(&Message)>(operation: F) {
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "example.Choice.1.0".parse().unwrap(),
+            "@union\nuint16 number\nfloat32 real\n@sealed\n".into(),
+        )
+        .unwrap();
+    package
+        .add_string(
+            None,
+            "example.Record.1.0".parse().unwrap(),
+            "int8 level\nuint8[<=16] name\nutf8[<=16] text\nbyte[<=4] data\n\
+             example.Choice.1.0 choice\nbool[2] flags\n@sealed\n"
+                .into(),
+        )
+        .unwrap();
+    let config = Config {
+        allow_utf8_and_byte: true,
+        ..Config::default()
+    };
+    let package = package.compile(&config).unwrap();
+    match &package
+        .get_by_key(&"example.Record.1.0".parse().unwrap())
+        .unwrap()
+        .kind
+    {
+        DsdlKind::Message(message) => operation(message),
+        DsdlKind::Service { .. } => unreachable!(),
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    with_message(|record| {
+        let json = json!({
+            "level": -3,
+            "name": [104, 105],
+            "text": "hello",
+            "data": [1, 2],
+            "choice": { "real": 0.5 },
+            "flags": [true, false],
+        });
+        let value = from_json(record, &json).unwrap();
+        assert_eq!(Some(&Value::String("hello".into())), value.field("text"));
+        assert_eq!(Some(&Value::Bytes(vec![1, 2])), value.field("data"));
+
+        let bytes = serialize(record, &value).unwrap();
+        assert_eq!(json, to_json(&deserialize(record, &bytes).unwrap()));
+    });
+}
+
+#[test]
+fn json_defaults_and_strings() {
+    with_message(|record| {
+        let value = from_json(record, &json!({ "name": "hi", "data": "ab" })).unwrap();
+        assert_eq!(
+            json!({
+                "level": 0,
+                "name": [104, 105],
+                "text": "",
+                "data": [97, 98],
+                "choice": { "number": 0 },
+                "flags": [false, false],
+            }),
+            to_json(&value)
+        );
+    });
+}
+
+#[test]
+fn json_errors() {
+    with_message(|record| {
+        assert_eq!(
+            Err(JsonError::UnknownField("size".into())),
+            from_json(record, &json!({ "size": 3 }))
+        );
+        assert_eq!(
+            Err(JsonError::UnknownVariant("text".into())),
+            from_json(record, &json!({ "choice": { "text": "a" } }))
+        );
+        assert!(matches!(
+            from_json(record, &json!({ "choice": { "number": 1, "real": 1.0 } })),
+            Err(JsonError::UnionEntries(_))
+        ));
+        assert!(matches!(
+            from_json(record, &json!({ "level": "high" })),
+            Err(JsonError::TypeMismatch { .. })
+        ));
+    });
+}
@@ -0,0 +1,122 @@
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dynamic;
+
+use canadensis_dsdl_frontend::{Config, Package};
+use canadensis_dynamic::registry::{PayloadKind, RegistryError, TypeRegistry};
+use canadensis_dynamic::Value;
+
+fn registry() -> TypeRegistry {
+    let mut package = Package::new();
+    for (port_id, key, dsdl) in [
+        (Some(100), "example.Status.1.0", "uint8 mode\n@sealed\n"),
+        (
+            Some(100),
+            "example.Status.1.1",
+            "uint8 mode\nuint8 health\n@sealed\n",
+        ),
+        (None, "example.Status.2.0", "uint16 mode\n@sealed\n"),
+        (
+            Some(200),
+            "example.GetMode.1.0",
+            "uint8 index\n@sealed\n---\nbool valid\nuint8 mode\n@sealed\n",
+        ),
+    ] {
+        package
+            .add_string(port_id, key.parse().unwrap(), dsdl.to_owned())
+            .unwrap();
+    }
+    TypeRegistry::new(package.compile(&Config::default()).unwrap())
+}
+
+#[test]
+fn find_by_name() {
+    let registry = registry();
+    let find = |name| registry.find(name).map(ToString::to_string);
+    assert_eq!(
+        find("example.Status.1.0").as_deref(),
+        Some("example.Status.1.0")
+    );
+    assert_eq!(
+        find("example.Status.1").as_deref(),
+        Some("example.Status.1.1")
+    );
+    assert_eq!(
+        find("example.Status").as_deref(),
+        Some("example.Status.2.0")
+    );
+    assert_eq!(find("example.Status.1.2"), None);
+    assert_eq!(find("example.Missing"), None);
+    assert_eq!(find("example.status"), None);
+}
+
+#[test]
+fn find_by_port_id() {
+    let registry = registry();
+    assert_eq!(
+        registry
+            .subject_type(100)
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("example.Status.1.1")
+    );
+    assert_eq!(
+        registry
+            .service_type(200)
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("example.GetMode.1.0")
+    );
+    assert!(registry.subject_type(200).is_none());
+    assert!(registry.service_type(100).is_none());
+}
+
+#[test]
+fn round_trip_service() {
+    let registry = registry();
+    let key = registry.find("example.GetMode").unwrap().clone();
+    let request = Value::Struct(vec![("index".to_owned(), Value::UInt(7))]);
+    let bytes = registry
+        .serialize(&key, PayloadKind::Request, &request)
+        .unwrap();
+    assert_eq!(bytes, [7]);
+    assert_eq!(
+        registry
+            .deserialize(&key, PayloadKind::Request, &bytes)
+            .unwrap(),
+        request
+    );
+
+    let response = registry
+        .deserialize(&key, PayloadKind::Response, &[0x07, 0x00])
+        .unwrap();
+    assert_eq!(response.field("valid"), Some(&Value::Bool(true)));
+    assert_eq!(response.field("mode"), Some(&Value::UInt(3)));
+}
+
+#[test]
+fn wrong_payload_kind() {
+    let registry = registry();
+    let message = registry.find("example.Status").unwrap();
+    let service = registry.find("example.GetMode").unwrap();
+    assert!(matches!(
+        registry.message(message, PayloadKind::Request),
+        Err(RegistryError::NotService(_))
+    ));
+    assert!(matches!(
+        registry.message(service, PayloadKind::Message),
+        Err(RegistryError::NotMessage(_))
+    ));
+}
+
+#[test]
+fn load_directory() {
+    let registry = TypeRegistry::load(
+        [concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../canadensis_dsdl_frontend/tests/simple_dsdl_no_byte_utf8"
+        )],
+        &Config::default(),
+    )
+    .unwrap();
+    assert!(registry.find("canadensis.Small").is_some());
+}
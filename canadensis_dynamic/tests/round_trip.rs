@@ -0,0 +1,186 @@
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dynamic;
+
+use canadensis_dsdl_frontend::compiled::package::CompiledPackage;
+use canadensis_dsdl_frontend::compiled::{DsdlKind, Message};
+use canadensis_dsdl_frontend::{Config, Package};
+use canadensis_dynamic::{deserialize, serialize, DeserializeError, SerializeError, Value};
+
+fn compile(types: &[(&str, &str)]) -> CompiledPackage {
+    let mut package = Package::new();
+    for (key, dsdl) in types {
+        package
+            .add_string(None, key.parse().unwrap(), (*dsdl).to_owned())
+            .unwrap();
+    }
+    let config = Config {
+        allow_utf8_and_byte: true,
+        ..Config::default()
+    };
+    package.compile(&config).unwrap()
+}
+
+fn message<'p>(package: &'p CompiledPackage, key: &str) -> &'p Message {
+    match &package.get_by_key(&key.parse().unwrap()).unwrap().kind {
+        DsdlKind::Message(message) => message,
+        DsdlKind::Service { .. } => panic!("{} is a service", key),
+    }
+}
+
+fn fields(fields: &[(&str, Value)]) -> Value {
+    Value::Struct(
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect(),
+    )
+}
+
+#[test]
+fn round_trip_all_kinds() {
+    let package = compile(&[
+        ("example.Inner.1.0", "uint8 a\n@extent 64\n"),
+        (
+            "example.Choice.1.0",
+            "@union\nuint16 number\nbool[<=3] flags\n@sealed\n",
+        ),
+        (
+            "example.Outer.1.0",
+            "bool flag\nint5 small\nvoid2\nfloat16 half\nexample.Inner.1.0 inner\n\
+             uint8[<=4] numbers\nbyte[2] bytes\nutf8[<=8] text\nexample.Choice.1.0[2] choices\n\
+             float64 big\n@sealed\n",
+        ),
+    ]);
+    let outer = message(&package, "example.Outer.1.0");
+    let value = fields(&[
+        ("flag", Value::Bool(true)),
+        ("small", Value::Int(-11)),
+        ("half", Value::Float(-2.5)),
+        ("inner", fields(&[("a", Value::UInt(200))])),
+        (
+            "numbers",
+            Value::Array(vec![Value::UInt(1), Value::UInt(2), Value::UInt(3)]),
+        ),
+        ("bytes", Value::Bytes(vec![0xca, 0xfe])),
+        ("text", Value::String("héllo".into())),
+        (
+            "choices",
+            Value::Array(vec![
+                Value::Union {
+                    variant: "number".into(),
+                    value: Box::new(Value::UInt(0xbeef)),
+                },
+                Value::Union {
+                    variant: "flags".into(),
+                    value: Box::new(Value::Array(vec![Value::Bool(false), Value::Bool(true)])),
+                },
+            ]),
+        ),
+        ("big", Value::Float(1e100)),
+    ]);
+
+    let bytes = serialize(outer, &value).unwrap();
+    assert_eq!(value, deserialize(outer, &bytes).unwrap());
+}
+
+#[test]
+fn saturation_and_truncation() {
+    let package = compile(&[(
+        "example.Numbers.1.0",
+        "int4 a\nsaturated uint4 b\ntruncated uint8 c\nsaturated float16 d\ntruncated float16 e\n\
+         @sealed\n",
+    )]);
+    let numbers = message(&package, "example.Numbers.1.0");
+    let value = fields(&[
+        ("a", Value::Int(-20)),
+        ("b", Value::UInt(100)),
+        ("c", Value::UInt(0x1ff)),
+        ("d", Value::Float(1e6)),
+        ("e", Value::Float(1e6)),
+    ]);
+
+    let bytes = serialize(numbers, &value).unwrap();
+    assert_eq!(bytes, [0xf8, 0xff, 0xff, 0x7b, 0x00, 0x7c]);
+    assert_eq!(
+        fields(&[
+            ("a", Value::Int(-8)),
+            ("b", Value::UInt(15)),
+            ("c", Value::UInt(0xff)),
+            ("d", Value::Float(65504.0)),
+            ("e", Value::Float(f64::INFINITY)),
+        ]),
+        deserialize(numbers, &bytes).unwrap()
+    );
+}
+
+#[test]
+fn delimited_header() {
+    let package = compile(&[
+        ("example.Inner.1.0", "uint8 a\n@extent 64\n"),
+        ("example.Outer.1.0", "example.Inner.1.0 inner\n@sealed\n"),
+    ]);
+    let outer = message(&package, "example.Outer.1.0");
+    let value = fields(&[("inner", fields(&[("a", Value::UInt(5))]))]);
+    assert_eq!(serialize(outer, &value).unwrap(), [1, 0, 0, 0, 5]);
+    // A longer version of the inner type gets truncated
+    assert_eq!(value, deserialize(outer, &[2, 0, 0, 0, 5, 9]).unwrap());
+    assert!(matches!(
+        deserialize(outer, &[6, 0, 0, 0, 5, 9]),
+        Err(DeserializeError::DelimitedLength)
+    ));
+}
+
+#[test]
+fn errors() {
+    let package = compile(&[
+        ("example.Values.1.0", "uint8[<=2] values\n@sealed\n"),
+        (
+            "example.Either.1.0",
+            "@union\nuint8 a\nutf8[<=2] b\n@sealed\n",
+        ),
+    ]);
+    let cyphal_struct = message(&package, "example.Values.1.0");
+    let union = message(&package, "example.Either.1.0");
+
+    assert_eq!(
+        Err(SerializeError::MissingField("values".into())),
+        serialize(cyphal_struct, &fields(&[]))
+    );
+    assert_eq!(
+        Err(SerializeError::ArrayTooLong {
+            length: 3,
+            max_length: 2
+        }),
+        serialize(
+            cyphal_struct,
+            &fields(&[("values", Value::Bytes(vec![1, 2, 3]))])
+        )
+    );
+    assert!(matches!(
+        serialize(cyphal_struct, &fields(&[("values", Value::Bool(true))])),
+        Err(SerializeError::TypeMismatch { .. })
+    ));
+    assert_eq!(
+        Err(SerializeError::UnknownVariant("c".into())),
+        serialize(
+            union,
+            &Value::Union {
+                variant: "c".into(),
+                value: Box::new(Value::UInt(0)),
+            }
+        )
+    );
+
+    assert!(matches!(
+        deserialize(cyphal_struct, &[3, 1, 2, 3]),
+        Err(DeserializeError::ArrayLength)
+    ));
+    assert!(matches!(
+        deserialize(union, &[2]),
+        Err(DeserializeError::UnionTag)
+    ));
+    assert!(matches!(
+        deserialize(union, &[1, 1, 0xff]),
+        Err(DeserializeError::Utf8)
+    ));
+}
@@ -0,0 +1,277 @@
+extern crate canadensis_dsdl_frontend;
+extern crate canadensis_dynamic;
+
+use canadensis_dsdl_frontend::compiled::DsdlKind;
+use canadensis_dsdl_frontend::{Config, Package};
+use canadensis_dynamic::yaml::{to_yaml, transfer_to_yaml, YamlConfig};
+use canadensis_dynamic::{serialize, Value};
+
+fn fields(fields: &[(&str, Value)]) -> Value {
+    Value::Struct(
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect(),
+    )
+}
+
+#[test]
+fn yaml_nested() {
+    let value = fields(&[
+        ("flag", Value::Bool(true)),
+        ("level", Value::Int(-3)),
+        ("ratio", Value::Float(0.5)),
+        ("invalid", Value::Float(f64::NAN)),
+        ("name", Value::String("org.example.node".into())),
+        ("text", Value::String("two\nlines: \"quoted\"".into())),
+        ("keyword", Value::String("true".into())),
+        ("data", Value::Bytes(vec![1, 2])),
+        ("empty", Value::Array(vec![])),
+        ("nothing", fields(&[])),
+        ("inner", fields(&[("a", Value::UInt(1))])),
+        (
+            "choice",
+            Value::Union {
+                variant: "natural16".into(),
+                value: Box::new(fields(&[(
+                    "value",
+                    Value::Array(vec![Value::UInt(1), Value::UInt(2)]),
+                )])),
+            },
+        ),
+        (
+            "items",
+            Value::Array(vec![
+                fields(&[("a", Value::UInt(1)), ("b", Value::UInt(2))]),
+                fields(&[("a", Value::UInt(3)), ("b", Value::UInt(4))]),
+            ]),
+        ),
+    ]);
+    let expected = r#"flag: true
+level: -3
+ratio: 0.5
+invalid: .nan
+name: org.example.node
+text: "two\nlines: \"quoted\""
+keyword: "true"
+data: [1, 2]
+empty: []
+nothing: {}
+inner:
+  a: 1
+choice:
+  natural16:
+    value: [1, 2]
+items:
+  - a: 1
+    b: 2
+  - a: 3
+    b: 4"#;
+    assert_eq!(expected, to_yaml(&value, &YamlConfig::default()));
+}
+
+#[test]
+fn yaml_truncation() {
+    let config = YamlConfig {
+        max_array_elements: Some(2),
+    };
+    let value = fields(&[
+        ("data", Value::Bytes(vec![1, 2, 3])),
+        (
+            "values",
+            Value::Array(vec![
+                Value::Int(1),
+                Value::Int(2),
+                Value::Int(3),
+                Value::Int(4),
+            ]),
+        ),
+        ("short", Value::Array(vec![Value::Int(1)])),
+        (
+            "items",
+            Value::Array(vec![
+                fields(&[("a", Value::UInt(1))]),
+                fields(&[("a", Value::UInt(2))]),
+                fields(&[("a", Value::UInt(3))]),
+            ]),
+        ),
+    ]);
+    let expected = "data: [1, 2]  # 1 more element
+values: [1, 2]  # 2 more elements
+short: [1]
+items:
+  - a: 1
+  - a: 2
+  # 1 more element";
+    assert_eq!(expected, to_yaml(&value, &config));
+}
+
+#[test]
+fn yaml_transfer() {
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "example.Heartbeat.1.0".parse().unwrap(),
+            "uint32 uptime\nuint8 mode\n@sealed\n".into(),
+        )
+        .unwrap();
+    let package = package.compile(&Config::default()).unwrap();
+    let message = match &package
+        .get_by_key(&"example.Heartbeat.1.0".parse().unwrap())
+        .unwrap()
+        .kind
+    {
+        DsdlKind::Message(message) => message,
+        DsdlKind::Service { .. } => unreachable!(),
+    };
+    let payload = serialize(
+        message,
+        &fields(&[("uptime", Value::UInt(123)), ("mode", Value::UInt(2))]),
+    )
+    .unwrap();
+    assert_eq!(
+        "---\n7509:\n  uptime: 123\n  mode: 2",
+        transfer_to_yaml(7509, message, &payload, &YamlConfig::default()).unwrap()
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn yaml_parse() {
+    use canadensis_dynamic::yaml::{parse_yaml, YamlError};
+    extern crate serde_json;
+    use serde_json::json;
+
+    let text = r##"---
+# Settings
+name: org.example.node  # Not a comment: "#"
+text: "two\nlines: \"quoted\""
+quoted: 'it''s'
+keyword: "true"
+level: -3
+mask: 0xff
+ratio: 0.5
+invalid: .nan
+nothing: ~
+flags: [true, false]
+inner:
+  a: 1
+  empty: {}
+items:
+  - a: 1
+    b: 2
+  - {a: 3, b: 4}
+  -
+    - 5
+list:
+- 6
+flow: {values: [1,
+  2, 3], "key with spaces": x}
+"##;
+    assert_eq!(
+        json!({
+            "name": "org.example.node",
+            "text": "two\nlines: \"quoted\"",
+            "quoted": "it's",
+            "keyword": "true",
+            "level": -3,
+            "mask": 255,
+            "ratio": 0.5,
+            "invalid": null,
+            "nothing": null,
+            "flags": [true, false],
+            "inner": { "a": 1, "empty": {} },
+            "items": [{ "a": 1, "b": 2 }, { "a": 3, "b": 4 }, [5]],
+            "list": [6],
+            "flow": { "values": [1, 2, 3], "key with spaces": "x" },
+        }),
+        parse_yaml(text).unwrap()
+    );
+    assert_eq!(json!([1, "a"]), parse_yaml(r#"[1, "a"]"#).unwrap());
+    assert_eq!(json!(null), parse_yaml("# Nothing\n").unwrap());
+
+    let error_line = |text| match parse_yaml(text) {
+        Err(YamlError::Syntax { line, .. }) => line,
+        other => panic!("Expected a syntax error, got {:?}", other),
+    };
+    assert_eq!(2, error_line("a: 1\na: 2\n"));
+    assert_eq!(3, error_line("a:\n  b: 1\n   c: 2\n"));
+    assert_eq!(1, error_line("a: [1, 2\n"));
+    assert_eq!(1, error_line("a: 'text\n"));
+    assert_eq!(2, error_line("a: 1\n- 2\n"));
+    assert_eq!(2, error_line("a: 1\n---\nb: 2\n"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn yaml_round_trip() {
+    use canadensis_dynamic::yaml::from_yaml;
+
+    let mut package = Package::new();
+    package
+        .add_string(
+            None,
+            "example.Choice.1.0".parse().unwrap(),
+            "@union\nuint16 number\nfloat32 real\n@sealed\n".into(),
+        )
+        .unwrap();
+    package
+        .add_string(
+            None,
+            "example.Record.1.0".parse().unwrap(),
+            "int8 level\nuint8[<=16] name\nexample.Choice.1.0[<=2] choices\nfloat64 ratio\n\
+             @sealed\n"
+                .into(),
+        )
+        .unwrap();
+    let package = package.compile(&Config::default()).unwrap();
+    let message = match &package
+        .get_by_key(&"example.Record.1.0".parse().unwrap())
+        .unwrap()
+        .kind
+    {
+        DsdlKind::Message(message) => message,
+        DsdlKind::Service { .. } => unreachable!(),
+    };
+    let value = fields(&[
+        ("level", Value::Int(-2)),
+        (
+            "name",
+            Value::Array(vec![Value::UInt(104), Value::UInt(105)]),
+        ),
+        (
+            "choices",
+            Value::Array(vec![
+                Value::Union {
+                    variant: "number".into(),
+                    value: Box::new(Value::UInt(7)),
+                },
+                Value::Union {
+                    variant: "real".into(),
+                    value: Box::new(Value::Float(0.25)),
+                },
+            ]),
+        ),
+        ("ratio", Value::Float(f64::NAN)),
+    ]);
+    let text = to_yaml(&value, &YamlConfig::default());
+    let parsed = from_yaml(message, &text).unwrap();
+    // NaN is not equal to itself, so compare the serialized forms
+    assert_eq!(
+        serialize(message, &value).unwrap(),
+        serialize(message, &parsed).unwrap()
+    );
+
+    // Fields that are left out get default values
+    assert_eq!(
+        serialize(message, &from_yaml(message, "name: hi").unwrap()).unwrap(),
+        serialize(
+            message,
+            &from_yaml(message, "{level: 0, name: [104, 105]}").unwrap()
+        )
+        .unwrap()
+    );
+    assert!(from_yaml(message, "").is_ok());
+    assert!(from_yaml(message, "unknown: 1").is_err());
+}
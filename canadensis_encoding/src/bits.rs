@@ -80,7 +80,8 @@ impl<const BYTES: usize> BitArray<BYTES> {
     /// before this function is called.
     pub fn deserialize_in_place(&mut self, cursor: &mut ReadCursor<'_>) {
         if self.bit_length % 8 == 0 && cursor.is_aligned_to_8_bits() {
-            self.bytes.fill_with(|| cursor.read_aligned_u8());
+            // Read only the bytes that contain bits in this array, not the whole capacity
+            self.bytes[..self.bit_length / 8].fill_with(|| cursor.read_aligned_u8());
         } else {
             for i in 0..self.bit_length {
                 self.set(i, cursor.read_bool());
@@ -248,3 +249,62 @@ fn compare<const LBYTES: usize, const RBYTES: usize>(
         Ordering::Equal
     })
 }
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::BitArray;
+    use core::fmt;
+    use serde::de::{Error, SeqAccess, Visitor};
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// A bit array is represented as a sequence of booleans
+    impl<const BYTES: usize> Serialize for BitArray<BYTES> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.len()))?;
+            for bit in self {
+                seq.serialize_element(&bit)?;
+            }
+            seq.end()
+        }
+    }
+
+    impl<'de, const BYTES: usize> Deserialize<'de> for BitArray<BYTES> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(BitArrayVisitor)
+        }
+    }
+
+    struct BitArrayVisitor<const BYTES: usize>;
+
+    impl<'de, const BYTES: usize> Visitor<'de> for BitArrayVisitor<BYTES> {
+        type Value = BitArray<BYTES>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a sequence of at most {} booleans", BYTES * 8)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut array = BitArray::<BYTES>::new(BYTES * 8);
+            let mut length = 0;
+            while let Some(bit) = seq.next_element()? {
+                if length == BYTES * 8 {
+                    return Err(A::Error::invalid_length(length + 1, &self));
+                }
+                array.set(length, bit);
+                length += 1;
+            }
+            array.bit_length = length;
+            Ok(array)
+        }
+    }
+}
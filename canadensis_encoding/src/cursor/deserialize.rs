@@ -6,13 +6,14 @@ use core::cmp;
 
 use half::f16;
 
-use crate::{Deserialize, DeserializeError};
+use crate::float16::Float16Backend;
+use crate::{Deserialize, DeserializeBorrowed, DeserializeError};
 
 /// A cursor over a byte slice for easy deserializing of Cyphal data types
 ///
 /// Functions that read values will return zero when reading beyond the end of the bytes,
 /// in accordance with the implicit zero extension rule (specification section 3.7.1.5)
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReadCursor<'b> {
     /// The bytes available to read from
     ///
@@ -83,6 +84,9 @@ impl<'b> ReadCursor<'b> {
     /// Reads an x-bit unsigned integer (x must be in the range 1..=16)
     fn read_up_to_u16(&mut self, bits: u8) -> u16 {
         debug_assert!(bits <= 16);
+        if self.bit_index == 0 && bits & 7 == 0 {
+            return self.read_aligned_le(usize::from(bits / 8)) as u16;
+        }
         let mut shift_bits = 0;
         // Read whole bytes, least significant first
         let mut value = 0;
@@ -98,6 +102,9 @@ impl<'b> ReadCursor<'b> {
     /// Reads an x-bit unsigned integer (x must be in the range 1..=32)
     fn read_up_to_u32(&mut self, bits: u8) -> u32 {
         debug_assert!(bits <= 32);
+        if self.bit_index == 0 && bits & 7 == 0 {
+            return self.read_aligned_le(usize::from(bits / 8)) as u32;
+        }
         let mut shift_bits = 0;
         // Read whole bytes, least significant first
         let mut value = 0;
@@ -113,6 +120,9 @@ impl<'b> ReadCursor<'b> {
     /// Reads an x-bit unsigned integer (x must be in the range 1..=64)
     fn read_up_to_u64(&mut self, bits: u8) -> u64 {
         debug_assert!(bits <= 64);
+        if self.bit_index == 0 && bits & 7 == 0 {
+            return self.read_aligned_le(usize::from(bits / 8));
+        }
         let mut shift_bits = 0;
         // Read whole bytes, least significant first
         let mut value = 0;
@@ -133,7 +143,7 @@ impl<'b> ReadCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn read_aligned_u8(&mut self) -> u8 {
-        assert!(self.is_aligned_to_8_bits());
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
         let value = self.read_current();
         self.advance_bytes(1);
         value
@@ -145,10 +155,8 @@ impl<'b> ReadCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn read_aligned_u16(&mut self) -> u16 {
-        // Least significant byte first
-        let lsb = self.read_aligned_u8();
-        let msb = self.read_aligned_u8();
-        (u16::from(msb) << 8) | u16::from(lsb)
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.read_aligned_le(2) as u16
     }
 
     /// Reads a 32-bit integer
@@ -157,10 +165,8 @@ impl<'b> ReadCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn read_aligned_u32(&mut self) -> u32 {
-        // Least significant byte first
-        let lsbs = self.read_aligned_u16();
-        let msbs = self.read_aligned_u16();
-        (u32::from(msbs) << 16) | u32::from(lsbs)
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.read_aligned_le(4) as u32
     }
 
     /// Reads a 64-bit integer
@@ -169,10 +175,21 @@ impl<'b> ReadCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn read_aligned_u64(&mut self) -> u64 {
-        // Least significant byte first
-        let lsbs = self.read_aligned_u32();
-        let msbs = self.read_aligned_u32();
-        (u64::from(msbs) << 32) | u64::from(lsbs)
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.read_aligned_le(8)
+    }
+
+    /// Reads a little-endian integer made of `length` whole bytes (`length` must be in the range
+    /// 0..=8), starting at a byte boundary
+    ///
+    /// Any bytes past the end are read as zero.
+    fn read_aligned_le(&mut self, length: usize) -> u64 {
+        debug_assert!(self.bit_index == 0 && length <= 8);
+        let mut value = [0u8; 8];
+        let available = cmp::min(length, self.bytes.len());
+        value[..available].copy_from_slice(&self.bytes[..available]);
+        self.advance_bytes(length);
+        u64::from_le_bytes(value)
     }
 
     /// Returns the value of the current byte being read, or 0 if the cursor is past the end
@@ -207,11 +224,81 @@ impl<'b> ReadCursor<'b> {
 
     /// Skips up to 7 bits so that this cursor is aligned to 8 bits (one byte)
     pub fn align_to_8_bits(&mut self) {
+        self.align_to_byte();
+    }
+
+    /// Skips up to 7 bits so that this cursor is aligned to a byte boundary
+    ///
+    /// This does nothing if the cursor is already at a byte boundary.
+    pub fn align_to_byte(&mut self) {
         if self.bit_index != 0 {
             self.advance_bits(8 - usize::from(self.bit_index))
         }
     }
 
+    /// Skips some number of bits
+    ///
+    /// Skipping past the end of the bytes is allowed, and leaves the cursor at the end.
+    pub fn skip_bits(&mut self, bits: usize) {
+        self.advance_bits(bits);
+    }
+
+    /// Returns the number of bits that remain to be read before the end of the bytes
+    ///
+    /// Reading past the end is still allowed, and produces zeros.
+    pub fn remaining_bits(&self) -> usize {
+        (self.bytes.len() * 8).saturating_sub(usize::from(self.bit_index))
+    }
+
+    /// Reads an x-bit unsigned integer (x must be in the range 0..=64)
+    ///
+    /// This is useful when the number of bits is not known at compile time.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `bits` is greater than 64.
+    pub fn read_bits(&mut self, bits: u8) -> u64 {
+        assert!(bits <= 64);
+        self.read_up_to_u64(bits)
+    }
+
+    /// Reads an x-bit unsigned integer (x must be in the range 0..=64) without advancing
+    /// the cursor
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `bits` is greater than 64.
+    pub fn peek_bits(&self, bits: u8) -> u64 {
+        assert!(bits <= 64);
+        self.clone().read_up_to_u64(bits)
+    }
+
+    /// Reads an 8-bit integer without advancing the cursor
+    #[inline]
+    pub fn peek_u8(&self) -> u8 {
+        self.peek_bits(8) as u8
+    }
+
+    /// Reads a 16-bit integer without advancing the cursor
+    #[inline]
+    pub fn peek_u16(&self) -> u16 {
+        self.peek_bits(16) as u16
+    }
+
+    /// Reads a 32-bit integer without advancing the cursor
+    ///
+    /// This can be used to check the delimiter header of a composite before reading it.
+    #[inline]
+    pub fn peek_u32(&self) -> u32 {
+        self.peek_bits(32) as u32
+    }
+
+    /// Reads a 64-bit integer without advancing the cursor
+    #[inline]
+    pub fn peek_u64(&self) -> u64 {
+        self.peek_bits(64)
+    }
+
     /// Returns true if this cursor is aligned to a multiple of 8 bits
     pub fn is_aligned_to_8_bits(&self) -> bool {
         self.bit_index == 0
@@ -233,6 +320,13 @@ impl<'b> ReadCursor<'b> {
         f16::from_bits(self.read_aligned_u16())
     }
 
+    /// Reads a 16-bit floating-point value and converts it into a 32-bit value using the
+    /// backend `B`
+    #[inline]
+    pub fn read_f16_as_f32<B: Float16Backend>(&mut self) -> f32 {
+        B::to_f32(self.read_f16())
+    }
+
     /// Reads a 32-bit floating-point value
     #[inline]
     pub fn read_f32(&mut self) -> f32 {
@@ -267,15 +361,31 @@ impl<'b> ReadCursor<'b> {
 
     /// Reads a byte array
     pub fn read_bytes(&mut self, bytes: &mut [u8]) {
-        for byte in bytes {
-            *byte = self.read_u8();
+        if self.bit_index == 0 {
+            // Copy the available bytes and zero-extend the rest
+            let available = cmp::min(bytes.len(), self.bytes.len());
+            let (copied, extended) = bytes.split_at_mut(available);
+            copied.copy_from_slice(&self.bytes[..available]);
+            extended.fill(0);
+            self.advance_bytes(available);
+        } else {
+            // Each byte is split across two bytes of the input. Bytes past the end are zero.
+            let bit_index = self.bit_index;
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                let current = self.bytes.get(i).copied().unwrap_or(0);
+                let next = self.bytes.get(i + 1).copied().unwrap_or(0);
+                *byte = (current >> bit_index) | (next << (8 - bit_index));
+            }
+            self.advance_bytes(bytes.len());
         }
     }
 
     /// Reads a composite object
     ///
-    /// This function returns an error if T is delimited and the delimiter header has an
-    /// invalid length.
+    /// If T is delimited, this function reads the delimiter header and then reads T from the
+    /// following bytes. Any bytes that T does not use (for example, fields that a newer version of
+    /// T added) are skipped. This function returns an error if the delimiter header has a length
+    /// larger than the extent of T or larger than the number of remaining bytes.
     ///
     /// It also return an error if T's deserialize implementation encounters an error.
     pub fn read_composite<T>(&mut self) -> Result<T, DeserializeError>
@@ -283,10 +393,13 @@ impl<'b> ReadCursor<'b> {
         T: Deserialize,
     {
         self.align_to_8_bits();
-        let status = if T::EXTENT_BYTES.is_some() {
+        let status = if let Some(extent_bytes) = T::EXTENT_BYTES {
             // This is a delimited type. Read the header and fork to read the object
             let composite_length_bytes = self.read_aligned_u32() as usize;
-            if composite_length_bytes > self.bytes.len() {
+            // No version of T can be larger than its extent
+            if composite_length_bytes > self.bytes.len()
+                || composite_length_bytes > extent_bytes as usize
+            {
                 Err(DeserializeError::DelimitedLength)
             } else {
                 let mut forked = self.fork(composite_length_bytes);
@@ -300,6 +413,72 @@ impl<'b> ReadCursor<'b> {
         status
     }
 
+    /// Reads a composite object that may borrow from the bytes that this cursor reads
+    ///
+    /// This function handles delimiter headers in the same way as
+    /// [`read_composite()`](#method.read_composite).
+    pub fn read_composite_borrowed<T>(&mut self) -> Result<T, DeserializeError>
+    where
+        T: DeserializeBorrowed<'b>,
+    {
+        self.align_to_8_bits();
+        let status = if let Some(extent_bytes) = T::EXTENT_BYTES {
+            // This is a delimited type. Read the header and fork to read the object
+            let composite_length_bytes = self.read_aligned_u32() as usize;
+            // No version of T can be larger than its extent
+            if composite_length_bytes > self.bytes.len()
+                || composite_length_bytes > extent_bytes as usize
+            {
+                Err(DeserializeError::DelimitedLength)
+            } else {
+                let mut forked = self.fork(composite_length_bytes);
+                T::deserialize_borrowed(&mut forked)
+            }
+        } else {
+            // Sealed type, read directly
+            T::deserialize_borrowed(self)
+        };
+        self.align_to_8_bits();
+        status
+    }
+
+    /// Returns a slice of the next `length` bytes and advances this cursor past them
+    ///
+    /// Implicit zero extension can't be applied to a borrowed slice, so this function returns
+    /// a [`DeserializeError::ArrayLength`] error if fewer than `length` bytes remain.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the cursor is not aligned to a multiple of 8 bits.
+    pub fn read_aligned_borrowed_bytes(
+        &mut self,
+        length: usize,
+    ) -> Result<&'b [u8], DeserializeError> {
+        assert_eq!(self.bit_index, 0, "Not aligned to a byte");
+        if length > self.bytes.len() {
+            return Err(DeserializeError::ArrayLength);
+        }
+        let (borrowed, remaining) = self.bytes.split_at(length);
+        self.bytes = remaining;
+        Ok(borrowed)
+    }
+
+    /// Returns a string slice of the next `length` bytes and advances this cursor past them
+    ///
+    /// This function returns a [`DeserializeError::Utf8`] error if the bytes are not valid
+    /// UTF-8, and a [`DeserializeError::ArrayLength`] error if fewer than `length` bytes remain.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the cursor is not aligned to a multiple of 8 bits.
+    pub fn read_aligned_borrowed_str(
+        &mut self,
+        length: usize,
+    ) -> Result<&'b str, DeserializeError> {
+        let bytes = self.read_aligned_borrowed_bytes(length)?;
+        core::str::from_utf8(bytes).map_err(|_| DeserializeError::Utf8)
+    }
+
     /// Reads a boolean value (1 bit)
     pub fn read_bool(&mut self) -> bool {
         self.read_u1() == 1
@@ -987,6 +1166,16 @@ impl ReadCursor<'_> {
 mod test {
     use super::*;
 
+    #[test]
+    fn borrowed_bytes() {
+        let bytes = [0x02u8, b'h', b'i', 0xff];
+        let mut cursor = ReadCursor::new(&bytes);
+        let length = usize::from(cursor.read_aligned_u8());
+        assert_eq!(cursor.read_aligned_borrowed_str(length).unwrap(), "hi");
+        assert_eq!(cursor.read_aligned_borrowed_bytes(1).unwrap(), &[0xff]);
+        assert!(cursor.read_aligned_borrowed_bytes(1).is_err());
+    }
+
     #[test]
     fn u8_one() {
         let bytes = [0xABu8];
@@ -1015,6 +1204,50 @@ mod test {
         assert_eq!(cursor.read_u64(), 0xA1B2C3D401234567);
     }
 
+    #[test]
+    fn aligned_zero_extension() {
+        let bytes = [0xCDu8, 0xAB, 0x34, 0x12, 0x01];
+        let mut cursor = ReadCursor::new(&bytes);
+        assert_eq!(cursor.read_u32(), 0x1234ABCD);
+        assert_eq!(cursor.read_aligned_u16(), 0x0001);
+        assert_eq!(cursor.read_u64(), 0);
+    }
+
+    #[test]
+    fn bytes_aligned_and_unaligned() {
+        let bytes = [0x01u8, 0x23, 0x45, 0x67];
+        let mut cursor = ReadCursor::new(&bytes);
+        let mut aligned = [0xffu8; 2];
+        cursor.read_bytes(&mut aligned);
+        assert_eq!(aligned, [0x01, 0x23]);
+        assert_eq!(cursor.read_u4(), 0x5);
+        let mut unaligned = [0xffu8; 2];
+        cursor.read_bytes(&mut unaligned);
+        assert_eq!(unaligned, [0x74, 0x06]);
+        let mut past_end = [0xffu8; 2];
+        cursor.read_bytes(&mut past_end);
+        assert_eq!(past_end, [0, 0]);
+    }
+
+    #[test]
+    fn peek_skip_remaining() {
+        let bytes = [0x34u8, 0x12, 0xff];
+        let mut cursor = ReadCursor::new(&bytes);
+        assert_eq!(cursor.remaining_bits(), 24);
+        assert_eq!(cursor.peek_u16(), 0x1234);
+        assert_eq!(cursor.peek_bits(4), 0x4);
+        cursor.skip_bits(4);
+        assert_eq!(cursor.remaining_bits(), 20);
+        assert_eq!(cursor.peek_u8(), 0x23);
+        assert_eq!(cursor.read_u6(), 0x23);
+        cursor.align_to_byte();
+        assert_eq!(cursor.remaining_bits(), 8);
+        assert_eq!(cursor.peek_u32(), 0xff);
+        cursor.skip_bits(100);
+        assert_eq!(cursor.remaining_bits(), 0);
+        assert_eq!(cursor.peek_u64(), 0);
+    }
+
     #[test]
     fn f16_one() {
         let bytes = [0xCDu8, 0xAB];
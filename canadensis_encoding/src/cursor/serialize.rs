@@ -1,11 +1,21 @@
 use half::f16;
 
+use crate::float16::Float16Backend;
 use crate::Serialize;
+use core::cmp;
 use core::convert::TryInto;
 
+/// A function that receives serialized bytes from a streaming cursor
+type Sink<'b> = &'b mut dyn FnMut(&[u8]);
+
 /// A cursor over a byte slice for easy serializing of Cyphal data types
 ///
 /// Functions that write values will panic if no space is available in the slice.
+///
+/// A cursor can also stream its output: when the slice fills up, the bytes that have been
+/// fully written are passed to a sink function and the slice is reused. This allows a value
+/// to be serialized without a buffer large enough to hold the whole serialized form
+/// (see [`Serialize::serialize_in_pieces`]).
 pub struct WriteCursor<'b> {
     /// The bytes available to write to
     ///
@@ -21,6 +31,13 @@ pub struct WriteCursor<'b> {
     ///
     /// Invariant: This is in the range 0..=7.
     bit_index: u8,
+    /// A function that receives bytes after they have been written, if this cursor is streaming
+    sink: Option<Sink<'b>>,
+    /// The number of bytes that have been passed to the sink and removed from `bytes`
+    flushed_bytes: usize,
+    /// The total number of bytes that can be written, including bytes that have been passed to
+    /// the sink
+    end_bytes: usize,
 }
 
 impl<'b> WriteCursor<'b> {
@@ -28,10 +45,90 @@ impl<'b> WriteCursor<'b> {
     pub fn new(bytes: &'b mut [u8]) -> Self {
         // Reset all the bytes to zero
         bytes.iter_mut().for_each(|b| *b = 0);
+        let end_bytes = bytes.len();
         WriteCursor {
             bytes,
             bytes_written: 0,
             bit_index: 0,
+            sink: None,
+            flushed_bytes: 0,
+            end_bytes,
+        }
+    }
+
+    /// Creates a cursor that will write `length` bytes in total, using `buffer` as temporary
+    /// storage and passing bytes to `sink` as they are written
+    ///
+    /// [`finish_streaming`](#method.finish_streaming) must be called after writing to pass the
+    /// last bytes to the sink.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `buffer` is shorter than 9 bytes, which is the largest number
+    /// of bytes that one unaligned 64-bit value can touch.
+    pub(crate) fn streaming(buffer: &'b mut [u8], length: usize, sink: Sink<'b>) -> Self {
+        assert!(buffer.len() >= 9, "Streaming buffer too small");
+        buffer.iter_mut().for_each(|b| *b = 0);
+        WriteCursor {
+            bytes: buffer,
+            bytes_written: 0,
+            bit_index: 0,
+            sink: Some(sink),
+            flushed_bytes: 0,
+            end_bytes: length,
+        }
+    }
+
+    /// Passes all remaining bytes to the sink of a streaming cursor
+    ///
+    /// If fewer bytes than the length passed to [`streaming`](#method.streaming) have been
+    /// written, this also passes zero bytes to make up the difference.
+    pub(crate) fn finish_streaming(mut self) {
+        if self.sink.is_none() {
+            return;
+        }
+        // Include any partially written byte
+        self.align_to_byte();
+        self.flush();
+        // Pad to the declared length (the flushed buffer contains only zeros)
+        while self.flushed_bytes < self.end_bytes {
+            let length = (self.end_bytes - self.flushed_bytes).min(self.bytes.len());
+            if let Some(sink) = self.sink.as_mut() {
+                sink(&self.bytes[..length]);
+            }
+            self.flushed_bytes += length;
+        }
+    }
+
+    /// If this cursor is streaming, passes the fully written bytes to the sink and moves any
+    /// partially written byte to the beginning of the buffer
+    fn flush(&mut self) {
+        if let Some(sink) = self.sink.as_mut() {
+            if self.bytes_written != 0 {
+                sink(&self.bytes[..self.bytes_written]);
+            }
+            let partial = self.bytes.get(self.bytes_written).copied().unwrap_or(0);
+            self.bytes.iter_mut().for_each(|b| *b = 0);
+            if let Some(first) = self.bytes.first_mut() {
+                *first = partial;
+            }
+            self.flushed_bytes += self.bytes_written;
+            self.bytes_written = 0;
+        }
+    }
+
+    /// Checks that enough space is available to write the specified number of bits (panicking
+    /// if it is not), and makes the bytes that will be written available in the buffer
+    ///
+    /// For a streaming cursor, this may pass written bytes to the sink. The number of bits must
+    /// fit in the buffer.
+    fn reserve(&mut self, bits: usize) {
+        self.check_length(bits);
+        if self.sink.is_some() {
+            let touched_bytes = (usize::from(self.bit_index) + bits).div_ceil(8);
+            if self.bytes.len() - self.bytes_written < touched_bytes {
+                self.flush();
+            }
         }
     }
 
@@ -53,6 +150,10 @@ impl<'b> WriteCursor<'b> {
     /// Writes an x-bit unsigned integer (x must be in the range 1..=64)
     fn write_up_to_u64(&mut self, mut value: u64, bits: u8) {
         debug_assert!(bits <= 64);
+        if self.bit_index == 0 && bits & 7 == 0 {
+            self.write_aligned_le(value, usize::from(bits / 8));
+            return;
+        }
         // Write whole bytes, least significant first
         for _ in 0..(bits / 8) {
             self.write_up_to_u8(value as u8, 8);
@@ -65,6 +166,10 @@ impl<'b> WriteCursor<'b> {
     /// Writes an x-bit unsigned integer (x must be in the range 1..=32)
     fn write_up_to_u32(&mut self, mut value: u32, bits: u8) {
         debug_assert!(bits <= 32);
+        if self.bit_index == 0 && bits & 7 == 0 {
+            self.write_aligned_le(u64::from(value), usize::from(bits / 8));
+            return;
+        }
         // Write whole bytes, least significant first
         for _ in 0..(bits / 8) {
             self.write_up_to_u8(value as u8, 8);
@@ -77,6 +182,10 @@ impl<'b> WriteCursor<'b> {
     /// Writes an x-bit unsigned integer (x must be in the range 1..=16)
     fn write_up_to_u16(&mut self, mut value: u16, bits: u8) {
         debug_assert!(bits <= 16);
+        if self.bit_index == 0 && bits & 7 == 0 {
+            self.write_aligned_le(u64::from(value), usize::from(bits / 8));
+            return;
+        }
         // Write whole bytes, least significant first
         for _ in 0..(bits / 8) {
             self.write_up_to_u8(value as u8, 8);
@@ -86,13 +195,22 @@ impl<'b> WriteCursor<'b> {
         self.write_up_to_u8(value as u8, bits % 8);
     }
 
+    /// Writes the `length` least significant bytes of a value (`length` must be in the range
+    /// 0..=8), starting at a byte boundary
+    fn write_aligned_le(&mut self, value: u64, length: usize) {
+        debug_assert!(self.bit_index == 0 && length <= 8);
+        self.reserve(length * 8);
+        self.remaining_bytes()[..length].copy_from_slice(&value.to_le_bytes()[..length]);
+        self.advance_bits(length * 8);
+    }
+
     /// Writes an x-bit unsigned integer (x must be in the range 0..=8)
     fn write_up_to_u8(&mut self, value: u8, bits: u8) {
         debug_assert!(bits <= 8);
         if bits == 0 {
             return;
         }
-        self.check_length(usize::from(bits));
+        self.reserve(usize::from(bits));
         // Constrain value to fit with the correct number of bits
         // Use 16 bits to correctly handle the case when bits = 8
         let mask = ((1u16 << u16::from(bits)) - 1) as u8;
@@ -123,7 +241,8 @@ impl<'b> WriteCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn write_aligned_u8(&mut self, value: u8) {
-        assert!(self.is_aligned_to_8_bits());
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.reserve(8);
         self.remaining_bytes()[0] = value;
         self.advance_bits(8);
     }
@@ -134,7 +253,8 @@ impl<'b> WriteCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn write_aligned_u16(&mut self, value: u16) {
-        assert!(self.is_aligned_to_8_bits());
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.reserve(2 * 8);
         let space = &mut self.remaining_bytes()[..2];
         space.copy_from_slice(&value.to_le_bytes());
         self.advance_bits(2 * 8);
@@ -146,7 +266,8 @@ impl<'b> WriteCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn write_aligned_u32(&mut self, value: u32) {
-        assert!(self.is_aligned_to_8_bits());
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.reserve(4 * 8);
         let space = &mut self.remaining_bytes()[..4];
         space.copy_from_slice(&value.to_le_bytes());
         self.advance_bits(4 * 8);
@@ -158,7 +279,8 @@ impl<'b> WriteCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn write_aligned_u64(&mut self, value: u64) {
-        assert!(self.is_aligned_to_8_bits());
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.reserve(8 * 8);
         let space = &mut self.remaining_bytes()[..8];
         space.copy_from_slice(&value.to_le_bytes());
         self.advance_bits(8 * 8);
@@ -169,7 +291,13 @@ impl<'b> WriteCursor<'b> {
     fn check_length(&self, bits: usize) {
         let extended_bit_index = usize::from(self.bit_index) + bits;
         let byte_increment = extended_bit_index / 8;
-        assert!(self.bytes.len() - self.bytes_written >= byte_increment);
+        assert!(
+            self.end_bytes - self.flushed_bytes - self.bytes_written >= byte_increment,
+            "Not enough space to write {} bits ({} bits remaining after {} bits written)",
+            bits,
+            self.remaining_bits(),
+            self.bits_written()
+        );
     }
 
     /// Advances to reflect that bits have been
@@ -182,24 +310,67 @@ impl<'b> WriteCursor<'b> {
         self.bytes_written += byte_increment;
     }
 
-    fn skip_bits(&mut self, bits: u8) {
-        self.check_length(usize::from(bits));
-        self.advance_bits(usize::from(bits));
+    /// Advances the cursor by some number of bits, leaving them set to zero
+    ///
+    /// # Panics
+    ///
+    /// This function panics if fewer than `bits` bits of space are available.
+    pub fn skip_bits(&mut self, mut bits: usize) {
+        self.check_length(bits);
+        // Skip in pieces that fit in the buffer of a streaming cursor
+        while bits > 64 {
+            self.reserve(64);
+            self.advance_bits(64);
+            bits -= 64;
+        }
+        self.reserve(bits);
+        self.advance_bits(bits);
     }
 
     /// Advances the cursor to a byte boundary (a multiple of 8 bits)
     pub fn align_to_8_bits(&mut self) {
+        self.align_to_byte();
+    }
+
+    /// Advances the cursor to a byte boundary (a multiple of 8 bits)
+    ///
+    /// This does nothing if the cursor is already at a byte boundary.
+    pub fn align_to_byte(&mut self) {
         if self.bit_index != 0 {
-            self.skip_bits(8 - self.bit_index);
+            self.skip_bits(usize::from(8 - self.bit_index));
         }
     }
 
+    /// Writes the x least significant bits of a value (x must be in the range 0..=64)
+    ///
+    /// This is useful when the number of bits is not known at compile time.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `bits` is greater than 64 or not enough space is available.
+    pub fn write_bits(&mut self, value: u64, bits: u8) {
+        assert!(bits <= 64);
+        self.write_up_to_u64(value, bits);
+    }
+
+    /// Returns the number of bits that can still be written to this cursor
+    pub fn remaining_bits(&self) -> usize {
+        (self.end_bytes - self.flushed_bytes - self.bytes_written) * 8 - usize::from(self.bit_index)
+    }
+
     /// Writes a 16-bit floating-point value
     #[inline]
     pub fn write_f16(&mut self, value: f16) {
         self.write_u16(value.to_bits());
     }
 
+    /// Converts a 32-bit floating-point value into a 16-bit value using the backend `B`,
+    /// and writes it
+    #[inline]
+    pub fn write_f32_as_f16<B: Float16Backend>(&mut self, value: f32) {
+        self.write_f16(B::from_f32(value));
+    }
+
     /// Writes a 32-bit floating-point value
     #[inline]
     pub fn write_f32(&mut self, value: f32) {
@@ -214,8 +385,23 @@ impl<'b> WriteCursor<'b> {
 
     /// Writes a byte array
     pub fn write_bytes(&mut self, bytes: &[u8]) {
-        for byte in bytes {
-            self.write_u8(*byte);
+        if self.is_aligned_to_8_bits() {
+            self.write_aligned_bytes(bytes);
+        } else {
+            self.check_length(8 * bytes.len());
+            let bit_index = self.bit_index;
+            // Each byte is split across two bytes of the buffer. Write in pieces that fit in
+            // the buffer of a streaming cursor, with one byte left over for the last partial byte.
+            let piece_length = cmp::max(self.bytes.len().saturating_sub(1), 1);
+            for piece in bytes.chunks(piece_length) {
+                self.reserve(8 * piece.len());
+                let remaining_bytes = self.remaining_bytes();
+                for (i, byte) in piece.iter().enumerate() {
+                    remaining_bytes[i] |= byte << bit_index;
+                    remaining_bytes[i + 1] |= byte >> (8 - bit_index);
+                }
+                self.advance_bits(8 * piece.len());
+            }
         }
     }
 
@@ -225,8 +411,18 @@ impl<'b> WriteCursor<'b> {
     ///
     /// This function panics if the cursor is not aligned to a multiple of 8 bits.
     pub fn write_aligned_bytes(&mut self, bytes: &[u8]) {
+        assert!(self.is_aligned_to_8_bits(), "Not aligned to a byte");
+        self.check_length(8 * bytes.len());
+        if self.sink.is_some() && bytes.len() > self.bytes.len() - self.bytes_written {
+            // Pass the bytes directly to the sink instead of copying them into the buffer
+            self.flush();
+            if let Some(sink) = self.sink.as_mut() {
+                sink(bytes);
+            }
+            self.flushed_bytes += bytes.len();
+            return;
+        }
         let remaining_bytes = self.remaining_bytes();
-        assert!(remaining_bytes.len() >= bytes.len());
         remaining_bytes[..bytes.len()].copy_from_slice(bytes);
         self.advance_bits(8 * bytes.len());
     }
@@ -237,17 +433,24 @@ impl<'b> WriteCursor<'b> {
         T: Serialize,
     {
         self.align_to_8_bits();
-        if T::EXTENT_BYTES.is_some() {
+        if let Some(extent_bytes) = T::EXTENT_BYTES {
             // Add delimiter header
             let composite_size_bits = value.size_bits();
             // Convert bits to bytes, round up
             let composite_size_bytes: u32 = ((composite_size_bits + 7) / 8)
                 .try_into()
                 .expect("Composite too large for u32");
+            // A receiver would truncate a value larger than the extent
+            debug_assert!(
+                composite_size_bytes <= extent_bytes,
+                "Composite size {} bytes is larger than its extent {} bytes",
+                composite_size_bytes,
+                extent_bytes
+            );
             self.write_u32(composite_size_bytes);
         }
         // Now serialize the components
-        value.serialize(self);
+        crate::serialize_checked(value, self);
         // If not at an 8-byte boundary, advance to the next one
         // This ensures that the composite is aligned to 8 bits.
         self.align_to_8_bits();
@@ -260,7 +463,7 @@ impl<'b> WriteCursor<'b> {
 
     /// Returns the number of bits that have been written to this cursor
     pub fn bits_written(&self) -> usize {
-        self.bytes_written * 8 + usize::from(self.bit_index)
+        (self.flushed_bytes + self.bytes_written) * 8 + usize::from(self.bit_index)
     }
 }
 
@@ -1058,6 +1261,39 @@ mod test {
         assert_eq!(bytes, [0x86, 0xa3, 0xbc, 0x24, 0x8b, 0x9a, 0x56, 0xfd]);
     }
 
+    #[test]
+    fn bytes_aligned_and_unaligned() {
+        let mut bytes = [0u8; 5];
+        let mut cursor = WriteCursor::new(&mut bytes);
+        cursor.write_bytes(&[0x01, 0x23]);
+        cursor.write_u24(0x89abcd);
+        assert_eq!(bytes, [0x01, 0x23, 0xcd, 0xab, 0x89]);
+
+        let mut bytes = [0u8; 3];
+        let mut cursor = WriteCursor::new(&mut bytes);
+        cursor.write_u4(0x5);
+        cursor.write_bytes(&[0x67, 0x89]);
+        cursor.write_u4(0xa);
+        assert_eq!(bytes, [0x75, 0x96, 0xa8]);
+    }
+
+    #[test]
+    fn skip_align_remaining() {
+        let mut bytes = [0xffu8; 4];
+        let mut cursor = WriteCursor::new(&mut bytes);
+        assert_eq!(cursor.remaining_bits(), 32);
+        cursor.write_u3(0b101);
+        cursor.skip_bits(2);
+        cursor.write_u1(1);
+        assert_eq!(cursor.remaining_bits(), 26);
+        cursor.align_to_byte();
+        assert_eq!(cursor.remaining_bits(), 24);
+        cursor.skip_bits(8);
+        cursor.write_u8(0x42);
+        assert_eq!(cursor.remaining_bits(), 8);
+        assert_eq!(bytes, [0b0010_0101, 0, 0x42, 0]);
+    }
+
     #[test]
     fn u64_aligned_basic() {
         let mut bytes = [0u8; 8];
@@ -0,0 +1,132 @@
+//!
+//! Conversion between 16-bit and 32-bit floating-point values
+//!
+//! Cyphal `float16` values are stored as [`f16`], which only holds the encoded bits. Converting
+//! to and from `f32` is done by a [`Float16Backend`]:
+//!
+//! * [`Software`] is a pure-software implementation with IEEE 754 round-to-nearest-even behavior
+//! * [`Half`] uses the conversion functions of the `half` crate, which may use hardware
+//!   instructions on some targets
+//!
+//! Targets with their own half-precision conversion instructions, or applications that need to
+//! match the rounding of another system exactly, can implement [`Float16Backend`].
+//!
+
+use half::f16;
+
+/// A way of converting between 16-bit and 32-bit floating-point values
+pub trait Float16Backend {
+    /// Converts a 32-bit value into a 16-bit value
+    fn from_f32(value: f32) -> f16;
+    /// Converts a 16-bit value into a 32-bit value
+    fn to_f32(value: f16) -> f32;
+}
+
+/// The default backend
+pub type DefaultBackend = Software;
+
+/// A pure-software conversion that does not depend on any hardware support
+///
+/// Values are rounded to the nearest representable value, with ties rounded to even. Values too
+/// large for 16 bits become infinity. The results are bit-exact with [`Half`] on targets
+/// without hardware conversion instructions.
+#[derive(Debug, Copy, Clone)]
+pub struct Software;
+
+impl Float16Backend for Software {
+    fn from_f32(value: f32) -> f16 {
+        f16::from_bits(f32_bits_to_f16_bits(value.to_bits()))
+    }
+
+    fn to_f32(value: f16) -> f32 {
+        f32::from_bits(f16_bits_to_f32_bits(value.to_bits()))
+    }
+}
+
+/// A conversion that uses the `half` crate
+#[derive(Debug, Copy, Clone)]
+pub struct Half;
+
+impl Float16Backend for Half {
+    #[inline]
+    fn from_f32(value: f32) -> f16 {
+        f16::from_f32(value)
+    }
+
+    #[inline]
+    fn to_f32(value: f16) -> f32 {
+        value.to_f32()
+    }
+}
+
+fn f32_bits_to_f16_bits(value: u32) -> u16 {
+    let sign = (value & 0x8000_0000) >> 16;
+    let exponent = (value & 0x7f80_0000) >> 23;
+    let mantissa = value & 0x007f_ffff;
+
+    if exponent == 0xff {
+        // Infinity or NaN. Keep the upper mantissa bits of a NaN and make sure it stays a NaN.
+        let nan_bit = if mantissa == 0 { 0 } else { 0x0200 };
+        return (sign | 0x7c00 | nan_bit | (mantissa >> 13)) as u16;
+    }
+
+    let half_exponent = exponent as i32 - 127 + 15;
+    if half_exponent >= 0x1f {
+        // Too large, becomes infinity
+        return (sign | 0x7c00) as u16;
+    }
+    if half_exponent <= 0 {
+        // Subnormal or zero
+        if 14 - half_exponent > 24 {
+            // Too small, becomes zero
+            return sign as u16;
+        }
+        // Add the implicit leading 1 and shift into subnormal position
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        let half_mantissa = mantissa >> shift;
+        let round_bit = 1 << (shift - 1);
+        return (sign | rounded(half_mantissa, mantissa, round_bit)) as u16;
+    }
+
+    // Normal value. Rounding up may carry into the exponent, which correctly produces the next
+    // power of two or infinity.
+    let half_bits = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    (sign | rounded(half_bits, mantissa, 0x1000)) as u16
+}
+
+/// Rounds a truncated value to the nearest even value based on the bits that were removed
+///
+/// `round_bit` is the most significant bit of `mantissa` that was removed.
+fn rounded(truncated: u32, mantissa: u32, round_bit: u32) -> u32 {
+    // Round up if more than halfway, or exactly halfway and the truncated value is odd
+    if (mantissa & round_bit) != 0 && (mantissa & (3 * round_bit - 1)) != 0 {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
+
+fn f16_bits_to_f32_bits(value: u16) -> u32 {
+    let sign = u32::from(value & 0x8000) << 16;
+    let exponent = u32::from((value >> 10) & 0x1f);
+    let mantissa = u32::from(value & 0x03ff);
+
+    match exponent {
+        0 if mantissa == 0 => sign,
+        0 => {
+            // Subnormal, becomes normal in 32 bits
+            let mut exponent = 127 - 15 + 1;
+            let mut mantissa = mantissa;
+            while mantissa & 0x0400 == 0 {
+                mantissa <<= 1;
+                exponent -= 1;
+            }
+            sign | (exponent << 23) | ((mantissa & 0x03ff) << 13)
+        }
+        0x1f if mantissa == 0 => sign | 0x7f80_0000,
+        // NaN, always quiet
+        0x1f => sign | 0x7fc0_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    }
+}
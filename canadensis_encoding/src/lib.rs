@@ -6,14 +6,20 @@
 #![deny(missing_docs)]
 
 extern crate half;
+#[cfg(feature = "serde")]
+extern crate serde;
 extern crate zerocopy;
 
 pub mod bits;
 mod cursor;
+pub mod float16;
+#[cfg(feature = "serde")]
+pub mod serde_array;
 
 pub use crate::cursor::deserialize::ReadCursor;
 pub use crate::cursor::serialize::WriteCursor;
 use core::cmp;
+use core::fmt;
 use zerocopy::{AsBytes, FromBytes};
 
 /// Trait for types that can be encoded into Cyphal transfers, or decoded from transfers
@@ -22,13 +28,48 @@ pub trait DataType {
     const EXTENT_BYTES: Option<u32>;
 }
 
+/// Trait for data types with a known maximum serialized size
+///
+/// This can be used to size static buffers and to find the maximum payload size when
+/// subscribing to a subject.
+///
+/// Code generated by `canadensis_codegen_rust` implements this trait for every data type.
+pub trait SerializedSize: DataType {
+    /// The maximum number of bytes in the serialized form of a value of this type
+    ///
+    /// For delimited types, this does not include the delimiter header.
+    const MAX_SERIALIZED_SIZE_BYTES: usize;
+
+    /// The maximum number of bytes that a receiver of this type needs to accept
+    ///
+    /// This is the extent for delimited types, or
+    /// [`MAX_SERIALIZED_SIZE_BYTES`](#associatedconstant.MAX_SERIALIZED_SIZE_BYTES) for sealed
+    /// types.
+    const RECEIVE_SIZE_BYTES: usize = match Self::EXTENT_BYTES {
+        Some(extent_bytes) => extent_bytes as usize,
+        None => Self::MAX_SERIALIZED_SIZE_BYTES,
+    };
+}
+
 /// Trait for types that can be serialized into Cyphal transfers
 pub trait Serialize: DataType {
     /// Returns the size of the encoded form of this value, in bits
     ///
     /// The returned value may not be a multiple of 8.
+    ///
+    /// When debug assertions are enabled, [`serialize_to_bytes`](#method.serialize_to_bytes),
+    /// [`try_serialize_to_bytes`](#method.try_serialize_to_bytes), and
+    /// [`WriteCursor::write_composite`] panic if [`serialize`](#tymethod.serialize) writes a
+    /// different number of bytes.
     fn size_bits(&self) -> usize;
 
+    /// Returns the size of the encoded form of this value, in bytes
+    ///
+    /// This is [`size_bits()`](#tymethod.size_bits) rounded up to a whole number of bytes.
+    fn size_bytes(&self) -> usize {
+        self.size_bits().div_ceil(8)
+    }
+
     /// Serializes this value into a buffer
     ///
     /// The provided cursor will allow writing at least the number of bits returned by the
@@ -37,9 +78,79 @@ pub trait Serialize: DataType {
 
     /// A convenience function that creates a cursor around the provided bytes and calls
     /// [`serialize`](#tymethod.serialize)
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `bytes` is shorter than [`size_bytes()`](#method.size_bytes).
+    /// [`try_serialize_to_bytes`](#method.try_serialize_to_bytes) returns an error instead.
     fn serialize_to_bytes(&self, bytes: &mut [u8]) {
         let mut cursor = WriteCursor::new(bytes);
-        self.serialize(&mut cursor);
+        serialize_checked(self, &mut cursor);
+    }
+
+    /// Serializes this value into the beginning of a slice of bytes, and returns the number of
+    /// bytes used
+    ///
+    /// This function returns an error, without writing anything, if `bytes` is shorter than
+    /// [`size_bytes()`](#method.size_bytes).
+    ///
+    /// # Panics
+    ///
+    /// The length check relies on [`size_bits()`](#tymethod.size_bits). If
+    /// [`serialize`](#tymethod.serialize) writes more bytes than `size_bits()` reports, this
+    /// function panics, even in release builds. Generated code always agrees with its
+    /// `size_bits()`, so this can only happen with an incorrect hand-written implementation.
+    fn try_serialize_to_bytes(&self, bytes: &mut [u8]) -> Result<usize, SerializeError> {
+        let size_bytes = self.size_bytes();
+        let available = bytes.len();
+        let bytes = bytes
+            .get_mut(..size_bytes)
+            .ok_or(SerializeError::BufferTooSmall {
+                required: size_bytes,
+                available,
+            })?;
+        let mut cursor = WriteCursor::new(bytes);
+        serialize_checked(self, &mut cursor);
+        Ok(size_bytes)
+    }
+
+    /// Serializes this value and passes the serialized bytes to `sink` in one or more pieces
+    ///
+    /// This does not require a buffer large enough to hold the whole serialized value. The total
+    /// length of the pieces is [`size_bytes()`](#method.size_bytes).
+    fn serialize_in_pieces(&self, sink: &mut dyn FnMut(&[u8])) {
+        let mut buffer = [0u8; 32];
+        let mut cursor = WriteCursor::streaming(&mut buffer, self.size_bytes(), sink);
+        serialize_checked(self, &mut cursor);
+        cursor.finish_streaming();
+    }
+}
+
+/// Serializes a value
+///
+/// When debug assertions are enabled, this also checks that the value wrote the number of bytes
+/// that its [`size_bits()`](Serialize::size_bits) function returned. A mismatch means that a
+/// serialize implementation disagrees with its size calculation, and a buffer sized using
+/// `size_bits()` may be too small.
+pub(crate) fn serialize_checked<T>(value: &T, cursor: &mut WriteCursor<'_>)
+where
+    T: Serialize + ?Sized,
+{
+    #[cfg(debug_assertions)]
+    let start_bits = cursor.bits_written();
+    value.serialize(cursor);
+    #[cfg(debug_assertions)]
+    {
+        let written_bits = cursor.bits_written() - start_bits;
+        let declared_bits = value.size_bits();
+        assert_eq!(
+            written_bits.div_ceil(8),
+            declared_bits.div_ceil(8),
+            "{} wrote {} bits, but its size_bits() returned {}",
+            core::any::type_name::<T>(),
+            written_bits,
+            declared_bits
+        );
     }
 }
 
@@ -87,6 +198,28 @@ pub trait Deserialize: DataType {
     }
 }
 
+/// Trait for types that can be deserialized from Cyphal transfers and may borrow data from the
+/// transfer payload
+///
+/// Types that implement this trait usually refer to variable-length byte arrays and strings in
+/// the payload instead of copying them.
+pub trait DeserializeBorrowed<'b>: DataType {
+    /// Deserializes a value and returns it
+    fn deserialize_borrowed(cursor: &mut ReadCursor<'b>) -> Result<Self, DeserializeError>
+    where
+        Self: Sized;
+
+    /// A convenience function that creates a cursor around the provided bytes and calls
+    /// [`deserialize_borrowed`](#tymethod.deserialize_borrowed)
+    fn deserialize_from_bytes_borrowed(bytes: &'b [u8]) -> Result<Self, DeserializeError>
+    where
+        Self: Sized,
+    {
+        let mut cursor = ReadCursor::new(bytes);
+        Self::deserialize_borrowed(&mut cursor)
+    }
+}
+
 /// Marker for message data types
 pub trait Message {}
 /// Marker for service request data types
@@ -94,6 +227,36 @@ pub trait Request {}
 /// Marker for service response data types
 pub trait Response {}
 
+/// Errors that can occur when serializing
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SerializeError {
+    /// The provided buffer was too small to hold the serialized value
+    BufferTooSmall {
+        /// The number of bytes needed
+        required: usize,
+        /// The number of bytes in the buffer
+        available: usize,
+    },
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializeError::BufferTooSmall {
+                required,
+                available,
+            } => write!(
+                f,
+                "Buffer too small ({} bytes required, {} available)",
+                required, available
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SerializeError {}
+
 /// Errors that can occur when deserializing
 #[non_exhaustive]
 #[derive(Debug)]
@@ -104,4 +267,41 @@ pub enum DeserializeError {
     UnionTag,
     /// A delimiter header had a length that was not valid for the expected type
     DelimitedLength,
+    /// A `utf8` array was not valid UTF-8
+    Utf8,
 }
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            DeserializeError::ArrayLength => "Array length too large",
+            DeserializeError::UnionTag => "Invalid union tag",
+            DeserializeError::DelimitedLength => "Invalid delimiter header length",
+            DeserializeError::Utf8 => "Invalid UTF-8",
+        };
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for DeserializeError {}
+
+/// An error that occurs when a value is too long to fit in a variable-length array field
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CapacityError {
+    /// The maximum number of elements that the field can hold
+    pub capacity: usize,
+    /// The number of elements in the value
+    pub length: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Value with {} elements does not fit into a field with capacity {}",
+            self.length, self.capacity
+        )
+    }
+}
+
+impl core::error::Error for CapacityError {}
@@ -0,0 +1,87 @@
+//!
+//! Serde support for fixed-length arrays of any length
+//!
+//! Serde only implements `Serialize` and `Deserialize` for arrays with up to 32 elements.
+//! DSDL fixed-length arrays can be much longer, so generated code uses this module for array
+//! fields:
+//!
+//! ```ignore
+//! #[serde(with = "::canadensis_encoding::serde_array")]
+//! pub values: [u16; 64],
+//! ```
+//!
+//! Arrays are represented as tuples, the same way serde represents short arrays.
+//!
+
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserializer, Serializer};
+
+/// Serializes an array as a tuple
+pub fn serialize<S, T, const N: usize>(array: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: serde::Serialize,
+{
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for element in array {
+        tuple.serialize_element(element)?;
+    }
+    tuple.end()
+}
+
+/// Deserializes an array from a tuple with exactly `N` elements
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
+
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+where
+    T: serde::Deserialize<'de>,
+{
+    type Value = [T; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an array of length {}", N)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut error = None;
+        let mut index = 0;
+        // Fill the array one element at a time. After an error, the remaining elements are None
+        // and the error is returned below.
+        let elements: [Option<T>; N] = [(); N].map(|_| {
+            if error.is_some() {
+                return None;
+            }
+            let element = match seq.next_element() {
+                Ok(Some(element)) => Some(element),
+                Ok(None) => {
+                    error = Some(A::Error::invalid_length(index, &self));
+                    None
+                }
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            };
+            index += 1;
+            element
+        });
+        match error {
+            Some(error) => Err(error),
+            None => Ok(elements.map(|element| element.expect("Missing array element"))),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+extern crate canadensis_encoding;
+
+use canadensis_encoding::bits::BitArray;
+use canadensis_encoding::ReadCursor;
+
+#[test]
+fn bit_array_deserialize_partial_capacity() {
+    // An array with 8 bits and capacity for 32, followed by another byte
+    let bytes = [0b1010_0101, 0x37];
+    let mut cursor = ReadCursor::new(&bytes);
+    let array = BitArray::<4>::deserialize(8, &mut cursor);
+    let expected = [true, false, true, false, false, true, false, true];
+    assert!(array.iter().eq(expected.iter().copied()));
+    // The array must not read beyond its length
+    assert_eq!(0x37, cursor.read_aligned_u8());
+}
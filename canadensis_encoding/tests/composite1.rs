@@ -1,7 +1,7 @@
 extern crate canadensis_encoding;
 
 use canadensis_encoding::{
-    DataType, Deserialize, DeserializeError, ReadCursor, Serialize, WriteCursor,
+    DataType, Deserialize, DeserializeError, ReadCursor, Serialize, SerializeError, WriteCursor,
 };
 
 #[derive(Debug, PartialEq)]
@@ -123,3 +123,36 @@ fn round_trip_1() {
     let deserialized = Outer::deserialize_from_bytes(&actual_bytes).unwrap();
     assert_eq!(value, deserialized);
 }
+
+#[test]
+fn try_serialize_buffer_size() {
+    let value = Outer {
+        a: 0x1621,
+        inner: Inner {
+            a: false,
+            b: true,
+            c: true,
+            d: 0x19,
+        },
+        b: 0x137ab90ceda,
+    };
+    assert_eq!(9, value.size_bytes());
+
+    let mut short_bytes = [0u8; 8];
+    assert_eq!(
+        Err(SerializeError::BufferTooSmall {
+            required: 9,
+            available: 8
+        }),
+        value.try_serialize_to_bytes(&mut short_bytes)
+    );
+    assert_eq!([0u8; 8], short_bytes);
+
+    let mut long_bytes = [0xffu8; 12];
+    assert_eq!(Ok(9), value.try_serialize_to_bytes(&mut long_bytes));
+    assert_eq!([0xff; 3], long_bytes[9..]);
+    assert_eq!(
+        value,
+        Outer::deserialize_from_bytes(&long_bytes[..9]).unwrap()
+    );
+}
@@ -0,0 +1,59 @@
+//! Checks that the software float16 backend matches the half crate
+
+extern crate canadensis_encoding;
+extern crate half;
+
+use canadensis_encoding::float16::{Float16Backend, Half, Software};
+use canadensis_encoding::{ReadCursor, WriteCursor};
+use half::f16;
+
+#[test]
+fn f16_to_f32_all_values() {
+    for bits in 0..=u16::MAX {
+        let value = f16::from_bits(bits);
+        assert_eq!(
+            Half::to_f32(value).to_bits(),
+            Software::to_f32(value).to_bits(),
+            "f16 bits {:#06x}",
+            bits
+        );
+    }
+}
+
+#[test]
+fn f32_to_f16_sampled() {
+    // Every 4097th bit pattern covers all exponents and many rounding cases
+    let special = [
+        0.0f32,
+        -0.0,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        f32::NAN,
+        f32::MAX,
+        f32::MIN_POSITIVE,
+        65504.0,
+        65520.0,
+        1.0 + f32::EPSILON,
+        5.960_464_5e-8,
+        2.980_232_2e-8,
+    ];
+    let sampled = (0..=u32::MAX).step_by(4097).map(f32::from_bits);
+    for value in special.iter().copied().chain(sampled) {
+        assert_eq!(
+            Half::from_f32(value).to_bits(),
+            Software::from_f32(value).to_bits(),
+            "f32 bits {:#010x}",
+            value.to_bits()
+        );
+    }
+}
+
+#[test]
+fn cursor_round_trip() {
+    let mut bytes = [0u8; 2];
+    let mut cursor = WriteCursor::new(&mut bytes);
+    cursor.write_f32_as_f16::<Software>(-2.5);
+    assert_eq!(bytes, [0x00, 0xc1]);
+    let mut cursor = ReadCursor::new(&bytes);
+    assert_eq!(cursor.read_f16_as_f32::<Software>(), -2.5);
+}
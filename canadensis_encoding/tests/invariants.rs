@@ -0,0 +1,87 @@
+//! Checks the invariants that serialization checks when debug assertions are enabled
+
+extern crate canadensis_encoding;
+
+use canadensis_encoding::{DataType, Serialize, WriteCursor};
+
+/// A type that writes 16 bits, but claims to write only 8
+struct WrongSize;
+
+impl DataType for WrongSize {
+    const EXTENT_BYTES: Option<u32> = None;
+}
+
+impl Serialize for WrongSize {
+    fn size_bits(&self) -> usize {
+        8
+    }
+
+    fn serialize(&self, cursor: &mut WriteCursor<'_>) {
+        cursor.write_u16(0x1234);
+    }
+}
+
+/// A type that contains a WrongSize
+struct ContainsWrongSize;
+
+impl DataType for ContainsWrongSize {
+    const EXTENT_BYTES: Option<u32> = None;
+}
+
+impl Serialize for ContainsWrongSize {
+    fn size_bits(&self) -> usize {
+        // Correct if WrongSize::size_bits() were correct
+        16
+    }
+
+    fn serialize(&self, cursor: &mut WriteCursor<'_>) {
+        cursor.write_u8(0);
+        cursor.write_composite(&WrongSize);
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "WrongSize wrote 16 bits, but its size_bits() returned 8")]
+fn size_mismatch_top_level() {
+    let mut bytes = [0u8; 4];
+    WrongSize.serialize_to_bytes(&mut bytes);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "WrongSize wrote 16 bits, but its size_bits() returned 8")]
+fn size_mismatch_nested() {
+    let mut bytes = [0u8; 4];
+    ContainsWrongSize.serialize_to_bytes(&mut bytes);
+}
+
+#[test]
+#[should_panic(
+    expected = "Not enough space to write 16 bits (8 bits remaining after 0 bits written)"
+)]
+fn serialize_too_long() {
+    let mut bytes = [0u8; 1];
+    let mut cursor = WriteCursor::new(&mut bytes);
+    WrongSize.serialize(&mut cursor);
+}
+
+/// try_serialize_to_bytes() only checks the buffer length against size_bits(), so it also
+/// panics in release builds
+#[test]
+#[should_panic(
+    expected = "Not enough space to write 16 bits (8 bits remaining after 0 bits written)"
+)]
+fn try_serialize_too_long() {
+    let mut bytes = [0u8; 4];
+    let _ = WrongSize.try_serialize_to_bytes(&mut bytes);
+}
+
+#[test]
+#[should_panic(expected = "Not aligned to a byte")]
+fn write_aligned_bytes_unaligned() {
+    let mut bytes = [0u8; 4];
+    let mut cursor = WriteCursor::new(&mut bytes);
+    cursor.write_u3(1);
+    cursor.write_aligned_bytes(&[1, 2]);
+}
@@ -0,0 +1,78 @@
+//! Tests the serde representations of bit arrays and long fixed-length arrays
+#![cfg(feature = "serde")]
+
+extern crate canadensis_encoding;
+extern crate serde;
+extern crate serde_test;
+
+use canadensis_encoding::bits::BitArray;
+use canadensis_encoding::serde_array;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+#[test]
+fn bit_array() {
+    let mut bits = BitArray::<2>::new(3);
+    bits.set(0, true);
+    bits.set(2, true);
+    assert_tokens(
+        &bits,
+        &[
+            Token::Seq { len: Some(3) },
+            Token::Bool(true),
+            Token::Bool(false),
+            Token::Bool(true),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn bit_array_too_long() {
+    let mut tokens = vec![Token::Seq { len: Some(9) }];
+    tokens.extend([Token::Bool(true); 9]);
+    tokens.push(Token::SeqEnd);
+    assert_de_tokens_error::<BitArray<1>>(
+        &tokens,
+        "invalid length 9, expected a sequence of at most 8 booleans",
+    );
+}
+
+/// A wrapper that uses serde_array like a generated field would
+#[derive(Debug, PartialEq)]
+struct LongArray([u8; 40]);
+
+impl Serialize for LongArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_array::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LongArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_array::deserialize(deserializer).map(LongArray)
+    }
+}
+
+#[test]
+fn long_array() {
+    let mut array = [0u8; 40];
+    for (i, element) in array.iter_mut().enumerate() {
+        *element = i as u8;
+    }
+    let mut tokens = vec![Token::Tuple { len: 40 }];
+    tokens.extend((0..40).map(Token::U8));
+    tokens.push(Token::TupleEnd);
+    assert_tokens(&LongArray(array), &tokens);
+}
+
+#[test]
+fn long_array_too_short() {
+    let mut tokens = vec![Token::Tuple { len: 40 }];
+    tokens.extend((0..39).map(Token::U8));
+    tokens.push(Token::TupleEnd);
+    assert_de_tokens_error::<LongArray>(
+        &tokens,
+        "invalid length 39, expected an array of length 40",
+    );
+}
@@ -0,0 +1,90 @@
+//! Checks that serializing a value in pieces produces the same bytes as serializing it into
+//! one buffer
+
+extern crate canadensis_encoding;
+
+use canadensis_encoding::{DataType, Serialize, WriteCursor};
+
+/// A type with unaligned values, long byte arrays, and large gaps
+struct Mixed {
+    prefix: u8,
+    bytes: Vec<u8>,
+    skip: usize,
+}
+
+impl DataType for Mixed {
+    const EXTENT_BYTES: Option<u32> = None;
+}
+
+impl Serialize for Mixed {
+    fn size_bits(&self) -> usize {
+        3 + 64 + 5 + 8 * self.bytes.len() + 3 + 8 * self.bytes.len() + self.skip + 16 + 1
+    }
+
+    fn serialize(&self, cursor: &mut WriteCursor<'_>) {
+        cursor.write_u3(self.prefix);
+        cursor.write_u64(0x0123_4567_89ab_cdef);
+        cursor.align_to_8_bits();
+        cursor.write_aligned_bytes(&self.bytes);
+        cursor.write_u3(self.prefix);
+        cursor.write_bytes(&self.bytes);
+        cursor.skip_bits(self.skip);
+        cursor.write_u16(0xbeef);
+        cursor.write_bool(true);
+    }
+}
+
+fn check(value: &Mixed) {
+    let mut expected = vec![0u8; value.size_bytes()];
+    value.serialize_to_bytes(&mut expected);
+
+    let mut actual = Vec::new();
+    let mut pieces = 0;
+    value.serialize_in_pieces(&mut |piece| {
+        assert!(!piece.is_empty());
+        actual.extend_from_slice(piece);
+        pieces += 1;
+    });
+    assert_eq!(expected, actual);
+    if value.size_bytes() > 32 {
+        assert!(pieces > 1);
+    }
+}
+
+#[test]
+fn streaming_matches_buffer() {
+    for length in [0, 1, 7, 8, 9, 31, 32, 33, 100, 300] {
+        for skip in [0, 1, 7, 64, 65, 500] {
+            check(&Mixed {
+                prefix: 5,
+                bytes: (0..length).map(|i| i as u8).collect(),
+                skip,
+            });
+        }
+    }
+}
+
+/// A type that writes fewer bits than its size_bits() function returns
+struct Short;
+
+impl DataType for Short {
+    const EXTENT_BYTES: Option<u32> = None;
+}
+
+impl Serialize for Short {
+    fn size_bits(&self) -> usize {
+        // Rounds up to the same number of bytes as the 1 bit actually written
+        7
+    }
+
+    fn serialize(&self, cursor: &mut WriteCursor<'_>) {
+        cursor.write_bool(true);
+    }
+}
+
+#[test]
+fn streaming_partial_byte() {
+    let mut actual = Vec::new();
+    Short.serialize_in_pieces(&mut |piece| actual.extend_from_slice(piece));
+    assert_eq!(actual, [0x01]);
+}
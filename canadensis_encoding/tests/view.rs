@@ -0,0 +1,122 @@
+//! Checks how a view type that borrows a byte array handles truncated payloads, compared to the
+//! owned type
+//!
+//! The types here are written like the code that canadensis_codegen_rust generates for this type:
+//!
+//! ```ignore
+//! # Blob.1.0
+//! uint8 tag
+//! uint8[<=8] data
+//! @sealed
+//! ```
+
+extern crate canadensis_encoding;
+extern crate heapless;
+
+use canadensis_encoding::{
+    DataType, Deserialize, DeserializeBorrowed, DeserializeError, ReadCursor,
+};
+
+#[derive(Debug, PartialEq)]
+struct Blob {
+    tag: u8,
+    data: heapless::Vec<u8, 8>,
+}
+
+impl DataType for Blob {
+    const EXTENT_BYTES: Option<u32> = None;
+}
+
+impl Deserialize for Blob {
+    fn deserialize(cursor: &mut ReadCursor<'_>) -> Result<Self, DeserializeError>
+    where
+        Self: Sized,
+    {
+        Ok(Blob {
+            tag: cursor.read_u8(),
+            data: {
+                let length = usize::from(cursor.read_u8());
+                if length <= 8 {
+                    let mut elements = heapless::Vec::new();
+                    let _ = elements.resize(length, 0);
+                    cursor.read_bytes(&mut elements);
+                    elements
+                } else {
+                    return Err(DeserializeError::ArrayLength);
+                }
+            },
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct BlobView<'a> {
+    tag: u8,
+    data: &'a [u8],
+}
+
+impl DataType for BlobView<'_> {
+    const EXTENT_BYTES: Option<u32> = None;
+}
+
+impl<'a> DeserializeBorrowed<'a> for BlobView<'a> {
+    fn deserialize_borrowed(cursor: &mut ReadCursor<'a>) -> Result<Self, DeserializeError>
+    where
+        Self: Sized,
+    {
+        Ok(BlobView {
+            tag: cursor.read_u8(),
+            data: {
+                let length = usize::from(cursor.read_u8());
+                if length <= 8 {
+                    cursor.read_aligned_borrowed_bytes(length)?
+                } else {
+                    return Err(DeserializeError::ArrayLength);
+                }
+            },
+        })
+    }
+}
+
+#[test]
+fn complete_payload() {
+    let payload = [0x11, 3, 0xa, 0xb, 0xc];
+    let owned = Blob::deserialize_from_bytes(&payload).unwrap();
+    let view = BlobView::deserialize_from_bytes_borrowed(&payload).unwrap();
+    assert_eq!(owned.tag, view.tag);
+    assert_eq!(&owned.data[..], view.data);
+}
+
+#[test]
+fn truncated_before_array() {
+    // The length is zero-extended, so both types have an empty array
+    let payload = [0x11];
+    let owned = Blob::deserialize_from_bytes(&payload).unwrap();
+    let view = BlobView::deserialize_from_bytes_borrowed(&payload).unwrap();
+    assert_eq!(
+        Blob {
+            tag: 0x11,
+            data: heapless::Vec::new()
+        },
+        owned
+    );
+    assert_eq!(
+        BlobView {
+            tag: 0x11,
+            data: &[]
+        },
+        view
+    );
+}
+
+#[test]
+fn truncated_inside_array() {
+    // The owned type fills the missing bytes with zeros, but the view type can't borrow them
+    let payload = [0x11, 3, 0xa];
+    let owned = Blob::deserialize_from_bytes(&payload).unwrap();
+    assert_eq!(&[0xa, 0, 0], &owned.data[..]);
+    assert!(matches!(
+        BlobView::deserialize_from_bytes_borrowed(&payload),
+        Err(DeserializeError::ArrayLength)
+    ));
+}
@@ -0,0 +1,170 @@
+//!
+//! Header and data CRC calculation
+//!
+//! With the `crc-table` feature (enabled by default), the CRCs are calculated one byte at a time
+//! using lookup tables. The header CRC table uses 512 bytes and the data CRC table uses 1024 bytes
+//! of read-only memory. Without that feature, the CRCs are calculated one bit at a time, which is
+//! slower but needs less code and no tables.
+//!
+
+/// Header CRC polynomial, not reversed
+const HEADER_CRC_POLY: u16 = 0x1021;
+/// Data CRC polynomial, reversed
+const DATA_CRC_POLY: u32 = 0x82f6_3b78;
+
+/// Calculates the header CRC (CRC-16/CCITT-FALSE)
+#[derive(Debug, Clone)]
+pub struct HeaderCrc {
+    value: u16,
+}
+
+impl HeaderCrc {
+    /// Creates a new CRC calculation
+    pub fn new() -> Self {
+        HeaderCrc { value: 0xffff }
+    }
+
+    /// Adds bytes to the CRC calculation
+    #[cfg(not(feature = "crc-table"))]
+    pub fn digest(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.value = header_crc_byte(self.value ^ (u16::from(byte) << 8));
+        }
+    }
+
+    /// Adds bytes to the CRC calculation
+    #[cfg(feature = "crc-table")]
+    pub fn digest(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let index = usize::from((self.value >> 8) as u8 ^ byte);
+            self.value = (self.value << 8) ^ HEADER_CRC_TABLE[index];
+        }
+    }
+
+    /// Returns the CRC of the bytes added so far
+    pub fn get_crc(&self) -> u16 {
+        self.value
+    }
+}
+
+impl Default for HeaderCrc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculates the data CRC (CRC-32C), used for payloads and transfers
+#[derive(Debug, Clone)]
+pub struct DataCrc {
+    value: u32,
+}
+
+impl DataCrc {
+    /// Creates a new CRC calculation
+    pub fn new() -> Self {
+        DataCrc { value: 0xffff_ffff }
+    }
+
+    /// Adds bytes to the CRC calculation
+    #[cfg(not(feature = "crc-table"))]
+    pub fn digest(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.value = data_crc_byte(self.value ^ u32::from(byte));
+        }
+    }
+
+    /// Adds bytes to the CRC calculation
+    #[cfg(feature = "crc-table")]
+    pub fn digest(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let index = usize::from(self.value as u8 ^ byte);
+            self.value = (self.value >> 8) ^ DATA_CRC_TABLE[index];
+        }
+    }
+
+    /// Returns the CRC of the bytes added so far
+    pub fn get_crc(&self) -> u32 {
+        !self.value
+    }
+}
+
+impl Default for DataCrc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shifts the 8 most significant bits out of a header CRC value
+const fn header_crc_byte(mut value: u16) -> u16 {
+    let mut bit = 0;
+    while bit < 8 {
+        value = if (value & 0x8000) != 0 {
+            (value << 1) ^ HEADER_CRC_POLY
+        } else {
+            value << 1
+        };
+        bit += 1;
+    }
+    value
+}
+
+/// Shifts the 8 least significant bits out of a data CRC value
+const fn data_crc_byte(mut value: u32) -> u32 {
+    let mut bit = 0;
+    while bit < 8 {
+        value = if (value & 1) != 0 {
+            (value >> 1) ^ DATA_CRC_POLY
+        } else {
+            value >> 1
+        };
+        bit += 1;
+    }
+    value
+}
+
+#[cfg(feature = "crc-table")]
+static HEADER_CRC_TABLE: [u16; 256] = {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = header_crc_byte((i as u16) << 8);
+        i += 1;
+    }
+    table
+};
+
+#[cfg(feature = "crc-table")]
+static DATA_CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = data_crc_byte(i as u32);
+        i += 1;
+    }
+    table
+};
+
+#[cfg(test)]
+mod test {
+    use super::{DataCrc, HeaderCrc};
+
+    #[test]
+    fn check_values() {
+        let mut header_crc = HeaderCrc::new();
+        header_crc.digest(b"123456789");
+        assert_eq!(0x29b1, header_crc.get_crc());
+
+        let mut data_crc = DataCrc::new();
+        data_crc.digest(b"123456789");
+        assert_eq!(0xe306_9283, data_crc.get_crc());
+    }
+
+    #[test]
+    fn split_digest() {
+        let mut data_crc = DataCrc::new();
+        data_crc.digest(b"1234");
+        data_crc.digest(b"");
+        data_crc.digest(b"56789");
+        assert_eq!(0xe306_9283, data_crc.get_crc());
+    }
+}
@@ -1,16 +1,17 @@
 #![no_std]
 
 extern crate canadensis_core;
-extern crate crc_any;
 extern crate zerocopy;
 
+pub mod crc;
+
+use crate::crc::HeaderCrc;
 use canadensis_core::time::Microseconds32;
 use canadensis_core::transfer::{MessageHeader, ServiceHeader};
 use canadensis_core::transport::{TransferId, Transport};
 use canadensis_core::{InvalidValue, Priority, ServiceId, SubjectId};
 use core::convert::TryFrom;
 use core::mem;
-use crc_any::CRCu16;
 use zerocopy::byteorder::{U16, U32, U64};
 use zerocopy::{AsBytes, BigEndian, FromBytes, LittleEndian};
 
@@ -330,8 +331,8 @@ fn check_node_id(id: u16) -> Result<Option<NodeId16>, InvalidValue> {
 }
 
 /// Returns a CRC calculator used for headers
-pub fn header_crc() -> CRCu16 {
-    CRCu16::crc16ccitt_false()
+pub fn header_crc() -> HeaderCrc {
+    HeaderCrc::new()
 }
 
 /// A 16-bit node ID
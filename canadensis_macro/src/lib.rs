@@ -11,6 +11,7 @@ use proc_macro2::{Ident, Span, TokenTree};
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::env;
+use std::fs;
 use syn::spanned::Spanned;
 
 /// Parses one or more DSDL definitions and expands into the corresponding Rust code
@@ -75,15 +76,29 @@ fn eval_generate_function(
     external_packages: &BTreeMap<Vec<String>, Vec<String>>,
     config: &Config,
 ) -> Result<proc_macro2::TokenStream, String> {
+    // Include each DSDL file in the output so that the compiler expands this macro again
+    // when a file changes
+    let tracked_files: Vec<String> = package
+        .paths()
+        .map(|path| {
+            fs::canonicalize(path)
+                .unwrap_or_else(|_| path.to_owned())
+                .display()
+                .to_string()
+        })
+        .collect();
     let compiled = package
         .compile(config)
         .map_err(|e| format!("Failed to compile DSDL: {}", ErrorChain(e)))?;
     let code = canadensis_codegen_rust::generate_code(&compiled, external_packages)
         .map_err(|e| format!("Failed to generate code from DSDL: {}", ErrorChain(e)))?;
     let code_string = code.to_string();
-    let parsed_code: proc_macro2::TokenStream = code_string
+    let mut parsed_code: proc_macro2::TokenStream = code_string
         .parse()
         .expect("Internal error: Generated invalid code");
+    parsed_code.extend(tracked_files.iter().map(|path| {
+        quote::quote! { const _: &[u8] = include_bytes!(#path); }
+    }));
     Ok(parsed_code)
 }
 
@@ -0,0 +1,104 @@
+//! Checks the padding around nested variable-length composite values
+
+extern crate canadensis_encoding;
+extern crate canadensis_macro;
+
+use canadensis_encoding::{Deserialize, Serialize};
+use canadensis_macro::types_from_dsdl;
+
+types_from_dsdl! {
+    type "canadensis.Inner.1.0" { r#"
+uint8[<=4] bytes
+@sealed
+    "#}
+    type "canadensis.UnalignedInner.1.0" { r#"
+uint8[<=4] bytes
+bool flag
+@sealed
+    "#}
+    type "canadensis.PaddingBefore.1.0" { r#"
+uint3 a
+Inner.1.0 inner
+bool b
+@sealed
+    "#}
+    type "canadensis.PaddingAfter.1.0" { r#"
+UnalignedInner.1.0 inner
+uint8 c
+@sealed
+    "#}
+    type "canadensis.UnalignedArray.1.0" { r#"
+bool flag
+Inner.1.0[<=2] items
+@sealed
+    "#}
+    generate()
+}
+
+use canadensis::inner_1_0::Inner;
+use canadensis::padding_after_1_0::PaddingAfter;
+use canadensis::padding_before_1_0::PaddingBefore;
+use canadensis::unaligned_array_1_0::UnalignedArray;
+use canadensis::unaligned_inner_1_0::UnalignedInner;
+
+fn inner(bytes: &[u8]) -> Inner {
+    Inner {
+        bytes: heapless::Vec::from_slice(bytes).unwrap(),
+    }
+}
+
+#[test]
+fn padding_before_composite() {
+    let value = PaddingBefore {
+        a: 5,
+        inner: inner(&[1, 2]),
+        b: true,
+    };
+    // 3 bits, 5 bits of padding, 24 bits of inner, 1 bit
+    assert_eq!(33, value.size_bits());
+    let mut bytes = [0u8; 5];
+    value.serialize_to_bytes(&mut bytes);
+    assert_eq!([5, 2, 1, 2, 1], bytes);
+    let decoded = PaddingBefore::deserialize_from_bytes(&bytes).unwrap();
+    assert_eq!(5, decoded.a);
+    assert_eq!(&[1, 2], &decoded.inner.bytes[..]);
+    assert!(decoded.b);
+}
+
+#[test]
+fn padding_after_composite() {
+    let value = PaddingAfter {
+        inner: UnalignedInner {
+            bytes: heapless::Vec::from_slice(&[9]).unwrap(),
+            flag: true,
+        },
+        c: 0x37,
+    };
+    // 17 bits of inner, 7 bits of padding, 8 bits
+    assert_eq!(32, value.size_bits());
+    let mut bytes = [0u8; 4];
+    value.serialize_to_bytes(&mut bytes);
+    assert_eq!([1, 9, 1, 0x37], bytes);
+    let decoded = PaddingAfter::deserialize_from_bytes(&bytes).unwrap();
+    assert_eq!(&[9], &decoded.inner.bytes[..]);
+    assert!(decoded.inner.flag);
+    assert_eq!(0x37, decoded.c);
+}
+
+#[test]
+fn unaligned_composite_array() {
+    let value = UnalignedArray {
+        flag: true,
+        items: core::iter::once(inner(&[7])).collect(),
+    };
+    // 1 bit, 7 bits of padding, 8-bit length, 16 bits of inner
+    assert_eq!(32, value.size_bits());
+    let mut bytes = [0u8; 4];
+    value.serialize_to_bytes(&mut bytes);
+    // The array length is aligned to a byte boundary
+    assert_eq!([1, 1, 1, 7], bytes);
+    let decoded = UnalignedArray::deserialize_from_bytes(&bytes).unwrap();
+    assert!(decoded.flag);
+    assert_eq!(1, decoded.items.len());
+    assert_eq!(&[7], &decoded.items[0].bytes[..]);
+}
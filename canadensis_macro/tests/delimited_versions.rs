@@ -0,0 +1,100 @@
+//! Checks that a nested delimited value from a newer version of its type can be read using
+//! an older version
+
+extern crate canadensis_encoding;
+extern crate canadensis_macro;
+
+use canadensis_encoding::{DataType, Deserialize, DeserializeError, Serialize};
+use canadensis_macro::types_from_dsdl;
+
+types_from_dsdl! {
+    type "canadensis.Setpoint.1.0" { r#"
+uint16 value
+@extent 4 * 8
+    "#}
+    type "canadensis.Setpoint.1.1" { r#"
+uint16 value
+uint8 rate
+uint8 flags
+@extent 4 * 8
+    "#}
+    type "canadensis.Command.1.0" { r#"
+Setpoint.1.0 setpoint
+uint8 sequence
+@sealed
+    "#}
+    type "canadensis.Command.1.1" { r#"
+Setpoint.1.1 setpoint
+uint8 sequence
+@sealed
+    "#}
+    generate()
+}
+
+use canadensis::command_1_0::Command as Command10;
+use canadensis::command_1_1::Command as Command11;
+use canadensis::setpoint_1_0::Setpoint as Setpoint10;
+use canadensis::setpoint_1_1::Setpoint as Setpoint11;
+
+#[test]
+fn newer_version_fields_skipped() {
+    let newer = Command11 {
+        setpoint: Setpoint11 {
+            value: 0x1234,
+            rate: 9,
+            flags: 0x80,
+        },
+        sequence: 77,
+    };
+    let mut bytes = [0u8; 9];
+    newer.serialize_to_bytes(&mut bytes);
+    // Delimiter header, setpoint, sequence
+    assert_eq!([4, 0, 0, 0, 0x34, 0x12, 9, 0x80, 77], bytes);
+
+    // The older version reads the value and skips the rate and flags
+    let older = Command10::deserialize_from_bytes(&bytes).unwrap();
+    assert_eq!(0x1234, { older.setpoint.value });
+    assert_eq!(77, older.sequence);
+}
+
+#[test]
+fn older_version_fields_zero_extended() {
+    let older = Command10 {
+        setpoint: Setpoint10 { value: 0x1234 },
+        sequence: 77,
+    };
+    let mut bytes = [0u8; 7];
+    older.serialize_to_bytes(&mut bytes);
+    assert_eq!([2, 0, 0, 0, 0x34, 0x12, 77], bytes);
+
+    // The newer version reads zero for the fields that the older version does not have
+    let newer = Command11::deserialize_from_bytes(&bytes).unwrap();
+    assert_eq!(0x1234, { newer.setpoint.value });
+    assert_eq!(0, newer.setpoint.rate);
+    assert_eq!(0, newer.setpoint.flags);
+    assert_eq!(77, newer.sequence);
+}
+
+#[test]
+fn delimiter_header_larger_than_extent() {
+    assert_eq!(Some(4), Setpoint10::EXTENT_BYTES);
+    // A setpoint that claims to be 5 bytes long, which is more than the extent
+    let bytes = [5, 0, 0, 0, 0x34, 0x12, 9, 0x80, 1, 77];
+    assert!(matches!(
+        Command10::deserialize_from_bytes(&bytes),
+        Err(DeserializeError::DelimitedLength)
+    ));
+    assert!(matches!(
+        Command11::deserialize_from_bytes(&bytes),
+        Err(DeserializeError::DelimitedLength)
+    ));
+}
+
+#[test]
+fn delimiter_header_larger_than_payload() {
+    let bytes = [4, 0, 0, 0, 0x34, 0x12];
+    assert!(matches!(
+        Command11::deserialize_from_bytes(&bytes),
+        Err(DeserializeError::DelimitedLength)
+    ));
+}
@@ -42,3 +42,23 @@ fn encoding_1() {
     let decoded = TestRequest::deserialize_from_bytes(&bytes).unwrap();
     assert_eq!(99, { decoded.a });
 }
+
+#[test]
+fn utf8_string() {
+    use canadensis::utf8_variable_size_array_1_0::Utf8VariableSizeArray;
+    let mut value = Utf8VariableSizeArray {
+        dessert_name: Default::default(),
+    };
+    value.dessert_name.push_str("crème brûlée").unwrap();
+
+    let mut bytes = [0u8; 17];
+    value.serialize_to_bytes(&mut bytes);
+    assert_eq!(bytes[0], 15);
+    assert_eq!(&bytes[1..16], "crème brûlée".as_bytes());
+    let decoded = Utf8VariableSizeArray::deserialize_from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.dessert_name, "crème brûlée");
+
+    // Invalid UTF-8
+    bytes[1] = 0xff;
+    assert!(Utf8VariableSizeArray::deserialize_from_bytes(&bytes).is_err());
+}
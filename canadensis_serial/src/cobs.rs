@@ -3,6 +3,7 @@
 //! Source: <http://www.stuartcheshire.org/papers/COBSforToN.pdf>
 
 use canadensis_core::OutOfMemoryError;
+use core::fmt;
 
 /// Encodes a sequence of bytes from an iterator and writes the encoded form to a destination
 ///
@@ -15,42 +16,80 @@ pub fn escape_from_iter<I>(source: I, destination: &mut [u8]) -> Result<usize, O
 where
     I: IntoIterator<Item = u8>,
 {
-    // Index in destination to write the next data byte
-    let mut dest_current = 1usize;
-    // Index in destination to write the next code byte
-    let mut dest_code = 0usize;
-    let mut code: u8 = 0x1;
-
+    let mut escaper = Escaper::new();
     for byte in source {
+        escaper.accept(byte, destination)?;
+    }
+    escaper.finish(destination)
+}
+
+/// A streaming escaper that accepts one byte at a time and writes the encoded form to a
+/// destination
+///
+/// The same destination must be passed to every call.
+pub struct Escaper {
+    /// Index in destination to write the next data byte
+    dest_current: usize,
+    /// Index in destination to write the next code byte
+    dest_code: usize,
+    code: u8,
+}
+
+impl Escaper {
+    pub fn new() -> Self {
+        Escaper {
+            dest_current: 1,
+            dest_code: 0,
+            code: 0x1,
+        }
+    }
+
+    /// Encodes a byte
+    ///
+    /// This function returns an error if the destination is not long enough.
+    pub fn accept(&mut self, byte: u8, destination: &mut [u8]) -> Result<(), OutOfMemoryError> {
         if byte == 0 {
             // Write the code (finish block operation)
-            let code_entry = destination.get_mut(dest_code).ok_or(OutOfMemoryError)?;
-            *code_entry = code;
-            code = 0x1;
+            let code_entry = destination
+                .get_mut(self.dest_code)
+                .ok_or(OutOfMemoryError)?;
+            *code_entry = self.code;
+            self.code = 0x1;
             // Advance the place where the next code will be stored
-            dest_code = dest_current;
-            dest_current += 1;
+            self.dest_code = self.dest_current;
+            self.dest_current += 1;
         } else {
-            if code == 0xff {
+            if self.code == 0xff {
                 // No zeros in 254 bytes (finish block operation)
-                let entry = destination.get_mut(dest_code).ok_or(OutOfMemoryError)?;
-                *entry = code;
-                code = 0x1;
-                dest_code = dest_current;
-                dest_current += 1;
+                let entry = destination
+                    .get_mut(self.dest_code)
+                    .ok_or(OutOfMemoryError)?;
+                *entry = self.code;
+                self.code = 0x1;
+                self.dest_code = self.dest_current;
+                self.dest_current += 1;
             }
             // Copy a normal, non-zero byte
-            let entry = destination.get_mut(dest_current).ok_or(OutOfMemoryError)?;
+            let entry = destination
+                .get_mut(self.dest_current)
+                .ok_or(OutOfMemoryError)?;
             *entry = byte;
-            dest_current += 1;
-            code += 1;
+            self.dest_current += 1;
+            self.code += 1;
         }
+        Ok(())
+    }
+
+    /// Finishes encoding and returns the number of bytes that were written to the destination
+    pub fn finish(self, destination: &mut [u8]) -> Result<usize, OutOfMemoryError> {
+        // Handle the logical zero at the end of the input, if one is not there already
+        let code_entry = destination
+            .get_mut(self.dest_code)
+            .ok_or(OutOfMemoryError)?;
+        *code_entry = self.code;
+        // Return the index of the last byte written in the destination
+        Ok(self.dest_current)
     }
-    // Handle the logical zero at the end of the input, if one is not there already
-    let code_entry = destination.get_mut(dest_code).ok_or(OutOfMemoryError)?;
-    *code_entry = code;
-    // Return the index of the last byte written in the destination
-    Ok(dest_current)
 }
 /// A streaming unescaper that accepts one character at a time
 pub struct Unescaper {
@@ -101,6 +140,14 @@ pub fn escaped_size(raw_size: usize) -> usize {
 #[derive(Debug)]
 pub struct DecodeZeroError;
 
+impl fmt::Display for DecodeZeroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Unexpected zero byte")
+    }
+}
+
+impl core::error::Error for DecodeZeroError {}
+
 #[cfg(test)]
 mod tests {
     use crate::cobs::{escape_from_iter, Unescaper};
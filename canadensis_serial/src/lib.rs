@@ -3,16 +3,17 @@
 extern crate alloc;
 extern crate canadensis_core;
 extern crate canadensis_header;
-extern crate crc_any;
 extern crate fallible_collections;
 extern crate heapless;
+#[cfg(feature = "log")]
 extern crate log;
 extern crate zerocopy;
 
-use crc_any::CRCu32;
+use core::fmt;
 
 use canadensis_core::transport::Transport;
 use canadensis_core::{OutOfMemoryError, Priority};
+use canadensis_header::crc::DataCrc;
 use canadensis_header::{NodeId16, TransferId64};
 
 pub use crate::rx::{SerialReceiver, Subscription};
@@ -47,7 +48,7 @@ pub type SerialTransferId = TransferId64;
 
 /// Calculates the CRC of a payload
 fn make_payload_crc(payload: &[u8]) -> u32 {
-    let mut crc = CRCu32::crc32c();
+    let mut crc = DataCrc::new();
     crc.digest(payload);
     crc.get_crc()
 }
@@ -66,3 +67,24 @@ impl<E> From<OutOfMemoryError> for Error<E> {
         Error::Memory(oom)
     }
 }
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Memory(_) => f.write_str("Out of memory"),
+            Error::Driver(_) => f.write_str("Driver error"),
+        }
+    }
+}
+
+impl<E> core::error::Error for Error<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::Memory(_) => None,
+            Error::Driver(inner) => Some(inner),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use core::mem;
 
 use fallible_collections::{FallibleVec, TryHashMap};
 
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::subscription::SubscriptionManager;
 use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
 use canadensis_core::transfer::{Header, Transfer};
@@ -20,12 +21,16 @@ use crate::{make_payload_crc, Error, SerialNodeId, SerialTransferId, SerialTrans
 /// A serial transport receiver
 ///
 /// This implementation does not support multi-frame transfers or timestamps.
-pub struct SerialReceiver<C, D, S> {
+///
+/// The profiler `P` measures the time spent handling each incoming byte.
+pub struct SerialReceiver<C, D, S, P = NoProfiler> {
     state: State,
     node_id: Option<SerialNodeId>,
     subscriptions: S,
     _driver: PhantomData<D>,
     _clock: PhantomData<C>,
+    /// Measures the duration of operations
+    profiler: P,
 }
 
 impl<C, D, S> SerialReceiver<C, D, S>
@@ -35,24 +40,44 @@ where
     S: SubscriptionManager<Subscription> + Default,
 {
     pub fn new(node_id: SerialNodeId) -> Self {
-        SerialReceiver {
-            state: State::Idle,
-            node_id: Some(node_id),
-            subscriptions: S::default(),
-            _driver: PhantomData,
-            _clock: PhantomData,
-        }
+        Self::with_profiler(Some(node_id), NoProfiler)
     }
     pub fn new_anonymous() -> Self {
+        Self::with_profiler(None, NoProfiler)
+    }
+}
+
+impl<C, D, S, P> SerialReceiver<C, D, S, P>
+where
+    C: Clock,
+    D: ReceiveDriver,
+    S: SubscriptionManager<Subscription> + Default,
+    P: Profiler,
+{
+    /// Creates a receiver that reports the duration of operations to a profiler
+    ///
+    /// node_id: The ID of this node, or None if this node is anonymous
+    pub fn with_profiler(node_id: Option<SerialNodeId>, profiler: P) -> Self {
         SerialReceiver {
             state: State::Idle,
-            node_id: None,
+            node_id,
             subscriptions: S::default(),
             _driver: PhantomData,
             _clock: PhantomData,
+            profiler,
         }
     }
 
+    /// Returns a reference to the profiler
+    pub fn profiler(&self) -> &P {
+        &self.profiler
+    }
+
+    /// Returns a mutable reference to the profiler
+    pub fn profiler_mut(&mut self) -> &mut P {
+        &mut self.profiler
+    }
+
     fn clean_expired_sessions(&mut self, now: Microseconds32) {
         self.subscriptions
             .for_each_message_subscription_mut(|sub| sub.clean_expired_sessions(now));
@@ -79,6 +104,7 @@ where
             State::BetweenTransfers => {
                 if byte != 0 {
                     // Start decoding
+                    #[cfg(feature = "log")]
                     log::debug!("Starting frame");
                     let mut unescaper = Unescaper::new();
                     match unescaper.accept(byte) {
@@ -132,13 +158,16 @@ where
                                         }
                                     } else {
                                         // Not interested in this transfer
+                                        #[cfg(feature = "log")]
                                         log::debug!("Got header, but not subscribed");
                                         State::Idle
                                     }
                                 }
+                                #[cfg_attr(not(feature = "log"), allow(unused_variables))]
                                 Err(e) => {
                                     // Invalid header CRC or format
-                                    log::debug!("Header format or CRC invalid: {:?}", e);
+                                    #[cfg(feature = "log")]
+                                    log::warn!("Header format or CRC invalid: {:?}", e);
                                     State::Idle
                                 }
                             }
@@ -197,11 +226,12 @@ where
     }
 }
 
-impl<C, D, S> Receiver<C> for SerialReceiver<C, D, S>
+impl<C, D, S, P> Receiver<C> for SerialReceiver<C, D, S, P>
 where
     C: Clock,
     D: ReceiveDriver,
     S: SubscriptionManager<Subscription> + Default,
+    P: Profiler,
 {
     type Transport = SerialTransport;
     type Driver = D;
@@ -215,11 +245,21 @@ where
         self.clean_expired_sessions(clock.now());
         loop {
             match driver.receive_byte() {
-                Ok(byte) => match self.handle_byte(byte, clock.now()) {
-                    Ok(Some(transfer)) => break Ok(Some(transfer)),
-                    Ok(None) => { /* Keep going and try another byte */ }
-                    Err(e) => break Err(e),
-                },
+                Ok(byte) => {
+                    let now = clock.now();
+                    let start = self.profiler.counter();
+                    let result = self.handle_byte(byte, now);
+                    let operation = match result {
+                        Ok(Some(_)) => Operation::Reassemble,
+                        _ => Operation::Accept,
+                    };
+                    self.profiler.finish(operation, start);
+                    match result {
+                        Ok(Some(transfer)) => break Ok(Some(transfer)),
+                        Ok(None) => { /* Keep going and try another byte */ }
+                        Err(e) => break Err(e),
+                    }
+                }
                 Err(nb::Error::WouldBlock) => break Ok(None),
                 Err(nb::Error::Other(e)) => break Err(Error::Driver(e)),
             }
@@ -242,6 +282,16 @@ where
         self.subscriptions.unsubscribe_message(subject);
     }
 
+    fn set_accept_anonymous(&mut self, subject: SubjectId, accept: bool) -> bool {
+        match self.subscriptions.find_message_subscription_mut(subject) {
+            Some(subscription) => {
+                subscription.accept_anonymous = accept;
+                true
+            }
+            None => false,
+        }
+    }
+
     fn subscribe_request(
         &mut self,
         service: ServiceId,
@@ -295,7 +345,7 @@ where
     }
 }
 
-impl<C, D, S> SerialReceiver<C, D, S>
+impl<C, D, S, P> SerialReceiver<C, D, S, P>
 where
     C: Clock,
     S: SubscriptionManager<Subscription>,
@@ -330,8 +380,8 @@ where
     }
 
     /// Returns true if this receiver has a matching subscription, its last transfer ID is less
-    /// than the provided header's transfer ID, and (for service transfers) this node is the
-    /// destination
+    /// than the provided header's transfer ID, it accepts the header's source (which may be
+    /// anonymous), and (for service transfers) this node is the destination
     fn is_interested(&self, header: &Header<SerialTransport>) -> Option<&Subscription> {
         self.subscriptions
             .find_subscription(header)
@@ -354,8 +404,20 @@ where
                         }
                     }
                     None => {
-                        // Anonymous transfers can't take advantage of deduplication. Always accept.
-                        Some(subscription)
+                        // Anonymous transfers can't take advantage of deduplication. Accept them
+                        // if the subscription allows it.
+                        if subscription.accept_anonymous {
+                            Some(subscription)
+                        } else {
+                            // This is not an error, so it is only logged at trace level
+                            #[cfg(feature = "log")]
+                            log::trace!(
+                                "Ignoring anonymous transfer {:?} on port {:?}",
+                                header.transfer_id(),
+                                header.port_id()
+                            );
+                            None
+                        }
                     }
                 }
             })
@@ -375,6 +437,13 @@ where
             let payload = payload_and_crc;
             if crc != make_payload_crc(&payload) {
                 // Incorrect CRC
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "Dropped transfer {:?} on port {:?} from {:?}: incorrect payload CRC",
+                    header.transfer_id(),
+                    header.port_id(),
+                    header.source()
+                );
                 return None;
             }
 
@@ -402,6 +471,13 @@ where
             }
         } else {
             // Not enough bytes for a CRC
+            #[cfg(feature = "log")]
+            log::warn!(
+                "Dropped transfer {:?} on port {:?} from {:?}: too short for a payload CRC",
+                header.transfer_id(),
+                header.port_id(),
+                header.source()
+            );
             None
         }
     }
@@ -416,6 +492,8 @@ pub struct Subscription {
     ///
     /// This is used to remove duplicates
     sessions: TryHashMap<SerialNodeId, Session>,
+    /// If transfers from anonymous nodes are accepted
+    accept_anonymous: bool,
 }
 
 impl Subscription {
@@ -424,6 +502,7 @@ impl Subscription {
             payload_size_max,
             timeout,
             sessions: Default::default(),
+            accept_anonymous: true,
         }
     }
 
@@ -5,12 +5,15 @@ use fallible_collections::FallibleVec;
 use heapless::Deque;
 use zerocopy::AsBytes;
 
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::time::Clock;
 use canadensis_core::transfer::Transfer;
-use canadensis_core::transport::Transmitter;
+use canadensis_core::transport::{StreamingPayload, Transmitter};
 use canadensis_core::{nb, OutOfMemoryError};
+use canadensis_header::crc::DataCrc;
 use canadensis_header::{Header, RawHeader};
 
+use crate::cobs::Escaper;
 use crate::driver::TransmitDriver;
 use crate::SerialTransport;
 use crate::{cobs, Error};
@@ -25,42 +28,48 @@ const DELIMITER: u8 = 0x0;
 /// A transmitter for the Cyphal/serial transport
 ///
 /// C is the size of the transmit queue in bytes
-pub struct SerialTransmitter<D, const C: usize> {
+///
+/// The profiler `P` measures the time spent escaping transfers into the queue and flushing the
+/// queue.
+pub struct SerialTransmitter<D, const C: usize, P = NoProfiler> {
     /// Queue of outgoing bytes
     queue: TransmitQueue<C>,
     _driver: PhantomData<D>,
+    /// Measures the duration of operations
+    profiler: P,
 }
 
 impl<D, const C: usize> SerialTransmitter<D, C> {
     pub fn new() -> Self {
+        Self::with_profiler(NoProfiler)
+    }
+}
+
+impl<D, const C: usize, P> SerialTransmitter<D, C, P> {
+    /// Creates a transmitter that reports the duration of operations to a profiler
+    pub fn with_profiler(profiler: P) -> Self {
         SerialTransmitter {
             queue: TransmitQueue::new(),
             _driver: PhantomData,
+            profiler,
         }
     }
-}
 
-impl<D, const C: usize> Default for SerialTransmitter<D, C> {
-    fn default() -> Self {
-        SerialTransmitter::new()
+    /// Returns a reference to the profiler
+    pub fn profiler(&self) -> &P {
+        &self.profiler
     }
-}
 
-impl<L, D, const C: usize> Transmitter<L> for SerialTransmitter<D, C>
-where
-    L: Clock,
-    D: TransmitDriver,
-{
-    type Transport = SerialTransport;
-    type Driver = D;
-    type Error = Error<D::Error>;
+    /// Returns a mutable reference to the profiler
+    pub fn profiler_mut(&mut self) -> &mut P {
+        &mut self.profiler
+    }
 
-    fn push<A>(
+    /// Escapes a transfer and adds it to the queue
+    fn push_inner<A>(
         &mut self,
-        transfer: Transfer<A, Self::Transport>,
-        _clock: &mut L,
-        _driver: &mut D,
-    ) -> nb::Result<(), Self::Error>
+        transfer: Transfer<A, SerialTransport>,
+    ) -> Result<(), OutOfMemoryError>
     where
         A: AsRef<[u8]>,
     {
@@ -70,13 +79,13 @@ where
         let length_on_wire = escaped_length + PER_FRAME_UNESCAPED_OVERHEAD;
 
         if length_on_wire > (self.queue.capacity() - self.queue.len()) {
-            return Err(nb::Error::Other(Error::Memory(OutOfMemoryError)));
+            return Err(OutOfMemoryError);
         }
         let header = RawHeader::from(Header::from(transfer.header));
         let payload_crc = crate::make_payload_crc(transfer.payload.as_ref());
         // Escape the header, payload, and payload CRC into a temporary buffer
-        let mut escape_buffer: Vec<u8> = FallibleVec::try_with_capacity(escaped_length)
-            .map_err(|e| Error::Memory(OutOfMemoryError::from(e)))?;
+        let mut escape_buffer: Vec<u8> =
+            FallibleVec::try_with_capacity(escaped_length).map_err(OutOfMemoryError::from)?;
         escape_buffer.resize(escaped_length, 0);
 
         let data_to_escape = header
@@ -87,23 +96,63 @@ where
             .chain(payload_crc.as_bytes().iter().copied());
         let escaped_length = cobs::escape_from_iter(data_to_escape, &mut escape_buffer)
             .expect("Incorrect escaped length");
-        // Calculate the required queue capacity based on the real escaped length
+        self.enqueue_frame(&escape_buffer[..escaped_length])
+    }
+
+    /// Escapes the payload pieces of a transfer directly into the frame and adds it to the queue
+    fn push_streaming_inner<Q>(
+        &mut self,
+        transfer: Transfer<&Q, SerialTransport>,
+    ) -> Result<(), OutOfMemoryError>
+    where
+        Q: StreamingPayload + ?Sized,
+    {
+        // Check queue capacity with worst-case escaping
+        let frame_length = transfer.payload.payload_length() + PER_FRAME_ESCAPED_OVERHEAD;
+        let escaped_length = cobs::escaped_size(frame_length);
         let length_on_wire = escaped_length + PER_FRAME_UNESCAPED_OVERHEAD;
-        if length_on_wire > (self.queue.capacity() - self.queue.len()) {
-            return Err(nb::Error::Other(Error::Memory(OutOfMemoryError)));
-        }
 
-        // Put in the queue: delimiter, escaped data, delimiter
-        self.queue.push_back(DELIMITER).unwrap();
-        for &byte in &escape_buffer[..escaped_length] {
-            self.queue.push_back(byte).unwrap();
+        if length_on_wire > (self.queue.capacity() - self.queue.len()) {
+            return Err(OutOfMemoryError);
         }
-        self.queue.push_back(DELIMITER).unwrap();
+        let header = RawHeader::from(Header::from(transfer.header));
+        let mut escape_buffer: Vec<u8> =
+            FallibleVec::try_with_capacity(escaped_length).map_err(OutOfMemoryError::from)?;
+        escape_buffer.resize(escaped_length, 0);
 
-        Ok(())
+        // Escape the header, payload, and payload CRC. This fails only if the payload is longer
+        // than its payload_length() function reported.
+        let mut escaper = Escaper::new();
+        let mut payload_crc = DataCrc::new();
+        let mut result = header
+            .as_bytes()
+            .iter()
+            .try_for_each(|&byte| escaper.accept(byte, &mut escape_buffer));
+        transfer.payload.write_to(&mut |piece| {
+            payload_crc.digest(piece);
+            if result.is_ok() {
+                result = piece
+                    .iter()
+                    .try_for_each(|&byte| escaper.accept(byte, &mut escape_buffer));
+            }
+        });
+        result
+            .and_then(|()| {
+                payload_crc
+                    .get_crc()
+                    .as_bytes()
+                    .iter()
+                    .try_for_each(|&byte| escaper.accept(byte, &mut escape_buffer))
+            })
+            .and_then(|()| escaper.finish(&mut escape_buffer))
+            .and_then(|escaped_length| self.enqueue_frame(&escape_buffer[..escaped_length]))
     }
 
-    fn flush(&mut self, _clock: &mut L, driver: &mut D) -> nb::Result<(), Self::Error> {
+    /// Sends bytes from the queue until it is empty or the driver cannot accept more
+    fn flush_inner(&mut self, driver: &mut D) -> nb::Result<(), Error<D::Error>>
+    where
+        D: TransmitDriver,
+    {
         while let Some(byte) = self.queue.pop_front() {
             match driver.send_byte(byte) {
                 Ok(()) => {}
@@ -113,16 +162,88 @@ where
                     self.queue
                         .push_front(byte)
                         .expect("No space to return byte to queue");
-                    return match e {
-                        nb::Error::WouldBlock => Err(nb::Error::WouldBlock),
-                        nb::Error::Other(e) => Err(nb::Error::Other(Error::Driver(e))),
-                    };
+                    return Err(e.map(Error::Driver));
                 }
             }
         }
         Ok(())
     }
 
+    /// Adds an escaped frame to the queue with a delimiter before and after it
+    ///
+    /// This function returns an error if the queue does not have enough space for the frame.
+    fn enqueue_frame(&mut self, escaped: &[u8]) -> Result<(), OutOfMemoryError> {
+        // Calculate the required queue capacity based on the real escaped length
+        let length_on_wire = escaped.len() + PER_FRAME_UNESCAPED_OVERHEAD;
+        if length_on_wire > (self.queue.capacity() - self.queue.len()) {
+            return Err(OutOfMemoryError);
+        }
+
+        // Put in the queue: delimiter, escaped data, delimiter
+        self.queue.push_back(DELIMITER).unwrap();
+        for &byte in escaped {
+            self.queue.push_back(byte).unwrap();
+        }
+        self.queue.push_back(DELIMITER).unwrap();
+        Ok(())
+    }
+}
+
+impl<D, const C: usize> Default for SerialTransmitter<D, C> {
+    fn default() -> Self {
+        SerialTransmitter::new()
+    }
+}
+
+impl<L, D, const C: usize, P> Transmitter<L> for SerialTransmitter<D, C, P>
+where
+    L: Clock,
+    D: TransmitDriver,
+    P: Profiler,
+{
+    type Transport = SerialTransport;
+    type Driver = D;
+    type Error = Error<D::Error>;
+
+    fn push<A>(
+        &mut self,
+        transfer: Transfer<A, Self::Transport>,
+        _clock: &mut L,
+        _driver: &mut D,
+    ) -> nb::Result<(), Self::Error>
+    where
+        A: AsRef<[u8]>,
+    {
+        let start = self.profiler.counter();
+        let result = self.push_inner(transfer);
+        self.profiler.finish(Operation::Serialize, start);
+        result.map_err(|e| nb::Error::Other(Error::Memory(e)))
+    }
+
+    /// Escapes the payload pieces directly into the frame, without collecting them into one
+    /// buffer first
+    fn push_streaming<Q>(
+        &mut self,
+        transfer: Transfer<&Q, Self::Transport>,
+        _clock: &mut L,
+        _driver: &mut D,
+    ) -> nb::Result<(), Self::Error>
+    where
+        Q: StreamingPayload + ?Sized,
+    {
+        let start = self.profiler.counter();
+        let result = self.push_streaming_inner(transfer);
+        self.profiler.finish(Operation::Serialize, start);
+        result.map_err(|e| nb::Error::Other(Error::Memory(e)))
+    }
+
+    fn flush(&mut self, _clock: &mut L, driver: &mut D) -> nb::Result<(), Self::Error> {
+        let start = self.profiler.counter();
+        let result = self.flush_inner(driver);
+        self.profiler.finish(Operation::Flush, start);
+        result
+    }
+
     fn mtu(&self) -> usize {
         // Virtually unlimited
         usize::MAX
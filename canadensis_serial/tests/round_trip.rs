@@ -2,6 +2,7 @@ extern crate canadensis_core;
 extern crate canadensis_serial;
 extern crate simplelog;
 
+use canadensis_core::profile::{Operation, Profiler};
 use canadensis_core::subscription::DynamicSubscriptionManager;
 use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
 use canadensis_core::transfer::{Header, MessageHeader, Transfer};
@@ -64,6 +65,129 @@ fn round_trip_no_payload() {
     assert_eq!(transfer, received);
 }
 
+#[test]
+fn reject_anonymous() {
+    let mut driver = MockDriver::default();
+    let subject = SubjectId::try_from(9u16).unwrap();
+    let timeout = MicrosecondDuration32::from_ticks(0);
+    let mut tx = SerialTransmitter::<_, 39>::new();
+    let mut rx: SerialReceiver<ZeroClock, MockDriver, DynamicSubscriptionManager<Subscription>> =
+        SerialReceiver::new(SerialNodeId::try_from(360).unwrap());
+    rx.subscribe_message(subject, 2, timeout, &mut driver)
+        .unwrap();
+    let mut send = |transfer: &Transfer<Vec<u8>, SerialTransport>, driver: &mut MockDriver| {
+        tx.push(transfer.clone(), &mut ZeroClock, driver).unwrap();
+        tx.flush(&mut ZeroClock, driver).unwrap();
+    };
+
+    // By default, anonymous transfers are accepted
+    let anonymous = message(subject, None, 0);
+    send(&anonymous, &mut driver);
+    assert_eq!(
+        Some(anonymous),
+        rx.receive(&mut ZeroClock, &mut driver).unwrap()
+    );
+
+    // A rejected anonymous transfer is skipped, and the receiver continues with the next one
+    assert!(!rx.set_accept_anonymous(SubjectId::try_from(11u16).unwrap(), false));
+    assert!(rx.set_accept_anonymous(subject, false));
+    send(&message(subject, None, 1), &mut driver);
+    let named = message(subject, Some(37u16.try_into().unwrap()), 1);
+    send(&named, &mut driver);
+    assert_eq!(
+        Some(named),
+        rx.receive(&mut ZeroClock, &mut driver).unwrap()
+    );
+    send(&message(subject, None, 2), &mut driver);
+    assert_eq!(None, rx.receive(&mut ZeroClock, &mut driver).unwrap());
+
+    // Subscribing again accepts anonymous transfers
+    rx.subscribe_message(subject, 2, timeout, &mut driver)
+        .unwrap();
+    let anonymous = message(subject, None, 3);
+    send(&anonymous, &mut driver);
+    assert_eq!(
+        Some(anonymous),
+        rx.receive(&mut ZeroClock, &mut driver).unwrap()
+    );
+}
+
+#[test]
+fn profile() {
+    /// A profiler with a counter that increases by one each time it is read
+    #[derive(Default)]
+    struct StepProfiler {
+        counter: u32,
+        records: Vec<(Operation, u32)>,
+    }
+    impl Profiler for StepProfiler {
+        fn counter(&mut self) -> u32 {
+            self.counter = self.counter.wrapping_add(1);
+            self.counter
+        }
+        fn record(&mut self, operation: Operation, duration: u32) {
+            self.records.push((operation, duration));
+        }
+    }
+
+    let mut driver = MockDriver::default();
+    let subject = SubjectId::try_from(9u16).unwrap();
+    let mut tx = SerialTransmitter::<_, 39, _>::with_profiler(StepProfiler::default());
+    let transfer = message(subject, Some(37u16.try_into().unwrap()), 0);
+    tx.push(transfer.clone(), &mut ZeroClock, &mut driver)
+        .unwrap();
+    tx.flush(&mut ZeroClock, &mut driver).unwrap();
+    assert_eq!(
+        &[(Operation::Serialize, 1), (Operation::Flush, 1)],
+        &tx.profiler().records[..]
+    );
+    let wire_length = driver.iter().len();
+
+    let mut rx: SerialReceiver<
+        ZeroClock,
+        MockDriver,
+        DynamicSubscriptionManager<Subscription>,
+        StepProfiler,
+    > = SerialReceiver::with_profiler(
+        Some(SerialNodeId::try_from(360).unwrap()),
+        StepProfiler::default(),
+    );
+    rx.subscribe_message(
+        subject,
+        2,
+        MicrosecondDuration32::from_ticks(0),
+        &mut driver,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(transfer),
+        rx.receive(&mut ZeroClock, &mut driver).unwrap()
+    );
+    // Each byte before the final delimiter is accepted, and the final delimiter completes
+    // the transfer
+    let mut expected = vec![(Operation::Accept, 1); wire_length - 1];
+    expected.push((Operation::Reassemble, 1));
+    assert_eq!(expected, rx.profiler().records);
+}
+
+fn message(
+    subject: SubjectId,
+    source: Option<SerialNodeId>,
+    transfer_id: u64,
+) -> Transfer<Vec<u8>, SerialTransport> {
+    Transfer {
+        header: Header::Message(MessageHeader {
+            timestamp: Microseconds32::from_ticks(0),
+            transfer_id: transfer_id.into(),
+            priority: Priority::Nominal,
+            subject,
+            source,
+        }),
+        loopback: false,
+        payload: vec![0x5a, 0x01],
+    }
+}
+
 /// A driver that stores frames in a queue and allows frames written to be read back
 #[derive(Default)]
 pub struct MockDriver {
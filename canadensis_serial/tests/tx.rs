@@ -52,6 +52,48 @@ fn transmit_minimum_capacity() {
     assert_eq!(queue.len(), MIN_QUEUE_CAPACITY)
 }
 
+#[test]
+fn streaming_matches_push() {
+    let header = Header::Message(MessageHeader {
+        timestamp: Microseconds32::from_ticks(0),
+        transfer_id: 12.into(),
+        priority: Priority::Nominal,
+        subject: 4919u16.try_into().unwrap(),
+        source: Some(59u16.try_into().unwrap()),
+    });
+    // Zeros, and a run of more than 254 non-zero bytes, exercise the escaping
+    let payload: Vec<u8> = (0..600u16).map(|i| (i % 300) as u8).collect();
+    let mut tx = SerialTransmitter::<_, 1024>::new();
+
+    let mut expected = MockDriver::default();
+    tx.push(
+        Transfer {
+            header: header.clone(),
+            loopback: false,
+            payload: &payload,
+        },
+        &mut ZeroClock,
+        &mut expected,
+    )
+    .unwrap();
+    tx.flush(&mut ZeroClock, &mut expected).unwrap();
+
+    let pieces: [&[u8]; 4] = [&payload[..10], &[], &payload[10..400], &payload[400..]];
+    let mut actual = MockDriver::default();
+    tx.push_streaming(
+        Transfer {
+            header,
+            loopback: false,
+            payload: &pieces[..],
+        },
+        &mut ZeroClock,
+        &mut actual,
+    )
+    .unwrap();
+    tx.flush(&mut ZeroClock, &mut actual).unwrap();
+    assert_eq!(expected.bytes, actual.bytes);
+}
+
 /// A driver that stores frames in a queue and allows frames written to be read back
 #[derive(Default)]
 pub struct MockDriver {
@@ -0,0 +1,320 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use canadensis_can::driver::{optimize_filters, ReceiveDriver, TransmitDriver};
+use canadensis_can::{CanNodeId, Frame};
+use canadensis_core::subscription::Subscription;
+use canadensis_core::time::{MicrosecondDuration32, Microseconds32};
+use canadensis_core::{nb, OutOfMemoryError};
+use canadensis_filter_config::Filter;
+
+use crate::clock::SimClock;
+
+/// How a simulated bus delivers frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfig {
+    /// The time from the transmission of a frame to its arrival at the other nodes
+    pub latency: MicrosecondDuration32,
+    /// The maximum random time added to the latency of each frame
+    ///
+    /// Frames still arrive at each node in the order that they were transmitted.
+    pub jitter: MicrosecondDuration32,
+    /// The probability (0 to 1) that a frame is lost on the way to each node
+    ///
+    /// Each node that should receive a frame loses it independently.
+    pub loss: f64,
+    /// The seed for the random numbers that select lost frames and jitter
+    pub seed: u64,
+}
+
+impl Default for SimConfig {
+    /// Returns a configuration with no latency, jitter, or loss
+    fn default() -> Self {
+        SimConfig {
+            latency: MicrosecondDuration32::from_ticks(0),
+            jitter: MicrosecondDuration32::from_ticks(0),
+            loss: 0.0,
+            seed: 1,
+        }
+    }
+}
+
+/// Identifies a driver on a bus
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct DriverId(usize);
+
+/// Counts of what has happened to frames on a bus
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SimStats {
+    /// The number of frames transmitted
+    pub transmitted: u64,
+    /// The number of frames that were not transmitted because their deadlines had passed
+    pub expired: u64,
+    /// The number of frames received by drivers (one frame received by three drivers counts
+    /// three times)
+    pub delivered: u64,
+    /// The number of frames lost on the way to drivers
+    pub lost: u64,
+}
+
+/// A simulated CAN bus that connects any number of drivers
+///
+/// Clones of a bus refer to the same bus.
+#[derive(Debug, Clone)]
+pub struct SimBus {
+    state: Rc<RefCell<BusState>>,
+}
+
+impl SimBus {
+    /// Creates a bus with no drivers attached
+    ///
+    /// The clock must be shared with the nodes on the bus.
+    pub fn new(clock: SimClock, config: SimConfig) -> Self {
+        let random = Random::new(config.seed);
+        SimBus {
+            state: Rc::new(RefCell::new(BusState {
+                clock,
+                config,
+                random,
+                ports: Vec::new(),
+                stats: SimStats::default(),
+            })),
+        }
+    }
+
+    /// Attaches a new driver to this bus and returns it
+    ///
+    /// The driver receives all frames until its filters are changed.
+    pub fn attach(&self) -> SimDriver {
+        let mut state = self.state.borrow_mut();
+        state.ports.push(Port {
+            inbox: VecDeque::new(),
+            filters: None,
+            connected: true,
+            last_delivery: None,
+        });
+        SimDriver {
+            id: DriverId(state.ports.len() - 1),
+            bus: self.clone(),
+        }
+    }
+
+    /// Returns the current configuration
+    pub fn config(&self) -> SimConfig {
+        self.state.borrow().config.clone()
+    }
+
+    /// Changes the latency, jitter, and loss for frames transmitted after this call
+    ///
+    /// The seed in the new configuration is ignored, and the random numbers continue from the
+    /// seed that was used to create the bus.
+    pub fn set_config(&self, config: SimConfig) {
+        self.state.borrow_mut().config = config;
+    }
+
+    /// Connects or disconnects a driver
+    ///
+    /// A disconnected driver still accepts frames to transmit, but the frames do not reach any
+    /// other driver, and it does not receive any frames that other drivers transmit.
+    pub fn set_connected(&self, driver: DriverId, connected: bool) {
+        self.state.borrow_mut().ports[driver.0].connected = connected;
+    }
+
+    /// Transmits a frame from outside the bus, which all connected drivers can receive
+    ///
+    /// This is useful for sending malformed or unusual frames that a node would not send.
+    pub fn inject(&self, frame: Frame) {
+        self.state.borrow_mut().transmit(None, frame)
+    }
+
+    /// Returns the numbers of frames that have been transmitted, delivered, and lost
+    pub fn stats(&self) -> SimStats {
+        self.state.borrow().stats.clone()
+    }
+}
+
+/// One node's connection to a [`SimBus`]
+///
+/// This implements the `canadensis_can` driver traits, so it can be used with a
+/// `CanTransmitter` and `CanReceiver`. Transmitted frames go onto the bus immediately without
+/// any queue.
+#[derive(Debug)]
+pub struct SimDriver {
+    id: DriverId,
+    bus: SimBus,
+}
+
+impl SimDriver {
+    /// Returns the identifier of this driver
+    pub fn id(&self) -> DriverId {
+        self.id
+    }
+
+    /// Returns the bus that this driver is attached to
+    pub fn bus(&self) -> &SimBus {
+        &self.bus
+    }
+}
+
+impl TransmitDriver<SimClock> for SimDriver {
+    type Error = Infallible;
+
+    fn try_reserve(&mut self, _frames: usize) -> Result<(), OutOfMemoryError> {
+        Ok(())
+    }
+
+    fn transmit(
+        &mut self,
+        frame: Frame,
+        _clock: &mut SimClock,
+    ) -> nb::Result<Option<Frame>, Self::Error> {
+        self.bus.state.borrow_mut().transmit(Some(self.id), frame);
+        Ok(None)
+    }
+
+    fn flush(&mut self, _clock: &mut SimClock) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl ReceiveDriver<SimClock> for SimDriver {
+    type Error = Infallible;
+
+    fn receive(&mut self, _clock: &mut SimClock) -> nb::Result<Frame, Self::Error> {
+        self.bus
+            .state
+            .borrow_mut()
+            .receive(self.id)
+            .ok_or(nb::Error::WouldBlock)
+    }
+
+    fn apply_filters<S>(&mut self, local_node: Option<CanNodeId>, subscriptions: S)
+    where
+        S: IntoIterator<Item = Subscription>,
+    {
+        let mut state = self.bus.state.borrow_mut();
+        let port = &mut state.ports[self.id.0];
+        optimize_filters(local_node, subscriptions, usize::MAX, |filters| {
+            port.filters = Some(filters.to_vec())
+        })
+        .expect("Out of memory")
+    }
+
+    fn apply_accept_all(&mut self) {
+        self.bus.state.borrow_mut().ports[self.id.0].filters = None;
+    }
+}
+
+#[derive(Debug)]
+struct BusState {
+    clock: SimClock,
+    config: SimConfig,
+    random: Random,
+    ports: Vec<Port>,
+    stats: SimStats,
+}
+
+impl BusState {
+    fn transmit(&mut self, source: Option<DriverId>, frame: Frame) {
+        let now = self.clock.time();
+        if frame.timestamp() < now {
+            log::debug!("Frame {:?} expired before transmission", frame);
+            self.stats.expired += 1;
+            return;
+        }
+        self.stats.transmitted += 1;
+        let source_connected = source.is_none_or(|source| self.ports[source.0].connected);
+        for (i, port) in self.ports.iter_mut().enumerate() {
+            let delivery = if source == Some(DriverId(i)) {
+                if !frame.loopback() {
+                    continue;
+                }
+                // Loopback frames come back without latency or loss
+                now
+            } else {
+                if !(source_connected && port.connected && port.accepts(&frame)) {
+                    continue;
+                }
+                if self.config.loss > 0.0 && self.random.next_f64() < self.config.loss {
+                    self.stats.lost += 1;
+                    continue;
+                }
+                let jitter = match self.config.jitter.ticks() {
+                    0 => 0,
+                    max => (self.random.next_u64() % (u64::from(max) + 1)) as u32,
+                };
+                now + self.config.latency + MicrosecondDuration32::from_ticks(jitter)
+            };
+            // Frames don't overtake each other
+            let delivery = port
+                .last_delivery
+                .map_or(delivery, |last| last.max(delivery));
+            port.last_delivery = Some(delivery);
+            let mut delivered = frame.clone();
+            delivered.set_timestamp(delivery);
+            port.inbox.push_back(delivered);
+        }
+    }
+
+    fn receive(&mut self, driver: DriverId) -> Option<Frame> {
+        let now = self.clock.time();
+        let inbox = &mut self.ports[driver.0].inbox;
+        if inbox.front()?.timestamp() <= now {
+            self.stats.delivered += 1;
+            inbox.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Port {
+    /// Frames on the way to this driver, with their delivery times as timestamps
+    inbox: VecDeque<Frame>,
+    /// The filters for accepted frames, or None to accept all frames
+    filters: Option<Vec<Filter>>,
+    connected: bool,
+    /// The delivery time of the last frame added to the inbox
+    last_delivery: Option<Microseconds32>,
+}
+
+impl Port {
+    fn accepts(&self, frame: &Frame) -> bool {
+        match &self.filters {
+            Some(filters) => filters
+                .iter()
+                .any(|filter| filter.accepts(u32::from(frame.id()))),
+            None => true,
+        }
+    }
+}
+
+/// A xorshift64* pseudorandom number generator
+#[derive(Debug)]
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        // The state must not be zero
+        Random(if seed == 0 {
+            0x9e37_79b9_7f4a_7c15
+        } else {
+            seed
+        })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Returns a number in the range [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
@@ -0,0 +1,50 @@
+use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// A clock that only moves when it is told to
+///
+/// Clones of a clock share the same time. The time starts at zero.
+#[derive(Debug, Clone)]
+pub struct SimClock {
+    time: Rc<Cell<Microseconds32>>,
+}
+
+impl SimClock {
+    /// Creates a clock with a time of zero
+    pub fn new() -> Self {
+        SimClock {
+            time: Rc::new(Cell::new(Microseconds32::from_ticks(0))),
+        }
+    }
+
+    /// Returns the current time
+    pub fn time(&self) -> Microseconds32 {
+        self.time.get()
+    }
+
+    /// Sets the current time
+    ///
+    /// The time can move backwards, but frames that are waiting for delivery on a
+    /// [`SimBus`](crate::SimBus) will then take longer to arrive.
+    pub fn set_time(&self, time: Microseconds32) {
+        self.time.set(time)
+    }
+
+    /// Moves the time forward
+    pub fn advance(&self, duration: MicrosecondDuration32) {
+        self.time.set(self.time.get() + duration)
+    }
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SimClock {
+    fn now(&mut self) -> Microseconds32 {
+        self.time.get()
+    }
+}
@@ -0,0 +1,50 @@
+//!
+//! # Simulated CAN bus for Cyphal
+//!
+//! This library connects any number of Cyphal/CAN nodes in one process through a simulated
+//! multi-drop bus, so that behavior that involves several nodes (plug-and-play node ID
+//! allocation, time synchronization, redundant transports, and so on) can be tested
+//! deterministically with `cargo test`.
+//!
+//! * A [`SimClock`] is a clock that only moves when the test moves it. All clones of a clock
+//!   share the same time, so each node gets a clone.
+//! * A [`SimBus`] delivers frames from each attached node to all the other nodes, after a
+//!   configurable latency and with a configurable probability of loss.
+//! * A [`SimDriver`] is one node's connection to a bus. It implements the `canadensis_can`
+//!   transmit and receive driver traits.
+//!
+//! Random loss and latency come from a pseudorandom generator with a configurable seed, so a
+//! test with the same seed and the same sequence of operations always has the same result.
+//!
+//! ```
+//! use canadensis_can::{CanNodeId, CanReceiver, CanTransmitter, Mtu};
+//! use canadensis_core::time::milliseconds;
+//! use canadensis_sim::{SimBus, SimClock, SimConfig, SimDriver};
+//! use std::convert::TryFrom;
+//!
+//! let clock = SimClock::new();
+//! let bus = SimBus::new(clock.clone(), SimConfig::default());
+//! let node_id = CanNodeId::try_from(3_u8).unwrap();
+//! // Each driver, together with a transmitter and receiver, makes a node (for example,
+//! // a canadensis::node::CoreNode)
+//! let driver = bus.attach();
+//! let transmitter = CanTransmitter::<SimClock, SimDriver>::new(Mtu::Can8);
+//! let receiver = CanReceiver::<SimClock, SimDriver>::new(node_id, Mtu::Can8);
+//!
+//! // Time only moves when the test moves it
+//! clock.advance(milliseconds(10));
+//! ```
+//!
+
+#![deny(missing_docs)]
+
+extern crate canadensis_can;
+extern crate canadensis_core;
+extern crate canadensis_filter_config;
+extern crate log;
+
+mod bus;
+mod clock;
+
+pub use crate::bus::{DriverId, SimBus, SimConfig, SimDriver, SimStats};
+pub use crate::clock::SimClock;
@@ -0,0 +1,347 @@
+//! Tests several nodes communicating through a simulated bus
+
+extern crate canadensis;
+extern crate canadensis_can;
+extern crate canadensis_core;
+extern crate canadensis_data_types;
+extern crate canadensis_encoding;
+extern crate canadensis_sim;
+
+use canadensis::node::CoreNode;
+use canadensis::requester::TransferIdFixedMap;
+use canadensis::service::get_info::GetInfoService;
+use canadensis::{Node, ResponseToken, TransferHandler};
+use canadensis_can::{CanId, CanNodeId, CanReceiver, CanTransmitter, CanTransport, Frame, Mtu};
+use canadensis_core::time::{milliseconds, MicrosecondDuration32, Microseconds32};
+use canadensis_core::transfer::{MessageTransfer, ServiceTransfer};
+use canadensis_core::{Priority, ServiceId, SubjectId};
+use canadensis_data_types::uavcan::node::get_info_1_0::{self, GetInfoRequest, GetInfoResponse};
+use canadensis_data_types::uavcan::node::version_1_0::Version;
+use canadensis_encoding::Deserialize;
+use canadensis_sim::{SimBus, SimClock, SimConfig, SimDriver, SimStats};
+use std::convert::TryFrom;
+
+type SimNode = CoreNode<
+    SimClock,
+    CanTransmitter<SimClock, SimDriver>,
+    CanReceiver<SimClock, SimDriver>,
+    TransferIdFixedMap<CanTransport, 4>,
+    SimDriver,
+    4,
+    4,
+>;
+
+fn make_node(clock: &SimClock, bus: &SimBus, node_id: u8) -> SimNode {
+    let node_id = CanNodeId::try_from(node_id).unwrap();
+    CoreNode::new(
+        clock.clone(),
+        node_id,
+        CanTransmitter::new(Mtu::Can8),
+        CanReceiver::new(node_id, Mtu::Can8),
+        bus.attach(),
+    )
+}
+
+const SUBJECT: SubjectId = SubjectId::from_truncating(100);
+
+/// Collects received message payloads and response transfers
+#[derive(Default)]
+struct Collector {
+    messages: Vec<(Microseconds32, Vec<u8>)>,
+    responses: Vec<(CanNodeId, Vec<u8>)>,
+}
+
+impl TransferHandler<CanTransport> for Collector {
+    fn handle_message<N: Node<Transport = CanTransport>>(
+        &mut self,
+        _node: &mut N,
+        transfer: &MessageTransfer<Vec<u8>, CanTransport>,
+    ) -> bool {
+        self.messages
+            .push((transfer.header.timestamp, transfer.payload.clone()));
+        true
+    }
+
+    fn handle_response<N: Node<Transport = CanTransport>>(
+        &mut self,
+        _node: &mut N,
+        transfer: &ServiceTransfer<Vec<u8>, CanTransport>,
+    ) -> bool {
+        self.responses
+            .push((transfer.header.source, transfer.payload.clone()));
+        true
+    }
+}
+
+#[test]
+fn multi_frame_message_with_latency() {
+    let clock = SimClock::new();
+    let bus = SimBus::new(
+        clock.clone(),
+        SimConfig {
+            latency: MicrosecondDuration32::from_ticks(500),
+            ..SimConfig::default()
+        },
+    );
+    let mut publisher = make_node(&clock, &bus, 1);
+    let mut subscriber = make_node(&clock, &bus, 2);
+    publisher
+        .start_publishing(SUBJECT, milliseconds(100), Priority::Nominal)
+        .unwrap();
+    subscriber
+        .subscribe_message(SUBJECT, 20, milliseconds(100))
+        .unwrap();
+
+    let payload: Vec<u8> = (0..20).collect();
+    clock.advance(milliseconds(1));
+    publisher.publish_vectored(SUBJECT, &[&payload]).unwrap();
+
+    let mut collector = Collector::default();
+    clock.advance(MicrosecondDuration32::from_ticks(499));
+    subscriber.receive(&mut collector).unwrap();
+    assert!(collector.messages.is_empty());
+
+    clock.advance(MicrosecondDuration32::from_ticks(1));
+    subscriber.receive(&mut collector).unwrap();
+    assert_eq!(
+        vec![(Microseconds32::from_ticks(1500), payload)],
+        collector.messages
+    );
+    // 20 bytes and a 2-byte transfer CRC, with 7 bytes in each frame, make 4 frames
+    assert_eq!(
+        SimStats {
+            transmitted: 4,
+            expired: 0,
+            delivered: 4,
+            lost: 0,
+        },
+        bus.stats()
+    );
+}
+
+#[test]
+fn service_round_trip() {
+    let clock = SimClock::new();
+    let bus = SimBus::new(
+        clock.clone(),
+        SimConfig {
+            latency: MicrosecondDuration32::from_ticks(200),
+            jitter: MicrosecondDuration32::from_ticks(100),
+            ..SimConfig::default()
+        },
+    );
+    let mut client = make_node(&clock, &bus, 1);
+    let mut server = make_node(&clock, &bus, 2);
+    let node_info = GetInfoResponse {
+        protocol_version: Version { major: 1, minor: 0 },
+        hardware_version: Version { major: 2, minor: 0 },
+        software_version: Version { major: 3, minor: 1 },
+        software_vcs_revision_id: 0xbeef,
+        unique_id: [7; 16],
+        name: Default::default(),
+        software_image_crc: Default::default(),
+        certificate_of_authenticity: Default::default(),
+    };
+    let get_info = GetInfoService::new(&mut server, node_info).unwrap();
+    let token = client
+        .start_sending_requests::<GetInfoRequest>(
+            get_info_1_0::SERVICE,
+            milliseconds(100),
+            313,
+            Priority::Nominal,
+        )
+        .unwrap();
+    client
+        .send_request(
+            &token,
+            &GetInfoRequest {},
+            CanNodeId::try_from(2_u8).unwrap(),
+        )
+        .unwrap();
+
+    let mut collector = Collector::default();
+    for _ in 0..10 {
+        clock.advance(MicrosecondDuration32::from_ticks(100));
+        server.receive(&mut get_info.handler()).unwrap();
+        client.receive(&mut collector).unwrap();
+    }
+    assert_eq!(1, collector.responses.len());
+    let (source, payload) = &collector.responses[0];
+    assert_eq!(CanNodeId::try_from(2_u8).unwrap(), *source);
+    let info = GetInfoResponse::deserialize_from_bytes(payload).unwrap();
+    assert_eq!(0xbeef, info.software_vcs_revision_id);
+    assert_eq!([7; 16], info.unique_id);
+}
+
+/// Responds to each request with the request payload after a prefix
+struct Echo;
+
+impl TransferHandler<CanTransport> for Echo {
+    fn handle_request<N: Node<Transport = CanTransport>>(
+        &mut self,
+        node: &mut N,
+        token: ResponseToken<CanTransport>,
+        transfer: &ServiceTransfer<Vec<u8>, CanTransport>,
+    ) -> bool {
+        node.send_response_vectored(token, milliseconds(100), &[b"echo:", &transfer.payload])
+            .unwrap();
+        true
+    }
+}
+
+#[test]
+fn vectored_service_round_trip() {
+    let clock = SimClock::new();
+    let bus = SimBus::new(clock.clone(), SimConfig::default());
+    let mut client = make_node(&clock, &bus, 1);
+    let mut server = make_node(&clock, &bus, 2);
+    let service = ServiceId::try_from(100u16).unwrap();
+    server
+        .subscribe_request(service, 32, milliseconds(100))
+        .unwrap();
+    // The type parameter only identifies the service
+    let token = client
+        .start_sending_requests::<GetInfoRequest>(service, milliseconds(100), 32, Priority::Nominal)
+        .unwrap();
+    client
+        .send_request_vectored(
+            &token,
+            &[b"multi", &[], b"-frame request"],
+            CanNodeId::try_from(2_u8).unwrap(),
+        )
+        .unwrap();
+
+    let mut collector = Collector::default();
+    for _ in 0..10 {
+        clock.advance(MicrosecondDuration32::from_ticks(100));
+        server.receive(&mut Echo).unwrap();
+        client.receive(&mut collector).unwrap();
+    }
+    assert_eq!(
+        vec![(
+            CanNodeId::try_from(2_u8).unwrap(),
+            b"echo:multi-frame request".to_vec()
+        )],
+        collector.responses
+    );
+}
+
+/// Publishes 100 single-frame messages from one node to two other nodes and returns what the
+/// other nodes received
+fn lossy_messages(seed: u64) -> (Vec<Vec<u8>>, Vec<Vec<u8>>, SimStats) {
+    let clock = SimClock::new();
+    let bus = SimBus::new(
+        clock.clone(),
+        SimConfig {
+            loss: 0.5,
+            seed,
+            ..SimConfig::default()
+        },
+    );
+    let mut publisher = make_node(&clock, &bus, 1);
+    let mut subscribers = [make_node(&clock, &bus, 2), make_node(&clock, &bus, 3)];
+    publisher
+        .start_publishing(SUBJECT, milliseconds(100), Priority::Nominal)
+        .unwrap();
+    for subscriber in subscribers.iter_mut() {
+        subscriber
+            .subscribe_message(SUBJECT, 1, milliseconds(100))
+            .unwrap();
+    }
+    let mut collectors = [Collector::default(), Collector::default()];
+    for i in 0..100_u8 {
+        publisher.publish_vectored(SUBJECT, &[&[i]]).unwrap();
+        clock.advance(milliseconds(1));
+        for (subscriber, collector) in subscribers.iter_mut().zip(collectors.iter_mut()) {
+            subscriber.receive(collector).unwrap();
+        }
+    }
+    let [collector0, collector1] = collectors;
+    let payloads = |collector: Collector| -> Vec<Vec<u8>> {
+        collector
+            .messages
+            .into_iter()
+            .map(|(_, payload)| payload)
+            .collect()
+    };
+    (payloads(collector0), payloads(collector1), bus.stats())
+}
+
+#[test]
+fn deterministic_loss() {
+    let (received0, received1, stats) = lossy_messages(42);
+    assert_eq!(100, stats.transmitted);
+    assert_eq!(200, stats.delivered + stats.lost);
+    assert_eq!(stats.delivered as usize, received0.len() + received1.len());
+    for received in [&received0, &received1] {
+        assert!(
+            (25..=75).contains(&received.len()),
+            "{} received",
+            received.len()
+        );
+    }
+    // Each node loses frames independently
+    assert_ne!(received0, received1);
+    // The same seed gives the same result
+    assert_eq!((received0, received1, stats), lossy_messages(42));
+}
+
+#[test]
+fn filters_and_disconnection() {
+    let clock = SimClock::new();
+    let bus = SimBus::new(clock.clone(), SimConfig::default());
+    let mut publisher = make_node(&clock, &bus, 1);
+    // A driver without a node receives everything
+    let mut listener = bus.attach();
+    let mut subscriber = make_node(&clock, &bus, 2);
+    subscriber
+        .subscribe_message(SUBJECT, 1, milliseconds(100))
+        .unwrap();
+    let other_subject = SubjectId::from_truncating(101);
+    publisher
+        .start_publishing(SUBJECT, milliseconds(100), Priority::Nominal)
+        .unwrap();
+    publisher
+        .start_publishing(other_subject, milliseconds(100), Priority::Nominal)
+        .unwrap();
+
+    publisher.publish_vectored(SUBJECT, &[&[1]]).unwrap();
+    publisher.publish_vectored(other_subject, &[&[2]]).unwrap();
+
+    let mut collector = Collector::default();
+    subscriber.receive(&mut collector).unwrap();
+    subscriber.receive(&mut collector).unwrap();
+    assert_eq!(1, collector.messages.len());
+    let mut listener_frames = receive_all(&mut listener, &clock);
+    assert_eq!(2, listener_frames.len());
+    // The subscriber's filters blocked the other subject
+    assert_eq!(3, bus.stats().delivered);
+
+    // A disconnected driver receives nothing
+    bus.set_connected(listener.id(), false);
+    publisher.publish_vectored(SUBJECT, &[&[3]]).unwrap();
+    assert!(receive_all(&mut listener, &clock).is_empty());
+    subscriber.receive(&mut collector).unwrap();
+    assert_eq!(2, collector.messages.len());
+
+    // Injected frames reach connected drivers
+    bus.set_connected(listener.id(), true);
+    let injected = Frame::new(
+        clock.time(),
+        CanId::try_from(0x1067_6501_u32).unwrap(),
+        &[0xe0],
+    );
+    bus.inject(injected.clone());
+    listener_frames = receive_all(&mut listener, &clock);
+    assert_eq!(vec![injected], listener_frames);
+}
+
+fn receive_all(driver: &mut SimDriver, clock: &SimClock) -> Vec<Frame> {
+    use canadensis_can::driver::ReceiveDriver;
+    let mut clock = clock.clone();
+    let mut frames = Vec::new();
+    while let Ok(frame) = driver.receive(&mut clock) {
+        frames.push(frame);
+    }
+    frames
+}
@@ -0,0 +1,1022 @@
+//!
+//! Offline decoding of Cyphal/UDP traffic from packet captures
+//!
+//! This module reads pcapng files (from Wireshark, `dumpcap`, or `tcpdump -w` with
+//! `--pcapng`/`editcap -F pcapng`), finds the Cyphal/UDP packets, and reassembles them into
+//! transfers with the same receiver logic that a live node uses. This allows traffic recorded on
+//! a vehicle to be analyzed later without replaying it on a network.
+//!
+//! * A [`PcapngReader`] reads captured packets from a file.
+//! * [`udp_datagram`] finds the UDP datagram in a captured Ethernet, Linux cooked, raw IPv4, or
+//!   loopback packet.
+//! * A [`Decoder`] reassembles Cyphal/UDP datagrams into transfers. It receives transfers on all
+//!   subjects and services, including service transfers between any two nodes.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use canadensis_udp::capture::{Decoder, PcapngReader};
+//! # use canadensis_core::time::MicrosecondDuration32;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let file = std::io::BufReader::new(std::fs::File::open("flight.pcapng")?);
+//! let mut reader = PcapngReader::new(file)?;
+//! let mut decoder = Decoder::new(
+//!     canadensis_udp::DEFAULT_PORT,
+//!     4096,
+//!     MicrosecondDuration32::from_ticks(1_000_000),
+//! );
+//! while let Some(packet) = reader.next_packet()? {
+//!     if let Some(transfer) = decoder.push_packet(&packet).expect("Out of memory") {
+//!         println!("{} {:?}", packet.timestamp, transfer.header);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A capture of redundant interfaces has a copy of each packet from each interface, so each
+//! transfer will be decoded more than once. [`Packet::interface`] can be used to select one
+//! interface.
+//!
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::convert::{Infallible, TryFrom};
+use core::fmt;
+use core::net::{Ipv4Addr, SocketAddrV4};
+use std::io::{self, Read};
+
+use zerocopy::FromBytes;
+
+use canadensis_core::session::SessionDynamicMap;
+use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
+use canadensis_core::transfer::Transfer;
+use canadensis_core::transport::Receiver;
+use canadensis_core::OutOfMemoryError;
+use canadensis_header::{DataSpecifier, Header as UdpHeader, RawHeader};
+
+use crate::driver::UdpSocket;
+use crate::{Error, UdpNodeId, UdpReceiver, UdpSessionData, UdpTransferId, UdpTransport};
+
+/// Link types from <https://www.tcpdump.org/linktypes.html>
+mod link_type {
+    /// BSD loopback, with a 4-byte address family in host byte order
+    pub const NULL: u16 = 0;
+    pub const ETHERNET: u16 = 1;
+    /// Raw IPv4 or IPv6 (the older number)
+    pub const RAW: u16 = 101;
+    pub const LINUX_SLL: u16 = 113;
+    /// Raw IPv4
+    pub const IPV4: u16 = 228;
+    pub const LINUX_SLL2: u16 = 276;
+}
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const ETHERTYPE_QINQ: u16 = 0x88a8;
+/// The IPv4 address family on BSD and Linux, which loopback captures use
+const AF_INET: u32 = 2;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// The largest block that a reader accepts, in bytes
+///
+/// This stops a corrupt length from causing a huge allocation.
+const MAX_BLOCK_LENGTH: u32 = 16 * 1024 * 1024;
+
+mod block_type {
+    pub const SECTION_HEADER: u32 = 0x0a0d_0d0a;
+    pub const INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+    pub const ENHANCED_PACKET: u32 = 0x0000_0006;
+}
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+
+/// Option codes in interface description blocks
+const OPTION_END: u16 = 0;
+const OPTION_IF_TSRESOL: u16 = 9;
+const OPTION_IF_TSOFFSET: u16 = 14;
+
+/// A packet from a capture file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packet {
+    /// The index of the interface that captured this packet, starting at 0 in each section of
+    /// the file
+    pub interface: u32,
+    /// The link type of the interface (1 for Ethernet), from
+    /// <https://www.tcpdump.org/linktypes.html>
+    pub link_type: u16,
+    /// The time when this packet was captured, in microseconds since the Unix epoch
+    pub timestamp: u64,
+    /// The captured bytes, starting with the link-layer header
+    ///
+    /// This may be shorter than the original packet if the capture had a snapshot length.
+    pub data: Vec<u8>,
+}
+
+/// Information from an interface description block
+#[derive(Debug, Clone)]
+struct Interface {
+    link_type: u16,
+    /// The number of timestamp units in a second
+    units_per_second: u128,
+    /// Seconds to add to each timestamp
+    offset_seconds: i64,
+}
+
+impl Interface {
+    /// Converts a timestamp into microseconds
+    fn microseconds(&self, timestamp: u64) -> u64 {
+        let micros = u128::from(timestamp) * 1_000_000 / self.units_per_second;
+        let offset = i128::from(self.offset_seconds) * 1_000_000;
+        (micros as i128 + offset).clamp(0, i128::from(u64::MAX)) as u64
+    }
+}
+
+/// Reads packets from a pcapng file
+///
+/// Files can have several sections, with either byte order. Enhanced packet blocks are read,
+/// and all other kinds of blocks are skipped.
+pub struct PcapngReader<R> {
+    reader: R,
+    big_endian: bool,
+    /// The interfaces in the current section
+    interfaces: Vec<Interface>,
+}
+
+impl<R: Read> PcapngReader<R> {
+    /// Creates a reader and reads the first section header from the file
+    ///
+    /// For better performance, the reader should be buffered.
+    pub fn new(mut reader: R) -> Result<Self, CaptureError> {
+        let mut block_type = [0u8; 4];
+        reader.read_exact(&mut block_type)?;
+        if u32::from_le_bytes(block_type) != block_type::SECTION_HEADER {
+            return Err(CaptureError::NotPcapng);
+        }
+        let mut pcapng = PcapngReader {
+            reader,
+            big_endian: false,
+            interfaces: Vec::new(),
+        };
+        pcapng.read_section_header()?;
+        Ok(pcapng)
+    }
+
+    /// Reads the next packet from the file
+    ///
+    /// This returns Ok(None) at the end of the file.
+    pub fn next_packet(&mut self) -> Result<Option<Packet>, CaptureError> {
+        loop {
+            let mut block_type = [0u8; 4];
+            if !self.read_or_end(&mut block_type)? {
+                return Ok(None);
+            }
+            let block_type = self.u32(block_type);
+            if block_type == block_type::SECTION_HEADER {
+                self.read_section_header()?;
+                continue;
+            }
+            let body = self.read_block_body(block_type)?;
+            match block_type {
+                block_type::INTERFACE_DESCRIPTION => {
+                    let interface = self.parse_interface(&body)?;
+                    self.interfaces.push(interface);
+                }
+                block_type::ENHANCED_PACKET => return self.parse_packet(&body).map(Some),
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads a section header block after its type, and sets the byte order
+    fn read_section_header(&mut self) -> Result<(), CaptureError> {
+        let mut length_and_magic = [0u8; 8];
+        self.reader.read_exact(&mut length_and_magic)?;
+        let magic = [
+            length_and_magic[4],
+            length_and_magic[5],
+            length_and_magic[6],
+            length_and_magic[7],
+        ];
+        self.big_endian = if u32::from_le_bytes(magic) == BYTE_ORDER_MAGIC {
+            false
+        } else if u32::from_be_bytes(magic) == BYTE_ORDER_MAGIC {
+            true
+        } else {
+            return Err(CaptureError::NotPcapng);
+        };
+        self.interfaces.clear();
+        let length = self.u32([
+            length_and_magic[0],
+            length_and_magic[1],
+            length_and_magic[2],
+            length_and_magic[3],
+        ]);
+        // The type, length, and magic have been read
+        self.skip_block_rest(block_type::SECTION_HEADER, length, 12)
+    }
+
+    /// Reads the length and body of a block after its type, and returns the body
+    fn read_block_body(&mut self, block_type: u32) -> Result<Vec<u8>, CaptureError> {
+        let mut length = [0u8; 4];
+        self.reader.read_exact(&mut length)?;
+        let length = self.u32(length);
+        check_block_length(block_type, length, 12)?;
+        let mut body = vec![0u8; length as usize - 12];
+        self.reader.read_exact(&mut body)?;
+        // Ignore the repeated length at the end
+        self.reader.read_exact(&mut [0u8; 4])?;
+        Ok(body)
+    }
+
+    /// Skips the rest of a block, after `read` bytes of it have been read
+    fn skip_block_rest(
+        &mut self,
+        block_type: u32,
+        length: u32,
+        read: u32,
+    ) -> Result<(), CaptureError> {
+        check_block_length(block_type, length, read + 4)?;
+        let rest = u64::from(length - read);
+        let skipped = io::copy(&mut (&mut self.reader).take(rest), &mut io::sink())?;
+        if skipped == rest {
+            Ok(())
+        } else {
+            Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+        }
+    }
+
+    fn parse_interface(&self, body: &[u8]) -> Result<Interface, CaptureError> {
+        let invalid = || CaptureError::InvalidBlock(block_type::INTERFACE_DESCRIPTION);
+        if body.len() < 8 {
+            return Err(invalid());
+        }
+        let mut interface = Interface {
+            link_type: self.u16([body[0], body[1]]),
+            units_per_second: 1_000_000,
+            offset_seconds: 0,
+        };
+        let mut options = &body[8..];
+        while options.len() >= 4 {
+            let code = self.u16([options[0], options[1]]);
+            let length = usize::from(self.u16([options[2], options[3]]));
+            let value = options.get(4..4 + length).ok_or_else(invalid)?;
+            match (code, value) {
+                (OPTION_END, _) => break,
+                (OPTION_IF_TSRESOL, &[resolution]) => {
+                    let exponent = u32::from(resolution & 0x7f);
+                    let base: u128 = if resolution & 0x80 == 0 { 10 } else { 2 };
+                    interface.units_per_second = base.checked_pow(exponent).ok_or_else(invalid)?;
+                }
+                (OPTION_IF_TSOFFSET, &[a, b, c, d, e, f, g, h]) => {
+                    let bytes = [a, b, c, d, e, f, g, h];
+                    interface.offset_seconds = if self.big_endian {
+                        i64::from_be_bytes(bytes)
+                    } else {
+                        i64::from_le_bytes(bytes)
+                    };
+                }
+                _ => {}
+            }
+            // Values are padded to a multiple of 4 bytes
+            let padded_length = (length + 3) & !3;
+            options = options.get(4 + padded_length..).unwrap_or(&[]);
+        }
+        Ok(interface)
+    }
+
+    fn parse_packet(&self, body: &[u8]) -> Result<Packet, CaptureError> {
+        let invalid = || CaptureError::InvalidBlock(block_type::ENHANCED_PACKET);
+        if body.len() < 20 {
+            return Err(invalid());
+        }
+        let field = |index: usize| {
+            self.u32([
+                body[index * 4],
+                body[index * 4 + 1],
+                body[index * 4 + 2],
+                body[index * 4 + 3],
+            ])
+        };
+        let interface_index = field(0);
+        let timestamp = (u64::from(field(1)) << 32) | u64::from(field(2));
+        let captured_length = field(3) as usize;
+        let data = body.get(20..20 + captured_length).ok_or_else(invalid)?;
+        let interface = self
+            .interfaces
+            .get(interface_index as usize)
+            .ok_or(CaptureError::UnknownInterface(interface_index))?;
+        Ok(Packet {
+            interface: interface_index,
+            link_type: interface.link_type,
+            timestamp: interface.microseconds(timestamp),
+            data: data.to_vec(),
+        })
+    }
+
+    /// Fills `buffer`, or returns false if the file ends before the first byte
+    fn read_or_end(&mut self, buffer: &mut [u8]) -> Result<bool, CaptureError> {
+        let mut read = 0;
+        while read < buffer.len() {
+            match self.reader.read(&mut buffer[read..]) {
+                Ok(0) if read == 0 => return Ok(false),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(true)
+    }
+
+    fn u16(&self, bytes: [u8; 2]) -> u16 {
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    fn u32(&self, bytes: [u8; 4]) -> u32 {
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+/// Checks that a block length is a multiple of 4, at least `min` bytes, and not too large
+fn check_block_length(block_type: u32, length: u32, min: u32) -> Result<(), CaptureError> {
+    if length.is_multiple_of(4) && length >= min && length <= MAX_BLOCK_LENGTH {
+        Ok(())
+    } else {
+        Err(CaptureError::InvalidBlock(block_type))
+    }
+}
+
+/// A UDP datagram from a captured packet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Datagram<'p> {
+    /// The address and port that sent the datagram
+    pub source: SocketAddrV4,
+    /// The address and port that the datagram was sent to
+    pub destination: SocketAddrV4,
+    /// The UDP payload
+    pub payload: &'p [u8],
+}
+
+/// Finds the UDP datagram in a captured packet with a link type from
+/// <https://www.tcpdump.org/linktypes.html>
+///
+/// This returns None if the link type is not supported, or if the packet does not contain a
+/// complete IPv4 UDP datagram. Fragmented IPv4 packets are not reassembled, so they also
+/// return None.
+pub fn udp_datagram(link_type: u16, packet: &[u8]) -> Option<Datagram<'_>> {
+    let ip_packet = match link_type {
+        link_type::NULL => {
+            let family = packet.get(..4)?;
+            let family = [family[0], family[1], family[2], family[3]];
+            // The byte order is the one of the computer that made the capture
+            if u32::from_le_bytes(family) != AF_INET && u32::from_be_bytes(family) != AF_INET {
+                return None;
+            }
+            &packet[4..]
+        }
+        link_type::ETHERNET => {
+            let mut ethertype_offset = 12;
+            loop {
+                let ethertype = read_u16_be(packet, ethertype_offset)?;
+                match ethertype {
+                    ETHERTYPE_VLAN | ETHERTYPE_QINQ => ethertype_offset += 4,
+                    ETHERTYPE_IPV4 => break packet.get(ethertype_offset + 2..)?,
+                    _ => return None,
+                }
+            }
+        }
+        link_type::LINUX_SLL => {
+            if read_u16_be(packet, 14)? != ETHERTYPE_IPV4 {
+                return None;
+            }
+            packet.get(16..)?
+        }
+        link_type::LINUX_SLL2 => {
+            if read_u16_be(packet, 0)? != ETHERTYPE_IPV4 {
+                return None;
+            }
+            packet.get(20..)?
+        }
+        link_type::RAW | link_type::IPV4 => packet,
+        _ => return None,
+    };
+    ipv4_udp_datagram(ip_packet)
+}
+
+fn ipv4_udp_datagram(packet: &[u8]) -> Option<Datagram<'_>> {
+    let version_and_length = *packet.first()?;
+    if version_and_length >> 4 != 4 {
+        return None;
+    }
+    let header_length = usize::from(version_and_length & 0xf) * 4;
+    let total_length = usize::from(read_u16_be(packet, 2)?);
+    let flags_and_offset = read_u16_be(packet, 6)?;
+    // More fragments flag or a fragment offset
+    if flags_and_offset & 0x3fff != 0 {
+        return None;
+    }
+    if *packet.get(9)? != IP_PROTOCOL_UDP || header_length < 20 || total_length < header_length {
+        return None;
+    }
+    let source = Ipv4Addr::from(<[u8; 4]>::try_from(packet.get(12..16)?).ok()?);
+    let destination = Ipv4Addr::from(<[u8; 4]>::try_from(packet.get(16..20)?).ok()?);
+    let udp = packet.get(header_length..total_length)?;
+
+    let udp_length = usize::from(read_u16_be(udp, 4)?);
+    if udp_length < 8 {
+        return None;
+    }
+    Some(Datagram {
+        source: SocketAddrV4::new(source, read_u16_be(udp, 0)?),
+        destination: SocketAddrV4::new(destination, read_u16_be(udp, 2)?),
+        payload: udp.get(8..udp_length)?,
+    })
+}
+
+fn read_u16_be(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// The largest UDP payload in an IPv4 packet
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// Reassembles captured Cyphal/UDP datagrams into transfers
+///
+/// A decoder receives all subjects and services, and decodes service transfers for all
+/// destination nodes.
+pub struct Decoder {
+    receiver: UdpReceiver<
+        ReplayClock,
+        SessionDynamicMap<UdpNodeId, UdpTransferId, UdpSessionData>,
+        ReplaySocket,
+        MAX_DATAGRAM_SIZE,
+    >,
+    clock: ReplayClock,
+    socket: ReplaySocket,
+    /// The UDP port that Cyphal/UDP datagrams are sent to
+    port: u16,
+    payload_size_max: usize,
+    timeout: MicrosecondDuration32,
+    /// The ports that the receiver has subscribed to
+    subscribed: BTreeSet<(PortKind, u16)>,
+}
+
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+enum PortKind {
+    Message,
+    Request,
+    Response,
+}
+
+impl Decoder {
+    /// Creates a decoder
+    ///
+    /// * `port`: The destination UDP port of Cyphal/UDP datagrams (usually
+    ///   [`DEFAULT_PORT`](crate::DEFAULT_PORT))
+    /// * `payload_size_max`: The maximum payload size of a transfer. Longer transfers are
+    ///   truncated.
+    /// * `timeout`: The maximum time between the first and last frames of a transfer
+    pub fn new(port: u16, payload_size_max: usize, timeout: MicrosecondDuration32) -> Self {
+        // The receiver only needs a node ID to subscribe to services. Because the replay socket
+        // does not filter anything, it receives service transfers to all nodes.
+        let node_id = UdpNodeId::try_from(0).expect("Bug: Invalid node ID");
+        Decoder {
+            receiver: UdpReceiver::new(Some(node_id), Ipv4Addr::UNSPECIFIED),
+            clock: ReplayClock(Microseconds32::from_ticks(0)),
+            socket: ReplaySocket(None),
+            port,
+            payload_size_max,
+            timeout,
+            subscribed: BTreeSet::new(),
+        }
+    }
+
+    /// Handles a captured packet
+    ///
+    /// Packets that are not UDP datagrams sent to the Cyphal/UDP port are ignored.
+    ///
+    /// If the packet completed a transfer, this returns the transfer. The transfer timestamp
+    /// is the lower 32 bits of the packet timestamp.
+    pub fn push_packet(
+        &mut self,
+        packet: &Packet,
+    ) -> Result<Option<Transfer<Vec<u8>, UdpTransport>>, OutOfMemoryError> {
+        match udp_datagram(packet.link_type, &packet.data) {
+            Some(datagram) if datagram.destination.port() == self.port => {
+                self.push_datagram(packet.timestamp, datagram.payload)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Handles the payload of a Cyphal/UDP datagram that was received at `timestamp`
+    /// (in microseconds)
+    ///
+    /// If the datagram completed a transfer, this returns the transfer. The transfer timestamp
+    /// is the lower 32 bits of `timestamp`.
+    pub fn push_datagram(
+        &mut self,
+        timestamp: u64,
+        datagram: &[u8],
+    ) -> Result<Option<Transfer<Vec<u8>, UdpTransport>>, OutOfMemoryError> {
+        let header = match RawHeader::read_from_prefix(datagram)
+            .and_then(|header| UdpHeader::try_from(header).ok())
+        {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        self.subscribe(&header.data_specifier)?;
+
+        self.clock.0 = Microseconds32::from_ticks(timestamp as u32);
+        self.socket.0 = Some(datagram.to_vec());
+        let result = self.receiver.receive(&mut self.clock, &mut self.socket);
+        self.socket.0 = None;
+        result.map_err(|e| match e {
+            Error::Memory(e) => e,
+            Error::Socket(never) => match never {},
+        })
+    }
+
+    /// Subscribes to the port of a datagram if it is not already subscribed
+    fn subscribe(&mut self, data_specifier: &DataSpecifier) -> Result<(), OutOfMemoryError> {
+        let port = match *data_specifier {
+            DataSpecifier::Subject { subject, .. } => (PortKind::Message, u16::from(subject)),
+            DataSpecifier::ServiceRequest { service, .. } => {
+                (PortKind::Request, u16::from(service))
+            }
+            DataSpecifier::ServiceResponse { service, .. } => {
+                (PortKind::Response, u16::from(service))
+            }
+        };
+        if self.subscribed.contains(&port) {
+            return Ok(());
+        }
+        let result = match *data_specifier {
+            DataSpecifier::Subject { subject, .. } => self
+                .receiver
+                .subscribe_message(
+                    subject,
+                    self.payload_size_max,
+                    self.timeout,
+                    &mut self.socket,
+                )
+                .map_err(Some),
+            DataSpecifier::ServiceRequest { service, .. } => self
+                .receiver
+                .subscribe_request(
+                    service,
+                    self.payload_size_max,
+                    self.timeout,
+                    &mut self.socket,
+                )
+                .map_err(|e| match e {
+                    canadensis_core::ServiceSubscribeError::Transport(e) => Some(e),
+                    canadensis_core::ServiceSubscribeError::Anonymous => None,
+                }),
+            DataSpecifier::ServiceResponse { service, .. } => self
+                .receiver
+                .subscribe_response(
+                    service,
+                    self.payload_size_max,
+                    self.timeout,
+                    &mut self.socket,
+                )
+                .map_err(|e| match e {
+                    canadensis_core::ServiceSubscribeError::Transport(e) => Some(e),
+                    canadensis_core::ServiceSubscribeError::Anonymous => None,
+                }),
+        };
+        match result {
+            Ok(()) => {
+                self.subscribed.insert(port);
+                Ok(())
+            }
+            Err(Some(Error::Memory(e))) => Err(e),
+            Err(Some(Error::Socket(never))) => match never {},
+            Err(None) => unreachable!("Decoder receiver is not anonymous"),
+        }
+    }
+}
+
+/// A clock that returns the time of the packet being decoded
+struct ReplayClock(Microseconds32);
+
+impl Clock for ReplayClock {
+    fn now(&mut self) -> Microseconds32 {
+        self.0
+    }
+}
+
+/// A socket that returns one datagram
+struct ReplaySocket(Option<Vec<u8>>);
+
+impl UdpSocket for ReplaySocket {
+    type Error = Infallible;
+
+    fn local_addr(&self) -> Result<SocketAddrV4, Self::Error> {
+        Ok(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))
+    }
+
+    fn join_multicast_v4(
+        &mut self,
+        _multiaddr: &Ipv4Addr,
+        _interface: &Ipv4Addr,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn leave_multicast_v4(
+        &mut self,
+        _multiaddr: &Ipv4Addr,
+        _interface: &Ipv4Addr,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_to(&mut self, data: &[u8], _destination: SocketAddrV4) -> Result<usize, Self::Error> {
+        Ok(data.len())
+    }
+
+    fn recv(&mut self, buffer: &mut [u8]) -> Result<usize, nb::Error<Self::Error>> {
+        let datagram = self.0.take().ok_or(nb::Error::WouldBlock)?;
+        // The buffer is as large as the largest datagram
+        buffer[..datagram.len()].copy_from_slice(&datagram);
+        Ok(datagram.len())
+    }
+}
+
+/// Errors that can occur when reading a capture file
+#[derive(Debug)]
+pub enum CaptureError {
+    /// Reading failed, or the file ended in the middle of a block
+    Io(io::Error),
+    /// The file does not start with a pcapng section header
+    NotPcapng,
+    /// A block of this type had an invalid length or content
+    InvalidBlock(u32),
+    /// A packet referred to an interface that was not described
+    UnknownInterface(u32),
+}
+
+impl From<io::Error> for CaptureError {
+    fn from(inner: io::Error) -> Self {
+        CaptureError::Io(inner)
+    }
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Io(_) => write!(f, "Failed to read capture"),
+            CaptureError::NotPcapng => write!(f, "Not a pcapng file"),
+            CaptureError::InvalidBlock(block_type) => {
+                write!(f, "Invalid block of type {:#010x}", block_type)
+            }
+            CaptureError::UnknownInterface(interface) => {
+                write!(f, "Packet from unknown interface {}", interface)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CaptureError::Io(inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use canadensis_core::time::milliseconds;
+    use canadensis_core::transfer::{Header, MessageHeader, ServiceHeader};
+    use canadensis_core::transport::Transmitter;
+    use canadensis_core::{Priority, ServiceId, SubjectId};
+
+    use crate::{UdpTransmitter, DEFAULT_PORT};
+
+    /// A socket that keeps the datagrams that are sent
+    #[derive(Default)]
+    struct RecordingSocket(Vec<(Vec<u8>, SocketAddrV4)>);
+
+    impl UdpSocket for RecordingSocket {
+        type Error = Infallible;
+        fn local_addr(&self) -> Result<SocketAddrV4, Infallible> {
+            Ok(SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 50000))
+        }
+        fn join_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn leave_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn send_to(&mut self, data: &[u8], destination: SocketAddrV4) -> Result<usize, Infallible> {
+            self.0.push((data.to_vec(), destination));
+            Ok(data.len())
+        }
+        fn recv(&mut self, _: &mut [u8]) -> Result<usize, nb::Error<Infallible>> {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Transmits a transfer with a small MTU and returns the datagrams
+    fn datagrams(header: Header<UdpTransport>, payload: &[u8]) -> Vec<(Vec<u8>, SocketAddrV4)> {
+        let mut transmitter = UdpTransmitter::<RecordingSocket, 64>::new(DEFAULT_PORT);
+        let mut socket = RecordingSocket::default();
+        let mut clock = ReplayClock(Microseconds32::from_ticks(0));
+        let transfer = Transfer {
+            header,
+            loopback: false,
+            payload,
+        };
+        transmitter.push(transfer, &mut clock, &mut socket).unwrap();
+        socket.0
+    }
+
+    /// Wraps a datagram in IPv4 and UDP headers
+    fn ipv4_packet(payload: &[u8], destination: SocketAddrV4) -> Vec<u8> {
+        let mut packet = vec![0x45, 0];
+        packet.extend_from_slice(&(28 + payload.len() as u16).to_be_bytes());
+        // Identification, flags (don't fragment), time to live, protocol, checksum
+        packet.extend_from_slice(&[0, 0, 0x40, 0, 16, IP_PROTOCOL_UDP, 0, 0]);
+        packet.extend_from_slice(&[192, 168, 1, 10]);
+        packet.extend_from_slice(&destination.ip().octets());
+        packet.extend_from_slice(&50000_u16.to_be_bytes());
+        packet.extend_from_slice(&destination.port().to_be_bytes());
+        packet.extend_from_slice(&(8 + payload.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    fn ethernet_frame(ethertype: u16, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x01, 0x00, 0x5e, 0x00, 0x00, 0x01, 2, 0, 0, 0, 0, 1];
+        frame.extend_from_slice(&ethertype.to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    /// Builds pcapng blocks in either byte order
+    struct Writer {
+        big_endian: bool,
+        data: Vec<u8>,
+    }
+
+    impl Writer {
+        fn u16(&self, value: u16) -> [u8; 2] {
+            if self.big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        }
+        fn u32(&self, value: u32) -> [u8; 4] {
+            if self.big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        }
+        fn block(&mut self, block_type: u32, mut body: Vec<u8>) {
+            while !body.len().is_multiple_of(4) {
+                body.push(0);
+            }
+            let length = self.u32(body.len() as u32 + 12);
+            let block_type = self.u32(block_type);
+            self.data.extend_from_slice(&block_type);
+            self.data.extend_from_slice(&length);
+            self.data.extend_from_slice(&body);
+            self.data.extend_from_slice(&length);
+        }
+        fn section_header(&mut self) {
+            let mut body = self.u32(BYTE_ORDER_MAGIC).to_vec();
+            // Version 1.0 and unknown section length
+            body.extend_from_slice(&self.u16(1));
+            body.extend_from_slice(&self.u16(0));
+            body.extend_from_slice(&[0xff; 8]);
+            self.block(block_type::SECTION_HEADER, body);
+        }
+        fn interface(&mut self, link_type: u16, resolution: Option<u8>) {
+            let mut body = self.u16(link_type).to_vec();
+            body.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+            if let Some(resolution) = resolution {
+                body.extend_from_slice(&self.u16(OPTION_IF_TSRESOL));
+                body.extend_from_slice(&self.u16(1));
+                body.extend_from_slice(&[resolution, 0, 0, 0]);
+                body.extend_from_slice(&[0, 0, 0, 0]);
+            }
+            self.block(block_type::INTERFACE_DESCRIPTION, body);
+        }
+        fn packet(&mut self, interface: u32, timestamp: u64, data: &[u8]) {
+            let mut body = Vec::new();
+            for value in [
+                interface,
+                (timestamp >> 32) as u32,
+                timestamp as u32,
+                data.len() as u32,
+                data.len() as u32,
+            ] {
+                body.extend_from_slice(&self.u32(value));
+            }
+            body.extend_from_slice(data);
+            self.block(block_type::ENHANCED_PACKET, body);
+        }
+    }
+
+    fn decode_all(capture: &[u8]) -> Vec<(u64, Transfer<Vec<u8>, UdpTransport>)> {
+        let mut reader = PcapngReader::new(capture).unwrap();
+        let mut decoder = Decoder::new(DEFAULT_PORT, 1024, milliseconds(1000));
+        let mut transfers = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            if let Some(transfer) = decoder.push_packet(&packet).unwrap() {
+                transfers.push((packet.timestamp, transfer));
+            }
+        }
+        transfers
+    }
+
+    #[test]
+    fn decode_capture() {
+        let node = |id: u16| UdpNodeId::try_from(id).unwrap();
+        let message_payload: Vec<u8> = (0..100).collect();
+        let message = datagrams(
+            Header::Message(MessageHeader {
+                timestamp: Microseconds32::from_ticks(1_000_000),
+                transfer_id: 3.into(),
+                priority: Priority::High,
+                subject: SubjectId::try_from(1234).unwrap(),
+                source: Some(node(7)),
+            }),
+            &message_payload,
+        );
+        assert_eq!(3, message.len());
+        let request = datagrams(
+            Header::Request(ServiceHeader {
+                timestamp: Microseconds32::from_ticks(1_000_000),
+                transfer_id: 9.into(),
+                priority: Priority::Nominal,
+                service: ServiceId::try_from(430).unwrap(),
+                source: node(7),
+                destination: node(300),
+            }),
+            &[0x42],
+        );
+
+        for big_endian in [false, true] {
+            let mut writer = Writer {
+                big_endian,
+                data: Vec::new(),
+            };
+            writer.section_header();
+            // Nanosecond timestamps
+            writer.interface(link_type::ETHERNET, Some(9));
+            writer.interface(link_type::IPV4, None);
+            let start = 1_700_000_000_000_000_000_u64;
+            for (i, (datagram, destination)) in message.iter().enumerate() {
+                let packet = ipv4_packet(datagram, *destination);
+                writer.packet(
+                    0,
+                    start + i as u64 * 1000,
+                    &ethernet_frame(ETHERTYPE_IPV4, &packet),
+                );
+                // Things that are not Cyphal/UDP in between
+                writer.packet(0, start, &ethernet_frame(0x0806, &[0; 28]));
+                let other_port = SocketAddrV4::new(*destination.ip(), 53);
+                writer.packet(1, 0, &ipv4_packet(datagram, other_port));
+            }
+            // A section with another byte order and microsecond timestamps
+            writer.big_endian = !big_endian;
+            writer.section_header();
+            writer.interface(link_type::IPV4, None);
+            let (datagram, destination) = &request[0];
+            writer.packet(
+                0,
+                1_700_000_001_000_000,
+                &ipv4_packet(datagram, *destination),
+            );
+
+            let transfers = decode_all(&writer.data);
+            assert_eq!(2, transfers.len());
+            let (timestamp, transfer) = &transfers[0];
+            assert_eq!(1_700_000_000_000_002, *timestamp);
+            assert_eq!(message_payload, transfer.payload);
+            match &transfer.header {
+                Header::Message(header) => {
+                    assert_eq!(SubjectId::try_from(1234).unwrap(), header.subject);
+                    assert_eq!(Some(node(7)), header.source);
+                    assert_eq!(Priority::High, header.priority);
+                    assert_eq!(UdpTransferId::from(3), header.transfer_id);
+                    assert_eq!(
+                        Microseconds32::from_ticks(1_700_000_000_000_002_u64 as u32),
+                        header.timestamp
+                    );
+                }
+                other => panic!("Unexpected header {:?}", other),
+            }
+            let (timestamp, transfer) = &transfers[1];
+            assert_eq!(1_700_000_001_000_000, *timestamp);
+            assert_eq!(vec![0x42], transfer.payload);
+            match &transfer.header {
+                Header::Request(header) => {
+                    assert_eq!(ServiceId::try_from(430).unwrap(), header.service);
+                    assert_eq!(node(7), header.source);
+                    assert_eq!(node(300), header.destination);
+                }
+                other => panic!("Unexpected header {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn link_layers() {
+        let destination = SocketAddrV4::new(Ipv4Addr::new(239, 0, 4, 210), DEFAULT_PORT);
+        let packet = ipv4_packet(&[1, 2, 3], destination);
+        let expected = Datagram {
+            source: SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 50000),
+            destination,
+            payload: &[1, 2, 3],
+        };
+
+        let mut vlan = vec![0x81, 0x00, 0x00, 0x05];
+        vlan.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        vlan.extend_from_slice(&packet);
+        let vlan = ethernet_frame(ETHERTYPE_VLAN, &vlan[2..]);
+        let mut sll = vec![0; 14];
+        sll.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        sll.extend_from_slice(&packet);
+        let mut sll2 = ETHERTYPE_IPV4.to_be_bytes().to_vec();
+        sll2.extend_from_slice(&[0; 18]);
+        sll2.extend_from_slice(&packet);
+        let mut null = AF_INET.to_le_bytes().to_vec();
+        null.extend_from_slice(&packet);
+        for (link_type, data) in [
+            (link_type::ETHERNET, ethernet_frame(ETHERTYPE_IPV4, &packet)),
+            (link_type::ETHERNET, vlan),
+            (link_type::LINUX_SLL, sll),
+            (link_type::LINUX_SLL2, sll2),
+            (link_type::NULL, null),
+            (link_type::RAW, packet.clone()),
+        ] {
+            assert_eq!(
+                Some(&expected),
+                udp_datagram(link_type, &data).as_ref(),
+                "link type {}",
+                link_type
+            );
+        }
+
+        // Truncated packet
+        assert_eq!(None, udp_datagram(link_type::IPV4, &packet[..30]));
+        // Fragment
+        let mut fragment = packet.clone();
+        fragment[6] |= 0x20;
+        assert_eq!(None, udp_datagram(link_type::IPV4, &fragment));
+        // Unsupported link type (IEEE 802.11)
+        assert_eq!(None, udp_datagram(105, &packet));
+    }
+
+    #[test]
+    fn invalid_captures() {
+        assert!(matches!(
+            PcapngReader::new(&[0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0][..]),
+            Err(CaptureError::NotPcapng)
+        ));
+        assert!(matches!(
+            PcapngReader::new(&[0x0a, 0x0d][..]),
+            Err(CaptureError::Io(_))
+        ));
+
+        let mut writer = Writer {
+            big_endian: false,
+            data: Vec::new(),
+        };
+        writer.section_header();
+        writer.packet(0, 0, &[]);
+        let mut reader = PcapngReader::new(&writer.data[..]).unwrap();
+        assert!(matches!(
+            reader.next_packet(),
+            Err(CaptureError::UnknownInterface(0))
+        ));
+        assert!(reader.next_packet().unwrap().is_none());
+
+        writer.interface(link_type::ETHERNET, None);
+        writer.packet(0, 0, &[1, 2, 3]);
+        // Cut off the end of the last block
+        let truncated = &writer.data[..writer.data.len() - 2];
+        let mut reader = PcapngReader::new(truncated).unwrap();
+        assert!(reader.next_packet().is_err());
+        assert!(matches!(reader.next_packet(), Err(CaptureError::Io(_))));
+    }
+}
@@ -26,23 +26,28 @@
 extern crate alloc;
 extern crate canadensis_core;
 extern crate canadensis_header;
-extern crate crc_any;
 extern crate fallible_collections;
 extern crate heapless;
+#[cfg(feature = "log")]
 extern crate log;
 extern crate nb;
+#[cfg(feature = "tracing")]
+extern crate tracing;
 extern crate zerocopy;
 
 use canadensis_core::transport::Transport;
 use canadensis_core::{OutOfMemoryError, Priority};
+use canadensis_header::crc::DataCrc;
 use canadensis_header::{NodeId16, TransferId64};
+use core::fmt;
 use core::fmt::Debug;
-use crc_any::CRCu32;
 
 pub use crate::rx::{UdpReceiver, UdpSessionData};
 pub use crate::tx::UdpTransmitter;
 
 mod address;
+#[cfg(feature = "std")]
+pub mod capture;
 pub mod driver;
 mod rx;
 mod tx;
@@ -94,9 +99,12 @@ impl AsMut<[UdpTransferId]> for UdpTransferIds {
 /// This is just a `u64`.
 pub type UdpTransferId = TransferId64;
 
+/// UDP transport errors
 #[derive(Debug)]
 pub enum Error<S> {
+    /// Memory allocation failed
     Memory(OutOfMemoryError),
+    /// The socket returned an error
     Socket(S),
 }
 
@@ -106,7 +114,28 @@ impl<S> From<OutOfMemoryError> for Error<S> {
     }
 }
 
+impl<S> fmt::Display for Error<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Memory(_) => f.write_str("Out of memory"),
+            Error::Socket(_) => f.write_str("Socket error"),
+        }
+    }
+}
+
+impl<S> core::error::Error for Error<S>
+where
+    S: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::Memory(_) => None,
+            Error::Socket(inner) => Some(inner),
+        }
+    }
+}
+
 /// Returns a CRC calculator used for data
-fn data_crc() -> CRCu32 {
-    CRCu32::crc32c()
+fn data_crc() -> DataCrc {
+    DataCrc::new()
 }
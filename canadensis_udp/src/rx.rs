@@ -1,11 +1,13 @@
 use alloc::vec::Vec;
 use core::convert::TryFrom;
+use core::fmt::Display;
 use core::marker::PhantomData;
 use core::net::Ipv4Addr;
 
 use fallible_collections::FallibleVec;
 use zerocopy::FromBytes;
 
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::session::{Session, SessionTracker};
 use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
 use canadensis_core::transfer::{Header, MessageHeader, ServiceHeader, Transfer};
@@ -24,7 +26,9 @@ mod buildup;
 mod subscriptions;
 
 /// UDP transport receiver
-pub struct UdpReceiver<C, T, S, const MTU: usize> {
+///
+/// The profiler `P` measures the time spent handling each incoming packet.
+pub struct UdpReceiver<C, T, S, const MTU: usize, P = NoProfiler> {
     subscriptions: Subscriptions<T>,
     /// The ID of this node, or None if this node is anonymous
     node_id: Option<UdpNodeId>,
@@ -33,6 +37,8 @@ pub struct UdpReceiver<C, T, S, const MTU: usize> {
     _socket: PhantomData<S>,
     _session_tracker: PhantomData<T>,
     _clock: PhantomData<C>,
+    /// Measures the duration of operations
+    profiler: P,
 }
 
 impl<C, T, S, const MTU: usize> UdpReceiver<C, T, S, MTU>
@@ -41,6 +47,22 @@ where
     S: UdpSocket,
 {
     pub fn new(node_id: Option<UdpNodeId>, interface_address: Ipv4Addr) -> Self {
+        Self::with_profiler(node_id, interface_address, NoProfiler)
+    }
+}
+
+impl<C, T, S, const MTU: usize, P> UdpReceiver<C, T, S, MTU, P>
+where
+    T: SessionTracker<UdpNodeId, UdpTransferId, UdpSessionData> + Default,
+    S: UdpSocket,
+    P: Profiler,
+{
+    /// Creates a receiver that reports the duration of operations to a profiler
+    pub fn with_profiler(
+        node_id: Option<UdpNodeId>,
+        interface_address: Ipv4Addr,
+        profiler: P,
+    ) -> Self {
         UdpReceiver {
             subscriptions: Subscriptions::new(),
             node_id,
@@ -48,9 +70,20 @@ where
             _socket: PhantomData,
             _session_tracker: PhantomData,
             _clock: PhantomData,
+            profiler,
         }
     }
 
+    /// Returns a reference to the profiler
+    pub fn profiler(&self) -> &P {
+        &self.profiler
+    }
+
+    /// Returns a mutable reference to the profiler
+    pub fn profiler_mut(&mut self) -> &mut P {
+        &mut self.profiler
+    }
+
     fn clean_expired_sessions(&mut self, now: Microseconds32)
     where
         T: SessionTracker<UdpNodeId, UdpTransferId, UdpSessionData> + Default,
@@ -80,9 +113,24 @@ where
     ) -> Result<Option<Transfer<Vec<u8>, UdpTransport>>, Error<nb::Error<S::Error>>> {
         let mut buffer: [u8; MTU] = [0; MTU];
         let bytes_received = socket.recv(&mut buffer).map_err(Error::Socket)?;
-        let buffer = &buffer[..bytes_received];
 
-        if bytes_received < MIN_PACKET_SIZE {
+        let start = self.profiler.counter();
+        let result = self.handle_packet(&buffer[..bytes_received], now);
+        let operation = match result {
+            Ok(Some(_)) => Operation::Reassemble,
+            _ => Operation::Accept,
+        };
+        self.profiler.finish(operation, start);
+        result.map_err(Error::Memory)
+    }
+
+    /// Processes an incoming packet through the matching subscription
+    fn handle_packet(
+        &mut self,
+        buffer: &[u8],
+        now: Microseconds32,
+    ) -> Result<Option<Transfer<Vec<u8>, UdpTransport>>, OutOfMemoryError> {
+        if buffer.len() < MIN_PACKET_SIZE {
             // Ignore packet
             return Ok(None);
         }
@@ -101,10 +149,9 @@ where
                 if let Some(subscription) =
                     self.subscriptions.find_message_subscription_mut(subject)
                 {
-                    return subscription
-                        .handle_frame(&header, bytes_after_header, now)
-                        .map_err(Error::Memory);
+                    return subscription.handle_frame(&header, bytes_after_header, now);
                 } else {
+                    #[cfg(feature = "log")]
                     log::trace!("No matching subject subscription");
                 }
             }
@@ -112,10 +159,9 @@ where
                 if let Some(subscription) =
                     self.subscriptions.find_request_subscription_mut(service)
                 {
-                    return subscription
-                        .handle_frame(&header, bytes_after_header, now)
-                        .map_err(Error::Memory);
+                    return subscription.handle_frame(&header, bytes_after_header, now);
                 } else {
+                    #[cfg(feature = "log")]
                     log::trace!("No matching subject subscription");
                 }
             }
@@ -123,10 +169,9 @@ where
                 if let Some(subscription) =
                     self.subscriptions.find_response_subscription_mut(service)
                 {
-                    return subscription
-                        .handle_frame(&header, bytes_after_header, now)
-                        .map_err(Error::Memory);
+                    return subscription.handle_frame(&header, bytes_after_header, now);
                 } else {
+                    #[cfg(feature = "log")]
                     log::trace!("No matching subject subscription");
                 }
             }
@@ -161,16 +206,21 @@ where
     }
 }
 
-impl<C, T, S, const MTU: usize> Receiver<C> for UdpReceiver<C, T, S, MTU>
+impl<C, T, S, const MTU: usize, P> Receiver<C> for UdpReceiver<C, T, S, MTU, P>
 where
     C: Clock,
     T: SessionTracker<UdpNodeId, UdpTransferId, UdpSessionData> + Default,
     S: UdpSocket,
+    P: Profiler,
 {
     type Transport = UdpTransport;
     type Driver = S;
     type Error = Error<S::Error>;
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "udp_receive", level = "trace", skip_all)
+    )]
     fn receive(
         &mut self,
         clock: &mut C,
@@ -213,6 +263,16 @@ where
         self.subscriptions.unsubscribe_message(subject);
     }
 
+    fn set_accept_anonymous(&mut self, subject: SubjectId, accept: bool) -> bool {
+        match self.subscriptions.find_message_subscription_mut(subject) {
+            Some(subscription) => {
+                subscription.accept_anonymous = accept;
+                true
+            }
+            None => false,
+        }
+    }
+
     fn subscribe_request(
         &mut self,
         service: ServiceId,
@@ -276,6 +336,8 @@ pub struct Subscription<T> {
     payload_size_max: usize,
     timeout: MicrosecondDuration32,
     sessions: T,
+    /// If transfers from anonymous nodes are accepted
+    accept_anonymous: bool,
 }
 
 impl<T> Subscription<T>
@@ -288,6 +350,7 @@ where
             payload_size_max,
             timeout,
             sessions: T::default(),
+            accept_anonymous: true,
         }
     }
 
@@ -297,18 +360,36 @@ where
         bytes_after_header: &[u8],
         now: Microseconds32,
     ) -> Result<Option<Transfer<Vec<u8>, UdpTransport>>, OutOfMemoryError> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            data_specifier = ?header.data_specifier,
+            transfer_id = ?header.transfer_id,
+            frame_index = header.frame_index,
+            "Frame accepted"
+        );
         let timeout = self.timeout;
         if let Some(source_node_id) = header.data_specifier.source_node_id() {
             let session = self.sessions.get_mut_or_insert_with(source_node_id, || {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(source = ?source_node_id, "Session created");
                 Session::new(now, timeout, None, UdpSessionData::default())
             })?;
             // Check transfer ID
             if let Some(last_transfer_id) = session.last_transfer_id() {
                 if header.transfer_id <= *last_transfer_id {
                     // Duplicate
+                    #[cfg(feature = "log")]
                     log::debug!(
-                        "Discarding duplicate transfer with ID {:?}",
-                        header.transfer_id
+                        "Discarding duplicate transfer {:?} from {:?}",
+                        header.transfer_id,
+                        source_node_id
+                    );
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        source = ?source_node_id,
+                        transfer_id = ?header.transfer_id,
+                        reason = "duplicate transfer ID",
+                        "Transfer dropped"
                     );
                     return Ok(None);
                 }
@@ -320,9 +401,30 @@ where
                 // Successfully received
                 // Don't need the session anymore
                 self.sessions.remove(source_node_id);
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    source = ?source_node_id,
+                    reason = "transfer completed",
+                    "Session destroyed"
+                );
             }
 
             self.convert_reassembly_result(result, header, now)
+        } else if !self.accept_anonymous {
+            // This subscription does not accept anonymous transfers. That is not an error.
+            #[cfg(feature = "log")]
+            log::trace!(
+                "Ignoring anonymous transfer {:?} ({:?})",
+                header.transfer_id,
+                header.data_specifier
+            );
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                data_specifier = ?header.data_specifier,
+                transfer_id = ?header.transfer_id,
+                "Anonymous transfer ignored"
+            );
+            Ok(None)
         } else {
             // Special case for anonymous transfers, which must be single-frame
             let mut session = Session::new(now, self.timeout, None, UdpSessionData::default());
@@ -339,6 +441,13 @@ where
     ) -> Result<Option<Transfer<Vec<u8>, UdpTransport>>, OutOfMemoryError> {
         match result {
             Ok(Some(reassembled)) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    data_specifier = ?header.data_specifier,
+                    transfer_id = ?header.transfer_id,
+                    length = reassembled.len(),
+                    "Transfer completed"
+                );
                 // Add the transfer headers and record the completed transfer
                 let header = match header.data_specifier {
                     DataSpecifier::Subject { from, subject, .. } => {
@@ -412,11 +521,9 @@ impl UdpSession for Session<UdpTransferId, UdpSessionData> {
         bytes_after_header: &[u8],
         max_payload_length: usize,
     ) -> Result<Option<Vec<u8>>, OutOfMemoryError> {
-        // The buildup will collect the payload and the transfer CRC in the last frame, so it
-        // needs extra capacity
-        let max_payload_and_crc_length = max_payload_length + TRANSFER_CRC_SIZE;
         if bytes_after_header.len() < TRANSFER_CRC_SIZE + 1 {
             // Frame not long enough
+            transfer_dropped(header, "frame too short");
             return Ok(None);
         }
         let payload_bytes = &bytes_after_header[..bytes_after_header.len() - TRANSFER_CRC_SIZE];
@@ -436,6 +543,7 @@ impl UdpSession for Session<UdpTransferId, UdpSessionData> {
                     payload.extend_from_slice(payload_bytes);
                     Ok(Some(payload))
                 } else {
+                    transfer_dropped(header, "incorrect frame CRC");
                     Ok(None)
                 }
             }
@@ -443,17 +551,17 @@ impl UdpSession for Session<UdpTransferId, UdpSessionData> {
                 // Frame index 0 and not last (beginning):
                 // Check frame CRC, create buildup and add payload only
                 if check_frame_crc(bytes_after_header) {
-                    let buildup =
-                        match Buildup::new(header, payload_bytes, max_payload_and_crc_length) {
-                            Ok(buildup) => buildup,
-                            Err(_) => {
-                                // payload_bytes was greater than max_payload_and_crc_length
-                                return Ok(None);
-                            }
-                        };
+                    let buildup = match Buildup::new(header, payload_bytes, max_payload_length) {
+                        Ok(buildup) => buildup,
+                        Err(_) => {
+                            // payload_bytes was greater than max_payload_length
+                            transfer_dropped(header, "payload too long");
+                            return Ok(None);
+                        }
+                    };
                     self.data_mut().buildup = Some(buildup);
                 } else {
-                    log::debug!("Incorrect first frame CRC");
+                    transfer_dropped(header, "incorrect frame CRC");
                 }
                 Ok(None)
             }
@@ -462,31 +570,49 @@ impl UdpSession for Session<UdpTransferId, UdpSessionData> {
                 // Check frame CRC, add payload only to buildup
                 if check_frame_crc(bytes_after_header) {
                     if let Some(buildup) = self.data_mut().buildup.as_mut() {
-                        let _ = buildup.push(header, payload_bytes);
+                        if let Err(e) = buildup.push(header, payload_bytes) {
+                            transfer_dropped(header, e);
+                        }
                     }
+                } else {
+                    transfer_dropped(header, "incorrect frame CRC");
                 }
                 Ok(None)
             }
             (false, true) => {
                 // Frame index >0 and last (end):
-                // Add payload and transfer CRC to buildup, extract combined payload and transfer CRC,
-                // check full transfer CRC, return combined payload
+                // Add payload to buildup, compare the transfer CRC (which the buildup has
+                // calculated as the frames arrived) with the CRC at the end of this frame,
+                // return combined payload
                 if let Some(mut buildup) = self.data_mut().buildup.take() {
-                    if buildup.push(header, bytes_after_header).is_ok() {
-                        let payload_and_crc = buildup.into_payload();
-                        if check_frame_crc(&payload_and_crc) {
-                            // Remove CRC from the end
-                            let mut payload = payload_and_crc;
-                            payload.truncate(payload.len() - TRANSFER_CRC_SIZE);
-                            Ok(Some(payload))
-                        } else {
+                    match buildup.push(header, payload_bytes) {
+                        Ok(()) => {
+                            if buildup.transfer_crc() == expected_crc(bytes_after_header) {
+                                Ok(Some(buildup.into_payload()))
+                            } else {
+                                transfer_dropped(header, "incorrect transfer CRC");
+                                Ok(None)
+                            }
+                        }
+                        Err(e) => {
+                            transfer_dropped(header, e);
                             Ok(None)
                         }
-                    } else {
-                        Ok(None)
                     }
                 } else {
-                    log::debug!("No buildup");
+                    #[cfg(feature = "log")]
+                    log::debug!(
+                        "Got the last frame of transfer {:?} ({:?}) without the first frame",
+                        header.transfer_id,
+                        header.data_specifier
+                    );
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        data_specifier = ?header.data_specifier,
+                        transfer_id = ?header.transfer_id,
+                        reason = "no start frame",
+                        "Transfer dropped"
+                    );
                     Ok(None)
                 }
             }
@@ -494,17 +620,41 @@ impl UdpSession for Session<UdpTransferId, UdpSessionData> {
     }
 }
 
+/// Reports that a frame was discarded and the transfer it belongs to can't be received
+#[cfg_attr(
+    not(any(feature = "log", feature = "tracing")),
+    allow(unused_variables)
+)]
+fn transfer_dropped<R: Display>(header: &UdpHeader, reason: R) {
+    #[cfg(feature = "log")]
+    log::warn!(
+        "Dropped transfer {:?} ({:?}): {}",
+        header.transfer_id,
+        header.data_specifier,
+        reason
+    );
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        data_specifier = ?header.data_specifier,
+        transfer_id = ?header.transfer_id,
+        reason = %reason,
+        "Transfer dropped"
+    );
+}
+
 fn check_frame_crc(bytes_after_header: &[u8]) -> bool {
     let crc_start = bytes_after_header.len() - TRANSFER_CRC_SIZE;
-    let expected_crc = {
-        let mut expected_crc_bytes: [u8; 4] = [0; 4];
-        expected_crc_bytes.copy_from_slice(&bytes_after_header[crc_start..]);
-        u32::from_le_bytes(expected_crc_bytes)
-    };
-
     let bytes_to_crc = &bytes_after_header[..crc_start];
 
     let mut crc = data_crc();
     crc.digest(bytes_to_crc);
-    crc.get_crc() == expected_crc
+    crc.get_crc() == expected_crc(bytes_after_header)
+}
+
+/// Returns the CRC at the end of a frame
+fn expected_crc(bytes_after_header: &[u8]) -> u32 {
+    let crc_start = bytes_after_header.len() - TRANSFER_CRC_SIZE;
+    let mut expected_crc_bytes: [u8; 4] = [0; 4];
+    expected_crc_bytes.copy_from_slice(&bytes_after_header[crc_start..]);
+    u32::from_le_bytes(expected_crc_bytes)
 }
@@ -1,13 +1,15 @@
 //! Reassembles UDP packets into transfers
 
 use alloc::vec::Vec;
+use core::fmt;
 
+use canadensis_header::crc::DataCrc;
 use fallible_collections::{FallibleVec, TryReserveError};
 
 use canadensis_core::{OutOfMemoryError, Priority};
 use canadensis_header::Header;
 
-use crate::UdpTransferId;
+use crate::{data_crc, UdpTransferId};
 
 // TODO: Add support for reassembling out-of-order frames
 
@@ -29,6 +31,11 @@ pub struct Buildup {
     priority: Priority,
     /// The transfer ID of the first frame, which all other frames should match
     transfer_id: UdpTransferId,
+    /// The transfer CRC of the bytes collected so far
+    ///
+    /// This is updated as each frame arrives, so the CRC does not need to be calculated over
+    /// the whole transfer when the last frame arrives.
+    transfer_crc: DataCrc,
 }
 
 impl Buildup {
@@ -46,12 +53,15 @@ impl Buildup {
         }
         let mut bytes: Vec<u8> = FallibleVec::try_with_capacity(max_length)?;
         bytes.extend_from_slice(bytes_after_header);
+        let mut transfer_crc = data_crc();
+        transfer_crc.digest(bytes_after_header);
 
         Ok(Buildup {
             bytes,
             next_frame_index: header.frame_index + 1,
             priority: header.priority,
             transfer_id: header.transfer_id,
+            transfer_crc,
         })
     }
 
@@ -72,10 +82,17 @@ impl Buildup {
             return Err(BuildupError::Length);
         }
         self.bytes.extend_from_slice(bytes_after_header);
+        self.transfer_crc.digest(bytes_after_header);
+        self.next_frame_index += 1;
         Ok(())
     }
 
-    /// Consumes this buildup and returns the payload bytes (possibly including a CRC at the end)
+    /// Returns the transfer CRC of all the bytes collected so far
+    pub fn transfer_crc(&self) -> u32 {
+        self.transfer_crc.get_crc()
+    }
+
+    /// Consumes this buildup and returns the payload bytes
     pub fn into_payload(self) -> Vec<u8> {
         self.bytes
     }
@@ -96,6 +113,18 @@ pub enum BuildupError {
     Memory(OutOfMemoryError),
 }
 
+impl fmt::Display for BuildupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildupError::Index => write!(f, "unexpected frame index"),
+            BuildupError::Priority => write!(f, "priority mismatch"),
+            BuildupError::TransferId => write!(f, "transfer ID mismatch"),
+            BuildupError::Length => write!(f, "payload too long"),
+            BuildupError::Memory(_) => write!(f, "out of memory"),
+        }
+    }
+}
+
 impl From<OutOfMemoryError> for BuildupError {
     fn from(oom: OutOfMemoryError) -> Self {
         BuildupError::Memory(oom)
@@ -106,3 +135,44 @@ impl From<TryReserveError> for BuildupError {
         BuildupError::Memory(inner.into())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Buildup, BuildupError};
+    use alloc::vec;
+    use canadensis_core::Priority;
+    use canadensis_header::{DataSpecifier, Header};
+    use core::convert::TryInto;
+
+    fn header(frame_index: u32, last_frame: bool) -> Header {
+        Header {
+            priority: Priority::Nominal,
+            data_specifier: DataSpecifier::Subject {
+                from: None,
+                subject: 73u16.try_into().unwrap(),
+            },
+            transfer_id: 9u64.into(),
+            frame_index,
+            last_frame,
+            data: 0,
+        }
+    }
+
+    #[test]
+    fn three_frames() {
+        let mut buildup = Buildup::new(&header(0, false), &[1, 2], 8).unwrap();
+        buildup.push(&header(1, false), &[3, 4]).unwrap();
+        buildup.push(&header(2, true), &[5]).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5], buildup.into_payload());
+    }
+
+    #[test]
+    fn repeated_frame_index() {
+        let mut buildup = Buildup::new(&header(0, false), &[1, 2], 8).unwrap();
+        buildup.push(&header(1, false), &[3, 4]).unwrap();
+        assert!(matches!(
+            buildup.push(&header(1, true), &[5]),
+            Err(BuildupError::Index)
+        ));
+    }
+}
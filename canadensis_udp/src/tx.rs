@@ -4,21 +4,28 @@ use core::marker::PhantomData;
 use core::net::SocketAddrV4;
 
 use canadensis_core::nb;
+use canadensis_core::profile::{NoProfiler, Operation, Profiler};
 use canadensis_core::time::{Clock, Microseconds32};
 use canadensis_core::transfer::{Header, Transfer};
-use canadensis_core::transport::Transmitter;
+use canadensis_core::transport::{StreamingPayload, Transmitter};
 use canadensis_header::DataSpecifier;
 
 use crate::address::Address;
-use crate::tx::breakdown::{Breakdown, HeaderBase};
+use crate::tx::breakdown::{Breakdown, FrameAssembler, HeaderBase};
 use crate::TRANSFER_CRC_SIZE;
 use crate::{Error, UdpTransport};
 
 mod breakdown;
 
-pub struct UdpTransmitter<S, const MTU: usize> {
+/// UDP transport transmitter
+///
+/// The profiler `P` measures the time spent splitting each transfer into frames. Because this
+/// transmitter sends frames as soon as they are assembled, this includes the time spent sending.
+pub struct UdpTransmitter<S, const MTU: usize, P = NoProfiler> {
     destination_port: u16,
     _socket: PhantomData<S>,
+    /// Measures the duration of operations
+    profiler: P,
 }
 impl<S, const MTU: usize> UdpTransmitter<S, MTU>
 where
@@ -31,6 +38,21 @@ where
     /// This function panics if `MTU` is less than 29. 29 bytes is the minimum MTU required to
     /// contain a header, transfer CRC, and one byte of payload in each frame.
     pub fn new(destination_port: u16) -> Self {
+        Self::with_profiler(destination_port, NoProfiler)
+    }
+}
+
+impl<S, const MTU: usize, P> UdpTransmitter<S, MTU, P>
+where
+    S: crate::driver::UdpSocket,
+    P: Profiler,
+{
+    /// Creates a transmitter that reports the duration of operations to a profiler
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `MTU` is less than 29.
+    pub fn with_profiler(destination_port: u16, profiler: P) -> Self {
         // MTU must be big enough for the header, transfer CRC, and at least 1 byte of data
         assert!(
             MTU > canadensis_header::SIZE + TRANSFER_CRC_SIZE + 1,
@@ -40,9 +62,20 @@ where
         UdpTransmitter {
             destination_port,
             _socket: PhantomData,
+            profiler,
         }
     }
 
+    /// Returns a reference to the profiler
+    pub fn profiler(&self) -> &P {
+        &self.profiler
+    }
+
+    /// Returns a mutable reference to the profiler
+    pub fn profiler_mut(&mut self) -> &mut P {
+        &mut self.profiler
+    }
+
     fn push_inner<C>(
         &mut self,
         header_base: HeaderBase,
@@ -71,25 +104,52 @@ where
         C: Clock,
     {
         for frame in breakdown {
-            if frame.deadline > clock.now() {
-                socket.send_to(&frame.data, destination_address)?;
-            } else {
-                log::trace!("Discarding outgoing frame because its deadline has passed");
-            }
+            Self::send_frame(frame, destination_address, clock, socket)?;
+        }
+        Ok(())
+    }
+
+    /// Sends a frame, or discards it if its deadline has passed
+    fn send_frame<C>(
+        frame: UdpFrame,
+        destination_address: SocketAddrV4,
+        clock: &mut C,
+        socket: &mut S,
+    ) -> Result<(), S::Error>
+    where
+        C: Clock,
+    {
+        if frame.deadline > clock.now() {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(destination = %destination_address, length = frame.data.len(), "Frame sent");
+            socket.send_to(&frame.data, destination_address)?;
+        } else {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "Discarding outgoing frame to {} because its deadline has passed",
+                destination_address
+            );
+            #[cfg(feature = "tracing")]
+            tracing::debug!(destination = %destination_address, reason = "deadline passed", "Frame dropped");
         }
         Ok(())
     }
 }
 
-impl<C, S, const MTU: usize> Transmitter<C> for UdpTransmitter<S, MTU>
+impl<C, S, const MTU: usize, P> Transmitter<C> for UdpTransmitter<S, MTU, P>
 where
     C: Clock,
     S: crate::driver::UdpSocket,
+    P: Profiler,
 {
     type Transport = UdpTransport;
     type Driver = S;
     type Error = Error<S::Error>;
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "udp_push", level = "trace", skip_all)
+    )]
     fn push<A>(
         &mut self,
         transfer: Transfer<A, Self::Transport>,
@@ -99,60 +159,60 @@ where
     where
         A: AsRef<[u8]>,
     {
+        let start = self.profiler.counter();
         let deadline = transfer.header.timestamp();
-        let (header_base, dest_address) = match transfer.header {
-            Header::Message(header) => {
-                let multicast_addr = Address::Multicast(header.subject);
-                let header_base = HeaderBase {
-                    data_specifier: DataSpecifier::Subject {
-                        from: header.source,
-                        subject: header.subject,
-                    },
-                    transfer_id: header.transfer_id,
-                    priority: header.priority,
-                    data: 0,
-                };
-                (header_base, multicast_addr)
-            }
-            Header::Request(header) => {
-                let dest_addr = Address::Node(header.destination);
-                let header_base = HeaderBase {
-                    data_specifier: DataSpecifier::ServiceRequest {
-                        from: header.source,
-                        to: header.destination,
-                        service: header.service,
-                    },
-                    transfer_id: header.transfer_id,
-                    priority: header.priority,
-                    data: 0,
-                };
-                (header_base, dest_addr)
-            }
-            Header::Response(header) => {
-                let dest_addr = Address::Node(header.destination);
-                let header_base = HeaderBase {
-                    data_specifier: DataSpecifier::ServiceResponse {
-                        from: header.source,
-                        to: header.destination,
-                        service: header.service,
-                    },
-                    transfer_id: header.transfer_id,
-                    priority: header.priority,
-                    data: 0,
-                };
-                (header_base, dest_addr)
-            }
-        };
-        self.push_inner(
+        let (header_base, dest_address) = header_base_and_address(transfer.header);
+        let result = self.push_inner(
             header_base,
             SocketAddrV4::new(dest_address.into(), self.destination_port),
             deadline,
             transfer.payload.as_ref(),
             clock,
             socket,
-        )
-        .map_err(Error::Socket)
-        .map_err(nb::Error::Other)
+        );
+        self.profiler.finish(Operation::Serialize, start);
+        result.map_err(Error::Socket).map_err(nb::Error::Other)
+    }
+
+    /// Writes the payload pieces directly into frames and sends each frame when it is full
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "udp_push_streaming", level = "trace", skip_all)
+    )]
+    fn push_streaming<Q>(
+        &mut self,
+        transfer: Transfer<&Q, Self::Transport>,
+        clock: &mut C,
+        socket: &mut S,
+    ) -> nb::Result<(), Self::Error>
+    where
+        Q: StreamingPayload + ?Sized,
+    {
+        let start = self.profiler.counter();
+        let deadline = transfer.header.timestamp();
+        let (header_base, dest_address) = header_base_and_address(transfer.header);
+        let destination = SocketAddrV4::new(dest_address.into(), self.destination_port);
+        let mut assembler = FrameAssembler::new(header_base, deadline, MTU);
+        let mut remaining = transfer.payload.payload_length();
+        // After a socket error, the rest of the payload is ignored
+        let mut result = Ok(());
+        transfer.payload.write_to(&mut |piece| {
+            for &byte in piece {
+                remaining = remaining.saturating_sub(1);
+                if let Some(frame) = assembler.push(byte, remaining == 0) {
+                    if result.is_ok() {
+                        result = Self::send_frame(frame, destination, clock, socket);
+                    }
+                }
+            }
+        });
+        if let Some(frame) = assembler.finish() {
+            if result.is_ok() {
+                result = Self::send_frame(frame, destination, clock, socket);
+            }
+        }
+        self.profiler.finish(Operation::Serialize, start);
+        result.map_err(Error::Socket).map_err(nb::Error::Other)
     }
 
     fn flush(
@@ -171,6 +231,53 @@ where
     }
 }
 
+/// Returns the header fields and destination address for a transfer
+fn header_base_and_address(header: Header<UdpTransport>) -> (HeaderBase, Address) {
+    match header {
+        Header::Message(header) => {
+            let multicast_addr = Address::Multicast(header.subject);
+            let header_base = HeaderBase {
+                data_specifier: DataSpecifier::Subject {
+                    from: header.source,
+                    subject: header.subject,
+                },
+                transfer_id: header.transfer_id,
+                priority: header.priority,
+                data: 0,
+            };
+            (header_base, multicast_addr)
+        }
+        Header::Request(header) => {
+            let dest_addr = Address::Node(header.destination);
+            let header_base = HeaderBase {
+                data_specifier: DataSpecifier::ServiceRequest {
+                    from: header.source,
+                    to: header.destination,
+                    service: header.service,
+                },
+                transfer_id: header.transfer_id,
+                priority: header.priority,
+                data: 0,
+            };
+            (header_base, dest_addr)
+        }
+        Header::Response(header) => {
+            let dest_addr = Address::Node(header.destination);
+            let header_base = HeaderBase {
+                data_specifier: DataSpecifier::ServiceResponse {
+                    from: header.source,
+                    to: header.destination,
+                    service: header.service,
+                },
+                transfer_id: header.transfer_id,
+                priority: header.priority,
+                data: 0,
+            };
+            (header_base, dest_addr)
+        }
+    }
+}
+
 pub(crate) struct UdpFrame {
     deadline: Microseconds32,
     data: Vec<u8>,
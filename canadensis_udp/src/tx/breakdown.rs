@@ -2,7 +2,7 @@ use alloc::vec::Vec;
 use core::iter::Peekable;
 use core::mem;
 
-use crc_any::CRCu32;
+use canadensis_header::crc::DataCrc;
 use zerocopy::AsBytes;
 
 use canadensis_core::time::Microseconds32;
@@ -14,18 +14,24 @@ use canadensis_header::{DataSpecifier, Header, RawHeader, LAST_FRAME};
 
 /// An iterator that breaks a transfer into UDP frames and adds a CRC to each frame
 pub(crate) struct Breakdown<P: Iterator<Item = u8>> {
+    /// The payload iterator
+    payload: Peekable<P>,
+    /// The frame assembler
+    assembler: FrameAssembler,
+    /// If the last frame has already been produced
+    done: bool,
+}
+
+/// Accepts the bytes of a transfer payload one at a time and assembles them into UDP frames
+pub(crate) struct FrameAssembler {
     /// Basic header information to apply to all frames
     header_base: HeaderBase,
     /// The transmit deadline for this transfer
     deadline: Microseconds32,
-    /// The payload iterator
-    payload: Peekable<P>,
     /// The index of the frame currently being assembled
     frame_index: u32,
-    /// If the last frame has already been produced
-    done: bool,
     /// A transfer CRC that has processed the data in all packets produced so far
-    transfer_crc: CRCu32,
+    transfer_crc: DataCrc,
     /// The payload in the frame currently being assembled
     ///
     /// Before the frame is returned, the first header::SIZE bytes are empty. The header and CRC
@@ -49,11 +55,19 @@ pub(crate) struct HeaderBase {
 impl<P: Iterator<Item = u8>> Breakdown<P> {
     pub fn new(header_base: HeaderBase, deadline: Microseconds32, payload: P, mtu: usize) -> Self {
         Breakdown {
+            payload: payload.peekable(),
+            assembler: FrameAssembler::new(header_base, deadline, mtu),
+            done: false,
+        }
+    }
+}
+
+impl FrameAssembler {
+    pub fn new(header_base: HeaderBase, deadline: Microseconds32, mtu: usize) -> Self {
+        FrameAssembler {
             header_base,
             deadline,
-            payload: payload.peekable(),
             frame_index: 0,
-            done: false,
             transfer_crc: data_crc(),
             // Initialize the current frame with empty space for the header. The payload will follow.
             current_frame: {
@@ -65,6 +79,51 @@ impl<P: Iterator<Item = u8>> Breakdown<P> {
         }
     }
 
+    /// Adds a payload byte to the current frame
+    ///
+    /// `last_byte` must be true if no more payload bytes will follow this one.
+    ///
+    /// If this byte fills the current frame, this function returns the frame.
+    pub fn push(&mut self, byte: u8, last_byte: bool) -> Option<UdpFrame> {
+        self.current_frame.push(byte);
+        self.transfer_crc.digest(&[byte]);
+
+        if self.current_frame.len() == self.current_frame.capacity() {
+            let header = self.make_header(last_byte);
+
+            // This is not the last frame, so calculate the CRC over the data in this
+            // frame only.
+            // The CRC hasn't been added yet, so go all the way to the end.
+            let data_crc = {
+                let mut crc = data_crc();
+                crc.digest(&self.current_frame[canadensis_header::SIZE..self.current_frame.len()]);
+                crc.get_crc()
+            };
+
+            let frame = self.take_frame(header, data_crc);
+            self.frame_index += 1;
+            assert_eq!(self.frame_index & LAST_FRAME, 0, "Frame index too large");
+            Some(frame)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the last frame of the transfer, if any payload bytes have been added since the
+    /// last frame was returned
+    pub fn finish(&mut self) -> Option<UdpFrame> {
+        if self.current_frame.len() != canadensis_header::SIZE {
+            // End of data, return a frame with the last frame bit set
+            // and with a CRC covering all the data
+            let header = self.make_header(true);
+            let transfer_crc = self.transfer_crc.get_crc();
+            Some(self.take_frame(header, transfer_crc))
+        } else {
+            // No data in the current frame
+            None
+        }
+    }
+
     /// Fills in self.current_frame with the provided header and CRC, clears self.current_frame,
     /// and returns a frame containing those bytes
     ///
@@ -114,44 +173,14 @@ where
         loop {
             match self.payload.next() {
                 Some(byte) => {
-                    self.current_frame.push(byte);
-                    self.transfer_crc.digest(&[byte]);
-
-                    if self.current_frame.len() == self.current_frame.capacity() {
-                        let more_payload_coming = self.payload.peek().is_some();
-                        let header = self.make_header(!more_payload_coming);
-
-                        // This is not the last frame, so calculate the CRC over the data in this
-                        // frame only.
-                        // The CRC hasn't been added yet, so go all the way to the end.
-                        let data_crc = {
-                            let mut crc = data_crc();
-                            crc.digest(
-                                &self.current_frame
-                                    [canadensis_header::SIZE..self.current_frame.len()],
-                            );
-                            crc.get_crc()
-                        };
-
-                        let frame = self.take_frame(header, data_crc);
-                        self.frame_index += 1;
-                        assert_eq!(self.frame_index & LAST_FRAME, 0, "Frame index too large");
+                    let last_byte = self.payload.peek().is_none();
+                    if let Some(frame) = self.assembler.push(byte, last_byte) {
                         break Some(frame);
                     }
                 }
                 None => {
-                    if self.current_frame.len() != canadensis_header::SIZE {
-                        // End of data, return a frame with the last frame bit set
-                        // and with a CRC covering all the data
-                        let header = self.make_header(true);
-                        let transfer_crc = self.transfer_crc.get_crc();
-                        let frame = self.take_frame(header, transfer_crc);
-                        self.done = true;
-                        break Some(frame);
-                    } else {
-                        // No data in the current frame
-                        break None;
-                    }
+                    self.done = true;
+                    break self.assembler.finish();
                 }
             }
         }
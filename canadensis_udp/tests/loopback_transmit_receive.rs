@@ -69,6 +69,56 @@ fn transmit_receive_message_two_frames() {
     );
 }
 
+#[test]
+fn transmit_receive_message_many_frames() {
+    init_test_logging();
+
+    let transmit_node_id = UdpNodeId::try_from(120).unwrap();
+    let mut clock = SystemClock::new();
+    // Split the payload across more than two frames
+    const MTU: usize = 256;
+
+    // Make a payload compatible with the uavcan.metatransport.ethernet.Frame.0.1 format format.
+    let payload = {
+        let mut payload = Vec::with_capacity(6 + 6 + 2 + 2 + MAJOR_GENERAL_SONG.len());
+        // Destination
+        payload.extend_from_slice(&[0xd1, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6]);
+        // Source
+        payload.extend_from_slice(&[0x31, 0x32, 0x33, 0x34, 0x35, 0x36]);
+        // Type IPv4
+        payload.extend_from_slice(&[0x00, 0x08]);
+        let length: u16 = MAJOR_GENERAL_SONG.len().try_into().unwrap();
+        payload.extend_from_slice(&length.to_le_bytes());
+        payload.extend_from_slice(MAJOR_GENERAL_SONG);
+        payload
+    };
+    let transfer = Transfer {
+        header: Header::Message(MessageHeader {
+            timestamp: clock.now() + milliseconds(5000),
+            transfer_id: UdpTransferId::default(),
+            priority: Priority::Nominal,
+            subject: SubjectId::try_from(73u16).unwrap(),
+            source: Some(transmit_node_id),
+        }),
+        loopback: false,
+        payload,
+    };
+    check_loopback::<_, _, MTU>(
+        transfer,
+        &mut clock,
+        |rx, socket| {
+            rx.subscribe_message(
+                73.try_into().unwrap(),
+                4096,
+                MicrosecondDuration32::from_ticks(2_000_000),
+                socket,
+            )
+            .unwrap()
+        },
+        |rx, socket| rx.unsubscribe_message(73.try_into().unwrap(), socket),
+    );
+}
+
 #[test]
 fn transmit_receive_message_one_byte_one_frame() {
     init_test_logging();
@@ -0,0 +1,144 @@
+//!
+//! Receiver tests that use an in-memory socket
+//!
+
+extern crate canadensis_core;
+extern crate canadensis_udp;
+
+use core::net::{Ipv4Addr, SocketAddrV4};
+use std::collections::VecDeque;
+use std::convert::{Infallible, TryFrom};
+
+use canadensis_core::session::SessionDynamicMap;
+use canadensis_core::time::{Clock, MicrosecondDuration32, Microseconds32};
+use canadensis_core::transfer::{Header, MessageHeader, Transfer};
+use canadensis_core::transport::{Receiver, Transmitter};
+use canadensis_core::{nb, Priority, SubjectId};
+use canadensis_udp::driver::UdpSocket;
+use canadensis_udp::{
+    UdpNodeId, UdpReceiver, UdpSessionData, UdpTransferId, UdpTransmitter, UdpTransport,
+    DEFAULT_PORT,
+};
+
+const MTU: usize = 1472;
+
+type TestReceiver = UdpReceiver<
+    ZeroClock,
+    SessionDynamicMap<UdpNodeId, UdpTransferId, UdpSessionData>,
+    LoopbackSocket,
+    MTU,
+>;
+
+#[test]
+fn reject_anonymous() {
+    let mut socket = LoopbackSocket::default();
+    let mut tx = UdpTransmitter::<LoopbackSocket, MTU>::new(DEFAULT_PORT);
+    let mut rx = TestReceiver::new(Some(UdpNodeId::try_from(3).unwrap()), Ipv4Addr::LOCALHOST);
+    let subject = SubjectId::try_from(10u16).unwrap();
+    let timeout = MicrosecondDuration32::from_ticks(1000);
+    rx.subscribe_message(subject, 8, timeout, &mut socket)
+        .unwrap();
+
+    // By default, anonymous transfers are accepted
+    let anonymous = message(subject, None, 0);
+    tx.push(anonymous.clone(), &mut ZeroClock, &mut socket)
+        .unwrap();
+    assert_eq!(
+        Some(received(anonymous)),
+        rx.receive(&mut ZeroClock, &mut socket).unwrap()
+    );
+
+    // A rejected anonymous transfer is skipped, and the receiver continues with the next packet
+    assert!(!rx.set_accept_anonymous(SubjectId::try_from(11u16).unwrap(), false));
+    assert!(rx.set_accept_anonymous(subject, false));
+    tx.push(message(subject, None, 1), &mut ZeroClock, &mut socket)
+        .unwrap();
+    let named = message(subject, Some(UdpNodeId::try_from(5).unwrap()), 1);
+    tx.push(named.clone(), &mut ZeroClock, &mut socket).unwrap();
+    assert_eq!(
+        Some(received(named)),
+        rx.receive(&mut ZeroClock, &mut socket).unwrap()
+    );
+    tx.push(message(subject, None, 2), &mut ZeroClock, &mut socket)
+        .unwrap();
+    assert_eq!(None, rx.receive(&mut ZeroClock, &mut socket).unwrap());
+    assert!(socket.packets.is_empty());
+
+    // Subscribing again accepts anonymous transfers
+    rx.subscribe_message(subject, 8, timeout, &mut socket)
+        .unwrap();
+    let anonymous = message(subject, None, 3);
+    tx.push(anonymous.clone(), &mut ZeroClock, &mut socket)
+        .unwrap();
+    assert_eq!(
+        Some(received(anonymous)),
+        rx.receive(&mut ZeroClock, &mut socket).unwrap()
+    );
+}
+
+/// Returns a message to send, with a deadline after the current time
+fn message(
+    subject: SubjectId,
+    source: Option<UdpNodeId>,
+    transfer_id: u64,
+) -> Transfer<Vec<u8>, UdpTransport> {
+    Transfer {
+        header: Header::Message(MessageHeader {
+            timestamp: Microseconds32::from_ticks(1000),
+            transfer_id: UdpTransferId::from(transfer_id),
+            priority: Priority::Nominal,
+            subject,
+            source,
+        }),
+        loopback: false,
+        payload: vec![0x5a, 0x01],
+    }
+}
+
+/// Returns a sent transfer as the receiver returns it, with the time when it was received
+fn received(mut transfer: Transfer<Vec<u8>, UdpTransport>) -> Transfer<Vec<u8>, UdpTransport> {
+    transfer.header.set_timestamp(ZeroClock.now());
+    transfer
+}
+
+/// A socket that receives the packets that are sent through it
+#[derive(Default)]
+struct LoopbackSocket {
+    packets: VecDeque<Vec<u8>>,
+}
+
+impl UdpSocket for LoopbackSocket {
+    type Error = Infallible;
+
+    fn local_addr(&self) -> Result<SocketAddrV4, Self::Error> {
+        Ok(SocketAddrV4::new(Ipv4Addr::LOCALHOST, DEFAULT_PORT))
+    }
+
+    fn join_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn leave_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_to(&mut self, data: &[u8], _: SocketAddrV4) -> Result<usize, Self::Error> {
+        self.packets.push_back(data.to_vec());
+        Ok(data.len())
+    }
+
+    fn recv(&mut self, buffer: &mut [u8]) -> Result<usize, nb::Error<Self::Error>> {
+        let packet = self.packets.pop_front().ok_or(nb::Error::WouldBlock)?;
+        buffer[..packet.len()].copy_from_slice(&packet);
+        Ok(packet.len())
+    }
+}
+
+/// A clock that always returns zero
+struct ZeroClock;
+
+impl Clock for ZeroClock {
+    fn now(&mut self) -> Microseconds32 {
+        Microseconds32::from_ticks(0)
+    }
+}
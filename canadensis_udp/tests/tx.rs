@@ -0,0 +1,105 @@
+//!
+//! Transmitter tests that record the packets that are sent
+//!
+
+extern crate canadensis_core;
+extern crate canadensis_udp;
+
+use core::net::{Ipv4Addr, SocketAddrV4};
+use std::convert::{Infallible, TryFrom};
+
+use canadensis_core::time::{Clock, Microseconds32};
+use canadensis_core::transfer::{Header, MessageHeader, Transfer};
+use canadensis_core::transport::Transmitter;
+use canadensis_core::{nb, Priority, SubjectId};
+use canadensis_udp::driver::UdpSocket;
+use canadensis_udp::{UdpNodeId, UdpTransferId, UdpTransmitter, DEFAULT_PORT};
+
+/// An MTU that fits 12 bytes of payload in each frame
+const MTU: usize = 40;
+
+#[test]
+fn streaming_matches_push() {
+    let header = Header::Message(MessageHeader {
+        timestamp: Microseconds32::from_ticks(1000),
+        transfer_id: UdpTransferId::from(9),
+        priority: Priority::Nominal,
+        subject: SubjectId::try_from(4919u16).unwrap(),
+        source: Some(UdpNodeId::try_from(59).unwrap()),
+    });
+    let mut tx = UdpTransmitter::<RecordingSocket, MTU>::new(DEFAULT_PORT);
+    // Lengths that end in the middle of a frame and at the end of a frame
+    for length in [1, 5, 12, 24, 30] {
+        let payload: Vec<u8> = (0..length).collect();
+        let mut expected = RecordingSocket::default();
+        tx.push(
+            Transfer {
+                header: header.clone(),
+                loopback: false,
+                payload: &payload,
+            },
+            &mut ZeroClock,
+            &mut expected,
+        )
+        .unwrap();
+
+        let (first, rest) = payload.split_at(usize::from(length / 3));
+        let pieces: [&[u8]; 3] = [first, &[], rest];
+        let mut actual = RecordingSocket::default();
+        tx.push_streaming(
+            Transfer {
+                header: header.clone(),
+                loopback: false,
+                payload: &pieces[..],
+            },
+            &mut ZeroClock,
+            &mut actual,
+        )
+        .unwrap();
+        assert_eq!(
+            expected.packets, actual.packets,
+            "Payload length {}",
+            length
+        );
+    }
+}
+
+/// A socket that records the packets that are sent through it
+#[derive(Default)]
+struct RecordingSocket {
+    packets: Vec<Vec<u8>>,
+}
+
+impl UdpSocket for RecordingSocket {
+    type Error = Infallible;
+
+    fn local_addr(&self) -> Result<SocketAddrV4, Self::Error> {
+        Ok(SocketAddrV4::new(Ipv4Addr::LOCALHOST, DEFAULT_PORT))
+    }
+
+    fn join_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn leave_multicast_v4(&mut self, _: &Ipv4Addr, _: &Ipv4Addr) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn send_to(&mut self, data: &[u8], _: SocketAddrV4) -> Result<usize, Self::Error> {
+        self.packets.push(data.to_vec());
+        Ok(data.len())
+    }
+
+    fn recv(&mut self, _: &mut [u8]) -> Result<usize, nb::Error<Self::Error>> {
+        Err(nb::Error::WouldBlock)
+    }
+}
+
+/// A clock that always returns zero
+struct ZeroClock;
+
+impl Clock for ZeroClock {
+    fn now(&mut self) -> Microseconds32 {
+        Microseconds32::from_ticks(0)
+    }
+}